@@ -0,0 +1,147 @@
+//! Per-strategy micro-benchmarks over small representative Go fixtures.
+//!
+//! Run with `cargo bench --bench strategies`.
+
+use std::collections::HashMap;
+
+use argflow::engine::strategies::{
+    BinaryStrategy, CallStrategy, CompositeStrategy, IdentifierStrategy, IndexStrategy,
+    LiteralStrategy, SelectorStrategy, UnaryStrategy,
+};
+use argflow::engine::{Context, Resolver, Strategy};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tree_sitter::{Node, Parser, Tree};
+
+struct Fixture {
+    name: &'static str,
+    source: &'static str,
+    kind: &'static str,
+    strategy: Box<dyn Strategy>,
+}
+
+fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture {
+            name: "literal",
+            source: "package main\nfunc f() { use(600000) }",
+            kind: "int_literal",
+            strategy: Box::new(LiteralStrategy::new()),
+        },
+        Fixture {
+            name: "unary",
+            source: "package main\nfunc f() { use(-32) }",
+            kind: "unary_expression",
+            strategy: Box::new(UnaryStrategy::new()),
+        },
+        Fixture {
+            name: "binary",
+            source: "package main\nfunc f() { use((1 << 16) * 8 + 1024) }",
+            kind: "binary_expression",
+            strategy: Box::new(BinaryStrategy::new()),
+        },
+        Fixture {
+            name: "identifier",
+            source: "package main\nconst Base = 1000\nconst Iterations = Base * 600\nfunc f() { use(Iterations) }",
+            kind: "identifier",
+            strategy: Box::new(IdentifierStrategy::new()),
+        },
+        Fixture {
+            name: "call",
+            source: "package main\nfunc keySize() int {\n\tif fips { return 32 }\n\treturn 16\n}\nfunc f() { use(keySize()) }",
+            kind: "call_expression",
+            strategy: Box::new(CallStrategy::new()),
+        },
+        Fixture {
+            name: "selector",
+            source: "package main\nvar cfg = Config{Iterations: 4096}\nfunc f() { use(cfg.Iterations) }",
+            kind: "selector_expression",
+            strategy: Box::new(SelectorStrategy::new()),
+        },
+        Fixture {
+            name: "index",
+            source: "package main\nvar sizes = []int{16, 24, 32}\nfunc f() { use(sizes[2]) }",
+            kind: "index_expression",
+            strategy: Box::new(IndexStrategy::new()),
+        },
+        Fixture {
+            name: "composite",
+            source: "package main\nfunc f() { use([]int{128, 192, 256}) }",
+            kind: "composite_literal",
+            strategy: Box::new(CompositeStrategy::new()),
+        },
+    ]
+}
+
+fn parse_go(source: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_go::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+/// Finds the last node of `kind`, which in these fixtures is the use site.
+fn find_last_node_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut found = None;
+    if node.kind() == kind {
+        found = Some(node);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if let Some(n) = find_last_node_of_kind(child, kind) {
+            found = Some(n);
+        }
+    }
+    found
+}
+
+fn context<'a>(tree: &'a Tree, source: &'a str) -> Context<'a> {
+    Context::new(
+        tree,
+        source.as_bytes(),
+        "bench.go".to_string(),
+        "go".to_string(),
+        HashMap::new(),
+    )
+}
+
+fn bench_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strategy");
+
+    for fixture in fixtures() {
+        let tree = parse_go(fixture.source);
+        let node = find_last_node_of_kind(tree.root_node(), fixture.kind)
+            .unwrap_or_else(|| panic!("fixture {} has no {} node", fixture.name, fixture.kind));
+
+        group.bench_function(fixture.name, |b| {
+            b.iter(|| {
+                let ctx = context(&tree, fixture.source);
+                black_box(fixture.strategy.resolve(&node, &ctx))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_resolver_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolver");
+    let resolver = Resolver::new();
+
+    for fixture in fixtures() {
+        let tree = parse_go(fixture.source);
+        let node = find_last_node_of_kind(tree.root_node(), fixture.kind).unwrap();
+
+        group.bench_function(fixture.name, |b| {
+            b.iter(|| {
+                let ctx = context(&tree, fixture.source);
+                black_box(resolver.resolve(&node, &ctx))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_strategies, bench_resolver_chain);
+criterion_main!(benches);
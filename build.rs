@@ -0,0 +1,113 @@
+fn main() {
+    record_grammar_versions();
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+
+    #[cfg(feature = "node")]
+    napi_build::setup();
+
+    #[cfg(feature = "grpc")]
+    generate_grpc_service();
+}
+
+/// Exposes the tree-sitter crate versions locked in `Cargo.lock` as
+/// `ARGFLOW_GRAMMARS` (`tree-sitter=0.25.10,tree-sitter-go=0.25.0,...`), for
+/// the metadata of reports. Grammars whose `lang-*` feature is off are left
+/// out. Empty when built without a lockfile.
+fn record_grammar_versions() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let mut versions = Vec::new();
+    let mut name = None;
+    for line in lock.lines() {
+        if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"').to_string());
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            if let Some(name) = name.take().filter(|n| {
+                n == "tree-sitter" || n.strip_prefix("tree-sitter-").is_some_and(grammar_enabled)
+            }) {
+                versions.push(format!("{name}={}", value.trim_matches('"')));
+            }
+        }
+    }
+    println!("cargo:rustc-env=ARGFLOW_GRAMMARS={}", versions.join(","));
+}
+
+/// Whether the grammar crate `tree-sitter-{grammar}` is compiled in, by its
+/// `lang-*` feature (`c-sharp` is `lang-csharp`, `kotlin-ng` is
+/// `lang-kotlin`).
+fn grammar_enabled(grammar: &str) -> bool {
+    let grammar = grammar.strip_suffix("-ng").unwrap_or(grammar);
+    let feature = grammar.replace('-', "").to_uppercase();
+    std::env::var_os(format!("CARGO_FEATURE_LANG_{feature}")).is_some()
+}
+
+/// Writes `include/argflow.h` from the `extern "C"` items in `src/ffi.rs`.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set");
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("ARGFLOW_H".to_string()),
+        header: Some("/* Generated by cbindgen from src/ffi.rs; do not edit. */".to_string()),
+        cpp_compat: true,
+        ..Default::default()
+    };
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(std::path::Path::new(&crate_dir).join("src/ffi.rs"))
+        .generate()
+        .expect("failed to generate C header")
+        .write_to_file(std::path::Path::new(&crate_dir).join("include/argflow.h"));
+}
+
+/// Generates the `ScanService` stubs. The messages are hand-written prost
+/// types in `src/grpc/proto.rs`, so no `protoc` is needed at build time; keep
+/// both in sync with `proto/argflow/v1/scan.proto`.
+#[cfg(feature = "grpc")]
+fn generate_grpc_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::proto::{input}"))
+            .output_type(format!("crate::grpc::proto::{output}"))
+            .codec_path("tonic_prost::ProstCodec")
+    };
+
+    let service = Service::builder()
+        .name("ScanService")
+        .package("argflow.v1")
+        .method(method("scan_file", "ScanFile", "ScanFileRequest", "ScanReport").build())
+        .method(
+            method(
+                "scan_project",
+                "ScanProject",
+                "ScanProjectRequest",
+                "ScanReport",
+            )
+            .build(),
+        )
+        .method(
+            method(
+                "stream_findings",
+                "StreamFindings",
+                "ScanProjectRequest",
+                "Finding",
+            )
+            .server_streaming()
+            .build(),
+        )
+        .build();
+
+    Builder::new().compile(&[service]);
+}
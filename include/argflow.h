@@ -0,0 +1,134 @@
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef ARGFLOW_H
+#define ARGFLOW_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define ARGFLOW_OK 0
+
+#define ARGFLOW_INVALID_ARGUMENT 1
+
+#define ARGFLOW_SCAN_ERROR 2
+
+#define ARGFLOW_PANIC 3
+
+/**
+ * A scanner built from one rules document, reused across scans. Opaque to
+ * C; create with [`argflow_scanner_new`] and release with
+ * [`argflow_scanner_free`].
+ */
+typedef struct ArgflowScanner ArgflowScanner;
+
+typedef struct ArgflowResult {
+  /**
+   * `ARGFLOW_OK` on success, otherwise one of the error codes.
+   */
+  int32_t status;
+  /**
+   * JSON report, or null on failure.
+   */
+  char *json;
+  /**
+   * Error message, or null on success.
+   */
+  char *error;
+} ArgflowResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Scans a single source string.
+ *
+ * `rules_json` may be null, in which case no calls are matched.
+ *
+ * # Safety
+ *
+ * `source` and `language` must be valid NUL-terminated strings; `rules_json`
+ * must be null or a valid NUL-terminated string.
+ */
+struct ArgflowResult *argflow_scan_source(const char *source,
+                                          const char *language,
+                                          const char *rules_json);
+
+/**
+ * Scans the user code under `path` for one language.
+ *
+ * # Safety
+ *
+ * `path` and `language` must be valid NUL-terminated strings; `rules_json`
+ * must be null or a valid NUL-terminated string.
+ */
+struct ArgflowResult *argflow_scan_directory(const char *path,
+                                             const char *language,
+                                             const char *rules_json);
+
+/**
+ * Builds a scanner from `rules_json`, which may be null, in which case no
+ * calls are matched. Returns null when the rules are invalid.
+ *
+ * # Safety
+ *
+ * `rules_json` must be null or a valid NUL-terminated string.
+ */
+struct ArgflowScanner *argflow_scanner_new(const char *rules_json);
+
+/**
+ * Like [`argflow_scan_source`], with the rules of `scanner`.
+ *
+ * # Safety
+ *
+ * `scanner` must be a live pointer returned by [`argflow_scanner_new`];
+ * `source` and `language` must be valid NUL-terminated strings.
+ */
+struct ArgflowResult *argflow_scanner_scan_source(const struct ArgflowScanner *scanner,
+                                                  const char *source,
+                                                  const char *language);
+
+/**
+ * Like [`argflow_scan_directory`], with the rules of `scanner`.
+ *
+ * # Safety
+ *
+ * `scanner` must be a live pointer returned by [`argflow_scanner_new`];
+ * `path` and `language` must be valid NUL-terminated strings.
+ */
+struct ArgflowResult *argflow_scanner_scan_directory(const struct ArgflowScanner *scanner,
+                                                     const char *path,
+                                                     const char *language);
+
+/**
+ * Releases a scanner returned by [`argflow_scanner_new`].
+ *
+ * # Safety
+ *
+ * `scanner` must be null or a pointer returned by [`argflow_scanner_new`]
+ * that has not already been freed.
+ */
+void argflow_scanner_free(struct ArgflowScanner *scanner);
+
+/**
+ * Releases a result returned by any `argflow_scan_*` function.
+ *
+ * # Safety
+ *
+ * `result` must be null or a pointer returned by this library that has not
+ * already been freed.
+ */
+void argflow_free_result(struct ArgflowResult *result);
+
+/**
+ * Returns the library version as a static NUL-terminated string.
+ */
+const char *argflow_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ARGFLOW_H */
@@ -0,0 +1,448 @@
+//! Library scanning API for embedders (wasm, C FFI and other bindings).
+//!
+//! [`scan_source`] works on source text only, with no filesystem access, so
+//! it is the one exposed to wasm; [`scan_file`] reads a single file and
+//! [`scan_directory`] needs the `discovery` feature. Each builds a scanner
+//! from the classifier's rules; a [`RulesScanner`] builds it once.
+
+use std::path::Path;
+
+use tree_sitter::Parser;
+
+use crate::classifier::RulesClassifier;
+use crate::engine::symbol_index::{file_grammar, language_for_path};
+use crate::engine::Language;
+use crate::error::{IoError, ParserError};
+use crate::grammars;
+use crate::output::{JsonOutput, OutputFormatter};
+use crate::scanner::Scanner;
+
+/// File name reported for findings from [`scan_source`].
+pub const SOURCE_FILE_NAME: &str = "<source>";
+
+/// A classifier together with the scanner built from its rules. Building
+/// a scanner clones the classifier's mappings, so embedders scanning more
+/// than once with the same rules keep one of these rather than calling the
+/// free functions, which build a scanner per call.
+pub struct RulesScanner {
+    classifier: RulesClassifier,
+    scanner: Scanner,
+}
+
+impl RulesScanner {
+    pub fn new(classifier: RulesClassifier) -> Self {
+        Self {
+            scanner: scanner_for(&classifier),
+            classifier,
+        }
+    }
+
+    pub fn classifier(&self) -> &RulesClassifier {
+        &self.classifier
+    }
+
+    /// See [`scan_source`].
+    pub fn scan_source(&self, source: &str, language: &str) -> Result<JsonOutput, ParserError> {
+        self.scan_named_source(source, SOURCE_FILE_NAME, language)
+    }
+
+    /// See [`scan_named_source`].
+    pub fn scan_named_source(
+        &self,
+        source: &str,
+        file_name: &str,
+        language: &str,
+    ) -> Result<JsonOutput, ParserError> {
+        named_source(&self.scanner, &self.classifier, source, file_name, language)
+    }
+
+    /// See [`scan_file`].
+    pub fn scan_file(
+        &self,
+        path: &Path,
+        language: Option<&str>,
+    ) -> crate::error::Result<JsonOutput> {
+        file(&self.scanner, &self.classifier, path, language)
+    }
+
+    /// See [`scan_directory`].
+    #[cfg(feature = "discovery")]
+    pub fn scan_directory(&self, root: &Path, language: &str) -> crate::error::Result<JsonOutput> {
+        let mut results = Vec::new();
+        directory_each(&self.scanner, root, language, |result| results.push(result))?;
+        Ok(OutputFormatter::build_output(&results, &self.classifier))
+    }
+
+    /// See [`scan_directory_each`].
+    #[cfg(feature = "discovery")]
+    pub fn scan_directory_each(
+        &self,
+        root: &Path,
+        language: &str,
+        on_result: impl FnMut(crate::scanner::ScanResult),
+    ) -> crate::error::Result<()> {
+        directory_each(&self.scanner, root, language, on_result)
+    }
+}
+
+/// Parses and scans a single source string, classifying calls with `classifier`.
+pub fn scan_source(
+    source: &str,
+    language: &str,
+    classifier: &RulesClassifier,
+) -> Result<JsonOutput, ParserError> {
+    scan_named_source(source, SOURCE_FILE_NAME, language, classifier)
+}
+
+/// Like [`scan_source`], reporting findings under `file_name`.
+pub fn scan_named_source(
+    source: &str,
+    file_name: &str,
+    language: &str,
+    classifier: &RulesClassifier,
+) -> Result<JsonOutput, ParserError> {
+    let scanner = scanner_for(classifier);
+    named_source(&scanner, classifier, source, file_name, language)
+}
+
+/// Reads and scans one file. Without `language` it is inferred from the
+/// file extension.
+pub fn scan_file(
+    path: &Path,
+    language: Option<&str>,
+    classifier: &RulesClassifier,
+) -> crate::error::Result<JsonOutput> {
+    file(&scanner_for(classifier), classifier, path, language)
+}
+
+/// Scans the user code under `root` for one language. Unlike the CLI this
+/// does not pre-filter files by preset imports; every discovered file is
+/// parsed and matched against `classifier`'s mappings.
+#[cfg(feature = "discovery")]
+pub fn scan_directory(
+    root: &Path,
+    language: &str,
+    classifier: &RulesClassifier,
+) -> crate::error::Result<JsonOutput> {
+    let mut results = Vec::new();
+    scan_directory_each(root, language, classifier, |result| results.push(result))?;
+    Ok(OutputFormatter::build_output(&results, classifier))
+}
+
+/// Like [`scan_directory`], but hands each file's result to `on_result` as
+/// soon as it is scanned instead of collecting a report. Files without
+/// matching calls are skipped.
+#[cfg(feature = "discovery")]
+pub fn scan_directory_each(
+    root: &Path,
+    language: &str,
+    classifier: &RulesClassifier,
+    on_result: impl FnMut(crate::scanner::ScanResult),
+) -> crate::error::Result<()> {
+    directory_each(&scanner_for(classifier), root, language, on_result)
+}
+
+fn named_source(
+    scanner: &Scanner,
+    classifier: &RulesClassifier,
+    source: &str,
+    file_name: &str,
+    language: &str,
+) -> Result<JsonOutput, ParserError> {
+    let (lang, mut parser) = parser_for(language)?;
+    scan_text(scanner, classifier, &mut parser, lang, source, file_name)
+}
+
+fn file(
+    scanner: &Scanner,
+    classifier: &RulesClassifier,
+    path: &Path,
+    language: Option<&str>,
+) -> crate::error::Result<JsonOutput> {
+    let language = match language {
+        Some(language) => language.to_string(),
+        None => language_for_path(path)
+            .map(|lang| lang.tree_sitter_name().to_string())
+            .ok_or_else(|| ParserError::unsupported_language(path.display().to_string()))?,
+    };
+    let source = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IoError::file_not_found(path),
+        _ => IoError::read_error(path, e),
+    })?;
+
+    let (lang, mut parser) = parser_for(&language)?;
+    set_file_grammar(&mut parser, lang, path)?;
+    Ok(scan_text(
+        scanner,
+        classifier,
+        &mut parser,
+        lang,
+        &source,
+        &path.to_string_lossy(),
+    )?)
+}
+
+#[cfg(feature = "discovery")]
+fn directory_each(
+    scanner: &Scanner,
+    root: &Path,
+    language: &str,
+    mut on_result: impl FnMut(crate::scanner::ScanResult),
+) -> crate::error::Result<()> {
+    use crate::discovery::LanguageRegistry;
+
+    let (lang, mut parser) = parser_for(language)?;
+    let cli_language = <crate::cli::Language as clap::ValueEnum>::from_str(language, true)
+        .map_err(|_| ParserError::unsupported_language(language))?;
+    let registry = LanguageRegistry::new();
+    let module = registry
+        .get_module(cli_language)
+        .ok_or_else(|| ParserError::unsupported_language(language))?;
+
+    let files = module.create_loader().load_user_code(root)?;
+
+    for file in files {
+        let Ok(source) = std::fs::read_to_string(&file.path) else {
+            tracing::warn!(file = %file.path.display(), "failed to read file");
+            continue;
+        };
+        set_file_grammar(&mut parser, lang, &file.path)?;
+        let Some(tree) = parser.parse(&source, None) else {
+            continue;
+        };
+        let mut result = scanner.scan_tree(
+            &tree,
+            source.as_bytes(),
+            &file.path.to_string_lossy(),
+            lang.tree_sitter_name(),
+        );
+        result.test_code = file
+            .path
+            .strip_prefix(root)
+            .is_ok_and(crate::policy::is_test_file);
+        if result.has_findings() {
+            on_result(result);
+        }
+    }
+
+    Ok(())
+}
+
+fn scan_text(
+    scanner: &Scanner,
+    classifier: &RulesClassifier,
+    parser: &mut Parser,
+    lang: Language,
+    source: &str,
+    file_name: &str,
+) -> Result<JsonOutput, ParserError> {
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| ParserError::parse_failed(file_name))?;
+
+    let result = scanner.scan_tree(&tree, source.as_bytes(), file_name, lang.tree_sitter_name());
+
+    Ok(OutputFormatter::build_output(&[result], classifier))
+}
+
+pub(crate) fn parser_for(language: &str) -> Result<(Language, Parser), ParserError> {
+    let lang =
+        Language::parse(language).ok_or_else(|| ParserError::unsupported_language(language))?;
+    let ts_language = grammars::require(lang)?;
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&ts_language)
+        .map_err(|_| ParserError::language_setup_failed(language))?;
+    Ok((lang, parser))
+}
+
+/// Points `parser` at the grammar for `path`, which differs from that of
+/// `lang` for `.tsx` and mixed JavaScript/TypeScript files.
+fn set_file_grammar(parser: &mut Parser, lang: Language, path: &Path) -> Result<(), ParserError> {
+    let grammar =
+        file_grammar(lang, path).ok_or_else(|| ParserError::language_not_compiled(lang))?;
+    parser
+        .set_language(&grammar)
+        .map_err(|_| ParserError::language_setup_failed(lang.tree_sitter_name()))
+}
+
+pub(crate) fn scanner_for(classifier: &RulesClassifier) -> Scanner {
+    Scanner::with_mappings_and_struct_fields(
+        classifier.get_mappings().clone(),
+        classifier.get_struct_fields().clone(),
+    )
+    .with_clients(classifier.get_clients().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"{
+        "classifications": {
+            "pbkdf2": {"algorithm": "PBKDF2", "findingType": "kdf", "operation": "keyderive"}
+        },
+        "mappings": {
+            "golang.org/x/crypto/pbkdf2": {"Key": "pbkdf2"}
+        }
+    }"#;
+
+    #[test]
+    fn test_scan_source_classifies_calls() {
+        let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+        let source = r#"package main
+
+import "golang.org/x/crypto/pbkdf2"
+
+const iterations = 600000
+
+func derive(pw, salt []byte) []byte {
+	return pbkdf2.Key(pw, salt, iterations, 32, nil)
+}
+"#;
+
+        let output = scan_source(source, "go", &classifier).unwrap();
+        assert_eq!(output.total_findings, 1);
+
+        let finding = &output.findings[0];
+        assert_eq!(finding.file, SOURCE_FILE_NAME);
+        assert_eq!(finding.algorithm.as_deref(), Some("PBKDF2"));
+        assert_eq!(finding.parameters["arg2"], 600000);
+    }
+
+    #[test]
+    fn test_rules_scanner_scans_repeatedly() {
+        let scanner = RulesScanner::new(RulesClassifier::from_json_str(RULES).unwrap());
+        let go = "package main\n\nimport \"golang.org/x/crypto/pbkdf2\"\n\nfunc f(pw, salt []byte) {\n\tpbkdf2.Key(pw, salt, 4096, 32, nil)\n}\n";
+
+        for file_name in ["a.go", "b.go"] {
+            let output = scanner.scan_named_source(go, file_name, "go").unwrap();
+            assert_eq!(output.findings[0].file, file_name);
+            assert_eq!(output.findings[0].algorithm.as_deref(), Some("PBKDF2"));
+        }
+        assert!(scanner.scan_source("x", "cobol").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "discovery")]
+    fn test_scan_directory_scans_user_code() {
+        let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("kdf.go"),
+            "package main\n\nimport \"golang.org/x/crypto/pbkdf2\"\n\nfunc f(pw, salt []byte) {\n\tpbkdf2.Key(pw, salt, 4096, 32, nil)\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("main.go"),
+            "package main\n\nfunc main() {}\n",
+        )
+        .unwrap();
+
+        let output = scan_directory(temp.path(), "go", &classifier).unwrap();
+        assert_eq!(output.files_scanned, 1);
+        assert_eq!(output.findings[0].parameters["arg2"], 4096);
+    }
+
+    #[test]
+    fn test_scan_file_infers_language() {
+        let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("kdf.go");
+        std::fs::write(
+            &path,
+            "package main\n\nimport \"golang.org/x/crypto/pbkdf2\"\n\nfunc f(pw, salt []byte) {\n\tpbkdf2.Key(pw, salt, 4096, 32, nil)\n}\n",
+        )
+        .unwrap();
+
+        let output = scan_file(&path, None, &classifier).unwrap();
+        assert_eq!(output.findings[0].file, path.to_string_lossy());
+        assert_eq!(output.findings[0].parameters["arg2"], 4096);
+
+        let err = scan_file(&temp.path().join("missing.go"), None, &classifier).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Io(IoError::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scan_source_attributes_kms_client_calls() {
+        let rules = r#"{
+            "classifications": {
+                "aws_kms_generate_data_key": {
+                    "findingType": "key_management", "operation": "keygen",
+                    "materialSource": "external_kms"
+                },
+                "aes": {"algorithm": "AES", "findingType": "symmetric", "operation": "encrypt"}
+            },
+            "mappings": {
+                "boto3": {"generate_data_key": "aws_kms_generate_data_key"},
+                "cryptography.hazmat.primitives.ciphers.algorithms": {"AES": "aes"}
+            },
+            "struct_fields": {
+                "*.generate_data_key": {"KeySpec": "kms_key_spec"}
+            },
+            "clients": ["boto3"]
+        }"#;
+        let classifier = RulesClassifier::from_json_str(rules).unwrap();
+        let source = r#"import boto3
+from cryptography.hazmat.primitives.ciphers import algorithms
+
+kms = boto3.client("kms")
+data_key = kms.generate_data_key(KeyId=KEY_ARN, KeySpec="AES_256")
+cipher = algorithms.AES(data_key["Plaintext"])
+kms.describe_key(KeyId=KEY_ARN)
+"#;
+
+        let output = scan_source(source, "python", &classifier).unwrap();
+        assert_eq!(output.total_findings, 2);
+
+        let kms = &output.findings[0];
+        assert_eq!(kms.full_name, "kms.generate_data_key");
+        assert_eq!(kms.import_path.as_deref(), Some("boto3"));
+        assert_eq!(kms.material_source.as_deref(), Some("external_kms"));
+        assert_eq!(output.findings[1].material_source, None);
+
+        assert_eq!(output.total_configs, 1);
+        let key_spec = &output.configs[0].fields[1];
+        assert_eq!(key_spec.classification_key.as_deref(), Some("kms_key_spec"));
+        assert_eq!(key_spec.value, "AES_256");
+    }
+
+    #[test]
+    fn test_scan_source_reports_jwt_alg_none() {
+        let rules = r#"{
+            "classifications": {
+                "jwt_valid_methods": {"findingType": "jwt", "operation": "verify"}
+            },
+            "mappings": {
+                "github.com/golang-jwt/jwt/v5": {"WithValidMethods": "jwt_valid_methods"}
+            }
+        }"#;
+        let classifier = RulesClassifier::from_json_str(rules).unwrap();
+        let source = r#"package main
+
+import "github.com/golang-jwt/jwt/v5"
+
+func parse(token string) {
+	jwt.Parse(token, keyFunc, jwt.WithValidMethods([]string{"none", "RS256"}))
+}
+"#;
+
+        let output = scan_source(source, "go", &classifier).unwrap();
+        assert_eq!(output.weaknesses.len(), 1);
+        let finding = &output.weaknesses[0];
+        assert_eq!(finding.rule, "jwt_alg_none");
+        assert_eq!(finding.function, "jwt.WithValidMethods");
+        assert_eq!(finding.finding, output.findings[0].fingerprint);
+    }
+
+    #[test]
+    fn test_scan_source_rejects_unknown_language() {
+        let classifier = RulesClassifier::new();
+        let err = scan_source("x", "cobol", &classifier).unwrap_err();
+        assert!(matches!(err, ParserError::UnsupportedLanguage { .. }));
+    }
+}
@@ -0,0 +1,291 @@
+//! Manifest and cross-repository summary of `argflow batch`.
+//!
+//! The manifest is JSON or YAML and lists the repositories to scan, with
+//! paths relative to the manifest:
+//!
+//! ```yaml
+//! repos:
+//!   - path: ../payments
+//!   - path: ../auth
+//!     name: auth-service
+//!     language: go
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::cli::Language;
+use crate::error::ConfigError;
+use crate::output::JsonOutput;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchManifest {
+    pub repos: Vec<BatchRepo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRepo {
+    pub path: PathBuf,
+    /// Names the repository's report and summary entry; defaults to the
+    /// last component of `path`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Detected from the repository when not given.
+    #[serde(default, deserialize_with = "deserialize_language")]
+    pub language: Option<Language>,
+}
+
+impl BatchRepo {
+    pub fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            self.path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| self.path.to_string_lossy().to_string())
+        })
+    }
+}
+
+fn deserialize_language<'de, D>(deserializer: D) -> Result<Option<Language>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(name) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    Language::from_str(&name, true)
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("unknown language `{name}`")))
+}
+
+impl BatchManifest {
+    /// Loads the manifest at `path`, resolving relative repository paths
+    /// against its directory. Repository URLs are rejected, as are two
+    /// repositories with the same name.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        debug!(path = %path.display(), "loading batch manifest");
+
+        let content =
+            fs::read_to_string(path).map_err(|e| ConfigError::read_error(path, e.to_string()))?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let mut manifest: Self = match extension {
+            "json" => serde_json::from_str(&content)
+                .map_err(|e| ConfigError::parse_error(path, e.to_string()))?,
+            "yaml" | "yml" => serde_yaml::from_str(&content)
+                .map_err(|e| ConfigError::parse_error(path, e.to_string()))?,
+            _ => return Err(ConfigError::unsupported_format(extension)),
+        };
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        let mut names = HashSet::new();
+        for repo in &mut manifest.repos {
+            let location = repo.path.to_string_lossy();
+            if location.contains("://") || location.starts_with("git@") {
+                return Err(ConfigError::parse_error(
+                    path,
+                    format!("`{location}` is a URL; clone it and list the local path"),
+                ));
+            }
+            if repo.path.is_relative() {
+                repo.path = base.join(&repo.path);
+            }
+            let name = repo.name();
+            if !names.insert(name.clone()) {
+                return Err(ConfigError::parse_error(
+                    path,
+                    format!("two repositories are named `{name}`; give one a `name`"),
+                ));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+/// The `summary.json` of a batch: each repository's totals and the
+/// algorithms found across all of them.
+#[derive(Debug, Default, Serialize)]
+pub struct BatchSummary {
+    pub repos: Vec<RepoSummary>,
+    pub total_repos: usize,
+    pub failed_repos: usize,
+    pub files_scanned: usize,
+    pub total_findings: usize,
+    /// Each algorithm's findings and the repositories they are in.
+    pub algorithms: BTreeMap<String, AlgorithmUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepoSummary {
+    pub name: String,
+    pub path: String,
+    /// Report written for the repository, absent when its scan failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub files_scanned: usize,
+    pub total_findings: usize,
+    pub risk_score: u32,
+    /// Findings by algorithm.
+    #[serde(skip)]
+    pub algorithms: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AlgorithmUsage {
+    pub findings: usize,
+    pub repos: BTreeSet<String>,
+}
+
+impl RepoSummary {
+    pub fn new(repo: &BatchRepo, output: &Path, report: &JsonOutput) -> Self {
+        let mut algorithms = BTreeMap::new();
+        for finding in &report.findings {
+            if let Some(algorithm) = &finding.algorithm {
+                *algorithms.entry(algorithm.clone()).or_default() += 1;
+            }
+        }
+        Self {
+            name: repo.name(),
+            path: repo.path.to_string_lossy().to_string(),
+            output: Some(output.to_string_lossy().to_string()),
+            error: None,
+            files_scanned: report.files_scanned,
+            total_findings: report.total_findings,
+            risk_score: report.risk_score,
+            algorithms,
+        }
+    }
+
+    pub fn failed(repo: &BatchRepo, error: &anyhow::Error) -> Self {
+        Self {
+            name: repo.name(),
+            path: repo.path.to_string_lossy().to_string(),
+            output: None,
+            error: Some(format!("{error:#}")),
+            files_scanned: 0,
+            total_findings: 0,
+            risk_score: 0,
+            algorithms: BTreeMap::new(),
+        }
+    }
+}
+
+impl BatchSummary {
+    /// Totals `repos`, kept in the given (manifest) order.
+    pub fn new(repos: Vec<RepoSummary>) -> Self {
+        let mut summary = Self {
+            total_repos: repos.len(),
+            ..Self::default()
+        };
+        for repo in &repos {
+            if repo.error.is_some() {
+                summary.failed_repos += 1;
+            }
+            summary.files_scanned += repo.files_scanned;
+            summary.total_findings += repo.total_findings;
+            for (algorithm, count) in &repo.algorithms {
+                let usage = summary.algorithms.entry(algorithm.clone()).or_default();
+                usage.findings += count;
+                usage.repos.insert(repo.name.clone());
+            }
+        }
+        summary.repos = repos;
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    use crate::output::Finding;
+    use crate::test_support;
+
+    fn report(algorithms: &[&str]) -> JsonOutput {
+        let findings = algorithms
+            .iter()
+            .enumerate()
+            .map(|(line, algorithm)| Finding {
+                algorithm: Some(algorithm.to_string()),
+                ..test_support::finding("a.go", line + 1, "p.f")
+            })
+            .collect();
+        test_support::report(findings, Vec::new())
+    }
+
+    #[test]
+    fn test_load_manifest_resolves_paths_against_it() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("repos.yaml");
+        fs::write(
+            &path,
+            "repos:\n  - path: services/payments\n  - path: /src/auth\n    name: auth-service\n    language: go\n",
+        )
+        .unwrap();
+
+        let manifest = BatchManifest::load(&path).unwrap();
+        assert_eq!(manifest.repos[0].path, dir.path().join("services/payments"));
+        assert_eq!(manifest.repos[0].name(), "payments");
+        assert_eq!(manifest.repos[0].language, None);
+        assert_eq!(manifest.repos[1].path, PathBuf::from("/src/auth"));
+        assert_eq!(manifest.repos[1].name(), "auth-service");
+        assert_eq!(manifest.repos[1].language, Some(Language::Go));
+    }
+
+    #[test]
+    fn test_load_manifest_rejects_urls_and_duplicate_names() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("repos.json");
+
+        fs::write(&path, r#"{"repos": [{"path": "https://github.com/a/b"}]}"#).unwrap();
+        assert!(BatchManifest::load(&path).is_err());
+
+        fs::write(
+            &path,
+            r#"{"repos": [{"path": "a/api"}, {"path": "b/api"}]}"#,
+        )
+        .unwrap();
+        assert!(BatchManifest::load(&path).is_err());
+
+        fs::write(&path, r#"{"repos": [{"path": "a", "language": "cobol"}]}"#).unwrap();
+        assert!(BatchManifest::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_summary_aggregates_algorithms_across_repos() {
+        let repo = |name: &str| BatchRepo {
+            path: PathBuf::from(name),
+            name: None,
+            language: None,
+        };
+        let summary = BatchSummary::new(vec![
+            RepoSummary::new(
+                &repo("a"),
+                Path::new("out/a.json"),
+                &report(&["AES", "MD5"]),
+            ),
+            RepoSummary::new(&repo("b"), Path::new("out/b.json"), &report(&["MD5"])),
+            RepoSummary::failed(&repo("c"), &anyhow::anyhow!("no project found")),
+        ]);
+
+        assert_eq!(summary.total_repos, 3);
+        assert_eq!(summary.failed_repos, 1);
+        assert_eq!(summary.total_findings, 3);
+        assert_eq!(summary.algorithms["MD5"].findings, 2);
+        assert_eq!(
+            summary.algorithms["MD5"].repos,
+            BTreeSet::from(["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(summary.repos[2].error.as_deref(), Some("no project found"));
+    }
+}
@@ -0,0 +1,55 @@
+//! `argflow-grpc`: serves `argflow.v1.ScanService` over gRPC.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use tracing::info;
+
+use argflow::grpc::ScanServer;
+use argflow::{logging, LogFilter, RulesClassifier, Verbosity};
+
+#[derive(Parser, Debug)]
+#[command(name = "argflow-grpc")]
+#[command(about = "Serve argflow scans over gRPC", long_about = None)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    listen: SocketAddr,
+
+    /// Default rules file for requests without `rules_json` (bundled crypto rules if omitted)
+    #[arg(long)]
+    rules: Option<PathBuf>,
+
+    /// Increase verbosity (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Per-module log levels, e.g. `grpc=debug,engine::strategies=trace`
+    #[arg(long, value_name = "DIRECTIVES")]
+    log_filter: Option<LogFilter>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    logging::init(
+        Verbosity::from_flags(args.verbose, false),
+        args.log_filter.as_ref(),
+    );
+
+    let classifier = match &args.rules {
+        Some(path) => RulesClassifier::from_file(path),
+        None => RulesClassifier::from_bundled(),
+    }
+    .context("Failed to load classifier rules")?;
+
+    info!(addr = %args.listen, "serving argflow.v1.ScanService");
+    tonic::transport::Server::builder()
+        .add_service(ScanServer::new(classifier).into_service())
+        .serve(args.listen)
+        .await
+        .context("gRPC server failed")?;
+    Ok(())
+}
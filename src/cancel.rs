@@ -0,0 +1,46 @@
+//! Cancelling a scan from SIGINT or SIGTERM. The first signal sets a flag
+//! that stops workers from starting new files, so the results completed so
+//! far are still written, marked `partial`; a second signal exits at once.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Exit status of a cancelled scan, as for a process killed by SIGINT.
+pub const EXIT_STATUS: i32 = 130;
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Asks every scan in progress to stop after the files it is parsing.
+pub fn cancel() {
+    CANCELLED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_cancelled() -> bool {
+    CANCELLED.load(Ordering::Relaxed)
+}
+
+/// Cancels the scan on SIGINT and SIGTERM instead of exiting.
+#[cfg(unix)]
+pub fn install_handlers() {
+    extern "C" fn on_signal(_: libc::c_int) {
+        if CANCELLED.swap(true, Ordering::SeqCst) {
+            // SAFETY: `_exit` is async-signal-safe.
+            unsafe { libc::_exit(EXIT_STATUS) };
+        }
+        let message =
+            b"argflow: cancelling, writing the results so far (interrupt again to abort)\n";
+        // SAFETY: `write` is async-signal-safe and `message` outlives the call.
+        unsafe { libc::write(libc::STDERR_FILENO, message.as_ptr().cast(), message.len()) };
+    }
+
+    let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    // SAFETY: the handler only touches an atomic and calls async-signal-safe
+    // functions.
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+/// Signals keep their default action where there are no handlers to set.
+#[cfg(not(unix))]
+pub fn install_handlers() {}
@@ -71,6 +71,11 @@ pub struct Classification {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub protocol_version: Option<String>,
 
+    /// Guidance page for findings of this classification, e.g. an internal
+    /// wiki page on migrating away from the algorithm.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
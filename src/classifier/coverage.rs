@@ -0,0 +1,367 @@
+//! How much of the crypto-capable dependencies' API the rules cover, to
+//! show where mappings are missing.
+//!
+//! Only functions the project calls are counted: a dependency whose called
+//! functions all classify is fully covered, whatever else it exports.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::{Classifier, RulesClassifier};
+use crate::scanner::Finding;
+
+/// Name fragments of packages that are likely to do cryptography even when
+/// no rules map them.
+const CRYPTO_TOKENS: &[&str] = &[
+    "crypt", "cipher", "hash", "ssl", "tls", "sodium", "nacl", "ring", "jwt", "jose", "x509",
+    "rsa", "aes", "hmac", "pbkdf", "argon", "sha", "blake", "ecdsa", "ed25519", "kms", "pem",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyCoverage {
+    pub dependency: String,
+    /// Whether a lockfile or manifest declares it; stdlib packages and
+    /// undeclared imports are not.
+    pub declared: bool,
+    /// Distinct functions the project calls on it.
+    pub called: usize,
+    /// How many of `called` the rules classify.
+    pub mapped: usize,
+    /// Called functions the rules do not classify.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmapped: Vec<String>,
+    /// Functions the rules map under the dependency, called or not.
+    pub rule_mappings: usize,
+}
+
+impl DependencyCoverage {
+    /// Share of the called functions that classify, unset when none are
+    /// called.
+    pub fn percent(&self) -> Option<u32> {
+        (self.called > 0).then(|| (100 * self.mapped / self.called) as u32)
+    }
+}
+
+/// Coverage of each crypto-capable dependency of a `language` project:
+/// those imported by `calls` and those in `declared`. A dependency is
+/// crypto-capable when the rules map any of its functions or its name
+/// suggests cryptography. Sorted by name.
+pub fn mapping_coverage(
+    calls: &[Finding],
+    declared: &[String],
+    language: &str,
+    classifier: &RulesClassifier,
+) -> Vec<DependencyCoverage> {
+    // Called functions per dependency, keyed by import path and function.
+    let mut called: BTreeMap<String, BTreeMap<(String, String), &Finding>> = BTreeMap::new();
+    for call in calls {
+        let Some(import_path) = call.import_path.as_deref() else {
+            continue;
+        };
+        let dependency = dependency_of(import_path, language, declared);
+        called.entry(dependency).or_default().insert(
+            (import_path.to_string(), call.function_name.to_lowercase()),
+            call,
+        );
+    }
+    // Declared dependencies by the name they are imported under.
+    let declared: BTreeMap<String, &String> = declared
+        .iter()
+        .map(|name| (import_name(name, language), name))
+        .collect();
+    let mut imported_as = BTreeMap::new();
+    for (key, name) in &declared {
+        if !called.keys().any(|d| normalize(d) == *key) {
+            called.entry((*name).clone()).or_default();
+            imported_as.insert((*name).clone(), key.clone());
+        }
+    }
+
+    called
+        .into_iter()
+        .filter_map(|(dependency, functions)| {
+            let lookup = imported_as.get(&dependency).unwrap_or(&dependency);
+            let rule_mappings = rule_mappings(lookup, classifier);
+            if rule_mappings == 0 && !names_crypto(&dependency) {
+                return None;
+            }
+            let unmapped: Vec<String> = functions
+                .values()
+                .filter(|call| {
+                    classifier
+                        .lookup_with_fallback(
+                            call.import_path.as_deref(),
+                            call.package.as_deref().unwrap_or(""),
+                            &call.function_name,
+                        )
+                        .is_unclassified()
+                })
+                .map(|call| call.full_name())
+                .collect();
+            Some(DependencyCoverage {
+                declared: declared.contains_key(&normalize(lookup)),
+                called: functions.len(),
+                mapped: functions.len() - unmapped.len(),
+                unmapped,
+                rule_mappings,
+                dependency,
+            })
+        })
+        .collect()
+}
+
+/// Whether a dependency of a `language` project is crypto-capable: the
+/// rules map any of its functions or its name suggests cryptography.
+pub fn is_crypto_capable(dependency: &str, language: &str, classifier: &RulesClassifier) -> bool {
+    names_crypto(dependency)
+        || rule_mappings(dependency, classifier) > 0
+        || rule_mappings(&import_name(dependency, language), classifier) > 0
+}
+
+fn names_crypto(dependency: &str) -> bool {
+    let lower = dependency.to_lowercase();
+    CRYPTO_TOKENS.iter().any(|token| lower.contains(token))
+}
+
+/// Python distributions imported under another name.
+const PYTHON_IMPORT_NAMES: &[(&str, &str)] = &[
+    ("pynacl", "nacl"),
+    ("pycryptodome", "crypto"),
+    ("pycryptodomex", "cryptodome"),
+    ("pyopenssl", "openssl"),
+    ("pyjwt", "jwt"),
+    ("python_jose", "jose"),
+    ("argon2_cffi", "argon2"),
+];
+
+/// The normalized name `declared` is imported under.
+fn import_name(declared: &str, language: &str) -> String {
+    let name = normalize(declared);
+    if language == "python" {
+        if let Some((_, import)) = PYTHON_IMPORT_NAMES.iter().find(|(d, _)| *d == name) {
+            return import.to_string();
+        }
+    }
+    name
+}
+
+/// The dependency providing `import_path`: the declared Go module it lies
+/// in, the crate, the top-level Python package, the Java package of the
+/// class, the header directory (`openssl` for `openssl/evp.h`) or header
+/// name (`sodium` for `sodium.h`) in C and C++, the declared NuGet package
+/// a C# namespace lies in (else the namespace itself), the gem named after
+/// the top-level Ruby module (`openssl` for `OpenSSL::Cipher`), the declared
+/// Composer package whose vendor names the top-level PHP namespace
+/// (`paragonie/halite` for `ParagonIE\Halite\Symmetric\Crypto`), the Swift
+/// module (`CryptoKit` for `CryptoKit.AES.GCM`), or the npm package.
+fn dependency_of(import_path: &str, language: &str, declared: &[String]) -> String {
+    match language {
+        "go" => declared
+            .iter()
+            .filter(|module| is_under(import_path, module, '/'))
+            .max_by_key(|module| module.len())
+            .cloned()
+            .unwrap_or_else(|| import_path.to_string()),
+        "rust" => import_path
+            .split("::")
+            .next()
+            .unwrap_or(import_path)
+            .to_string(),
+        "python" | "swift" => import_path
+            .split('.')
+            .next()
+            .unwrap_or(import_path)
+            .to_string(),
+        "java" => import_path
+            .rsplit_once('.')
+            .map_or(import_path, |(package, _)| package)
+            .to_string(),
+        "csharp" => declared
+            .iter()
+            .filter(|package| is_under(import_path, package, '.'))
+            .max_by_key(|package| package.len())
+            .cloned()
+            .unwrap_or_else(|| import_path.to_string()),
+        "ruby" => import_path
+            .split("::")
+            .next()
+            .unwrap_or(import_path)
+            .to_lowercase(),
+        "php" => {
+            let namespace: Vec<String> = import_path.split('\\').map(str::to_lowercase).collect();
+            let package = namespace
+                .iter()
+                .take(2)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("/");
+            declared
+                .iter()
+                .find(|declared| declared.to_lowercase() == package)
+                .or_else(|| {
+                    declared
+                        .iter()
+                        .find(|declared| declared.split('/').next() == Some(&namespace[0]))
+                })
+                .cloned()
+                .unwrap_or_else(|| namespace[0].clone())
+        }
+        "c" | "cpp" => match import_path.split_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => import_path
+                .strip_suffix(".h")
+                .unwrap_or(import_path)
+                .to_string(),
+        },
+        _ => {
+            let path = import_path.strip_prefix("node:").unwrap_or(import_path);
+            let segments = if path.starts_with('@') { 2 } else { 1 };
+            path.split('/').take(segments).collect::<Vec<_>>().join("/")
+        }
+    }
+}
+
+fn is_under(path: &str, prefix: &str, separator: char) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(separator))
+}
+
+/// Mapped functions whose import path lies under `dependency`.
+fn rule_mappings(dependency: &str, classifier: &RulesClassifier) -> usize {
+    let dependency = dependency.to_lowercase();
+    classifier
+        .get_mappings()
+        .iter()
+        .filter(|(path, _)| {
+            is_under(path, &dependency, '/')
+                || is_under(path, &dependency, '.')
+                || is_under(path, &dependency, ':')
+                || normalize(path) == normalize(&dependency)
+        })
+        .map(|(_, functions)| functions.len())
+        .sum()
+}
+
+/// Package names as registries compare them: `PyNaCl` is `pynacl`, and
+/// `ring-compat` is `ring_compat`.
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(import_path: &str, package: &str, function: &str) -> Finding {
+        Finding {
+            file_path: "main.py".to_string(),
+            line: 1,
+            column: 1,
+            function_name: function.to_string(),
+            package: Some(package.to_string()),
+            import_path: Some(import_path.to_string()),
+            arguments: vec![],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
+            raw_text: String::new(),
+            language: "python".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mapping_coverage() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {"md5": {"algorithm": "MD5", "findingType": "hash", "operation": "hash"}},
+                "mappings": {"hashlib": {"md5": "md5"}, "Crypto.Hash.MD5": {"new": "md5"}}
+            }"#,
+        )
+        .unwrap();
+        let calls = [
+            call("hashlib", "hashlib", "md5"),
+            call("hashlib", "hashlib", "blake2b"),
+            call("hashlib", "hashlib", "md5"),
+            call("nacl.secret", "secret", "SecretBox"),
+            call("requests", "requests", "get"),
+        ];
+        let declared = ["PyNaCl".to_string(), "pycryptodome".to_string()];
+        let coverage = mapping_coverage(&calls, &declared, "python", &classifier);
+
+        let names: Vec<&str> = coverage.iter().map(|c| c.dependency.as_str()).collect();
+        assert_eq!(names, vec!["hashlib", "nacl", "pycryptodome"]);
+        assert_eq!(coverage[0].percent(), Some(50));
+        assert_eq!(coverage[0].unmapped, vec!["hashlib.blake2b"]);
+        assert!(!coverage[0].declared);
+        // PyNaCl is imported as nacl, which no rules map.
+        assert!(coverage[1].declared);
+        assert_eq!(coverage[1].percent(), Some(0));
+        // pycryptodome is not called, but the rules map its Crypto package.
+        assert_eq!(coverage[2].percent(), None);
+        assert_eq!(coverage[2].rule_mappings, 1);
+    }
+
+    #[test]
+    fn test_is_crypto_capable() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {"aes": {"algorithm": "AES", "findingType": "symmetric", "operation": "encrypt"}},
+                "mappings": {"Crypto.Cipher.AES": {"new": "aes"}, "fernet_wrapper": {"encrypt": "aes"}}
+            }"#,
+        )
+        .unwrap();
+
+        assert!(is_crypto_capable("pycryptodome", "python", &classifier));
+        assert!(is_crypto_capable("fernet-wrapper", "python", &classifier));
+        assert!(is_crypto_capable("PyJWT", "python", &classifier));
+        assert!(!is_crypto_capable("requests", "python", &classifier));
+    }
+
+    #[test]
+    fn test_dependency_of() {
+        let modules = ["golang.org/x/crypto".to_string()];
+        assert_eq!(
+            dependency_of("golang.org/x/crypto/pbkdf2", "go", &modules),
+            "golang.org/x/crypto"
+        );
+        assert_eq!(
+            dependency_of("crypto/sha256", "go", &modules),
+            "crypto/sha256"
+        );
+        assert_eq!(dependency_of("ring::aead", "rust", &[]), "ring");
+        assert_eq!(
+            dependency_of("@noble/hashes/sha256", "javascript", &[]),
+            "@noble/hashes"
+        );
+        assert_eq!(dependency_of("node:crypto", "javascript", &[]), "crypto");
+        assert_eq!(dependency_of("openssl/evp.h", "c", &[]), "openssl");
+        assert_eq!(dependency_of("sodium.h", "c", &[]), "sodium");
+        assert_eq!(dependency_of("botan/hash.h", "cpp", &[]), "botan");
+        let packages = ["Microsoft.IdentityModel.Tokens".to_string()];
+        assert_eq!(
+            dependency_of("Microsoft.IdentityModel.Tokens.Jwt", "csharp", &packages),
+            "Microsoft.IdentityModel.Tokens"
+        );
+        assert_eq!(
+            dependency_of("System.Security.Cryptography", "csharp", &packages),
+            "System.Security.Cryptography"
+        );
+        assert_eq!(dependency_of("OpenSSL::PKey::RSA", "ruby", &[]), "openssl");
+        let packages = [
+            "paragonie/halite".to_string(),
+            "phpseclib/phpseclib".to_string(),
+        ];
+        assert_eq!(
+            dependency_of("ParagonIE\\Halite\\Symmetric\\Crypto", "php", &packages),
+            "paragonie/halite"
+        );
+        assert_eq!(dependency_of("php", "php", &packages), "php");
+        assert_eq!(
+            dependency_of("CryptoKit.AES.GCM", "swift", &[]),
+            "CryptoKit"
+        );
+    }
+}
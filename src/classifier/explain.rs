@@ -0,0 +1,74 @@
+//! Why a call got the classification it did, for `argflow classify`.
+
+use std::fmt;
+
+use super::{Classification, RuleSource};
+
+/// The lookups [`super::classify_call`] makes for one call, in order, and
+/// the classification it ends up with.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub steps: Vec<LookupStep>,
+    pub classification: Classification,
+    /// The operation the function name narrowed the classification's to,
+    /// see [`super::refine_operation`].
+    pub refined_operation: Option<&'static str>,
+}
+
+/// One lookup of the function under an import path or package name.
+#[derive(Debug, Clone)]
+pub struct LookupStep {
+    pub path: String,
+    pub outcome: LookupOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupOutcome {
+    /// No rules map any function under the path.
+    UnknownPath,
+    /// The path is mapped, but not this function; `mapped` lists some of
+    /// the functions that are.
+    UnmappedFunction {
+        mapped: Vec<String>,
+    },
+    /// The function maps to `key`, which no rules define.
+    UndefinedClassification {
+        key: String,
+        source: RuleSource,
+    },
+    Matched {
+        key: String,
+        source: RuleSource,
+    },
+}
+
+impl Explanation {
+    pub fn is_classified(&self) -> bool {
+        !self.classification.is_unclassified()
+    }
+}
+
+impl fmt::Display for LookupOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPath => write!(f, "no mappings for this path"),
+            Self::UnmappedFunction { mapped } => {
+                write!(f, "path is mapped, function is not")?;
+                if !mapped.is_empty() {
+                    write!(f, " (mapped: {})", mapped.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::UndefinedClassification { key, source } => write!(
+                f,
+                "mapped to {key} by {} (precedence {}), but no rules define {key}",
+                source.namespace, source.precedence
+            ),
+            Self::Matched { key, source } => write!(
+                f,
+                "matched {key} from {} (precedence {})",
+                source.namespace, source.precedence
+            ),
+        }
+    }
+}
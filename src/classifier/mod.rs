@@ -1,8 +1,20 @@
 mod classification;
+mod coverage;
+mod explain;
+mod operation;
+mod rule_tests;
 mod rules;
+mod usage;
 
 pub use classification::Classification;
-pub use rules::{Classifier, RulesClassifier};
+pub use coverage::{is_crypto_capable, mapping_coverage, DependencyCoverage};
+pub use explain::{Explanation, LookupOutcome, LookupStep};
+pub use operation::refine_operation;
+pub use rule_tests::{
+    load_rule_tests, run_rule_test, run_rule_tests, ExpectedFinding, RuleTest, RuleTestResult,
+};
+pub use rules::{Classifier, RuleSource, RulesClassifier, RulesInfo, BUNDLED_NAMESPACE};
+pub use usage::{hash_usage, Confidence, HashUsage, UsageContext};
 
 pub use crate::error::ClassifierError;
 
@@ -27,12 +39,18 @@ impl ClassifiedCall {
     }
 }
 
+/// Classifies `call`, narrowing a shared operation such as `sign` to the
+/// direction the called function performs, see [`refine_operation`].
 pub fn classify_call<C: Classifier>(call: &Finding, classifier: &C) -> Classification {
-    classifier.lookup_with_fallback(
+    let mut classification = classifier.lookup_with_fallback(
         call.import_path.as_deref(),
         call.package.as_deref().unwrap_or(""),
         &call.function_name,
-    )
+    );
+    if let Some(operation) = refine_operation(&classification.operation, &call.function_name) {
+        classification.operation = operation.to_string();
+    }
+    classification
 }
 
 #[cfg(test)]
@@ -53,6 +71,9 @@ mod integration_tests {
             package: package.map(|s| s.to_string()),
             import_path: import_path.map(|s| s.to_string()),
             arguments: vec![],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
             raw_text: format!("{function}()"),
             language: language.to_string(),
         }
@@ -243,4 +264,23 @@ mod integration_tests {
         assert_eq!(result.protocol_name, Some("TLS".to_string()));
         assert_eq!(result.protocol_version, Some("1.3".to_string()));
     }
+
+    #[test]
+    fn test_operation_follows_called_function() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {
+                    "rsa_pss": {"algorithm": "RSASSA-PSS", "findingType": "signature", "operation": "sign"}
+                },
+                "mappings": {"crypto/rsa": {"SignPSS": "rsa_pss", "VerifyPSS": "rsa_pss"}}
+            }"#,
+        )
+        .unwrap();
+
+        let sign = make_call(Some("crypto/rsa"), Some("rsa"), "SignPSS", "go");
+        assert_eq!(classify_call(&sign, &classifier).operation, "sign");
+
+        let verify = make_call(Some("crypto/rsa"), Some("rsa"), "VerifyPSS", "go");
+        assert_eq!(classify_call(&verify, &classifier).operation, "verify");
+    }
 }
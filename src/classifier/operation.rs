@@ -0,0 +1,97 @@
+//! Per-call operation direction.
+//!
+//! A classification is shared by every function mapped to it, so
+//! `rsa.SignPSS` and `rsa.VerifyPSS`, or `Seal` and `Open`, end up with
+//! the same `operation`. The called function's name tells the direction
+//! apart, and [`refine_operation`] applies it within the classification's
+//! family: encrypt/decrypt, sign/verify or wrap/unwrap.
+
+use crate::utils::identifier_words;
+
+/// The two directions of one kind of operation.
+const FAMILIES: &[(&str, &str)] = &[
+    ("encrypt", "decrypt"),
+    ("sign", "verify"),
+    ("wrap", "unwrap"),
+];
+
+/// Function name words and the operation they name.
+const NAME_WORDS: &[(&str, &str)] = &[
+    ("encrypt", "encrypt"),
+    ("encrypter", "encrypt"),
+    ("encryptor", "encrypt"),
+    ("seal", "encrypt"),
+    ("cipheriv", "encrypt"),
+    ("decrypt", "decrypt"),
+    ("decrypter", "decrypt"),
+    ("decryptor", "decrypt"),
+    ("open", "decrypt"),
+    ("decipher", "decrypt"),
+    ("decipheriv", "decrypt"),
+    ("sign", "sign"),
+    ("signer", "sign"),
+    ("verify", "verify"),
+    ("verifier", "verify"),
+    ("wrap", "wrap"),
+    ("unwrap", "unwrap"),
+];
+
+/// The operation of a call to `function` under a classification with
+/// `operation`, when the name picks one direction of its family. A
+/// combined operation such as `sign_verify` or `encrypt/decrypt` is also
+/// resolved this way.
+pub fn refine_operation(operation: &str, function: &str) -> Option<&'static str> {
+    let family = family_of(operation)?;
+    identifier_words(function).find_map(|word| {
+        NAME_WORDS
+            .iter()
+            .find(|(name, _)| *name == word)
+            .map(|(_, op)| *op)
+            .filter(|op| family.0 == *op || family.1 == *op)
+    })
+}
+
+fn family_of(operation: &str) -> Option<(&'static str, &'static str)> {
+    let operation = operation.to_ascii_lowercase();
+    let parts: Vec<&str> = operation
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .collect();
+    FAMILIES.iter().copied().find(|(a, b)| {
+        parts
+            .iter()
+            .any(|part| part == a || part == b || *part == format!("key{a}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_from_function_name() {
+        assert_eq!(refine_operation("sign", "VerifyPKCS1v15"), Some("verify"));
+        assert_eq!(refine_operation("verify", "SignPSS"), Some("sign"));
+        assert_eq!(refine_operation("encrypt", "Open"), Some("decrypt"));
+        assert_eq!(
+            refine_operation("encrypt", "createDecipheriv"),
+            Some("decrypt")
+        );
+        assert_eq!(
+            refine_operation("encrypt", "NewCBCDecrypter"),
+            Some("decrypt")
+        );
+        assert_eq!(
+            refine_operation("keywrap", "aes_key_unwrap"),
+            Some("unwrap")
+        );
+        assert_eq!(refine_operation("sign_verify", "sign"), Some("sign"));
+    }
+
+    #[test]
+    fn test_other_families_are_left_alone() {
+        assert_eq!(refine_operation("hash", "Sum256"), None);
+        assert_eq!(refine_operation("keyderive", "Open"), None);
+        assert_eq!(refine_operation("encrypt", "NewGCM"), None);
+        assert_eq!(refine_operation("encrypt", "Sign"), None);
+    }
+}
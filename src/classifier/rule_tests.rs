@@ -0,0 +1,334 @@
+//! Fixture tests embedded in rules files.
+//!
+//! A rules file may carry a `tests` list. Each test is a source snippet
+//! (inline or in a file next to the rules file) and the findings the rules
+//! should produce for it:
+//!
+//! ```yaml
+//! tests:
+//!   - name: pbkdf2 iterations are captured
+//!     language: python
+//!     source: |
+//!       import hashlib
+//!       hashlib.pbkdf2_hmac("sha256", pw, salt, 600000)
+//!     expect:
+//!       - function: pbkdf2_hmac
+//!         algorithm: PBKDF2
+//!         parameters: { arg3: 600000 }
+//! ```
+//!
+//! Snippets go through the same scan and report as the CLI. Only the fields
+//! given in an expectation are compared, and an empty `expect` asserts that
+//! nothing is found.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{ClassifierError, RulesClassifier};
+use crate::output::{Finding, JsonOutput};
+
+#[derive(Debug, Default, Deserialize)]
+struct RuleTestsFile {
+    #[serde(default)]
+    tests: Vec<RuleTest>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleTest {
+    pub name: String,
+    pub language: String,
+    /// The snippet to scan.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// A file holding the snippet, relative to the rules file.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    #[serde(default)]
+    pub expect: Vec<ExpectedFinding>,
+    /// Weakness rule IDs that must be reported, e.g. `cipher_ecb_mode`.
+    #[serde(default)]
+    pub weaknesses: Vec<String>,
+}
+
+/// Fields a finding must have; unset fields are not compared.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedFinding {
+    /// `function` or `full_name` of the finding.
+    pub function: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    #[serde(default)]
+    pub finding_type: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    #[serde(default)]
+    pub primitive: Option<String>,
+    /// Resolved argument values by name (`arg0`, ...), as in the report.
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTestResult {
+    pub name: String,
+    /// Why the test failed; empty when it passed.
+    pub failures: Vec<String>,
+}
+
+impl RuleTestResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// The tests in the rules file at `path`.
+pub fn load_rule_tests(path: &Path) -> Result<Vec<RuleTest>, ClassifierError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ClassifierError::rules_file_read_error(path, e.to_string()))?;
+    let file: RuleTestsFile = match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "json" => serde_json::from_str(&content)
+            .map_err(|e| ClassifierError::rules_parse_error(path, e.to_string()))?,
+        "yaml" | "yml" => serde_yaml::from_str(&content)
+            .map_err(|e| ClassifierError::rules_parse_error(path, e.to_string()))?,
+        extension => return Err(ClassifierError::unsupported_format(extension)),
+    };
+    Ok(file.tests)
+}
+
+/// Runs the tests of the rules file at `path` against `classifier`.
+pub fn run_rule_tests(
+    path: &Path,
+    classifier: &RulesClassifier,
+) -> Result<Vec<RuleTestResult>, ClassifierError> {
+    let base = path.parent().unwrap_or(Path::new("."));
+    Ok(load_rule_tests(path)?
+        .iter()
+        .map(|test| run_rule_test(test, base, classifier))
+        .collect())
+}
+
+/// Runs one test; `file` snippets are read relative to `base`.
+pub fn run_rule_test(test: &RuleTest, base: &Path, classifier: &RulesClassifier) -> RuleTestResult {
+    let failures = match scan_fixture(test, base, classifier) {
+        Ok(report) => check_report(test, &report),
+        Err(message) => vec![message],
+    };
+    RuleTestResult {
+        name: test.name.clone(),
+        failures,
+    }
+}
+
+fn scan_fixture(
+    test: &RuleTest,
+    base: &Path,
+    classifier: &RulesClassifier,
+) -> Result<JsonOutput, String> {
+    let (source, file_name) = match (&test.source, &test.file) {
+        (Some(source), None) => (source.clone(), format!("<{}>", test.name)),
+        (None, Some(file)) => {
+            let path = base.join(file);
+            let source = fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read fixture {}: {e}", path.display()))?;
+            (source, file.to_string_lossy().to_string())
+        }
+        _ => return Err("give exactly one of `source` or `file`".to_string()),
+    };
+    crate::api::scan_named_source(&source, &file_name, &test.language, classifier)
+        .map_err(|e| e.to_string())
+}
+
+fn check_report(test: &RuleTest, report: &JsonOutput) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    if test.expect.is_empty() && !report.findings.is_empty() {
+        failures.push(format!(
+            "expected no findings, found {}",
+            report
+                .findings
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let mut unmatched: Vec<&Finding> = report.findings.iter().collect();
+    for expected in &test.expect {
+        let candidates: Vec<usize> = unmatched
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.function == expected.function || f.full_name == expected.function)
+            .map(|(i, _)| i)
+            .collect();
+        match candidates
+            .iter()
+            .find(|&&i| mismatches(expected, unmatched[i]).is_empty())
+        {
+            Some(&i) => {
+                unmatched.remove(i);
+            }
+            None => match candidates.first() {
+                Some(&i) => failures.push(format!(
+                    "{}: {}",
+                    describe(unmatched[i]),
+                    mismatches(expected, unmatched[i]).join(", ")
+                )),
+                None => failures.push(format!("no finding for {}", expected.function)),
+            },
+        }
+    }
+
+    for rule in &test.weaknesses {
+        if !report.weaknesses.iter().any(|w| &w.rule == rule) {
+            failures.push(format!("weakness {rule} not reported"));
+        }
+    }
+
+    failures
+}
+
+fn mismatches(expected: &ExpectedFinding, finding: &Finding) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut compare = |field: &str, want: &Option<String>, got: &Option<String>| {
+        if let Some(want) = want {
+            if got.as_ref() != Some(want) {
+                out.push(format!(
+                    "{field} is {}, expected {want}",
+                    got.as_deref().unwrap_or("unset")
+                ));
+            }
+        }
+    };
+    compare("algorithm", &expected.algorithm, &finding.algorithm);
+    compare(
+        "finding_type",
+        &expected.finding_type,
+        &finding.finding_type,
+    );
+    compare("operation", &expected.operation, &finding.operation);
+    compare("primitive", &expected.primitive, &finding.primitive);
+
+    if let Some(line) = expected.line {
+        if finding.line != line {
+            out.push(format!("line is {}, expected {line}", finding.line));
+        }
+    }
+    let mut names: Vec<&String> = expected.parameters.keys().collect();
+    names.sort();
+    for name in names {
+        let want = &expected.parameters[name];
+        match finding.parameters.get(name) {
+            Some(got) if got == want => {}
+            Some(got) => out.push(format!("{name} is {got}, expected {want}")),
+            None => out.push(format!("{name} is missing, expected {want}")),
+        }
+    }
+    out
+}
+
+fn describe(finding: &Finding) -> String {
+    format!("{} (line {})", finding.full_name, finding.line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"{
+        "classifications": {
+            "pbkdf2": {"algorithm": "PBKDF2", "findingType": "kdf", "operation": "keyderive"}
+        },
+        "mappings": {"hashlib": {"pbkdf2_hmac": "pbkdf2"}}
+    }"#;
+
+    fn test(source: &str, expect: serde_json::Value) -> RuleTest {
+        serde_json::from_value(serde_json::json!({
+            "name": "pbkdf2", "language": "python", "source": source, "expect": expect
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_expected_finding_passes_and_fails() {
+        let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+        let source = "import hashlib\nhashlib.pbkdf2_hmac(\"sha256\", pw, salt, 600000)\n";
+
+        let pass = test(
+            source,
+            serde_json::json!([{"function": "pbkdf2_hmac", "algorithm": "PBKDF2", "parameters": {"arg3": 600000}}]),
+        );
+        assert!(run_rule_test(&pass, Path::new("."), &classifier).passed());
+
+        let fail = test(
+            source,
+            serde_json::json!([{"function": "pbkdf2_hmac", "operation": "hash", "parameters": {"arg3": 1000}}]),
+        );
+        let result = run_rule_test(&fail, Path::new("."), &classifier);
+        assert_eq!(
+            result.failures,
+            vec!["hashlib.pbkdf2_hmac (line 2): operation is keyderive, expected hash, arg3 is 600000, expected 1000"]
+        );
+    }
+
+    #[test]
+    fn test_empty_expect_asserts_no_findings() {
+        let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+
+        let clean = test(
+            "import hashlib\nhashlib.sha256(b'x')\n",
+            serde_json::json!([]),
+        );
+        assert!(run_rule_test(&clean, Path::new("."), &classifier).passed());
+
+        let noisy = test(
+            "import hashlib\nhashlib.pbkdf2_hmac('sha1', pw, salt, 1)\n",
+            serde_json::json!([]),
+        );
+        let result = run_rule_test(&noisy, Path::new("."), &classifier);
+        assert_eq!(
+            result.failures,
+            vec!["expected no findings, found hashlib.pbkdf2_hmac (line 2)"]
+        );
+    }
+
+    #[test]
+    fn test_fixture_file_relative_to_rules() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("rules.yaml"),
+            r#"
+classifications:
+  pbkdf2: {algorithm: PBKDF2, findingType: kdf, operation: keyderive}
+mappings:
+  hashlib: {pbkdf2_hmac: pbkdf2}
+tests:
+  - name: from file
+    language: python
+    file: fixtures/kdf.py
+    expect:
+      - {function: pbkdf2_hmac, line: 2}
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir(temp.path().join("fixtures")).unwrap();
+        std::fs::write(
+            temp.path().join("fixtures/kdf.py"),
+            "import hashlib\nhashlib.pbkdf2_hmac('sha256', pw, salt, 600000)\n",
+        )
+        .unwrap();
+
+        let rules = temp.path().join("rules.yaml");
+        let classifier = RulesClassifier::from_file(&rules).unwrap();
+        let results = run_rule_tests(&rules, &classifier).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed(), "{:?}", results[0].failures);
+    }
+}
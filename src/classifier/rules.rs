@@ -1,7 +1,10 @@
-use super::Classification;
+use super::explain::{Explanation, LookupOutcome, LookupStep};
+use super::{refine_operation, Classification};
 use crate::error::ClassifierError;
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::scanner::fallback::{FallbackPattern, FallbackRule};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
 use tracing::{debug, trace};
@@ -35,14 +38,14 @@ pub trait Classifier: Send + Sync {
 
 #[derive(Debug, Deserialize)]
 struct ClassificationsFile {
-    #[allow(dead_code)]
     version: String,
     classifications: HashMap<String, Classification>,
+    #[serde(default)]
+    help_uris: HashMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct MappingsFile {
-    #[allow(dead_code)]
     version: String,
     #[allow(dead_code)]
     language: String,
@@ -51,6 +54,12 @@ struct MappingsFile {
     struct_fields: HashMap<String, HashMap<String, String>>,
     #[serde(default)]
     constants: HashMap<String, HashMap<String, ConstantValue>>,
+    #[serde(default)]
+    clients: Vec<String>,
+    #[serde(default)]
+    weak_algorithms: Vec<String>,
+    #[serde(default)]
+    non_crypto_imports: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -66,20 +75,78 @@ type ImportMap = HashMap<String, HashMap<String, String>>;
 type StructFieldMap = HashMap<String, HashMap<String, String>>;
 type ConstantsMap = HashMap<String, HashMap<String, ConstantValue>>;
 
+/// Namespace of rules loaded from presets.
+pub const BUNDLED_NAMESPACE: &str = "bundled";
+/// Namespace of rules given as a string rather than a file.
+const DEFAULT_NAMESPACE: &str = "user";
+/// Precedence of rules files that declare none, above the presets' 0.
+const DEFAULT_PRECEDENCE: i32 = 1;
+/// Mapped functions listed when a path maps others but not the one asked.
+const EXPLAIN_MAPPED_SHOWN: usize = 8;
+
+/// Where a set of rules came from. When two sources map the same function,
+/// the higher `precedence` wins; on a tie the one loaded later does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSource {
+    pub namespace: String,
+    pub precedence: i32,
+}
+
+/// Which rules a classifier was built from, recorded in report metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesInfo {
+    /// The `version`s declared by the preset files, sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<String>,
+    /// SHA-256 over the SHA-256 of each rules document, one hex digest per
+    /// line in load order, so any change to the rules or their layering
+    /// changes it.
+    pub sha256: String,
+    /// Rules documents loaded.
+    pub files: usize,
+}
+
 pub struct RulesClassifier {
-    classifications: HashMap<String, Classification>,
+    sources: Vec<RuleSource>,
+    /// Each key's classification per defining source, as indexes into
+    /// `sources`.
+    classifications: HashMap<String, Vec<(usize, Classification)>>,
     mappings: ImportMap,
+    /// The source of each entry in `mappings`.
+    mapping_sources: HashMap<String, HashMap<String, usize>>,
     struct_fields: StructFieldMap,
     constants: ConstantsMap,
+    clients: Vec<String>,
+    /// Algorithm names whose mention alone makes a file worth scanning.
+    weak_algorithms: Vec<String>,
+    /// Mapped imports that alone do not make a file worth scanning.
+    non_crypto_imports: Vec<String>,
+    /// Guidance page of each weakness rule, e.g. `weak_hash`.
+    help_uris: HashMap<String, String>,
+    /// Patterns matched against files no grammar parses, with
+    /// `--fallback-scan`.
+    fallback_rules: Vec<FallbackRule>,
+    /// SHA-256 of each rules document loaded, in load order.
+    loaded_digests: Vec<String>,
+    versions: BTreeSet<String>,
 }
 
 impl RulesClassifier {
     pub fn new() -> Self {
         Self {
+            sources: Vec::new(),
             classifications: HashMap::new(),
             mappings: HashMap::new(),
+            mapping_sources: HashMap::new(),
             struct_fields: HashMap::new(),
             constants: HashMap::new(),
+            clients: Vec::new(),
+            weak_algorithms: Vec::new(),
+            non_crypto_imports: Vec::new(),
+            help_uris: HashMap::new(),
+            fallback_rules: Vec::new(),
+            loaded_digests: Vec::new(),
+            versions: BTreeSet::new(),
         }
     }
 
@@ -92,9 +159,15 @@ impl RulesClassifier {
 
         let file: ClassificationsFile = serde_json::from_str(&content)
             .map_err(|e| ClassifierError::rules_parse_error(path, e.to_string()))?;
+        self.record_loaded(&content, Some(file.version));
 
-        self.classifications = file.classifications;
-        debug!(count = self.classifications.len(), "loaded classifications");
+        let source = self.source(BUNDLED_NAMESPACE, 0);
+        let count = file.classifications.len();
+        for (key, classification) in file.classifications {
+            self.insert_classification(source, key, classification);
+        }
+        self.help_uris.extend(file.help_uris);
+        debug!(count, "loaded classifications");
         Ok(())
     }
 
@@ -107,26 +180,22 @@ impl RulesClassifier {
 
         let file: MappingsFile = serde_json::from_str(&content)
             .map_err(|e| ClassifierError::rules_parse_error(path, e.to_string()))?;
+        self.record_loaded(&content, Some(file.version.clone()));
 
         // Parse nested mappings format: { "import_path": { "function": "key" } }
+        let source = self.source(BUNDLED_NAMESPACE, 0);
         let mut count = 0;
         for (import_path, functions) in file.mappings {
-            let import_lower = import_path.to_lowercase();
-            let entry = self.mappings.entry(import_lower).or_default();
             for (func, key) in functions {
-                entry.insert(func.to_lowercase(), key);
+                self.insert_mapping(source, &import_path, &func, key);
                 count += 1;
             }
         }
 
-        // Load struct field mappings
-        for (struct_type, fields) in file.struct_fields {
-            let type_lower = struct_type.to_lowercase();
-            let entry = self.struct_fields.entry(type_lower).or_default();
-            for (field, key) in fields {
-                entry.insert(field.to_lowercase(), key);
-            }
-        }
+        self.merge_struct_fields(file.struct_fields);
+        self.merge_clients(file.clients);
+        merge_tokens(&mut self.weak_algorithms, file.weak_algorithms);
+        merge_tokens(&mut self.non_crypto_imports, file.non_crypto_imports);
 
         // Load constant values
         for (package, constants) in file.constants {
@@ -141,6 +210,28 @@ impl RulesClassifier {
         Ok(())
     }
 
+    fn merge_struct_fields(&mut self, struct_fields: StructFieldMap) {
+        for (struct_type, fields) in struct_fields {
+            // Generic structs are matched without their type parameters
+            // (`vault.Options[T]` as `vault.Options`)
+            let struct_type = struct_type.split('[').next().unwrap_or(&struct_type);
+            let type_lower = struct_type.trim().to_lowercase();
+            let entry = self.struct_fields.entry(type_lower).or_default();
+            for (field, key) in fields {
+                entry.insert(field.to_lowercase(), key);
+            }
+        }
+    }
+
+    fn merge_clients(&mut self, clients: Vec<String>) {
+        for client in clients {
+            let client = client.to_lowercase();
+            if !self.clients.contains(&client) {
+                self.clients.push(client);
+            }
+        }
+    }
+
     pub fn load_user_rules<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ClassifierError> {
         let path = path.as_ref();
         debug!(path = %path.display(), "loading user rules");
@@ -150,48 +241,253 @@ impl RulesClassifier {
 
         let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
+        // Rules files without a namespace are named after the file.
+        let namespace = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(DEFAULT_NAMESPACE);
+
         match extension {
-            "json" => self.parse_user_rules_json(&content),
-            "yaml" | "yml" => self.parse_user_rules_yaml(&content),
+            "json" => self.parse_user_rules_json(&content, namespace),
+            "yaml" | "yml" => self.parse_user_rules_yaml(&content, namespace),
             _ => Err(ClassifierError::unsupported_format(extension)),
         }
     }
 
-    fn parse_user_rules_json(&mut self, content: &str) -> Result<(), ClassifierError> {
+    fn parse_user_rules_json(
+        &mut self,
+        content: &str,
+        namespace: &str,
+    ) -> Result<(), ClassifierError> {
         let rules: UserRulesFile =
             serde_json::from_str(content).map_err(|e| ClassifierError::RulesParseError {
                 path: "user_rules".into(),
                 message: e.to_string(),
             })?;
-        self.merge_user_rules(rules);
-        Ok(())
+        self.record_loaded(content, None);
+        self.merge_user_rules(rules, namespace)
     }
 
-    fn parse_user_rules_yaml(&mut self, content: &str) -> Result<(), ClassifierError> {
+    fn parse_user_rules_yaml(
+        &mut self,
+        content: &str,
+        namespace: &str,
+    ) -> Result<(), ClassifierError> {
         let rules: UserRulesFile =
             serde_yaml::from_str(content).map_err(|e| ClassifierError::RulesParseError {
                 path: "user_rules".into(),
                 message: e.to_string(),
             })?;
-        self.merge_user_rules(rules);
-        Ok(())
+        self.record_loaded(content, None);
+        self.merge_user_rules(rules, namespace)
+    }
+
+    fn record_loaded(&mut self, content: &str, version: Option<String>) {
+        self.loaded_digests
+            .push(format!("{:x}", Sha256::digest(content.as_bytes())));
+        self.versions.extend(version.filter(|v| !v.is_empty()));
     }
 
-    fn merge_user_rules(&mut self, rules: UserRulesFile) {
+    /// The rules this classifier was built from, or `None` when it has none.
+    pub fn rules_info(&self) -> Option<RulesInfo> {
+        if self.loaded_digests.is_empty() {
+            return None;
+        }
+        let manifest: String = self
+            .loaded_digests
+            .iter()
+            .map(|digest| format!("{digest}\n"))
+            .collect();
+        Some(RulesInfo {
+            versions: self.versions.iter().cloned().collect(),
+            sha256: format!("{:x}", Sha256::digest(manifest.as_bytes())),
+            files: self.loaded_digests.len(),
+        })
+    }
+
+    fn merge_user_rules(
+        &mut self,
+        rules: UserRulesFile,
+        namespace: &str,
+    ) -> Result<(), ClassifierError> {
+        let source = self.source(
+            rules.namespace.as_deref().unwrap_or(namespace),
+            rules.precedence.unwrap_or(DEFAULT_PRECEDENCE),
+        );
         if let Some(classifications) = rules.classifications {
             for (key, classification) in classifications {
-                self.classifications.insert(key, classification);
+                self.insert_classification(source, key, classification);
             }
         }
         if let Some(mappings) = rules.mappings {
             for (import_path, functions) in mappings {
-                let import_lower = import_path.to_lowercase();
-                let entry = self.mappings.entry(import_lower).or_default();
                 for (func, key) in functions {
-                    entry.insert(func.to_lowercase(), key);
+                    self.insert_mapping(source, &import_path, &func, key);
+                }
+            }
+        }
+        if let Some(struct_fields) = rules.struct_fields {
+            self.merge_struct_fields(struct_fields);
+        }
+        if let Some(clients) = rules.clients {
+            self.merge_clients(clients);
+        }
+        merge_tokens(&mut self.weak_algorithms, rules.weak_algorithms);
+        merge_tokens(&mut self.non_crypto_imports, rules.non_crypto_imports);
+        self.help_uris.extend(rules.help_uris);
+        for pattern in rules.fallback_patterns {
+            let rule =
+                FallbackRule::compile(pattern).map_err(|e| ClassifierError::InvalidSchema {
+                    message: format!("invalid fallback pattern: {e}"),
+                })?;
+            self.fallback_rules.push(rule);
+        }
+        Ok(())
+    }
+
+    /// Index of the source `namespace`, added with `precedence` if new.
+    fn source(&mut self, namespace: &str, precedence: i32) -> usize {
+        if let Some(index) = self.sources.iter().position(|s| s.namespace == namespace) {
+            return index;
+        }
+        self.sources.push(RuleSource {
+            namespace: namespace.to_string(),
+            precedence,
+        });
+        self.sources.len() - 1
+    }
+
+    fn insert_classification(
+        &mut self,
+        source: usize,
+        key: String,
+        classification: Classification,
+    ) {
+        let defined = self.classifications.entry(key).or_default();
+        match defined.iter_mut().find(|(s, _)| *s == source) {
+            Some(entry) => entry.1 = classification,
+            None => defined.push((source, classification)),
+        }
+    }
+
+    /// Maps `function` to `key` for `source`, unless a source with higher
+    /// precedence already maps it.
+    fn insert_mapping(&mut self, source: usize, import_path: &str, function: &str, key: String) {
+        let import_lower = import_path.to_lowercase();
+        let func_lower = function.to_lowercase();
+        let sources = self
+            .mapping_sources
+            .entry(import_lower.clone())
+            .or_default();
+        if let Some(&existing) = sources.get(&func_lower) {
+            if self.sources[existing].precedence > self.sources[source].precedence {
+                debug!(
+                    import_path,
+                    function,
+                    kept = %self.sources[existing].namespace,
+                    ignored = %self.sources[source].namespace,
+                    "mapping overridden by a source with lower precedence"
+                );
+                return;
+            }
+        }
+        sources.insert(func_lower.clone(), source);
+        self.mappings
+            .entry(import_lower)
+            .or_default()
+            .insert(func_lower, key);
+    }
+
+    /// The classification `key` as seen by mappings from `source`: its own
+    /// definition, or else the one from the source with the highest
+    /// precedence.
+    fn classification_for(&self, key: &str, source: usize) -> Option<&Classification> {
+        let defined = self.classifications.get(key)?;
+        defined
+            .iter()
+            .find(|(s, _)| *s == source)
+            .or_else(|| {
+                defined
+                    .iter()
+                    .max_by_key(|(s, _)| (self.sources[*s].precedence, *s))
+            })
+            .map(|(_, classification)| classification)
+    }
+
+    fn mapping(&self, import_path: &str, function: &str) -> Option<(&str, usize)> {
+        let import_lower = import_path.to_lowercase();
+        let func_lower = function.to_lowercase();
+        let key = self.mappings.get(&import_lower)?.get(&func_lower)?;
+        let source = *self.mapping_sources.get(&import_lower)?.get(&func_lower)?;
+        Some((key, source))
+    }
+
+    /// The source whose mapping classifies `function`, looked up like
+    /// [`Classifier::lookup_with_fallback`].
+    pub fn rule_source(
+        &self,
+        import_path: Option<&str>,
+        package: &str,
+        function: &str,
+    ) -> Option<&RuleSource> {
+        import_path.into_iter().chain([package]).find_map(|path| {
+            let (key, source) = self.mapping(path, function)?;
+            self.classification_for(key, source)?;
+            Some(&self.sources[source])
+        })
+    }
+
+    /// Each lookup [`super::classify_call`] makes for `function`, with what
+    /// it found.
+    pub fn explain(&self, import_path: Option<&str>, package: &str, function: &str) -> Explanation {
+        let mut steps = Vec::new();
+        let mut classification = Classification::unclassified();
+        for path in import_path.into_iter().chain([package]) {
+            if path.is_empty() || steps.iter().any(|s: &LookupStep| s.path == path) {
+                continue;
+            }
+            let outcome = match self.mapping(path, function) {
+                Some((key, source)) => {
+                    let rule_source = self.sources[source].clone();
+                    match self.classification_for(key, source) {
+                        Some(found) => {
+                            classification = found.clone();
+                            LookupOutcome::Matched {
+                                key: key.to_string(),
+                                source: rule_source,
+                            }
+                        }
+                        None => LookupOutcome::UndefinedClassification {
+                            key: key.to_string(),
+                            source: rule_source,
+                        },
+                    }
                 }
+                None => match self.mappings.get(&path.to_lowercase()) {
+                    Some(functions) => {
+                        let mut mapped: Vec<String> = functions.keys().cloned().collect();
+                        mapped.sort();
+                        mapped.truncate(EXPLAIN_MAPPED_SHOWN);
+                        LookupOutcome::UnmappedFunction { mapped }
+                    }
+                    None => LookupOutcome::UnknownPath,
+                },
+            };
+            let matched = matches!(outcome, LookupOutcome::Matched { .. });
+            steps.push(LookupStep {
+                path: path.to_string(),
+                outcome,
+            });
+            if matched {
+                break;
             }
         }
+        let refined_operation = refine_operation(&classification.operation, function);
+        Explanation {
+            steps,
+            classification,
+            refined_operation,
+        }
     }
 
     pub fn from_bundled() -> Result<Self, ClassifierError> {
@@ -203,27 +499,48 @@ impl RulesClassifier {
     }
 
     pub fn from_preset_path(preset_dir: &Path) -> Result<Self, ClassifierError> {
-        debug!(path = %preset_dir.display(), "loading classifier rules from preset");
         let mut classifier = Self::new();
+        classifier.load_preset(preset_dir)?;
+        Ok(classifier)
+    }
+
+    /// Adds a preset's classifications and mappings, in the
+    /// [`BUNDLED_NAMESPACE`].
+    pub fn load_preset(&mut self, preset_dir: &Path) -> Result<(), ClassifierError> {
+        debug!(path = %preset_dir.display(), "loading classifier rules from preset");
 
         let classifications_path = preset_dir.join("classifications.json");
         if classifications_path.exists() {
-            classifier.load_classifications(&classifications_path)?;
+            self.load_classifications(&classifications_path)?;
         }
 
-        for lang in &["go", "python", "rust", "javascript"] {
+        for lang in &[
+            "go",
+            "python",
+            "rust",
+            "javascript",
+            "java",
+            "kotlin",
+            "scala",
+            "c",
+            "cpp",
+            "csharp",
+            "ruby",
+            "php",
+            "swift",
+        ] {
             let mappings_path = preset_dir.join(lang).join("mappings.json");
             if mappings_path.exists() {
-                classifier.load_mappings(&mappings_path)?;
+                self.load_mappings(&mappings_path)?;
             }
         }
 
         debug!(
-            classifications = classifier.classification_count(),
-            mappings = classifier.mapping_count(),
+            classifications = self.classification_count(),
+            mappings = self.mapping_count(),
             "preset rules loaded"
         );
-        Ok(classifier)
+        Ok(())
     }
 
     pub fn from_file(path: &Path) -> Result<Self, ClassifierError> {
@@ -233,6 +550,14 @@ impl RulesClassifier {
         Ok(classifier)
     }
 
+    /// Builds a classifier from user rules given as a JSON string, for
+    /// callers without filesystem access.
+    pub fn from_json_str(content: &str) -> Result<Self, ClassifierError> {
+        let mut classifier = Self::new();
+        classifier.parse_user_rules_json(content, DEFAULT_NAMESPACE)?;
+        Ok(classifier)
+    }
+
     pub fn from_bundled_for_language(language: &str) -> Result<Self, ClassifierError> {
         debug!(language, "loading bundled classifier rules for language");
         let mut classifier = Self::new();
@@ -270,6 +595,34 @@ impl RulesClassifier {
         &self.struct_fields
     }
 
+    /// Modules whose functions are called as methods on client objects,
+    /// see [`crate::scanner::Scanner::with_clients`].
+    pub fn get_clients(&self) -> &[String] {
+        &self.clients
+    }
+
+    /// Algorithm names, lowercase, that make the import filters scan a
+    /// file even without a mapped import, e.g. `des`, `rc4`, `md5`.
+    pub fn get_weak_algorithms(&self) -> &[String] {
+        &self.weak_algorithms
+    }
+
+    /// The guidance page the rules give for the weakness rule `rule`.
+    pub fn help_uri(&self, rule: &str) -> Option<&str> {
+        self.help_uris.get(rule).map(String::as_str)
+    }
+
+    /// Patterns of the rules files for `--fallback-scan`, in load order.
+    pub fn fallback_rules(&self) -> &[FallbackRule] {
+        &self.fallback_rules
+    }
+
+    /// Mapped imports, lowercase, that the import filters do not count,
+    /// e.g. `base64` or `hash/crc32`.
+    pub fn get_non_crypto_imports(&self) -> &[String] {
+        &self.non_crypto_imports
+    }
+
     pub fn get_constants(&self) -> &ConstantsMap {
         &self.constants
     }
@@ -305,25 +658,44 @@ impl Default for RulesClassifier {
 
 impl Classifier for RulesClassifier {
     fn lookup(&self, import_path: &str, function: &str) -> Classification {
-        let import_lower = import_path.to_lowercase();
-        let func_lower = function.to_lowercase();
+        self.mapping(import_path, function)
+            .and_then(|(key, source)| self.classification_for(key, source))
+            .cloned()
+            .unwrap_or_else(Classification::unclassified)
+    }
+}
 
-        if let Some(functions) = self.mappings.get(&import_lower) {
-            if let Some(key) = functions.get(&func_lower) {
-                if let Some(classification) = self.classifications.get(key) {
-                    return classification.clone();
-                }
-            }
+/// Adds `tokens`, lowercased, to `list` if not present.
+fn merge_tokens(list: &mut Vec<String>, tokens: Vec<String>) {
+    for token in tokens {
+        let token = token.to_lowercase();
+        if !list.contains(&token) {
+            list.push(token);
         }
-
-        Classification::unclassified()
     }
 }
 
 #[derive(Debug, Deserialize)]
 struct UserRulesFile {
+    /// Name recorded on findings from these rules; defaults to the file
+    /// name.
+    namespace: Option<String>,
+    precedence: Option<i32>,
     classifications: Option<HashMap<String, Classification>>,
     mappings: Option<HashMap<String, HashMap<String, String>>>,
+    struct_fields: Option<StructFieldMap>,
+    clients: Option<Vec<String>>,
+    #[serde(default)]
+    weak_algorithms: Vec<String>,
+    #[serde(default)]
+    non_crypto_imports: Vec<String>,
+    /// Guidance page of each weakness rule; a later rules file overrides
+    /// an earlier one.
+    #[serde(default)]
+    help_uris: HashMap<String, String>,
+    /// Patterns matched line by line against files no grammar parses.
+    #[serde(default)]
+    fallback_patterns: Vec<FallbackPattern>,
 }
 
 #[cfg(test)]
@@ -337,6 +709,132 @@ mod tests {
         assert_eq!(classifier.mapping_count(), 0);
     }
 
+    #[test]
+    fn test_help_uris() {
+        let mut classifier = RulesClassifier::from_json_str(
+            r#"{
+                "mappings": {"crypto/md5": {"Sum": "md5"}},
+                "classifications": {"md5": {
+                    "algorithm": "MD5", "findingType": "hash", "operation": "hash",
+                    "helpUri": "https://wiki.example.com/crypto/md5"
+                }},
+                "help_uris": {
+                    "weak_hash": "https://wiki.example.com/crypto/weak-hash",
+                    "jwt_alg_none": "https://wiki.example.com/crypto/jwt"
+                }
+            }"#,
+        )
+        .unwrap();
+        classifier
+            .parse_user_rules_yaml(
+                "help_uris:\n  jwt_alg_none: https://security.example.com/jwt\n",
+                "team",
+            )
+            .unwrap();
+
+        assert_eq!(
+            classifier.lookup("crypto/md5", "Sum").help_uri.as_deref(),
+            Some("https://wiki.example.com/crypto/md5")
+        );
+        assert_eq!(
+            classifier.help_uri("weak_hash"),
+            Some("https://wiki.example.com/crypto/weak-hash")
+        );
+        assert_eq!(
+            classifier.help_uri("jwt_alg_none"),
+            Some("https://security.example.com/jwt")
+        );
+        assert_eq!(classifier.help_uri("weak_cipher"), None);
+    }
+
+    #[test]
+    fn test_fallback_patterns() {
+        let mut classifier = RulesClassifier::new();
+        classifier
+            .parse_user_rules_yaml(
+                "fallback_patterns:\n  - name: perl_md5\n    pattern: 'Digest::MD5'\n    algorithm: MD5\n",
+                "team",
+            )
+            .unwrap();
+        assert_eq!(classifier.fallback_rules().len(), 1);
+        assert_eq!(classifier.fallback_rules()[0].pattern.name, "perl_md5");
+
+        let err = classifier
+            .parse_user_rules_json(r#"{"fallback_patterns": [{"pattern": "md5("}]}"#, "team")
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid fallback pattern"));
+    }
+
+    #[test]
+    fn test_user_rules_struct_fields() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{"struct_fields": {"*.put_object": {"ServerSideEncryption": "storage_sse"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            classifier.lookup_struct_field("*.put_object", "serversideencryption"),
+            Some("storage_sse")
+        );
+    }
+
+    #[test]
+    fn test_generic_struct_fields_drop_type_parameters() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{"struct_fields": {"vault.Options[T]": {"Cipher": "vault_cipher"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            classifier.lookup_struct_field("vault.options", "cipher"),
+            Some("vault_cipher")
+        );
+    }
+
+    #[test]
+    fn test_rule_sources_layer_by_precedence() {
+        let mut classifier = RulesClassifier::new();
+        classifier
+            .parse_user_rules_json(
+                r#"{
+                    "namespace": "org", "precedence": 10,
+                    "classifications": {"weak_hash": {"algorithm": "MD5", "findingType": "hash", "operation": "hash"}},
+                    "mappings": {"hashlib": {"md5": "weak_hash"}}
+                }"#,
+                "org-rules",
+            )
+            .unwrap();
+        classifier
+            .parse_user_rules_json(
+                r#"{
+                    "classifications": {"weak_hash": {"algorithm": "SHA-1", "findingType": "hash", "operation": "hash"}},
+                    "mappings": {"hashlib": {"md5": "weak_hash", "sha1": "weak_hash"}}
+                }"#,
+                "team",
+            )
+            .unwrap();
+
+        // The org mapping outranks the team's, and keeps its own
+        // classification of the shared key.
+        assert_eq!(
+            classifier.lookup("hashlib", "md5").algorithm.as_deref(),
+            Some("MD5")
+        );
+        assert_eq!(
+            classifier.rule_source(Some("hashlib"), "hashlib", "md5"),
+            Some(&RuleSource {
+                namespace: "org".to_string(),
+                precedence: 10
+            })
+        );
+
+        assert_eq!(
+            classifier.lookup("hashlib", "sha1").algorithm.as_deref(),
+            Some("SHA-1")
+        );
+        let team = classifier.rule_source(None, "hashlib", "sha1").unwrap();
+        assert_eq!(team.namespace, "team");
+        assert_eq!(team.precedence, DEFAULT_PRECEDENCE);
+    }
+
     #[test]
     fn test_lookup_unclassified() {
         let classifier = RulesClassifier::new();
@@ -447,4 +945,38 @@ mod tests {
         assert!(!result2.is_unclassified());
         assert!(!result3.is_unclassified());
     }
+
+    #[test]
+    fn test_explain_lists_lookups() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {"weak_hash": {"algorithm": "MD5", "findingType": "hash", "operation": "hash"}},
+                "mappings": {"hashlib": {"md5": "weak_hash", "sha1": "sha1_hash"}}
+            }"#,
+        )
+        .unwrap();
+
+        let explanation = classifier.explain(Some("Crypto.Hash"), "hashlib", "md5");
+        assert_eq!(explanation.steps[0].outcome, LookupOutcome::UnknownPath);
+        assert!(matches!(
+            &explanation.steps[1].outcome,
+            LookupOutcome::Matched { key, .. } if key == "weak_hash"
+        ));
+        assert_eq!(explanation.classification.algorithm.as_deref(), Some("MD5"));
+
+        let explanation = classifier.explain(None, "hashlib", "sha256");
+        assert_eq!(
+            explanation.steps[0].outcome,
+            LookupOutcome::UnmappedFunction {
+                mapped: vec!["md5".to_string(), "sha1".to_string()]
+            }
+        );
+        assert!(!explanation.is_classified());
+
+        let explanation = classifier.explain(None, "hashlib", "sha1");
+        assert!(matches!(
+            &explanation.steps[0].outcome,
+            LookupOutcome::UndefinedClassification { key, .. } if key == "sha1_hash"
+        ));
+    }
 }
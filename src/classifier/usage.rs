@@ -0,0 +1,203 @@
+//! What a hash call is used for.
+//!
+//! The weight of a weak hash depends on its use: SHA-1 as a cache key is
+//! fine, as a password hash or in a signature it is not. [`hash_usage`]
+//! reads the names around the call ([`CallSite`]) for that use. A name the
+//! result is passed to is the strongest signal, argument and variable names
+//! are weaker.
+
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::CallSite;
+use crate::utils::identifier_words;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashUsage {
+    /// Integrity checks, checksums, cache keys and other fingerprints.
+    Checksum,
+    Signature,
+    Password,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageContext {
+    pub usage: HashUsage,
+    pub confidence: Confidence,
+    /// The name the usage was read from, e.g. `passed to SignPKCS1v15`.
+    pub reason: String,
+}
+
+impl HashUsage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Checksum => "checksum",
+            Self::Signature => "signature",
+            Self::Password => "password",
+        }
+    }
+}
+
+impl Confidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+const PASSWORD_WORDS: &[&str] = &[
+    "password",
+    "passwd",
+    "pwd",
+    "passphrase",
+    "pass",
+    "credential",
+    "credentials",
+];
+const CHECKSUM_WORDS: &[&str] = &[
+    "checksum",
+    "integrity",
+    "etag",
+    "fingerprint",
+    "crc",
+    "dedup",
+    "cache",
+];
+const SIGNATURE_WORDS: &[&str] = &["sign", "signer", "signature", "signed", "signing", "verify"];
+/// Argument names that suggest file or message content being checksummed.
+const CONTENT_WORDS: &[&str] = &[
+    "file", "contents", "content", "body", "payload", "chunk", "blob",
+];
+
+/// The usage of a hash call at `site`, if any name around it tells.
+pub fn hash_usage(site: &CallSite) -> Option<UsageContext> {
+    let mut found: Vec<UsageContext> = Vec::new();
+    let mut add = |usage, confidence, reason: String| {
+        found.push(UsageContext {
+            usage,
+            confidence,
+            reason,
+        })
+    };
+
+    for consumer in &site.consumers {
+        if let Some(usage) = usage_of(consumer) {
+            let confidence = match usage {
+                HashUsage::Checksum => Confidence::Medium,
+                _ => Confidence::High,
+            };
+            add(usage, confidence, format!("passed to {consumer}"));
+        }
+    }
+    for name in &site.argument_names {
+        match usage_of(name) {
+            Some(HashUsage::Password) => add(
+                HashUsage::Password,
+                Confidence::Medium,
+                format!("argument {name}"),
+            ),
+            _ if has_word(name, CONTENT_WORDS) => add(
+                HashUsage::Checksum,
+                Confidence::Low,
+                format!("argument {name}"),
+            ),
+            _ => {}
+        }
+    }
+    if let Some(function) = &site.enclosing_function {
+        if let Some(usage) = usage_of(function) {
+            add(usage, Confidence::Medium, format!("in {function}"));
+        }
+    }
+    if let Some(variable) = &site.assigned_to {
+        if let Some(usage) = usage_of(variable) {
+            add(usage, Confidence::Medium, format!("assigned to {variable}"));
+        }
+    }
+
+    // Highest confidence first; on a tie the use where a weak hash matters
+    // most.
+    found
+        .into_iter()
+        .max_by_key(|context| (context.confidence, context.usage))
+}
+
+/// The usage one name points to. A name mentioning both a password and a
+/// checksum is about the password.
+fn usage_of(name: &str) -> Option<HashUsage> {
+    [
+        (HashUsage::Password, PASSWORD_WORDS),
+        (HashUsage::Checksum, CHECKSUM_WORDS),
+        (HashUsage::Signature, SIGNATURE_WORDS),
+    ]
+    .into_iter()
+    .find(|(_, words)| has_word(name, words))
+    .map(|(usage, _)| usage)
+}
+
+fn has_word(name: &str, words: &[&str]) -> bool {
+    identifier_words(name).any(|word| words.contains(&word.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(
+        function: Option<&str>,
+        assigned_to: Option<&str>,
+        arguments: &[&str],
+        consumers: &[&str],
+    ) -> CallSite {
+        CallSite {
+            enclosing_function: function.map(str::to_string),
+            assigned_to: assigned_to.map(str::to_string),
+            argument_names: arguments.iter().map(|s| s.to_string()).collect(),
+            consumers: consumers.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_consumer_outweighs_names() {
+        let usage = hash_usage(&site(
+            Some("verifyChecksum"),
+            Some("hashed"),
+            &["msg"],
+            &["SignPKCS1v15"],
+        ))
+        .unwrap();
+        assert_eq!(usage.usage, HashUsage::Signature);
+        assert_eq!(usage.confidence, Confidence::High);
+        assert_eq!(usage.reason, "passed to SignPKCS1v15");
+    }
+
+    #[test]
+    fn test_password_names() {
+        let usage = hash_usage(&site(Some("login"), None, &["user", "password"], &[])).unwrap();
+        assert_eq!(usage.usage, HashUsage::Password);
+        assert_eq!(usage.confidence, Confidence::Medium);
+
+        let usage = hash_usage(&site(Some("fileChecksum"), None, &["f"], &[])).unwrap();
+        assert_eq!(usage.usage, HashUsage::Checksum);
+        assert_eq!(usage.reason, "in fileChecksum");
+    }
+
+    #[test]
+    fn test_no_hint() {
+        assert_eq!(
+            hash_usage(&site(Some("main"), Some("h"), &["data"], &["Println"])),
+            None
+        );
+    }
+}
@@ -1,11 +1,40 @@
 use anyhow::{Context as AnyhowContext, Result};
-use clap::{Parser, ValueEnum};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "discovery")]
+use crate::discovery::archive::ArchiveKind;
+#[cfg(feature = "discovery")]
+use crate::discovery::extensions::{self, ExtensionOverride};
+use crate::engine::ResolverConfig;
+use crate::logging::LogFilter;
+use crate::output::SigningKey;
+use crate::utils::{DepsBudget, Shard};
+
+/// Seconds a package-manager command may run unless `--tool-timeout` says
+/// otherwise.
+pub const DEFAULT_TOOL_TIMEOUT_SECS: u64 = 120;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Json,
     Cbom,
+    /// One `path:line:col: severity: message [rule]` line per finding
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompareFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CoverageFormat {
+    Text,
+    Json,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
@@ -15,23 +44,56 @@ pub enum Language {
     Rust,
     Javascript,
     Typescript,
+    Java,
+    C,
+    Cpp,
+    #[value(name = "csharp")]
+    CSharp,
+    Ruby,
+    Php,
+    Swift,
 }
 
 #[derive(Parser, Debug)]
-#[command(name = "argflow")]
+#[command(name = "argflow", version)]
 #[command(about = "Argument flow analyzer - trace where function arguments come from", long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Path to file or directory to analyze
-    #[arg(long, value_name = "PATH")]
-    pub path: PathBuf,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to file or directory to analyze, or an archive (zip, jar, wheel,
+    /// tarball) to unpack and analyze. Several paths give one report with a
+    /// summary per path, each in its own detected language.
+    #[arg(
+        long,
+        value_name = "PATH",
+        required_unless_present = "git_url",
+        num_args = 1..
+    )]
+    pub path: Vec<PathBuf>,
+
+    /// Git repository to fetch and analyze instead of a --path, e.g.
+    /// https://github.com/org/repo. Only the one commit is fetched, into a
+    /// temporary directory removed after the scan
+    #[arg(long, value_name = "URL", conflicts_with = "path")]
+    pub git_url: Option<String>,
+
+    /// Branch, tag or commit of --git-url to analyze (default: the
+    /// remote's HEAD)
+    #[arg(long = "ref", value_name = "REF", requires = "git_url")]
+    pub git_ref: Option<String>,
 
     /// Preset to use (e.g., crypto, tls). Can be specified multiple times.
     #[arg(long, value_name = "PRESET")]
     pub preset: Vec<String>,
 
-    /// Custom rules file (JSON format)
+    /// Custom rules file (JSON or YAML). Can be specified multiple times;
+    /// files are layered over the presets by their `precedence`. Required
+    /// for java, kotlin, scala, c, cpp, csharp, ruby, php and swift, which
+    /// the bundled crypto preset has no mappings for.
     #[arg(long, value_name = "FILE")]
-    pub rules: Option<PathBuf>,
+    pub rules: Vec<PathBuf>,
 
     /// Output file path (prints to stdout if not specified)
     #[arg(short = 'O', long, value_name = "FILE")]
@@ -49,34 +111,656 @@ pub struct Args {
     #[arg(long)]
     pub include_deps: bool,
 
+    /// Most dependency files and bytes --include-deps scans in full (e.g.
+    /// 5000, 512M, or `unlimited`); over it, only dependencies that look
+    /// crypto-related are scanned and the rest are reported as skipped
+    #[arg(long, value_name = "FILES,SIZE", default_value_t = DepsBudget::default())]
+    pub deps_budget: DepsBudget,
+
+    /// Seconds to wait for each package-manager command (go list, cargo
+    /// metadata, pip list) before falling back to reading the filesystem
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_TOOL_TIMEOUT_SECS)]
+    pub tool_timeout: u64,
+
+    /// Never access the network: package managers may only read what is
+    /// already installed, and options that need a service are rejected
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Python environment whose installed packages --include-deps scans:
+    /// a virtualenv or its site-packages (default: $VIRTUAL_ENV, else a
+    /// virtualenv in the project)
+    #[arg(long, value_name = "PATH")]
+    pub python_env: Option<PathBuf>,
+
+    /// Also scan compiled artifacts (ELF, Mach-O, wasm, jars, .pyc) for
+    /// embedded crypto indicators; reported as low-confidence findings
+    #[arg(long)]
+    pub scan_binaries: bool,
+
+    /// Also match the `fallback_patterns` of the rules files against files
+    /// no grammar parses (Perl, COBOL copybooks, ...); reported as
+    /// low-confidence findings
+    #[arg(long)]
+    pub fallback_scan: bool,
+
+    /// Add a password_storage section checking password hashing cost
+    /// factors (PBKDF2, bcrypt, scrypt, Argon2) against the policy minimums
+    #[arg(long)]
+    pub password_policy: bool,
+
+    /// Write edits fixing some findings to FILE as a unified diff, and list
+    /// them under `fixes` in the report: PBKDF2 iteration counts below the
+    /// password policy, Go sha1.New, Go tls.Config below TLS 1.2
+    #[arg(long, value_name = "FILE")]
+    pub suggest_fixes: Option<PathBuf>,
+
+    /// Add an agility section telling whether crypto calls go through
+    /// internal wrapper modules, listing the direct calls outside them
+    #[arg(long)]
+    pub agility: bool,
+
+    /// Add a hotspots section ranking files and functions by the density
+    /// and algorithm diversity of their crypto calls
+    #[arg(long)]
+    pub hotspots: bool,
+
+    /// Record in the report metadata how long each phase of the scan took,
+    /// in total and per language
+    #[arg(long)]
+    pub timings: bool,
+
+    /// List every file found but not scanned under `skipped_files` in the
+    /// report, with the reason: not crypto-related, excluded, too large,
+    /// unreadable, unparsable or in an unsupported language
+    #[arg(long)]
+    pub audit_coverage: bool,
+
+    /// Config file (JSON or YAML)
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Print the effective resolver configuration before scanning
+    #[arg(long)]
+    pub explain: bool,
+
+    #[command(flatten)]
+    pub resolver: ResolverArgs,
+
+    /// Approximate memory budget for retained results (e.g. 512M, 2G);
+    /// results beyond it are spilled to a temp file
+    #[arg(long, value_name = "SIZE", value_parser = crate::output::parse_byte_size)]
+    pub max_memory: Option<usize>,
+
+    /// List at most N findings in the report; the rest are counted by type,
+    /// algorithm and weakness under `overflow`, and the totals, weaknesses
+    /// and risk score still cover every finding
+    #[arg(long, value_name = "N")]
+    pub max_findings: Option<usize>,
+
+    /// Workers scanning the project and each dependency in parallel
+    /// (defaults to the number of CPUs)
+    #[arg(short = 'j', long, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Only scan shard INDEX of COUNT (e.g. 2/4), partitioning discovered files
+    /// by a stable hash of their path; combine the reports with `merge`
+    #[arg(long, value_name = "INDEX/COUNT")]
+    pub shard: Option<Shard>,
+
+    /// Sign an attestation for the report with cosign: `keyless` for Sigstore
+    /// keyless signing, or a key reference (file path or KMS URI). Requires -O
+    #[arg(long, value_name = "KEY")]
+    pub sign: Option<SigningKey>,
+
+    /// Save the scanned file, anonymized, with its report as a regression
+    /// case in DIR; see `replay`
+    #[arg(long, value_name = "DIR")]
+    pub record: Option<PathBuf>,
+
+    /// Warnings of each kind (e.g. unreadable files) to log before the rest
+    /// are only counted in a summary line; all are listed in the report's
+    /// `errors`
+    #[arg(long, value_name = "N", default_value_t = crate::output::DEFAULT_WARNING_LIMIT)]
+    pub max_warnings: usize,
+
     /// Increase verbosity (-v info, -vv debug, -vvv trace)
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     pub verbose: u8,
 
     /// Suppress all output except errors
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub quiet: bool,
+
+    /// Per-module log levels, e.g. `scanner=debug,engine::strategies=trace`
+    #[arg(long, value_name = "DIRECTIVES", global = true)]
+    pub log_filter: Option<LogFilter>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Combine JSON reports from `--shard` runs into one report
+    Merge(MergeArgs),
+    /// Show findings added, removed or changed between two JSON reports
+    Compare(CompareArgs),
+    /// Work with rules files
+    #[command(subcommand)]
+    Rules(RulesCommand),
+    /// Scan the cases of a `--record` corpus and compare each with its
+    /// expected report
+    Replay(ReplayArgs),
+    /// Evaluate constant expressions in Python and JavaScript files with the
+    /// real runtime and report where the resolver disagrees
+    CheckConstants(CheckConstantsArgs),
+    /// List the files a directory scan would parse and why the others are
+    /// skipped, without scanning
+    Discover(DiscoverArgs),
+    /// Show which mapping classifies a call, every lookup made on the way
+    /// and the resulting classification
+    Classify(ClassifyArgs),
+    /// Report how many of the functions called on each crypto-capable
+    /// dependency the rules map
+    Coverage(CoverageArgs),
+    /// Scan the filesystem of a container image, attributing findings to
+    /// the layers that added them
+    Image(ImageArgs),
+    /// Print the tool version, the rules digest and the grammar versions
+    /// recorded in report metadata
+    Version(VersionArgs),
+    /// Scan each repository listed in a manifest, writing a report per
+    /// repository and a cross-repository summary
+    Batch(BatchArgs),
+    /// Make the fixes of a report written with `--suggest-fixes` to the
+    /// scanned files, or show them as a diff
+    Fix(FixArgs),
+    /// Fail when a report uses algorithms or key sizes missing from the
+    /// release's approved manifest, writing a report for sign-off
+    Gate(GateArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RulesCommand {
+    /// Run the fixture tests embedded in rules files
+    Test(RulesTestArgs),
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct RulesTestArgs {
+    /// Rules files whose `tests` to run; all of them are layered into the
+    /// classifier, as with `--rules`
+    #[arg(required = true, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+
+    /// Preset to load under the rules. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct MergeArgs {
+    /// JSON reports to combine
+    #[arg(required = true, value_name = "REPORT")]
+    pub reports: Vec<PathBuf>,
+
+    /// Output file path (prints to stdout if not specified)
+    #[arg(short = 'O', long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct CompareArgs {
+    /// Report of the earlier run
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// Report of the later run
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+
+    /// Output format (text, json, markdown)
+    #[arg(short = 'f', long, default_value = "text")]
+    pub format: CompareFormat,
+
+    /// Output file path (prints to stdout if not specified)
+    #[arg(short = 'O', long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct GateArgs {
+    /// JSON report of the scan to check
+    #[arg(value_name = "REPORT")]
+    pub report: PathBuf,
+
+    /// Manifest of the approved `algorithms` and `key_sizes` by algorithm
+    #[arg(long, value_name = "MANIFEST")]
+    pub against: PathBuf,
+
+    /// Output file path for the gate report (prints to stdout if not
+    /// specified)
+    #[arg(short = 'O', long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ReplayArgs {
+    /// Corpus directory written by `--record`
+    #[arg(value_name = "DIR")]
+    pub corpus: PathBuf,
+
+    /// Preset to use. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+
+    /// Custom rules file. Can be specified multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct CheckConstantsArgs {
+    /// File or directory to check
+    #[arg(value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// Python interpreter used for .py files
+    #[arg(long, value_name = "BIN", default_value = "python3")]
+    pub python: String,
+
+    /// Node.js binary used for .js and .ts files
+    #[arg(long, value_name = "BIN", default_value = "node")]
+    pub node: String,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ClassifyArgs {
+    /// Import path of the called function's package, e.g.
+    /// golang.org/x/crypto/pbkdf2
+    #[arg(long = "import", value_name = "PATH", conflicts_with = "at")]
+    pub import_path: Option<String>,
+
+    /// Package name the call is written with (defaults to the last segment
+    /// of --import)
+    #[arg(long, value_name = "NAME", conflicts_with = "at")]
+    pub package: Option<String>,
+
+    /// Called function, e.g. Key
+    #[arg(long, value_name = "NAME", required_unless_present = "at")]
+    pub function: Option<String>,
+
+    /// Explain the calls on one line of a source file instead
+    #[arg(long, value_name = "FILE:LINE", value_parser = parse_source_line)]
+    pub at: Option<SourceLine>,
+
+    /// Preset to use. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+
+    /// Custom rules file. Can be specified multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct CoverageArgs {
+    /// Project directory; dependencies are read from its lockfiles and
+    /// manifests
+    #[arg(value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// Language (detected from the project's manifest files if not specified)
+    #[arg(short, long)]
+    pub language: Option<Language>,
+
+    /// Preset to use. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+
+    /// Custom rules file. Can be specified multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+
+    /// Output format (text, json)
+    #[arg(short = 'f', long, default_value = "text")]
+    pub format: CoverageFormat,
+
+    /// Output file path (prints to stdout if not specified)
+    #[arg(short = 'O', long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ImageArgs {
+    /// Image tarball (`docker save`, or an OCI layout packed with tar) or
+    /// OCI image layout directory on disk. Registry references are not
+    /// pulled; export them first with `docker save` or `skopeo copy`
+    #[arg(value_name = "IMAGE")]
+    pub image: PathBuf,
+
+    /// Preset to use. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET", required = true)]
+    pub preset: Vec<String>,
+
+    /// Custom rules file, layered over the presets. Can be specified
+    /// multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+
+    /// Also scan the image's compiled artifacts for embedded crypto
+    /// indicators
+    #[arg(long)]
+    pub scan_binaries: bool,
+
+    /// Output format (json, cbom)
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: OutputFormat,
+
+    /// Output file path (prints to stdout if not specified)
+    #[arg(short = 'O', long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct VersionArgs {
+    /// Print the metadata as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Preset whose rules to digest instead of the bundled ones. Can be
+    /// specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+
+    /// Custom rules file, layered over the presets. Can be specified
+    /// multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct BatchArgs {
+    /// JSON or YAML file listing the repositories as `repos: [{path, name,
+    /// language}]`, with paths relative to the manifest
+    #[arg(long, value_name = "FILE")]
+    pub manifest: PathBuf,
+
+    /// Directory for the per-repository reports and `summary.json`
+    #[arg(short = 'o', long, value_name = "DIR")]
+    pub output_dir: PathBuf,
+
+    /// Preset to use. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET", required = true)]
+    pub preset: Vec<String>,
+
+    /// Custom rules file, layered over the presets. Can be specified
+    /// multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+
+    /// Include each repository's dependencies
+    #[arg(long)]
+    pub include_deps: bool,
+
+    /// Output format of the per-repository reports (json, cbom)
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: OutputFormat,
+
+    /// Repositories scanned at the same time (defaults to the number of
+    /// CPUs); the CPUs are shared out among them
+    #[arg(long, value_name = "N")]
+    pub concurrency: Option<NonZeroUsize>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct FixArgs {
+    /// JSON report with `fixes`; its file paths are read from the current
+    /// directory, as the scan wrote them
+    #[arg(value_name = "REPORT")]
+    pub report: PathBuf,
+
+    /// Rule whose fixes to make, e.g. `pbkdf2_iterations`. Can be specified
+    /// multiple times; fixes of other rules are left alone.
+    #[arg(
+        long = "rule",
+        value_name = "RULE",
+        required = true,
+        value_parser = clap::builder::PossibleValuesParser::new(crate::policy::FIX_RULES)
+    )]
+    pub rules: Vec<String>,
+
+    /// Print the changes as a unified diff without writing any file
+    #[arg(long, required_unless_present = "write", conflicts_with = "write")]
+    pub dry_run: bool,
+
+    /// Write the changes to the files, keeping each original as `FILE.orig`
+    #[arg(long)]
+    pub write: bool,
+
+    /// Do not keep `FILE.orig` backups with `--write`
+    #[arg(long, conflicts_with = "dry_run")]
+    pub no_backup: bool,
+}
+
+/// A line of a source file, given as `FILE:LINE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLine {
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+fn parse_source_line(value: &str) -> Result<SourceLine, String> {
+    let (file, line) = value
+        .rsplit_once(':')
+        .ok_or_else(|| format!("expected FILE:LINE, got {value}"))?;
+    let line = line
+        .parse::<usize>()
+        .ok()
+        .filter(|&line| line > 0)
+        .ok_or_else(|| format!("invalid line number: {line}"))?;
+    Ok(SourceLine {
+        file: PathBuf::from(file),
+        line,
+    })
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct DiscoverArgs {
+    /// Directory to discover files in
+    #[arg(value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// Language (detected from the project's manifest files if not specified)
+    #[arg(short, long)]
+    pub language: Option<Language>,
+
+    /// Preset whose imports select files. Can be specified multiple times.
+    #[arg(long, value_name = "PRESET")]
+    pub preset: Vec<String>,
+
+    /// Rules file whose `weak_algorithms` and `non_crypto_imports` apply.
+    /// Can be specified multiple times.
+    #[arg(long, value_name = "FILE")]
+    pub rules: Vec<PathBuf>,
+
+    /// Include dependencies (vendor/, go mod cache, node_modules/, etc.)
+    #[arg(long)]
+    pub include_deps: bool,
+
+    /// Mark the files of dependencies --include-deps leaves out over this
+    /// budget as skipped, see `scan --deps-budget`
+    #[arg(long, value_name = "FILES,SIZE", default_value_t = DepsBudget::default())]
+    pub deps_budget: DepsBudget,
+
+    /// Mark files outside shard INDEX of COUNT as skipped
+    #[arg(long, value_name = "INDEX/COUNT")]
+    pub shard: Option<Shard>,
+}
+
+/// Resolver overrides; each flag takes precedence over the config file.
+#[derive(ClapArgs, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolverArgs {
+    /// Strategy chain in order (comma-separated, e.g. literal,identifier,call)
+    #[arg(long, value_name = "LIST", value_delimiter = ',')]
+    pub strategies: Option<Vec<String>>,
+
+    /// Disable a resolution strategy. Can be specified multiple times.
+    #[arg(long, value_name = "NAME")]
+    pub disable_strategy: Vec<String>,
+
+    /// Maximum resolution depth
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+
+    /// Maximum function-call depth followed by the Call strategy
+    #[arg(long, value_name = "N")]
+    pub call_depth: Option<usize>,
+
+    /// Disable cross-file constant resolution
+    #[arg(long)]
+    pub no_cross_file: bool,
+
+    /// Report per-strategy invocation counts and timings, and per-dependency
+    /// scan times, after the scan
+    #[arg(long)]
+    pub profile_resolution: bool,
+}
+
+impl ResolverArgs {
+    pub fn apply_to(&self, config: &mut ResolverConfig) {
+        if let Some(ref strategies) = self.strategies {
+            config.strategies = Some(strategies.clone());
+        }
+        config
+            .disabled
+            .extend(self.disable_strategy.iter().cloned());
+        if self.max_depth.is_some() {
+            config.max_depth = self.max_depth;
+        }
+        if self.call_depth.is_some() {
+            config.call_depth = self.call_depth;
+        }
+        if self.no_cross_file {
+            config.cross_file = false;
+        }
+    }
 }
 
 impl Args {
+    /// The `--path` to scan; clap requires it unless a subcommand is given.
+    pub fn scan_paths(&self) -> &[PathBuf] {
+        &self.path
+    }
+
     pub fn validate(&self) -> Result<()> {
-        validate_path(&self.path)?;
-        if let Some(ref rules_path) = self.rules {
+        for path in self.scan_paths() {
+            validate_path(path)?;
+        }
+        for rules_path in &self.rules {
             if !rules_path.exists() {
                 anyhow::bail!("Rules file does not exist: {}", rules_path.display());
             }
         }
+        if let Some(ref env) = self.python_env {
+            if !env.is_dir() {
+                anyhow::bail!("Python environment is not a directory: {}", env.display());
+            }
+        }
+        if let Some(ref config_path) = self.config {
+            if !config_path.exists() {
+                anyhow::bail!("Config file does not exist: {}", config_path.display());
+            }
+        }
+        if self.sign.is_some() && self.output_file.is_none() {
+            anyhow::bail!("--sign requires an output file (-O)");
+        }
+        if self.record.is_some() && !matches!(self.scan_paths(), [path] if path.is_file()) {
+            anyhow::bail!("--record requires a single file --path");
+        }
+        if self.offline {
+            if let Some(key) = self.sign.as_ref().filter(|key| key.needs_network()) {
+                let (key, service) = match key {
+                    SigningKey::Keyless => ("keyless", "Sigstore (Fulcio and Rekor)"),
+                    SigningKey::Key(key) => (key.as_str(), "the key management service"),
+                };
+                anyhow::bail!(
+                    "--sign {key} needs network access to {service}, which --offline forbids; sign with a key file instead"
+                );
+            }
+        }
+        #[cfg(feature = "discovery")]
+        if self
+            .scan_paths()
+            .iter()
+            .any(|path| path.is_file() && ArchiveKind::for_path(path).is_some())
+        {
+            if self.sign.is_some() {
+                anyhow::bail!("--sign does not apply to archives; scan the unpacked directory");
+            }
+            if self.max_memory.is_some() {
+                anyhow::bail!(
+                    "--max-memory does not apply to archives; scan the unpacked directory"
+                );
+            }
+            if self.suggest_fixes.is_some() {
+                anyhow::bail!(
+                    "--suggest-fixes does not apply to archives; scan the unpacked directory"
+                );
+            }
+        }
+        if self.git_ref.is_some() && self.git_url.is_none() {
+            anyhow::bail!("--ref requires --git-url");
+        }
+        if self.git_url.is_some() {
+            if self.offline {
+                anyhow::bail!("--git-url needs network access, which --offline forbids");
+            }
+            if self.sign.is_some() {
+                anyhow::bail!("--sign does not apply to --git-url; scan a local clone");
+            }
+            if self.max_memory.is_some() {
+                anyhow::bail!("--max-memory does not apply to --git-url; scan a local clone");
+            }
+            if self.suggest_fixes.is_some() {
+                anyhow::bail!("--suggest-fixes does not apply to --git-url; scan a local clone");
+            }
+        }
+        if self.path.len() > 1 {
+            if self.sign.is_some() {
+                anyhow::bail!("--sign requires a single --path");
+            }
+            if self.max_memory.is_some() {
+                anyhow::bail!("--max-memory requires a single --path");
+            }
+        }
+        if self.suggest_fixes.is_some() && self.max_memory.is_some() {
+            anyhow::bail!("--suggest-fixes cannot be used with --max-memory");
+        }
         Ok(())
     }
 }
 
+/// The language of `file_path` by its extension, after the config file's
+/// extension overrides.
 pub fn detect_language(file_path: &Path) -> Option<Language> {
+    #[cfg(feature = "discovery")]
+    match extensions::override_for(file_path) {
+        Some(ExtensionOverride::Language(language)) => return Some(language),
+        Some(ExtensionOverride::Skip) => return None,
+        None => {}
+    }
     file_path.extension()?.to_str().and_then(|ext| match ext {
         "go" => Some(Language::Go),
         "py" => Some(Language::Python),
         "rs" => Some(Language::Rust),
-        "js" => Some(Language::Javascript),
-        "ts" => Some(Language::Typescript),
+        "js" | "jsx" | "mjs" | "cjs" => Some(Language::Javascript),
+        "ts" | "tsx" | "mts" | "cts" => Some(Language::Typescript),
+        "java" | "kt" | "kts" | "scala" | "sc" => Some(Language::Java),
+        "c" | "h" => Some(Language::C),
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Some(Language::Cpp),
+        "cs" => Some(Language::CSharp),
+        "rb" => Some(Language::Ruby),
+        "php" => Some(Language::Php),
+        "swift" => Some(Language::Swift),
         _ => None,
     })
 }
@@ -106,6 +790,13 @@ impl Language {
             Language::Rust => "rust",
             Language::Javascript => "javascript",
             Language::Typescript => "typescript",
+            Language::Java => "java",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::CSharp => "csharp",
+            Language::Ruby => "ruby",
+            Language::Php => "php",
+            Language::Swift => "swift",
         }
     }
 
@@ -116,6 +807,13 @@ impl Language {
             Language::Rust => "rust",
             Language::Javascript => "javascript",
             Language::Typescript => "javascript",
+            Language::Java => "java",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::CSharp => "csharp",
+            Language::Ruby => "ruby",
+            Language::Php => "php",
+            Language::Swift => "swift",
         }
     }
 
@@ -126,6 +824,13 @@ impl Language {
             Language::Go => "/",
             Language::Javascript => "/",
             Language::Typescript => "/",
+            Language::Java => ".",
+            Language::C => "/",
+            Language::Cpp => "/",
+            Language::CSharp => ".",
+            Language::Ruby => "::",
+            Language::Php => "\\",
+            Language::Swift => ".",
         }
     }
 }
@@ -135,6 +840,7 @@ impl OutputFormat {
         match self {
             OutputFormat::Json => "json",
             OutputFormat::Cbom => "cbom",
+            OutputFormat::Compact => "compact",
         }
     }
 }
@@ -175,6 +881,69 @@ mod tests {
         assert_eq!(detect_language(path), Some(Language::Typescript));
     }
 
+    #[test]
+    fn test_detect_language_jsx_and_tsx() {
+        assert_eq!(
+            detect_language(Path::new("App.jsx")),
+            Some(Language::Javascript)
+        );
+        assert_eq!(
+            detect_language(Path::new("App.tsx")),
+            Some(Language::Typescript)
+        );
+    }
+
+    #[test]
+    fn test_detect_language_java() {
+        let path = Path::new("Test.java");
+        assert_eq!(detect_language(path), Some(Language::Java));
+    }
+
+    #[test]
+    fn test_detect_language_c() {
+        assert_eq!(detect_language(Path::new("crypt.c")), Some(Language::C));
+        assert_eq!(detect_language(Path::new("crypt.h")), Some(Language::C));
+    }
+
+    #[test]
+    fn test_detect_language_cpp() {
+        assert_eq!(detect_language(Path::new("hash.cpp")), Some(Language::Cpp));
+        assert_eq!(detect_language(Path::new("hash.cc")), Some(Language::Cpp));
+        assert_eq!(
+            detect_language(Path::new("params.hpp")),
+            Some(Language::Cpp)
+        );
+    }
+
+    #[test]
+    fn test_detect_language_csharp() {
+        assert_eq!(
+            detect_language(Path::new("Crypto.cs")),
+            Some(Language::CSharp)
+        );
+    }
+
+    #[test]
+    fn test_detect_language_ruby() {
+        assert_eq!(
+            detect_language(Path::new("crypto.rb")),
+            Some(Language::Ruby)
+        );
+    }
+
+    #[test]
+    fn test_detect_language_php() {
+        assert_eq!(detect_language(Path::new("Vault.php")), Some(Language::Php));
+    }
+
+    #[test]
+    fn test_detect_language_swift() {
+        assert_eq!(
+            detect_language(Path::new("Vault.swift")),
+            Some(Language::Swift)
+        );
+    }
+
     #[test]
     fn test_detect_language_unknown() {
         let path = Path::new("test.txt");
@@ -193,6 +962,13 @@ mod tests {
         assert_eq!(Language::Python.as_str(), "python");
         assert_eq!(Language::Rust.as_str(), "rust");
         assert_eq!(Language::Javascript.as_str(), "javascript");
+        assert_eq!(Language::Java.as_str(), "java");
+        assert_eq!(Language::C.as_str(), "c");
+        assert_eq!(Language::Cpp.as_str(), "cpp");
+        assert_eq!(Language::CSharp.as_str(), "csharp");
+        assert_eq!(Language::Ruby.as_str(), "ruby");
+        assert_eq!(Language::Php.as_str(), "php");
+        assert_eq!(Language::Swift.as_str(), "swift");
         assert_eq!(Language::Typescript.as_str(), "typescript");
     }
 
@@ -202,6 +978,13 @@ mod tests {
         assert_eq!(Language::Python.preset_language_name(), "python");
         assert_eq!(Language::Rust.preset_language_name(), "rust");
         assert_eq!(Language::Javascript.preset_language_name(), "javascript");
+        assert_eq!(Language::Java.preset_language_name(), "java");
+        assert_eq!(Language::C.preset_language_name(), "c");
+        assert_eq!(Language::Cpp.preset_language_name(), "cpp");
+        assert_eq!(Language::CSharp.preset_language_name(), "csharp");
+        assert_eq!(Language::Ruby.preset_language_name(), "ruby");
+        assert_eq!(Language::Php.preset_language_name(), "php");
+        assert_eq!(Language::Swift.preset_language_name(), "swift");
         assert_eq!(Language::Typescript.preset_language_name(), "javascript");
     }
 
@@ -211,6 +994,13 @@ mod tests {
         assert_eq!(Language::Python.path_separator(), ".");
         assert_eq!(Language::Go.path_separator(), "/");
         assert_eq!(Language::Javascript.path_separator(), "/");
+        assert_eq!(Language::Java.path_separator(), ".");
+        assert_eq!(Language::C.path_separator(), "/");
+        assert_eq!(Language::Cpp.path_separator(), "/");
+        assert_eq!(Language::CSharp.path_separator(), ".");
+        assert_eq!(Language::Ruby.path_separator(), "::");
+        assert_eq!(Language::Php.path_separator(), "\\");
+        assert_eq!(Language::Swift.path_separator(), ".");
         assert_eq!(Language::Typescript.path_separator(), "/");
     }
 
@@ -248,15 +1038,41 @@ mod tests {
         fs::write(&file_path, "package main").unwrap();
 
         let args = Args {
-            path: file_path,
+            command: None,
+            path: vec![file_path],
+            git_url: None,
+            git_ref: None,
             preset: vec![],
-            rules: None,
+            rules: vec![],
             output_file: None,
             format: OutputFormat::Json,
             language: Some(Language::Go),
             include_deps: false,
+            deps_budget: DepsBudget::default(),
+            tool_timeout: 120,
+            offline: false,
+            python_env: None,
+            config: None,
+            explain: false,
+            resolver: ResolverArgs::default(),
+            max_memory: None,
+            max_findings: None,
+            audit_coverage: false,
+            jobs: None,
+            shard: None,
+            sign: None,
+            record: None,
+            max_warnings: crate::output::DEFAULT_WARNING_LIMIT,
+            scan_binaries: false,
+            fallback_scan: false,
+            password_policy: false,
+            suggest_fixes: None,
+            agility: false,
+            hotspots: false,
+            timings: false,
             verbose: 0,
             quiet: false,
+            log_filter: None,
         };
 
         assert!(args.validate().is_ok());
@@ -269,15 +1085,41 @@ mod tests {
         fs::write(&file_path, "package main").unwrap();
 
         let args = Args {
-            path: file_path,
+            command: None,
+            path: vec![file_path],
+            git_url: None,
+            git_ref: None,
             preset: vec!["crypto".to_string()],
-            rules: None,
+            rules: vec![],
             output_file: None,
             format: OutputFormat::Json,
             language: Some(Language::Go),
             include_deps: false,
+            deps_budget: DepsBudget::default(),
+            tool_timeout: 120,
+            offline: false,
+            python_env: None,
+            config: None,
+            explain: false,
+            resolver: ResolverArgs::default(),
+            max_memory: None,
+            max_findings: None,
+            audit_coverage: false,
+            jobs: None,
+            shard: None,
+            sign: None,
+            record: None,
+            max_warnings: crate::output::DEFAULT_WARNING_LIMIT,
+            scan_binaries: false,
+            fallback_scan: false,
+            password_policy: false,
+            suggest_fixes: None,
+            agility: false,
+            hotspots: false,
+            timings: false,
             verbose: 0,
             quiet: false,
+            log_filter: None,
         };
 
         assert!(args.validate().is_ok());
@@ -286,34 +1128,473 @@ mod tests {
     #[test]
     fn test_args_validate_invalid_path() {
         let args = Args {
-            path: PathBuf::from("/nonexistent/path"),
+            command: None,
+            path: vec![PathBuf::from("/nonexistent/path")],
+            git_url: None,
+            git_ref: None,
             preset: vec![],
-            rules: None,
+            rules: vec![],
             output_file: None,
             format: OutputFormat::Json,
             language: None,
             include_deps: false,
+            deps_budget: DepsBudget::default(),
+            tool_timeout: 120,
+            offline: false,
+            python_env: None,
+            config: None,
+            explain: false,
+            resolver: ResolverArgs::default(),
+            max_memory: None,
+            max_findings: None,
+            audit_coverage: false,
+            jobs: None,
+            shard: None,
+            sign: None,
+            record: None,
+            max_warnings: crate::output::DEFAULT_WARNING_LIMIT,
+            scan_binaries: false,
+            fallback_scan: false,
+            password_policy: false,
+            suggest_fixes: None,
+            agility: false,
+            hotspots: false,
+            timings: false,
             verbose: 0,
             quiet: false,
+            log_filter: None,
         };
 
         assert!(args.validate().is_err());
     }
 
+    #[test]
+    fn test_resolver_args_override_config() {
+        let mut config = ResolverConfig {
+            disabled: vec!["index".to_string()],
+            call_depth: Some(4),
+            ..ResolverConfig::default()
+        };
+        let args = ResolverArgs {
+            strategies: Some(vec!["literal".to_string(), "call".to_string()]),
+            disable_strategy: vec!["composite".to_string()],
+            max_depth: None,
+            call_depth: Some(1),
+            no_cross_file: true,
+            profile_resolution: false,
+        };
+
+        args.apply_to(&mut config);
+
+        assert_eq!(
+            config.strategies,
+            Some(vec!["literal".to_string(), "call".to_string()])
+        );
+        assert_eq!(
+            config.disabled,
+            vec!["index".to_string(), "composite".to_string()]
+        );
+        assert_eq!(config.call_depth, Some(1));
+        assert_eq!(config.max_depth, None);
+        assert!(!config.cross_file);
+    }
+
+    #[test]
+    fn test_resolver_args_parse_strategy_list() {
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            ".",
+            "--strategies",
+            "literal,identifier",
+            "--disable-strategy",
+            "call",
+        ]);
+        assert_eq!(
+            args.resolver.strategies,
+            Some(vec!["literal".to_string(), "identifier".to_string()])
+        );
+        assert_eq!(args.resolver.disable_strategy, vec!["call".to_string()]);
+    }
+
     #[test]
     fn test_verbose_flag_incremental() {
         let args = Args {
-            path: PathBuf::from("."),
+            command: None,
+            path: vec![PathBuf::from(".")],
+            git_url: None,
+            git_ref: None,
             preset: vec![],
-            rules: None,
+            rules: vec![],
             output_file: None,
             format: OutputFormat::Json,
             language: None,
             include_deps: false,
+            deps_budget: DepsBudget::default(),
+            tool_timeout: 120,
+            offline: false,
+            python_env: None,
+            config: None,
+            explain: false,
+            resolver: ResolverArgs::default(),
+            max_memory: None,
+            max_findings: None,
+            audit_coverage: false,
+            jobs: None,
+            shard: None,
+            sign: None,
+            record: None,
+            max_warnings: crate::output::DEFAULT_WARNING_LIMIT,
+            scan_binaries: false,
+            fallback_scan: false,
+            password_policy: false,
+            suggest_fixes: None,
+            agility: false,
+            hotspots: false,
+            timings: false,
             verbose: 2,
             quiet: false,
+            log_filter: None,
         };
 
         assert_eq!(args.verbose, 2);
     }
+
+    #[test]
+    fn test_parse_shard_flag() {
+        let args = Args::parse_from(["argflow", "--path", ".", "--shard", "2/4"]);
+        assert_eq!(args.shard, Some(Shard::new(2, 4).unwrap()));
+        assert!(Args::try_parse_from(["argflow", "--path", ".", "--shard", "5/4"]).is_err());
+    }
+
+    #[test]
+    fn test_sign_requires_output_file() {
+        let args = Args::parse_from(["argflow", "--path", ".", "--sign", "keyless"]);
+        assert_eq!(args.sign, Some(SigningKey::Keyless));
+        assert!(args.validate().is_err());
+
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            ".",
+            "--sign",
+            "cosign.key",
+            "-O",
+            "out.json",
+        ]);
+        assert_eq!(args.sign, Some(SigningKey::Key("cosign.key".to_string())));
+        assert!(args.validate().is_ok());
+    }
+
+    #[test]
+    fn test_offline_rejects_network_signing() {
+        let parse = |key: &str| {
+            Args::parse_from([
+                "argflow",
+                "--path",
+                ".",
+                "--offline",
+                "--sign",
+                key,
+                "-O",
+                "out.json",
+            ])
+        };
+        let err = parse("keyless").validate().unwrap_err();
+        assert!(err.to_string().contains("--offline"), "{err}");
+        assert!(parse("awskms:///alias/argflow").validate().is_err());
+        assert!(parse("cosign.key").validate().is_ok());
+    }
+
+    #[test]
+    fn test_git_url_replaces_path() {
+        let args = Args::parse_from([
+            "argflow",
+            "--git-url",
+            "https://github.com/org/repo",
+            "--ref",
+            "v1.2.0",
+        ]);
+        assert!(args.scan_paths().is_empty());
+        assert_eq!(args.git_ref.as_deref(), Some("v1.2.0"));
+        assert!(args.validate().is_ok());
+
+        assert!(Args::try_parse_from(["argflow", "--path", ".", "--git-url", "x"]).is_err());
+        let args = Args::parse_from(["argflow", "--path", ".", "--ref", "main"]);
+        assert!(args.validate().is_err());
+        let args = Args::parse_from(["argflow", "--git-url", "x", "--offline"]);
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_merge_subcommand_does_not_need_path() {
+        let args = Args::parse_from([
+            "argflow", "-v", "merge", "a.json", "b.json", "-O", "out.json",
+        ]);
+        assert!(args.path.is_empty());
+        assert_eq!(args.verbose, 1);
+        let Some(Command::Merge(merge)) = args.command else {
+            panic!("expected merge subcommand");
+        };
+        assert_eq!(merge.reports.len(), 2);
+        assert_eq!(merge.output_file, Some(PathBuf::from("out.json")));
+
+        assert!(Args::try_parse_from(["argflow"]).is_err());
+    }
+
+    #[test]
+    fn test_compare_subcommand() {
+        let args = Args::parse_from([
+            "argflow", "compare", "old.json", "new.json", "-f", "markdown",
+        ]);
+        let Some(Command::Compare(compare)) = args.command else {
+            panic!("expected compare subcommand");
+        };
+        assert_eq!(compare.old, PathBuf::from("old.json"));
+        assert_eq!(compare.new, PathBuf::from("new.json"));
+        assert_eq!(compare.format, CompareFormat::Markdown);
+
+        assert!(Args::try_parse_from(["argflow", "compare", "old.json"]).is_err());
+    }
+
+    #[test]
+    fn test_version_subcommand() {
+        let args = Args::parse_from(["argflow", "version", "--json", "--preset", "crypto"]);
+        let Some(Command::Version(version)) = args.command else {
+            panic!("expected version subcommand");
+        };
+        assert!(version.json);
+        assert_eq!(version.preset, vec!["crypto"]);
+        assert!(version.rules.is_empty());
+    }
+
+    #[test]
+    fn test_batch_subcommand() {
+        let args = Args::parse_from([
+            "argflow",
+            "batch",
+            "--manifest",
+            "repos.yaml",
+            "-o",
+            "reports",
+            "--preset",
+            "crypto",
+            "--concurrency",
+            "4",
+        ]);
+        let Some(Command::Batch(batch)) = args.command else {
+            panic!("expected batch subcommand");
+        };
+        assert_eq!(batch.manifest, PathBuf::from("repos.yaml"));
+        assert_eq!(batch.output_dir, PathBuf::from("reports"));
+        assert_eq!(batch.concurrency, NonZeroUsize::new(4));
+        assert_eq!(batch.format, OutputFormat::Json);
+
+        assert!(Args::try_parse_from(["argflow", "batch", "--manifest", "repos.yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_fix_subcommand() {
+        let args = Args::parse_from([
+            "argflow",
+            "fix",
+            "report.json",
+            "--rule",
+            "pbkdf2_iterations",
+            "--rule",
+            "sha1_new",
+            "--write",
+        ]);
+        let Some(Command::Fix(fix)) = args.command else {
+            panic!("expected fix subcommand");
+        };
+        assert_eq!(fix.report, PathBuf::from("report.json"));
+        assert_eq!(fix.rules, ["pbkdf2_iterations", "sha1_new"]);
+        assert!(fix.write && !fix.dry_run && !fix.no_backup);
+
+        let fix = |extra: &[&str]| {
+            Args::try_parse_from(
+                ["argflow", "fix", "report.json", "--rule", "sha1_new"]
+                    .iter()
+                    .chain(extra),
+            )
+        };
+        assert!(fix(&["--dry-run"]).is_ok());
+        assert!(fix(&[]).is_err());
+        assert!(fix(&["--dry-run", "--write"]).is_err());
+        assert!(fix(&["--dry-run", "--no-backup"]).is_err());
+        assert!(fix(&["--dry-run", "--rule", "md5_everywhere"]).is_err());
+        assert!(Args::try_parse_from(["argflow", "fix", "report.json", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn test_rules_test_subcommand() {
+        let args = Args::parse_from([
+            "argflow",
+            "rules",
+            "test",
+            "org.yaml",
+            "team.json",
+            "--preset",
+            "crypto",
+        ]);
+        let Some(Command::Rules(RulesCommand::Test(test))) = args.command else {
+            panic!("expected rules test subcommand");
+        };
+        assert_eq!(
+            test.rules,
+            vec![PathBuf::from("org.yaml"), PathBuf::from("team.json")]
+        );
+        assert_eq!(test.preset, vec!["crypto"]);
+
+        assert!(Args::try_parse_from(["argflow", "rules", "test"]).is_err());
+    }
+
+    #[test]
+    fn test_record_requires_a_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            temp_dir.path().to_str().unwrap(),
+            "--record",
+            "corpus",
+        ]);
+        assert_eq!(args.record, Some(PathBuf::from("corpus")));
+        assert!(args.validate().is_err());
+
+        let args = Args::parse_from(["argflow", "replay", "corpus", "--preset", "crypto"]);
+        let Some(Command::Replay(replay)) = args.command else {
+            panic!("expected replay subcommand");
+        };
+        assert_eq!(replay.corpus, PathBuf::from("corpus"));
+    }
+
+    #[test]
+    fn test_several_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("svc-a");
+        let b = temp_dir.path().join("svc-b");
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            a.to_str().unwrap(),
+            b.to_str().unwrap(),
+            "--preset",
+            "crypto",
+        ]);
+        assert_eq!(args.scan_paths(), [a.clone(), b.clone()]);
+        assert!(args.validate().is_ok());
+
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            a.to_str().unwrap(),
+            "--path",
+            b.to_str().unwrap(),
+            "--max-memory",
+            "1G",
+        ]);
+        assert_eq!(args.scan_paths().len(), 2);
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_check_constants_subcommand() {
+        let args = Args::parse_from([
+            "argflow",
+            "check-constants",
+            "src",
+            "--python",
+            "python3.12",
+        ]);
+        let Some(Command::CheckConstants(check)) = args.command else {
+            panic!("expected check-constants subcommand");
+        };
+        assert_eq!(check.path, PathBuf::from("src"));
+        assert_eq!(check.python, "python3.12");
+        assert_eq!(check.node, "node");
+    }
+
+    #[test]
+    fn test_log_filter_flag() {
+        let args = Args::parse_from([
+            "argflow",
+            "--path",
+            "src",
+            "--log-filter",
+            "engine::strategies=trace",
+        ]);
+        assert_eq!(
+            args.log_filter,
+            Some("engine::strategies=trace".parse().unwrap())
+        );
+
+        let args = Args::try_parse_from(["argflow", "merge", "a.json", "--log-filter", "x=loud"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_classify_subcommand() {
+        let args = Args::parse_from([
+            "argflow",
+            "classify",
+            "--import",
+            "golang.org/x/crypto/pbkdf2",
+            "--function",
+            "Key",
+        ]);
+        let Some(Command::Classify(classify)) = args.command else {
+            panic!("expected classify subcommand");
+        };
+        assert_eq!(
+            classify.import_path.as_deref(),
+            Some("golang.org/x/crypto/pbkdf2")
+        );
+        assert_eq!(classify.function.as_deref(), Some("Key"));
+
+        let args = Args::parse_from(["argflow", "classify", "--at", "src/auth.go:42"]);
+        let Some(Command::Classify(classify)) = args.command else {
+            panic!("expected classify subcommand");
+        };
+        assert_eq!(
+            classify.at,
+            Some(SourceLine {
+                file: PathBuf::from("src/auth.go"),
+                line: 42
+            })
+        );
+
+        assert!(Args::try_parse_from(["argflow", "classify", "--import", "hashlib"]).is_err());
+        assert!(Args::try_parse_from(["argflow", "classify", "--at", "auth.go"]).is_err());
+    }
+
+    #[test]
+    fn test_coverage_subcommand() {
+        let args = Args::parse_from([
+            "argflow", "coverage", ".", "--preset", "crypto", "-f", "json",
+        ]);
+        let Some(Command::Coverage(coverage)) = args.command else {
+            panic!("expected coverage subcommand");
+        };
+        assert_eq!(coverage.path, PathBuf::from("."));
+        assert_eq!(coverage.format, CoverageFormat::Json);
+    }
+
+    #[test]
+    fn test_discover_subcommand() {
+        let args = Args::parse_from([
+            "argflow", "discover", "src", "--preset", "crypto", "--shard", "1/2",
+        ]);
+        let Some(Command::Discover(discover)) = args.command else {
+            panic!("expected discover subcommand");
+        };
+        assert_eq!(discover.path, PathBuf::from("src"));
+        assert_eq!(discover.preset, vec!["crypto"]);
+        assert!(discover.shard.is_some());
+        assert!(!discover.include_deps);
+    }
 }
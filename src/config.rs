@@ -0,0 +1,215 @@
+//! Project configuration file (`--config`).
+//!
+//! The file may be JSON or YAML and carries resolver options and policy
+//! thresholds:
+//!
+//! ```yaml
+//! resolver:
+//!   strategies: [literal, unary, binary, identifier, selector]
+//!   disabled: [composite]
+//!   call_depth: 2
+//!   cross_file: false
+//! password_policy:
+//!   pbkdf2_iterations: 310000
+//!   bcrypt_cost: 12
+//! extensions:
+//!   mts: typescript
+//!   gotmpl: skip
+//! waivers:
+//!   - rule: tls_verification_disabled
+//!     file: internal/legacy/
+//!     owner: platform-team
+//!     reason: legacy partner endpoint, replaced in Q3
+//!     expires: 2026-09-30
+//! notify:
+//!   webhook: https://hooks.slack.com/services/T000/B000/XXXX
+//!   min_severity: high
+//!   baseline: reports/main.json
+//! ```
+
+#[cfg(feature = "discovery")]
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::debug;
+
+#[cfg(feature = "discovery")]
+use crate::discovery::extensions::ExtensionOverride;
+use crate::engine::ResolverConfig;
+use crate::error::ConfigError;
+use crate::output::NotifyConfig;
+use crate::policy::{PasswordPolicy, Waiver};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub resolver: ResolverConfig,
+    /// Minimum password hashing cost factors for `--password-policy`.
+    pub password_policy: PasswordPolicy,
+    /// Languages for file extensions, or `skip`; see
+    /// [`crate::discovery::extensions`].
+    #[cfg(feature = "discovery")]
+    pub extensions: BTreeMap<String, ExtensionOverride>,
+    /// Accepted exceptions to the policy; see [`crate::policy::Waiver`].
+    pub waivers: Vec<Waiver>,
+    /// Webhook posted a summary after a scan; see
+    /// [`crate::output::NotifyConfig`].
+    pub notify: Option<NotifyConfig>,
+}
+
+impl Config {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        debug!(path = %path.display(), "loading config file");
+
+        let content =
+            fs::read_to_string(path).map_err(|e| ConfigError::read_error(path, e.to_string()))?;
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let config: Self = match extension {
+            "json" => serde_json::from_str(&content)
+                .map_err(|e| ConfigError::parse_error(path, e.to_string()))?,
+            "yaml" | "yml" => serde_yaml::from_str(&content)
+                .map_err(|e| ConfigError::parse_error(path, e.to_string()))?,
+            _ => return Err(ConfigError::unsupported_format(extension)),
+        };
+        for waiver in &config.waivers {
+            waiver
+                .validate()
+                .map_err(|e| ConfigError::parse_error(path, e))?;
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_yaml_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.yaml");
+        fs::write(
+            &path,
+            "resolver:\n  disabled: [call]\n  cross_file: false\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.resolver.disabled, vec!["call".to_string()]);
+        assert!(!config.resolver.cross_file);
+    }
+
+    #[test]
+    fn test_load_json_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.json");
+        fs::write(&path, r#"{"resolver": {"call_depth": 3}}"#).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.resolver.call_depth, Some(3));
+        assert!(config.resolver.cross_file);
+    }
+
+    #[test]
+    fn test_load_password_policy() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.yaml");
+        fs::write(&path, "password_policy:\n  bcrypt_cost: 12\n").unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.password_policy.bcrypt_cost, 12);
+        assert_eq!(config.password_policy.pbkdf2_iterations, 600_000);
+    }
+
+    #[cfg(feature = "discovery")]
+    #[test]
+    fn test_load_extension_overrides() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.yaml");
+        fs::write(&path, "extensions:\n  .mts: typescript\n  gotmpl: skip\n").unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(
+            config.extensions.get(".mts"),
+            Some(&ExtensionOverride::Language(
+                crate::cli::Language::Typescript
+            ))
+        );
+        assert_eq!(
+            config.extensions.get("gotmpl"),
+            Some(&ExtensionOverride::Skip)
+        );
+
+        fs::write(&path, "extensions:\n  pyx: cython\n").unwrap();
+        assert!(matches!(
+            Config::load(&path),
+            Err(ConfigError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_waivers() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.yaml");
+        fs::write(
+            &path,
+            "waivers:\n  - rule: jwt_alg_none\n    owner: auth-team\n    expires: 2026-03-31\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.waivers.len(), 1);
+        assert_eq!(config.waivers[0].owner, "auth-team");
+        assert_eq!(config.waivers[0].expires.as_deref(), Some("2026-03-31"));
+
+        fs::write(
+            &path,
+            "waivers:\n  - rule: jwt_alg_none\n    owner: auth-team\n    expires: next year\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            Config::load(&path),
+            Err(ConfigError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_notify() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.yaml");
+        fs::write(
+            &path,
+            "notify:\n  webhook: https://hooks.example.com/T000\n  min_severity: medium\n",
+        )
+        .unwrap();
+
+        let notify = Config::load(&path).unwrap().notify.unwrap();
+        assert_eq!(notify.webhook, "https://hooks.example.com/T000");
+        assert_eq!(notify.min_severity, crate::policy::Severity::Medium);
+    }
+
+    #[test]
+    fn test_load_unsupported_format() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("argflow.toml");
+        fs::write(&path, "").unwrap();
+
+        assert!(matches!(
+            Config::load(&path),
+            Err(ConfigError::UnsupportedFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(matches!(
+            Config::load("/nonexistent/argflow.yaml"),
+            Err(ConfigError::ReadError { .. })
+        ));
+    }
+}
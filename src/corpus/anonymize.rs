@@ -0,0 +1,275 @@
+//! Stripping proprietary names and text from a source file.
+//!
+//! A recorded case must still reproduce the analysis, so the names the
+//! report depends on are kept: the crypto APIs called and their packages,
+//! config types and fields, and string values that were resolved into
+//! arguments, along with members of kept packages and keyword argument
+//! names. Every other identifier is renamed consistently (`v1`, `v2`,
+//! ...), other string contents are replaced by filler of the same length
+//! (lengths matter for key and nonce checks) and comments are removed.
+
+use std::collections::{HashMap, HashSet};
+
+use tree_sitter::Node;
+
+use crate::api::parser_for;
+use crate::error::ParserError;
+use crate::output::JsonOutput;
+use crate::utils::unquote_string;
+
+/// Leaf kinds holding the text of a string literal.
+const STRING_CONTENT_KINDS: &[&str] = &[
+    "string_content",
+    "string_fragment",
+    "interpreted_string_literal_content",
+    "raw_string_literal_content",
+];
+
+/// Names that are part of the language rather than of the code base.
+const BUILTINS: &[&str] = &[
+    "self",
+    "this",
+    "super",
+    "cls",
+    "len",
+    "make",
+    "new",
+    "append",
+    "byte",
+    "string",
+    "int",
+    "bool",
+    "error",
+    "bytes",
+    "str",
+    "print",
+    "range",
+    "undefined",
+    "require",
+    "module",
+    "exports",
+    "Buffer",
+    "String",
+    "Number",
+    "Object",
+    "Array",
+    "Vec",
+    "Some",
+    "None",
+    "Ok",
+    "Err",
+    "main",
+];
+
+/// `source` with everything not needed to reproduce `report` removed.
+pub fn anonymize(source: &str, language: &str, report: &JsonOutput) -> Result<String, ParserError> {
+    let (_, mut parser) = parser_for(language)?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| ParserError::parse_failed("<record>"))?;
+
+    let mut anonymizer = Anonymizer::new(report);
+    anonymizer.visit(tree.root_node(), source);
+
+    let mut out = source.to_string();
+    anonymizer.edits.sort_by_key(|(start, _, _)| *start);
+    for (start, end, replacement) in anonymizer.edits.into_iter().rev() {
+        out.replace_range(start..end, &replacement);
+    }
+    Ok(out)
+}
+
+struct Anonymizer {
+    names: HashSet<String>,
+    strings: HashSet<String>,
+    renamed: HashMap<String, String>,
+    filled: HashMap<String, String>,
+    edits: Vec<(usize, usize, String)>,
+}
+
+impl Anonymizer {
+    fn new(report: &JsonOutput) -> Self {
+        let mut names: HashSet<String> = BUILTINS.iter().map(|s| s.to_string()).collect();
+        let mut strings = HashSet::new();
+        let mut add_name = |name: &str, strings: &mut HashSet<String>| {
+            strings.insert(name.to_string());
+            names.extend(
+                name.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string),
+            );
+        };
+
+        for finding in &report.findings {
+            add_name(&finding.function, &mut strings);
+            add_name(&finding.full_name, &mut strings);
+            for name in finding.package.iter().chain(&finding.import_path) {
+                add_name(name, &mut strings);
+            }
+            for value in finding.parameters.values() {
+                collect_strings(value, &mut strings);
+            }
+        }
+        for config in &report.configs {
+            add_name(&config.struct_type, &mut strings);
+            add_name(&config.full_type, &mut strings);
+            for name in config
+                .package
+                .iter()
+                .chain(&config.import_path)
+                .chain(&config.call)
+            {
+                add_name(name, &mut strings);
+            }
+            for field in &config.fields {
+                add_name(&field.field_name, &mut strings);
+                collect_strings(&field.value, &mut strings);
+            }
+        }
+
+        Self {
+            names,
+            strings,
+            renamed: HashMap::new(),
+            filled: HashMap::new(),
+            edits: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, node: Node, source: &str) {
+        let kind = node.kind();
+        let text = &source[node.byte_range()];
+
+        if kind.contains("comment") {
+            let newlines = "\n".repeat(text.matches('\n').count());
+            self.edits
+                .push((node.start_byte(), node.end_byte(), newlines));
+            return;
+        }
+        if STRING_CONTENT_KINDS.contains(&kind) {
+            if !self.strings.contains(text) {
+                let filler = self.filler(text);
+                self.edits
+                    .push((node.start_byte(), node.end_byte(), filler));
+            }
+            return;
+        }
+        if node.child_count() == 0 && kind.ends_with("identifier") {
+            if !self.names.contains(text) && !self.is_api_member(node, source) {
+                let next = self.renamed.len() + 1;
+                let name = self
+                    .renamed
+                    .entry(text.to_string())
+                    .or_insert_with(|| format!("v{next}"))
+                    .clone();
+                self.edits.push((node.start_byte(), node.end_byte(), name));
+            }
+            return;
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child, source);
+        }
+    }
+
+    /// Whether `node` names a member of a kept package or type, e.g.
+    /// `MODE_ECB` in `AES.MODE_ECB`, or a keyword argument, e.g.
+    /// `initialization_vector=`. Policies read both.
+    fn is_api_member(&self, node: Node, source: &str) -> bool {
+        let Some(parent) = node.parent() else {
+            return false;
+        };
+        if parent.kind() == "keyword_argument" {
+            return parent
+                .child_by_field_name("name")
+                .is_some_and(|name| name.id() == node.id());
+        }
+        let object = ["operand", "object", "value", "path", "package"]
+            .iter()
+            .find_map(|field| parent.child_by_field_name(field));
+        object.is_some_and(|object| {
+            object.id() != node.id() && self.names.contains(&source[object.byte_range()])
+        })
+    }
+
+    /// Filler with the character count of `text`, the same for equal
+    /// strings.
+    fn filler(&mut self, text: &str) -> String {
+        let next = self.filled.len() + 1;
+        self.filled
+            .entry(text.to_string())
+            .or_insert_with(|| {
+                let len = text.chars().count();
+                let tag = format!("s{next}");
+                if tag.len() <= len {
+                    format!("{tag:x<len$}")
+                } else {
+                    "x".repeat(len)
+                }
+            })
+            .clone()
+    }
+}
+
+/// The string values in `value`, without quotes or a bytes prefix.
+fn collect_strings(value: &serde_json::Value, out: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::String(s) => {
+            out.insert(unquote_string(s.strip_prefix('b').unwrap_or(s)));
+            out.insert(s.clone());
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::scan_named_source;
+    use crate::classifier::RulesClassifier;
+
+    #[test]
+    fn test_keeps_crypto_calls_and_renames_the_rest() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {"pbkdf2": {"algorithm": "PBKDF2", "findingType": "kdf", "operation": "keyderive"}},
+                "mappings": {"hashlib": {"pbkdf2_hmac": "pbkdf2"}}
+            }"#,
+        )
+        .unwrap();
+        let source = r#"import hashlib
+
+# Acme billing secrets
+BILLING_ROUNDS = 1000
+
+def acme_login(customer_password):
+    banner = "acme corp"
+    return hashlib.pbkdf2_hmac("sha256", customer_password, b"0123456789abcdef", BILLING_ROUNDS)
+"#;
+        let report = scan_named_source(source, "a.py", "python", &classifier).unwrap();
+        let anonymized = anonymize(source, "python", &report).unwrap();
+
+        assert_eq!(
+            anonymized,
+            r#"import hashlib
+
+
+v1 = 1000
+
+def v2(v3):
+    v4 = "s1xxxxxxx"
+    return hashlib.pbkdf2_hmac("sha256", v3, b"0123456789abcdef", v1)
+"#
+        );
+
+        let replayed = scan_named_source(&anonymized, "a.py", "python", &classifier).unwrap();
+        assert_eq!(replayed.findings.len(), 1);
+        assert_eq!(
+            replayed.findings[0].parameters["arg3"],
+            report.findings[0].parameters["arg3"]
+        );
+    }
+}
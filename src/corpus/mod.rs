@@ -0,0 +1,217 @@
+//! Regression corpus of recorded scan inputs.
+//!
+//! When a value is resolved wrongly, `--record <DIR>` saves the scanned
+//! file, [anonymized](anonymize), as a case in `DIR` together with the
+//! report for it:
+//!
+//! ```text
+//! corpus/
+//!   case-1f2e3d4c5b6a7988.py
+//!   case-1f2e3d4c5b6a7988.expected.json
+//! ```
+//!
+//! The reporter corrects the expected report to what the scan should have
+//! found and shares the directory. [`replay_corpus`] scans every case again
+//! and compares the result with its expected report, so fixes can be
+//! checked and kept from regressing.
+
+mod anonymize;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::scan_named_source;
+use crate::classifier::RulesClassifier;
+use crate::engine::symbol_index::language_for_path;
+use crate::error::{IoError, Result};
+use crate::output::{compare_reports, JsonOutput, ReportDiff};
+use crate::utils::stable_hash;
+
+pub use anonymize::anonymize;
+
+const EXPECTED_SUFFIX: &str = ".expected.json";
+
+/// A case written by [`record_case`].
+#[derive(Debug)]
+pub struct RecordedCase {
+    pub source: PathBuf,
+    pub expected: PathBuf,
+    /// Whether the anonymized file produces as many findings and configs,
+    /// with the same algorithms, hash usage and weaknesses, as the
+    /// original. When not, a name the analysis depends on was renamed and
+    /// the case should be checked by hand.
+    pub faithful: bool,
+}
+
+/// The outcome of replaying one case.
+#[derive(Debug)]
+pub struct ReplayResult {
+    pub case: PathBuf,
+    pub outcome: ReplayOutcome,
+}
+
+#[derive(Debug)]
+pub enum ReplayOutcome {
+    /// The scan differs from the expected report; empty when it matches.
+    Diff(ReportDiff),
+    /// The case could not be read or scanned.
+    Error(String),
+}
+
+impl ReplayResult {
+    pub fn passed(&self) -> bool {
+        matches!(&self.outcome, ReplayOutcome::Diff(diff) if diff.is_empty())
+    }
+}
+
+/// Anonymizes `source` and writes it to `dir` as a case, with the report of
+/// the anonymized file as its expected output. `extension` is the source
+/// file's, so replay can tell its language.
+pub fn record_case(
+    dir: &Path,
+    source: &str,
+    language: &str,
+    extension: &str,
+    classifier: &RulesClassifier,
+) -> Result<RecordedCase> {
+    let report = scan_named_source(source, "<record>", language, classifier)?;
+    let anonymized = anonymize(source, language, &report)?;
+    let name = format!("case-{:016x}", stable_hash(anonymized.as_bytes()));
+    let file_name = format!("{name}.{extension}");
+
+    let expected = scan_named_source(&anonymized, &file_name, language, classifier)?;
+    let faithful = summary(&expected) == summary(&report);
+
+    fs::create_dir_all(dir).map_err(|e| IoError::write_error(dir, e))?;
+    let source_path = dir.join(&file_name);
+    let expected_path = dir.join(format!("{name}{EXPECTED_SUFFIX}"));
+    fs::write(&source_path, anonymized).map_err(|e| IoError::write_error(&source_path, e))?;
+    let json = serde_json::to_string_pretty(&expected).expect("reports serialize");
+    fs::write(&expected_path, json + "\n").map_err(|e| IoError::write_error(&expected_path, e))?;
+
+    Ok(RecordedCase {
+        source: source_path,
+        expected: expected_path,
+        faithful,
+    })
+}
+
+/// Scans every case in `dir` with `classifier` and compares it with its
+/// expected report. Cases are returned in file name order.
+pub fn replay_corpus(dir: &Path, classifier: &RulesClassifier) -> Result<Vec<ReplayResult>> {
+    let entries = fs::read_dir(dir).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IoError::directory_not_found(dir),
+        _ => IoError::read_error(dir, e),
+    })?;
+    let mut cases: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && expected_path(path).is_file())
+        .collect();
+    cases.sort();
+
+    Ok(cases
+        .into_iter()
+        .map(|case| {
+            let outcome = match replay_case(&case, classifier) {
+                Ok(diff) => ReplayOutcome::Diff(diff),
+                Err(message) => ReplayOutcome::Error(message),
+            };
+            ReplayResult { case, outcome }
+        })
+        .collect())
+}
+
+fn replay_case(
+    case: &Path,
+    classifier: &RulesClassifier,
+) -> std::result::Result<ReportDiff, String> {
+    let language = language_for_path(case)
+        .ok_or_else(|| format!("unsupported file type: {}", case.display()))?;
+    let source = fs::read_to_string(case).map_err(|e| e.to_string())?;
+    let expected_path = expected_path(case);
+    let expected: JsonOutput = fs::read_to_string(&expected_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .map_err(|e| format!("failed to load {}: {e}", expected_path.display()))?;
+
+    let file_name = case
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let actual = scan_named_source(&source, &file_name, language.tree_sitter_name(), classifier)
+        .map_err(|e| e.to_string())?;
+    Ok(compare_reports(expected, actual))
+}
+
+/// `case-x.py` -> `case-x.expected.json`
+fn expected_path(case: &Path) -> PathBuf {
+    let stem = case
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    case.with_file_name(format!("{stem}{EXPECTED_SUFFIX}"))
+}
+
+type Summary<'a> = (Vec<(Option<&'a str>, Option<&'a str>)>, usize, Vec<&'a str>);
+
+/// What a faithful anonymization must preserve.
+fn summary(report: &JsonOutput) -> Summary<'_> {
+    let mut algorithms: Vec<_> = report
+        .findings
+        .iter()
+        .map(|f| {
+            (
+                f.algorithm.as_deref(),
+                f.usage_context.as_ref().map(|u| u.usage.as_str()),
+            )
+        })
+        .collect();
+    algorithms.sort();
+    let mut weaknesses: Vec<&str> = report.weaknesses.iter().map(|w| w.rule.as_str()).collect();
+    weaknesses.sort();
+    (algorithms, report.configs.len(), weaknesses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_then_replay() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{
+                "classifications": {"sha1": {"algorithm": "SHA-1", "findingType": "hash", "operation": "hash"}},
+                "mappings": {"hashlib": {"sha1": "sha1"}}
+            }"#,
+        )
+        .unwrap();
+        // Renaming `etag` loses the checksum usage read from it.
+        let source = "import hashlib\ndef etag(body):\n    return hashlib.sha1(body)\n";
+        let other = tempfile::TempDir::new().unwrap();
+        let case = record_case(other.path(), source, "python", "py", &classifier).unwrap();
+        assert!(!case.faithful);
+
+        let source = "import hashlib\ndef digest(data):\n    return hashlib.sha1(data)\n";
+        let dir = tempfile::TempDir::new().unwrap();
+        let case = record_case(dir.path(), source, "python", "py", &classifier).unwrap();
+        assert!(case.faithful);
+        assert!(fs::read_to_string(&case.source)
+            .unwrap()
+            .contains("hashlib.sha1(v2)"));
+
+        let results = replay_corpus(dir.path(), &classifier).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed(), "{:?}", results[0].outcome);
+
+        // A corrected expectation the scan does not meet yet fails.
+        let mut expected: JsonOutput =
+            serde_json::from_str(&fs::read_to_string(&case.expected).unwrap()).unwrap();
+        expected.findings[0].algorithm = Some("SHA-256".to_string());
+        fs::write(&case.expected, serde_json::to_string(&expected).unwrap()).unwrap();
+        let results = replay_corpus(dir.path(), &classifier).unwrap();
+        let ReplayOutcome::Diff(diff) = &results[0].outcome else {
+            panic!("expected a diff");
+        };
+        assert_eq!(diff.changed.len(), 1);
+    }
+}
@@ -0,0 +1,288 @@
+//! Release artifacts as a scan target: a zip archive (`.zip`, `.jar`,
+//! `.war`, `.ear`, `.aar`, `.whl`) or a tarball (`.tar`, `.tar.gz`, `.tgz`,
+//! `.crate`) is unpacked into a temporary directory and scanned as the
+//! project inside it.
+//!
+//! Only regular files are unpacked, so nothing in an archive can point
+//! extraction outside the directory. Entries larger than
+//! [`MAX_ENTRY_SIZE`] are left out.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use tar::EntryType;
+use tempfile::TempDir;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use super::image::contained_path;
+use super::languages::java::jar;
+use super::LanguageRegistry;
+use crate::cli::{detect_language, Language};
+use crate::output::{rebase_paths, JsonOutput};
+
+pub const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// The kind of archive `path` is, by its name.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".crate") {
+            return Some(Self::TarGz);
+        }
+        match name.rsplit_once('.')?.1 {
+            "tar" => Some(Self::Tar),
+            "zip" | "jar" | "war" | "ear" | "aar" | "whl" => Some(Self::Zip),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("cannot unpack {}: {source}", path.display())]
+    Unpack {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// An archive unpacked into a fresh temporary directory, removed on drop.
+pub struct UnpackedArchive {
+    archive: PathBuf,
+    dir: TempDir,
+    root: PathBuf,
+}
+
+impl UnpackedArchive {
+    pub fn unpack(path: &Path, kind: ArchiveKind) -> Result<Self, ArchiveError> {
+        // Removed on drop, including when unpacking fails below.
+        let dir = tempfile::Builder::new()
+            .prefix("argflow-archive-")
+            .tempdir()?;
+        let dest = dir.path();
+        let result = match kind {
+            ArchiveKind::Zip => jar::extract(path, dest, MAX_ENTRY_SIZE, |_| true).map(drop),
+            ArchiveKind::Tar => {
+                File::open(path).and_then(|file| unpack_tar(BufReader::new(file), dest))
+            }
+            ArchiveKind::TarGz => File::open(path)
+                .and_then(|file| unpack_tar(GzDecoder::new(BufReader::new(file)), dest)),
+        };
+        result.map_err(|source| ArchiveError::Unpack {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(Self {
+            archive: path.to_path_buf(),
+            root: single_directory(dest).unwrap_or_else(|| dest.to_path_buf()),
+            dir,
+        })
+    }
+
+    /// Where the archive was unpacked, its paths relative to this.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// The directory to scan: the archive's only top-level directory, as in
+    /// source distributions and npm tarballs, else where it was unpacked.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The language of the archive's code for when no manifest tells: Java
+    /// for JARs and their kin, Python for wheels, else the language of most
+    /// of its source files.
+    pub fn language(&self) -> Option<Language> {
+        let extension = self.archive.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "jar" | "war" | "ear" | "aar" => return Some(Language::Java),
+            "whl" => return Some(Language::Python),
+            _ => {}
+        }
+        let mut counts: HashMap<Language, usize> = HashMap::new();
+        for entry in WalkDir::new(&self.root).into_iter().flatten() {
+            if entry.file_type().is_file() {
+                if let Some(language) = detect_language(entry.path()) {
+                    *counts.entry(language).or_default() += 1;
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|(language, count)| (*count, std::cmp::Reverse(language.as_str())))
+            .map(|(language, _)| language)
+    }
+
+    /// The language to scan the archive in: `requested`, else none when the
+    /// manifests in [`root`](Self::root) tell, else [`language`](Self::language).
+    pub fn scan_language(
+        &self,
+        registry: &LanguageRegistry,
+        requested: Option<Language>,
+    ) -> Option<Language> {
+        requested.or_else(|| {
+            registry
+                .detect_languages(&self.root)
+                .is_empty()
+                .then(|| self.language())
+                .flatten()
+        })
+    }
+
+    /// Rewrites the paths in `report` from where the archive was unpacked to
+    /// inside it, as in `app.jar!/com/example/Keys.java`.
+    pub fn rebase(&self, report: &mut JsonOutput) {
+        rebase_paths(
+            report,
+            &self.dir().to_string_lossy(),
+            &format!("{}!", self.archive.display()),
+        );
+    }
+}
+
+fn unpack_tar(reader: impl Read, dest: &Path) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !matches!(
+            entry.header().entry_type(),
+            EntryType::Regular | EntryType::Continuous
+        ) || entry.size() > MAX_ENTRY_SIZE
+        {
+            continue;
+        }
+        let Some(relative) = contained_path(&entry.path()?) else {
+            continue;
+        };
+        let path = dest.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut File::create(&path)?)?;
+    }
+    Ok(())
+}
+
+fn single_directory(dir: &Path) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(dir).ok()?.flatten();
+    let only = entries.next()?.path();
+    (entries.next().is_none() && only.is_dir()).then_some(only)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tarball(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_archive_kinds() {
+        let kind = |name| ArchiveKind::for_path(Path::new(name));
+        assert_eq!(kind("dist/app-1.0.jar"), Some(ArchiveKind::Zip));
+        assert_eq!(kind("pkg-1.0-py3-none-any.whl"), Some(ArchiveKind::Zip));
+        assert_eq!(kind("pkg-1.0.tar.gz"), Some(ArchiveKind::TarGz));
+        assert_eq!(kind("ring-0.17.8.crate"), Some(ArchiveKind::TarGz));
+        assert_eq!(kind("rootfs.TAR"), Some(ArchiveKind::Tar));
+        assert_eq!(kind("main.go"), None);
+        assert_eq!(kind("Makefile"), None);
+    }
+
+    #[test]
+    fn test_unpack_wheel() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let wheel = dir.path().join("tokens-1.0-py3-none-any.whl");
+        fs::write(
+            &wheel,
+            jar::zip(&[
+                ("tokens/__init__.py", "import hashlib\n"),
+                ("tokens-1.0.dist-info/METADATA", "Name: tokens\n"),
+            ]),
+        )
+        .unwrap();
+
+        let unpacked = UnpackedArchive::unpack(&wheel, ArchiveKind::Zip).unwrap();
+        assert!(unpacked.root().join("tokens/__init__.py").is_file());
+        assert_eq!(unpacked.language(), Some(Language::Python));
+
+        let unpacked_dir = unpacked.dir().to_path_buf();
+        drop(unpacked);
+        assert!(!unpacked_dir.exists());
+    }
+
+    #[test]
+    fn test_unpack_sdist_tarball() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sdist = dir.path().join("keys-2.0.tar.gz");
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        io::Write::write_all(
+            &mut encoder,
+            &tarball(&[
+                ("keys-2.0/keys.go", "package keys\n"),
+                ("keys-2.0/internal/sign.go", "package internal\n"),
+                ("keys-2.0/sign.py", "pass\n"),
+            ]),
+        )
+        .unwrap();
+        fs::write(&sdist, encoder.finish().unwrap()).unwrap();
+
+        let unpacked = UnpackedArchive::unpack(&sdist, ArchiveKind::TarGz).unwrap();
+        assert!(unpacked.root().ends_with("keys-2.0"));
+        assert!(unpacked.root().join("internal/sign.go").is_file());
+        assert_eq!(unpacked.language(), Some(Language::Go));
+        let registry = LanguageRegistry::new();
+        assert_eq!(unpacked.scan_language(&registry, None), Some(Language::Go));
+        assert_eq!(
+            unpacked.scan_language(&registry, Some(Language::Python)),
+            Some(Language::Python)
+        );
+
+        let file = unpacked.root().join("keys.go");
+        let mut report = crate::test_support::report(
+            vec![crate::test_support::finding(
+                &file.to_string_lossy(),
+                3,
+                "crypto/rsa.GenerateKey",
+            )],
+            Vec::new(),
+        );
+        unpacked.rebase(&mut report);
+        assert_eq!(
+            report.findings[0].file,
+            format!("{}!/keys-2.0/keys.go", sdist.display())
+        );
+
+        fs::write(&sdist, "not gzip").unwrap();
+        assert!(matches!(
+            UnpackedArchive::unpack(&sdist, ArchiveKind::TarGz),
+            Err(ArchiveError::Unpack { .. })
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+//! A size budget for `--include-deps`: under one, dependency packages are
+//! picked by name before they are walked where the loader can name them,
+//! and over it only the dependencies that look crypto-relevant are
+//! scanned. The others are reported as skipped rather than silently
+//! dropped.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::output::SkippedDependency;
+pub use crate::utils::DepsBudget;
+
+use super::cache::DiscoveryCache;
+use super::loader::{LoadError, PackageLoader};
+use super::{SourceFile, SourceType};
+
+/// The dependencies of an `--include-deps` scan, split by `budget`.
+#[derive(Debug, Default)]
+pub struct BudgetedDependencies {
+    /// The files to scan.
+    pub files: Vec<SourceFile>,
+    /// Files loaded but left out over the budget.
+    pub over_budget: Vec<SourceFile>,
+    /// Packages turned down by name, never walked.
+    pub unselected: Vec<String>,
+}
+
+impl BudgetedDependencies {
+    /// Every skipped package, sorted by package and version.
+    pub fn skipped(&self) -> Vec<SkippedDependency> {
+        let mut skipped = skipped_dependencies(&self.over_budget);
+        skipped.extend(self.unselected.iter().map(|package| SkippedDependency {
+            package: package.clone(),
+            version: None,
+            files: None,
+            bytes: None,
+        }));
+        skipped.sort_by(|a, b| (&a.package, &a.version).cmp(&(&b.package, &b.version)));
+        skipped.dedup_by(|a, b| a.package == b.package && a.version == b.version);
+        skipped
+    }
+}
+
+/// The dependencies `loader` finds under `root`. Unless `budget` is
+/// unlimited, only the packages `is_relevant` accepts by name are walked;
+/// the files of packages a loader cannot name up front are then held to
+/// `budget` as in [`apply_budget`].
+pub fn load_dependencies(
+    loader: &dyn PackageLoader,
+    root: &Path,
+    cache: &mut DiscoveryCache,
+    budget: DepsBudget,
+    is_relevant: impl Fn(&str) -> bool,
+) -> Result<BudgetedDependencies, LoadError> {
+    if budget == DepsBudget::UNLIMITED {
+        return Ok(BudgetedDependencies {
+            files: loader.load_dependencies(root, cache)?,
+            ..Default::default()
+        });
+    }
+
+    let mut unselected = Vec::new();
+    let dependencies = loader.load_selected_dependencies(root, cache, &mut |package| {
+        let selected = is_relevant(package);
+        if !selected {
+            unselected.push(package.to_string());
+        }
+        selected
+    })?;
+    let (files, over_budget) = apply_budget(dependencies, budget, is_relevant);
+    Ok(BudgetedDependencies {
+        files,
+        over_budget,
+        unselected,
+    })
+}
+
+/// Splits `dependencies` into the files to scan and those to skip. Within
+/// `budget` everything is scanned; over it, only the files of the packages
+/// `is_relevant` accepts, however many they are.
+pub fn apply_budget(
+    dependencies: Vec<SourceFile>,
+    budget: DepsBudget,
+    is_relevant: impl Fn(&str) -> bool,
+) -> (Vec<SourceFile>, Vec<SourceFile>) {
+    let bytes: u64 = dependencies.iter().map(|file| file.metadata.size).sum();
+    if budget.admits(dependencies.len(), bytes) {
+        return (dependencies, Vec::new());
+    }
+
+    dependencies
+        .into_iter()
+        .partition(|file| dependency_name(file).is_none_or(|package| is_relevant(&package)))
+}
+
+/// The packages of the `skipped` files with their file counts and sizes,
+/// sorted by package and version.
+pub fn skipped_dependencies(skipped: &[SourceFile]) -> Vec<SkippedDependency> {
+    let mut packages: BTreeMap<(String, Option<String>), SkippedDependency> = BTreeMap::new();
+    for file in skipped {
+        let (Some(package), SourceType::Dependency { version, .. }) =
+            (dependency_name(file), &file.source_type)
+        else {
+            continue;
+        };
+        let entry = packages
+            .entry((package.clone(), version.clone()))
+            .or_insert_with(|| SkippedDependency {
+                package,
+                version: version.clone(),
+                files: Some(0),
+                bytes: Some(0),
+            });
+        *entry.files.get_or_insert(0) += 1;
+        *entry.bytes.get_or_insert(0) += file.metadata.size;
+    }
+    packages.into_values().collect()
+}
+
+/// The package a dependency file belongs to: the one the loader tagged it
+/// with, else the one its path lies in (`node_modules/@noble/hashes`,
+/// `site-packages/nacl`, `pkg/mod/golang.org/x/crypto@v0.21.0`,
+/// `registry/src/<index>/ring-0.17.8`). Unset for user code and stdlib.
+pub fn dependency_name(file: &SourceFile) -> Option<String> {
+    let SourceType::Dependency { package, .. } = &file.source_type else {
+        return None;
+    };
+    if package != "unknown" {
+        return Some(package.clone());
+    }
+
+    let segments: Vec<String> = file
+        .path
+        .parent()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let after = |marker: &str| {
+        segments
+            .iter()
+            .rposition(|segment| segment == marker)
+            .map(|i| &segments[i + 1..])
+            .filter(|rest| !rest.is_empty())
+    };
+
+    if let Some(rest) = after("node_modules") {
+        let take = if rest[0].starts_with('@') { 2 } else { 1 };
+        return Some(rest[..take.min(rest.len())].join("/"));
+    }
+    if let Some(rest) = after("site-packages").or_else(|| after("dist-packages")) {
+        return Some(rest[0].clone());
+    }
+    if let Some(rest) = after("mod") {
+        if let Some(end) = rest.iter().position(|segment| segment.contains('@')) {
+            let module = rest[..=end].join("/");
+            return Some(module.split('@').next().unwrap_or(&module).to_string());
+        }
+    }
+    if let Some(rest) = after("registry").filter(|rest| rest[0] == "src") {
+        let krate = rest.get(2)?;
+        let name = krate
+            .rsplit_once('-')
+            .filter(|(_, version)| version.starts_with(|c: char| c.is_ascii_digit()))
+            .map_or(krate.as_str(), |(name, _)| name);
+        return Some(name.to_string());
+    }
+    after("vendor").map(|rest| rest.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Language;
+    use crate::discovery::FileMetadata;
+    use std::path::PathBuf;
+
+    fn dependency(package: &str, size: u64) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(format!("deps/{package}/lib.py")),
+            language: Language::Python,
+            source_type: SourceType::Dependency {
+                package: package.to_string(),
+                version: Some("1.0".to_string()),
+            },
+            package: None,
+            metadata: FileMetadata {
+                size,
+                modified: None,
+                hash: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_dependency_name() {
+        let untagged = |path: &str| SourceFile {
+            path: PathBuf::from(path),
+            source_type: SourceType::Dependency {
+                package: "unknown".to_string(),
+                version: None,
+            },
+            ..dependency("unknown", 0)
+        };
+
+        let names: Vec<Option<String>> = [
+            "app/node_modules/@noble/hashes/esm/sha256.js",
+            "app/node_modules/jose/dist/node/cjs/index.js",
+            "venv/lib/python3.11/site-packages/nacl/secret.py",
+            "go/pkg/mod/golang.org/x/crypto@v0.21.0/pbkdf2/pbkdf2.go",
+            ".cargo/registry/src/index.crates.io-6f17d22bba15001f/ring-0.17.8/src/aead.rs",
+            "app/vendor/github.com/golang-jwt/jwt/token.go",
+        ]
+        .into_iter()
+        .map(|path| dependency_name(&untagged(path)))
+        .collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("@noble/hashes".to_string()),
+                Some("jose".to_string()),
+                Some("nacl".to_string()),
+                Some("golang.org/x/crypto".to_string()),
+                Some("ring".to_string()),
+                Some("github.com/golang-jwt/jwt".to_string()),
+            ]
+        );
+        assert_eq!(
+            dependency_name(&dependency("requests", 0)),
+            Some("requests".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_budget() {
+        let dependencies = || {
+            vec![
+                dependency("cryptography", 10),
+                dependency("requests", 20),
+                dependency("requests", 30),
+            ]
+        };
+        let is_relevant = |package: &str| package.contains("crypt");
+
+        let (kept, skipped) = apply_budget(dependencies(), DepsBudget::default(), is_relevant);
+        assert_eq!(kept.len(), 3);
+        assert!(skipped.is_empty());
+
+        let budget = DepsBudget {
+            files: 2,
+            bytes: crate::utils::DEFAULT_DEPS_BYTES,
+        };
+        let (kept, skipped) = apply_budget(dependencies(), budget, is_relevant);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(
+            skipped_dependencies(&skipped),
+            vec![SkippedDependency {
+                package: "requests".to_string(),
+                version: Some("1.0".to_string()),
+                files: Some(2),
+                bytes: Some(50),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_load_dependencies_by_name() {
+        struct NamedLoader;
+
+        impl PackageLoader for NamedLoader {
+            fn load_user_code(&self, _root: &Path) -> Result<Vec<SourceFile>, LoadError> {
+                Ok(vec![])
+            }
+
+            fn load_dependencies(
+                &self,
+                root: &Path,
+                cache: &mut DiscoveryCache,
+            ) -> Result<Vec<SourceFile>, LoadError> {
+                self.load_selected_dependencies(root, cache, &mut |_| true)
+            }
+
+            fn load_selected_dependencies(
+                &self,
+                _root: &Path,
+                _cache: &mut DiscoveryCache,
+                select: &mut dyn FnMut(&str) -> bool,
+            ) -> Result<Vec<SourceFile>, LoadError> {
+                Ok(["cryptography", "requests"]
+                    .into_iter()
+                    .filter(|package| select(package))
+                    .map(|package| dependency(package, 10))
+                    .collect())
+            }
+
+            fn language(&self) -> Language {
+                Language::Python
+            }
+        }
+
+        let is_relevant = |package: &str| package.contains("crypt");
+        let root = Path::new(".");
+        let mut cache = DiscoveryCache::default();
+
+        let loaded = load_dependencies(
+            &NamedLoader,
+            root,
+            &mut cache,
+            DepsBudget::default(),
+            is_relevant,
+        )
+        .unwrap();
+        assert_eq!(loaded.files.len(), 1);
+        assert_eq!(
+            loaded.skipped(),
+            vec![SkippedDependency {
+                package: "requests".to_string(),
+                version: None,
+                files: None,
+                bytes: None,
+            }]
+        );
+
+        let loaded = load_dependencies(
+            &NamedLoader,
+            root,
+            &mut cache,
+            DepsBudget::UNLIMITED,
+            is_relevant,
+        )
+        .unwrap();
+        assert_eq!(loaded.files.len(), 2);
+        assert!(loaded.skipped().is_empty());
+    }
+}
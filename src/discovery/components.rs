@@ -0,0 +1,159 @@
+//! Independent projects nested in a scanned tree, such as a JavaScript
+//! frontend with its own `package.json` inside a Go service. Each one is
+//! scanned as a component of its own, with its own dependency discovery,
+//! and its files are left out of the project around it.
+
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::cli::Language;
+
+use super::languages::excluded_dirs;
+use super::languages::javascript::workspace::workspace_members;
+use super::LanguageRegistry;
+
+/// Directories holding installed dependencies or build output, whose
+/// manifests belong to someone else's project.
+const THIRD_PARTY_DIRS: &[&str] = &[
+    "vendor",
+    "venv",
+    ".venv",
+    "site-packages",
+    "dist-packages",
+    "target",
+];
+
+/// A directory with a manifest of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub root: PathBuf,
+    /// Languages whose manifest files are in `root`.
+    pub languages: Vec<Language>,
+}
+
+/// `root` itself, then every directory under it with a manifest of its
+/// own, in walk order. A nested crate without its own `Cargo.lock` is a
+/// workspace member and stays part of the component around it, and so does
+/// a package listed in the `workspaces` of a `package.json` above it.
+pub fn find_components(root: &Path, registry: &LanguageRegistry) -> Vec<Component> {
+    let languages = registry.detect_languages(root);
+    let mut js_members = Vec::new();
+    if languages.contains(&Language::Javascript) {
+        js_members.extend(workspace_members(root));
+    }
+    let mut components = vec![Component {
+        root: root.to_path_buf(),
+        languages,
+    }];
+    let walker = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_type().is_dir() && !is_skipped(entry.file_name()));
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        let dir = entry.path();
+        let languages: Vec<Language> = registry
+            .detect_languages(dir)
+            .into_iter()
+            .filter(|&language| language != Language::Rust || dir.join("Cargo.lock").exists())
+            .filter(|&language| {
+                language != Language::Javascript || !js_members.iter().any(|m| m == dir)
+            })
+            .collect();
+        if languages.contains(&Language::Javascript) {
+            js_members.extend(workspace_members(dir));
+        }
+        if !languages.is_empty() {
+            components.push(Component {
+                root: dir.to_path_buf(),
+                languages,
+            });
+        }
+    }
+    components
+}
+
+pub(crate) fn is_skipped(name: &std::ffi::OsStr) -> bool {
+    let name = name.to_string_lossy();
+    name.starts_with('.')
+        || THIRD_PARTY_DIRS.contains(&name.as_ref())
+        || [
+            Language::Go,
+            Language::Python,
+            Language::Javascript,
+            Language::Rust,
+        ]
+        .iter()
+        .any(|&language| excluded_dirs(language).contains(&name.as_ref()))
+}
+
+/// Whether `path` lies in one of `nested`, and so belongs to a component
+/// other than the one being scanned.
+pub fn in_nested(path: &Path, nested: &[PathBuf]) -> bool {
+    nested.iter().any(|root| path.starts_with(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_components() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join("go.mod"), "module example.com/svc\n").unwrap();
+        fs::create_dir_all(root.join("web/node_modules/jose")).unwrap();
+        fs::write(root.join("web/package.json"), "{}").unwrap();
+        fs::write(root.join("web/node_modules/jose/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("tools/gen")).unwrap();
+        fs::write(root.join("tools/gen/Cargo.toml"), "[package]\n").unwrap();
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(root.join("scripts/requirements.txt"), "cryptography\n").unwrap();
+
+        let components = find_components(root, &LanguageRegistry::new());
+        let found: Vec<(PathBuf, Vec<Language>)> = components
+            .into_iter()
+            .map(|c| {
+                (
+                    c.root.strip_prefix(root).unwrap().to_path_buf(),
+                    c.languages,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PathBuf::new(), vec![Language::Go]),
+                (PathBuf::from("scripts"), vec![Language::Python]),
+                (PathBuf::from("web"), vec![Language::Javascript]),
+            ]
+        );
+
+        let nested = [root.join("web")];
+        assert!(in_nested(&root.join("web/src/app.js"), &nested));
+        assert!(!in_nested(&root.join("webhooks/app.go"), &nested));
+    }
+
+    #[test]
+    fn test_workspace_members_are_not_components() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        for dir in ["packages/api", "packages/web", "examples/demo"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("package.json"), "{}").unwrap();
+        }
+
+        let roots: Vec<PathBuf> = find_components(root, &LanguageRegistry::new())
+            .into_iter()
+            .map(|c| c.root.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(roots, vec![PathBuf::new(), PathBuf::from("examples/demo")]);
+    }
+}
@@ -0,0 +1,301 @@
+//! Discovery without scanning, for `argflow discover`: every file a
+//! directory scan would consider, and why the ones it would not parse are
+//! skipped.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+use walkdir::WalkDir;
+
+use crate::classifier::{is_crypto_capable, RulesClassifier};
+use crate::cli::Language;
+use crate::utils::Shard;
+
+use super::budget::{load_dependencies, DepsBudget};
+use super::cache::DiscoveryCache;
+use super::extensions::override_for;
+use super::filter::{FilterError, ImportFileFilter};
+use super::languages::excluded_dirs;
+use super::loader::{LoadError, PackageLoader};
+use super::SourceType;
+
+#[derive(Debug, Clone)]
+pub struct DiscoveredFile {
+    pub path: PathBuf,
+    pub language: Language,
+    /// Unset for excluded directories, which are not walked.
+    pub source_type: Option<SourceType>,
+    /// Why the file would not be scanned; unset when it would be.
+    pub skipped: Option<SkipReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// A directory the loader does not enter, e.g. `testdata`.
+    ExcludedDirectory,
+    /// Assigned to another `--shard`.
+    OtherShard,
+    /// A dependency left out over the `--deps-budget`.
+    OverBudget,
+    /// A dependency package not walked because its name is not
+    /// crypto-related; listed by package rather than by file.
+    Unselected,
+    TooLarge {
+        size: u64,
+        max: u64,
+    },
+    /// Imports no mapped package and names no weak algorithm.
+    NotCrypto,
+    Unreadable(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExcludedDirectory => write!(f, "excluded directory"),
+            Self::OtherShard => write!(f, "in another shard"),
+            Self::OverBudget => write!(f, "dependency over the budget"),
+            Self::Unselected => write!(f, "dependency not crypto-related by name"),
+            Self::TooLarge { size, max } => write!(f, "too large ({size} bytes, max {max})"),
+            Self::NotCrypto => write!(f, "no crypto imports"),
+            Self::Unreadable(error) => write!(f, "unreadable: {error}"),
+        }
+    }
+}
+
+/// The files under `root` that `loader` finds, each checked against
+/// `deps_budget`, `shard` and `filter` in the order a directory scan
+/// applies them, followed by the excluded directories. Sorted by path.
+pub fn discover(
+    root: &Path,
+    loader: &dyn PackageLoader,
+    filter: &dyn ImportFileFilter,
+    include_deps: bool,
+    deps_budget: DepsBudget,
+    classifier: &RulesClassifier,
+    shard: Option<Shard>,
+) -> Result<Vec<DiscoveredFile>, LoadError> {
+    let language = loader.language();
+    let mut files = loader.load_user_code(root)?;
+    let mut over_budget = Vec::new();
+    let mut unselected = Vec::new();
+    if include_deps {
+        let loaded = load_dependencies(
+            loader,
+            root,
+            &mut DiscoveryCache::default(),
+            deps_budget,
+            |package| {
+                is_crypto_capable(package, language.as_str(), classifier)
+                    || filter.matches_package(package)
+            },
+        );
+        match loaded {
+            Ok(loaded) => {
+                files.extend(loaded.files);
+                over_budget = loaded.over_budget;
+                unselected = loaded.unselected;
+            }
+            Err(e) => warn!(error = %e, "failed to load dependencies, listing user code only"),
+        }
+    }
+
+    let mut discovered: Vec<DiscoveredFile> = over_budget
+        .into_iter()
+        .map(|file| DiscoveredFile {
+            path: file.path,
+            language: file.language,
+            source_type: Some(file.source_type),
+            skipped: Some(SkipReason::OverBudget),
+        })
+        .collect();
+    discovered.extend(unselected.into_iter().map(|package| DiscoveredFile {
+        path: PathBuf::from(&package),
+        language,
+        source_type: Some(SourceType::Dependency {
+            package,
+            version: None,
+        }),
+        skipped: Some(SkipReason::Unselected),
+    }));
+    discovered.extend(files.into_iter().map(|file| {
+        let skipped = if shard.is_some_and(|shard| !shard.contains(&file.path, root)) {
+            Some(SkipReason::OtherShard)
+        } else {
+            match filter.has_matching_imports(&file.path) {
+                Ok(true) => None,
+                Ok(false) => Some(SkipReason::NotCrypto),
+                Err(FilterError::TooLarge { size, max }) => {
+                    Some(SkipReason::TooLarge { size, max })
+                }
+                Err(e) => Some(SkipReason::Unreadable(e.to_string())),
+            }
+        };
+        DiscoveredFile {
+            path: file.path,
+            language: file.language,
+            source_type: Some(file.source_type),
+            skipped,
+        }
+    }));
+
+    discovered.extend(
+        excluded_directories(root, language)
+            .into_iter()
+            .map(|path| DiscoveredFile {
+                path,
+                language,
+                source_type: None,
+                skipped: Some(SkipReason::ExcludedDirectory),
+            }),
+    );
+    discovered.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(discovered)
+}
+
+/// Extensions of source languages no scan parses, whose files are listed
+/// as unsupported by `--audit-coverage`. Kotlin and Scala are parsed by
+/// Java scans only.
+pub const UNSUPPORTED_EXTENSIONS: &[&str] = &[
+    "kt", "kts", "scala", "sc", "groovy", "clj", "cljs", "dart", "ex", "exs", "erl", "hs", "lua",
+    "pl", "pm", "r", "jl", "m", "mm", "fs", "fsx", "vb", "zig", "nim", "ml", "cr", "sh", "bash",
+    "ps1",
+];
+
+/// The directories under `root` that a `language` loader does not enter.
+pub fn excluded_directories(root: &Path, language: Language) -> Vec<PathBuf> {
+    let excluded = excluded_dirs(language);
+    let mut directories = Vec::new();
+    WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let skip = entry.file_type().is_dir()
+                && excluded.contains(&entry.file_name().to_string_lossy().as_ref());
+            if skip {
+                directories.push(entry.path().to_path_buf());
+            }
+            !skip
+        })
+        .for_each(drop);
+    directories
+}
+
+/// The files under `root`, outside the directories a `language` loader
+/// does not enter, that are written in a language a `language` scan does
+/// not parse. Sorted by path.
+pub fn unsupported_files(root: &Path, language: Language) -> Vec<PathBuf> {
+    let excluded = excluded_dirs(language);
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            !entry.file_type().is_dir()
+                || !excluded.contains(&entry.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            override_for(path).is_none()
+                && crate::cli::detect_language(path) != Some(language)
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| UNSUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classifier::RulesClassifier;
+    use crate::discovery::filter::FilterTokens;
+    use crate::discovery::languages::python::{PythonImportFilter, PythonPackageLoader};
+
+    #[test]
+    fn test_discover_reports_skip_reasons() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let preset = temp.path().join("preset");
+        std::fs::create_dir_all(preset.join("python")).unwrap();
+        std::fs::write(
+            preset.join("python/mappings.json"),
+            r#"{"mappings": {"cryptography.hazmat": {"Cipher": "cipher"}}}"#,
+        )
+        .unwrap();
+        let root = &temp.path().join("project");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("testdata")).unwrap();
+        std::fs::write(root.join("src/auth.py"), "h = hashlib.md5(pw)\n").unwrap();
+        std::fs::write(root.join("src/util.py"), "print(1)\n").unwrap();
+        std::fs::write(root.join("testdata/weak.py"), "hashlib.md5(pw)\n").unwrap();
+
+        let classifier = RulesClassifier::from_json_str(r#"{"weak_algorithms": ["md5"]}"#).unwrap();
+        let filter = PythonImportFilter::new(&[preset])
+            .unwrap()
+            .with_tokens(FilterTokens::from_classifier(&classifier));
+        let files = discover(
+            root,
+            &PythonPackageLoader,
+            &filter,
+            false,
+            DepsBudget::default(),
+            &classifier,
+            None,
+        )
+        .unwrap();
+
+        let listed: Vec<(String, Option<SkipReason>)> = files
+            .iter()
+            .map(|f| {
+                let path = f.path.strip_prefix(root).unwrap();
+                (path.display().to_string(), f.skipped.clone())
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("src/auth.py".to_string(), None),
+                ("src/util.py".to_string(), Some(SkipReason::NotCrypto)),
+                ("testdata".to_string(), Some(SkipReason::ExcludedDirectory)),
+            ]
+        );
+        assert!(matches!(files[0].source_type, Some(SourceType::UserCode)));
+    }
+
+    #[test]
+    fn test_unsupported_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("android/app")).unwrap();
+        std::fs::create_dir_all(root.join("testdata/lib")).unwrap();
+        std::fs::write(root.join("main.go"), "package main\n").unwrap();
+        std::fs::write(root.join("README.md"), "# app\n").unwrap();
+        std::fs::write(root.join("android/app/Crypto.kt"), "object Crypto\n").unwrap();
+        std::fs::write(root.join("gen-keys.sh"), "openssl genrsa 2048\n").unwrap();
+        std::fs::write(root.join("testdata/lib/Helper.kt"), "object Helper\n").unwrap();
+
+        let files: Vec<PathBuf> = unsupported_files(root, Language::Go)
+            .into_iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("android/app/Crypto.kt"),
+                PathBuf::from("gen-keys.sh")
+            ]
+        );
+
+        // A Java scan parses the Kotlin sources
+        let files: Vec<PathBuf> = unsupported_files(root, Language::Java)
+            .into_iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(files, vec![PathBuf::from("gen-keys.sh")]);
+    }
+}
@@ -0,0 +1,233 @@
+//! File extension overrides from the `extensions` section of the config
+//! file: extensions mapped to a language are scanned as that language, and
+//! extensions mapped to `skip` are never scanned.
+//!
+//! ```yaml
+//! extensions:
+//!   mts: typescript
+//!   pyx: python
+//!   gotmpl: skip
+//!   min.js: skip
+//! ```
+//!
+//! An extension matches the end of a file name, so `min.js` covers
+//! `app.min.js`; the longest matching extension wins. The overrides are set
+//! once per run, like the tool settings, because language detection happens
+//! far from where the config is read.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer};
+use walkdir::WalkDir;
+
+use crate::cli::Language;
+use crate::discovery::{FileMetadata, SourceFile, SourceType};
+
+/// Directories never searched for overridden extensions: VCS metadata,
+/// installed dependencies and build output.
+const EXCLUDED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "vendor",
+    "target",
+    "testdata",
+    "venv",
+    ".venv",
+    "__pycache__",
+];
+
+static OVERRIDES: RwLock<BTreeMap<String, ExtensionOverride>> = RwLock::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionOverride {
+    Language(Language),
+    Skip,
+}
+
+impl<'de> Deserialize<'de> for ExtensionOverride {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        if value == "skip" {
+            return Ok(Self::Skip);
+        }
+        Language::from_str(&value, true)
+            .map(Self::Language)
+            .map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "unknown language `{value}` (expected a --language value or `skip`)"
+                ))
+            })
+    }
+}
+
+/// Sets the overrides for every later lookup, keyed by extension with or
+/// without the leading dot.
+pub fn set_overrides(overrides: &BTreeMap<String, ExtensionOverride>) {
+    *OVERRIDES.write().unwrap() = overrides
+        .iter()
+        .map(|(extension, value)| (extension.trim_start_matches('.').to_string(), *value))
+        .collect();
+}
+
+/// The override for `path`, if one of its extensions has one.
+pub fn override_for(path: &Path) -> Option<ExtensionOverride> {
+    let name = path.file_name()?.to_str()?;
+    let overrides = OVERRIDES.read().unwrap();
+    overrides
+        .iter()
+        .filter(|(extension, _)| {
+            name.len() > extension.len() + 1
+                && name.ends_with(extension.as_str())
+                && name.as_bytes()[name.len() - extension.len() - 1] == b'.'
+        })
+        .max_by_key(|(extension, _)| extension.len())
+        .map(|(_, value)| *value)
+}
+
+/// Whether a scan of `language` keeps `path`: files with no override or one
+/// to a language parsed along with `language`.
+pub fn keeps(path: &Path, language: Language) -> bool {
+    match override_for(path) {
+        None => true,
+        Some(ExtensionOverride::Skip) => false,
+        Some(ExtensionOverride::Language(mapped)) => same_project(mapped, language),
+    }
+}
+
+/// Applies the overrides to the user code a loader found for a `language`
+/// project at `root`: drops skipped files and those mapped to another
+/// language, and adds the files whose extensions are mapped to `language`.
+pub fn apply_to_user_code(files: &mut Vec<SourceFile>, root: &Path, language: Language) {
+    files.retain(|file| keeps(&file.path, language));
+    let maps_to_language = |value: Option<&ExtensionOverride>| matches!(value, Some(ExtensionOverride::Language(mapped)) if same_project(*mapped, language));
+    if !OVERRIDES
+        .read()
+        .unwrap()
+        .values()
+        .any(|v| maps_to_language(Some(v)))
+    {
+        return;
+    }
+
+    let known: HashSet<PathBuf> = files.iter().map(|file| file.path.clone()).collect();
+    let walker = WalkDir::new(root).into_iter().filter_entry(|entry| {
+        !entry.file_type().is_dir()
+            || !EXCLUDED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+    });
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        if !entry.file_type().is_file()
+            || known.contains(entry.path())
+            || !maps_to_language(override_for(entry.path()).as_ref())
+        {
+            continue;
+        }
+        let metadata = entry.metadata().ok();
+        files.push(SourceFile {
+            path: entry.path().to_path_buf(),
+            language,
+            source_type: SourceType::UserCode,
+            package: None,
+            metadata: FileMetadata {
+                size: metadata.as_ref().map_or(0, |m| m.len()),
+                modified: metadata.and_then(|m| m.modified().ok()),
+                hash: None,
+            },
+        });
+    }
+}
+
+/// JavaScript and TypeScript files are scanned together as one project.
+fn same_project(a: Language, b: Language) -> bool {
+    let web = |l| matches!(l, Language::Javascript | Language::Typescript);
+    a == b || (web(a) && web(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Mutex;
+
+    /// Serializes the tests that set the process-wide overrides.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn overrides(pairs: &[(&str, ExtensionOverride)]) -> BTreeMap<String, ExtensionOverride> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_override_for() {
+        let _guard = LOCK.lock().unwrap();
+        set_overrides(&overrides(&[
+            (".mts", ExtensionOverride::Language(Language::Typescript)),
+            ("js", ExtensionOverride::Language(Language::Javascript)),
+            ("min.js", ExtensionOverride::Skip),
+        ]));
+
+        assert_eq!(
+            override_for(Path::new("src/util.mts")),
+            Some(ExtensionOverride::Language(Language::Typescript))
+        );
+        assert_eq!(
+            override_for(Path::new("dist/app.min.js")),
+            Some(ExtensionOverride::Skip)
+        );
+        assert_eq!(
+            override_for(Path::new("src/admin.js")),
+            Some(ExtensionOverride::Language(Language::Javascript))
+        );
+        assert_eq!(
+            override_for(Path::new("src/min.js")),
+            Some(ExtensionOverride::Language(Language::Javascript))
+        );
+        assert_eq!(override_for(Path::new("mts")), None);
+        set_overrides(&BTreeMap::new());
+    }
+
+    #[test]
+    fn test_apply_to_user_code() {
+        let _guard = LOCK.lock().unwrap();
+        let root = tempfile::TempDir::new().unwrap();
+        let root = root.path();
+        for file in [
+            "app.go",
+            "page.gotmpl",
+            "gen/keys.go.tmpl",
+            "tools/x.pyx",
+            "vendor/y.gotmpl",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        set_overrides(&overrides(&[
+            ("gotmpl", ExtensionOverride::Skip),
+            ("go.tmpl", ExtensionOverride::Language(Language::Go)),
+            ("pyx", ExtensionOverride::Language(Language::Python)),
+        ]));
+
+        let user_code = |path: &str| SourceFile {
+            path: root.join(path),
+            language: Language::Go,
+            source_type: SourceType::UserCode,
+            package: None,
+            metadata: FileMetadata {
+                size: 0,
+                modified: None,
+                hash: None,
+            },
+        };
+        let mut files = vec![user_code("app.go"), user_code("page.gotmpl")];
+        apply_to_user_code(&mut files, root, Language::Go);
+        let mut paths: Vec<PathBuf> = files.into_iter().map(|file| file.path).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![root.join("app.go"), root.join("gen/keys.go.tmpl")]
+        );
+        set_overrides(&BTreeMap::new());
+    }
+}
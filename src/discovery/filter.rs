@@ -1,13 +1,81 @@
+use std::collections::HashSet;
 use std::path::Path;
 
+use crate::classifier::RulesClassifier;
 use crate::cli::Language;
 
 pub trait ImportFileFilter: Send + Sync {
     fn has_matching_imports(&self, file_path: &Path) -> Result<bool, FilterError>;
 
+    /// Whether one of the imports the filter looks for comes from the
+    /// dependency `package`, known before any of its files are read.
+    fn matches_package(&self, package: &str) -> bool {
+        let _ = package;
+        false
+    }
+
     fn language(&self) -> Language;
 }
 
+/// Whether one of the import paths in `patterns` is `package` or lies
+/// within it (`@noble/hashes/sha256`, `golang.org/x/crypto/pbkdf2`,
+/// `cryptography.hazmat`).
+pub fn package_provides_import(package: &str, patterns: &[(String, String)]) -> bool {
+    patterns.iter().any(|(import_path, _)| {
+        import_path
+            .strip_prefix(package)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '.', ':']))
+    })
+}
+
+/// Rule-driven overrides of the import check, from the classifier's
+/// `weak_algorithms` and `non_crypto_imports` lists.
+#[derive(Debug, Clone, Default)]
+pub struct FilterTokens {
+    weak_algorithms: HashSet<String>,
+    non_crypto_imports: HashSet<String>,
+}
+
+impl FilterTokens {
+    pub fn from_classifier(classifier: &RulesClassifier) -> Self {
+        Self {
+            weak_algorithms: classifier.get_weak_algorithms().iter().cloned().collect(),
+            non_crypto_imports: classifier
+                .get_non_crypto_imports()
+                .iter()
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Whether `content` has a weak algorithm name as a whole word, in any
+    /// case: `DES.new`, `createHash('md5')`, `"des-ede3-cbc"`.
+    pub fn names_weak_algorithm(&self, content: &str) -> bool {
+        !self.weak_algorithms.is_empty()
+            && content
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| {
+                    !word.is_empty() && self.weak_algorithms.contains(&word.to_ascii_lowercase())
+                })
+    }
+
+    pub fn is_non_crypto_import(&self, import_path: &str) -> bool {
+        self.non_crypto_imports
+            .contains(&import_path.to_lowercase())
+    }
+
+    /// Whether a file with `content` should be scanned: it names a weak
+    /// algorithm, or matches the pattern of an import that is not listed as
+    /// non-crypto. `patterns` pairs each import path with one of its
+    /// patterns.
+    pub fn matches(&self, content: &str, patterns: &[(String, String)]) -> bool {
+        self.names_weak_algorithm(content)
+            || patterns.iter().any(|(import_path, pattern)| {
+                content.contains(pattern.as_str()) && !self.is_non_crypto_import(import_path)
+            })
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum FilterError {
     #[error("IO error: {0}")]
@@ -15,12 +83,32 @@ pub enum FilterError {
 
     #[error("File read error: {0}")]
     FileRead(String),
+
+    #[error("File too large: {size} bytes (max: {max} bytes)")]
+    TooLarge { size: u64, max: u64 },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_package_provides_import() {
+        let patterns: Vec<(String, String)> =
+            ["@noble/hashes/sha256", "golang.org/x/crypto/pbkdf2"]
+                .into_iter()
+                .map(|path| (path.to_string(), format!("\"{path}\"")))
+                .collect();
+        assert!(package_provides_import("@noble/hashes", &patterns));
+        assert!(package_provides_import("golang.org/x/crypto", &patterns));
+        assert!(package_provides_import(
+            "golang.org/x/crypto/pbkdf2",
+            &patterns
+        ));
+        assert!(!package_provides_import("@noble/hash", &patterns));
+        assert!(!package_provides_import("golang.org/x/net", &patterns));
+    }
+
     #[test]
     fn test_import_file_filter_trait_compiles() {
         struct TestFilter;
@@ -40,6 +128,25 @@ mod tests {
         assert!(filter.has_matching_imports(&file_path).is_ok());
     }
 
+    #[test]
+    fn test_filter_tokens() {
+        let classifier = RulesClassifier::from_json_str(
+            r#"{"weak_algorithms": ["DES", "rc4", "md5"], "non_crypto_imports": ["base64"]}"#,
+        )
+        .unwrap();
+        let tokens = FilterTokens::from_classifier(&classifier);
+        let patterns = vec![
+            ("base64".to_string(), "import base64".to_string()),
+            ("hashlib".to_string(), "import hashlib".to_string()),
+        ];
+
+        assert!(!tokens.matches("import base64\nbase64.b64encode(x)", &patterns));
+        assert!(tokens.matches("import hashlib", &patterns));
+        assert!(tokens.matches("c = getattr(m, 'DES').new(k)", &patterns));
+        assert!(tokens.matches("createCipheriv('rc4', key, '')", &patterns));
+        assert!(!tokens.matches("describe('codes', () => {})", &patterns));
+    }
+
     #[test]
     fn test_filter_error_display() {
         let io_error = std::io::Error::from(std::io::ErrorKind::NotFound);
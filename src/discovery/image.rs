@@ -0,0 +1,654 @@
+//! Container images as a scan target: the layers of a `docker save`
+//! tarball or an OCI image layout are applied in order into one merged
+//! filesystem, remembering which layer wrote each file.
+//!
+//! Symbolic links are not recreated, so nothing in a layer can point
+//! extraction outside the merged tree; hard links are copied.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use tar::{Archive, EntryType};
+use tempfile::TempDir;
+use thiserror::Error;
+use tracing::debug;
+
+use super::components::find_components;
+use super::roots::{compiled_roots, scan_roots, RootError, ScanRoot};
+use super::LanguageRegistry;
+use crate::output::{rebase_paths, ImageSummary, JsonOutput};
+
+const WHITEOUT_PREFIX: &str = ".wh.";
+const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Error, Debug)]
+pub enum ImageError {
+    #[error(
+        "{} is not a local image. Pulling from a registry is not supported; \
+         export the image first with `docker save IMAGE -o image.tar` \
+         or `skopeo copy docker://IMAGE oci:DIR`",
+        path.display()
+    )]
+    NotLocal { path: PathBuf },
+
+    #[error("{} is not an image tarball or OCI image layout (no manifest.json or index.json)", path.display())]
+    UnknownFormat { path: PathBuf },
+
+    #[error("invalid image: {0}")]
+    Invalid(String),
+
+    #[error("layer {digest} is {compression}-compressed, which is not supported")]
+    UnsupportedLayer { digest: String, compression: String },
+
+    #[error("no project of a supported language found in the image")]
+    NoProject,
+
+    #[error(transparent)]
+    Roots(#[from] RootError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// One filesystem layer, bottom first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageLayer {
+    /// The uncompressed layer's digest (`diff_id`) when the image config
+    /// lists it, else the blob's.
+    pub digest: String,
+    /// The Dockerfile instruction that made the layer, from the history.
+    pub created_by: Option<String>,
+}
+
+/// An image unpacked into a fresh temporary directory, removed on drop.
+pub struct ContainerImage {
+    dir: TempDir,
+    rootfs: PathBuf,
+    layers: Vec<ImageLayer>,
+    /// The layer that last wrote each file, by path relative to `rootfs`.
+    owners: HashMap<PathBuf, usize>,
+}
+
+impl ContainerImage {
+    /// Unpacks the image at `path`: a tarball from `docker save` or of an
+    /// OCI image layout (optionally gzipped), or an OCI layout directory.
+    /// Registry references are not pulled.
+    pub fn unpack(path: &Path) -> Result<Self, ImageError> {
+        if !path.exists() {
+            return Err(ImageError::NotLocal {
+                path: path.to_path_buf(),
+            });
+        }
+        // Removed on drop, including when unpacking fails below.
+        let dir = tempfile::Builder::new()
+            .prefix("argflow-image-")
+            .tempdir()?;
+        let mut image = Self {
+            rootfs: dir.path().join("rootfs"),
+            dir,
+            layers: Vec::new(),
+            owners: HashMap::new(),
+        };
+        image.fill(path)?;
+        Ok(image)
+    }
+
+    fn fill(&mut self, path: &Path) -> Result<(), ImageError> {
+        let source = if path.is_dir() {
+            path.to_path_buf()
+        } else {
+            let extracted = self.dir.path().join("archive");
+            fs::create_dir(&extracted)?;
+            Archive::new(decompressed(File::open(path)?)?).unpack(&extracted)?;
+            extracted
+        };
+
+        let manifest = if source.join("index.json").is_file() {
+            read_oci_layout(&source)?
+        } else if source.join("manifest.json").is_file() {
+            read_docker_archive(&source)?
+        } else {
+            return Err(ImageError::UnknownFormat {
+                path: path.to_path_buf(),
+            });
+        };
+
+        fs::create_dir(&self.rootfs)?;
+        for (index, blob) in manifest.blobs.iter().enumerate() {
+            let layer = &manifest.layers[index];
+            debug!(layer = %layer.digest, "applying layer");
+            let reader = decompressed(File::open(source.join(blob))?).map_err(|e| match e {
+                ImageError::UnsupportedLayer { compression, .. } => ImageError::UnsupportedLayer {
+                    digest: layer.digest.clone(),
+                    compression,
+                },
+                other => other,
+            })?;
+            apply_layer(reader, &self.rootfs, index, &mut self.owners)?;
+        }
+        self.layers = manifest.layers;
+        Ok(())
+    }
+
+    /// The merged filesystem.
+    pub fn rootfs(&self) -> &Path {
+        &self.rootfs
+    }
+
+    pub fn layers(&self) -> &[ImageLayer] {
+        &self.layers
+    }
+
+    /// The index in [`ContainerImage::layers`] of the layer that wrote
+    /// `path`, given under [`ContainerImage::rootfs`] or relative to it.
+    pub fn layer_of(&self, path: &Path) -> Option<usize> {
+        let relative = path.strip_prefix(&self.rootfs).unwrap_or(path);
+        self.owners.get(relative).copied()
+    }
+
+    /// The projects in the image to scan, each in the languages its
+    /// manifests name.
+    pub fn scan_roots(&self, registry: &LanguageRegistry) -> Result<Vec<ScanRoot>, ImageError> {
+        // Projects are found by their manifests; the image root has none.
+        if find_components(&self.rootfs, registry)
+            .iter()
+            .all(|component| component.languages.is_empty())
+        {
+            return Err(ImageError::NoProject);
+        }
+        Ok(compiled_roots(
+            scan_roots(registry, &self.rootfs, None, true)?,
+            None,
+        )?)
+    }
+
+    /// Rewrites the paths in `report` to where they are in the image and
+    /// attributes its findings to the layers that wrote their files.
+    /// `reference` is the tarball or OCI layout the image came from.
+    pub fn attribute(&self, reference: &str, report: &mut JsonOutput) {
+        rebase_paths(report, &self.rootfs.to_string_lossy(), "");
+        let summary = ImageSummary::new(
+            reference,
+            self.layers
+                .iter()
+                .map(|layer| (layer.digest.clone(), layer.created_by.clone())),
+            report,
+            |file| self.layer_of(Path::new(file.trim_start_matches('/'))),
+        );
+        report.image = Some(summary);
+    }
+}
+
+/// Layer blobs, with the layers they hold.
+struct ImageManifest {
+    blobs: Vec<PathBuf>,
+    layers: Vec<ImageLayer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DockerManifest {
+    config: String,
+    layers: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct ImageConfig {
+    #[serde(default)]
+    rootfs: Option<RootFs>,
+    #[serde(default)]
+    history: Vec<History>,
+}
+
+#[derive(Deserialize)]
+struct RootFs {
+    #[serde(default)]
+    diff_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct History {
+    #[serde(default)]
+    created_by: Option<String>,
+    #[serde(default)]
+    empty_layer: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    #[serde(default)]
+    media_type: String,
+    digest: String,
+}
+
+#[derive(Deserialize)]
+struct OciIndex {
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+struct OciManifest {
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+}
+
+fn read_docker_archive(dir: &Path) -> Result<ImageManifest, ImageError> {
+    let manifests: Vec<DockerManifest> = read_json(&dir.join("manifest.json"))?;
+    let manifest = match manifests.as_slice() {
+        [manifest] => manifest,
+        [] => {
+            return Err(ImageError::Invalid(
+                "manifest.json lists no image".to_string(),
+            ))
+        }
+        _ => {
+            return Err(ImageError::Invalid(
+                "the tarball holds several images; save one at a time".to_string(),
+            ))
+        }
+    };
+    let config: ImageConfig = read_json(&dir.join(&manifest.config)).unwrap_or_default();
+    let blobs = manifest
+        .layers
+        .iter()
+        .map(|layer| {
+            contained_path(Path::new(layer))
+                .ok_or_else(|| ImageError::Invalid(format!("layer path {layer} leaves the image")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let fallback: Vec<String> = manifest.layers.iter().map(|l| blob_digest(l)).collect();
+    Ok(ImageManifest {
+        layers: image_layers(&config, &fallback),
+        blobs,
+    })
+}
+
+fn read_oci_layout(dir: &Path) -> Result<ImageManifest, ImageError> {
+    let mut descriptor = read_json::<OciIndex>(&dir.join("index.json"))?
+        .manifests
+        .into_iter()
+        .next()
+        .ok_or_else(|| ImageError::Invalid("index.json lists no manifest".to_string()))?;
+    // A multi-platform image: take its first manifest.
+    while descriptor.media_type.ends_with("image.index.v1+json")
+        || descriptor.media_type.ends_with("manifest.list.v2+json")
+    {
+        descriptor = read_json::<OciIndex>(&blob_path(dir, &descriptor.digest)?)?
+            .manifests
+            .into_iter()
+            .next()
+            .ok_or_else(|| ImageError::Invalid("image index lists no manifest".to_string()))?;
+    }
+    let manifest: OciManifest = read_json(&blob_path(dir, &descriptor.digest)?)?;
+    let config: ImageConfig =
+        read_json(&blob_path(dir, &manifest.config.digest)?).unwrap_or_default();
+    let blobs = manifest
+        .layers
+        .iter()
+        .map(|layer| blob_path(dir, &layer.digest))
+        .collect::<Result<Vec<_>, _>>()?;
+    let fallback: Vec<String> = manifest.layers.into_iter().map(|l| l.digest).collect();
+    Ok(ImageManifest {
+        layers: image_layers(&config, &fallback),
+        blobs,
+    })
+}
+
+/// A layer per entry of `blob_digests`, with the config's `diff_ids` and
+/// the history entries that made a layer.
+fn image_layers(config: &ImageConfig, blob_digests: &[String]) -> Vec<ImageLayer> {
+    let diff_ids = config
+        .rootfs
+        .as_ref()
+        .map(|rootfs| rootfs.diff_ids.as_slice())
+        .filter(|ids| ids.len() == blob_digests.len());
+    let mut history = config.history.iter().filter(|h| !h.empty_layer);
+    blob_digests
+        .iter()
+        .enumerate()
+        .map(|(i, blob)| ImageLayer {
+            digest: diff_ids.map_or_else(|| blob.clone(), |ids| ids[i].clone()),
+            created_by: history.next().and_then(|h| h.created_by.clone()),
+        })
+        .collect()
+}
+
+/// `blobs/sha256/<hex>` for `sha256:<hex>`.
+fn blob_path(dir: &Path, digest: &str) -> Result<PathBuf, ImageError> {
+    let (algorithm, hex) = digest
+        .split_once(':')
+        .filter(|(a, h)| is_plain_name(a) && is_plain_name(h))
+        .ok_or_else(|| ImageError::Invalid(format!("malformed digest {digest}")))?;
+    Ok(dir.join("blobs").join(algorithm).join(hex))
+}
+
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The digest in a `docker save` layer path: `<hex>/layer.tar` or
+/// `blobs/sha256/<hex>`.
+fn blob_digest(layer: &str) -> String {
+    let mut parts = layer.rsplit('/');
+    match (parts.next(), parts.next()) {
+        (Some("layer.tar"), Some(hex)) => format!("sha256:{hex}"),
+        (Some(hex), Some(algorithm)) => format!("{algorithm}:{hex}"),
+        _ => layer.to_string(),
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ImageError> {
+    let content = fs::read(path)?;
+    serde_json::from_slice(&content)
+        .map_err(|e| ImageError::Invalid(format!("{}: {e}", path.display())))
+}
+
+/// `file` with gzip undone; tar layers are also stored uncompressed.
+fn decompressed(file: File) -> Result<Box<dyn Read>, ImageError> {
+    let mut reader = BufReader::new(file);
+    let mut magic = [0u8; 4];
+    let read = read_prefix(&mut reader, &mut magic)?;
+    let prefix = io::Cursor::new(magic[..read].to_vec()).chain(reader);
+    if magic[..read].starts_with(GZIP_MAGIC) {
+        Ok(Box::new(GzDecoder::new(prefix)))
+    } else if magic[..read].starts_with(ZSTD_MAGIC) {
+        Err(ImageError::UnsupportedLayer {
+            digest: String::new(),
+            compression: "zstd".to_string(),
+        })
+    } else {
+        Ok(Box::new(prefix))
+    }
+}
+
+fn read_prefix(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    Ok(read)
+}
+
+/// Applies one layer tarball to `rootfs`, honouring whiteouts, and records
+/// `layer` as the owner of every file it writes.
+fn apply_layer(
+    reader: impl Read,
+    rootfs: &Path,
+    layer: usize,
+    owners: &mut HashMap<PathBuf, usize>,
+) -> Result<(), ImageError> {
+    let mut archive = Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(relative) = contained_path(&entry.path()?) else {
+            continue;
+        };
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let parent = relative.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        if name == OPAQUE_WHITEOUT {
+            let dir = rootfs.join(&parent);
+            if dir.is_dir() {
+                for child in fs::read_dir(&dir)? {
+                    remove(&child?.path())?;
+                }
+            }
+            owners.retain(|path, _| !path.starts_with(&parent) || path == &parent);
+            continue;
+        }
+        if let Some(hidden) = name.strip_prefix(WHITEOUT_PREFIX) {
+            let target = parent.join(hidden);
+            remove(&rootfs.join(&target))?;
+            owners.retain(|path, _| !path.starts_with(&target));
+            continue;
+        }
+
+        let destination = rootfs.join(&relative);
+        match entry.header().entry_type() {
+            EntryType::Directory => {
+                if destination.is_file() {
+                    fs::remove_file(&destination)?;
+                }
+                fs::create_dir_all(&destination)?;
+            }
+            EntryType::Regular | EntryType::Continuous => {
+                prepare_file(&destination)?;
+                io::copy(&mut entry, &mut File::create(&destination)?)?;
+                owners.insert(relative, layer);
+            }
+            EntryType::Link => {
+                let Some(target) = entry
+                    .link_name()?
+                    .and_then(|target| contained_path(&target))
+                else {
+                    continue;
+                };
+                if rootfs.join(&target).is_file() {
+                    prepare_file(&destination)?;
+                    fs::copy(rootfs.join(&target), &destination)?;
+                    owners.insert(relative, layer);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// `path` relative to the layer root, or `None` if it would leave it.
+pub(crate) fn contained_path(path: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir | Component::RootDir => {}
+            Component::ParentDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+fn prepare_file(destination: &Path) -> io::Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if destination.is_dir() {
+        fs::remove_dir_all(destination)?;
+    }
+    Ok(())
+}
+
+fn remove(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use tempfile::TempDir;
+
+    fn layer(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            // Set by hand, since the builder refuses paths that leave the
+            // archive.
+            header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        io::Write::write_all(&mut encoder, data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn archive(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, data)| (*path, std::str::from_utf8(data).unwrap_or("")))
+            .collect();
+        layer(&files)
+    }
+
+    #[test]
+    fn test_unpack_docker_archive() {
+        let base = layer(&[
+            ("app/requirements.txt", "cryptography\n"),
+            ("app/main.py", "import hashlib\n"),
+            ("app/old.py", "import md5\n"),
+            ("../escape.py", "x = 1\n"),
+        ]);
+        let update = gzip(&layer(&[
+            ("app/.wh.old.py", ""),
+            ("app/main.py", "import hashlib\nhashlib.md5()\n"),
+        ]));
+        let config = r#"{
+            "rootfs": {"type": "layers", "diff_ids": ["sha256:aaa", "sha256:bbb"]},
+            "history": [
+                {"created_by": "COPY app /app"},
+                {"created_by": "ENV A=1", "empty_layer": true},
+                {"created_by": "RUN patch"}
+            ]
+        }"#;
+        let manifest =
+            r#"[{"Config": "config.json", "Layers": ["one/layer.tar", "two/layer.tar"]}]"#;
+
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, data) in [
+            ("manifest.json", manifest.as_bytes()),
+            ("config.json", config.as_bytes()),
+            ("one/layer.tar", &base),
+            ("two/layer.tar", &update),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, data).unwrap();
+        }
+        let temp = TempDir::new().unwrap();
+        let tarball = temp.path().join("image.tar");
+        fs::write(&tarball, builder.into_inner().unwrap()).unwrap();
+
+        let image = ContainerImage::unpack(&tarball).unwrap();
+        let rootfs = image.rootfs();
+        assert_eq!(
+            image.layers(),
+            &[
+                ImageLayer {
+                    digest: "sha256:aaa".to_string(),
+                    created_by: Some("COPY app /app".to_string()),
+                },
+                ImageLayer {
+                    digest: "sha256:bbb".to_string(),
+                    created_by: Some("RUN patch".to_string()),
+                },
+            ]
+        );
+        assert!(!rootfs.join("app/old.py").exists());
+        assert!(!rootfs.join("app/.wh.old.py").exists());
+        assert!(!temp.path().join("escape.py").exists());
+        assert_eq!(
+            fs::read_to_string(rootfs.join("app/main.py")).unwrap(),
+            "import hashlib\nhashlib.md5()\n"
+        );
+        assert_eq!(image.layer_of(&rootfs.join("app/main.py")), Some(1));
+        assert_eq!(image.layer_of(Path::new("app/requirements.txt")), Some(0));
+        assert_eq!(image.layer_of(Path::new("app/old.py")), None);
+
+        let roots = image.scan_roots(&LanguageRegistry::new()).unwrap();
+        let found: Vec<_> = roots.iter().map(|r| (&r.path, r.language)).collect();
+        assert_eq!(
+            found,
+            vec![(&rootfs.join("app"), crate::cli::Language::Python)]
+        );
+
+        let main = rootfs.join("app/main.py");
+        let mut report = crate::test_support::report(
+            vec![crate::test_support::finding(
+                &main.to_string_lossy(),
+                2,
+                "hashlib.md5",
+            )],
+            Vec::new(),
+        );
+        image.attribute("image.tar", &mut report);
+        assert_eq!(report.findings[0].file, "/app/main.py");
+        let summary = report.image.unwrap();
+        assert_eq!(summary.reference, "image.tar");
+        let totals: Vec<_> = summary.layers.iter().map(|l| l.total_findings).collect();
+        assert_eq!(totals, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_unpack_oci_layout() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let blobs = dir.join("blobs/sha256");
+        fs::create_dir_all(&blobs).unwrap();
+        fs::write(dir.join("oci-layout"), r#"{"imageLayoutVersion": "1.0.0"}"#).unwrap();
+        fs::write(
+            blobs.join("layer"),
+            gzip(&archive(&[(
+                "srv/index.js",
+                b"require('crypto')\n".to_vec(),
+            )])),
+        )
+        .unwrap();
+        fs::write(blobs.join("config"), "{}").unwrap();
+        fs::write(
+            blobs.join("manifest"),
+            r#"{"config": {"mediaType": "application/vnd.oci.image.config.v1+json", "digest": "sha256:config"},
+                "layers": [{"mediaType": "application/vnd.oci.image.layer.v1.tar+gzip", "digest": "sha256:layer"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("index.json"),
+            r#"{"manifests": [{"mediaType": "application/vnd.oci.image.manifest.v1+json", "digest": "sha256:manifest"}]}"#,
+        )
+        .unwrap();
+
+        let image = ContainerImage::unpack(dir).unwrap();
+        assert_eq!(image.layers()[0].digest, "sha256:layer");
+        assert!(image.rootfs().join("srv/index.js").is_file());
+        assert_eq!(image.layer_of(Path::new("srv/index.js")), Some(0));
+
+        assert!(matches!(
+            ContainerImage::unpack(&blobs),
+            Err(ImageError::UnknownFormat { .. })
+        ));
+
+        // A registry reference is not pulled
+        let error = ContainerImage::unpack(Path::new("myservice:1.4"))
+            .err()
+            .unwrap();
+        assert!(matches!(error, ImageError::NotLocal { .. }));
+        assert!(error.to_string().contains("docker save"));
+    }
+}
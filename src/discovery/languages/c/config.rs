@@ -0,0 +1,14 @@
+pub const FILE_EXTENSIONS: &[&str] = &["c", "h"];
+
+/// Build and package files of C and C++ projects alike.
+pub const BUILD_FILES: &[&str] = &[
+    "CMakeLists.txt",
+    "meson.build",
+    "configure.ac",
+    "vcpkg.json",
+    "conanfile.txt",
+];
+
+pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "build", "out", "CMakeFiles"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
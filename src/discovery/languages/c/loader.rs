@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::cli::Language;
+use crate::discovery::cache::DiscoveryCache;
+use crate::discovery::loader::{LoadError, PackageLoader};
+use crate::discovery::utils::walk_source_files;
+use crate::discovery::{FileMetadata, SourceFile, SourceType};
+
+use super::config::*;
+
+pub struct CPackageLoader;
+
+impl PackageLoader for CPackageLoader {
+    fn load_user_code(&self, root: &Path) -> Result<Vec<SourceFile>, LoadError> {
+        if !root.exists() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path does not exist: {}",
+                root.display()
+            )));
+        }
+
+        if !root.is_dir() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path is not a directory: {}",
+                root.display()
+            )));
+        }
+
+        let mut all_files = Vec::new();
+        for ext in FILE_EXTENSIONS {
+            let paths = walk_source_files(root, ext, EXCLUDED_DIRS, false)?;
+            all_files.extend(paths.into_iter().map(|path| SourceFile {
+                metadata: get_file_metadata(&path),
+                path,
+                language: Language::C,
+                source_type: SourceType::UserCode,
+                package: None,
+            }));
+        }
+
+        Ok(all_files)
+    }
+
+    /// Libraries are linked from the system or a package manager's
+    /// binaries, so there are no dependency sources to scan.
+    fn load_dependencies(
+        &self,
+        root: &Path,
+        _cache: &mut DiscoveryCache,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        debug!(root = %root.display(), "no C dependency sources to scan");
+        Ok(Vec::new())
+    }
+
+    fn language(&self) -> Language {
+        Language::C
+    }
+}
+
+fn get_file_metadata(path: &PathBuf) -> FileMetadata {
+    fs::metadata(path)
+        .ok()
+        .map(|m| FileMetadata {
+            size: m.len(),
+            modified: m.modified().ok(),
+            hash: None,
+        })
+        .unwrap_or_else(|| FileMetadata {
+            size: 0,
+            modified: None,
+            hash: None,
+        })
+}
@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod filter;
+pub mod loader;
+
+pub use filter::CImportFilter;
+pub use loader::CPackageLoader;
+
+pub struct CModule;
+
+impl LanguageModule for CModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(CPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(CImportFilter::from_bundled().expect("Failed to load bundled C import filter"))
+    }
+
+    fn language(&self) -> Language {
+        Language::C
+    }
+
+    /// A project with C++ sources is left to the C++ module.
+    fn detect(&self, root: &Path) -> bool {
+        config::BUILD_FILES
+            .iter()
+            .any(|name| root.join(name).exists())
+            && !super::cpp::has_sources(root)
+    }
+}
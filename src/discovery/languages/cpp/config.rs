@@ -0,0 +1,5 @@
+pub const FILE_EXTENSIONS: &[&str] = &["cpp", "cc", "cxx", "hpp", "hh", "hxx", "h"];
+
+pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "build", "out", "CMakeFiles"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::c;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+use crate::discovery::utils::walk_source_files;
+
+pub mod config;
+pub mod filter;
+pub mod loader;
+
+pub use filter::CppImportFilter;
+pub use loader::CppPackageLoader;
+
+pub struct CppModule;
+
+impl LanguageModule for CppModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(CppPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(CppImportFilter::from_bundled().expect("Failed to load bundled C++ import filter"))
+    }
+
+    fn language(&self) -> Language {
+        Language::Cpp
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        c::config::BUILD_FILES
+            .iter()
+            .any(|name| root.join(name).exists())
+            && has_sources(root)
+    }
+}
+
+/// Whether `root` holds any C++ source file. C and C++ projects share
+/// their build files, so the sources tell them apart.
+pub fn has_sources(root: &Path) -> bool {
+    ["cpp", "cc", "cxx"].iter().any(|ext| {
+        walk_source_files(root, ext, config::EXCLUDED_DIRS, false)
+            .is_ok_and(|files| !files.is_empty())
+    })
+}
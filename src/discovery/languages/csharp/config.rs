@@ -0,0 +1,8 @@
+pub const FILE_EXTENSIONS: &[&str] = &["cs"];
+
+pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "bin", "obj", "packages", ".vs"];
+
+/// Project and solution files that mark a .NET project root.
+pub const PROJECT_EXTENSIONS: &[&str] = &["csproj", "sln"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
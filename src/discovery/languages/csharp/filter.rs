@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Language;
+use crate::discovery::filter::{FilterError, FilterTokens, ImportFileFilter};
+use serde::Deserialize;
+
+use super::config::*;
+
+#[derive(Debug, Deserialize)]
+struct MappingsFile {
+    mappings: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+}
+
+pub struct CSharpImportFilter {
+    /// Import path and one of its patterns.
+    import_patterns: Vec<(String, String)>,
+    tokens: FilterTokens,
+}
+
+impl CSharpImportFilter {
+    pub fn new(preset_paths: &[PathBuf]) -> Result<Self, FilterError> {
+        let import_patterns = load_import_patterns_from_presets(preset_paths, "csharp")?;
+        Ok(Self {
+            import_patterns,
+            tokens: FilterTokens::default(),
+        })
+    }
+
+    pub fn from_bundled() -> Result<Self, FilterError> {
+        let preset_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("presets")
+            .join("crypto");
+        Self::new(&[preset_dir])
+    }
+
+    /// Applies the classifier's weak-algorithm and non-crypto import lists.
+    pub fn with_tokens(mut self, tokens: FilterTokens) -> Self {
+        self.tokens = tokens;
+        self
+    }
+}
+
+impl ImportFileFilter for CSharpImportFilter {
+    fn has_matching_imports(&self, file_path: &Path) -> Result<bool, FilterError> {
+        let metadata = fs::metadata(file_path).map_err(|e| {
+            FilterError::FileRead(format!(
+                "Failed to read metadata for {}: {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+
+        if metadata.len() > MAX_FILE_SIZE {
+            return Err(FilterError::TooLarge {
+                size: metadata.len(),
+                max: MAX_FILE_SIZE,
+            });
+        }
+
+        let content = fs::read_to_string(file_path).map_err(|e| {
+            FilterError::FileRead(format!(
+                "Failed to read file {}: {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+
+        Ok(self.tokens.matches(&content, &self.import_patterns))
+    }
+
+    fn language(&self) -> Language {
+        Language::CSharp
+    }
+}
+
+fn load_import_patterns_from_presets(
+    preset_paths: &[PathBuf],
+    language: &str,
+) -> Result<Vec<(String, String)>, FilterError> {
+    let mut all_patterns = HashSet::new();
+
+    for preset_path in preset_paths {
+        let mappings_path = preset_path.join(language).join("mappings.json");
+        if mappings_path.exists() {
+            let patterns = load_import_patterns_from_file(&mappings_path)?;
+            all_patterns.extend(patterns);
+        }
+    }
+
+    if all_patterns.is_empty() {
+        return Err(FilterError::FileRead(format!(
+            "No {language} mappings found in any preset. Checked: {preset_paths:?}"
+        )));
+    }
+
+    Ok(all_patterns.into_iter().collect())
+}
+
+fn load_import_patterns_from_file(
+    mappings_path: &Path,
+) -> Result<Vec<(String, String)>, FilterError> {
+    let content = fs::read_to_string(mappings_path).map_err(|e| {
+        FilterError::FileRead(format!("Failed to read {}: {}", mappings_path.display(), e))
+    })?;
+
+    let file: MappingsFile = serde_json::from_str(&content).map_err(|e| {
+        FilterError::FileRead(format!(
+            "Failed to parse {}: {}",
+            mappings_path.display(),
+            e
+        ))
+    })?;
+
+    // C# mappings are keyed by type (`System.Security.Cryptography.Aes`),
+    // or by namespace for constructors, which are brought in with `using`
+    // on the namespace, aliased (`using Sha = ...SHA256;`) or written out
+    // in full at the call.
+    let mut patterns = HashSet::new();
+    for name in file.mappings.keys() {
+        patterns.insert((name.clone(), format!("{name};")));
+        patterns.insert((name.clone(), format!("{name}.")));
+        patterns.insert((name.clone(), format!("{name}(")));
+        if let Some((namespace, _)) = name.rsplit_once('.') {
+            patterns.insert((name.clone(), format!("using {namespace};")));
+        }
+    }
+
+    Ok(patterns.into_iter().collect())
+}
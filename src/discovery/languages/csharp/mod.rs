@@ -0,0 +1,44 @@
+use std::fs;
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod filter;
+pub mod loader;
+
+pub use filter::CSharpImportFilter;
+pub use loader::CSharpPackageLoader;
+
+pub struct CSharpModule;
+
+impl LanguageModule for CSharpModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(CSharpPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(
+            CSharpImportFilter::from_bundled().expect("Failed to load bundled C# import filter"),
+        )
+    }
+
+    fn language(&self) -> Language {
+        Language::CSharp
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        fs::read_dir(root).is_ok_and(|entries| {
+            entries.filter_map(|e| e.ok()).any(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| config::PROJECT_EXTENSIONS.contains(&ext))
+            })
+        })
+    }
+}
@@ -0,0 +1,308 @@
+//! Go files the scanner cannot analyze: assembly and cgo.
+//!
+//! Crypto implemented in `.s` files or behind `import "C"` never shows up as a
+//! call the scanner can resolve, so a package that looks clean may not be.
+//! These files are collected during discovery and, when their package imports
+//! crypto, reported as explicit coverage gaps.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::loader::LoadError;
+
+use super::config::{EXCLUDED_DIRS, FILE_EXTENSIONS, MAX_FILE_SIZE};
+
+pub const ASSEMBLY_EXTENSIONS: &[&str] = &["s", "S"];
+pub const CGO_SOURCE_EXTENSIONS: &[&str] = &["c", "h", "cc", "cpp", "hh", "hpp", "m"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GoArtifactKind {
+    /// Go assembly (`.s`).
+    Assembly,
+    /// A `.go` file that imports the `C` pseudo-package.
+    Cgo,
+    /// C/C++ sources compiled into a cgo package.
+    CgoSource,
+}
+
+impl GoArtifactKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Assembly => "go_assembly",
+            Self::Cgo => "cgo",
+            Self::CgoSource => "cgo_source",
+        }
+    }
+
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Assembly => "assembly is not analyzed; crypto implemented here is not reported",
+            Self::Cgo => "calls through cgo into C are not analyzed",
+            Self::CgoSource => "C sources compiled via cgo are not analyzed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoArtifact {
+    pub path: PathBuf,
+    pub kind: GoArtifactKind,
+    /// Directory of the Go package the file belongs to.
+    pub package_dir: PathBuf,
+}
+
+/// Walks `root` for assembly files, cgo files and the C sources of cgo packages.
+pub fn find_artifacts(root: &Path) -> Result<Vec<GoArtifact>, LoadError> {
+    let mut assembly = Vec::new();
+    let mut cgo = Vec::new();
+    let mut c_sources = Vec::new();
+
+    for entry in WalkDir::new(root).into_iter().filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        !(e.file_type().is_dir()
+            && e.depth() > 0
+            && (name.starts_with('.')
+                || name == "vendor"
+                || EXCLUDED_DIRS.contains(&name.as_ref())))
+    }) {
+        let entry = entry.map_err(|e| LoadError::DirectoryScanError {
+            path: root.to_path_buf(),
+            source: e,
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+
+        if ASSEMBLY_EXTENSIONS.contains(&ext) {
+            assembly.push(path.to_path_buf());
+        } else if FILE_EXTENSIONS.contains(&ext) {
+            if is_cgo_file(path) {
+                cgo.push(path.to_path_buf());
+            }
+        } else if CGO_SOURCE_EXTENSIONS.contains(&ext) {
+            c_sources.push(path.to_path_buf());
+        }
+    }
+
+    let cgo_dirs: Vec<PathBuf> = cgo.iter().filter_map(|p| parent_dir(p)).collect();
+
+    let mut artifacts: Vec<GoArtifact> = assembly
+        .into_iter()
+        .map(|p| artifact(p, GoArtifactKind::Assembly))
+        .chain(cgo.into_iter().map(|p| artifact(p, GoArtifactKind::Cgo)))
+        .chain(
+            c_sources
+                .into_iter()
+                .filter(|p| parent_dir(p).is_some_and(|d| cgo_dirs.contains(&d)))
+                .map(|p| artifact(p, GoArtifactKind::CgoSource)),
+        )
+        .collect();
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(artifacts)
+}
+
+/// Keeps artifacts whose package has at least one `.go` file matching `filter`.
+pub fn crypto_adjacent(
+    artifacts: Vec<GoArtifact>,
+    filter: &dyn ImportFileFilter,
+) -> Vec<GoArtifact> {
+    let mut package_matches: HashMap<PathBuf, bool> = HashMap::new();
+
+    artifacts
+        .into_iter()
+        .filter(|artifact| {
+            *package_matches
+                .entry(artifact.package_dir.clone())
+                .or_insert_with(|| package_imports_match(&artifact.package_dir, filter))
+        })
+        .collect()
+}
+
+fn package_imports_match(package_dir: &Path, filter: &dyn ImportFileFilter) -> bool {
+    let Ok(entries) = fs::read_dir(package_dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| FILE_EXTENSIONS.contains(&ext))
+            && filter.has_matching_imports(&path).unwrap_or(false)
+    })
+}
+
+fn is_cgo_file(path: &Path) -> bool {
+    if fs::metadata(path).map_or(true, |m| m.len() > MAX_FILE_SIZE) {
+        return false;
+    }
+    let Ok(content) = fs::read_to_string(path) else {
+        return false;
+    };
+
+    let mut in_import_block = false;
+    for line in content.lines().map(str::trim) {
+        if line == "import \"C\"" {
+            return true;
+        }
+        if line.starts_with("import (") {
+            in_import_block = true;
+        } else if in_import_block {
+            if line == ")" {
+                in_import_block = false;
+            } else if line == "\"C\"" {
+                return true;
+            }
+        } else if line.starts_with("func ") {
+            break;
+        }
+    }
+    false
+}
+
+fn parent_dir(path: &Path) -> Option<PathBuf> {
+    path.parent().map(Path::to_path_buf)
+}
+
+fn artifact(path: PathBuf, kind: GoArtifactKind) -> GoArtifact {
+    let package_dir = parent_dir(&path).unwrap_or_default();
+    GoArtifact {
+        path,
+        kind,
+        package_dir,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Language;
+    use crate::discovery::filter::FilterError;
+    use tempfile::TempDir;
+
+    struct ContainsFilter(&'static str);
+
+    impl ImportFileFilter for ContainsFilter {
+        fn has_matching_imports(&self, file_path: &Path) -> Result<bool, FilterError> {
+            Ok(fs::read_to_string(file_path)?.contains(self.0))
+        }
+
+        fn language(&self) -> Language {
+            Language::Go
+        }
+    }
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn kinds(artifacts: &[GoArtifact]) -> Vec<(String, GoArtifactKind)> {
+        artifacts
+            .iter()
+            .map(|a| {
+                (
+                    a.path.file_name().unwrap().to_string_lossy().to_string(),
+                    a.kind,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_artifacts_detects_assembly_and_cgo() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "hash/sum.go",
+            "package hash\nimport \"crypto/sha256\"\n",
+        );
+        write(root, "hash/sum_amd64.s", "TEXT ·block(SB),NOSPLIT,$0\n");
+        write(
+            root,
+            "ossl/wrap.go",
+            "package ossl\n\n// #include <openssl/evp.h>\nimport \"C\"\n",
+        );
+        write(root, "ossl/shim.c", "int shim(void) { return 0; }\n");
+        write(root, "plain/util.c", "int unrelated(void) { return 0; }\n");
+        write(root, "plain/main.go", "package plain\n");
+
+        let artifacts = find_artifacts(root).unwrap();
+        assert_eq!(
+            kinds(&artifacts),
+            vec![
+                ("sum_amd64.s".to_string(), GoArtifactKind::Assembly),
+                ("shim.c".to_string(), GoArtifactKind::CgoSource),
+                ("wrap.go".to_string(), GoArtifactKind::Cgo),
+            ]
+        );
+        assert_eq!(artifacts[0].package_dir, root.join("hash"));
+    }
+
+    #[test]
+    fn test_cgo_detected_inside_import_block() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "a.go",
+            "package a\n\nimport (\n\t\"fmt\"\n\t\"C\"\n)\n\nfunc f() { fmt.Println(\"C\") }\n",
+        );
+        write(
+            root,
+            "b.go",
+            "package a\n\nfunc g() string {\n\treturn \"C\"\n}\n",
+        );
+
+        let artifacts = find_artifacts(root).unwrap();
+        assert_eq!(
+            kinds(&artifacts),
+            vec![("a.go".to_string(), GoArtifactKind::Cgo)]
+        );
+    }
+
+    #[test]
+    fn test_find_artifacts_skips_vendor_and_testdata() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "vendor/x/asm.s", "");
+        write(root, "testdata/asm.s", "");
+        write(root, ".cache/asm.s", "");
+
+        assert!(find_artifacts(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_crypto_adjacent_filters_by_package_imports() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "hash/sum.go",
+            "package hash\nimport \"crypto/sha256\"\n",
+        );
+        write(root, "hash/sum_amd64.s", "");
+        write(root, "math/add.go", "package math\n");
+        write(root, "math/add_amd64.s", "");
+
+        let artifacts = find_artifacts(root).unwrap();
+        assert_eq!(artifacts.len(), 2);
+
+        let adjacent = crypto_adjacent(artifacts, &ContainsFilter("crypto/"));
+        assert_eq!(
+            kinds(&adjacent),
+            vec![("sum_amd64.s".to_string(), GoArtifactKind::Assembly)]
+        );
+    }
+}
@@ -2,6 +2,9 @@ pub const FILE_EXTENSIONS: &[&str] = &["go"];
 
 pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git"];
 
+/// Where `go mod vendor` puts dependencies, read only by `--include-deps`.
+pub const VENDOR_DIR: &str = "vendor";
+
 pub const GO_COMMAND: &str = "go";
 
 pub const STD_PREFIX: &str = "std/";
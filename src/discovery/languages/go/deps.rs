@@ -1,36 +1,39 @@
 use std::collections::HashSet;
+use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::str;
 use std::sync::OnceLock;
 
+use tracing::warn;
+
 use crate::discovery::cache::DiscoveryCache;
 use crate::discovery::loader::LoadError;
+use crate::discovery::manifest::go_requirements;
+use crate::discovery::tooling;
 use crate::discovery::utils::walk_source_files;
 
 use super::config::*;
 
-static STDLIB_CACHE: OnceLock<HashSet<String>> = OnceLock::new();
+static STDLIB_CACHE: OnceLock<Option<HashSet<String>>> = OnceLock::new();
 
-fn get_stdlib_packages() -> &'static HashSet<String> {
-    STDLIB_CACHE.get_or_init(|| {
-        query_go_stdlib().expect(
-            "Failed to query Go stdlib packages. Go tooling is required for dependency discovery.",
-        )
-    })
+/// The packages `go list std` reports, or `None` without a Go toolchain.
+fn get_stdlib_packages() -> Option<&'static HashSet<String>> {
+    STDLIB_CACHE
+        .get_or_init(|| match query_go_stdlib() {
+            Ok(packages) => Some(packages),
+            Err(e) => {
+                warn!(error = %e, "cannot list Go stdlib packages, treating dotless paths as stdlib");
+                None
+            }
+        })
+        .as_ref()
 }
 
 fn query_go_stdlib() -> Result<HashSet<String>, LoadError> {
-    let output = Command::new(GO_COMMAND)
-        .args(GO_LIST_STD_ARGS)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'go list std': {e}")))?;
-
-    if !output.status.success() {
-        return Err(LoadError::PackageManager("go list std failed".to_string()));
-    }
+    let output = tooling::run(GO_COMMAND, GO_LIST_STD_ARGS, None)?;
 
-    let stdout = str::from_utf8(&output.stdout)
+    let stdout = str::from_utf8(&output)
         .map_err(|e| LoadError::PackageManager(format!("Invalid UTF-8 from go list std: {e}")))?;
 
     let mut packages = HashSet::new();
@@ -61,7 +64,10 @@ pub fn is_stdlib_package(package_path: &str) -> bool {
 }
 
 fn is_known_stdlib_package(package_path: &str) -> bool {
-    let stdlib = get_stdlib_packages();
+    // Only module paths have a dot in their first element.
+    let Some(stdlib) = get_stdlib_packages() else {
+        return true;
+    };
     let root_package = package_path.split('/').next().unwrap_or("");
     stdlib.contains(package_path) || stdlib.contains(root_package)
 }
@@ -69,6 +75,7 @@ fn is_known_stdlib_package(package_path: &str) -> bool {
 pub fn scan_dependencies_using_go_tooling(
     project_root: &Path,
     _cache: &mut DiscoveryCache,
+    select: &mut dyn FnMut(&str) -> bool,
 ) -> Result<Vec<(PathBuf, bool)>, LoadError> {
     let go_mod_path = project_root.join("go.mod");
 
@@ -76,33 +83,28 @@ pub fn scan_dependencies_using_go_tooling(
         return Ok(vec![]);
     }
 
-    let dependency_packages = get_dependency_packages(project_root)?;
-
-    if dependency_packages.is_empty() {
-        return Ok(vec![]);
+    let listed = get_dependency_packages(project_root).and_then(|mut packages| {
+        packages.retain(|package| is_stdlib_package(package) || select(package));
+        if packages.is_empty() {
+            return Ok(vec![]);
+        }
+        resolve_package_paths_to_files(project_root, &packages)
+    });
+    match listed {
+        Ok(files) => Ok(files),
+        Err(e) => {
+            warn!(error = %e, "go list failed, reading required modules from the module cache");
+            scan_module_cache(project_root, &module_cache_dir(), select)
+        }
     }
-
-    resolve_package_paths_to_files(project_root, &dependency_packages)
 }
 
 fn get_dependency_packages(project_root: &Path) -> Result<Vec<String>, LoadError> {
-    let output = Command::new(GO_COMMAND)
-        .args(GO_LIST_DEPS_ARGS)
-        .args([GO_LIST_IMPORT_PATH_TEMPLATE, GO_LIST_PACKAGE_PATTERN])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'go list': {e}")))?;
-
-    if !output.status.success() {
-        let stderr = str::from_utf8(&output.stderr)
-            .unwrap_or("Unknown error")
-            .to_string();
-        return Err(LoadError::PackageManager(format!(
-            "go list failed: {stderr}"
-        )));
-    }
+    let mut args = GO_LIST_DEPS_ARGS.to_vec();
+    args.extend([GO_LIST_IMPORT_PATH_TEMPLATE, GO_LIST_PACKAGE_PATTERN]);
+    let output = tooling::run(GO_COMMAND, &args, Some(project_root))?;
 
-    let stdout = str::from_utf8(&output.stdout)
+    let stdout = str::from_utf8(&output)
         .map_err(|e| LoadError::PackageManager(format!("Invalid UTF-8 from go list: {e}")))?;
 
     let mut packages: Vec<String> = stdout
@@ -145,20 +147,15 @@ fn get_package_files(
     project_root: &Path,
     package_path: &str,
 ) -> Result<Option<Vec<PathBuf>>, LoadError> {
-    let output = Command::new(GO_COMMAND)
-        .args(GO_LIST_DIR_ARGS)
-        .args([GO_LIST_DIR_TEMPLATE, package_path])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| {
-            LoadError::PackageManager(format!("Failed to run 'go list' for {package_path}: {e}"))
-        })?;
-
-    if !output.status.success() {
-        return Ok(None);
-    }
-
-    let stdout = str::from_utf8(&output.stdout)
+    let mut args = GO_LIST_DIR_ARGS.to_vec();
+    args.extend([GO_LIST_DIR_TEMPLATE, package_path]);
+    let output = match tooling::run(GO_COMMAND, &args, Some(project_root)) {
+        Ok(output) => output,
+        Err(tooling::ToolError::Failed { .. }) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let stdout = str::from_utf8(&output)
         .map_err(|e| LoadError::PackageManager(format!("Invalid UTF-8 from go list: {e}")))?;
 
     let dir_str = stdout.trim();
@@ -179,3 +176,81 @@ fn get_package_files(
         Ok(Some(files))
     }
 }
+
+/// Where `go mod download` puts modules: `$GOMODCACHE`, else `pkg/mod` in
+/// the first `$GOPATH` entry or in `~/go`.
+fn module_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("GOMODCACHE").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let gopath = env::var_os("GOPATH")
+        .and_then(|paths| env::split_paths(&paths).next())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join("go")))
+        .unwrap_or_else(|| PathBuf::from("go"));
+    gopath.join("pkg").join("mod")
+}
+
+/// Source files of the modules `go.mod` requires that are already in
+/// `cache_dir`, found without the Go toolchain. Modules not downloaded are
+/// left out.
+fn scan_module_cache(
+    project_root: &Path,
+    cache_dir: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<(PathBuf, bool)>, LoadError> {
+    let go_mod = fs::read_to_string(project_root.join("go.mod"))?;
+    let mut files = Vec::new();
+    for (module, version) in go_requirements(&go_mod) {
+        let module_dir = cache_dir.join(format!("{}@{version}", escape_module_path(&module)));
+        if module_dir.is_dir() && select(&module) {
+            for file in walk_source_files(&module_dir, FILE_EXTENSIONS[0], EXCLUDED_DIRS, true)? {
+                files.push((file, false));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// A module path as the module cache spells it on disk, with each upper
+/// case letter as `!` and its lower case form.
+fn escape_module_path(module: &str) -> String {
+    let mut escaped = String::with_capacity(module.len());
+    for c in module.chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_module_cache() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        fs::write(
+            project.path().join("go.mod"),
+            "module example.com/app\n\nrequire (\n\tgithub.com/BurntSushi/toml v1.3.2\n\tgolang.org/x/crypto v0.21.0\n)\n",
+        )
+        .unwrap();
+        let module = cache.path().join("github.com/!burnt!sushi/toml@v1.3.2");
+        fs::create_dir_all(module.join("testdata")).unwrap();
+        fs::write(module.join("decode.go"), "package toml\n").unwrap();
+        fs::write(module.join("testdata/case.go"), "package testdata\n").unwrap();
+
+        let files = scan_module_cache(project.path(), cache.path(), &mut |_| true).unwrap();
+        assert_eq!(files, vec![(module.join("decode.go"), false)]);
+
+        let files = scan_module_cache(project.path(), cache.path(), &mut |module| {
+            module.contains("crypto")
+        })
+        .unwrap();
+        assert!(files.is_empty());
+    }
+}
@@ -3,7 +3,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::cli::Language;
-use crate::discovery::filter::{FilterError, ImportFileFilter};
+use crate::discovery::filter::{
+    package_provides_import, FilterError, FilterTokens, ImportFileFilter,
+};
 use serde::Deserialize;
 
 use super::config::*;
@@ -14,13 +16,18 @@ struct MappingsFile {
 }
 
 pub struct GoImportFilter {
-    import_patterns: Vec<String>,
+    /// Import path and one of its patterns.
+    import_patterns: Vec<(String, String)>,
+    tokens: FilterTokens,
 }
 
 impl GoImportFilter {
     pub fn new(preset_paths: &[PathBuf]) -> Result<Self, FilterError> {
         let import_patterns = load_import_patterns_from_presets(preset_paths, "go")?;
-        Ok(Self { import_patterns })
+        Ok(Self {
+            import_patterns,
+            tokens: FilterTokens::default(),
+        })
     }
 
     pub fn from_bundled() -> Result<Self, FilterError> {
@@ -29,6 +36,12 @@ impl GoImportFilter {
             .join("crypto");
         Self::new(&[preset_dir])
     }
+
+    /// Applies the classifier's weak-algorithm and non-crypto import lists.
+    pub fn with_tokens(mut self, tokens: FilterTokens) -> Self {
+        self.tokens = tokens;
+        self
+    }
 }
 
 impl ImportFileFilter for GoImportFilter {
@@ -42,11 +55,10 @@ impl ImportFileFilter for GoImportFilter {
         })?;
 
         if metadata.len() > MAX_FILE_SIZE {
-            return Err(FilterError::FileRead(format!(
-                "File too large: {} bytes (max: {} bytes)",
-                metadata.len(),
-                MAX_FILE_SIZE
-            )));
+            return Err(FilterError::TooLarge {
+                size: metadata.len(),
+                max: MAX_FILE_SIZE,
+            });
         }
 
         let content = fs::read_to_string(file_path).map_err(|e| {
@@ -57,10 +69,11 @@ impl ImportFileFilter for GoImportFilter {
             ))
         })?;
 
-        Ok(self
-            .import_patterns
-            .iter()
-            .any(|pattern| content.contains(pattern)))
+        Ok(self.tokens.matches(&content, &self.import_patterns))
+    }
+
+    fn matches_package(&self, package: &str) -> bool {
+        package_provides_import(package, &self.import_patterns)
     }
 
     fn language(&self) -> Language {
@@ -71,7 +84,7 @@ impl ImportFileFilter for GoImportFilter {
 fn load_import_patterns_from_presets(
     preset_paths: &[PathBuf],
     language: &str,
-) -> Result<Vec<String>, FilterError> {
+) -> Result<Vec<(String, String)>, FilterError> {
     let mut all_patterns = HashSet::new();
 
     for preset_path in preset_paths {
@@ -91,7 +104,9 @@ fn load_import_patterns_from_presets(
     Ok(all_patterns.into_iter().collect())
 }
 
-fn load_import_patterns_from_file(mappings_path: &Path) -> Result<Vec<String>, FilterError> {
+fn load_import_patterns_from_file(
+    mappings_path: &Path,
+) -> Result<Vec<(String, String)>, FilterError> {
     let content = fs::read_to_string(mappings_path).map_err(|e| {
         FilterError::FileRead(format!("Failed to read {}: {}", mappings_path.display(), e))
     })?;
@@ -106,9 +121,9 @@ fn load_import_patterns_from_file(mappings_path: &Path) -> Result<Vec<String>, F
 
     let mut patterns = HashSet::new();
     for import_path in file.mappings.keys() {
-        patterns.insert(format!("\"{import_path}\""));
-        patterns.insert(format!("`{import_path}`"));
-        patterns.insert(import_path.to_string());
+        patterns.insert((import_path.clone(), format!("\"{import_path}\"")));
+        patterns.insert((import_path.clone(), format!("`{import_path}`")));
+        patterns.insert((import_path.clone(), import_path.to_string()));
     }
 
     Ok(patterns.into_iter().collect())
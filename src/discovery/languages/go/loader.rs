@@ -9,6 +9,7 @@ use crate::discovery::{FileMetadata, SourceFile, SourceType};
 
 use super::config::*;
 use super::deps;
+use super::vendor;
 
 pub struct GoPackageLoader;
 
@@ -28,7 +29,8 @@ impl PackageLoader for GoPackageLoader {
             )));
         }
 
-        let paths = walk_source_files(root, FILE_EXTENSIONS[0], EXCLUDED_DIRS, false)?;
+        let excluded: Vec<&str> = EXCLUDED_DIRS.iter().copied().chain([VENDOR_DIR]).collect();
+        let paths = walk_source_files(root, FILE_EXTENSIONS[0], &excluded, false)?;
         Ok(paths
             .into_iter()
             .map(|path| SourceFile {
@@ -67,56 +69,75 @@ impl PackageLoader for GoPackageLoader {
                 .collect());
         }
 
-        let vendor_dirs = find_all_vendor_dirs(root)?;
+        let all_files = dependency_files(root, cache, &mut |_| true)?;
+        let paths_for_cache: Vec<_> = all_files.iter().map(|f| f.path.clone()).collect();
+        cache.set_dependencies(cache_key, paths_for_cache);
 
-        let mut all_files = Vec::new();
-        if !vendor_dirs.is_empty() {
-            for vendor_path in vendor_dirs {
-                let files = scan_vendor(&vendor_path)?;
-                for file in files {
-                    let metadata = get_file_metadata(&file);
-                    all_files.push(SourceFile {
-                        path: file,
-                        language: Language::Go,
-                        source_type: SourceType::Dependency {
-                            package: "unknown".to_string(),
-                            version: None,
-                        },
-                        package: None,
-                        metadata,
-                    });
-                }
-            }
-        } else {
-            let dep_results = deps::scan_dependencies_using_go_tooling(root, cache)?;
-            for (path, is_stdlib) in dep_results {
-                let metadata = get_file_metadata(&path);
+        Ok(all_files)
+    }
+
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, cache, select)
+    }
+
+    fn language(&self) -> Language {
+        Language::Go
+    }
+}
+
+/// A `vendor/` tree is read instead of asking `go list` or the module
+/// cache, with its files tagged by the modules `vendor/modules.txt` lists.
+fn dependency_files(
+    root: &Path,
+    cache: &mut DiscoveryCache,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<SourceFile>, LoadError> {
+    let vendor_dirs = find_all_vendor_dirs(root)?;
+
+    let mut all_files = Vec::new();
+    if !vendor_dirs.is_empty() {
+        for vendor_path in vendor_dirs {
+            for file in vendor::vendored_files(&vendor_path, select)? {
+                let metadata = get_file_metadata(&file.path);
                 all_files.push(SourceFile {
-                    path,
+                    path: file.path,
                     language: Language::Go,
-                    source_type: if is_stdlib {
-                        SourceType::Stdlib
-                    } else {
-                        SourceType::Dependency {
-                            package: "unknown".to_string(),
-                            version: None,
-                        }
+                    source_type: SourceType::Dependency {
+                        package: file.module.unwrap_or_else(|| "unknown".to_string()),
+                        version: file.version,
                     },
                     package: None,
                     metadata,
                 });
             }
         }
-
-        let paths_for_cache: Vec<_> = all_files.iter().map(|f| f.path.clone()).collect();
-        cache.set_dependencies(cache_key, paths_for_cache);
-
-        Ok(all_files)
+    } else {
+        let dep_results = deps::scan_dependencies_using_go_tooling(root, cache, select)?;
+        for (path, is_stdlib) in dep_results {
+            let metadata = get_file_metadata(&path);
+            all_files.push(SourceFile {
+                path,
+                language: Language::Go,
+                source_type: if is_stdlib {
+                    SourceType::Stdlib
+                } else {
+                    SourceType::Dependency {
+                        package: "unknown".to_string(),
+                        version: None,
+                    }
+                },
+                package: None,
+                metadata,
+            });
+        }
     }
 
-    fn language(&self) -> Language {
-        Language::Go
-    }
+    Ok(all_files)
 }
 
 fn get_file_metadata(path: &PathBuf) -> FileMetadata {
@@ -156,7 +177,7 @@ fn find_all_vendor_dirs(root: &Path) -> Result<Vec<PathBuf>, LoadError> {
                 continue;
             }
 
-            if dir_name == "vendor" {
+            if dir_name == VENDOR_DIR {
                 vendor_dirs.push(path);
                 continue;
             }
@@ -170,7 +191,3 @@ fn find_all_vendor_dirs(root: &Path) -> Result<Vec<PathBuf>, LoadError> {
     walk_for_vendor(root, &mut vendor_dirs).map_err(LoadError::Io)?;
     Ok(vendor_dirs)
 }
-
-fn scan_vendor(vendor_path: &Path) -> Result<Vec<PathBuf>, LoadError> {
-    walk_source_files(vendor_path, FILE_EXTENSIONS[0], &[], true)
-}
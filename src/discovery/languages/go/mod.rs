@@ -5,10 +5,13 @@ use crate::discovery::filter::ImportFileFilter;
 use crate::discovery::languages::LanguageModule;
 use crate::discovery::loader::PackageLoader;
 
+pub mod artifacts;
 pub mod config;
 pub mod deps;
 pub mod filter;
 pub mod loader;
+pub mod vendor;
+pub mod workspace;
 
 pub use filter::GoImportFilter;
 pub use loader::GoPackageLoader;
@@ -0,0 +1,216 @@
+//! Dependencies vendored with `go mod vendor`. `vendor/modules.txt` lists
+//! each vendored module as a `# path version` line, or
+//! `# path version => replacement version` when `go.mod` replaces it,
+//! followed by the module's packages. A vendored file is attributed to the
+//! module of the package directory it is in, so neither the go command nor
+//! a module cache is needed.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::discovery::loader::LoadError;
+use crate::discovery::utils::walk_source_files;
+
+use super::config::*;
+
+pub const MODULES_TXT: &str = "modules.txt";
+
+/// A vendored source file and the module it belongs to, when
+/// `modules.txt` lists it.
+pub struct DependencyFile {
+    pub path: PathBuf,
+    pub module: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendoredModule {
+    pub path: String,
+    /// The version vendored: the replacement's when replaced, `None` for a
+    /// replacement by a local directory.
+    pub version: Option<String>,
+}
+
+/// The Go files under `vendor`, except those of modules `select` turns
+/// down by module path. Files no listed module claims are all kept.
+pub fn vendored_files(
+    vendor: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
+    let modules = match fs::read_to_string(vendor.join(MODULES_TXT)) {
+        Ok(content) => parse_modules_txt(&content),
+        Err(_) => Vec::new(),
+    };
+    let packages: HashMap<&str, &VendoredModule> = modules
+        .iter()
+        .flat_map(|(module, packages)| packages.iter().map(move |p| (p.as_str(), module)))
+        .collect();
+    let mut selected: HashMap<String, bool> = HashMap::new();
+
+    let mut files = Vec::new();
+    for path in walk_source_files(vendor, FILE_EXTENSIONS[0], &[], true)? {
+        let package = package_path(vendor, &path);
+        let module = packages.get(package.as_str()).copied().or_else(|| {
+            // A directory that is not a listed package, e.g. one holding
+            // only files for other platforms: the longest enclosing module.
+            modules
+                .iter()
+                .map(|(module, _)| module)
+                .filter(|module| {
+                    package
+                        .strip_prefix(&module.path)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+                .max_by_key(|module| module.path.len())
+        });
+        if let Some(module) = module {
+            let keep = *selected
+                .entry(module.path.clone())
+                .or_insert_with(|| select(&module.path));
+            if !keep {
+                continue;
+            }
+        }
+        files.push(DependencyFile {
+            path,
+            module: module.map(|m| m.path.clone()),
+            version: module.and_then(|m| m.version.clone()),
+        });
+    }
+    Ok(files)
+}
+
+/// The modules of a `modules.txt`, each with the packages vendored from it.
+pub fn parse_modules_txt(content: &str) -> Vec<(VendoredModule, Vec<String>)> {
+    let mut modules: Vec<(VendoredModule, Vec<String>)> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix("# ") {
+            let (module, replacement) = match header.split_once("=>") {
+                Some((module, replacement)) => (module, Some(replacement)),
+                None => (header, None),
+            };
+            let mut fields = module.split_whitespace();
+            let Some(path) = fields.next() else {
+                continue;
+            };
+            let version = match replacement {
+                Some(replacement) => replacement.split_whitespace().nth(1),
+                None => fields.next(),
+            };
+            modules.push((
+                VendoredModule {
+                    path: path.to_string(),
+                    version: version.map(str::to_string),
+                },
+                Vec::new(),
+            ));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            if let Some((_, packages)) = modules.last_mut() {
+                packages.push(line.to_string());
+            }
+        }
+    }
+    modules
+}
+
+/// The import path of the package holding `file`, from its directory
+/// under `vendor`.
+fn package_path(vendor: &Path, file: &Path) -> String {
+    file.parent()
+        .and_then(|dir| dir.strip_prefix(vendor).ok())
+        .map(|dir| {
+            dir.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULES: &str = "# github.com/golang-jwt/jwt/v5 v5.2.0
+## explicit; go 1.18
+github.com/golang-jwt/jwt/v5
+# golang.org/x/crypto v0.17.0 => golang.org/x/crypto v0.18.0
+## explicit; go 1.18
+golang.org/x/crypto/bcrypt
+golang.org/x/crypto/blowfish
+# example.com/internal/keys => ../keys
+example.com/internal/keys
+# golang.org/x/crypto => golang.org/x/crypto v0.18.0
+";
+
+    #[test]
+    fn test_parse_modules_txt() {
+        let modules = parse_modules_txt(MODULES);
+        let listed: Vec<(&str, Option<&str>, usize)> = modules
+            .iter()
+            .map(|(m, packages)| (m.path.as_str(), m.version.as_deref(), packages.len()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("github.com/golang-jwt/jwt/v5", Some("v5.2.0"), 1),
+                ("golang.org/x/crypto", Some("v0.18.0"), 2),
+                ("example.com/internal/keys", None, 1),
+                ("golang.org/x/crypto", Some("v0.18.0"), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vendored_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vendor = dir.path().join("vendor");
+        for file in [
+            "github.com/golang-jwt/jwt/v5/token.go",
+            "golang.org/x/crypto/bcrypt/bcrypt.go",
+            "golang.org/x/crypto/internal/alias/alias.go",
+            "example.com/internal/keys/keys.go",
+        ] {
+            let path = vendor.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "package x\n").unwrap();
+        }
+        fs::write(vendor.join(MODULES_TXT), MODULES).unwrap();
+
+        let mut files =
+            vendored_files(&vendor, &mut |module| module != "example.com/internal/keys").unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let tags: Vec<(String, Option<&str>, Option<&str>)> = files
+            .iter()
+            .map(|f| {
+                (
+                    package_path(&vendor, &f.path),
+                    f.module.as_deref(),
+                    f.version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (
+                    "github.com/golang-jwt/jwt/v5".to_string(),
+                    Some("github.com/golang-jwt/jwt/v5"),
+                    Some("v5.2.0")
+                ),
+                (
+                    "golang.org/x/crypto/bcrypt".to_string(),
+                    Some("golang.org/x/crypto"),
+                    Some("v0.18.0")
+                ),
+                (
+                    "golang.org/x/crypto/internal/alias".to_string(),
+                    Some("golang.org/x/crypto"),
+                    Some("v0.18.0")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Go workspaces: the modules a `go.work` file `use`s, each a directory
+//! with a `go.mod` of its own. Read without the go command, so a scan of a
+//! checkout without a toolchain finds them too.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const GO_WORK: &str = "go.work";
+
+/// The directories the `go.work` at `root` uses that hold a `go.mod`,
+/// sorted. Empty when `root` has no `go.work`.
+pub fn workspace_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join(GO_WORK)) else {
+        return Vec::new();
+    };
+    let mut members: Vec<PathBuf> = use_directives(&content)
+        .iter()
+        .map(
+            |dir| match dir.trim_start_matches("./").trim_end_matches('/') {
+                "." | "" => root.to_path_buf(),
+                dir => root.join(dir),
+            },
+        )
+        .filter(|dir| dir.join("go.mod").is_file())
+        .collect();
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// The module path the `go.mod` in `dir` declares.
+pub fn module_path(dir: &Path) -> Option<String> {
+    let go_mod = fs::read_to_string(dir.join("go.mod")).ok()?;
+    go_mod.lines().find_map(|line| {
+        let path = line.split("//").next()?.trim().strip_prefix("module")?;
+        path.starts_with([' ', '\t'])
+            .then(|| unquote(path.trim()))
+            .filter(|path| !path.is_empty())
+    })
+}
+
+/// The directories of the `use ./dir` lines and `use ( ... )` blocks.
+fn use_directives(content: &str) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            match line {
+                ")" => in_block = false,
+                "" => {}
+                dir => dirs.push(unquote(dir)),
+            }
+            continue;
+        }
+        let Some(rest) = line
+            .strip_prefix("use")
+            .filter(|rest| rest.starts_with([' ', '\t', '(']))
+        else {
+            continue;
+        };
+        match rest.trim() {
+            "(" => in_block = true,
+            dir => dirs.push(unquote(dir)),
+        }
+    }
+    dirs
+}
+
+fn unquote(text: &str) -> String {
+    text.trim_matches(['"', '`']).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_members() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        for (module, path) in [
+            ("api", "example.com/shop/api"),
+            ("svc/billing", "example.com/shop/billing"),
+        ] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(
+                root.join(module).join("go.mod"),
+                format!("module {path} // the {module} module\n\ngo 1.22\n"),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(root.join("tools")).unwrap();
+        fs::write(
+            root.join(GO_WORK),
+            "go 1.22\n\nuse (\n\t./api\n\t\"./svc/billing/\" // payments\n\t./missing\n)\nuse ./tools\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            workspace_members(root),
+            vec![root.join("api"), root.join("svc/billing")]
+        );
+        assert_eq!(
+            module_path(&root.join("svc/billing")).as_deref(),
+            Some("example.com/shop/billing")
+        );
+        assert_eq!(module_path(&root.join("tools")), None);
+    }
+}
@@ -0,0 +1,11 @@
+pub const FILE_EXTENSIONS: &[&str] = &["java"];
+
+pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "target", "build", ".gradle", "out"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Where Maven and Gradle keep a module's production sources.
+pub const SOURCE_ROOTS: &[&str] = &["src/main/java", "src/main/kotlin", "src/main/scala"];
+
+/// Written into a directory a sources JAR was unpacked into, once complete.
+pub const UNPACKED_MARKER: &str = ".argflow-unpacked";
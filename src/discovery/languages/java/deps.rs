@@ -0,0 +1,322 @@
+//! Java dependency sources. Dependencies ship as compiled JARs, but Maven
+//! and Gradle keep the `-sources.jar` an IDE downloaded next to them, so
+//! for each artifact `pom.xml` or the Gradle build script pins to a
+//! version, its sources JAR is looked up in the local Maven repository
+//! (`~/.m2/repository`) and the Gradle cache (`~/.gradle/caches`), and its
+//! `.java` files are unpacked once under `~/.cache/argflow/java-sources`.
+//! Artifacts without a sources JAR are left out.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+use walkdir::WalkDir;
+
+use crate::discovery::loader::LoadError;
+use crate::discovery::utils::walk_source_files;
+
+use super::config::*;
+use super::jar;
+
+/// A dependency's source file, unpacked from its sources JAR.
+pub struct DependencyFile {
+    pub path: PathBuf,
+    /// `groupId:artifactId`.
+    pub artifact: String,
+    pub version: String,
+}
+
+/// A Maven artifact pinned to a version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Coordinates {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+}
+
+impl Coordinates {
+    fn name(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+
+    fn sources_jar_name(&self) -> String {
+        format!("{}-{}-sources.jar", self.artifact, self.version)
+    }
+}
+
+/// The source files of the dependencies of the project at `project_root`
+/// that `select` accepts by `groupId:artifactId`.
+pub fn scan_dependencies(
+    project_root: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
+    let repositories = Repositories::from_env();
+    let mut files = Vec::new();
+    for coordinates in declared_coordinates(project_root) {
+        let name = coordinates.name();
+        if !select(&name) {
+            continue;
+        }
+        let Some(sources_jar) = repositories.sources_jar(&coordinates) else {
+            debug!(artifact = %name, version = %coordinates.version, "no sources JAR");
+            continue;
+        };
+        let dir = repositories
+            .unpacked
+            .join(&coordinates.group)
+            .join(format!("{}-{}", coordinates.artifact, coordinates.version));
+        for path in unpack(&sources_jar, &dir)? {
+            files.push(DependencyFile {
+                path,
+                artifact: name.clone(),
+                version: coordinates.version.clone(),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// The `.java` files of `sources_jar`, unpacked into `dir` unless an
+/// earlier scan already did.
+fn unpack(sources_jar: &Path, dir: &Path) -> Result<Vec<PathBuf>, LoadError> {
+    let marker = dir.join(UNPACKED_MARKER);
+    if !marker.exists() {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        jar::extract(sources_jar, dir, MAX_FILE_SIZE, |name| {
+            name.ends_with(".java")
+        })?;
+        fs::write(&marker, sources_jar.to_string_lossy().as_bytes())?;
+    }
+    walk_source_files(dir, FILE_EXTENSIONS[0], &[], true)
+}
+
+/// Where sources JARs are looked up and unpacked.
+struct Repositories {
+    maven: PathBuf,
+    gradle: PathBuf,
+    unpacked: PathBuf,
+}
+
+impl Repositories {
+    fn from_env() -> Self {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let gradle_home = env::var_os("GRADLE_USER_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".gradle"));
+        Self {
+            maven: home.join(".m2").join("repository"),
+            gradle: gradle_home.join("caches/modules-2/files-2.1"),
+            unpacked: home.join(".cache/argflow/java-sources"),
+        }
+    }
+
+    /// `group/with/slashes/artifact/version/artifact-version-sources.jar`
+    /// in the Maven repository, else the Gradle cache's
+    /// `group/artifact/version/<sha1>/artifact-version-sources.jar`.
+    fn sources_jar(&self, coordinates: &Coordinates) -> Option<PathBuf> {
+        let jar_name = coordinates.sources_jar_name();
+        let maven = self
+            .maven
+            .join(coordinates.group.replace('.', "/"))
+            .join(&coordinates.artifact)
+            .join(&coordinates.version)
+            .join(&jar_name);
+        if maven.is_file() {
+            return Some(maven);
+        }
+        let gradle = self
+            .gradle
+            .join(&coordinates.group)
+            .join(&coordinates.artifact)
+            .join(&coordinates.version);
+        WalkDir::new(gradle)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_name().to_string_lossy() == jar_name)
+            .map(|entry| entry.into_path())
+    }
+}
+
+/// The versioned artifacts `pom.xml` or the Gradle build script at `root`
+/// declares, sorted. Versions given by `${property}` are looked up in the
+/// POM's `<properties>`; artifacts without a version are left out.
+pub fn declared_coordinates(root: &Path) -> BTreeSet<Coordinates> {
+    let read = |name: &str| fs::read_to_string(root.join(name)).ok();
+    if let Some(pom) = read("pom.xml") {
+        return pom_coordinates(&pom);
+    }
+    read("build.gradle")
+        .or_else(|| read("build.gradle.kts"))
+        .map(|script| gradle_coordinates(&script))
+        .unwrap_or_default()
+}
+
+fn pom_coordinates(pom: &str) -> BTreeSet<Coordinates> {
+    let properties = pom_properties(pom);
+    let resolve = |value: String| -> Option<String> {
+        match value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+            Some(property) => properties.get(property).cloned(),
+            None => Some(value),
+        }
+    };
+    pom.split("<dependency>")
+        .skip(1)
+        .filter_map(|block| {
+            let block = block.split("</dependency>").next()?;
+            Some(Coordinates {
+                group: resolve(element(block, "groupId")?)?,
+                artifact: resolve(element(block, "artifactId")?)?,
+                version: resolve(element(block, "version")?)?,
+            })
+        })
+        .collect()
+}
+
+/// The `<properties>` of a POM, by element name.
+fn pom_properties(pom: &str) -> BTreeMap<String, String> {
+    let Some(properties) = element(pom, "properties") else {
+        return BTreeMap::new();
+    };
+    properties
+        .split('<')
+        .filter_map(|tag| {
+            let (name, value) = tag.split_once('>')?;
+            (!name.starts_with('/') && !name.starts_with('!'))
+                .then(|| (name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn element(block: &str, name: &str) -> Option<String> {
+    let start = block.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = block[start..].find(&format!("</{name}>"))? + start;
+    Some(block[start..end].trim().to_string())
+}
+
+/// Every string notation `group:artifact:version` in a Gradle build script,
+/// e.g. `implementation 'org.bouncycastle:bcprov-jdk18on:1.78'`.
+fn gradle_coordinates(script: &str) -> BTreeSet<Coordinates> {
+    let is_name = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    };
+    script
+        .split(['\'', '"'])
+        .skip(1)
+        .step_by(2)
+        .filter_map(|notation| {
+            let mut parts = notation.split(':');
+            let (group, artifact, version) = (parts.next()?, parts.next()?, parts.next()?);
+            (is_name(group) && is_name(artifact) && is_name(version)).then(|| Coordinates {
+                group: group.to_string(),
+                artifact: artifact.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinates(group: &str, artifact: &str, version: &str) -> Coordinates {
+        Coordinates {
+            group: group.to_string(),
+            artifact: artifact.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pom_coordinates() {
+        let pom = r#"<project>
+  <properties>
+    <bc.version>1.78</bc.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>org.bouncycastle</groupId>
+      <artifactId>bcprov-jdk18on</artifactId>
+      <version>${bc.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>com.google.crypto.tink</groupId>
+      <artifactId>tink</artifactId>
+      <version>1.13.0</version>
+    </dependency>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+    </dependency>
+  </dependencies>
+</project>"#;
+        assert_eq!(
+            pom_coordinates(pom).into_iter().collect::<Vec<_>>(),
+            vec![
+                coordinates("com.google.crypto.tink", "tink", "1.13.0"),
+                coordinates("org.bouncycastle", "bcprov-jdk18on", "1.78"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gradle_coordinates() {
+        let script = "dependencies {\n    implementation(\"org.bouncycastle:bcprov-jdk18on:1.78\")\n    implementation 'com.nimbusds:nimbus-jose-jwt:$joseVersion'\n    testImplementation 'junit:junit'\n}\n";
+        assert_eq!(
+            gradle_coordinates(script).into_iter().collect::<Vec<_>>(),
+            vec![coordinates("org.bouncycastle", "bcprov-jdk18on", "1.78")]
+        );
+    }
+
+    #[test]
+    fn test_sources_jar_lookup_and_unpack() {
+        let home = tempfile::TempDir::new().unwrap();
+        let repositories = Repositories {
+            maven: home.path().join(".m2/repository"),
+            gradle: home.path().join(".gradle/caches/modules-2/files-2.1"),
+            unpacked: home.path().join(".cache/argflow/java-sources"),
+        };
+        let bcprov = coordinates("org.bouncycastle", "bcprov-jdk18on", "1.78");
+        let tink = coordinates("com.google.crypto.tink", "tink", "1.13.0");
+        let maven_jar = repositories
+            .maven
+            .join("org/bouncycastle/bcprov-jdk18on/1.78/bcprov-jdk18on-1.78-sources.jar");
+        let gradle_jar = repositories
+            .gradle
+            .join("com.google.crypto.tink/tink/1.13.0/0f3c1a/tink-1.13.0-sources.jar");
+        for path in [&maven_jar, &gradle_jar] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(
+                path,
+                jar::zip(&[("org/example/Cipher.java", "class Cipher {}")]),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(repositories.sources_jar(&bcprov), Some(maven_jar.clone()));
+        assert_eq!(repositories.sources_jar(&tink), Some(gradle_jar));
+        assert_eq!(
+            repositories.sources_jar(&coordinates("org.bouncycastle", "bcprov-jdk18on", "1.77")),
+            None
+        );
+
+        let dir = repositories.unpacked.join("bcprov");
+        let files = unpack(&maven_jar, &dir).unwrap();
+        assert_eq!(files, vec![dir.join("org/example/Cipher.java")]);
+        // Unpacked once: a second scan reads the directory as it is.
+        fs::write(&maven_jar, "").unwrap();
+        assert_eq!(unpack(&maven_jar, &dir).unwrap(), files);
+    }
+}
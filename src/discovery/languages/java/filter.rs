@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Language;
+use crate::discovery::filter::{FilterError, FilterTokens, ImportFileFilter};
+use serde::Deserialize;
+
+use super::config::*;
+use super::{kotlin, scala};
+
+#[derive(Debug, Deserialize)]
+struct MappingsFile {
+    mappings: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+}
+
+pub struct JavaImportFilter {
+    /// Import path and one of its patterns.
+    import_patterns: Vec<(String, String)>,
+    tokens: FilterTokens,
+}
+
+impl JavaImportFilter {
+    pub fn new(preset_paths: &[PathBuf]) -> Result<Self, FilterError> {
+        let import_patterns = load_import_patterns_from_presets(preset_paths, "java")?;
+        Ok(Self {
+            import_patterns,
+            tokens: FilterTokens::default(),
+        })
+    }
+
+    pub fn from_bundled() -> Result<Self, FilterError> {
+        let preset_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("presets")
+            .join("crypto");
+        Self::new(&[preset_dir])
+    }
+
+    /// Applies the classifier's weak-algorithm and non-crypto import lists.
+    pub fn with_tokens(mut self, tokens: FilterTokens) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// Whether a Kotlin file imports or names a mapped class, with the
+    /// Java patterns less their `;`: `import javax.crypto.Cipher`.
+    fn has_matching_kotlin_imports(&self, file_path: &Path) -> Result<bool, FilterError> {
+        let content = read_source(file_path)?;
+        let patterns: Vec<(String, String)> = self
+            .import_patterns
+            .iter()
+            .map(|(class, pattern)| (class.clone(), pattern.trim_end_matches(';').to_string()))
+            .collect();
+        Ok(self.tokens.matches(&content, &patterns))
+    }
+
+    /// Whether a Scala file imports or names a mapped class: the Kotlin
+    /// patterns plus Scala's package imports, `import javax.crypto._` and
+    /// `import javax.crypto.{Cipher, Mac}`.
+    fn has_matching_scala_imports(&self, file_path: &Path) -> Result<bool, FilterError> {
+        let content = read_source(file_path)?;
+        let mut patterns: Vec<(String, String)> = Vec::new();
+        for (class, pattern) in &self.import_patterns {
+            patterns.push((class.clone(), pattern.trim_end_matches(';').to_string()));
+            if let Some(package) = pattern
+                .strip_prefix("import ")
+                .and_then(|p| p.strip_suffix(".*;"))
+            {
+                patterns.push((class.clone(), format!("import {package}._")));
+                patterns.push((class.clone(), format!("import {package}.{{")));
+            }
+        }
+        Ok(self.tokens.matches(&content, &patterns))
+    }
+}
+
+impl ImportFileFilter for JavaImportFilter {
+    fn has_matching_imports(&self, file_path: &Path) -> Result<bool, FilterError> {
+        let extension = file_path.extension().and_then(|ext| ext.to_str());
+        if extension.is_some_and(|ext| kotlin::FILE_EXTENSIONS.contains(&ext)) {
+            return self.has_matching_kotlin_imports(file_path);
+        }
+        if extension.is_some_and(|ext| scala::FILE_EXTENSIONS.contains(&ext)) {
+            return self.has_matching_scala_imports(file_path);
+        }
+        let content = read_source(file_path)?;
+        Ok(self.tokens.matches(&content, &self.import_patterns))
+    }
+
+    fn language(&self) -> Language {
+        Language::Java
+    }
+}
+
+fn read_source(file_path: &Path) -> Result<String, FilterError> {
+    let metadata = fs::metadata(file_path).map_err(|e| {
+        FilterError::FileRead(format!(
+            "Failed to read metadata for {}: {}",
+            file_path.display(),
+            e
+        ))
+    })?;
+
+    if metadata.len() > MAX_FILE_SIZE {
+        return Err(FilterError::TooLarge {
+            size: metadata.len(),
+            max: MAX_FILE_SIZE,
+        });
+    }
+
+    fs::read_to_string(file_path).map_err(|e| {
+        FilterError::FileRead(format!(
+            "Failed to read file {}: {}",
+            file_path.display(),
+            e
+        ))
+    })
+}
+
+fn load_import_patterns_from_presets(
+    preset_paths: &[PathBuf],
+    language: &str,
+) -> Result<Vec<(String, String)>, FilterError> {
+    let mut all_patterns = HashSet::new();
+
+    for preset_path in preset_paths {
+        let mappings_path = preset_path.join(language).join("mappings.json");
+        if mappings_path.exists() {
+            let patterns = load_import_patterns_from_file(&mappings_path)?;
+            all_patterns.extend(patterns);
+        }
+    }
+
+    if all_patterns.is_empty() {
+        return Err(FilterError::FileRead(format!(
+            "No {language} mappings found in any preset. Checked: {preset_paths:?}"
+        )));
+    }
+
+    Ok(all_patterns.into_iter().collect())
+}
+
+fn load_import_patterns_from_file(
+    mappings_path: &Path,
+) -> Result<Vec<(String, String)>, FilterError> {
+    let content = fs::read_to_string(mappings_path).map_err(|e| {
+        FilterError::FileRead(format!("Failed to read {}: {}", mappings_path.display(), e))
+    })?;
+
+    let file: MappingsFile = serde_json::from_str(&content).map_err(|e| {
+        FilterError::FileRead(format!(
+            "Failed to parse {}: {}",
+            mappings_path.display(),
+            e
+        ))
+    })?;
+
+    // Java mappings are keyed by class (`javax.crypto.Cipher`), which is
+    // imported by name, through its package (`import javax.crypto.*;`) or
+    // written out in full at the call.
+    let mut patterns = HashSet::new();
+    for class in file.mappings.keys() {
+        patterns.insert((class.clone(), format!("import {class};")));
+        patterns.insert((class.clone(), format!("import static {class}.")));
+        patterns.insert((class.clone(), format!("{class}.")));
+        if let Some((package, _)) = class.rsplit_once('.') {
+            patterns.insert((class.clone(), format!("import {package}.*;")));
+        }
+    }
+
+    Ok(patterns.into_iter().collect())
+}
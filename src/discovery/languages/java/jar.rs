@@ -0,0 +1,131 @@
+//! Reading the entries of a JAR, which is a zip archive.
+
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+
+use zip::ZipArchive;
+
+/// Writes the entries of the JAR at `jar` that `keep` accepts under `dest`,
+/// and returns the paths written. Entries larger than `max_size` and names
+/// that would leave `dest` are skipped.
+pub fn extract(
+    jar: &Path,
+    dest: &Path,
+    max_size: u64,
+    keep: impl Fn(&str) -> bool,
+) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for_each_entry(File::open(jar)?, max_size, keep, |relative, contents| {
+        let path = dest.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        written.push(path);
+        Ok(())
+    })?;
+    Ok(written)
+}
+
+/// The names and contents of the entries of the JAR `data`, skipping
+/// those larger than `max_size`.
+pub fn read_entries(data: &[u8], max_size: u64) -> io::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut entries = Vec::new();
+    for_each_entry(
+        Cursor::new(data),
+        max_size,
+        |_| true,
+        |name, contents| {
+            entries.push((name, contents));
+            Ok(())
+        },
+    )?;
+    Ok(entries)
+}
+
+/// Calls `f` with the relative path and inflated contents of every file
+/// entry of `archive` that `keep` accepts, up to `max_size` bytes and
+/// without names that leave the archive.
+fn for_each_entry(
+    archive: impl Read + Seek,
+    max_size: u64,
+    keep: impl Fn(&str) -> bool,
+    mut f: impl FnMut(PathBuf, Vec<u8>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut archive = ZipArchive::new(archive)?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() || entry.size() > max_size || !keep(entry.name()) {
+            continue;
+        }
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        (&mut entry).take(max_size).read_to_end(&mut contents)?;
+        f(relative, contents)?;
+    }
+    Ok(())
+}
+
+/// A zip archive of `files`, deflated, as `jar` writes them.
+#[cfg(test)]
+pub(crate) fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in files {
+        writer.start_file(*name, options).unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let jar = dir.path().join("bcprov-sources.jar");
+        fs::write(
+            &jar,
+            zip(&[
+                ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n"),
+                (
+                    "org/bouncycastle/Digest.java",
+                    "public interface Digest {}\n",
+                ),
+                ("../evil.java", "class Evil {}\n"),
+            ]),
+        )
+        .unwrap();
+
+        let dest = dir.path().join("out");
+        let written = extract(&jar, &dest, 1024, |name| name.ends_with(".java")).unwrap();
+        assert_eq!(written, vec![dest.join("org/bouncycastle/Digest.java")]);
+        assert_eq!(
+            fs::read_to_string(&written[0]).unwrap(),
+            "public interface Digest {}\n"
+        );
+        assert!(!dir.path().join("evil.java").exists());
+
+        let entries = read_entries(&fs::read(&jar).unwrap(), 1024).unwrap();
+        let names: Vec<_> = entries.iter().map(|(name, _)| name.clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("META-INF/MANIFEST.MF"),
+                PathBuf::from("org/bouncycastle/Digest.java")
+            ]
+        );
+
+        fs::write(&jar, "not a zip").unwrap();
+        assert!(extract(&jar, &dest, 1024, |_| true).is_err());
+    }
+}
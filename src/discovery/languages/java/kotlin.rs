@@ -0,0 +1,49 @@
+//! Kotlin sources in JVM projects. They are loaded with the Java sources,
+//! filtered on the Java mappings and scanned with the Kotlin grammar, so
+//! their JCA calls meet the same rules.
+
+pub const FILE_EXTENSIONS: &[&str] = &["kt", "kts"];
+
+#[cfg(test)]
+mod tests {
+    use super::super::{JavaImportFilter, JavaPackageLoader};
+    use crate::discovery::filter::ImportFileFilter;
+    use crate::discovery::loader::PackageLoader;
+    use std::fs;
+
+    #[test]
+    fn test_kotlin_sources_are_user_code() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let preset = temp.path().join("preset");
+        fs::create_dir_all(preset.join("java")).unwrap();
+        fs::write(
+            preset.join("java/mappings.json"),
+            r#"{"mappings": {"javax.crypto.Cipher": {"getInstance": "cipher"}}}"#,
+        )
+        .unwrap();
+        let root = temp.path().join("app");
+        fs::create_dir_all(root.join("src/main/kotlin")).unwrap();
+        fs::create_dir_all(root.join("build")).unwrap();
+        fs::write(
+            root.join("src/main/kotlin/Crypto.kt"),
+            "import javax.crypto.Cipher\n\nfun cipher() = Cipher.getInstance(\"AES/GCM/NoPadding\")\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/main/kotlin/Util.kt"), "fun id(x: Int) = x\n").unwrap();
+        fs::write(
+            root.join("build/Generated.kt"),
+            "import javax.crypto.Cipher\n",
+        )
+        .unwrap();
+
+        let filter = JavaImportFilter::new(&[preset]).unwrap();
+        let found: Vec<_> = JavaPackageLoader
+            .load_user_code(&root)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .filter(|path| filter.has_matching_imports(path).unwrap())
+            .collect();
+        assert_eq!(found, vec![root.join("src/main/kotlin/Crypto.kt")]);
+    }
+}
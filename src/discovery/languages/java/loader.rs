@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::cli::Language;
+use crate::discovery::cache::DiscoveryCache;
+use crate::discovery::loader::{LoadError, PackageLoader};
+use crate::discovery::utils::walk_source_files;
+use crate::discovery::{FileMetadata, SourceFile, SourceType};
+
+use super::config::*;
+use super::{deps, kotlin, scala};
+
+pub struct JavaPackageLoader;
+
+impl PackageLoader for JavaPackageLoader {
+    fn load_user_code(&self, root: &Path) -> Result<Vec<SourceFile>, LoadError> {
+        if !root.exists() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path does not exist: {}",
+                root.display()
+            )));
+        }
+
+        if !root.is_dir() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path is not a directory: {}",
+                root.display()
+            )));
+        }
+
+        // The `src/main/java`, `src/main/kotlin` and `src/main/scala` of the
+        // root and of every module of a multi-module build; sources laid out
+        // otherwise are read whole.
+        let mut source_roots = source_roots(root);
+        if source_roots.is_empty() {
+            source_roots.push(root.to_path_buf());
+        }
+        let mut paths = Vec::new();
+        for dir in &source_roots {
+            let extensions = FILE_EXTENSIONS
+                .iter()
+                .chain(kotlin::FILE_EXTENSIONS)
+                .chain(scala::FILE_EXTENSIONS);
+            for ext in extensions {
+                paths.extend(walk_source_files(dir, ext, EXCLUDED_DIRS, false)?);
+            }
+        }
+
+        Ok(paths
+            .into_iter()
+            .map(|path| SourceFile {
+                metadata: get_file_metadata(&path),
+                path,
+                language: Language::Java,
+                source_type: SourceType::UserCode,
+                package: None,
+            })
+            .collect())
+    }
+
+    fn load_dependencies(
+        &self,
+        root: &Path,
+        _cache: &mut DiscoveryCache,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, &mut |_| true)
+    }
+
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        _cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, select)
+    }
+
+    fn language(&self) -> Language {
+        Language::Java
+    }
+}
+
+fn dependency_files(
+    root: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<SourceFile>, LoadError> {
+    Ok(deps::scan_dependencies(root, select)?
+        .into_iter()
+        .map(|file| SourceFile {
+            metadata: get_file_metadata(&file.path),
+            path: file.path,
+            language: Language::Java,
+            source_type: SourceType::Dependency {
+                package: file.artifact,
+                version: Some(file.version),
+            },
+            package: None,
+        })
+        .collect())
+}
+
+/// Every `src/main/java`, `src/main/kotlin` and `src/main/scala` directory
+/// under `root`, sorted.
+fn source_roots(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.file_type().is_dir()
+                && (entry.depth() == 0
+                    || !EXCLUDED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            SOURCE_ROOTS
+                .iter()
+                .any(|source_root| entry.path().ends_with(source_root))
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+fn get_file_metadata(path: &PathBuf) -> FileMetadata {
+    fs::metadata(path)
+        .ok()
+        .map(|m| FileMetadata {
+            size: m.len(),
+            modified: m.modified().ok(),
+            hash: None,
+        })
+        .unwrap_or_else(|| FileMetadata {
+            size: 0,
+            modified: None,
+            hash: None,
+        })
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod deps;
+pub mod filter;
+pub mod jar;
+pub mod kotlin;
+pub mod loader;
+pub mod scala;
+
+pub use filter::JavaImportFilter;
+pub use loader::JavaPackageLoader;
+
+pub struct JavaModule;
+
+impl LanguageModule for JavaModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(JavaPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(
+            JavaImportFilter::from_bundled().expect("Failed to load bundled Java import filter"),
+        )
+    }
+
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        root.join("pom.xml").exists()
+            || root.join("build.gradle").exists()
+            || root.join("build.gradle.kts").exists()
+            || root.join("build.sbt").exists()
+    }
+}
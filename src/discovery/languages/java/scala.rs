@@ -0,0 +1,63 @@
+//! Scala sources in JVM projects. They are loaded with the Java sources,
+//! filtered on the Java mappings, Scala's package imports included, and
+//! scanned with the Scala grammar, so their JCA calls meet the same rules.
+
+pub const FILE_EXTENSIONS: &[&str] = &["scala", "sc"];
+
+#[cfg(test)]
+mod tests {
+    use super::super::{JavaImportFilter, JavaPackageLoader};
+    use crate::discovery::filter::ImportFileFilter;
+    use crate::discovery::loader::PackageLoader;
+    use std::fs;
+
+    #[test]
+    fn test_scala_sources_are_user_code() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let preset = temp.path().join("preset");
+        fs::create_dir_all(preset.join("java")).unwrap();
+        fs::write(
+            preset.join("java/mappings.json"),
+            r#"{"mappings": {"javax.crypto.Cipher": {"getInstance": "cipher"}, "java.security.MessageDigest": {"getInstance": "hash"}}}"#,
+        )
+        .unwrap();
+        let root = temp.path().join("app");
+        let src = root.join("src/main/scala");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(
+            src.join("Sealing.scala"),
+            "import javax.crypto.{Cipher, Mac}\n\nobject Sealing { def cipher = Cipher.getInstance(\"AES/GCM/NoPadding\") }\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("Digest.scala"),
+            "import java.security._\n\nobject Digest { def sha = MessageDigest.getInstance(\"SHA-256\") }\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("Util.scala"),
+            "object Util { def id(x: Int) = x }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("target/Generated.scala"),
+            "import javax.crypto.Cipher\n",
+        )
+        .unwrap();
+
+        let filter = JavaImportFilter::new(&[preset]).unwrap();
+        let mut found: Vec<_> = JavaPackageLoader
+            .load_user_code(&root)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .filter(|path| filter.has_matching_imports(path).unwrap())
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![src.join("Digest.scala"), src.join("Sealing.scala")]
+        );
+    }
+}
@@ -10,6 +10,9 @@ pub const EXCLUDED_DIRS: &[&str] = &[
     "build",
 ];
 
+/// Where pnpm installs every package, linked from `node_modules`.
+pub const PNPM_STORE_DIR: &str = ".pnpm";
+
 pub const NPM_COMMAND: &str = "npm";
 
 pub const YARN_COMMAND: &str = "yarn";
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use serde::Deserialize;
+use walkdir::WalkDir;
+
 use crate::cli::Language;
 use crate::discovery::cache::DiscoveryCache;
 use crate::discovery::loader::LoadError;
-use crate::discovery::utils::{load_stdlib_from_mappings, walk_source_files};
+use crate::discovery::utils::load_stdlib_from_mappings;
 
 use super::config::*;
+use super::workspace::workspace_members;
 
 static STDLIB_CACHE: OnceLock<HashSet<String>> = OnceLock::new();
 
@@ -27,32 +32,357 @@ pub fn is_stdlib_package(package_path: &str) -> bool {
     stdlib.contains(package_path) || stdlib.contains(root_package)
 }
 
+/// A dependency's source file, tagged with the package that holds it.
+pub struct DependencyFile {
+    pub path: PathBuf,
+    /// The `name` in the package's `package.json`, else its directory name.
+    pub package: String,
+    pub version: Option<String>,
+    pub is_stdlib: bool,
+}
+
+/// The source files of the packages installed in the `node_modules` of the
+/// project and of its workspace members that `select` accepts by name.
+/// Packages nested in another's `node_modules` are packages of their own,
+/// and pnpm's store (`node_modules/.pnpm`) adds the packages only installed
+/// as dependencies of others. Each package is read once, however many links
+/// lead to it.
 pub fn scan_dependencies_using_javascript_tooling(
     project_root: &Path,
     _cache: &mut DiscoveryCache,
-) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let node_modules = project_root.join("node_modules");
-    if !node_modules.exists() {
-        return Ok(vec![]);
-    }
-
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
     let mut files = Vec::new();
+    let mut seen = HashSet::new();
 
-    for ext in FILE_EXTENSIONS {
-        if let Ok(package_files) = walk_source_files(&node_modules, ext, &[], true) {
-            for file in package_files {
-                let is_stdlib = is_stdlib_package(
-                    file.strip_prefix(&node_modules)
-                        .unwrap_or(&file)
-                        .components()
-                        .next()
-                        .and_then(|c| c.as_os_str().to_str())
-                        .unwrap_or(""),
-                );
-                files.push((file, is_stdlib));
+    let roots = std::iter::once(project_root.to_path_buf()).chain(workspace_members(project_root));
+    for node_modules in roots.map(|root| root.join("node_modules")) {
+        if !node_modules.exists() {
+            continue;
+        }
+        for (name, package_dir) in installed_packages(&node_modules)? {
+            if is_workspace_link(&package_dir, project_root) {
+                continue;
+            }
+            let real = package_dir
+                .canonicalize()
+                .unwrap_or_else(|_| package_dir.clone());
+            if !seen.insert(real) {
+                continue;
             }
+            let manifest = read_manifest(&package_dir);
+            let package = manifest.name.unwrap_or(name);
+            if !select(&package) {
+                continue;
+            }
+            let is_stdlib = is_stdlib_package(&package);
+            files.extend(
+                package_files(&package_dir)
+                    .into_iter()
+                    .map(|path| DependencyFile {
+                        path,
+                        package: package.clone(),
+                        version: manifest.version.clone(),
+                        is_stdlib,
+                    }),
+            );
         }
     }
 
     Ok(files)
 }
+
+/// Whether `package_dir` is the link a package manager puts in
+/// `node_modules` for a workspace member, whose files are user code.
+fn is_workspace_link(package_dir: &Path, project_root: &Path) -> bool {
+    let is_link = fs::symlink_metadata(package_dir).is_ok_and(|m| m.file_type().is_symlink());
+    is_link
+        && match (package_dir.canonicalize(), project_root.canonicalize()) {
+            (Ok(target), Ok(root)) => {
+                target.starts_with(&root)
+                    && !target.components().any(|c| c.as_os_str() == "node_modules")
+            }
+            _ => false,
+        }
+}
+
+/// Every package under `node_modules`: those installed directly, then
+/// those in their own nested `node_modules`, then those in pnpm's store
+/// (`.pnpm/<name>@<version>/node_modules/<name>`).
+fn installed_packages(node_modules: &Path) -> Result<Vec<(String, PathBuf)>, LoadError> {
+    let mut packages = node_modules_packages(node_modules)?;
+    let mut i = 0;
+    while i < packages.len() {
+        let nested = packages[i].1.join("node_modules");
+        if nested.is_dir() && !is_symlink(&packages[i].1) {
+            packages.extend(node_modules_packages(&nested)?);
+        }
+        i += 1;
+    }
+
+    let store = node_modules.join(PNPM_STORE_DIR);
+    if store.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&store)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join("node_modules"))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        entries.sort();
+        for dir in entries {
+            // Each entry also links the package's dependencies.
+            packages.extend(
+                node_modules_packages(&dir)?
+                    .into_iter()
+                    .filter(|(_, path)| !is_symlink(path)),
+            );
+        }
+    }
+    Ok(packages)
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// The packages installed directly under `node_modules`, by name: `jose`,
+/// or `@noble/hashes` for scoped ones.
+fn node_modules_packages(node_modules: &Path) -> Result<Vec<(String, PathBuf)>, LoadError> {
+    let mut packages = Vec::new();
+    for entry in fs::read_dir(node_modules)? {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if name.starts_with('.') || !path.is_dir() {
+            continue;
+        }
+        if name.starts_with('@') {
+            for scoped in fs::read_dir(&path)? {
+                let scoped = scoped?.path();
+                if let Some(package) = scoped.file_name().and_then(|n| n.to_str()) {
+                    if scoped.is_dir() {
+                        packages.push((format!("{name}/{package}"), scoped.clone()));
+                    }
+                }
+            }
+        } else {
+            packages.push((name, path));
+        }
+    }
+    packages.sort();
+    Ok(packages)
+}
+
+#[derive(Default, Deserialize)]
+struct Manifest {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+/// The name and version in the `package.json` of `package_dir`.
+fn read_manifest(package_dir: &Path) -> Manifest {
+    fs::read_to_string(package_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The source files of the package at `package_dir`, without those of the
+/// packages in its `node_modules` or in hidden directories.
+fn package_files(package_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(package_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0
+                || !(entry.file_type().is_dir()
+                    && (name == "node_modules" || name.starts_with('.')))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| FILE_EXTENSIONS.contains(&ext))
+                && entry.metadata().is_ok_and(|m| m.len() <= MAX_FILE_SIZE)
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_selected_node_modules() {
+        let project = tempfile::TempDir::new().unwrap();
+        let node_modules = project.path().join("node_modules");
+        for package in ["jose", "left-pad", "@noble/hashes", "@types/node"] {
+            fs::create_dir_all(node_modules.join(package)).unwrap();
+            fs::write(node_modules.join(package).join("index.js"), "").unwrap();
+        }
+        fs::create_dir_all(node_modules.join(".bin")).unwrap();
+
+        let mut seen = Vec::new();
+        let files = scan_dependencies_using_javascript_tooling(
+            project.path(),
+            &mut DiscoveryCache::default(),
+            &mut |package| {
+                seen.push(package.to_string());
+                package == "jose" || package.starts_with("@noble/")
+            },
+        )
+        .unwrap();
+
+        assert_eq!(seen, ["@noble/hashes", "@types/node", "jose", "left-pad"]);
+        let mut paths: Vec<PathBuf> = files.into_iter().map(|file| file.path).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                node_modules.join("@noble/hashes/index.js"),
+                node_modules.join("jose/index.js"),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_workspace_node_modules() {
+        let project = tempfile::TempDir::new().unwrap();
+        let root = project.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("packages/api/node_modules/jose")).unwrap();
+        fs::write(root.join("packages/api/package.json"), "{}").unwrap();
+        fs::write(root.join("packages/api/index.js"), "").unwrap();
+        fs::write(root.join("packages/api/node_modules/jose/index.js"), "").unwrap();
+        fs::create_dir_all(root.join("node_modules/@acme")).unwrap();
+        std::os::unix::fs::symlink(
+            root.join("packages/api"),
+            root.join("node_modules/@acme/api"),
+        )
+        .unwrap();
+
+        let files = scan_dependencies_using_javascript_tooling(
+            root,
+            &mut DiscoveryCache::default(),
+            &mut |_| true,
+        )
+        .unwrap();
+        let paths: Vec<PathBuf> = files.into_iter().map(|file| file.path).collect();
+        assert_eq!(
+            paths,
+            vec![root.join("packages/api/node_modules/jose/index.js")]
+        );
+    }
+
+    #[test]
+    fn test_package_tags_and_nested_packages() {
+        let project = tempfile::TempDir::new().unwrap();
+        let node_modules = project.path().join("node_modules");
+        let write = |path: &str, contents: &str| {
+            let path = node_modules.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(
+            "jsonwebtoken/package.json",
+            r#"{"name": "jsonwebtoken", "version": "9.0.2"}"#,
+        );
+        write("jsonwebtoken/sign.js", "");
+        write(
+            "jsonwebtoken/node_modules/jws/package.json",
+            r#"{"name": "jws", "version": "3.2.2"}"#,
+        );
+        write("jsonwebtoken/node_modules/jws/lib/sign-stream.js", "");
+        write("untagged/index.js", "");
+
+        let files = scan_dependencies_using_javascript_tooling(
+            project.path(),
+            &mut DiscoveryCache::default(),
+            &mut |_| true,
+        )
+        .unwrap();
+        let tags: Vec<(PathBuf, &str, Option<&str>)> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.strip_prefix(&node_modules).unwrap().to_path_buf(),
+                    file.package.as_str(),
+                    file.version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (
+                    PathBuf::from("jsonwebtoken/sign.js"),
+                    "jsonwebtoken",
+                    Some("9.0.2")
+                ),
+                (PathBuf::from("untagged/index.js"), "untagged", None),
+                (
+                    PathBuf::from("jsonwebtoken/node_modules/jws/lib/sign-stream.js"),
+                    "jws",
+                    Some("3.2.2")
+                ),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pnpm_store() {
+        let project = tempfile::TempDir::new().unwrap();
+        let node_modules = project.path().join("node_modules");
+        let store = node_modules.join(".pnpm");
+        let jose = store.join("jose@5.2.0/node_modules/jose");
+        let noble = store.join("@noble+hashes@1.3.3/node_modules/@noble/hashes");
+        for (dir, name, version) in [(&jose, "jose", "5.2.0"), (&noble, "@noble/hashes", "1.3.3")] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(
+                dir.join("package.json"),
+                format!(r#"{{"name": "{name}", "version": "{version}"}}"#),
+            )
+            .unwrap();
+            fs::write(dir.join("index.js"), "").unwrap();
+        }
+        // jose's dependency, linked next to it in the store.
+        fs::create_dir_all(store.join("jose@5.2.0/node_modules/@noble")).unwrap();
+        std::os::unix::fs::symlink(&noble, store.join("jose@5.2.0/node_modules/@noble/hashes"))
+            .unwrap();
+        std::os::unix::fs::symlink(&jose, node_modules.join("jose")).unwrap();
+
+        let files = scan_dependencies_using_javascript_tooling(
+            project.path(),
+            &mut DiscoveryCache::default(),
+            &mut |_| true,
+        )
+        .unwrap();
+        let tags: Vec<(PathBuf, &str)> = files
+            .iter()
+            .map(|file| (file.path.clone(), file.package.as_str()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (node_modules.join("jose/index.js"), "jose"),
+                (noble.join("index.js"), "@noble/hashes"),
+            ]
+        );
+    }
+}
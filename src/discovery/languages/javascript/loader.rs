@@ -71,38 +71,56 @@ impl PackageLoader for JavaScriptPackageLoader {
                 .collect());
         }
 
-        let dep_results = deps::scan_dependencies_using_javascript_tooling(root, cache)?;
-        let mut all_files = Vec::new();
-
-        for (path, is_stdlib) in dep_results {
-            let metadata = get_file_metadata(&path);
-            all_files.push(SourceFile {
-                path,
-                language: Language::Javascript,
-                source_type: if is_stdlib {
-                    SourceType::Stdlib
-                } else {
-                    SourceType::Dependency {
-                        package: "unknown".to_string(),
-                        version: None,
-                    }
-                },
-                package: None,
-                metadata,
-            });
-        }
-
+        let all_files = dependency_files(root, cache, &mut |_| true)?;
         let paths_for_cache: Vec<_> = all_files.iter().map(|f| f.path.clone()).collect();
         cache.set_dependencies(cache_key, paths_for_cache);
 
         Ok(all_files)
     }
 
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, cache, select)
+    }
+
     fn language(&self) -> Language {
         Language::Javascript
     }
 }
 
+fn dependency_files(
+    root: &Path,
+    cache: &mut DiscoveryCache,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<SourceFile>, LoadError> {
+    let dep_results = deps::scan_dependencies_using_javascript_tooling(root, cache, select)?;
+    let mut all_files = Vec::new();
+
+    for dep in dep_results {
+        let metadata = get_file_metadata(&dep.path);
+        all_files.push(SourceFile {
+            path: dep.path,
+            language: Language::Javascript,
+            source_type: if dep.is_stdlib {
+                SourceType::Stdlib
+            } else {
+                SourceType::Dependency {
+                    package: dep.package,
+                    version: dep.version,
+                }
+            },
+            package: None,
+            metadata,
+        });
+    }
+
+    Ok(all_files)
+}
+
 fn get_file_metadata(path: &PathBuf) -> FileMetadata {
     fs::metadata(path)
         .ok()
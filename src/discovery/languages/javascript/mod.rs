@@ -9,6 +9,7 @@ pub mod config;
 pub mod deps;
 pub mod filter;
 pub mod loader;
+pub mod workspace;
 
 pub use filter::JavaScriptImportFilter;
 pub use loader::JavaScriptPackageLoader;
@@ -0,0 +1,156 @@
+//! npm, yarn and pnpm workspaces: the member packages listed by the
+//! `workspaces` of a root `package.json` or the `packages` of a
+//! `pnpm-workspace.yaml`. Members are part of the monorepo's project rather
+//! than projects of their own, and each can have a `node_modules` next to
+//! the hoisted one at the root.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::discovery::utils::glob_match;
+
+use super::config::EXCLUDED_DIRS;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Workspaces {
+    Patterns(Vec<String>),
+    /// Yarn 1's `{"packages": [...], "nohoist": [...]}`.
+    Config {
+        #[serde(default)]
+        packages: Vec<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    workspaces: Option<Workspaces>,
+}
+
+fn read_package_json(dir: &Path) -> Option<PackageJson> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// The workspace patterns declared at `root`, e.g. `packages/*`; patterns
+/// starting with `!` exclude.
+pub fn workspace_patterns(root: &Path) -> Vec<String> {
+    let mut patterns = Vec::new();
+    if let Some(package) = read_package_json(root) {
+        match package.workspaces {
+            Some(Workspaces::Patterns(list)) | Some(Workspaces::Config { packages: list }) => {
+                patterns.extend(list)
+            }
+            None => {}
+        }
+    }
+    if let Some(pnpm) = fs::read_to_string(root.join("pnpm-workspace.yaml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<PnpmWorkspace>(&content).ok())
+    {
+        patterns.extend(pnpm.packages);
+    }
+    patterns
+}
+
+/// The `name` of the `package.json` in `dir`.
+pub fn package_name(dir: &Path) -> Option<String> {
+    read_package_json(dir)?.name.filter(|name| !name.is_empty())
+}
+
+/// The directories under `root` holding a `package.json` that its
+/// workspace patterns match, sorted. Empty when `root` is not a
+/// workspace root.
+pub fn workspace_members(root: &Path) -> Vec<PathBuf> {
+    let patterns = workspace_patterns(root);
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let (excludes, includes): (Vec<&str>, Vec<&str>) = patterns
+        .iter()
+        .map(|pattern| pattern.trim_start_matches("./").trim_end_matches('/'))
+        .partition(|pattern| pattern.starts_with('!'));
+    let excludes: Vec<&str> = excludes.iter().map(|p| &p[1..]).collect();
+
+    let walker = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.file_type().is_dir()
+                && !name.starts_with('.')
+                && !EXCLUDED_DIRS.contains(&name.as_ref())
+        });
+    walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("package.json").is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?;
+            let matched = |pattern: &&str| glob_match(pattern, relative);
+            (includes.iter().any(matched) && !excludes.iter().any(matched))
+                .then(|| entry.path().to_path_buf())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_members() {
+        let root = tempfile::TempDir::new().unwrap();
+        let root = root.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"workspaces": ["packages/*", "!packages/legacy"]}"#,
+        )
+        .unwrap();
+        for dir in [
+            "packages/api",
+            "packages/legacy",
+            "packages/web",
+            "packages/web/node_modules/jose",
+            "tools/cli",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("package.json"), "{}").unwrap();
+        }
+
+        assert_eq!(
+            workspace_members(root),
+            vec![root.join("packages/api"), root.join("packages/web")]
+        );
+        fs::write(
+            root.join("packages/api/package.json"),
+            r#"{"name": "@shop/api"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            package_name(&root.join("packages/api")).as_deref(),
+            Some("@shop/api")
+        );
+        assert_eq!(package_name(&root.join("packages/web")), None);
+
+        fs::write(root.join("package.json"), "{}").unwrap();
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'tools/*'\n",
+        )
+        .unwrap();
+        assert_eq!(workspace_members(root), vec![root.join("tools/cli")]);
+    }
+}
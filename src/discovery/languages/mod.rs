@@ -4,15 +4,46 @@ use crate::cli::Language;
 use crate::discovery::filter::ImportFileFilter;
 use crate::discovery::loader::PackageLoader;
 
+pub mod c;
+pub mod cpp;
+pub mod csharp;
 pub mod go;
+pub mod java;
 pub mod javascript;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod swift;
 
+pub use c::{CImportFilter, CPackageLoader};
+pub use cpp::{CppImportFilter, CppPackageLoader};
+pub use csharp::{CSharpImportFilter, CSharpPackageLoader};
 pub use go::{GoImportFilter, GoPackageLoader};
+pub use java::{JavaImportFilter, JavaPackageLoader};
 pub use javascript::{JavaScriptImportFilter, JavaScriptPackageLoader};
+pub use php::{PhpImportFilter, PhpPackageLoader};
 pub use python::{PythonImportFilter, PythonPackageLoader};
+pub use ruby::{RubyImportFilter, RubyPackageLoader};
 pub use rust::{RustImportFilter, RustPackageLoader};
+pub use swift::{SwiftImportFilter, SwiftPackageLoader};
+
+/// Directories `language`'s loader does not enter in user code.
+pub fn excluded_dirs(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Go => go::config::EXCLUDED_DIRS,
+        Language::Python => python::config::EXCLUDED_DIRS,
+        Language::Javascript | Language::Typescript => javascript::config::EXCLUDED_DIRS,
+        Language::Rust => rust::config::EXCLUDED_DIRS,
+        Language::Java => java::config::EXCLUDED_DIRS,
+        Language::C => c::config::EXCLUDED_DIRS,
+        Language::Cpp => cpp::config::EXCLUDED_DIRS,
+        Language::CSharp => csharp::config::EXCLUDED_DIRS,
+        Language::Ruby => ruby::config::EXCLUDED_DIRS,
+        Language::Php => php::config::EXCLUDED_DIRS,
+        Language::Swift => swift::config::EXCLUDED_DIRS,
+    }
+}
 
 pub trait LanguageModule: Send + Sync {
     fn create_loader(&self) -> Box<dyn PackageLoader>;
@@ -38,6 +69,13 @@ impl LanguageRegistry {
         registry.register(Box::new(python::PythonModule));
         registry.register(Box::new(javascript::JavaScriptModule));
         registry.register(Box::new(rust::RustModule));
+        registry.register(Box::new(java::JavaModule));
+        registry.register(Box::new(c::CModule));
+        registry.register(Box::new(cpp::CppModule));
+        registry.register(Box::new(csharp::CSharpModule));
+        registry.register(Box::new(ruby::RubyModule));
+        registry.register(Box::new(php::PhpModule));
+        registry.register(Box::new(swift::SwiftModule));
         registry
     }
 
@@ -86,4 +124,19 @@ mod tests {
         let registry = LanguageRegistry::new();
         assert!(registry.get_module(Language::Go).is_some());
     }
+
+    #[test]
+    fn test_detect_c_or_cpp_by_sources() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let registry = LanguageRegistry::new();
+
+        std::fs::write(root.join("CMakeLists.txt"), "project(app)\n").unwrap();
+        std::fs::write(root.join("main.c"), "int main(void) { return 0; }\n").unwrap();
+        assert_eq!(registry.detect_languages(root), vec![Language::C]);
+
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/hash.cpp"), "int f() { return 0; }\n").unwrap();
+        assert_eq!(registry.detect_languages(root), vec![Language::Cpp]);
+    }
 }
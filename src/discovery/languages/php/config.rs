@@ -0,0 +1,21 @@
+pub const FILE_EXTENSIONS: &[&str] = &["php"];
+
+pub const EXCLUDED_DIRS: &[&str] = &[
+    "testdata",
+    ".git",
+    "vendor",
+    "node_modules",
+    "storage",
+    "cache",
+];
+
+/// Directories of installed packages that hold no library code.
+pub const DEPENDENCY_EXCLUDED_DIRS: &[&str] = &[".git", "tests", "test", "Tests", "docs"];
+
+/// Files that mark a PHP project root.
+pub const PROJECT_FILES: &[&str] = &["composer.json", "composer.lock"];
+
+/// Where Composer installs packages, as `vendor/<vendor>/<package>`.
+pub const VENDOR_DIR: &str = "vendor";
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
@@ -0,0 +1,105 @@
+//! Packages Composer installed into `vendor/<vendor>/<package>`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::discovery::loader::LoadError;
+use crate::discovery::utils::walk_source_files;
+
+use super::config::*;
+
+/// Installed packages under `root`, by name (`paragonie/halite`), in name
+/// order.
+pub fn installed_packages(root: &Path) -> Vec<(String, PathBuf)> {
+    let mut packages = Vec::new();
+    for vendor in sorted_dirs(&root.join(VENDOR_DIR)) {
+        // Composer's own autoloader, not a package
+        if vendor
+            .file_name()
+            .is_some_and(|name| name == "composer" || name == "bin")
+        {
+            continue;
+        }
+        for package in sorted_dirs(&vendor) {
+            let name = format!(
+                "{}/{}",
+                vendor.file_name().unwrap_or_default().to_string_lossy(),
+                package.file_name().unwrap_or_default().to_string_lossy()
+            );
+            packages.push((name, package));
+        }
+    }
+    packages
+}
+
+/// The PHP sources of the installed packages `select` accepts, with the
+/// package each belongs to.
+pub fn package_sources(
+    root: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<(String, PathBuf)>, LoadError> {
+    let mut sources = Vec::new();
+    for (name, dir) in installed_packages(root) {
+        if !select(&name) {
+            continue;
+        }
+        let paths = walk_source_files(&dir, FILE_EXTENSIONS[0], DEPENDENCY_EXCLUDED_DIRS, true)?;
+        sources.extend(paths.into_iter().map(|path| (name.clone(), path)));
+    }
+    Ok(sources)
+}
+
+fn sorted_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "<?php\n").unwrap();
+    }
+
+    #[test]
+    fn test_installed_packages() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "vendor/paragonie/halite/src/Halite.php");
+        write(temp.path(), "vendor/defuse/php-encryption/src/Crypto.php");
+        write(temp.path(), "vendor/composer/ClassLoader.php");
+        write(temp.path(), "vendor/autoload.php");
+
+        let names: Vec<_> = installed_packages(temp.path())
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(names, vec!["defuse/php-encryption", "paragonie/halite"]);
+    }
+
+    #[test]
+    fn test_package_sources_skip_tests_and_unselected() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "vendor/paragonie/halite/src/Halite.php");
+        write(temp.path(), "vendor/paragonie/halite/tests/HaliteTest.php");
+        write(temp.path(), "vendor/monolog/monolog/src/Logger.php");
+
+        let sources = package_sources(temp.path(), &mut |name| name != "monolog/monolog").unwrap();
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].0, "paragonie/halite");
+        assert!(sources[0].1.ends_with("src/Halite.php"));
+    }
+}
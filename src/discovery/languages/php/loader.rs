@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Language;
+use crate::discovery::cache::DiscoveryCache;
+use crate::discovery::loader::{LoadError, PackageLoader};
+use crate::discovery::utils::walk_source_files;
+use crate::discovery::{FileMetadata, SourceFile, SourceType};
+
+use super::config::*;
+use super::deps;
+
+pub struct PhpPackageLoader;
+
+impl PackageLoader for PhpPackageLoader {
+    fn load_user_code(&self, root: &Path) -> Result<Vec<SourceFile>, LoadError> {
+        if !root.exists() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path does not exist: {}",
+                root.display()
+            )));
+        }
+
+        if !root.is_dir() {
+            return Err(LoadError::InvalidPath(format!(
+                "Path is not a directory: {}",
+                root.display()
+            )));
+        }
+
+        let paths = walk_source_files(root, FILE_EXTENSIONS[0], EXCLUDED_DIRS, false)?;
+
+        Ok(paths
+            .into_iter()
+            .map(|path| SourceFile {
+                metadata: get_file_metadata(&path),
+                path,
+                language: Language::Php,
+                source_type: SourceType::UserCode,
+                package: None,
+            })
+            .collect())
+    }
+
+    /// Composer installs packages into the project's `vendor` directory.
+    fn load_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        self.load_selected_dependencies(root, cache, &mut |_| true)
+    }
+
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        _cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        Ok(deps::package_sources(root, select)?
+            .into_iter()
+            .map(|(package, path)| SourceFile {
+                metadata: get_file_metadata(&path),
+                path,
+                language: Language::Php,
+                source_type: SourceType::Dependency {
+                    package,
+                    version: None,
+                },
+                package: None,
+            })
+            .collect())
+    }
+
+    fn language(&self) -> Language {
+        Language::Php
+    }
+}
+
+fn get_file_metadata(path: &PathBuf) -> FileMetadata {
+    fs::metadata(path)
+        .ok()
+        .map(|m| FileMetadata {
+            size: m.len(),
+            modified: m.modified().ok(),
+            hash: None,
+        })
+        .unwrap_or_else(|| FileMetadata {
+            size: 0,
+            modified: None,
+            hash: None,
+        })
+}
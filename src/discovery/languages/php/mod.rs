@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod deps;
+pub mod filter;
+pub mod loader;
+
+pub use filter::PhpImportFilter;
+pub use loader::PhpPackageLoader;
+
+pub struct PhpModule;
+
+impl LanguageModule for PhpModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(PhpPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(PhpImportFilter::from_bundled().expect("Failed to load bundled PHP import filter"))
+    }
+
+    fn language(&self) -> Language {
+        Language::Php
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        config::PROJECT_FILES
+            .iter()
+            .any(|name| root.join(name).exists())
+    }
+}
@@ -2,6 +2,12 @@ pub const FILE_EXTENSIONS: &[&str] = &["py"];
 
 pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "__pycache__", ".pytest_cache"];
 
+/// Conventional virtualenv directory names, looked for under the project root.
+pub const VIRTUALENV_DIRS: &[&str] = &["venv", ".venv", "env"];
+
+/// Written by `python -m venv` and virtualenv at the root of every environment.
+pub const VIRTUALENV_MARKER: &str = "pyvenv.cfg";
+
 pub const PYTHON_COMMAND: &str = "python3";
 
 pub const PIP_COMMAND: &str = "pip3";
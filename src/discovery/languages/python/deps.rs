@@ -1,12 +1,15 @@
-use std::collections::HashSet;
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::collections::{BTreeSet, HashSet};
+use std::env;
+use std::path::{Component, Path, PathBuf};
 use std::str;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
+
+use tracing::debug;
 
 use crate::cli::Language;
 use crate::discovery::cache::DiscoveryCache;
 use crate::discovery::loader::LoadError;
+use crate::discovery::tooling;
 use crate::discovery::utils::{load_stdlib_from_mappings, walk_source_files};
 
 use super::config::*;
@@ -41,165 +44,446 @@ pub fn is_stdlib_package(package_path: &str) -> bool {
     stdlib.contains(package_path) || stdlib.contains(root_package)
 }
 
+/// A dependency's source file, tagged with the distribution that installed it.
+pub struct DependencyFile {
+    pub path: PathBuf,
+    /// The distribution name from its `METADATA`, or the top-level module
+    /// for files no distribution records.
+    pub package: String,
+    pub version: Option<String>,
+    pub is_stdlib: bool,
+}
+
+static PYTHON_ENV: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets the environment whose packages are the dependencies of every later
+/// scan, from `--python-env`: a virtualenv or a `site-packages` directory.
+pub fn set_python_env(env: Option<PathBuf>) {
+    *PYTHON_ENV.write().unwrap() = env;
+}
+
+/// The installed packages of the project at `project_root`. They are read
+/// from the `site-packages` of `--python-env`, else of the active virtualenv
+/// (`$VIRTUAL_ENV`), else of a virtualenv under the project. Without one,
+/// pip, Poetry and uv are asked where the packages are installed.
 pub fn scan_dependencies_using_python_tooling(
     project_root: &Path,
     _cache: &mut DiscoveryCache,
-) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let mut files = Vec::new();
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
+    if let Some(site_packages) = environment_site_packages(project_root) {
+        debug!(site_packages = %site_packages.display(), "reading installed distributions");
+        return scan_site_packages(&site_packages, select);
+    }
 
+    let mut locations = BTreeSet::new();
     if project_root.join("requirements.txt").exists() {
-        if let Ok(pip_files) = scan_pip_dependencies(project_root) {
-            files.extend(pip_files);
+        match package_locations(PIP_COMMAND, &["list", "--format=json"], project_root) {
+            Ok(found) => locations.extend(found),
+            Err(e) => debug!(error = %e, "pip listing unavailable"),
         }
     }
 
     if project_root.join("pyproject.toml").exists() || project_root.join("poetry.lock").exists() {
-        if let Ok(poetry_files) = scan_poetry_dependencies(project_root) {
-            files.extend(poetry_files);
+        match poetry_site_packages(project_root) {
+            Ok(found) => locations.extend(found),
+            Err(e) => debug!(error = %e, "poetry environment unavailable"),
         }
     }
 
     if project_root.join("uv.lock").exists() || project_root.join("pyproject.toml").exists() {
-        if let Ok(uv_files) = scan_uv_dependencies(project_root) {
-            files.extend(uv_files);
+        match package_locations(UV_COMMAND, &["pip", "list", "--format=json"], project_root) {
+            Ok(found) => locations.extend(found),
+            Err(e) => debug!(error = %e, "uv listing unavailable"),
         }
     }
 
-    if files.is_empty() {
-        if let Ok(site_packages_files) = scan_site_packages(project_root) {
-            files.extend(site_packages_files);
-        }
+    let mut files = Vec::new();
+    for location in locations {
+        files.extend(scan_site_packages(&location, select)?);
     }
-
     Ok(files)
 }
 
-fn scan_pip_dependencies(project_root: &Path) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let output = Command::new(PIP_COMMAND)
-        .args(["list", "--format=json"])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'pip list': {e}")))?;
+/// The `site-packages` of `--python-env`, `$VIRTUAL_ENV` or the first
+/// virtualenv under `project_root`, in that order.
+fn environment_site_packages(project_root: &Path) -> Option<PathBuf> {
+    let configured = PYTHON_ENV.read().unwrap().clone();
+    let active = env::var_os("VIRTUAL_ENV")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    configured
+        .into_iter()
+        .chain(active)
+        .chain(virtualenvs(project_root))
+        .find_map(|env| {
+            if env.file_name().is_some_and(|name| name == "site-packages") && env.is_dir() {
+                return Some(env);
+            }
+            let site_packages = site_packages_dir(&env);
+            if site_packages.is_none() {
+                debug!(env = %env.display(), "no site-packages in environment");
+            }
+            site_packages
+        })
+}
 
-    if !output.status.success() {
-        return Ok(vec![]);
-    }
+/// The distinct install locations `command` lists, as `pip list
+/// --format=json` prints them.
+fn package_locations(
+    command: &str,
+    args: &[&str],
+    project_root: &Path,
+) -> Result<BTreeSet<PathBuf>, LoadError> {
+    let output = tooling::run(command, args, Some(project_root))?;
+
+    let stdout = str::from_utf8(&output).map_err(|e| {
+        LoadError::PackageManager(format!("Invalid UTF-8 from {command} list: {e}"))
+    })?;
 
-    let stdout = str::from_utf8(&output.stdout)
-        .map_err(|e| LoadError::PackageManager(format!("Invalid UTF-8 from pip list: {e}")))?;
+    let packages: Vec<serde_json::Value> = serde_json::from_str(stdout).map_err(|e| {
+        LoadError::PackageManager(format!("Failed to parse {command} list output: {e}"))
+    })?;
 
-    let packages: Vec<serde_json::Value> = serde_json::from_str(stdout)
-        .map_err(|e| LoadError::PackageManager(format!("Failed to parse pip list output: {e}")))?;
+    Ok(packages
+        .iter()
+        .filter_map(|package| package.get("location")?.as_str())
+        .map(PathBuf::from)
+        .filter(|location| location.is_dir())
+        .collect())
+}
 
+/// The `site-packages` of the virtualenv Poetry manages for the project.
+fn poetry_site_packages(project_root: &Path) -> Result<Option<PathBuf>, LoadError> {
+    let output = tooling::run(
+        POETRY_COMMAND,
+        &["env", "info", "--path", "--no-ansi"],
+        Some(project_root),
+    )?;
+    let venv = String::from_utf8_lossy(&output).trim().to_string();
+    Ok((!venv.is_empty())
+        .then(|| site_packages_dir(Path::new(&venv)))
+        .flatten())
+}
+
+/// The source files of the distributions installed in `site_packages` that
+/// `select` accepts by name. Each `*.dist-info` (or `*.egg-info`) directory
+/// names its distribution and version and lists its files in `RECORD`, or
+/// its top-level modules in `top_level.txt`. Modules that no distribution
+/// claims are taken under their own names.
+fn scan_site_packages(
+    site_packages: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
     let mut files = Vec::new();
-    for package in packages {
-        if let (Some(name), Some(location)) = (
-            package.get("name").and_then(|n| n.as_str()),
-            package.get("location").and_then(|l| l.as_str()),
-        ) {
-            let package_path = PathBuf::from(location);
-            if package_path.exists() {
-                let is_stdlib = is_stdlib_package(name);
-                if let Ok(package_files) =
-                    walk_source_files(&package_path, FILE_EXTENSIONS[0], &[], true)
-                {
-                    for file in package_files {
-                        files.push((file, is_stdlib));
-                    }
-                }
+    let mut claimed = HashSet::new();
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(site_packages)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for metadata_dir in entries.iter().filter(|path| is_metadata_dir(path)) {
+        let Some(distribution) = Distribution::read(site_packages, metadata_dir) else {
+            continue;
+        };
+        claimed.extend(distribution.top_level.iter().cloned());
+        let is_stdlib = is_stdlib_package(&distribution.name);
+        if !is_stdlib && !select(&distribution.name) {
+            continue;
+        }
+        files.extend(distribution.files.into_iter().map(|path| DependencyFile {
+            path,
+            package: distribution.name.clone(),
+            version: distribution.version.clone(),
+            is_stdlib,
+        }));
+    }
+
+    for path in entries {
+        let Some(name) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let package = name.strip_suffix(".py").unwrap_or(&name);
+        if package.contains('.') || package == "__pycache__" || claimed.contains(package) {
+            continue;
+        }
+        let is_stdlib = is_stdlib_package(package);
+        if !is_stdlib && !select(package) {
+            continue;
+        }
+        let tag = |file| DependencyFile {
+            path: file,
+            package: package.to_string(),
+            version: None,
+            is_stdlib,
+        };
+        if path.is_dir() {
+            if let Ok(package_files) = walk_source_files(&path, FILE_EXTENSIONS[0], &[], true) {
+                files.extend(package_files.into_iter().map(tag));
             }
+        } else if name.ends_with(".py") {
+            files.push(tag(path));
         }
     }
 
     Ok(files)
 }
 
-fn scan_poetry_dependencies(project_root: &Path) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let output = Command::new(POETRY_COMMAND)
-        .args(["show", "--no-ansi"])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'poetry show': {e}")))?;
+fn is_metadata_dir(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "dist-info" || extension == "egg-info")
+        && path.is_dir()
+}
+
+/// An installed distribution, read from its metadata directory.
+struct Distribution {
+    name: String,
+    version: Option<String>,
+    /// Its `.py` files.
+    files: Vec<PathBuf>,
+    /// The top-level modules and packages its files are under.
+    top_level: HashSet<String>,
+}
+
+impl Distribution {
+    fn read(site_packages: &Path, metadata_dir: &Path) -> Option<Self> {
+        let metadata = std::fs::read_to_string(metadata_dir.join("METADATA"))
+            .or_else(|_| std::fs::read_to_string(metadata_dir.join("PKG-INFO")))
+            .ok()?;
+        let header = |key: &str| {
+            metadata
+                .lines()
+                .take_while(|line| !line.is_empty())
+                .find_map(|line| Some(line.strip_prefix(key)?.strip_prefix(':')?.trim()))
+                .map(str::to_string)
+        };
+        let name = header("Name")?;
 
-    if !output.status.success() {
-        return Ok(vec![]);
+        let mut files = Vec::new();
+        let mut top_level = HashSet::new();
+        if let Ok(record) = std::fs::read_to_string(metadata_dir.join("RECORD")) {
+            for line in record.lines() {
+                let Some(relative) = record_path(line) else {
+                    continue;
+                };
+                if let Some(Component::Normal(first)) = relative.components().next() {
+                    let first = first.to_string_lossy();
+                    top_level.insert(first.strip_suffix(".py").unwrap_or(&first).to_string());
+                }
+                let path = site_packages.join(relative);
+                if relative
+                    .extension()
+                    .is_some_and(|e| e == FILE_EXTENSIONS[0])
+                    && path.is_file()
+                {
+                    files.push(path);
+                }
+            }
+        } else if let Ok(modules) = std::fs::read_to_string(metadata_dir.join("top_level.txt")) {
+            for module in modules.lines().map(str::trim).filter(|m| !m.is_empty()) {
+                top_level.insert(module.to_string());
+                let dir = site_packages.join(module);
+                let file = site_packages.join(format!("{module}.py"));
+                if dir.is_dir() {
+                    files.extend(walk_source_files(&dir, FILE_EXTENSIONS[0], &[], true).ok()?);
+                } else if file.is_file() {
+                    files.push(file);
+                }
+            }
+        }
+        files.sort();
+
+        Some(Self {
+            version: header("Version"),
+            name,
+            files,
+            top_level,
+        })
     }
+}
 
-    Ok(vec![])
+/// The path of a `RECORD` line (`path,hash,size`), if it stays inside
+/// `site-packages`; scripts are recorded as `../../../bin/...`.
+fn record_path(line: &str) -> Option<&Path> {
+    let path = match line.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => line.split(',').next()?,
+    };
+    let path = Path::new(path);
+    (!path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_))))
+    .then_some(path)
 }
 
-fn scan_uv_dependencies(project_root: &Path) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let output = Command::new(UV_COMMAND)
-        .args(["pip", "list", "--format=json"])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'uv pip list': {e}")))?;
+/// Whether `dir` is the root of a virtualenv.
+pub fn is_virtualenv(dir: &Path) -> bool {
+    dir.join(VIRTUALENV_MARKER).is_file()
+}
 
-    if !output.status.success() {
-        return Ok(vec![]);
+/// Virtualenvs directly under `project_root`: the conventional names first,
+/// then any other directory holding a `pyvenv.cfg`.
+fn virtualenvs(project_root: &Path) -> Vec<PathBuf> {
+    let mut venvs: Vec<PathBuf> = VIRTUALENV_DIRS
+        .iter()
+        .map(|name| project_root.join(name))
+        .filter(|path| path.is_dir())
+        .collect();
+    let mut others: Vec<PathBuf> = std::fs::read_dir(project_root)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_virtualenv(path) && !venvs.contains(path))
+        .collect();
+    others.sort();
+    venvs.extend(others);
+    venvs
+}
+
+/// The `site-packages` of a virtualenv: `lib/python3.X/site-packages`, or
+/// `Lib/site-packages` on Windows.
+fn site_packages_dir(venv: &Path) -> Option<PathBuf> {
+    let windows = venv.join("Lib").join("site-packages");
+    if windows.is_dir() {
+        return Some(windows);
     }
+    let mut versions: Vec<PathBuf> = std::fs::read_dir(venv.join("lib"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("python3"))
+        .map(|entry| entry.path().join("site-packages"))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    versions.sort();
+    versions.pop()
+}
 
-    let stdout = str::from_utf8(&output.stdout)
-        .map_err(|e| LoadError::PackageManager(format!("Invalid UTF-8 from uv pip list: {e}")))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let packages: Vec<serde_json::Value> = serde_json::from_str(stdout).map_err(|e| {
-        LoadError::PackageManager(format!("Failed to parse uv pip list output: {e}"))
-    })?;
+    #[test]
+    fn test_site_packages_dir() {
+        let venv = tempfile::TempDir::new().unwrap();
+        assert_eq!(site_packages_dir(venv.path()), None);
+        let site_packages = venv.path().join("lib/python3.12/site-packages");
+        std::fs::create_dir_all(&site_packages).unwrap();
+        assert_eq!(site_packages_dir(venv.path()), Some(site_packages));
+    }
 
-    let mut files = Vec::new();
-    for package in packages {
-        if let (Some(name), Some(location)) = (
-            package.get("name").and_then(|n| n.as_str()),
-            package.get("location").and_then(|l| l.as_str()),
-        ) {
-            let package_path = PathBuf::from(location);
-            if package_path.exists() {
-                let is_stdlib = is_stdlib_package(name);
-                if let Ok(package_files) =
-                    walk_source_files(&package_path, FILE_EXTENSIONS[0], &[], true)
-                {
-                    for file in package_files {
-                        files.push((file, is_stdlib));
-                    }
-                }
-            }
+    #[test]
+    fn test_scan_selected_site_packages() {
+        let project = tempfile::TempDir::new().unwrap();
+        let site_packages = project.path().join(".venv/lib/python3.12/site-packages");
+        for package in ["nacl", "requests", "nacl-1.5.0.dist-info"] {
+            std::fs::create_dir_all(site_packages.join(package)).unwrap();
+            std::fs::write(site_packages.join(package).join("__init__.py"), "").unwrap();
         }
+        std::fs::write(site_packages.join("six.py"), "").unwrap();
+
+        let files =
+            scan_site_packages(&site_packages, &mut |package| package != "requests").unwrap();
+        let mut paths: Vec<PathBuf> = files.into_iter().map(|file| file.path).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                site_packages.join("nacl/__init__.py"),
+                site_packages.join("six.py"),
+            ]
+        );
     }
 
-    Ok(files)
-}
+    #[test]
+    fn test_scan_distributions() {
+        let project = tempfile::TempDir::new().unwrap();
+        let site_packages = project.path().join("lib/python3.12/site-packages");
+        let write = |path: &str, contents: &str| {
+            let path = site_packages.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+        write("nacl/__init__.py", "");
+        write("nacl/secret.py", "");
+        write(
+            "PyNaCl-1.5.0.dist-info/METADATA",
+            "Metadata-Version: 2.1\nName: PyNaCl\nVersion: 1.5.0\n\nName: not a header\n",
+        );
+        write(
+            "PyNaCl-1.5.0.dist-info/RECORD",
+            "nacl/__init__.py,sha256=abc,10\nnacl/secret.py,,\n../../../bin/nacl-tool,,\nPyNaCl-1.5.0.dist-info/METADATA,,\n",
+        );
+        write("rsa/pkcs1.py", "");
+        write("rsa-4.9.egg-info/PKG-INFO", "Name: rsa\nVersion: 4.9\n");
+        write("rsa-4.9.egg-info/top_level.txt", "rsa\n");
+        write("six.py", "");
 
-fn scan_site_packages(project_root: &Path) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let venv_paths = vec![
-        project_root.join("venv"),
-        project_root.join(".venv"),
-        project_root.join("env"),
-    ];
-
-    for venv_path in venv_paths {
-        if venv_path.exists() {
-            let site_packages = venv_path.join("lib").join("python3").join("site-packages");
-            if site_packages.exists() {
-                let mut files = Vec::new();
-                if let Ok(package_files) =
-                    walk_source_files(&site_packages, FILE_EXTENSIONS[0], &[], true)
-                {
-                    for file in package_files {
-                        let is_stdlib = is_stdlib_package(
-                            file.strip_prefix(&site_packages)
-                                .unwrap_or(&file)
-                                .components()
-                                .next()
-                                .and_then(|c| c.as_os_str().to_str())
-                                .unwrap_or(""),
-                        );
-                        files.push((file, is_stdlib));
-                    }
-                }
-                return Ok(files);
-            }
-        }
+        let files = scan_site_packages(&site_packages, &mut |_| true).unwrap();
+        let tags: Vec<(PathBuf, &str, Option<&str>)> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.path
+                        .strip_prefix(&site_packages)
+                        .unwrap()
+                        .to_path_buf(),
+                    file.package.as_str(),
+                    file.version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (PathBuf::from("nacl/__init__.py"), "PyNaCl", Some("1.5.0")),
+                (PathBuf::from("nacl/secret.py"), "PyNaCl", Some("1.5.0")),
+                (PathBuf::from("rsa/pkcs1.py"), "rsa", Some("4.9")),
+                (PathBuf::from("six.py"), "six", None),
+            ]
+        );
+
+        let files = scan_site_packages(&site_packages, &mut |name| name != "PyNaCl").unwrap();
+        assert!(files.iter().all(|file| file.package != "PyNaCl"));
+    }
+
+    #[test]
+    fn test_python_env_site_packages() {
+        let project = tempfile::TempDir::new().unwrap();
+        let env = project.path().join("envs/py312");
+        let site_packages = env.join("lib/python3.12/site-packages");
+        std::fs::create_dir_all(&site_packages).unwrap();
+
+        set_python_env(Some(env));
+        assert_eq!(
+            environment_site_packages(project.path()),
+            Some(site_packages.clone())
+        );
+        set_python_env(Some(site_packages.clone()));
+        assert_eq!(
+            environment_site_packages(project.path()),
+            Some(site_packages)
+        );
+        set_python_env(None);
     }
 
-    Ok(vec![])
+    #[test]
+    fn test_virtualenvs_found_by_name_or_marker() {
+        let project = tempfile::TempDir::new().unwrap();
+        for dir in [".venv", "py311", "src"] {
+            std::fs::create_dir_all(project.path().join(dir)).unwrap();
+        }
+        std::fs::write(project.path().join("py311/pyvenv.cfg"), "").unwrap();
+
+        assert_eq!(
+            virtualenvs(project.path()),
+            vec![project.path().join(".venv"), project.path().join("py311")]
+        );
+    }
 }
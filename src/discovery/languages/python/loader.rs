@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -12,6 +13,29 @@ use super::deps;
 
 pub struct PythonPackageLoader;
 
+/// Whether `path` is inside a virtualenv or `site-packages` below `root`;
+/// installed packages are dependencies, not user code, whatever the
+/// environment is called. `venv_dirs` caches the directories checked.
+fn in_virtualenv(root: &Path, path: &Path, venv_dirs: &mut HashMap<PathBuf, bool>) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    if relative
+        .components()
+        .any(|component| component.as_os_str() == "site-packages")
+    {
+        return true;
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != root)
+        .any(|dir| {
+            *venv_dirs
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| deps::is_virtualenv(dir))
+        })
+}
+
 impl PackageLoader for PythonPackageLoader {
     fn load_user_code(&self, root: &Path) -> Result<Vec<SourceFile>, LoadError> {
         if !root.exists() {
@@ -29,8 +53,10 @@ impl PackageLoader for PythonPackageLoader {
         }
 
         let paths = walk_source_files(root, FILE_EXTENSIONS[0], EXCLUDED_DIRS, false)?;
+        let mut venv_dirs = HashMap::new();
         Ok(paths
             .into_iter()
+            .filter(|path| !in_virtualenv(root, path, &mut venv_dirs))
             .map(|path| SourceFile {
                 path: path.clone(),
                 language: Language::Python,
@@ -67,38 +93,56 @@ impl PackageLoader for PythonPackageLoader {
                 .collect());
         }
 
-        let dep_results = deps::scan_dependencies_using_python_tooling(root, cache)?;
-        let mut all_files = Vec::new();
-
-        for (path, is_stdlib) in dep_results {
-            let metadata = get_file_metadata(&path);
-            all_files.push(SourceFile {
-                path,
-                language: Language::Python,
-                source_type: if is_stdlib {
-                    SourceType::Stdlib
-                } else {
-                    SourceType::Dependency {
-                        package: "unknown".to_string(),
-                        version: None,
-                    }
-                },
-                package: None,
-                metadata,
-            });
-        }
-
+        let all_files = dependency_files(root, cache, &mut |_| true)?;
         let paths_for_cache: Vec<_> = all_files.iter().map(|f| f.path.clone()).collect();
         cache.set_dependencies(cache_key, paths_for_cache);
 
         Ok(all_files)
     }
 
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, cache, select)
+    }
+
     fn language(&self) -> Language {
         Language::Python
     }
 }
 
+fn dependency_files(
+    root: &Path,
+    cache: &mut DiscoveryCache,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<SourceFile>, LoadError> {
+    let dep_results = deps::scan_dependencies_using_python_tooling(root, cache, select)?;
+    let mut all_files = Vec::new();
+
+    for dep in dep_results {
+        let metadata = get_file_metadata(&dep.path);
+        all_files.push(SourceFile {
+            path: dep.path,
+            language: Language::Python,
+            source_type: if dep.is_stdlib {
+                SourceType::Stdlib
+            } else {
+                SourceType::Dependency {
+                    package: dep.package,
+                    version: dep.version,
+                }
+            },
+            package: None,
+            metadata,
+        });
+    }
+
+    Ok(all_files)
+}
+
 fn get_file_metadata(path: &PathBuf) -> FileMetadata {
     fs::metadata(path)
         .ok()
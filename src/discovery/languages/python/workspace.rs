@@ -0,0 +1,108 @@
+//! Python monorepos: directories with a `pyproject.toml`, `setup.py` or
+//! `setup.cfg` of their own, each a distribution that is built and
+//! published apart from the rest of the tree.
+
+use std::fs;
+use std::path::Path;
+
+use crate::discovery::utils::toml_string;
+
+/// The files that make a directory a Python project.
+pub const PROJECT_FILES: &[&str] = &["pyproject.toml", "setup.py", "setup.cfg"];
+
+/// Whether `dir` holds a Python project.
+pub fn is_project(dir: &Path) -> bool {
+    PROJECT_FILES.iter().any(|file| dir.join(file).is_file())
+}
+
+/// The distribution name of the project in `dir`: the `[project]` or
+/// `[tool.poetry]` name of its `pyproject.toml`, the `[metadata]` name of
+/// its `setup.cfg`, or else the name of `dir`.
+pub fn project_name(dir: &Path) -> Option<String> {
+    if let Ok(pyproject) = fs::read_to_string(dir.join("pyproject.toml")) {
+        let name = toml_string(&pyproject, "project", "name")
+            .or_else(|| toml_string(&pyproject, "tool.poetry", "name"));
+        if name.is_some() {
+            return name;
+        }
+    }
+    if let Ok(setup_cfg) = fs::read_to_string(dir.join("setup.cfg")) {
+        if let Some(name) = setup_cfg_name(&setup_cfg) {
+            return Some(name);
+        }
+    }
+    dir.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+/// `name` in the `[metadata]` section, an INI value that is not quoted.
+fn setup_cfg_name(setup_cfg: &str) -> Option<String> {
+    let mut in_metadata = false;
+    for line in setup_cfg.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_metadata = line == "[metadata]";
+        } else if in_metadata {
+            let Some((key, value)) = line.split_once(['=', ':']) else {
+                continue;
+            };
+            if key.trim() == "name" && !value.trim().is_empty() {
+                return Some(value.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_name() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        for project in ["auth", "billing", "tokens", "scripts"] {
+            fs::create_dir_all(root.join(project)).unwrap();
+        }
+        fs::write(
+            root.join("auth/pyproject.toml"),
+            "[build-system]\nrequires = [\"hatchling\"]\n\n[project]\nname = \"shop-auth\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("billing/pyproject.toml"),
+            "[tool.poetry]\nname = \"shop-billing\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tokens/setup.cfg"),
+            "[metadata]\nname = shop-tokens\nversion = 1.0\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("scripts/setup.py"),
+            "from setuptools import setup\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            project_name(&root.join("auth")).as_deref(),
+            Some("shop-auth")
+        );
+        assert_eq!(
+            project_name(&root.join("billing")).as_deref(),
+            Some("shop-billing")
+        );
+        assert_eq!(
+            project_name(&root.join("tokens")).as_deref(),
+            Some("shop-tokens")
+        );
+        assert_eq!(
+            project_name(&root.join("scripts")).as_deref(),
+            Some("scripts")
+        );
+        assert!(is_project(&root.join("scripts")));
+        assert!(!is_project(root));
+    }
+}
@@ -0,0 +1,8 @@
+pub const FILE_EXTENSIONS: &[&str] = &["rb"];
+
+pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "vendor", ".bundle", "tmp", "log"];
+
+/// Files that mark a Ruby project root.
+pub const PROJECT_FILES: &[&str] = &["Gemfile", "Gemfile.lock", "Rakefile"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
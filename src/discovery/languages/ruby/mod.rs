@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod filter;
+pub mod loader;
+
+pub use filter::RubyImportFilter;
+pub use loader::RubyPackageLoader;
+
+pub struct RubyModule;
+
+impl LanguageModule for RubyModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(RubyPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(
+            RubyImportFilter::from_bundled().expect("Failed to load bundled Ruby import filter"),
+        )
+    }
+
+    fn language(&self) -> Language {
+        Language::Ruby
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        config::PROJECT_FILES
+            .iter()
+            .any(|name| root.join(name).exists())
+    }
+}
@@ -5,3 +5,6 @@ pub const EXCLUDED_DIRS: &[&str] = &["testdata", ".git", "target"];
 pub const CARGO_COMMAND: &str = "cargo";
 
 pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Where `cargo vendor` copies the dependency crates.
+pub const VENDOR_DIR: &str = "vendor";
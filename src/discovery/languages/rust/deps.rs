@@ -1,12 +1,17 @@
 use std::collections::HashSet;
+use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::str;
 use std::sync::OnceLock;
 
+use serde::Deserialize;
+use tracing::{debug, warn};
+
 use crate::cli::Language;
 use crate::discovery::cache::DiscoveryCache;
 use crate::discovery::loader::LoadError;
+use crate::discovery::tooling;
 use crate::discovery::utils::{load_stdlib_from_mappings, walk_source_files};
 
 use super::config::*;
@@ -34,56 +39,325 @@ pub fn is_stdlib_package(package_path: &str) -> bool {
     stdlib.contains(package_path) || stdlib.contains(root_package)
 }
 
+/// A source file of a dependency crate.
+pub struct DependencyFile {
+    pub path: PathBuf,
+    pub krate: String,
+    pub version: Option<String>,
+}
+
+/// The sources of the crates the project at `project_root` depends on that
+/// `select` accepts by name: the `vendor/` directory `cargo vendor` writes
+/// when there is one, else the crates cargo resolves offline, else those
+/// `Cargo.lock` pins that are already in the cargo registry.
 pub fn scan_dependencies_using_cargo_tooling(
     project_root: &Path,
     _cache: &mut DiscoveryCache,
-) -> Result<Vec<(PathBuf, bool)>, LoadError> {
-    let cargo_toml = project_root.join("Cargo.toml");
-    if !cargo_toml.exists() {
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
+    if !project_root.join("Cargo.toml").exists() && !project_root.join("Cargo.lock").exists() {
         return Ok(vec![]);
     }
 
-    let output = Command::new(CARGO_COMMAND)
-        .args(["metadata", "--format-version", "1", "--no-deps"])
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| LoadError::PackageManager(format!("Failed to run 'cargo metadata': {e}")))?;
+    let vendor_dir = project_root.join(VENDOR_DIR);
+    if vendor_dir.is_dir() {
+        return scan_vendor_dir(&vendor_dir, select);
+    }
+
+    let crates = match cargo_metadata_crates(project_root) {
+        Ok(crates) => crates,
+        Err(e) => {
+            if matches!(&e, LoadError::Tool(e) if e.is_not_found()) {
+                debug!(error = %e, "reading Cargo.lock without cargo");
+            } else {
+                warn!(error = %e, "cargo metadata failed, reading locked crates from the registry");
+            }
+            registry_crates(project_root, &cargo_home())?
+        }
+    };
 
-    if !output.status.success() {
-        return Ok(vec![]);
+    let mut files = Vec::new();
+    for (krate, version, dir) in crates {
+        if !select(&krate) {
+            continue;
+        }
+        let src = dir.join("src");
+        let walk_root = if src.is_dir() { src } else { dir };
+        for path in walk_source_files(&walk_root, FILE_EXTENSIONS[0], EXCLUDED_DIRS, true)? {
+            files.push(DependencyFile {
+                path,
+                krate: krate.clone(),
+                version: Some(version.clone()),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// The crates `cargo vendor` copied into `vendor_dir`, one directory each.
+fn scan_vendor_dir(
+    vendor_dir: &Path,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<DependencyFile>, LoadError> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(vendor_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("Cargo.toml").is_file())
+        .collect();
+    dirs.sort();
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        let (krate, version) = crate_name_and_version(&dir);
+        if !select(&krate) {
+            continue;
+        }
+        for path in walk_source_files(&dir, FILE_EXTENSIONS[0], EXCLUDED_DIRS, true)? {
+            files.push(DependencyFile {
+                path,
+                krate: krate.clone(),
+                version: version.clone(),
+            });
+        }
     }
+    Ok(files)
+}
 
-    let stdout = str::from_utf8(&output.stdout).map_err(|e| {
+/// A vendored crate's name and version from its manifest, else from its
+/// directory name (`ring` or, when versions clash, `ring-0.16.20`).
+fn crate_name_and_version(dir: &Path) -> (String, Option<String>) {
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap_or_default();
+    let mut name = None;
+    let mut version = None;
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if in_package {
+            match line.split_once('=').map(|(k, v)| (k.trim(), v)) {
+                Some(("name", value)) => name = Some(unquote(value)),
+                Some(("version", value)) => version = Some(unquote(value)),
+                _ => {}
+            }
+        }
+    }
+    let name = name.unwrap_or_else(|| {
+        let dir_name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        dir_name
+            .rsplit_once('-')
+            .filter(|(_, version)| version.starts_with(|c: char| c.is_ascii_digit()))
+            .map_or(dir_name.clone(), |(name, _)| name.to_string())
+    });
+    (name, version)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches(['"', '\'']).to_string()
+}
+
+/// The non-workspace crates `cargo metadata` resolves without the network,
+/// as name, version and source directory.
+fn cargo_metadata_crates(project_root: &Path) -> Result<Vec<(String, String, PathBuf)>, LoadError> {
+    let output = tooling::run(
+        CARGO_COMMAND,
+        &["metadata", "--format-version", "1", "--offline"],
+        Some(project_root),
+    )?;
+    let stdout = str::from_utf8(&output).map_err(|e| {
         LoadError::PackageManager(format!("Invalid UTF-8 from cargo metadata: {e}"))
     })?;
-
-    let _metadata: serde_json::Value = serde_json::from_str(stdout)
+    let metadata: CargoMetadata = serde_json::from_str(stdout)
         .map_err(|e| LoadError::PackageManager(format!("Failed to parse cargo metadata: {e}")))?;
+    Ok(metadata
+        .packages
+        .into_iter()
+        .filter(|package| package.source.is_some())
+        .filter_map(|package| {
+            let dir = package.manifest_path.parent()?.to_path_buf();
+            Some((package.name, package.version, dir))
+        })
+        .collect())
+}
 
-    let target_dir = project_root.join("target");
-    if !target_dir.exists() {
-        return Ok(vec![]);
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    manifest_path: PathBuf,
+}
+
+fn cargo_home() -> PathBuf {
+    if let Some(dir) = env::var_os("CARGO_HOME").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
     }
+    env::var_os("HOME")
+        .map(|home| Path::new(&home).join(".cargo"))
+        .unwrap_or_else(|| PathBuf::from(".cargo"))
+}
 
-    let deps_dir = target_dir.join("debug").join("deps");
-    if !deps_dir.exists() {
+/// The registry crates `Cargo.lock` pins that are already unpacked under
+/// `cargo_home`, found without cargo. Crates not downloaded are left out.
+fn registry_crates(
+    project_root: &Path,
+    cargo_home: &Path,
+) -> Result<Vec<(String, String, PathBuf)>, LoadError> {
+    let Ok(lock) = fs::read_to_string(project_root.join("Cargo.lock")) else {
         return Ok(vec![]);
-    }
+    };
+    let registry_src = cargo_home.join("registry").join("src");
+    let mut indexes: Vec<PathBuf> = match fs::read_dir(&registry_src) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+        Err(_) => return Ok(vec![]),
+    };
+    indexes.sort();
 
-    let mut files = Vec::new();
-    if let Ok(package_files) = walk_source_files(&deps_dir, FILE_EXTENSIONS[0], &[], true) {
-        for file in package_files {
-            let is_stdlib = is_stdlib_package(
-                file.strip_prefix(&deps_dir)
-                    .unwrap_or(&file)
-                    .components()
-                    .next()
-                    .and_then(|c| c.as_os_str().to_str())
-                    .unwrap_or(""),
-            );
-            files.push((file, is_stdlib));
+    Ok(locked_registry_crates(&lock)
+        .into_iter()
+        .filter_map(|(name, version)| {
+            let dir_name = format!("{name}-{version}");
+            let dir = indexes
+                .iter()
+                .map(|index| index.join(&dir_name))
+                .find(|dir| dir.is_dir())?;
+            Some((name, version, dir))
+        })
+        .collect())
+}
+
+/// The name and version of each `[[package]]` in `lock` that comes from a
+/// registry; path and git crates have no registry copy.
+fn locked_registry_crates(lock: &str) -> Vec<(String, String)> {
+    let mut crates = Vec::new();
+    for package in lock.split("[[package]]").skip(1) {
+        let mut name = None;
+        let mut version = None;
+        let mut registry = false;
+        for line in package.lines().map(str::trim) {
+            if line.starts_with('[') {
+                break;
+            }
+            match line.split_once('=').map(|(k, v)| (k.trim(), v)) {
+                Some(("name", value)) => name = Some(unquote(value)),
+                Some(("version", value)) => version = Some(unquote(value)),
+                Some(("source", value)) => {
+                    let source = unquote(value);
+                    registry = source.starts_with("registry+") || source.starts_with("sparse+");
+                }
+                _ => {}
+            }
+        }
+        if let (Some(name), Some(version), true) = (name, version, registry) {
+            crates.push((name, version));
         }
     }
+    crates
+}
 
-    Ok(files)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "ring",
+]
+
+[[package]]
+name = "ring"
+version = "0.17.8"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "c17fa4cb658e3583423e915b9f3acc01cceaee1860e33d59ebae66adc3a2dc0d"
+
+[[package]]
+name = "sha2"
+version = "0.10.8"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "patched"
+version = "1.0.0"
+source = "git+https://github.com/example/patched#abc123"
+"#;
+
+    #[test]
+    fn test_locked_registry_crates() {
+        assert_eq!(
+            locked_registry_crates(LOCK),
+            vec![
+                ("ring".to_string(), "0.17.8".to_string()),
+                ("sha2".to_string(), "0.10.8".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registry_crates() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cargo_home = tempfile::TempDir::new().unwrap();
+        fs::write(project.path().join("Cargo.lock"), LOCK).unwrap();
+        let ring = cargo_home
+            .path()
+            .join("registry/src/index.crates.io-6f17d22bba15001f/ring-0.17.8");
+        fs::create_dir_all(ring.join("src")).unwrap();
+
+        let crates = registry_crates(project.path(), cargo_home.path()).unwrap();
+        assert_eq!(
+            crates,
+            vec![("ring".to_string(), "0.17.8".to_string(), ring)]
+        );
+    }
+
+    #[test]
+    fn test_vendor_dir() {
+        let project = tempfile::TempDir::new().unwrap();
+        fs::write(
+            project.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n",
+        )
+        .unwrap();
+        for (dir, manifest) in [
+            (
+                "vendor/ring",
+                "[package]\nname = \"ring\"\nversion = \"0.17.8\"\n",
+            ),
+            ("vendor/sha2-0.9.9", ""),
+            (
+                "vendor/md-5",
+                "[package]\nname = \"md-5\"\nversion = \"0.10.6\"\n",
+            ),
+        ] {
+            let dir = project.path().join(dir);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+            fs::write(dir.join("src/lib.rs"), "").unwrap();
+        }
+
+        let mut cache = DiscoveryCache::default();
+        let files =
+            scan_dependencies_using_cargo_tooling(project.path(), &mut cache, &mut |krate| {
+                krate != "md-5"
+            })
+            .unwrap();
+        let crates: Vec<(&str, Option<&str>)> = files
+            .iter()
+            .map(|file| (file.krate.as_str(), file.version.as_deref()))
+            .collect();
+        assert_eq!(crates, vec![("ring", Some("0.17.8")), ("sha2", None)]);
+    }
 }
@@ -9,6 +9,7 @@ use crate::discovery::{FileMetadata, SourceFile, SourceType};
 
 use super::config::*;
 use super::deps;
+use super::workspace::workspace_members;
 
 pub struct RustPackageLoader;
 
@@ -28,11 +29,27 @@ impl PackageLoader for RustPackageLoader {
             )));
         }
 
-        let src_dir = root.join("src");
-        let paths = if src_dir.exists() {
-            walk_source_files(&src_dir, FILE_EXTENSIONS[0], EXCLUDED_DIRS, false)?
+        // The root package's and each workspace member's `src/`; a loose
+        // directory of Rust files without one is read whole.
+        let src_dirs: Vec<PathBuf> = std::iter::once(root.to_path_buf())
+            .chain(workspace_members(root))
+            .map(|dir| dir.join("src"))
+            .filter(|src| src.is_dir())
+            .collect();
+        let paths = if src_dirs.is_empty() {
+            let excluded: Vec<&str> = EXCLUDED_DIRS.iter().copied().chain([VENDOR_DIR]).collect();
+            walk_source_files(root, FILE_EXTENSIONS[0], &excluded, false)?
         } else {
-            walk_source_files(root, FILE_EXTENSIONS[0], EXCLUDED_DIRS, false)?
+            let mut paths = Vec::new();
+            for src in &src_dirs {
+                paths.extend(walk_source_files(
+                    src,
+                    FILE_EXTENSIONS[0],
+                    EXCLUDED_DIRS,
+                    false,
+                )?);
+            }
+            paths
         };
 
         Ok(paths
@@ -73,38 +90,52 @@ impl PackageLoader for RustPackageLoader {
                 .collect());
         }
 
-        let dep_results = deps::scan_dependencies_using_cargo_tooling(root, cache)?;
-        let mut all_files = Vec::new();
-
-        for (path, is_stdlib) in dep_results {
-            let metadata = get_file_metadata(&path);
-            all_files.push(SourceFile {
-                path,
-                language: Language::Rust,
-                source_type: if is_stdlib {
-                    SourceType::Stdlib
-                } else {
-                    SourceType::Dependency {
-                        package: "unknown".to_string(),
-                        version: None,
-                    }
-                },
-                package: None,
-                metadata,
-            });
-        }
-
+        let all_files = dependency_files(root, cache, &mut |_| true)?;
         let paths_for_cache: Vec<_> = all_files.iter().map(|f| f.path.clone()).collect();
         cache.set_dependencies(cache_key, paths_for_cache);
 
         Ok(all_files)
     }
 
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        dependency_files(root, cache, select)
+    }
+
     fn language(&self) -> Language {
         Language::Rust
     }
 }
 
+fn dependency_files(
+    root: &Path,
+    cache: &mut DiscoveryCache,
+    select: &mut dyn FnMut(&str) -> bool,
+) -> Result<Vec<SourceFile>, LoadError> {
+    Ok(
+        deps::scan_dependencies_using_cargo_tooling(root, cache, select)?
+            .into_iter()
+            .map(|file| {
+                let metadata = get_file_metadata(&file.path);
+                SourceFile {
+                    path: file.path,
+                    language: Language::Rust,
+                    source_type: SourceType::Dependency {
+                        package: file.krate,
+                        version: file.version,
+                    },
+                    package: None,
+                    metadata,
+                }
+            })
+            .collect(),
+    )
+}
+
 fn get_file_metadata(path: &PathBuf) -> FileMetadata {
     fs::metadata(path)
         .ok()
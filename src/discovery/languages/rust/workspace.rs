@@ -0,0 +1,153 @@
+//! Cargo workspaces: the member crates the `[workspace]` table of a root
+//! `Cargo.toml` lists in `members`, less those in `exclude`. Read without
+//! cargo, so a scan of an unbuilt checkout finds them too.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::discovery::utils::{glob_match, toml_string};
+
+use super::config::{EXCLUDED_DIRS, VENDOR_DIR};
+
+/// The directories of the workspace members declared at `root` that hold
+/// a `Cargo.toml`, sorted. Empty when `root` is not a workspace root.
+pub fn workspace_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(manifest) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let members = string_array(&manifest, "workspace", "members");
+    if members.is_empty() {
+        return Vec::new();
+    }
+    let excludes = string_array(&manifest, "workspace", "exclude");
+
+    let walker = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.file_type().is_dir()
+                && !name.starts_with('.')
+                && !EXCLUDED_DIRS.contains(&name.as_ref())
+                && name != VENDOR_DIR
+        });
+    walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?;
+            let matched = |pattern: &String| glob_match(pattern.trim_start_matches("./"), relative);
+            (members.iter().any(matched) && !excludes.iter().any(matched))
+                .then(|| entry.path().to_path_buf())
+        })
+        .collect()
+}
+
+/// The `[package]` name of the crate in `dir`.
+pub fn package_name(dir: &Path) -> Option<String> {
+    toml_string(
+        &fs::read_to_string(dir.join("Cargo.toml")).ok()?,
+        "package",
+        "name",
+    )
+}
+
+/// The strings of the array `key` in the TOML table `table`, which may
+/// span several lines: `members = ["crates/*", "xtask"]`.
+fn string_array(manifest: &str, table: &str, key: &str) -> Vec<String> {
+    let mut in_table = false;
+    let mut in_array = false;
+    let mut values = Vec::new();
+    for line in manifest.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if !in_array && line.starts_with('[') {
+            in_table = line.trim_matches(['[', ']']).trim() == table;
+            continue;
+        }
+        let rest = if in_array {
+            line
+        } else if in_table {
+            match line.split_once('=') {
+                Some((name, value)) if name.trim() == key => {
+                    in_array = true;
+                    value.trim().trim_start_matches('[')
+                }
+                _ => continue,
+            }
+        } else {
+            continue;
+        };
+        let (items, closed) = match rest.split_once(']') {
+            Some((items, _)) => (items, true),
+            None => (rest, false),
+        };
+        values.extend(
+            items
+                .split(',')
+                .map(|item| item.trim().trim_matches(['"', '\'']))
+                .filter(|item| !item.is_empty())
+                .map(str::to_string),
+        );
+        if closed {
+            in_array = false;
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_array() {
+        let manifest = "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\n    \"crates/*\", # libraries\n    'xtask',\n]\nexclude = [\"crates/legacy\"]\n";
+        assert_eq!(
+            string_array(manifest, "workspace", "members"),
+            vec!["crates/*", "xtask"]
+        );
+        assert_eq!(
+            string_array(manifest, "workspace", "exclude"),
+            vec!["crates/legacy"]
+        );
+        assert!(string_array(manifest, "package", "members").is_empty());
+    }
+
+    #[test]
+    fn test_workspace_members() {
+        let root = tempfile::TempDir::new().unwrap();
+        let root = root.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+        )
+        .unwrap();
+        for dir in [
+            "crates/core",
+            "crates/legacy",
+            "crates/cli",
+            "examples/demo",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("Cargo.toml"), "[package]\n").unwrap();
+        }
+
+        assert_eq!(
+            workspace_members(root),
+            vec![root.join("crates/cli"), root.join("crates/core")]
+        );
+        assert_eq!(package_name(&root.join("crates/cli")), None);
+        fs::write(
+            root.join("crates/cli/Cargo.toml"),
+            "[package]\nname = \"shop-cli\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            package_name(&root.join("crates/cli")).as_deref(),
+            Some("shop-cli")
+        );
+    }
+}
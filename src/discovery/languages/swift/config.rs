@@ -0,0 +1,15 @@
+pub const FILE_EXTENSIONS: &[&str] = &["swift"];
+
+pub const EXCLUDED_DIRS: &[&str] = &[
+    "testdata",
+    ".git",
+    ".build",
+    "Pods",
+    "Carthage",
+    "DerivedData",
+];
+
+/// Files that mark a Swift package or CocoaPods project root.
+pub const PROJECT_FILES: &[&str] = &["Package.swift", "Podfile"];
+
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use crate::cli::Language;
+use crate::discovery::filter::ImportFileFilter;
+use crate::discovery::languages::LanguageModule;
+use crate::discovery::loader::PackageLoader;
+
+pub mod config;
+pub mod filter;
+pub mod loader;
+
+pub use filter::SwiftImportFilter;
+pub use loader::SwiftPackageLoader;
+
+pub struct SwiftModule;
+
+impl LanguageModule for SwiftModule {
+    fn create_loader(&self) -> Box<dyn PackageLoader> {
+        Box::new(SwiftPackageLoader)
+    }
+
+    fn create_filter(&self) -> Box<dyn ImportFileFilter> {
+        Box::new(
+            SwiftImportFilter::from_bundled().expect("Failed to load bundled Swift import filter"),
+        )
+    }
+
+    fn language(&self) -> Language {
+        Language::Swift
+    }
+
+    fn detect(&self, root: &Path) -> bool {
+        config::PROJECT_FILES
+            .iter()
+            .any(|name| root.join(name).exists())
+    }
+}
@@ -13,6 +13,19 @@ pub trait PackageLoader: Send + Sync {
         cache: &mut DiscoveryCache,
     ) -> Result<Vec<SourceFile>, LoadError>;
 
+    /// The dependencies of the packages `select` accepts by name, without
+    /// walking the others. Stdlib is always loaded. Loaders that only learn
+    /// a file's package by walking it load every dependency.
+    fn load_selected_dependencies(
+        &self,
+        root: &Path,
+        cache: &mut DiscoveryCache,
+        select: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<SourceFile>, LoadError> {
+        let _ = select;
+        self.load_dependencies(root, cache)
+    }
+
     fn language(&self) -> Language;
 }
 
@@ -27,6 +40,9 @@ pub enum LoadError {
     #[error("Package manager error: {0}")]
     PackageManager(String),
 
+    #[error(transparent)]
+    Tool(#[from] crate::discovery::tooling::ToolError),
+
     #[error("Failed to scan directory at {path}: {source}")]
     DirectoryScanError {
         path: std::path::PathBuf,
@@ -67,6 +83,9 @@ mod tests {
         let root = PathBuf::from("/tmp");
         assert!(loader.load_user_code(&root).is_ok());
         assert!(loader.load_dependencies(&root, &mut cache).is_ok());
+        assert!(loader
+            .load_selected_dependencies(&root, &mut cache, &mut |_| false)
+            .is_ok());
     }
 
     #[test]
@@ -0,0 +1,471 @@
+//! Dependencies a project declares in its lockfiles and manifests, read
+//! without the package manager.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::cli::Language;
+
+/// Names of the packages declared for `language` under `root`: Go modules
+/// from `go.mod`, crates from `Cargo.lock` (or `Cargo.toml`), npm packages
+/// from `package.json`, Python distributions from `poetry.lock` or
+/// `requirements.txt`, Maven artifacts (`group:artifact`) from
+/// `pom.xml` or `build.gradle`, C and C++ libraries from `vcpkg.json` or
+/// `conanfile.txt`, NuGet packages from the `*.csproj` files, gems from
+/// `Gemfile.lock` (or `Gemfile`), Composer packages from
+/// `composer.lock` (or `composer.json`), and Swift packages from
+/// `Package.resolved`. Missing or unreadable files declare nothing.
+pub fn declared_dependencies(root: &Path, language: Language) -> Vec<String> {
+    let read = |name: &str| fs::read_to_string(root.join(name)).ok();
+    let names = match language {
+        Language::Go => read("go.mod").map(|s| go_mod(&s)),
+        Language::Rust => read("Cargo.lock")
+            .map(|s| lock_packages(&s))
+            .or_else(|| read("Cargo.toml").map(|s| cargo_toml(&s))),
+        Language::Javascript | Language::Typescript => {
+            read("package.json").map(|s| package_json(&s))
+        }
+        Language::Python => read("poetry.lock")
+            .map(|s| lock_packages(&s))
+            .or_else(|| read("requirements.txt").map(|s| requirements(&s))),
+        Language::Java => read("pom.xml").map(|s| pom_xml(&s)).or_else(|| {
+            read("build.gradle")
+                .or_else(|| read("build.gradle.kts"))
+                .map(|s| gradle(&s))
+        }),
+        Language::C | Language::Cpp => read("vcpkg.json")
+            .map(|s| vcpkg_json(&s))
+            .or_else(|| read("conanfile.txt").map(|s| conanfile(&s))),
+        Language::CSharp => Some(csproj_packages(root)),
+        Language::Ruby => read("Gemfile.lock")
+            .map(|s| gemfile_lock(&s))
+            .or_else(|| read("Gemfile").map(|s| gemfile(&s))),
+        Language::Php => read("composer.lock")
+            .map(|s| composer_lock(&s))
+            .or_else(|| read("composer.json").map(|s| composer_json(&s))),
+        Language::Swift => read("Package.resolved").map(|s| package_resolved(&s)),
+    };
+    names.unwrap_or_default().into_iter().collect()
+}
+
+fn go_mod(content: &str) -> BTreeSet<String> {
+    go_requirements(content)
+        .into_iter()
+        .map(|(module, _)| module)
+        .collect()
+}
+
+/// The module path and version of every `require` in a `go.mod`.
+pub fn go_requirements(content: &str) -> Vec<(String, String)> {
+    let mut requirements = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let spec = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(spec) = line.strip_prefix("require ") {
+            spec
+        } else {
+            continue;
+        };
+        let mut fields = spec.split_whitespace();
+        if let (Some(module), Some(version)) = (fields.next(), fields.next()) {
+            requirements.push((module.to_string(), version.to_string()));
+        }
+    }
+    requirements
+}
+
+/// The `name` of every `[[package]]` in a Cargo or Poetry lockfile.
+fn lock_packages(content: &str) -> BTreeSet<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("name = "))
+        .map(|name| name.trim_matches('"').to_string())
+        .collect()
+}
+
+fn cargo_toml(content: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut in_dependencies = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_dependencies = line.trim_matches(['[', ']']).ends_with("dependencies");
+            continue;
+        }
+        if in_dependencies {
+            if let Some((name, _)) = line.split_once('=') {
+                let name = name.trim();
+                if !name.is_empty() && !name.starts_with('#') {
+                    names.insert(name.to_string());
+                }
+            }
+        }
+    }
+    names
+}
+
+fn package_json(content: &str) -> BTreeSet<String> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return BTreeSet::new();
+    };
+    ["dependencies", "devDependencies", "optionalDependencies"]
+        .iter()
+        .filter_map(|section| manifest.get(section)?.as_object())
+        .flat_map(|deps| deps.keys().cloned())
+        .collect()
+}
+
+/// The `name` of every installed package in a `composer.lock`.
+fn composer_lock(content: &str) -> BTreeSet<String> {
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+        return BTreeSet::new();
+    };
+    ["packages", "packages-dev"]
+        .iter()
+        .filter_map(|section| lock.get(section)?.as_array())
+        .flatten()
+        .filter_map(|package| package.get("name")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The packages a `composer.json` requires, without the PHP version and
+/// extension (`ext-sodium`) requirements.
+fn composer_json(content: &str) -> BTreeSet<String> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return BTreeSet::new();
+    };
+    ["require", "require-dev"]
+        .iter()
+        .filter_map(|section| manifest.get(section)?.as_object())
+        .flat_map(|deps| deps.keys())
+        .filter(|name| name.contains('/'))
+        .cloned()
+        .collect()
+}
+
+/// The pinned packages of a `Package.resolved`: their `identity`
+/// (`swift-crypto`), or their `package` name in the version 1 format.
+fn package_resolved(content: &str) -> BTreeSet<String> {
+    let Ok(resolved) = serde_json::from_str::<serde_json::Value>(content) else {
+        return BTreeSet::new();
+    };
+    let pins = resolved
+        .get("pins")
+        .or_else(|| resolved.get("object")?.get("pins"))
+        .and_then(|pins| pins.as_array());
+    pins.into_iter()
+        .flatten()
+        .filter_map(|pin| pin.get("identity").or_else(|| pin.get("package"))?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+fn requirements(content: &str) -> BTreeSet<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(|line| {
+            let end = line
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(line.len());
+            (end > 0).then(|| line[..end].to_string())
+        })
+        .collect()
+}
+
+/// `groupId:artifactId` of every `<dependency>` in a `pom.xml`.
+fn pom_xml(content: &str) -> BTreeSet<String> {
+    let element = |block: &str, name: &str| {
+        let start = block.find(&format!("<{name}>"))? + name.len() + 2;
+        let end = block[start..].find(&format!("</{name}>"))? + start;
+        Some(block[start..end].trim().to_string())
+    };
+    content
+        .split("<dependency>")
+        .skip(1)
+        .filter_map(|block| {
+            let block = block.split("</dependency>").next()?;
+            Some(format!(
+                "{}:{}",
+                element(block, "groupId")?,
+                element(block, "artifactId")?
+            ))
+        })
+        .collect()
+}
+
+/// `group:artifact` of every string dependency notation in a Gradle build
+/// script, e.g. `implementation 'org.bouncycastle:bcprov-jdk18on:1.78'`.
+fn gradle(content: &str) -> BTreeSet<String> {
+    content
+        .split(['\'', '"'])
+        .skip(1)
+        .step_by(2)
+        .filter_map(|notation| {
+            let mut parts = notation.split(':');
+            let group = parts.next()?;
+            let artifact = parts.next()?;
+            let is_name = |s: &str| {
+                !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            };
+            (is_name(group) && is_name(artifact)).then(|| format!("{group}:{artifact}"))
+        })
+        .collect()
+}
+
+/// The name of every entry of `dependencies` in a `vcpkg.json`, given as
+/// a string or as an object with a `name`.
+fn vcpkg_json(content: &str) -> BTreeSet<String> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return BTreeSet::new();
+    };
+    manifest
+        .get("dependencies")
+        .and_then(|deps| deps.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| dep.as_str().or_else(|| dep.get("name")?.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The package of every reference under `[requires]` in a
+/// `conanfile.txt`, e.g. `openssl` for `openssl/3.2.1`.
+fn conanfile(content: &str) -> BTreeSet<String> {
+    let mut in_requires = false;
+    let mut names = BTreeSet::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            in_requires = line == "[requires]";
+        } else if in_requires && !line.is_empty() {
+            names.insert(line.split('/').next().unwrap_or(line).to_string());
+        }
+    }
+    names
+}
+
+/// The `Include` of every `<PackageReference>` in the project files
+/// directly under `root`.
+fn csproj_packages(root: &Path) -> BTreeSet<String> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return BTreeSet::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csproj"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .split("<PackageReference")
+                .skip(1)
+                .filter_map(|element| {
+                    let start = element.find("Include=\"")? + "Include=\"".len();
+                    let end = element[start..].find('"')? + start;
+                    Some(element[start..end].to_string())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The gems a `Gemfile.lock` resolved: the `name (version)` entries under
+/// each `specs:`, leaving out their own dependencies, which are indented
+/// further.
+fn gemfile_lock(content: &str) -> BTreeSet<String> {
+    content
+        .lines()
+        .filter_map(|line| line.strip_prefix("    "))
+        .filter(|spec| !spec.starts_with(' '))
+        .filter_map(|spec| spec.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The name of every `gem "name"` in a `Gemfile`.
+fn gemfile(content: &str) -> BTreeSet<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("gem "))
+        .filter_map(|args| {
+            let quote = args.trim_start().chars().next()?;
+            let name = args.trim_start().strip_prefix(['"', '\''])?;
+            Some(name[..name.find(quote)?].to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_dependencies() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("go.mod"),
+            "module example.com/app\n\nrequire golang.org/x/crypto v0.21.0\nrequire (\n\tgithub.com/golang-jwt/jwt/v5 v5.2.0 // indirect\n)\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("requirements.txt"),
+            "# crypto\ncryptography>=42.0\nPyNaCl==1.5.0 ; python_version > '3.8'\n-r dev.txt\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{"dependencies": {"jose": "^5.0.0"}, "devDependencies": {"@noble/hashes": "1.3.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nring = \"0.17\"\nsha2 = { version = \"0.10\" }\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            go_requirements(&fs::read_to_string(root.join("go.mod")).unwrap())[0],
+            ("golang.org/x/crypto".to_string(), "v0.21.0".to_string())
+        );
+        assert_eq!(
+            declared_dependencies(root, Language::Go),
+            vec!["github.com/golang-jwt/jwt/v5", "golang.org/x/crypto"]
+        );
+        assert_eq!(
+            declared_dependencies(root, Language::Python),
+            vec!["PyNaCl", "cryptography"]
+        );
+        assert_eq!(
+            declared_dependencies(root, Language::Javascript),
+            vec!["@noble/hashes", "jose"]
+        );
+        assert_eq!(
+            declared_dependencies(root, Language::Rust),
+            vec!["ring", "sha2"]
+        );
+
+        fs::write(
+            root.join("build.gradle"),
+            "dependencies {\n    implementation 'org.bouncycastle:bcprov-jdk18on:1.78'\n    testImplementation \"junit:junit:4.13.2\"\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Java),
+            vec!["junit:junit", "org.bouncycastle:bcprov-jdk18on"]
+        );
+        fs::write(
+            root.join("pom.xml"),
+            "<project><dependencies><dependency>\n<groupId>com.google.crypto.tink</groupId>\n<artifactId>tink</artifactId>\n</dependency></dependencies></project>",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Java),
+            vec!["com.google.crypto.tink:tink"]
+        );
+
+        fs::write(
+            root.join("conanfile.txt"),
+            "[requires]\nopenssl/3.2.1\nzlib/1.3 # compression\n\n[generators]\nCMakeDeps\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::C),
+            vec!["openssl", "zlib"]
+        );
+        fs::write(
+            root.join("vcpkg.json"),
+            r#"{"name": "app", "dependencies": ["openssl", {"name": "libsodium"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::C),
+            vec!["libsodium", "openssl"]
+        );
+
+        fs::write(
+            root.join("App.csproj"),
+            "<Project Sdk=\"Microsoft.NET.Sdk\">\n  <ItemGroup>\n    <PackageReference Include=\"BouncyCastle.Cryptography\" Version=\"2.3.0\" />\n    <PackageReference Include=\"Microsoft.IdentityModel.Tokens\" Version=\"7.5.0\" />\n  </ItemGroup>\n</Project>\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::CSharp),
+            vec![
+                "BouncyCastle.Cryptography",
+                "Microsoft.IdentityModel.Tokens"
+            ]
+        );
+
+        fs::write(
+            root.join("Gemfile"),
+            "source \"https://rubygems.org\"\ngem \"rbnacl\", \"~> 7.1\"\ngem 'jwt'\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Ruby),
+            vec!["jwt", "rbnacl"]
+        );
+        fs::write(
+            root.join("Gemfile.lock"),
+            "GEM\n  remote: https://rubygems.org/\n  specs:\n    ffi (1.16.3)\n    rbnacl (7.1.1)\n      ffi\n\nPLATFORMS\n  ruby\n\nDEPENDENCIES\n  rbnacl (~> 7.1)\n\nBUNDLED WITH\n   2.5.6\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Ruby),
+            vec!["ffi", "rbnacl"]
+        );
+
+        fs::write(
+            root.join("composer.json"),
+            r#"{"require": {"php": ">=8.1", "ext-sodium": "*", "paragonie/halite": "^5.1"}, "require-dev": {"phpunit/phpunit": "^10"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Php),
+            vec!["paragonie/halite", "phpunit/phpunit"]
+        );
+        fs::write(
+            root.join("composer.lock"),
+            r#"{"packages": [{"name": "paragonie/halite", "version": "v5.1.0"}, {"name": "paragonie/hidden-string", "version": "v2.0.0"}], "packages-dev": []}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Php),
+            vec!["paragonie/halite", "paragonie/hidden-string"]
+        );
+
+        fs::write(
+            root.join("Package.resolved"),
+            r#"{"pins": [{"identity": "swift-crypto", "kind": "remoteSourceControl", "state": {"version": "3.3.0"}}], "version": 2}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Swift),
+            vec!["swift-crypto"]
+        );
+        fs::write(
+            root.join("Package.resolved"),
+            r#"{"object": {"pins": [{"package": "CryptoSwift", "state": {"version": "1.8.1"}}]}, "version": 1}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            declared_dependencies(root, Language::Swift),
+            vec!["CryptoSwift"]
+        );
+    }
+}
@@ -1,9 +1,20 @@
+pub mod archive;
+pub mod budget;
 pub mod cache;
+pub mod components;
 pub mod detector;
+pub mod dry_run;
+pub mod extensions;
 pub mod filter;
+pub mod git;
+pub mod image;
 pub mod languages;
 pub mod loader;
+pub mod manifest;
+pub mod roots;
+pub mod tooling;
 pub mod utils;
+pub mod workspace;
 
 pub use cache::DiscoveryCache;
 pub use detector::LanguageDetector;
@@ -12,6 +23,7 @@ pub use languages::{GoImportFilter, GoPackageLoader, LanguageModule, LanguageReg
 pub use loader::PackageLoader;
 pub use utils::walk_source_files;
 
+use std::fmt;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
@@ -36,6 +48,20 @@ pub enum SourceType {
     Stdlib,
 }
 
+impl fmt::Display for SourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UserCode => write!(f, "user"),
+            Self::Dependency {
+                package,
+                version: Some(version),
+            } => write!(f, "dependency {package}@{version}"),
+            Self::Dependency { package, .. } => write!(f, "dependency {package}"),
+            Self::Stdlib => write!(f, "stdlib"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FileMetadata {
     pub size: u64,
@@ -0,0 +1,342 @@
+//! The roots a scan is split into: each scanned path in each language
+//! found there, with the projects nested in it scanned as roots of their
+//! own.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use super::components::find_components;
+use super::workspace::Workspace;
+use super::LanguageRegistry;
+use crate::cli::{detect_language, Language};
+use crate::engine::Language as EngineLanguage;
+use crate::error::ParserError;
+use crate::grammars;
+use crate::output::{merge_reports, JsonOutput, RootSummary};
+
+#[derive(Error, Debug)]
+pub enum RootError {
+    #[error("Could not detect language of {}. Please specify --language", .0.display())]
+    Undetected(PathBuf),
+
+    #[error("Found several languages in {} ({}). Please specify --language", path.display(), names(languages))]
+    Ambiguous {
+        path: PathBuf,
+        languages: Vec<Language>,
+    },
+
+    #[error("No grammar for {}", .0.as_str())]
+    NoGrammar(Language),
+
+    #[error(transparent)]
+    Grammar(#[from] ParserError),
+}
+
+fn names(languages: &[Language]) -> String {
+    languages
+        .iter()
+        .map(|l| l.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A path scanned in one language, less the `nested` projects inside it
+/// that are scanned on their own.
+#[derive(Debug, Clone)]
+pub struct ScanRoot {
+    pub path: PathBuf,
+    pub language: Language,
+    pub nested: Vec<PathBuf>,
+    /// The workspace members under the scanned path, to attribute findings
+    /// to.
+    pub workspace: Workspace,
+}
+
+/// `language`, or else the one language detected under `root`.
+pub fn project_language(
+    registry: &LanguageRegistry,
+    root: &Path,
+    language: Option<Language>,
+) -> Result<Language, RootError> {
+    if let Some(language) = language {
+        return Ok(language);
+    }
+    match registry.detect_languages(root).as_slice() {
+        [language] => Ok(*language),
+        [] => Err(RootError::Undetected(root.to_path_buf())),
+        detected => Err(RootError::Ambiguous {
+            path: root.to_path_buf(),
+            languages: detected.to_vec(),
+        }),
+    }
+}
+
+/// The language of `path`: `language` if given, else detected from the
+/// file extension or the project's manifest files.
+fn root_language(
+    registry: &LanguageRegistry,
+    path: &Path,
+    language: Option<Language>,
+) -> Result<Language, RootError> {
+    if path.is_file() {
+        return language
+            .or_else(|| detect_language(path))
+            .ok_or_else(|| RootError::Undetected(path.to_path_buf()));
+    }
+    project_language(registry, path, language)
+}
+
+/// `language`, or else every language detected in the directory `root`,
+/// each scanned as a root of its own: a monorepo holding a `go.mod`, a
+/// `requirements.txt` and a `package.json` side by side is scanned in all
+/// three.
+fn root_languages(
+    registry: &LanguageRegistry,
+    root: &Path,
+    language: Option<Language>,
+) -> Result<Vec<Language>, RootError> {
+    if let Some(language) = language {
+        return Ok(vec![language]);
+    }
+    let detected = registry.detect_languages(root);
+    if detected.is_empty() {
+        return Err(RootError::Undetected(root.to_path_buf()));
+    }
+    if detected.len() > 1 {
+        info!(
+            path = %root.display(),
+            languages = %names(&detected),
+            "found several languages, scanning each"
+        );
+    }
+    Ok(detected)
+}
+
+/// The roots to scan for `path`: a file, or a directory split into the
+/// projects nested in it when `split` is set.
+pub fn scan_roots(
+    registry: &LanguageRegistry,
+    path: &Path,
+    language: Option<Language>,
+    split: bool,
+) -> Result<Vec<ScanRoot>, RootError> {
+    let workspace = match path.is_dir() {
+        true => Workspace::discover(path),
+        false => Workspace::default(),
+    };
+    if !workspace.is_empty() {
+        info!(
+            path = %path.display(),
+            members = workspace.members().len(),
+            "found workspace members, attributing findings to each"
+        );
+    }
+    let whole = |language| ScanRoot {
+        path: path.to_path_buf(),
+        language,
+        nested: Vec::new(),
+        workspace: workspace.clone(),
+    };
+    if path.is_file() || !split {
+        return Ok(vec![whole(root_language(registry, path, language)?)]);
+    }
+
+    let components = find_components(path, registry);
+    if components.len() == 1 {
+        return Ok(root_languages(registry, path, language)?
+            .into_iter()
+            .map(whole)
+            .collect());
+    }
+    info!(
+        path = %path.display(),
+        nested = components.len() - 1,
+        "found nested projects, scanning each as its own component"
+    );
+
+    let mut pairs = Vec::new();
+    for component in &components {
+        let languages = if component.root == path {
+            match language {
+                Some(language) => vec![language],
+                // A directory holding only nested projects is not one itself.
+                None if component.languages.is_empty() => Vec::new(),
+                None => root_languages(registry, path, None)?,
+            }
+        } else {
+            match language {
+                Some(language) => component
+                    .languages
+                    .iter()
+                    .copied()
+                    .filter(|&l| l == language)
+                    .collect(),
+                None => component.languages.clone(),
+            }
+        };
+        pairs.extend(languages.into_iter().map(|l| (component.root.clone(), l)));
+    }
+
+    // Files under a nested project scanned in the same language are its own.
+    Ok(pairs
+        .iter()
+        .map(|(root, language)| ScanRoot {
+            path: root.clone(),
+            language: *language,
+            nested: pairs
+                .iter()
+                .filter(|(other, l)| l == language && other != root && other.starts_with(root))
+                .map(|(other, _)| other.clone())
+                .collect(),
+            workspace: workspace.clone(),
+        })
+        .collect())
+}
+
+/// Whether the grammar of `language` is compiled into this build.
+fn compiled(language: Language) -> Result<(), RootError> {
+    let engine_language =
+        EngineLanguage::parse(language.as_str()).ok_or(RootError::NoGrammar(language))?;
+    grammars::require(engine_language)?;
+    Ok(())
+}
+
+/// The roots whose language is compiled into this build. A language asked
+/// for with `--language`, or the only one found, not being compiled in is
+/// an error; other roots of missing languages are skipped with a warning.
+pub fn compiled_roots(
+    roots: Vec<ScanRoot>,
+    requested: Option<Language>,
+) -> Result<Vec<ScanRoot>, RootError> {
+    if let Some(language) = requested {
+        compiled(language)?;
+    }
+    let (compiled_roots, missing): (Vec<_>, Vec<_>) = roots
+        .into_iter()
+        .partition(|root| compiled(root.language).is_ok());
+    if compiled_roots.is_empty() {
+        if let Some(root) = missing.first() {
+            compiled(root.language)?;
+        }
+    }
+    for root in &missing {
+        if let Err(e) = compiled(root.language) {
+            warn!(path = %root.path.display(), "skipping: {e}");
+        }
+    }
+    Ok(compiled_roots)
+}
+
+/// The reports of `roots`, one each in the same order, merged into one
+/// that sums up each root.
+pub fn merge_root_reports(roots: &[ScanRoot], reports: Vec<JsonOutput>) -> JsonOutput {
+    let summaries = roots
+        .iter()
+        .zip(&reports)
+        .map(|(root, report)| {
+            RootSummary::new(&root.path.to_string_lossy(), root.language.as_str(), report)
+        })
+        .collect();
+    let mut combined = merge_reports(reports);
+    combined.roots = summaries;
+    combined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn languages(roots: &[ScanRoot]) -> Vec<(PathBuf, Language)> {
+        roots.iter().map(|r| (r.path.clone(), r.language)).collect()
+    }
+
+    #[test]
+    fn test_side_by_side_manifests_give_a_root_each() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("go.mod"), "module example.com/app\n").unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let roots = scan_roots(&LanguageRegistry::new(), dir.path(), None, true).unwrap();
+        let found = languages(&roots);
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&(dir.path().to_path_buf(), Language::Go)));
+        assert!(found.contains(&(dir.path().to_path_buf(), Language::Javascript)));
+    }
+
+    #[test]
+    fn test_nested_project_is_left_out_of_the_one_around_it() {
+        let dir = TempDir::new().unwrap();
+        let web = dir.path().join("web");
+        fs::create_dir(&web).unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        fs::write(web.join("package.json"), "{}").unwrap();
+        let roots = scan_roots(&LanguageRegistry::new(), dir.path(), None, true).unwrap();
+        assert_eq!(roots.len(), 2);
+        let outer = roots.iter().find(|r| r.path == dir.path()).unwrap();
+        assert_eq!(outer.nested, vec![web.clone()]);
+        let inner = roots.iter().find(|r| r.path == web).unwrap();
+        assert!(inner.nested.is_empty());
+    }
+
+    #[test]
+    fn test_unsplit_directory_needs_one_language() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("go.mod"), "module example.com/app\n").unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let registry = LanguageRegistry::new();
+        assert!(matches!(
+            scan_roots(&registry, dir.path(), None, false),
+            Err(RootError::Ambiguous { .. })
+        ));
+        let roots = scan_roots(&registry, dir.path(), Some(Language::Go), false).unwrap();
+        assert_eq!(
+            languages(&roots),
+            vec![(dir.path().to_path_buf(), Language::Go)]
+        );
+    }
+
+    #[test]
+    fn test_merge_root_reports_sums_up_each_root() {
+        use crate::test_support::{finding, report};
+
+        let root = |path: &str, language| ScanRoot {
+            path: PathBuf::from(path),
+            language,
+            nested: Vec::new(),
+            workspace: Workspace::default(),
+        };
+        let roots = [root("api", Language::Go), root("web", Language::Javascript)];
+        let reports = vec![
+            report(
+                vec![finding("api/keys.go", 3, "crypto/rsa.GenerateKey")],
+                Vec::new(),
+            ),
+            report(
+                vec![
+                    finding("web/a.js", 1, "crypto.createHash"),
+                    finding("web/b.js", 2, "crypto.createHash"),
+                ],
+                Vec::new(),
+            ),
+        ];
+        let combined = merge_root_reports(&roots, reports);
+        assert_eq!(combined.total_findings, 3);
+        let totals: Vec<_> = combined
+            .roots
+            .iter()
+            .map(|r| (r.path.as_str(), r.language.as_str(), r.total_findings))
+            .collect();
+        assert_eq!(totals, vec![("api", "go", 1), ("web", "javascript", 2)]);
+    }
+
+    #[test]
+    fn test_undetected_directory_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let err = scan_roots(&LanguageRegistry::new(), dir.path(), None, true).unwrap_err();
+        assert!(matches!(err, RootError::Undetected(_)));
+        assert!(err.to_string().contains("Please specify --language"));
+    }
+}
@@ -0,0 +1,300 @@
+//! Running package-manager commands (`go list`, `cargo metadata`, `pip
+//! list`) for dependency discovery.
+//!
+//! Every command gets a timeout, no stdin and a trimmed environment, so a
+//! hanging proxy or a credential prompt cannot stall a scan. Callers fall
+//! back to reading the filesystem when a command fails; a missing
+//! toolchain is expected in minimal CI containers.
+
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+/// How long a command may run by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(crate::cli::DEFAULT_TOOL_TIMEOUT_SECS);
+
+static TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT.as_secs());
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Variables passed through to commands as they are: the search path, the
+/// home and temp dirs, proxies, the SSH agent for `--git-url`, and the
+/// toolchains' own settings.
+const KEPT_VARIABLES: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "SYSTEMROOT",
+    "LOCALAPPDATA",
+    "APPDATA",
+    "VIRTUAL_ENV",
+    "CONDA_PREFIX",
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "SSH_AUTH_SOCK",
+    "GIT_SSH_COMMAND",
+];
+const KEPT_PREFIXES: &[&str] = &["CARGO_", "RUSTUP_", "PIP_", "POETRY_", "UV_"];
+/// Suffixes of registry credentials, which listing commands never need.
+const SECRET_SUFFIXES: &[&str] = &["_TOKEN", "_PASSWORD"];
+
+/// Set for every command: plain, untranslated messages, and no prompts
+/// or toolchain downloads.
+const FIXED_VARIABLES: &[(&str, &str)] = &[
+    ("LC_ALL", "C"),
+    ("NO_COLOR", "1"),
+    ("GIT_TERMINAL_PROMPT", "0"),
+    ("GOTOOLCHAIN", "local"),
+    ("PIP_NO_INPUT", "1"),
+    ("PIP_DISABLE_PIP_VERSION_CHECK", "1"),
+];
+
+/// Set with `--offline`, so package managers only read what is already
+/// installed and fail instead of downloading.
+const OFFLINE_VARIABLES: &[(&str, &str)] = &[
+    ("GOPROXY", "off"),
+    ("GOSUMDB", "off"),
+    ("CARGO_NET_OFFLINE", "true"),
+    ("PIP_NO_INDEX", "1"),
+    ("UV_OFFLINE", "1"),
+];
+
+/// Lines of stderr kept in a [`ToolError::Failed`].
+const STDERR_LINES: usize = 20;
+
+#[derive(Error, Debug)]
+pub enum ToolError {
+    #[error("{program} is not installed or not on PATH")]
+    NotFound { program: String },
+
+    #[error("failed to run {command}: {source}")]
+    Spawn { command: String, source: io::Error },
+
+    #[error("{command} timed out after {}s", timeout.as_secs())]
+    TimedOut { command: String, timeout: Duration },
+
+    #[error("{command} exited with {}: {stderr}", code.map_or("a signal".to_string(), |c| format!("status {c}")))]
+    Failed {
+        command: String,
+        code: Option<i32>,
+        stderr: String,
+    },
+}
+
+impl ToolError {
+    /// Whether the toolchain is absent, rather than failing on this project.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound { .. })
+    }
+}
+
+/// Sets the timeout for every later command, from `--tool-timeout`.
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT_SECS.store(timeout.as_secs().max(1), Ordering::Relaxed);
+}
+
+pub fn timeout() -> Duration {
+    Duration::from_secs(TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+/// Keeps every later command off the network, from `--offline`.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Runs `program` with `args` in `dir` and returns its stdout, or why it
+/// produced none.
+pub fn run(program: &str, args: &[&str], dir: Option<&Path>) -> Result<Vec<u8>, ToolError> {
+    let command_line = std::iter::once(program)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .env_clear()
+        .envs(sanitized_env(env::vars(), is_offline()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let mut child = command.spawn().map_err(|source| {
+        if source.kind() == io::ErrorKind::NotFound {
+            ToolError::NotFound {
+                program: program.to_string(),
+            }
+        } else {
+            ToolError::Spawn {
+                command: command_line.clone(),
+                source,
+            }
+        }
+    })?;
+
+    // Drained while waiting, so a large listing cannot fill the pipe.
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let timeout = timeout();
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(source) => {
+                let _ = child.kill();
+                return Err(ToolError::Spawn {
+                    command: command_line,
+                    source,
+                });
+            }
+        }
+        if started.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ToolError::TimedOut {
+                command: command_line,
+                timeout,
+            });
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = stderr.join().unwrap_or_default();
+        return Err(ToolError::Failed {
+            command: command_line,
+            code: status.code(),
+            stderr: summarize(&String::from_utf8_lossy(&stderr)),
+        });
+    }
+    Ok(stdout)
+}
+
+/// The variables of `vars` a command gets, plus [`FIXED_VARIABLES`] and,
+/// when `offline`, [`OFFLINE_VARIABLES`].
+fn sanitized_env(
+    vars: impl Iterator<Item = (String, String)>,
+    offline: bool,
+) -> HashMap<String, String> {
+    let mut kept: HashMap<String, String> = vars
+        .filter(|(name, _)| {
+            KEPT_VARIABLES.contains(&name.as_str())
+                || is_go_variable(name)
+                || (KEPT_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+                    && !SECRET_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+        })
+        .collect();
+    let fixed = FIXED_VARIABLES
+        .iter()
+        .chain(OFFLINE_VARIABLES.iter().filter(|_| offline));
+    for (name, value) in fixed {
+        kept.insert(name.to_string(), value.to_string());
+    }
+    kept
+}
+
+/// `GOPATH`, `GOFLAGS`, `GOPROXY` and the like; Go's own variables have
+/// no underscore, unlike `GOOGLE_APPLICATION_CREDENTIALS`.
+fn is_go_variable(name: &str) -> bool {
+    name.starts_with("GO") && !name.contains('_')
+}
+
+/// The last [`STDERR_LINES`] non-empty lines of `stderr`.
+fn summarize(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let start = lines.len().saturating_sub(STDERR_LINES);
+    lines[start..].join("\n")
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut out = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut out);
+        }
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_tool() {
+        let err = run("argflow-no-such-tool", &["--version"], None).unwrap_err();
+        assert!(err.is_not_found(), "{err}");
+    }
+
+    #[test]
+    fn test_sanitized_env() {
+        let vars = [
+            ("PATH", "/usr/bin"),
+            ("GOPATH", "/go"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("GITHUB_TOKEN", "token"),
+            ("GOOGLE_APPLICATION_CREDENTIALS", "/key.json"),
+            ("CARGO_HOME", "/cargo"),
+            ("CARGO_REGISTRY_TOKEN", "token"),
+            ("LC_ALL", "de_DE.UTF-8"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+        let env = sanitized_env(vars.clone(), false);
+        assert_eq!(env.get("PATH").map(String::as_str), Some("/usr/bin"));
+        assert_eq!(env.get("GOPATH").map(String::as_str), Some("/go"));
+        assert_eq!(env.get("LC_ALL").map(String::as_str), Some("C"));
+        assert!(!env.contains_key("AWS_SECRET_ACCESS_KEY"));
+        assert!(!env.contains_key("GITHUB_TOKEN"));
+        assert!(!env.contains_key("GOOGLE_APPLICATION_CREDENTIALS"));
+        assert!(env.contains_key("CARGO_HOME"));
+        assert!(!env.contains_key("CARGO_REGISTRY_TOKEN"));
+        assert!(!env.contains_key("GOPROXY"));
+
+        let env = sanitized_env(vars, true);
+        assert_eq!(env.get("GOPROXY").map(String::as_str), Some("off"));
+        assert_eq!(
+            env.get("CARGO_NET_OFFLINE").map(String::as_str),
+            Some("true")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failure_keeps_stderr() {
+        let err = run("sh", &["-c", "echo one >&2; echo two >&2; exit 3"], None).unwrap_err();
+        match err {
+            ToolError::Failed { code, stderr, .. } => {
+                assert_eq!(code, Some(3));
+                assert_eq!(stderr, "one\ntwo");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use walkdir::WalkDir;
 
 use crate::cli::Language;
@@ -101,9 +101,86 @@ pub fn load_stdlib_from_mappings(
     Ok(packages)
 }
 
+/// Matches a relative path against a workspace member glob (npm
+/// `workspaces`, Cargo `members`): `*` and `?` within a segment, `**` for
+/// any number of segments.
+pub fn glob_match(pattern: &str, path: &Path) -> bool {
+    let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    match_segments(&segments, &path)
+}
+
+fn match_segments(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            match_segment(segment.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+        }),
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+/// The string `key` of the TOML table `table`, e.g. the `name` of
+/// `[package]` in a `Cargo.toml`.
+pub fn toml_string(manifest: &str, table: &str, key: &str) -> Option<String> {
+    let mut in_table = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_table = line.trim_matches(['[', ']']).trim() == table;
+            continue;
+        }
+        if !in_table {
+            continue;
+        }
+        if let Some((name, value)) = line.split_once('=') {
+            if name.trim() == key {
+                let value = value.trim();
+                let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+                let value = &value[1..];
+                return value.find(quote).map(|end| value[..end].to_string());
+            }
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_toml_string() {
+        let manifest = "[package]\nname = \"keys\" # the crate\nversion = '0.1.0'\n\n[project]\nname = \"other\"\n";
+        assert_eq!(
+            toml_string(manifest, "package", "name").as_deref(),
+            Some("keys")
+        );
+        assert_eq!(
+            toml_string(manifest, "package", "version").as_deref(),
+            Some("0.1.0")
+        );
+        assert_eq!(
+            toml_string(manifest, "project", "name").as_deref(),
+            Some("other")
+        );
+        assert_eq!(toml_string(manifest, "workspace", "name"), None);
+    }
     use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
@@ -223,4 +300,14 @@ mod tests {
         assert_eq!(py_files.len(), 1);
         assert_eq!(c_files.len(), 1);
     }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("packages/*", Path::new("packages/api")));
+        assert!(!glob_match("packages/*", Path::new("packages/api/src")));
+        assert!(glob_match("apps/**", Path::new("apps/web/admin")));
+        assert!(glob_match("tools/cli", Path::new("tools/cli")));
+        assert!(glob_match("libs/ui-*", Path::new("libs/ui-kit")));
+        assert!(!glob_match("libs/ui-*", Path::new("libs/core")));
+    }
 }
@@ -0,0 +1,250 @@
+//! The member packages of the workspaces and monorepos in a scanned tree:
+//! Go workspaces (`go.work`), Cargo workspaces, npm, yarn and pnpm
+//! workspaces, and Python monorepos with a project per directory. Findings
+//! in a member's files are attributed to it by name in the report.
+
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::cli::Language;
+
+use super::components::is_skipped;
+use super::languages::{go, javascript, python, rust};
+
+/// A package of a workspace, and the directory its files are under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The package's own name: its Go module path, crate or npm package
+    /// name, or Python distribution name.
+    pub name: String,
+    pub root: PathBuf,
+    pub language: Language,
+}
+
+/// Every workspace member found under a directory.
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    members: Vec<WorkspaceMember>,
+}
+
+impl Workspace {
+    /// The members of every workspace at or under `root`. The package at a
+    /// workspace's root, if it is one, is a member too. A tree without a
+    /// workspace, or with a single Python project, has none.
+    pub fn discover(root: &Path) -> Self {
+        let mut workspace = Self::default();
+        let mut python_projects = Vec::new();
+        let walker = WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.file_type().is_dir() && (entry.depth() == 0 || !is_skipped(entry.file_name()))
+            });
+        for entry in walker.filter_map(|entry| entry.ok()) {
+            let dir = entry.path();
+            workspace.add_members(
+                dir,
+                Language::Go,
+                go::workspace::workspace_members(dir),
+                go::workspace::module_path,
+            );
+            workspace.add_members(
+                dir,
+                Language::Rust,
+                rust::workspace::workspace_members(dir),
+                rust::workspace::package_name,
+            );
+            workspace.add_members(
+                dir,
+                Language::Javascript,
+                javascript::workspace::workspace_members(dir),
+                javascript::workspace::package_name,
+            );
+            if python::workspace::is_project(dir) {
+                python_projects.push(dir.to_path_buf());
+            }
+        }
+        if python_projects.len() > 1 {
+            for dir in &python_projects {
+                workspace.add(dir, Language::Python, python::workspace::project_name);
+            }
+        }
+        workspace
+    }
+
+    /// `members` of the workspace at `dir`, and the package at `dir` when
+    /// there are any.
+    fn add_members(
+        &mut self,
+        dir: &Path,
+        language: Language,
+        members: Vec<PathBuf>,
+        name: fn(&Path) -> Option<String>,
+    ) {
+        if members.is_empty() {
+            return;
+        }
+        self.add(dir, language, name);
+        for member in &members {
+            self.add(member, language, name);
+        }
+    }
+
+    fn add(&mut self, dir: &Path, language: Language, name: fn(&Path) -> Option<String>) {
+        if self
+            .members
+            .iter()
+            .any(|member| member.root == dir && member.language == language)
+        {
+            return;
+        }
+        if let Some(name) = name(dir) {
+            self.members.push(WorkspaceMember {
+                name,
+                root: dir.to_path_buf(),
+                language,
+            });
+        }
+    }
+
+    pub fn members(&self) -> &[WorkspaceMember] {
+        &self.members
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The member `file` belongs to: the one with the deepest root above
+    /// it, preferring a member of `language` among those at the same root.
+    pub fn member_of(&self, file: &Path, language: Language) -> Option<&WorkspaceMember> {
+        self.members
+            .iter()
+            .filter(|member| file.starts_with(&member.root))
+            .max_by_key(|member| {
+                (
+                    member.root.components().count(),
+                    member.language == language,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn names(workspace: &Workspace) -> Vec<(&str, Language)> {
+        workspace
+            .members()
+            .iter()
+            .map(|member| (member.name.as_str(), member.language))
+            .collect()
+    }
+
+    #[test]
+    fn test_discover_members() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "go.work",
+            "go 1.22\n\nuse (\n\t./api\n\t./worker\n)\n",
+        );
+        write(root, "api/go.mod", "module example.com/shop/api\n");
+        write(root, "worker/go.mod", "module example.com/shop/worker\n");
+        write(
+            root,
+            "web/package.json",
+            r#"{"name": "shop-web", "workspaces": ["packages/*"]}"#,
+        );
+        write(
+            root,
+            "web/packages/ui/package.json",
+            r#"{"name": "@shop/ui"}"#,
+        );
+        write(
+            root,
+            "web/node_modules/jose/package.json",
+            r#"{"name": "jose", "workspaces": ["lib"]}"#,
+        );
+        write(
+            root,
+            "tools/Cargo.toml",
+            "[workspace]\nmembers = [\"gen\"]\n",
+        );
+        write(
+            root,
+            "tools/gen/Cargo.toml",
+            "[package]\nname = \"shop-gen\"\n",
+        );
+
+        let workspace = Workspace::discover(root);
+        assert_eq!(
+            names(&workspace),
+            vec![
+                ("example.com/shop/api", Language::Go),
+                ("example.com/shop/worker", Language::Go),
+                ("shop-gen", Language::Rust),
+                ("shop-web", Language::Javascript),
+                ("@shop/ui", Language::Javascript),
+            ]
+        );
+
+        let member = |file: &str, language| {
+            workspace
+                .member_of(&root.join(file), language)
+                .map(|member| member.name.as_str())
+        };
+        assert_eq!(
+            member("api/hash.go", Language::Go),
+            Some("example.com/shop/api")
+        );
+        assert_eq!(
+            member("web/packages/ui/src/sign.ts", Language::Javascript),
+            Some("@shop/ui")
+        );
+        assert_eq!(
+            member("web/src/app.js", Language::Javascript),
+            Some("shop-web")
+        );
+        assert_eq!(
+            member("tools/gen/src/main.rs", Language::Rust),
+            Some("shop-gen")
+        );
+        assert_eq!(member("scripts/rotate.py", Language::Python), None);
+    }
+
+    #[test]
+    fn test_discover_python_monorepo() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "auth/pyproject.toml",
+            "[project]\nname = \"shop-auth\"\n",
+        );
+        write(root, "billing/setup.py", "from setuptools import setup\n");
+        write(root, ".venv/lib/site-packages/jwt/setup.py", "");
+
+        let workspace = Workspace::discover(root);
+        assert_eq!(
+            names(&workspace),
+            vec![
+                ("shop-auth", Language::Python),
+                ("billing", Language::Python)
+            ]
+        );
+
+        write(root, "only/pyproject.toml", "[project]\nname = \"only\"\n");
+        assert!(Workspace::discover(&root.join("only")).is_empty());
+    }
+}
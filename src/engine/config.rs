@@ -0,0 +1,71 @@
+use serde::Deserialize;
+
+/// User-facing resolver options, loaded from the `resolver` section of the
+/// config file and overridable from the command line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResolverConfig {
+    /// Explicit strategy chain, in order. Defaults to the built-in chain.
+    pub strategies: Option<Vec<String>>,
+    /// Strategies removed from the chain.
+    pub disabled: Vec<String>,
+    pub max_depth: Option<usize>,
+    /// Maximum nesting of function-return resolution (Call strategy).
+    pub call_depth: Option<usize>,
+    /// Whether constants may be resolved from other files in the same package.
+    pub cross_file: bool,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            strategies: None,
+            disabled: Vec::new(),
+            max_depth: None,
+            call_depth: None,
+            cross_file: true,
+        }
+    }
+}
+
+impl ResolverConfig {
+    pub fn is_enabled(&self, strategy: &str) -> bool {
+        !self
+            .disabled
+            .iter()
+            .any(|d| d.eq_ignore_ascii_case(strategy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolver_config_defaults() {
+        let config = ResolverConfig::default();
+        assert!(config.cross_file);
+        assert!(config.strategies.is_none());
+        assert!(config.is_enabled("call"));
+    }
+
+    #[test]
+    fn test_resolver_config_from_yaml() {
+        let yaml = "strategies: [literal, identifier]\ndisabled: [Call]\ncall_depth: 2\ncross_file: false\n";
+        let config: ResolverConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            config.strategies,
+            Some(vec!["literal".to_string(), "identifier".to_string()])
+        );
+        assert!(!config.is_enabled("call"));
+        assert_eq!(config.call_depth, Some(2));
+        assert!(!config.cross_file);
+        assert_eq!(config.max_depth, None);
+    }
+
+    #[test]
+    fn test_resolver_config_rejects_unknown_fields() {
+        let result: Result<ResolverConfig, _> = serde_yaml::from_str("strategy_order: [call]");
+        assert!(result.is_err());
+    }
+}
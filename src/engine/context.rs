@@ -1,13 +1,19 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::rc::Rc;
+use std::sync::Arc;
 use tree_sitter::{Node, Tree};
 
+use super::expr_cache::ExpressionCache;
 use super::file_cache::{FileCache, FunctionInfo};
-use super::lang_features;
+use super::literals;
 use super::node_types::{Language, NodeCategory, NodeTypes};
+use super::project_index::ProjectIndex;
 use super::scope::{Scope, ScopeEntry};
+use super::symbol_index::{SymbolIndex, SymbolKind};
+use super::value::{Evidence, EvidenceKind, Span};
+use super::Resolver;
 
 const MAX_CACHE_SIZE: usize = 10_000;
 
@@ -20,8 +26,18 @@ pub struct Context<'a> {
     scopes: RefCell<Vec<Scope>>,
     constants: RefCell<HashMap<String, ScopeEntry>>,
     file_cache: Option<Rc<RefCell<FileCache>>>,
-    value_cache: RefCell<HashMap<usize, crate::Value>>,
+    symbol_index: Option<Arc<SymbolIndex>>,
+    project_index: Option<Arc<ProjectIndex>>,
+    expression_cache: Option<Arc<ExpressionCache>>,
+    value_cache: RefCell<HashMap<usize, Rc<crate::Value>>>,
     visited_nodes: RefCell<HashSet<usize>>,
+    call_depth: Cell<usize>,
+    imported_names: HashSet<String>,
+    /// The import path each imported name stands for.
+    import_paths: HashMap<String, String>,
+    /// The resolver resolving on this context, which nested resolution
+    /// goes through.
+    resolver: RefCell<Option<Resolver>>,
 }
 
 impl<'a> Context<'a> {
@@ -41,8 +57,15 @@ impl<'a> Context<'a> {
             scopes: RefCell::new(Vec::new()),
             constants: RefCell::new(HashMap::new()),
             file_cache: None,
+            symbol_index: None,
+            project_index: None,
+            expression_cache: None,
             value_cache: RefCell::new(HashMap::new()),
             visited_nodes: RefCell::new(HashSet::new()),
+            call_depth: Cell::new(0),
+            imported_names: HashSet::new(),
+            import_paths: HashMap::new(),
+            resolver: RefCell::new(None),
         }
     }
 
@@ -63,11 +86,117 @@ impl<'a> Context<'a> {
             scopes: RefCell::new(Vec::new()),
             constants: RefCell::new(HashMap::new()),
             file_cache: Some(file_cache),
+            symbol_index: None,
+            project_index: None,
+            expression_cache: None,
             value_cache: RefCell::new(HashMap::new()),
             visited_nodes: RefCell::new(HashSet::new()),
+            call_depth: Cell::new(0),
+            imported_names: HashSet::new(),
+            import_paths: HashMap::new(),
+            resolver: RefCell::new(None),
         }
     }
 
+    /// A context for `tree`, parsed from another file of the project in
+    /// the same language, sharing this one's indexes and caches and
+    /// starting at its call depth.
+    pub fn for_file<'b>(
+        &self,
+        tree: &'b Tree,
+        source_code: &'b [u8],
+        file_path: String,
+    ) -> Context<'b> {
+        let mut ctx = Context::new(
+            tree,
+            source_code,
+            file_path,
+            self.language.clone(),
+            self.node_mappings.clone(),
+        );
+        ctx.file_cache = self.file_cache.clone();
+        ctx.symbol_index = self.symbol_index.clone();
+        ctx.project_index = self.project_index.clone();
+        ctx.expression_cache = self.expression_cache.clone();
+        ctx.call_depth.set(self.call_depth.get());
+        ctx.resolver = RefCell::new(self.resolver.borrow().clone());
+        ctx
+    }
+
+    /// Resolves `node`, a sub-expression of the node a strategy resolves,
+    /// through the resolver resolving on this context. A strategy run on
+    /// its own gets the default chain.
+    pub fn resolve(&self, node: &Node<'a>) -> crate::Value {
+        Rc::unwrap_or_clone(self.resolve_shared(node))
+    }
+
+    /// Like [`resolve`](Self::resolve), without copying the cached value.
+    pub fn resolve_shared(&self, node: &Node<'a>) -> Rc<crate::Value> {
+        let resolver = self
+            .resolver
+            .borrow_mut()
+            .get_or_insert_with(Resolver::new)
+            .clone();
+        resolver.resolve_shared(node, self)
+    }
+
+    /// Makes `resolver` the one nested resolution goes through, unless
+    /// another already is. Returns whether it was made so.
+    pub(crate) fn enter_resolver(&self, resolver: &Resolver) -> bool {
+        let mut current = self.resolver.borrow_mut();
+        if current.is_some() {
+            return false;
+        }
+        *current = Some(resolver.clone());
+        true
+    }
+
+    pub(crate) fn exit_resolver(&self) {
+        self.resolver.borrow_mut().take();
+    }
+
+    /// Attaches a shared symbol index used as a fallback for cross-file lookups.
+    pub fn with_symbol_index(mut self, symbol_index: Arc<SymbolIndex>) -> Self {
+        self.symbol_index = Some(symbol_index);
+        self
+    }
+
+    /// Attaches a run-wide cache for context-free expressions such as `1 << 16`.
+    pub fn with_expression_cache(mut self, expression_cache: Arc<ExpressionCache>) -> Self {
+        self.expression_cache = Some(expression_cache);
+        self
+    }
+
+    /// Records the local names the file binds to imported modules, so
+    /// unresolved `pkg.Name` references can be told apart from method calls.
+    pub fn with_imported_names(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.imported_names = names.into_iter().collect();
+        self
+    }
+
+    /// Records the import path each imported name stands for, which also
+    /// makes it an imported name.
+    pub fn with_import_paths(
+        mut self,
+        imports: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        for (name, path) in imports {
+            self.imported_names.insert(name.clone());
+            self.import_paths.insert(name, path);
+        }
+        self
+    }
+
+    /// Attaches a project index of constants declared in other packages.
+    pub fn with_project_index(mut self, project_index: Arc<ProjectIndex>) -> Self {
+        self.project_index = Some(project_index);
+        self
+    }
+
+    pub fn is_imported_name(&self, name: &str) -> bool {
+        self.imported_names.contains(name)
+    }
+
     pub fn tree(&self) -> &Tree {
         self.tree
     }
@@ -84,6 +213,26 @@ impl<'a> Context<'a> {
         &self.language
     }
 
+    /// An evidence step for `name`, declared at `node` in this file.
+    pub fn evidence(&self, kind: EvidenceKind, name: &str, node: &Node) -> Evidence {
+        Evidence {
+            kind,
+            name: name.to_string(),
+            file: self.file_path.clone(),
+            line: node.start_position().row + 1,
+        }
+    }
+
+    pub fn span(&self, node: &Node) -> Span {
+        Span {
+            file: self.file_path.clone(),
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+        }
+    }
+
     pub fn node_types(&self) -> Option<NodeTypes> {
         NodeTypes::from_language_str(&self.language)
     }
@@ -126,37 +275,21 @@ impl<'a> Context<'a> {
         node.child(index).map(|child| self.get_node_text(&child))
     }
 
-    pub fn unquote_string(&self, text: &str) -> String {
-        let text = text.trim();
-
-        if text.len() < 2 {
-            return text.to_string();
-        }
-
-        let first = text.chars().next().unwrap();
-        let last = text.chars().last().unwrap();
-
-        let inner = if (first == '"' && last == '"')
-            || (first == '\'' && last == '\'')
-            || (first == '`' && last == '`')
-        {
-            &text[1..text.len() - 1]
-        } else {
-            text
-        };
+    /// The language literals are read in, Go when the file's is unknown.
+    fn literal_language(&self) -> Language {
+        Language::parse(&self.language).unwrap_or(Language::Go)
+    }
 
-        inner
-            .replace("\\n", "\n")
-            .replace("\\t", "\t")
-            .replace("\\r", "\r")
-            .replace("\\\\", "\\")
-            .replace("\\\"", "\"")
-            .replace("\\'", "'")
+    pub fn unquote_string(&self, text: &str) -> String {
+        literals::unquote_string(text, self.literal_language())
     }
 
     pub fn parse_int_literal(&self, text: &str) -> Option<i64> {
-        let lang = Language::parse(&self.language).unwrap_or(Language::Go);
-        lang_features::parse_int_literal(text, lang)
+        literals::parse_int_literal(text, self.literal_language())
+    }
+
+    pub fn parse_float_literal(&self, text: &str) -> Option<f64> {
+        literals::parse_float_literal(text, self.literal_language())
     }
 
     pub fn get_named_children(&self, node: &Node<'a>) -> Vec<Node<'a>> {
@@ -185,10 +318,19 @@ impl<'a> Context<'a> {
     }
 
     pub fn get_cached_value(&self, node: &Node) -> Option<crate::Value> {
+        self.get_cached_shared(node).map(|v| (*v).clone())
+    }
+
+    /// Returns the cached value without copying its vectors.
+    pub fn get_cached_shared(&self, node: &Node) -> Option<Rc<crate::Value>> {
         self.value_cache.borrow().get(&node.id()).cloned()
     }
 
     pub fn cache_value(&self, node: &Node, value: crate::Value) {
+        self.cache_shared(node, Rc::new(value));
+    }
+
+    pub fn cache_shared(&self, node: &Node, value: Rc<crate::Value>) {
         let mut cache = self.value_cache.borrow_mut();
         if cache.len() >= MAX_CACHE_SIZE {
             let first_key = *cache.keys().next().unwrap();
@@ -197,6 +339,37 @@ impl<'a> Context<'a> {
         cache.insert(node.id(), value);
     }
 
+    pub fn expression_cache(&self) -> Option<&Arc<ExpressionCache>> {
+        self.expression_cache.as_ref()
+    }
+
+    pub fn get_shared_expression(&self, expression: &str) -> Option<crate::Value> {
+        let cache = self.expression_cache.as_ref()?;
+        let language = Language::parse(&self.language)?;
+        cache.get(language, expression)
+    }
+
+    pub fn cache_shared_expression(&self, expression: String, value: crate::Value) {
+        if let (Some(cache), Some(language)) = (
+            self.expression_cache.as_ref(),
+            Language::parse(&self.language),
+        ) {
+            cache.insert(language, expression, value);
+        }
+    }
+
+    pub fn call_depth(&self) -> usize {
+        self.call_depth.get()
+    }
+
+    pub fn enter_call(&self) {
+        self.call_depth.set(self.call_depth.get() + 1);
+    }
+
+    pub fn exit_call(&self) {
+        self.call_depth.set(self.call_depth.get().saturating_sub(1));
+    }
+
     pub fn push_scope(&self) {
         self.scopes.borrow_mut().push(Scope::new());
     }
@@ -235,14 +408,72 @@ impl<'a> Context<'a> {
     }
 
     pub fn find_cross_file_constant(&self, name: &str) -> Option<crate::Value> {
-        let cache = self.file_cache.as_ref()?;
-        let cache = cache.borrow();
+        if let Some(cache) = self.file_cache.as_ref() {
+            let cache = cache.borrow();
+
+            let found = if let Some(parent) = Path::new(&self.file_path).parent() {
+                cache.find_constant_in_package(name, &parent.to_string_lossy())
+            } else {
+                cache.find_constant(name)
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
 
-        if let Some(parent) = Path::new(&self.file_path).parent() {
-            cache.find_constant_in_package(name, &parent.to_string_lossy())
-        } else {
-            cache.find_constant(name)
+        self.find_indexed_constant(name)
+    }
+
+    /// The constant `name` of the package the file imports as `package`
+    /// (`crypto.DefaultIterations`), from the project index.
+    pub fn find_package_constant(&self, package: &str, name: &str) -> Option<crate::Value> {
+        let index = self.project_index.as_ref()?;
+        index.find_constant(self.import_paths.get(package)?, name)
+    }
+
+    /// The constant a Python file imports by name
+    /// (`from app.settings import ITERATIONS`), from the project index.
+    pub fn find_imported_constant(&self, name: &str) -> Option<crate::Value> {
+        if self.language != "python" {
+            return None;
         }
+        let index = self.project_index.as_ref()?;
+        let (module, constant) = self.import_paths.get(name)?.rsplit_once('.')?;
+        index.find_constant(module, constant)
+    }
+
+    fn find_indexed_constant(&self, name: &str) -> Option<crate::Value> {
+        let index = self.symbol_index.as_ref()?;
+        let language = Language::parse(&self.language)?;
+        let package_dir = Path::new(&self.file_path).parent()?;
+        index.find_constant(package_dir, name, language)
+    }
+
+    /// The function `name` declared in another file of this file's package
+    /// (its directory), from the symbol index. With `module`, only the file
+    /// of that name is searched, as for `params.get_iterations()` or
+    /// `params::get_iterations()`.
+    pub fn find_package_function(&self, name: &str, module: Option<&str>) -> Option<FunctionInfo> {
+        let index = self.symbol_index.as_ref()?;
+        let language = Language::parse(&self.language)?;
+        let package_dir = Path::new(&self.file_path).parent()?;
+        let package = index.package(package_dir);
+        let symbol = package.iter().find(|symbol| {
+            symbol.kind == SymbolKind::Function
+                && symbol.language == language
+                && symbol.name == name
+                && symbol.file_path != self.file_path
+                && module.is_none_or(|module| {
+                    Path::new(&symbol.file_path)
+                        .file_stem()
+                        .is_some_and(|stem| stem == module)
+                })
+        })?;
+        Some(symbol.function_info())
+    }
+
+    pub fn symbol_index(&self) -> Option<&Arc<SymbolIndex>> {
+        self.symbol_index.as_ref()
     }
 
     pub fn find_cross_file_function(&self, name: &str) -> Option<FunctionInfo> {
@@ -711,4 +942,30 @@ mod tests {
         assert_eq!(named_children[0].kind(), "package_clause");
         assert_eq!(named_children[1].kind(), "const_declaration");
     }
+
+    #[test]
+    fn test_cross_file_constant_from_symbol_index() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("params.go"),
+            "package main\nconst Iterations = 4096\n",
+        )
+        .unwrap();
+        let file_path = dir.path().join("main.go");
+
+        let source = "package main";
+        let tree = parse_go_source(source);
+        let ctx = Context::new(
+            &tree,
+            source.as_bytes(),
+            file_path.to_string_lossy().to_string(),
+            "go".to_string(),
+            HashMap::new(),
+        );
+        assert!(ctx.find_cross_file_constant("Iterations").is_none());
+
+        let ctx = ctx.with_symbol_index(SymbolIndex::shared());
+        let value = ctx.find_cross_file_constant("Iterations").unwrap();
+        assert_eq!(value.int_values, vec![4096]);
+    }
 }
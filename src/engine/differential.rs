@@ -0,0 +1,337 @@
+//! Differential checks of constant folding against the language runtimes.
+//!
+//! The binary and unary strategies reimplement each language's operator
+//! semantics: integer division, `**`, string repetition, JavaScript's
+//! string/number `+`. [`constant_expressions`] collects expressions built
+//! only from literals and operators, with the value the resolver gives them,
+//! and [`evaluate`] runs the same text in `python3` or `node`. Any
+//! [`divergences`] point at folding rules that disagree with the language.
+//!
+//! Expressions with a name in them are never collected, so the runtime sees
+//! no variables, attributes or calls; Python evaluates without builtins and
+//! Node in an empty `vm` context. A batch is killed after [`TIMEOUT`].
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Tree};
+
+use super::{Context, Language, NodeCategory, Resolver};
+
+/// Time allowed for one runtime to evaluate a batch.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+const PYTHON_EVAL: &str = r#"
+import json, sys
+out = []
+for expr in json.load(sys.stdin):
+    try:
+        v = eval(compile(expr, "<expr>", "eval"), {"__builtins__": {}}, {})
+        if isinstance(v, bytes):
+            v = v.decode("latin-1")
+        if isinstance(v, (bool, int, float, str)):
+            out.append({"value": v})
+        else:
+            out.append({"error": "unsupported result " + type(v).__name__})
+    except Exception as e:
+        out.append({"error": type(e).__name__ + ": " + str(e)})
+json.dump(out, sys.stdout)
+"#;
+
+const NODE_EVAL: &str = r#"
+const vm = require("vm");
+let input = "";
+process.stdin.on("data", (d) => (input += d));
+process.stdin.on("end", () => {
+  const out = JSON.parse(input).map((expr) => {
+    try {
+      const v = vm.runInNewContext(expr, Object.create(null), { timeout: 1000 });
+      if (typeof v === "number" && !Number.isFinite(v)) return { error: "non-finite " + v };
+      if (["number", "string", "boolean"].includes(typeof v)) return { value: v };
+      if (typeof v === "bigint") return { value: Number(v) };
+      return { error: "unsupported result " + typeof v };
+    } catch (e) {
+      return { error: String(e) };
+    }
+  });
+  process.stdout.write(JSON.stringify(out));
+});
+"#;
+
+/// A constant expression and the value the resolver folded it to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstantExpression {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub expression: String,
+    pub value: serde_json::Value,
+}
+
+/// What the runtime made of one expression.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuntimeResult {
+    Value(serde_json::Value),
+    Error(String),
+}
+
+/// An expression the resolver and the runtime disagree on.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub expression: String,
+    pub engine: serde_json::Value,
+    /// The runtime's value, or the error it raised.
+    pub runtime: String,
+}
+
+/// The runtime that evaluates `language`, if one is supported.
+pub fn runtime_for(language: Language) -> Option<&'static str> {
+    match language {
+        Language::Python => Some("python3"),
+        Language::JavaScript | Language::TypeScript => Some("node"),
+        _ => None,
+    }
+}
+
+/// Literal-and-operator expressions in `tree` that `resolver` resolves to a
+/// single value. Nested ones are covered by the outermost.
+pub fn constant_expressions(
+    tree: &Tree,
+    source: &[u8],
+    file_path: &str,
+    language: Language,
+    resolver: &Resolver,
+) -> Vec<ConstantExpression> {
+    let ctx = Context::new(
+        tree,
+        source,
+        file_path.to_string(),
+        language.tree_sitter_name().to_string(),
+        Default::default(),
+    );
+    let mut out = Vec::new();
+    collect(tree.root_node(), &ctx, resolver, &mut out);
+    out
+}
+
+fn collect<'a>(
+    node: Node<'a>,
+    ctx: &Context<'a>,
+    resolver: &Resolver,
+    out: &mut Vec<ConstantExpression>,
+) {
+    let is_operation = ctx.is_node_category(node.kind(), NodeCategory::BinaryExpression)
+        || ctx.is_node_category(node.kind(), NodeCategory::UnaryExpression);
+    if is_operation && is_closed(node, ctx) {
+        if let Some(value) = single_value(&resolver.resolve(&node, ctx)) {
+            let position = node.start_position();
+            out.push(ConstantExpression {
+                file: ctx.file_path().to_string(),
+                line: position.row + 1,
+                column: position.column + 1,
+                expression: ctx.get_node_text(&node),
+                value,
+            });
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect(child, ctx, resolver, out);
+    }
+}
+
+/// Whether `node` is made of literals and operators only.
+fn is_closed(node: Node, ctx: &Context) -> bool {
+    let kind = node.kind();
+    if kind.contains("identifier")
+        || ctx.is_node_category(kind, NodeCategory::Identifier)
+        || kind == "interpolation"
+        || kind == "template_substitution"
+        || ctx.is_node_category(kind, NodeCategory::CallExpression)
+        || ctx.is_node_category(kind, NodeCategory::SelectorExpression)
+        || ctx.is_node_category(kind, NodeCategory::IndexExpression)
+    {
+        return false;
+    }
+    let mut cursor = node.walk();
+    let closed = node
+        .named_children(&mut cursor)
+        .all(|child| is_closed(child, ctx));
+    closed
+}
+
+fn single_value(value: &super::Value) -> Option<serde_json::Value> {
+    if !value.is_resolved {
+        return None;
+    }
+    match (value.int_values.as_slice(), value.string_values.as_slice()) {
+        ([int], []) => Some((*int).into()),
+        ([], [string]) => Some(string.clone().into()),
+        _ => None,
+    }
+}
+
+/// Evaluates `expressions` in `runtime` (`python3` or `node`, or a path to
+/// either), one result per expression.
+pub fn evaluate(runtime: &str, expressions: &[String]) -> Result<Vec<RuntimeResult>, String> {
+    let is_node = runtime.contains("node");
+    let mut command = Command::new(runtime);
+    if is_node {
+        command.args(["-e", NODE_EVAL]);
+    } else {
+        command.args(["-I", "-S", "-c", PYTHON_EVAL]);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {runtime}: {e}"))?;
+
+    let input = serde_json::to_vec(expressions).expect("strings serialize");
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(&input)
+            .map_err(|e| format!("failed to write to {runtime}: {e}"))?;
+    }
+    // Drained while waiting, so a large batch cannot fill the pipe.
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| format!("failed to wait for {runtime}: {e}"))?
+        {
+            break status;
+        }
+        if started.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("{runtime} timed out after {}s", TIMEOUT.as_secs()));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = stderr.join().unwrap_or_default();
+        return Err(format!(
+            "{runtime} failed: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        ));
+    }
+    let results: Vec<RuntimeResult> = serde_json::from_slice(&stdout)
+        .map_err(|e| format!("unexpected output from {runtime}: {e}"))?;
+    if results.len() != expressions.len() {
+        return Err(format!("{runtime} returned {} results", results.len()));
+    }
+    Ok(results)
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut out = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut out);
+        }
+        out
+    })
+}
+
+/// The expressions whose runtime result differs from the resolver's.
+pub fn divergences(
+    expressions: &[ConstantExpression],
+    results: &[RuntimeResult],
+) -> Vec<Divergence> {
+    expressions
+        .iter()
+        .zip(results)
+        .filter_map(|(expression, result)| {
+            let runtime = match result {
+                RuntimeResult::Value(value) if same_value(&expression.value, value) => return None,
+                RuntimeResult::Value(value) => value.to_string(),
+                RuntimeResult::Error(error) => error.clone(),
+            };
+            Some(Divergence {
+                file: expression.file.clone(),
+                line: expression.line,
+                column: expression.column,
+                expression: expression.expression.clone(),
+                engine: expression.value.clone(),
+                runtime,
+            })
+        })
+        .collect()
+}
+
+/// Integers compare by value, so `True` matches 1 and `2.0` matches 2.
+fn same_value(engine: &serde_json::Value, runtime: &serde_json::Value) -> bool {
+    match (engine.as_i64(), runtime) {
+        (Some(int), serde_json::Value::Bool(b)) => int == i64::from(*b),
+        (Some(int), serde_json::Value::Number(n)) => n.as_f64() == Some(int as f64),
+        _ => engine == runtime,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::symbol_index::tree_sitter_language;
+
+    fn expressions(source: &str, language: Language) -> Vec<ConstantExpression> {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_language(language).unwrap())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        constant_expressions(&tree, source.as_bytes(), "a", language, &Resolver::new())
+    }
+
+    #[test]
+    fn test_collects_outermost_closed_expressions() {
+        let found = expressions(
+            "ROUNDS = 100_000 * 6\nKEY = (1 << 4) + 1\nn = size * 8\n",
+            Language::Python,
+        );
+        let texts: Vec<&str> = found.iter().map(|e| e.expression.as_str()).collect();
+        assert_eq!(texts, vec!["100_000 * 6", "(1 << 4) + 1"]);
+        assert_eq!(found[0].value, serde_json::json!(600000));
+        assert_eq!(found[1].line, 2);
+    }
+
+    #[test]
+    fn test_divergences() {
+        let expression = |text: &str, value: serde_json::Value| ConstantExpression {
+            file: "a.py".to_string(),
+            line: 1,
+            column: 1,
+            expression: text.to_string(),
+            value,
+        };
+        let found = divergences(
+            &[
+                expression("7 / 2", serde_json::json!(3)),
+                expression("1 < 2", serde_json::json!(1)),
+                expression("'a' + 1", serde_json::json!("a1")),
+            ],
+            &[
+                RuntimeResult::Value(serde_json::json!(3.5)),
+                RuntimeResult::Value(serde_json::json!(true)),
+                RuntimeResult::Error("TypeError: can only concatenate str".to_string()),
+            ],
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].runtime, "3.5");
+        assert_eq!(found[1].engine, serde_json::json!("a1"));
+    }
+}
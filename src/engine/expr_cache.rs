@@ -0,0 +1,319 @@
+//! Content-addressed cache for context-free expressions.
+//!
+//! Expressions built only from literals and operators (e.g. `1 << 16`,
+//! `32 * 8`) resolve to the same value wherever they appear, so their
+//! results can be shared across every file in a run. The key is the
+//! language plus the expression text; anything that references an
+//! identifier, call, or interpolation is never cached.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use tree_sitter::Node;
+
+use super::node_types::{Language, NodeCategory};
+use super::{Context, Span, Value};
+
+const MAX_EXPRESSION_CACHE_SIZE: usize = 50_000;
+
+// PHP interpolates variables (`"$salt:$rounds"`) without a wrapper node
+const INTERPOLATION_KINDS: &[&str] = &[
+    "interpolation",
+    "template_substitution",
+    "variable_name",
+    "interpolated_expression",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExpressionCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl ExpressionCacheStats {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.lookups() as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExpressionCache {
+    entries: RwLock<HashMap<(Language, String), Value>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ExpressionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    pub fn get(&self, language: Language, expression: &str) -> Option<Value> {
+        let found = self
+            .entries
+            .read()
+            .unwrap()
+            .get(&(language, expression.to_string()))
+            .cloned();
+
+        if found.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        found
+    }
+
+    /// Stores a value; once the cache is full new expressions are dropped.
+    pub fn insert(&self, language: Language, expression: String, value: Value) {
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= MAX_EXPRESSION_CACHE_SIZE {
+            return;
+        }
+        entries.insert((language, expression), value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> ExpressionCacheStats {
+        ExpressionCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.len(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Returns the cache key text for `node` if it is an operator expression
+/// whose value does not depend on where it appears.
+pub fn cache_key(node: &Node, ctx: &Context) -> Option<String> {
+    let is_operator = ctx.is_node_category(node.kind(), NodeCategory::BinaryExpression)
+        || ctx.is_node_category(node.kind(), NodeCategory::UnaryExpression);
+
+    if is_operator && is_context_free(node, ctx) {
+        Some(ctx.get_node_text(node))
+    } else {
+        None
+    }
+}
+
+/// Spans of the literals in a cached expression. A shared value carries
+/// the locations of whichever file first resolved it, so a hit is
+/// re-pointed at the literals under `node`.
+pub fn literal_spans(node: &Node, ctx: &Context) -> Vec<Span> {
+    let literals = [
+        NodeCategory::IntegerLiteral,
+        NodeCategory::FloatLiteral,
+        NodeCategory::StringLiteral,
+        NodeCategory::BooleanLiteral,
+        NodeCategory::NilLiteral,
+    ];
+    if literals
+        .iter()
+        .any(|c| ctx.is_node_category(node.kind(), *c))
+    {
+        return vec![ctx.span(node)];
+    }
+
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+        .iter()
+        .flat_map(|child| literal_spans(child, ctx))
+        .collect()
+}
+
+fn is_context_free(node: &Node, ctx: &Context) -> bool {
+    let kind = node.kind();
+
+    if ctx.is_node_category(kind, NodeCategory::StringLiteral) {
+        return !contains_kind(node, INTERPOLATION_KINDS);
+    }
+
+    let allowed = [
+        NodeCategory::IntegerLiteral,
+        NodeCategory::FloatLiteral,
+        NodeCategory::BooleanLiteral,
+        NodeCategory::NilLiteral,
+        NodeCategory::BinaryExpression,
+        NodeCategory::UnaryExpression,
+        NodeCategory::ParenthesizedExpression,
+    ];
+    if !allowed.iter().any(|c| ctx.is_node_category(kind, *c)) {
+        return false;
+    }
+
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children.iter().all(|child| is_context_free(child, ctx))
+}
+
+fn contains_kind(node: &Node, kinds: &[&str]) -> bool {
+    if kinds.contains(&node.kind()) {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children.iter().any(|child| contains_kind(child, kinds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Resolver;
+
+    fn parse(source: &str, language: tree_sitter::Language) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&language).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    fn find_first_node_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+        if node.kind() == kind {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if let Some(found) = find_first_node_of_kind(child, kind) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    fn context<'a>(tree: &'a tree_sitter::Tree, source: &'a str, lang: &str) -> Context<'a> {
+        Context::new(
+            tree,
+            source.as_bytes(),
+            "test".to_string(),
+            lang.to_string(),
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_cache_key_for_literal_expression() {
+        let source = "package main\nconst x = 1 << 16";
+        let tree = parse(source, tree_sitter_go::LANGUAGE.into());
+        let ctx = context(&tree, source, "go");
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+        assert_eq!(cache_key(&node, &ctx), Some("1 << 16".to_string()));
+    }
+
+    #[test]
+    fn test_cache_key_rejects_identifiers() {
+        let source = "package main\nconst x = size * 8";
+        let tree = parse(source, tree_sitter_go::LANGUAGE.into());
+        let ctx = context(&tree, source, "go");
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+        assert_eq!(cache_key(&node, &ctx), None);
+    }
+
+    #[test]
+    fn test_cache_key_rejects_plain_literals() {
+        let source = "package main\nconst x = 42";
+        let tree = parse(source, tree_sitter_go::LANGUAGE.into());
+        let ctx = context(&tree, source, "go");
+
+        let node = find_first_node_of_kind(tree.root_node(), "int_literal").unwrap();
+        assert_eq!(cache_key(&node, &ctx), None);
+    }
+
+    #[test]
+    fn test_cache_key_rejects_interpolated_strings() {
+        let source = "x = f\"{a}\" + \"b\"\n";
+        let tree = parse(source, tree_sitter_python::LANGUAGE.into());
+        let ctx = context(&tree, source, "python");
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_operator").unwrap();
+        assert_eq!(cache_key(&node, &ctx), None);
+    }
+
+    #[test]
+    fn test_cache_hit_rate() {
+        let cache = ExpressionCache::new();
+        assert!(cache.get(Language::Go, "1 << 16").is_none());
+
+        cache.insert(
+            Language::Go,
+            "1 << 16".to_string(),
+            Value::resolved_int(65536),
+        );
+        assert_eq!(
+            cache.get(Language::Go, "1 << 16").unwrap().int_values,
+            vec![65536]
+        );
+        assert!(cache.get(Language::Python, "1 << 16").is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 1);
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_resolver_shares_results_across_files() {
+        let cache = ExpressionCache::shared();
+        let resolver = Resolver::new();
+
+        for file in ["a.go", "b.go"] {
+            let source = "package main\nconst x = 1 << 16";
+            let tree = parse(source, tree_sitter_go::LANGUAGE.into());
+            let ctx = Context::new(
+                &tree,
+                source.as_bytes(),
+                file.to_string(),
+                "go".to_string(),
+                HashMap::new(),
+            )
+            .with_expression_cache(Arc::clone(&cache));
+
+            let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+            let value = resolver.resolve(&node, &ctx);
+            assert_eq!(value.int_values, vec![65536]);
+
+            // A hit points at this file's literals, not the first file's
+            let files: Vec<&str> = value.spans.iter().map(|s| s.file.as_str()).collect();
+            assert_eq!(files, vec![file, file]);
+            let texts: Vec<&str> = value
+                .spans
+                .iter()
+                .map(|s| &source[s.start_byte..s.end_byte])
+                .collect();
+            assert_eq!(texts, vec!["1", "16"]);
+        }
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+}
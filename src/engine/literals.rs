@@ -0,0 +1,512 @@
+//! Literal tokens as each language writes them. Every strategy that reads a
+//! literal goes through here, so they all agree on its value:
+//! - digit separators: `_`, and `'` in C and C++
+//! - integer prefixes (`0x`, `0o`, `0b`, Ruby's `0d`), and leading-zero
+//!   octal in the languages that have it (Go, C, C++, Java, JavaScript,
+//!   TypeScript, PHP, Ruby)
+//! - integer and float suffixes: Rust `u32`/`f64`, C and C++ `UL`/`f`, C#
+//!   `UL`/`m`, Java `L`/`d`, Kotlin `uL`/`f`, JavaScript BigInt `n`
+//! - string prefixes, raw strings and escapes: Python `r""`/`b""`, Rust
+//!   `r#""#`/`b""`, C++ `R"(...)"`/`u8""`, C# `@""` and `"""raw"""`, Swift
+//!   `#""#`, Kotlin raw `"""` strings, Go backquotes, single-quoted Ruby
+//!   and PHP strings, Ruby symbols, and multi-line `"""` strings
+
+use super::Language;
+
+pub const RUST_INT_SUFFIXES: &[&str] = &[
+    "u128", "i128", "usize", "isize", "u64", "i64", "u32", "i32", "u16", "i16", "u8", "i8",
+];
+
+pub const RUST_FLOAT_SUFFIXES: &[&str] = &["f32", "f64"];
+
+pub fn parse_int_literal(text: &str, language: Language) -> Option<i64> {
+    let text = strip_separators(text, language);
+    let text = strip_int_suffix(&text, language);
+    let prefix = text.get(..2).map(str::to_ascii_lowercase);
+    let (radix, digits) = match prefix.as_deref() {
+        Some("0x") => (16, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        Some("0d") if language == Language::Ruby => (10, &text[2..]),
+        _ => return parse_decimal(text, language),
+    };
+    i64::from_str_radix(digits, radix).ok()
+}
+
+pub fn parse_float_literal(text: &str, language: Language) -> Option<f64> {
+    let text = strip_separators(text, language);
+    let text = match language {
+        Language::Rust => RUST_FLOAT_SUFFIXES
+            .iter()
+            .find_map(|suffix| text.strip_suffix(suffix))
+            .unwrap_or(&text),
+        Language::C | Language::Cpp => text.trim_end_matches(['f', 'F', 'l', 'L']),
+        Language::Java | Language::Scala => text.trim_end_matches(['f', 'F', 'd', 'D']),
+        Language::Kotlin => text.trim_end_matches(['f', 'F']),
+        Language::CSharp => text.trim_end_matches(['f', 'F', 'd', 'D', 'm', 'M']),
+        _ => &text,
+    };
+    text.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// The integer a float literal stands for, as with `1e3` or `256.0`.
+pub fn integral(value: f64) -> Option<i64> {
+    (value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64)
+        .then_some(value as i64)
+}
+
+/// The value of a string literal: without its prefix and delimiters, and
+/// with its escapes decoded unless it is a raw string. Text that is not a
+/// quoted literal only has its escapes decoded.
+pub fn unquote_string(text: &str, language: Language) -> String {
+    let text = text.trim();
+    match language {
+        Language::Python => python_string(text),
+        Language::Rust => rust_string(text),
+        Language::C | Language::Cpp => c_string(text),
+        Language::CSharp => csharp_string(text),
+        Language::Swift => swift_string(text),
+        Language::Go if text.starts_with('`') => unquote(text, Escapes::None),
+        Language::Java => match triple_quoted(text, '"') {
+            Some(inner) => unescape(&multi_line(inner), Escapes::All),
+            None => unquote(text, Escapes::All),
+        },
+        // `"""` strings are raw; `trimIndent()` is a call on them
+        Language::Kotlin => match triple_quoted(text, '"') {
+            Some(inner) => inner.to_string(),
+            None => unquote(text, Escapes::Templates),
+        },
+        // Scala `"""` strings are raw too
+        Language::Scala => match triple_quoted(text, '"') {
+            Some(inner) => inner.to_string(),
+            None => unquote(text, Escapes::All),
+        },
+        // Symbols stand for their name: :gcm, :"aes-256-gcm"
+        Language::Ruby => single_or_double(text.strip_prefix(':').unwrap_or(text)),
+        Language::Php => single_or_double(text),
+        Language::Go | Language::JavaScript | Language::TypeScript => unquote(text, Escapes::All),
+    }
+}
+
+fn strip_separators(text: &str, language: Language) -> String {
+    let text = text.trim();
+    match language {
+        // A character literal such as '7' is not a number
+        Language::C | Language::Cpp if !text.starts_with('\'') => text.replace(['_', '\''], ""),
+        _ => text.replace('_', ""),
+    }
+}
+
+pub fn strip_int_suffix(text: &str, language: Language) -> &str {
+    match language {
+        Language::Rust => RUST_INT_SUFFIXES
+            .iter()
+            .find_map(|suffix| text.strip_suffix(suffix))
+            .unwrap_or(text),
+        Language::C | Language::Cpp => text.trim_end_matches(['u', 'U', 'l', 'L', 'z', 'Z']),
+        Language::CSharp => text.trim_end_matches(['u', 'U', 'l', 'L']),
+        Language::Java | Language::Scala => text.strip_suffix(['l', 'L']).unwrap_or(text),
+        Language::Kotlin => text.trim_end_matches(['u', 'U', 'L']),
+        Language::JavaScript | Language::TypeScript => text.strip_suffix('n').unwrap_or(text),
+        _ => text,
+    }
+}
+
+fn parse_decimal(text: &str, language: Language) -> Option<i64> {
+    let legacy_octal = matches!(
+        language,
+        Language::Go
+            | Language::C
+            | Language::Cpp
+            | Language::Java
+            | Language::JavaScript
+            | Language::TypeScript
+            | Language::Php
+            | Language::Ruby
+    );
+    match text.strip_prefix('0') {
+        // 0755: octal, or decimal where a digit is not, as JavaScript reads 089
+        Some(octal) if legacy_octal && !octal.is_empty() => i64::from_str_radix(octal, 8)
+            .ok()
+            .or_else(|| text.parse().ok()),
+        // Python only allows leading zeros in zero itself
+        Some(rest) if language == Language::Python && !rest.is_empty() => {
+            rest.bytes().all(|b| b == b'0').then_some(0)
+        }
+        _ => text.parse().ok(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escapes {
+    All,
+    /// [`Escapes::All`] and `\$`, which keeps a Kotlin `$` from starting a
+    /// template.
+    Templates,
+    /// Only `\\` and `\'`, as in single-quoted Ruby and PHP strings.
+    Quotes,
+    None,
+}
+
+/// Decodes the escapes of `inner` in one pass, so `\\n` stays a backslash
+/// followed by `n`. Escapes not known here are kept as written.
+fn unescape(inner: &str, escapes: Escapes) -> String {
+    if escapes == Escapes::None || !inner.contains('\\') {
+        return inner.to_string();
+    }
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let Some(next) = chars.next() else {
+            out.push('\\');
+            break;
+        };
+        match (escapes, next) {
+            (_, '\\') => out.push('\\'),
+            (_, '\'') => out.push('\''),
+            (Escapes::All | Escapes::Templates, '"') => out.push('"'),
+            (Escapes::All | Escapes::Templates, '`') => out.push('`'),
+            (Escapes::All | Escapes::Templates, 'n') => out.push('\n'),
+            (Escapes::All | Escapes::Templates, 't') => out.push('\t'),
+            (Escapes::All | Escapes::Templates, 'r') => out.push('\r'),
+            (Escapes::Templates, '$') => out.push('$'),
+            _ => {
+                out.push('\\');
+                out.push(next);
+            }
+        }
+    }
+    out
+}
+
+/// `text` between one pair of matching quotes or backquotes.
+fn strip_quotes(text: &str) -> Option<&str> {
+    ['"', '\'', '`']
+        .into_iter()
+        .find_map(|quote| text.strip_prefix(quote)?.strip_suffix(quote))
+}
+
+fn unquote(text: &str, escapes: Escapes) -> String {
+    unescape(strip_quotes(text).unwrap_or(text), escapes)
+}
+
+fn single_or_double(text: &str) -> String {
+    let escapes = if text.starts_with('\'') {
+        Escapes::Quotes
+    } else {
+        Escapes::All
+    };
+    unquote(text, escapes)
+}
+
+fn triple_quoted(text: &str, quote: char) -> Option<&str> {
+    let delimiter = [quote; 3].iter().collect::<String>();
+    (text.len() >= 6)
+        .then(|| text.strip_prefix(&delimiter)?.strip_suffix(&delimiter))
+        .flatten()
+}
+
+/// The lines of a multi-line string between its delimiters, without the
+/// indentation of the closing delimiter, as Swift and Java text blocks have
+/// it.
+fn multi_line(inner: &str) -> String {
+    let inner = inner.strip_prefix('\n').unwrap_or(inner);
+    let (body, indent) = inner.rsplit_once('\n').unwrap_or((inner, ""));
+    if !indent.trim().is_empty() {
+        return inner.to_string();
+    }
+    body.lines()
+        .map(|line| line.strip_prefix(indent).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Splits one of `prefixes`, compared ignoring case, off the quoted text.
+fn split_prefix<'t>(text: &'t str, prefixes: &[&str]) -> (&'t str, &'t str) {
+    match text.find(['"', '\'']) {
+        Some(at) if at > 0 && prefixes.iter().any(|p| p.eq_ignore_ascii_case(&text[..at])) => {
+            text.split_at(at)
+        }
+        _ => ("", text),
+    }
+}
+
+/// Python `r""`, `b""`, `u""` and `rb""` strings, quoted once or thrice.
+/// f-strings are left as written.
+fn python_string(text: &str) -> String {
+    let (prefix, body) = split_prefix(text, &["r", "u", "b", "br", "rb"]);
+    let escapes = if prefix.to_ascii_lowercase().contains('r') {
+        Escapes::None
+    } else {
+        Escapes::All
+    };
+    match triple_quoted(body, '"').or_else(|| triple_quoted(body, '\'')) {
+        Some(inner) => unescape(inner, escapes),
+        None => unquote(body, escapes),
+    }
+}
+
+/// Rust `b""` and `c""` strings, and `r#""#` raw strings with any number
+/// of `#`.
+fn rust_string(text: &str) -> String {
+    let Some(at) = text.find('"').filter(|&at| at > 0) else {
+        return unquote(text, Escapes::All);
+    };
+    let (prefix, body) = text.split_at(at);
+    let kind = prefix.trim_end_matches('#');
+    let hashes = &prefix[kind.len()..];
+    match kind {
+        "r" | "br" | "cr" => body
+            .strip_prefix('"')
+            .and_then(|b| b.strip_suffix(hashes))
+            .and_then(|b| b.strip_suffix('"'))
+            .unwrap_or(body)
+            .to_string(),
+        "b" | "c" if hashes.is_empty() => unquote(body, Escapes::All),
+        _ => unquote(text, Escapes::All),
+    }
+}
+
+/// C and C++ `L""`, `u""`, `U""` and `u8""` strings, and C++
+/// `R"delimiter(raw)delimiter"` raw strings.
+fn c_string(text: &str) -> String {
+    let (prefix, body) = split_prefix(text, &["L", "u", "U", "u8", "R", "LR", "uR", "UR", "u8R"]);
+    if prefix.ends_with(['R', 'r']) {
+        let raw = strip_quotes(body).and_then(|inner| {
+            let (delimiter, rest) = inner.split_once('(')?;
+            rest.strip_suffix(delimiter)?.strip_suffix(')')
+        });
+        return raw.unwrap_or(body).to_string();
+    }
+    unquote(body, Escapes::All)
+}
+
+/// C# `@"verbatim"` strings, which double their quotes, and `"""raw"""`
+/// strings delimited by three or more quotes.
+fn csharp_string(text: &str) -> String {
+    if let Some(inner) = text.strip_prefix("@\"").and_then(|t| t.strip_suffix('"')) {
+        return inner.replace("\"\"", "\"");
+    }
+    if text.starts_with("\"\"\"") {
+        let quotes = text.len() - text.trim_start_matches('"').len();
+        let inner = &text[quotes..text.len().saturating_sub(quotes).max(quotes)];
+        return match inner.strip_prefix('\n') {
+            Some(_) => multi_line(inner),
+            None => inner.to_string(),
+        };
+    }
+    unquote(text, Escapes::All)
+}
+
+/// Swift `#"raw"#` strings with any number of `#`, and `"""` multi-line
+/// strings.
+fn swift_string(text: &str) -> String {
+    let unhashed = text.trim_start_matches('#');
+    let hashes = &text[..text.len() - unhashed.len()];
+    let (body, escapes) = match unhashed.strip_suffix(hashes) {
+        Some(body) if !hashes.is_empty() => (body, Escapes::None),
+        _ => (text, Escapes::All),
+    };
+    match triple_quoted(body, '"') {
+        Some(inner) => unescape(&multi_line(inner), escapes),
+        None => unquote(body, escapes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Language::*;
+
+    const INT_CASES: &[(Language, &str, Option<i64>)] = &[
+        (Go, "100", Some(100)),
+        (Go, "100_000", Some(100_000)),
+        (Go, "0xFF", Some(255)),
+        (Go, "0o40", Some(32)),
+        (Go, "0b1010", Some(10)),
+        (Go, "0755", Some(493)),
+        (Go, "0", Some(0)),
+        (Go, "100u32", None),
+        (Python, "0XFF", Some(255)),
+        (Python, "0O40", Some(32)),
+        (Python, "0b_1000", Some(8)),
+        (Python, "1_000_000", Some(1_000_000)),
+        (Python, "000", Some(0)),
+        (Python, "0755", None),
+        (Rust, "100u32", Some(100)),
+        (Rust, "200i64", Some(200)),
+        (Rust, "300usize", Some(300)),
+        (Rust, "1_024_u16", Some(1024)),
+        (Rust, "0xFFu8", Some(255)),
+        (Rust, "0B100000", Some(32)),
+        (Rust, "0o644", Some(420)),
+        (Rust, "0755", Some(755)),
+        (JavaScript, "4096", Some(4096)),
+        (JavaScript, "65_537", Some(65537)),
+        (JavaScript, "123n", Some(123)),
+        (JavaScript, "0x10n", Some(16)),
+        (JavaScript, "0755", Some(493)),
+        (JavaScript, "089", Some(89)),
+        (TypeScript, "2048n", Some(2048)),
+        (TypeScript, "0b11", Some(3)),
+        (Java, "100L", Some(100)),
+        (Java, "0x7fl", Some(127)),
+        (Java, "1_000", Some(1000)),
+        (Java, "010", Some(8)),
+        (Kotlin, "600_000", Some(600_000)),
+        (Kotlin, "0xFFL", Some(255)),
+        (Kotlin, "10uL", Some(10)),
+        (Scala, "600_000", Some(600_000)),
+        (Scala, "0xFFL", Some(255)),
+        (Scala, "-1", Some(-1)),
+        (C, "100000UL", Some(100_000)),
+        (C, "0x10u", Some(16)),
+        (C, "32", Some(32)),
+        (C, "256ULL", Some(256)),
+        (C, "0644", Some(420)),
+        (Cpp, "1'000'000", Some(1_000_000)),
+        (Cpp, "0b1000'0000", Some(128)),
+        (Cpp, "16uz", Some(16)),
+        (CSharp, "128UL", Some(128)),
+        (CSharp, "0x_FF", Some(255)),
+        (CSharp, "0b1001", Some(9)),
+        (CSharp, "0755", Some(755)),
+        (Ruby, "0d255", Some(255)),
+        (Ruby, "0o17", Some(15)),
+        (Ruby, "017", Some(15)),
+        (Ruby, "1_000", Some(1000)),
+        (Php, "0x1A", Some(26)),
+        (Php, "0755", Some(493)),
+        (Php, "1_000", Some(1000)),
+        (Swift, "0x20", Some(32)),
+        (Swift, "0755", Some(755)),
+        (Swift, "2_048", Some(2048)),
+        (Go, "9223372036854775808", None),
+    ];
+
+    const FLOAT_CASES: &[(Language, &str, Option<f64>)] = &[
+        (Go, "1.5", Some(1.5)),
+        (Go, "1e3", Some(1000.0)),
+        (Go, "1_000.5", Some(1000.5)),
+        (Python, "2.5e-1", Some(0.25)),
+        (Python, "1.", Some(1.0)),
+        (Rust, "1.0f64", Some(1.0)),
+        (Rust, "2.5_f32", Some(2.5)),
+        (JavaScript, ".5", Some(0.5)),
+        (Java, "1.5f", Some(1.5)),
+        (Java, "2.0d", Some(2.0)),
+        (Kotlin, "1.5f", Some(1.5)),
+        (Scala, "1.5d", Some(1.5)),
+        (C, "0.25f", Some(0.25)),
+        (Cpp, "1'000.5L", Some(1000.5)),
+        (CSharp, "9.99m", Some(9.99)),
+        (CSharp, "1.5D", Some(1.5)),
+        (Ruby, "3.0", Some(3.0)),
+        (Php, "1_000.0", Some(1000.0)),
+        (Swift, "6.5", Some(6.5)),
+        (Go, "1.5i", None),
+    ];
+
+    const STRING_CASES: &[(Language, &str, &str)] = &[
+        (Go, r#""aes-256-gcm""#, "aes-256-gcm"),
+        (Go, r#""a\tb\n""#, "a\tb\n"),
+        (Go, r#""C:\\keys\\new""#, r"C:\keys\new"),
+        (Go, r"`raw\n`", r"raw\n"),
+        (Go, "'x'", "x"),
+        (Python, "'sha256'", "sha256"),
+        (Python, r#""say \"hi\"""#, r#"say "hi""#),
+        (Python, "b'salt'", "salt"),
+        (Python, r"r'\d+'", r"\d+"),
+        (Python, r"Rb'\x00'", r"\x00"),
+        (Python, "u'key'", "key"),
+        (Python, "\"\"\"doc\nstring\"\"\"", "doc\nstring"),
+        (Python, "'''md5'''", "md5"),
+        (Python, "f'{x}'", "f'{x}'"),
+        (Rust, r#""sha256""#, "sha256"),
+        (Rust, r#"b"salt""#, "salt"),
+        (Rust, r##"r#"say "hi""#"##, r#"say "hi""#),
+        (Rust, r#"r"\d""#, r"\d"),
+        (Rust, r#"br"\x00""#, r"\x00"),
+        (Rust, "'\"'", "\""),
+        (JavaScript, "'des-ede3'", "des-ede3"),
+        (JavaScript, r"`line\n`", "line\n"),
+        (TypeScript, r#""it\'s""#, "it's"),
+        (Java, r#""AES/CBC/PKCS5Padding""#, "AES/CBC/PKCS5Padding"),
+        (Java, "\"\"\"\n    AES\n      GCM\n    \"\"\"", "AES\n  GCM"),
+        (Kotlin, r#""AES\tGCM""#, "AES\tGCM"),
+        (Kotlin, r#""\$5""#, "$5"),
+        (Kotlin, r#""""raw\n""""#, r"raw\n"),
+        (Scala, r#""AES\tGCM""#, "AES\tGCM"),
+        (Scala, r#""""raw\n""""#, r"raw\n"),
+        (C, r#""EVP_sha1""#, "EVP_sha1"),
+        (C, r#"L"wide""#, "wide"),
+        (Cpp, r#"u8"utf8""#, "utf8"),
+        (Cpp, r#"R"(C:\keys)""#, r"C:\keys"),
+        (Cpp, r#"R"pem(a)"b)pem""#, r#"a)"b"#),
+        (CSharp, r#"@"C:\keys\""quoted""""#, r#"C:\keys\"quoted""#),
+        (CSharp, r#""""raw "quoted" text""""#, r#"raw "quoted" text"#),
+        (CSharp, r#""\"SHA256\"""#, r#""SHA256""#),
+        (Ruby, r#""aes\n""#, "aes\n"),
+        (Ruby, r"'aes\n'", r"aes\n"),
+        (Ruby, r"'it\'s'", "it's"),
+        (Ruby, ":gcm", "gcm"),
+        (Ruby, r#":"aes-256-gcm""#, "aes-256-gcm"),
+        (Php, r"'sha\256'", r"sha\256"),
+        (Php, r#""sha\t256""#, "sha\t256"),
+        (Swift, r#""SHA256""#, "SHA256"),
+        (Swift, r##"#"raw\n"#"##, r"raw\n"),
+        (
+            Swift,
+            "\"\"\"\n    line one\n      line two\n    \"\"\"",
+            "line one\n  line two",
+        ),
+        (Swift, "#\"\"\"\n  a\\n\n  \"\"\"#", "a\\n"),
+    ];
+
+    #[test]
+    fn test_int_literals() {
+        for (language, text, expected) in INT_CASES {
+            assert_eq!(
+                parse_int_literal(text, *language),
+                *expected,
+                "{language:?} {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_float_literals() {
+        for (language, text, expected) in FLOAT_CASES {
+            assert_eq!(
+                parse_float_literal(text, *language),
+                *expected,
+                "{language:?} {text}"
+            );
+        }
+        assert_eq!(integral(1e3), Some(1000));
+        assert_eq!(integral(0.5), None);
+        assert_eq!(integral(1e300), None);
+    }
+
+    #[test]
+    fn test_string_literals() {
+        for (language, text, expected) in STRING_CASES {
+            assert_eq!(
+                unquote_string(text, *language),
+                *expected,
+                "{language:?} {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unquoted_text_only_decodes_escapes() {
+        assert_eq!(unquote_string(r"a\\n", Go), r"a\n");
+        assert_eq!(unquote_string("plain", Python), "plain");
+        assert_eq!(unquote_string("\"", JavaScript), "\"");
+    }
+}
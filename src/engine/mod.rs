@@ -1,24 +1,41 @@
+pub mod config;
 pub mod context;
+pub mod differential;
+pub mod expr_cache;
 pub mod file_cache;
-pub mod lang_features;
+pub mod literals;
 pub mod node_types;
+pub mod observer;
 pub mod operators;
+pub mod profile;
+pub mod project_index;
 pub mod scope;
 pub mod sources;
 pub mod strategies;
+pub mod symbol_index;
 pub mod value;
 
+pub use config::ResolverConfig;
 pub use context::Context;
+pub use expr_cache::{ExpressionCache, ExpressionCacheStats};
 pub use file_cache::{CachedFileEntry, FileCache, FunctionInfo};
 pub use node_types::{Language, NodeCategory, NodeTypes};
+pub use observer::ResolveObserver;
 pub use operators::{BinaryOp, UnaryOp};
+pub use profile::{ResolutionProfile, StrategyStats};
+pub use project_index::ProjectIndex;
 pub use scope::{Scope, ScopeEntry};
 pub use sources::UnresolvedSource;
-pub use value::Value;
+pub use symbol_index::{PackageSymbols, Symbol, SymbolIndex, SymbolKind};
+pub use value::{Evidence, EvidenceKind, Span, Value};
 
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
 use strategies::BinaryStrategy;
 use strategies::CallStrategy;
 use strategies::CompositeStrategy;
+use strategies::ConditionalStrategy;
 use strategies::IdentifierStrategy;
 use strategies::IndexStrategy;
 use strategies::LiteralStrategy;
@@ -26,35 +43,79 @@ use strategies::SelectorStrategy;
 use strategies::UnaryStrategy;
 use tree_sitter::Node;
 
+use crate::error::ConfigError;
+
 const DEFAULT_MAX_DEPTH: usize = 50;
 
+/// Names of the built-in strategies, in default chain order.
+pub const STRATEGY_NAMES: &[&str] = &[
+    "literal",
+    "unary",
+    "binary",
+    "conditional",
+    "identifier",
+    "call",
+    "selector",
+    "index",
+    "composite",
+];
+
+pub fn strategy_by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name.to_lowercase().as_str() {
+        "literal" => Box::new(LiteralStrategy::new()),
+        "unary" => Box::new(UnaryStrategy::new()),
+        "binary" => Box::new(BinaryStrategy::new()),
+        "conditional" => Box::new(ConditionalStrategy::new()),
+        "identifier" => Box::new(IdentifierStrategy::new()),
+        "call" => Box::new(CallStrategy::new()),
+        "selector" => Box::new(SelectorStrategy::new()),
+        "index" => Box::new(IndexStrategy::new()),
+        "composite" => Box::new(CompositeStrategy::new()),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
 pub trait Strategy: Send + Sync {
     fn name(&self) -> &'static str;
     fn can_handle<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> bool;
     fn resolve<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value;
 }
 
+/// Runs the strategy chain on a node. Clones share the chain. Strategies
+/// resolve sub-expressions through [`Context::resolve`], which goes back
+/// to the resolver resolving on the context, so its strategies, limits,
+/// profile and observers apply at every level.
+#[derive(Clone)]
 pub struct Resolver {
-    strategies: Vec<Box<dyn Strategy>>,
+    strategies: Arc<[Box<dyn Strategy>]>,
     max_depth: usize,
+    max_call_depth: Option<usize>,
+    profile: Option<Arc<ResolutionProfile>>,
+    observers: Arc<[Arc<dyn ResolveObserver>]>,
 }
 
 impl Resolver {
     pub fn new() -> Self {
         Self {
-            strategies: Self::default_strategies(),
+            strategies: Self::default_strategies().into(),
             max_depth: DEFAULT_MAX_DEPTH,
+            max_call_depth: None,
+            profile: None,
+            observers: Arc::new([]),
         }
     }
 
-    /// Returns the default strategy chain in order of complexity.
-    /// Uncomment strategies as they are implemented.
+    /// Returns the default strategy chain in order of complexity: every
+    /// implemented strategy, each tried until one handles the node.
     fn default_strategies() -> Vec<Box<dyn Strategy>> {
         vec![
             // Order matters: simpler strategies first
             Box::new(LiteralStrategy::new()),
             Box::new(UnaryStrategy::new()),
             Box::new(BinaryStrategy::new()),
+            // Before identifier: Go branch assignments are reached by name
+            Box::new(ConditionalStrategy::new()),
             Box::new(IdentifierStrategy::new()),
             Box::new(CallStrategy::new()),
             Box::new(SelectorStrategy::new()),
@@ -68,12 +129,28 @@ impl Resolver {
     }
 
     pub fn resolve<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
-        self.resolve_with_depth(node, ctx, 0)
+        Rc::unwrap_or_clone(self.resolve_shared(node, ctx))
     }
 
-    fn resolve_with_depth<'a>(&self, node: &Node<'a>, ctx: &Context<'a>, depth: usize) -> Value {
+    /// Like [`resolve`](Self::resolve), but hands out the cached value
+    /// without copying it. Prefer this when the result is only inspected.
+    pub fn resolve_shared<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Rc<Value> {
+        let entered = ctx.enter_resolver(self);
+        let value = self.resolve_with_depth(node, ctx, 0);
+        if entered {
+            ctx.exit_resolver();
+        }
+        value
+    }
+
+    fn resolve_with_depth<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+        depth: usize,
+    ) -> Rc<Value> {
         if depth >= self.max_depth {
-            return Value::unextractable(UnresolvedSource::CycleDetected);
+            return Rc::new(Value::unextractable(UnresolvedSource::BudgetExceeded));
         }
 
         // Unwrap parenthesized expressions - they're just wrappers
@@ -83,29 +160,128 @@ impl Resolver {
             }
         }
 
-        if let Some(cached) = ctx.get_cached_value(node) {
+        // Swift parses parentheses as a tuple of one element
+        if node.kind() == "tuple_expression" && node.named_child_count() == 1 {
+            if let Some(inner) = node.child_by_field_name("value") {
+                return self.resolve_with_depth(&inner, ctx, depth);
+            }
+        }
+
+        // Swift `try` and `await` keep the value of the expression they mark
+        if matches!(node.kind(), "try_expression" | "await_expression") {
+            if let Some(expr) = node.child_by_field_name("expr") {
+                return self.resolve_with_depth(&expr, ctx, depth);
+            }
+        }
+
+        // C casts (`(const unsigned char *)SALT`) keep the value they convert
+        if node.kind() == "cast_expression" {
+            if let Some(value) = node.child_by_field_name("value") {
+                return self.resolve_with_depth(&value, ctx, depth);
+            }
+        }
+
+        // C++ casts (`reinterpret_cast<const uint8_t*>(SALT)`) likewise
+        if let Some(value) = cpp_cast_operand(node, ctx) {
+            return self.resolve_with_depth(&value, ctx, depth);
+        }
+
+        // C++ names qualified by their namespace (`crypto::kIterations`)
+        // resolve as the bare name
+        if node.kind() == "qualified_identifier" {
+            if let Some(name) = node.child_by_field_name("name") {
+                return self.resolve_with_depth(&name, ctx, depth);
+            }
+        }
+
+        if let Some(cached) = ctx.get_cached_shared(node) {
             return cached;
         }
 
+        let shared_key = ctx
+            .expression_cache()
+            .and_then(|_| expr_cache::cache_key(node, ctx));
+        if let Some(value) = shared_key
+            .as_deref()
+            .and_then(|key| ctx.get_shared_expression(key))
+        {
+            let value = Rc::new(Value {
+                spans: expr_cache::literal_spans(node, ctx),
+                ..value
+            });
+            ctx.cache_shared(node, Rc::clone(&value));
+            return value;
+        }
+
         if ctx.has_visited(node) {
-            return Value::unextractable(UnresolvedSource::CycleDetected);
+            return Rc::new(Value::unextractable(UnresolvedSource::CycleDetected));
         }
         ctx.mark_visited(node);
 
-        let result = self.try_strategies(node, ctx);
+        let mut result = self.try_strategies(node, ctx);
+        if result.is_resolved && result.spans.is_empty() {
+            result = result.with_span(ctx.span(node));
+        }
+        if let Some(key) = shared_key {
+            ctx.cache_shared_expression(key, result.clone());
+        }
 
-        ctx.cache_value(node, result.clone());
+        let result = Rc::new(result);
+        ctx.cache_shared(node, Rc::clone(&result));
         ctx.unmark_visited(node);
         result
     }
 
     fn try_strategies<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
-        for strategy in &self.strategies {
+        for strategy in self.strategies.iter() {
             if strategy.can_handle(node, ctx) {
-                return strategy.resolve(node, ctx);
+                for observer in self.observers.iter() {
+                    observer.on_strategy_attempt(strategy.name(), node, ctx);
+                }
+                let start =
+                    (self.profile.is_some() || !self.observers.is_empty()).then(Instant::now);
+                let result = if strategy.name() == "call" {
+                    self.resolve_call(strategy.as_ref(), node, ctx)
+                } else {
+                    strategy.resolve(node, ctx)
+                };
+                let elapsed = start.map(|start| start.elapsed()).unwrap_or_default();
+                if let Some(profile) = &self.profile {
+                    profile.record(strategy.name(), elapsed);
+                }
+                for observer in self.observers.iter() {
+                    if result.is_resolved {
+                        observer.on_value_resolved(strategy.name(), node, ctx, &result, elapsed);
+                    } else {
+                        observer.on_unresolved(Some(strategy.name()), node, ctx, &result);
+                    }
+                }
+                return result;
             }
         }
-        Value::unextractable(UnresolvedSource::NotImplemented)
+        let result = Value::unextractable(UnresolvedSource::NotImplemented);
+        for observer in self.observers.iter() {
+            observer.on_unresolved(None, node, ctx, &result);
+        }
+        result
+    }
+
+    fn resolve_call<'a>(
+        &self,
+        strategy: &dyn Strategy,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Value {
+        if let Some(max) = self.max_call_depth {
+            if ctx.call_depth() >= max {
+                return Value::unextractable(UnresolvedSource::BudgetExceeded);
+            }
+        }
+
+        ctx.enter_call();
+        let result = strategy.resolve(node, ctx);
+        ctx.exit_call();
+        result
     }
 
     pub fn strategy_count(&self) -> usize {
@@ -115,6 +291,22 @@ impl Resolver {
     pub fn strategy_names(&self) -> Vec<&'static str> {
         self.strategies.iter().map(|s| s.name()).collect()
     }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn max_call_depth(&self) -> Option<usize> {
+        self.max_call_depth
+    }
+
+    pub fn profile(&self) -> Option<&Arc<ResolutionProfile>> {
+        self.profile.as_ref()
+    }
+
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
 }
 
 impl Default for Resolver {
@@ -126,7 +318,11 @@ impl Default for Resolver {
 pub struct ResolverBuilder {
     strategies: Vec<Box<dyn Strategy>>,
     max_depth: usize,
+    max_call_depth: Option<usize>,
     include_defaults: bool,
+    disabled: Vec<String>,
+    profile: Option<Arc<ResolutionProfile>>,
+    observers: Vec<Arc<dyn ResolveObserver>>,
 }
 
 impl ResolverBuilder {
@@ -134,10 +330,48 @@ impl ResolverBuilder {
         Self {
             strategies: Vec::new(),
             max_depth: DEFAULT_MAX_DEPTH,
+            max_call_depth: None,
             include_defaults: true,
+            disabled: Vec::new(),
+            profile: None,
+            observers: Vec::new(),
         }
     }
 
+    /// Builds a resolver from user configuration, rejecting unknown strategy names.
+    pub fn from_config(config: &ResolverConfig) -> Result<Self, ConfigError> {
+        for name in config
+            .strategies
+            .iter()
+            .flatten()
+            .chain(config.disabled.iter())
+        {
+            if strategy_by_name(name).is_none() {
+                return Err(ConfigError::unknown_strategy(name, STRATEGY_NAMES));
+            }
+        }
+
+        let mut builder = Self::new();
+        if let Some(order) = &config.strategies {
+            builder = builder.without_defaults();
+            for name in order {
+                if let Some(strategy) = strategy_by_name(name) {
+                    builder.strategies.push(strategy);
+                }
+            }
+        }
+        for name in &config.disabled {
+            builder = builder.disable_strategy(name);
+        }
+        if let Some(depth) = config.max_depth {
+            builder = builder.with_max_depth(depth);
+        }
+        if let Some(depth) = config.call_depth {
+            builder = builder.with_call_depth(depth);
+        }
+        Ok(builder)
+    }
+
     pub fn with_strategy<S: Strategy + 'static>(mut self, strategy: S) -> Self {
         self.strategies.push(Box::new(strategy));
         self
@@ -148,19 +382,49 @@ impl ResolverBuilder {
         self
     }
 
+    /// Caps how many function calls deep the Call strategy will follow.
+    pub fn with_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = Some(depth);
+        self
+    }
+
+    /// Records per-strategy invocation counts and elapsed time into `profile`.
+    pub fn with_profile(mut self, profile: Arc<ResolutionProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Reports each strategy run to `observer`, after any added before it.
+    pub fn with_observer(mut self, observer: Arc<dyn ResolveObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
     pub fn without_defaults(mut self) -> Self {
         self.include_defaults = false;
         self
     }
 
+    pub fn disable_strategy(mut self, name: &str) -> Self {
+        self.disabled.push(name.to_lowercase());
+        self
+    }
+
     pub fn build(mut self) -> Resolver {
         if self.include_defaults && self.strategies.is_empty() {
             self.strategies = Resolver::default_strategies();
         }
 
+        let disabled = self.disabled;
+        self.strategies
+            .retain(|s| !disabled.iter().any(|d| d == s.name()));
+
         Resolver {
-            strategies: self.strategies,
+            strategies: self.strategies.into(),
             max_depth: self.max_depth,
+            max_call_depth: self.max_call_depth,
+            profile: self.profile,
+            observers: self.observers.into(),
         }
     }
 }
@@ -171,6 +435,25 @@ impl Default for ResolverBuilder {
     }
 }
 
+/// The operand of a C++ named cast such as `static_cast<int>(x)`.
+fn cpp_cast_operand<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<Node<'a>> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = node.child_by_field_name("function")?;
+    if function.kind() != "template_function" {
+        return None;
+    }
+    let name = ctx.get_node_text(&function.child_by_field_name("name")?);
+    if !matches!(
+        name.as_str(),
+        "static_cast" | "reinterpret_cast" | "const_cast"
+    ) {
+        return None;
+    }
+    node.child_by_field_name("arguments")?.named_child(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,13 +496,14 @@ mod tests {
     #[test]
     fn test_resolver_default() {
         let resolver = Resolver::new();
-        assert_eq!(resolver.strategy_count(), 8);
+        assert_eq!(resolver.strategy_count(), 9);
         assert_eq!(
             resolver.strategy_names(),
             vec![
                 "literal",
                 "unary",
                 "binary",
+                "conditional",
                 "identifier",
                 "call",
                 "selector",
@@ -232,13 +516,14 @@ mod tests {
     #[test]
     fn test_resolver_builder_defaults() {
         let resolver = Resolver::builder().build();
-        assert_eq!(resolver.strategy_count(), 8);
+        assert_eq!(resolver.strategy_count(), 9);
         assert_eq!(
             resolver.strategy_names(),
             vec![
                 "literal",
                 "unary",
                 "binary",
+                "conditional",
                 "identifier",
                 "call",
                 "selector",
@@ -260,6 +545,182 @@ mod tests {
         assert_eq!(resolver.max_depth, 10);
     }
 
+    #[test]
+    fn test_resolver_builder_disable_strategy() {
+        let resolver = Resolver::builder()
+            .disable_strategy("call")
+            .disable_strategy("Composite")
+            .build();
+        assert_eq!(
+            resolver.strategy_names(),
+            vec![
+                "literal",
+                "unary",
+                "binary",
+                "conditional",
+                "identifier",
+                "selector",
+                "index"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolver_builder_from_config_order() {
+        let config = ResolverConfig {
+            strategies: Some(vec!["identifier".to_string(), "literal".to_string()]),
+            call_depth: Some(1),
+            max_depth: Some(20),
+            ..ResolverConfig::default()
+        };
+        let resolver = ResolverBuilder::from_config(&config).unwrap().build();
+        assert_eq!(resolver.strategy_names(), vec!["identifier", "literal"]);
+        assert_eq!(resolver.max_depth(), 20);
+        assert_eq!(resolver.max_call_depth(), Some(1));
+    }
+
+    #[test]
+    fn test_resolver_builder_from_config_unknown_strategy() {
+        let config = ResolverConfig {
+            disabled: vec!["interprocedural".to_string()],
+            ..ResolverConfig::default()
+        };
+        assert!(ResolverBuilder::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_resolver_call_depth_zero_skips_calls() {
+        let source = "package main\nfunc f() int { return 5 }\nvar x = f()";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let node = find_first_node_of_kind(tree.root_node(), "call_expression").unwrap();
+
+        let resolver = Resolver::new();
+        assert_eq!(resolver.resolve(&node, &ctx).int_values, vec![5]);
+
+        let ctx = create_context(&tree, source.as_bytes());
+        let limited = Resolver::builder().with_call_depth(0).build();
+        let value = limited.resolve(&node, &ctx);
+        assert!(!value.is_resolved);
+        assert_eq!(ctx.call_depth(), 0);
+    }
+
+    #[test]
+    fn test_resolver_records_profile() {
+        let source = "package main\nconst x = 1 + 2";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let profile = ResolutionProfile::shared();
+        let resolver = Resolver::builder()
+            .with_profile(Arc::clone(&profile))
+            .build();
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+        resolver.resolve(&node, &ctx);
+
+        assert_eq!(profile.get("binary").unwrap().invocations, 1);
+        assert!(profile.get("call").is_none());
+    }
+
+    #[test]
+    fn test_resolver_notifies_observers() {
+        #[derive(Default)]
+        struct Events(std::sync::Mutex<Vec<String>>);
+
+        impl ResolveObserver for Events {
+            fn on_strategy_attempt(&self, strategy: &'static str, node: &Node, _ctx: &Context) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("attempt {strategy} {}", node.kind()));
+            }
+
+            fn on_value_resolved(
+                &self,
+                strategy: &'static str,
+                _node: &Node,
+                _ctx: &Context,
+                value: &Value,
+                _elapsed: std::time::Duration,
+            ) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("resolved {strategy} {:?}", value.int_values));
+            }
+
+            fn on_unresolved(
+                &self,
+                strategy: Option<&'static str>,
+                node: &Node,
+                _ctx: &Context,
+                _value: &Value,
+            ) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("unresolved {strategy:?} {}", node.kind()));
+            }
+        }
+
+        let source = "package main\nconst x = 1 + 2\nvar y = os.Args";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let events = Arc::new(Events::default());
+        let resolver = Resolver::builder()
+            .with_observer(Arc::clone(&events) as Arc<dyn ResolveObserver>)
+            .build();
+        assert_eq!(resolver.observer_count(), 1);
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+        resolver.resolve(&node, &ctx);
+        // Cached: not reported again
+        resolver.resolve(&node, &ctx);
+        let node = find_first_node_of_kind(tree.root_node(), "var_declaration").unwrap();
+        resolver.resolve(&node, &ctx);
+
+        let events = events.0.lock().unwrap();
+        assert_eq!(events.first().unwrap(), "attempt binary binary_expression");
+        assert!(events.contains(&"resolved binary [3]".to_string()));
+        assert_eq!(events.last().unwrap(), "unresolved None var_declaration");
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| e.starts_with("attempt binary"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_resolve_shared_reuses_cached_value() {
+        let source = "package main\nconst x = 42";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let resolver = Resolver::new();
+
+        let node = find_first_node_of_kind(tree.root_node(), "int_literal").unwrap();
+        let first = resolver.resolve_shared(&node, &ctx);
+        let second = resolver.resolve_shared(&node, &ctx);
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(second.int_values, vec![42]);
+    }
+
+    #[test]
+    fn test_nested_resolution_uses_resolver_of_context() {
+        let source = "package main\nconst base = 1000\nvar x = base * 2";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let resolver = Resolver::builder().disable_strategy("identifier").build();
+
+        let node = find_first_node_of_kind(tree.root_node(), "binary_expression").unwrap();
+        assert!(!resolver.resolve(&node, &ctx).is_resolved);
+
+        let ctx = create_context(&tree, source.as_bytes());
+        assert_eq!(Resolver::new().resolve(&node, &ctx).int_values, vec![2000]);
+    }
+
     #[test]
     fn test_resolver_resolves_literal() {
         let source = "package main\nconst x = 10000";
@@ -337,7 +798,8 @@ mod tests {
         let node = find_first_node_of_kind(tree.root_node(), "int_literal").unwrap();
         let value = resolver.resolve(&node, &ctx);
 
-        // With max_depth=0, should immediately return CycleDetected
+        // With max_depth=0, should immediately run out of budget
         assert!(!value.is_resolved);
+        assert_eq!(value.source, "budget_exceeded");
     }
 }
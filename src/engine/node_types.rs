@@ -10,6 +10,12 @@ pub enum Language {
     C,
     Cpp,
     Java,
+    CSharp,
+    Ruby,
+    Php,
+    Swift,
+    Kotlin,
+    Scala,
 }
 
 impl Language {
@@ -23,6 +29,12 @@ impl Language {
             "c" => Some(Self::C),
             "cpp" | "c++" => Some(Self::Cpp),
             "java" => Some(Self::Java),
+            "csharp" | "c_sharp" | "c#" | "cs" => Some(Self::CSharp),
+            "ruby" | "rb" => Some(Self::Ruby),
+            "php" => Some(Self::Php),
+            "swift" => Some(Self::Swift),
+            "kotlin" | "kt" => Some(Self::Kotlin),
+            "scala" => Some(Self::Scala),
             _ => None,
         }
     }
@@ -37,6 +49,12 @@ impl Language {
             Self::C => "c",
             Self::Cpp => "cpp",
             Self::Java => "java",
+            Self::CSharp => "c_sharp",
+            Self::Ruby => "ruby",
+            Self::Php => "php",
+            Self::Swift => "swift",
+            Self::Kotlin => "kotlin",
+            Self::Scala => "scala",
         }
     }
 }
@@ -130,6 +148,19 @@ impl NodeTypes {
             ]
             .into_iter()
             .collect(),
+            Language::CSharp => ["integer_literal"].into_iter().collect(),
+            Language::Ruby => ["integer"].into_iter().collect(),
+            Language::Php => ["integer"].into_iter().collect(),
+            Language::Swift => [
+                "integer_literal",
+                "hex_literal",
+                "oct_literal",
+                "bin_literal",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Kotlin => ["number_literal"].into_iter().collect(),
+            Language::Scala => ["integer_literal"].into_iter().collect(),
         }
     }
 
@@ -146,6 +177,12 @@ impl NodeTypes {
             ]
             .into_iter()
             .collect(),
+            Language::CSharp => ["real_literal"].into_iter().collect(),
+            Language::Ruby => ["float"].into_iter().collect(),
+            Language::Php => ["float"].into_iter().collect(),
+            Language::Swift => ["real_literal"].into_iter().collect(),
+            Language::Kotlin => ["float_literal"].into_iter().collect(),
+            Language::Scala => ["floating_point_literal"].into_iter().collect(),
         }
     }
 
@@ -165,6 +202,39 @@ impl NodeTypes {
             Language::Java => ["string_literal", "character_literal"]
                 .into_iter()
                 .collect(),
+            Language::CSharp => [
+                "string_literal",
+                "verbatim_string_literal",
+                "raw_string_literal",
+                "character_literal",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Ruby => ["string", "simple_symbol", "delimited_symbol"]
+                .into_iter()
+                .collect(),
+            Language::Php => ["string", "encapsed_string"].into_iter().collect(),
+            Language::Swift => [
+                "line_string_literal",
+                "multi_line_string_literal",
+                "raw_string_literal",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Kotlin => [
+                "string_literal",
+                "multiline_string_literal",
+                "character_literal",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Scala => [
+                "string",
+                "interpolated_string_expression",
+                "character_literal",
+            ]
+            .into_iter()
+            .collect(),
         }
     }
 
@@ -176,6 +246,12 @@ impl NodeTypes {
             Language::JavaScript | Language::TypeScript => ["true", "false"].into_iter().collect(),
             Language::C | Language::Cpp => ["true", "false"].into_iter().collect(),
             Language::Java => ["true", "false"].into_iter().collect(),
+            Language::CSharp => ["boolean_literal"].into_iter().collect(),
+            Language::Ruby => ["true", "false"].into_iter().collect(),
+            Language::Php => ["boolean"].into_iter().collect(),
+            Language::Swift => ["boolean_literal"].into_iter().collect(),
+            Language::Kotlin => HashSet::new(), // `true` parses as an identifier
+            Language::Scala => ["boolean_literal"].into_iter().collect(),
         }
     }
 
@@ -189,6 +265,12 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["null", "nullptr"].into_iter().collect(),
             Language::Java => ["null_literal"].into_iter().collect(),
+            Language::CSharp => ["null_literal"].into_iter().collect(),
+            Language::Ruby => ["nil"].into_iter().collect(),
+            Language::Php => ["null"].into_iter().collect(),
+            Language::Swift => ["nil"].into_iter().collect(),
+            Language::Kotlin => HashSet::new(), // `null` parses as an identifier
+            Language::Scala => ["null_literal"].into_iter().collect(),
         }
     }
 
@@ -198,8 +280,15 @@ impl NodeTypes {
             Language::Python => ["identifier"].into_iter().collect(),
             Language::Rust => ["identifier"].into_iter().collect(),
             Language::JavaScript | Language::TypeScript => ["identifier"].into_iter().collect(),
-            Language::C | Language::Cpp => ["identifier"].into_iter().collect(),
+            Language::C => ["identifier"].into_iter().collect(),
+            Language::Cpp => ["identifier", "qualified_identifier"].into_iter().collect(),
             Language::Java => ["identifier"].into_iter().collect(),
+            Language::CSharp => ["identifier"].into_iter().collect(),
+            Language::Ruby => ["identifier", "constant"].into_iter().collect(),
+            Language::Php => ["variable_name", "name"].into_iter().collect(),
+            Language::Swift => ["simple_identifier"].into_iter().collect(),
+            Language::Kotlin => ["identifier"].into_iter().collect(),
+            Language::Scala => ["identifier"].into_iter().collect(),
         }
     }
 
@@ -215,6 +304,23 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["binary_expression"].into_iter().collect(),
             Language::Java => ["binary_expression"].into_iter().collect(),
+            Language::CSharp => ["binary_expression"].into_iter().collect(),
+            Language::Ruby => ["binary"].into_iter().collect(),
+            Language::Php => ["binary_expression"].into_iter().collect(),
+            Language::Swift => [
+                "additive_expression",
+                "multiplicative_expression",
+                "comparison_expression",
+                "equality_expression",
+                "conjunction_expression",
+                "disjunction_expression",
+                "bitwise_operation",
+                "nil_coalescing_expression",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Kotlin => ["binary_expression"].into_iter().collect(),
+            Language::Scala => ["infix_expression"].into_iter().collect(),
         }
     }
 
@@ -234,6 +340,12 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["unary_expression"].into_iter().collect(),
             Language::Java => ["unary_expression"].into_iter().collect(),
+            Language::CSharp => ["prefix_unary_expression"].into_iter().collect(),
+            Language::Ruby => ["unary"].into_iter().collect(),
+            Language::Php => ["unary_op_expression"].into_iter().collect(),
+            Language::Swift => ["prefix_expression"].into_iter().collect(),
+            Language::Kotlin => ["unary_expression"].into_iter().collect(),
+            Language::Scala => ["prefix_expression"].into_iter().collect(),
         }
     }
 
@@ -247,6 +359,22 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["call_expression"].into_iter().collect(),
             Language::Java => ["method_invocation"].into_iter().collect(),
+            Language::CSharp => ["invocation_expression", "object_creation_expression"]
+                .into_iter()
+                .collect(),
+            Language::Ruby => ["call"].into_iter().collect(),
+            Language::Php => [
+                "function_call_expression",
+                "member_call_expression",
+                "scoped_call_expression",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Swift => ["call_expression"].into_iter().collect(),
+            Language::Kotlin => ["call_expression"].into_iter().collect(),
+            Language::Scala => ["call_expression", "instance_expression"]
+                .into_iter()
+                .collect(),
         }
     }
 
@@ -260,6 +388,17 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["field_expression"].into_iter().collect(),
             Language::Java => ["field_access"].into_iter().collect(),
+            Language::CSharp => ["member_access_expression"].into_iter().collect(),
+            Language::Ruby => ["scope_resolution"].into_iter().collect(),
+            Language::Php => [
+                "class_constant_access_expression",
+                "member_access_expression",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Swift => ["navigation_expression"].into_iter().collect(),
+            Language::Kotlin => ["navigation_expression"].into_iter().collect(),
+            Language::Scala => ["field_expression"].into_iter().collect(),
         }
     }
 
@@ -273,6 +412,12 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["subscript_expression"].into_iter().collect(),
             Language::Java => ["array_access"].into_iter().collect(),
+            Language::CSharp => ["element_access_expression"].into_iter().collect(),
+            Language::Ruby => ["element_reference"].into_iter().collect(),
+            Language::Php => ["subscript_expression"].into_iter().collect(),
+            Language::Swift => HashSet::new(), // subscripts parse as calls
+            Language::Kotlin => ["index_expression"].into_iter().collect(),
+            Language::Scala => HashSet::new(), // indexing parses as a call, `arr(0)`
         }
     }
 
@@ -286,6 +431,21 @@ impl NodeTypes {
             Language::JavaScript | Language::TypeScript => ["array"].into_iter().collect(),
             Language::C | Language::Cpp => ["initializer_list"].into_iter().collect(),
             Language::Java => ["array_initializer"].into_iter().collect(),
+            Language::CSharp => [
+                "initializer_expression",
+                "array_creation_expression",
+                "implicit_array_creation_expression",
+                "collection_expression",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Ruby => ["array", "string_array", "symbol_array"]
+                .into_iter()
+                .collect(),
+            Language::Php => ["array_creation_expression"].into_iter().collect(),
+            Language::Swift => ["array_literal"].into_iter().collect(),
+            Language::Kotlin => ["collection_literal"].into_iter().collect(),
+            Language::Scala => HashSet::new(), // `Array(...)` and `List(...)` are calls
         }
     }
 
@@ -297,6 +457,14 @@ impl NodeTypes {
             Language::JavaScript | Language::TypeScript => ["object"].into_iter().collect(),
             Language::C | Language::Cpp => ["initializer_list"].into_iter().collect(),
             Language::Java => ["object_creation_expression"].into_iter().collect(),
+            Language::CSharp => ["anonymous_object_creation_expression"]
+                .into_iter()
+                .collect(),
+            Language::Ruby => ["hash"].into_iter().collect(),
+            Language::Php => HashSet::new(), // PHP arrays are both lists and maps
+            Language::Swift => ["dictionary_literal"].into_iter().collect(),
+            Language::Kotlin => HashSet::new(), // maps are built by `mapOf(...)`
+            Language::Scala => HashSet::new(),  // maps are built by `Map(...)`
         }
     }
 
@@ -323,6 +491,22 @@ impl NodeTypes {
             Language::Java => ["method_declaration", "constructor_declaration"]
                 .into_iter()
                 .collect(),
+            Language::CSharp => [
+                "method_declaration",
+                "constructor_declaration",
+                "local_function_statement",
+            ]
+            .into_iter()
+            .collect(),
+            Language::Ruby => ["method", "singleton_method"].into_iter().collect(),
+            Language::Php => ["function_definition", "method_declaration"]
+                .into_iter()
+                .collect(),
+            Language::Swift => ["function_declaration", "init_declaration"]
+                .into_iter()
+                .collect(),
+            Language::Kotlin => ["function_declaration"].into_iter().collect(),
+            Language::Scala => ["function_definition"].into_iter().collect(),
         }
     }
 
@@ -342,6 +526,14 @@ impl NodeTypes {
             Language::Java => ["local_variable_declaration", "field_declaration"]
                 .into_iter()
                 .collect(),
+            Language::CSharp => ["local_declaration_statement", "field_declaration"]
+                .into_iter()
+                .collect(),
+            Language::Ruby => ["assignment"].into_iter().collect(),
+            Language::Php => ["assignment_expression"].into_iter().collect(),
+            Language::Swift => ["property_declaration"].into_iter().collect(),
+            Language::Kotlin => ["property_declaration"].into_iter().collect(),
+            Language::Scala => ["val_definition", "var_definition"].into_iter().collect(),
         }
     }
 
@@ -355,6 +547,14 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["declaration"].into_iter().collect(),
             Language::Java => ["field_declaration"].into_iter().collect(),
+            Language::CSharp => ["field_declaration", "local_declaration_statement"]
+                .into_iter()
+                .collect(),
+            Language::Ruby => ["assignment"].into_iter().collect(),
+            Language::Php => ["const_declaration"].into_iter().collect(),
+            Language::Swift => ["property_declaration"].into_iter().collect(),
+            Language::Kotlin => ["property_declaration"].into_iter().collect(),
+            Language::Scala => ["val_definition"].into_iter().collect(),
         }
     }
 
@@ -368,6 +568,14 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["assignment_expression"].into_iter().collect(),
             Language::Java => ["assignment_expression"].into_iter().collect(),
+            Language::CSharp => ["assignment_expression"].into_iter().collect(),
+            Language::Ruby => ["assignment", "operator_assignment"].into_iter().collect(),
+            Language::Php => ["assignment_expression", "augmented_assignment_expression"]
+                .into_iter()
+                .collect(),
+            Language::Swift => ["assignment"].into_iter().collect(),
+            Language::Kotlin => ["assignment"].into_iter().collect(),
+            Language::Scala => ["assignment_expression"].into_iter().collect(),
         }
     }
 
@@ -381,6 +589,12 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["compound_statement"].into_iter().collect(),
             Language::Java => ["block"].into_iter().collect(),
+            Language::CSharp => ["block"].into_iter().collect(),
+            Language::Ruby => ["body_statement"].into_iter().collect(),
+            Language::Php => ["compound_statement"].into_iter().collect(),
+            Language::Swift => ["statements"].into_iter().collect(),
+            Language::Kotlin => ["block"].into_iter().collect(),
+            Language::Scala => ["block", "template_body"].into_iter().collect(),
         }
     }
 
@@ -392,6 +606,14 @@ impl NodeTypes {
             Language::JavaScript | Language::TypeScript => ["if_statement"].into_iter().collect(),
             Language::C | Language::Cpp => ["if_statement"].into_iter().collect(),
             Language::Java => ["if_statement"].into_iter().collect(),
+            Language::CSharp => ["if_statement"].into_iter().collect(),
+            Language::Ruby => ["if", "unless", "if_modifier", "unless_modifier"]
+                .into_iter()
+                .collect(),
+            Language::Php => ["if_statement"].into_iter().collect(),
+            Language::Swift => ["if_statement", "guard_statement"].into_iter().collect(),
+            Language::Kotlin => ["if_expression"].into_iter().collect(),
+            Language::Scala => ["if_expression"].into_iter().collect(),
         }
     }
 
@@ -409,6 +631,16 @@ impl NodeTypes {
             Language::Java => ["switch_expression", "switch_statement"]
                 .into_iter()
                 .collect(),
+            Language::CSharp => ["switch_expression", "switch_statement"]
+                .into_iter()
+                .collect(),
+            Language::Ruby => ["case"].into_iter().collect(),
+            Language::Php => ["switch_statement", "match_expression"]
+                .into_iter()
+                .collect(),
+            Language::Swift => ["switch_statement"].into_iter().collect(),
+            Language::Kotlin => ["when_expression"].into_iter().collect(),
+            Language::Scala => ["match_expression"].into_iter().collect(),
         }
     }
 
@@ -422,6 +654,12 @@ impl NodeTypes {
             }
             Language::C | Language::Cpp => ["return_statement"].into_iter().collect(),
             Language::Java => ["return_statement"].into_iter().collect(),
+            Language::CSharp => ["return_statement"].into_iter().collect(),
+            Language::Ruby => ["return"].into_iter().collect(),
+            Language::Php => ["return_statement"].into_iter().collect(),
+            Language::Swift => ["control_transfer_statement"].into_iter().collect(),
+            Language::Kotlin => ["return_expression"].into_iter().collect(),
+            Language::Scala => ["return_expression"].into_iter().collect(),
         }
     }
 }
@@ -515,5 +753,9 @@ mod tests {
 
         let java = NodeTypes::new(Language::Java);
         assert!(java.is_category("method_invocation", NodeCategory::CallExpression));
+
+        let csharp = NodeTypes::new(Language::CSharp);
+        assert!(csharp.is_category("invocation_expression", NodeCategory::CallExpression));
+        assert!(csharp.is_category("object_creation_expression", NodeCategory::CallExpression));
     }
 }
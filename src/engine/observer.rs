@@ -0,0 +1,44 @@
+//! Callbacks into the resolution loop, for library users collecting their
+//! own metrics, logging, or driving progress display without forking
+//! [`Resolver`](super::Resolver).
+//!
+//! Observers are told about each node a strategy is run on. Values served
+//! from the resolver's caches, and wrappers it looks through (parentheses,
+//! casts), are not reported again. Sub-expressions strategies resolve on
+//! the way are reported too, as they go through the same resolver.
+
+use std::time::Duration;
+
+use tree_sitter::Node;
+
+use super::{Context, Value};
+
+/// Every method does nothing by default, so an observer implements only
+/// the events it needs. Observers are shared across scanning threads.
+pub trait ResolveObserver: Send + Sync {
+    /// `strategy` is about to resolve `node`.
+    fn on_strategy_attempt(&self, _strategy: &'static str, _node: &Node, _ctx: &Context) {}
+
+    /// `strategy` resolved `node` to `value` in `elapsed`, nested
+    /// resolution included.
+    fn on_value_resolved(
+        &self,
+        _strategy: &'static str,
+        _node: &Node,
+        _ctx: &Context,
+        _value: &Value,
+        _elapsed: Duration,
+    ) {
+    }
+
+    /// `node` was left unresolved, as `value` tells, by `strategy`, or with
+    /// `None` when no strategy handles its kind of node.
+    fn on_unresolved(
+        &self,
+        _strategy: Option<&'static str>,
+        _node: &Node,
+        _ctx: &Context,
+        _value: &Value,
+    ) {
+    }
+}
@@ -0,0 +1,136 @@
+//! Per-strategy resolution timing (`--profile-resolution`).
+//!
+//! Times are inclusive: a strategy that recurses into the resolver is charged
+//! for the nested work as well.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StrategyStats {
+    pub invocations: u64,
+    pub elapsed: Duration,
+}
+
+impl StrategyStats {
+    pub fn average(&self) -> Duration {
+        if self.invocations == 0 {
+            Duration::ZERO
+        } else {
+            self.elapsed / self.invocations as u32
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ResolutionProfile {
+    stats: Mutex<HashMap<&'static str, StrategyStats>>,
+}
+
+impl ResolutionProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    pub fn record(&self, strategy: &'static str, elapsed: Duration) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = stats.entry(strategy).or_default();
+        entry.invocations += 1;
+        entry.elapsed += elapsed;
+    }
+
+    pub fn get(&self, strategy: &str) -> Option<StrategyStats> {
+        self.stats.lock().unwrap().get(strategy).copied()
+    }
+
+    /// Returns stats sorted by total elapsed time, slowest first.
+    pub fn snapshot(&self) -> Vec<(&'static str, StrategyStats)> {
+        let mut entries: Vec<_> = self
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, stats)| (*name, *stats))
+            .collect();
+        entries.sort_by(|a, b| b.1.elapsed.cmp(&a.1.elapsed).then(a.0.cmp(b.0)));
+        entries
+    }
+
+    pub fn total_invocations(&self) -> u64 {
+        self.stats
+            .lock()
+            .unwrap()
+            .values()
+            .map(|s| s.invocations)
+            .sum()
+    }
+
+    /// Renders a plain-text table for the end-of-run summary.
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "{:<12} {:>12} {:>14} {:>12}\n",
+            "strategy", "invocations", "total", "avg"
+        );
+        for (name, stats) in self.snapshot() {
+            out.push_str(&format!(
+                "{:<12} {:>12} {:>14} {:>12}\n",
+                name,
+                stats.invocations,
+                format!("{:.3}ms", stats.elapsed.as_secs_f64() * 1000.0),
+                format!("{:.1}us", stats.average().as_secs_f64() * 1_000_000.0),
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates() {
+        let profile = ResolutionProfile::new();
+        profile.record("literal", Duration::from_micros(10));
+        profile.record("literal", Duration::from_micros(30));
+        profile.record("call", Duration::from_micros(100));
+
+        let literal = profile.get("literal").unwrap();
+        assert_eq!(literal.invocations, 2);
+        assert_eq!(literal.elapsed, Duration::from_micros(40));
+        assert_eq!(literal.average(), Duration::from_micros(20));
+        assert_eq!(profile.total_invocations(), 3);
+        assert!(profile.get("index").is_none());
+    }
+
+    #[test]
+    fn test_snapshot_sorted_by_elapsed() {
+        let profile = ResolutionProfile::new();
+        profile.record("literal", Duration::from_micros(5));
+        profile.record("call", Duration::from_micros(50));
+
+        let names: Vec<_> = profile.snapshot().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["call", "literal"]);
+    }
+
+    #[test]
+    fn test_render_lists_strategies() {
+        let profile = ResolutionProfile::new();
+        profile.record("binary", Duration::from_millis(2));
+
+        let rendered = profile.render();
+        assert!(rendered.starts_with("strategy"));
+        assert!(rendered.contains("binary"));
+        assert!(rendered.contains("2.000ms"));
+    }
+
+    #[test]
+    fn test_average_of_empty_stats() {
+        assert_eq!(StrategyStats::default().average(), Duration::ZERO);
+    }
+}
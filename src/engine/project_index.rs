@@ -0,0 +1,247 @@
+//! Project-wide index of exported constants by import path, for values
+//! declared in another package than the one using them
+//! (`crypto.DefaultIterations`).
+//!
+//! The index is filled in a first pass over a root's files, before any of
+//! them is scanned, so the scan that follows finds constants whichever file
+//! declares them. Go packages are keyed by their module path and directory,
+//! Python modules by their dotted path from the root. Like
+//! [`SymbolIndex`](super::SymbolIndex), it is `Send + Sync` and shared
+//! between workers behind an `Arc`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "discovery")]
+use crate::discovery::languages::go::workspace::module_path;
+
+use super::file_cache::CachedFileEntry;
+use super::node_types::Language;
+use super::symbol_index::{file_symbols, language_for_path};
+use super::{SymbolKind, Value};
+
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    /// The exported symbols of each indexed file.
+    files: RwLock<HashMap<String, CachedFileEntry>>,
+    /// The files of each package or module, by import path.
+    packages: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl ProjectIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    /// Indexes the exported constants and functions of `files` under
+    /// `root`, returning how many files were indexed. Files of languages
+    /// without import paths here, and files already indexed, are skipped.
+    pub fn index_files(&self, root: &Path, files: &[PathBuf]) -> usize {
+        let mut go_packages: HashMap<PathBuf, Option<String>> = HashMap::new();
+        let mut indexed = 0;
+        for path in files {
+            let file_path = path.to_string_lossy().to_string();
+            if self.files.read().unwrap().contains_key(&file_path) {
+                continue;
+            }
+            let Some(language) = language_for_path(path) else {
+                continue;
+            };
+            let Some(package_dir) = path.parent() else {
+                continue;
+            };
+            let import_path = match language {
+                Language::Go => go_packages
+                    .entry(package_dir.to_path_buf())
+                    .or_insert_with(|| go_import_path(package_dir))
+                    .clone(),
+                Language::Python => python_module(root, path),
+                _ => None,
+            };
+            let Some(import_path) = import_path else {
+                continue;
+            };
+
+            let mut entry = CachedFileEntry {
+                constants: HashMap::new(),
+                functions: HashMap::new(),
+            };
+            for symbol in file_symbols(path, language, package_dir) {
+                if !is_exported(&symbol.name, language) {
+                    continue;
+                }
+                match symbol.kind {
+                    SymbolKind::Constant => {
+                        if let Some(value) = symbol.value {
+                            entry.constants.insert(symbol.name, value);
+                        }
+                    }
+                    SymbolKind::Function => {
+                        let info = symbol.function_info();
+                        entry.functions.insert(symbol.name, info);
+                    }
+                }
+            }
+            self.add_file(import_path, file_path, entry);
+            indexed += 1;
+        }
+        indexed
+    }
+
+    /// Records the exported symbols of `file_path`, part of the package or
+    /// module imported as `import_path`.
+    pub fn add_file(&self, import_path: String, file_path: String, entry: CachedFileEntry) {
+        let mut packages = self.packages.write().unwrap();
+        let files = packages.entry(import_path).or_default();
+        if !files.contains(&file_path) {
+            files.push(file_path.clone());
+        }
+        self.files.write().unwrap().insert(file_path, entry);
+    }
+
+    /// The value of the constant `name` declared in the package or module
+    /// imported as `import_path`.
+    pub fn find_constant(&self, import_path: &str, name: &str) -> Option<Value> {
+        let packages = self.packages.read().unwrap();
+        let files = self.files.read().unwrap();
+        packages
+            .get(import_path)?
+            .iter()
+            .find_map(|file| files.get(file)?.constants.get(name).cloned())
+    }
+
+    pub fn package_count(&self) -> usize {
+        self.packages.read().unwrap().len()
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.read().unwrap().len()
+    }
+
+    pub fn clear(&self) {
+        self.packages.write().unwrap().clear();
+        self.files.write().unwrap().clear();
+    }
+}
+
+/// Names other packages can use: capitalized in Go, not `_`-prefixed in
+/// Python.
+fn is_exported(name: &str, language: Language) -> bool {
+    match language {
+        Language::Go => name.starts_with(|c: char| c.is_uppercase()),
+        _ => !name.starts_with('_'),
+    }
+}
+
+/// The import path of the Go package in `dir`: the path of the nearest
+/// module around it joined with the directory's path within the module.
+#[cfg(feature = "discovery")]
+fn go_import_path(dir: &Path) -> Option<String> {
+    dir.ancestors().find_map(|module_dir| {
+        let module = module_path(module_dir)?;
+        let relative = dir.strip_prefix(module_dir).ok()?;
+        Some(
+            std::iter::once(module)
+                .chain(relative.iter().map(|c| c.to_string_lossy().to_string()))
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    })
+}
+
+/// Without discovery `go.mod` files are not read, so Go packages are not
+/// indexed.
+#[cfg(not(feature = "discovery"))]
+fn go_import_path(_dir: &Path) -> Option<String> {
+    None
+}
+
+/// The dotted module path of the Python file at `path` from `root`, with
+/// `pkg/__init__.py` standing for `pkg`.
+fn python_module(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?.with_extension("");
+    let mut parts: Vec<String> = relative
+        .iter()
+        .map(|c| c.to_string_lossy().to_string())
+        .collect();
+    if parts.last().is_some_and(|last| last == "__init__") {
+        parts.pop();
+    }
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_file(root: &Path, name: &str, content: &str) -> PathBuf {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_index_go_packages_by_import_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "go.mod", "module example.com/shop\n\ngo 1.22\n");
+        let files = vec![
+            write_file(
+                root,
+                "internal/crypto/params.go",
+                "package crypto\n\nconst (\n\tDefaultIterations = 600_000\n\tsaltSize = 16\n)\n\nfunc Derive() {}\n",
+            ),
+            write_file(root, "main.go", "package main\n\nconst Rounds = 10\n"),
+        ];
+
+        let index = ProjectIndex::new();
+        assert_eq!(index.index_files(root, &files), 2);
+        assert_eq!(index.index_files(root, &files), 0);
+        assert_eq!(index.package_count(), 2);
+
+        let value = index
+            .find_constant("example.com/shop/internal/crypto", "DefaultIterations")
+            .unwrap();
+        assert_eq!(value.int_values, vec![600_000]);
+        assert!(index
+            .find_constant("example.com/shop/internal/crypto", "saltSize")
+            .is_none());
+        assert!(index.find_constant("example.com/shop", "Rounds").is_some());
+        assert!(index.find_constant("crypto", "DefaultIterations").is_none());
+    }
+
+    #[test]
+    fn test_index_python_modules_by_dotted_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = vec![
+            write_file(
+                root,
+                "app/settings.py",
+                "ITERATIONS = 310000\n_PEPPER = 'x'\n",
+            ),
+            write_file(root, "app/__init__.py", "DEFAULT_HASH = 'sha256'\n"),
+        ];
+
+        let index = ProjectIndex::new();
+        index.index_files(root, &files);
+
+        let value = index.find_constant("app.settings", "ITERATIONS").unwrap();
+        assert_eq!(value.int_values, vec![310000]);
+        assert!(index.find_constant("app.settings", "_PEPPER").is_none());
+        assert_eq!(
+            index
+                .find_constant("app", "DEFAULT_HASH")
+                .unwrap()
+                .string_values,
+            vec!["sha256"]
+        );
+    }
+}
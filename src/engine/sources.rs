@@ -2,6 +2,8 @@
 pub enum UnresolvedSource {
     FunctionParameter,
     FunctionNotFound,
+    /// Read from a configuration file or library, named by the expression
+    /// as a key path (`crypto.iterations`) when the keys are literals.
     ConfigValue,
     RuntimeValue,
     ExternalDependency,
@@ -11,6 +13,21 @@ pub enum UnresolvedSource {
     PartiallyResolved,
     MixedResolution,
     MixedTypes,
+    /// Read from the command line or standard input.
+    ExternalInput,
+    /// Read from an environment variable, named by the expression when
+    /// the name is a literal.
+    EnvironmentVariable,
+    /// A name in another package that is not indexed.
+    CrossPackage,
+    /// A method called on a value, so the callee depends on its type.
+    DynamicDispatch,
+    /// The resolution depth or call depth limit was reached.
+    BudgetExceeded,
+    /// An operator the evaluator does not implement.
+    UnsupportedOperator,
+    /// A variable bound by a loop, taking a value per iteration.
+    LoopDependent,
     Unknown,
 }
 
@@ -28,6 +45,13 @@ impl UnresolvedSource {
             Self::PartiallyResolved => "partially_resolved",
             Self::MixedResolution => "mixed_resolution",
             Self::MixedTypes => "mixed_types",
+            Self::ExternalInput => "external_input",
+            Self::EnvironmentVariable => "environment_variable",
+            Self::CrossPackage => "cross_package",
+            Self::DynamicDispatch => "dynamic_dispatch",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::UnsupportedOperator => "unsupported_operator",
+            Self::LoopDependent => "loop_dependent",
             Self::Unknown => "unknown",
         }
     }
@@ -45,6 +69,168 @@ impl From<UnresolvedSource> for String {
     }
 }
 
+/// Calls and selectors that read the environment, the command line or
+/// standard input, written as they appear in source.
+const EXTERNAL_INPUTS: &[&str] = &[
+    // Go
+    "os.Getenv",
+    "os.LookupEnv",
+    "os.Args",
+    "os.Stdin",
+    // Python
+    "os.getenv",
+    "os.environ",
+    "os.environ.get",
+    "sys.argv",
+    "sys.stdin",
+    "input",
+    // JavaScript / TypeScript
+    "process.env",
+    "process.argv",
+    // Java
+    "System.getenv",
+    "System.getProperty",
+    // Rust
+    "env::var",
+    "env::args",
+    "std::env::var",
+    "std::env::args",
+    // C / C++
+    "getenv",
+    "secure_getenv",
+    "std::getenv",
+];
+
+/// The entries of [`EXTERNAL_INPUTS`] that read the environment.
+const ENVIRONMENT_READS: &[&str] = &[
+    "os.Getenv",
+    "os.LookupEnv",
+    "os.getenv",
+    "os.environ",
+    "os.environ.get",
+    "process.env",
+    "System.getenv",
+    "env::var",
+    "std::env::var",
+    "getenv",
+    "secure_getenv",
+    "std::getenv",
+];
+
+/// Whether `expression` names a read of external input, such as
+/// `os.Getenv` or `process.env`.
+pub fn is_external_input(expression: &str) -> bool {
+    EXTERNAL_INPUTS.contains(&expression)
+}
+
+/// Whether `expression` names a read of the environment, such as
+/// `os.Getenv` or `process.env`, rather than of the command line or
+/// standard input.
+pub fn is_environment_read(expression: &str) -> bool {
+    ENVIRONMENT_READS.contains(&expression)
+}
+
+/// Calls reading one configuration key, named by their first argument.
+const CONFIG_READS: &[&str] = &[
+    // Go, spf13/viper
+    "viper.Get",
+    "viper.GetBool",
+    "viper.GetDuration",
+    "viper.GetFloat64",
+    "viper.GetInt",
+    "viper.GetInt32",
+    "viper.GetInt64",
+    "viper.GetIntSlice",
+    "viper.GetSizeInBytes",
+    "viper.GetString",
+    "viper.GetStringSlice",
+    "viper.GetUint",
+    "viper.GetUint32",
+    "viper.GetUint64",
+    // JavaScript / TypeScript, node-config
+    "config.get",
+];
+
+/// Calls loading a whole configuration document, whose keys are read by
+/// indexing or field access on the result.
+const CONFIG_LOADERS: &[&str] = &[
+    // Python
+    "yaml.safe_load",
+    "yaml.load",
+    "yaml.full_load",
+    "json.load",
+    "toml.load",
+    "tomllib.load",
+    // JavaScript / TypeScript, the yaml package (js-yaml's yaml.load is
+    // listed above)
+    "YAML.parse",
+    // Ruby
+    "YAML.load_file",
+    "YAML.safe_load_file",
+];
+
+/// Calls decoding a configuration document into the variable passed to
+/// them by reference.
+const CONFIG_DECODERS: &[&str] = &[
+    // Go
+    "json.Unmarshal",
+    "yaml.Unmarshal",
+    "toml.Unmarshal",
+    "toml.Decode",
+    "toml.DecodeFile",
+    "viper.Unmarshal",
+    "viper.UnmarshalKey",
+];
+
+/// Whether `expression` names a call reading one configuration key, such
+/// as `viper.GetInt`.
+pub fn is_config_read(expression: &str) -> bool {
+    CONFIG_READS.contains(&expression)
+}
+
+/// Whether `expression` names a call loading a configuration document,
+/// such as `yaml.safe_load`.
+pub fn is_config_loader(expression: &str) -> bool {
+    CONFIG_LOADERS.contains(&expression)
+}
+
+/// Whether `expression` names a call decoding configuration into one of
+/// its arguments, such as `json.Unmarshal`.
+pub fn is_config_decoder(expression: &str) -> bool {
+    CONFIG_DECODERS.contains(&expression)
+}
+
+/// Calls converting their first argument to a number, whose value is
+/// that of the argument: `strconv.Atoi(os.Getenv("ITERATIONS"))` is as
+/// unknown as the variable it reads.
+const CONVERSIONS: &[&str] = &[
+    // Go
+    "strconv.Atoi",
+    "strconv.ParseInt",
+    "strconv.ParseUint",
+    "strconv.ParseFloat",
+    // Python
+    "int",
+    "float",
+    // JavaScript / TypeScript
+    "parseInt",
+    "parseFloat",
+    "Number",
+    "Number.parseInt",
+    "Number.parseFloat",
+    // Java
+    "Integer.parseInt",
+    "Integer.valueOf",
+    "Long.parseLong",
+    "Long.valueOf",
+];
+
+/// Whether `expression` names a call converting its argument to a number,
+/// such as `strconv.Atoi` or `parseInt`.
+pub fn is_conversion(expression: &str) -> bool {
+    CONVERSIONS.contains(&expression)
+}
+
 pub const NOT_RESOLVED: &str = "not_resolved";
 pub const UNRESOLVED: &str = "unresolved";
 
@@ -52,6 +238,43 @@ pub const UNRESOLVED: &str = "unresolved";
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_external_input() {
+        assert!(is_external_input("os.Getenv"));
+        assert!(is_external_input("process.env"));
+        assert!(is_external_input("std::env::var"));
+        assert!(!is_external_input("os.ReadFile"));
+        assert!(!is_external_input("env"));
+    }
+
+    #[test]
+    fn test_is_environment_read() {
+        assert!(is_environment_read("os.Getenv"));
+        assert!(is_environment_read("os.environ"));
+        assert!(is_environment_read("System.getenv"));
+        assert!(!is_environment_read("sys.argv"));
+        assert!(!is_environment_read("System.getProperty"));
+        assert!(ENVIRONMENT_READS.iter().all(|read| is_external_input(read)));
+    }
+
+    #[test]
+    fn test_config_sources() {
+        assert!(is_config_read("viper.GetInt"));
+        assert!(!is_config_read("viper.Unmarshal"));
+        assert!(is_config_loader("yaml.safe_load"));
+        assert!(!is_config_loader("json.loads"));
+        assert!(is_config_decoder("json.Unmarshal"));
+        assert!(!is_config_decoder("json.Marshal"));
+    }
+
+    #[test]
+    fn test_is_conversion() {
+        assert!(is_conversion("strconv.Atoi"));
+        assert!(is_conversion("int"));
+        assert!(is_conversion("parseInt"));
+        assert!(!is_conversion("strconv.Itoa"));
+    }
+
     #[test]
     fn test_source_as_str() {
         assert_eq!(
@@ -59,6 +282,8 @@ mod tests {
             "function_parameter"
         );
         assert_eq!(UnresolvedSource::CycleDetected.as_str(), "cycle_detected");
+        assert_eq!(UnresolvedSource::BudgetExceeded.as_str(), "budget_exceeded");
+        assert_eq!(UnresolvedSource::LoopDependent.as_str(), "loop_dependent");
     }
 
     #[test]
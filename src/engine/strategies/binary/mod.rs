@@ -1,9 +1,8 @@
-use crate::engine::{BinaryOp, Context, NodeCategory, Resolver, Strategy, UnresolvedSource, Value};
+use crate::engine::{BinaryOp, Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
+use std::rc::Rc;
 use tree_sitter::Node;
 
-pub struct BinaryStrategy {
-    resolver: Option<Resolver>,
-}
+pub struct BinaryStrategy;
 
 impl Default for BinaryStrategy {
     fn default() -> Self {
@@ -13,13 +12,7 @@ impl Default for BinaryStrategy {
 
 impl BinaryStrategy {
     pub fn new() -> Self {
-        Self { resolver: None }
-    }
-
-    pub fn with_resolver(resolver: Resolver) -> Self {
-        Self {
-            resolver: Some(resolver),
-        }
+        Self
     }
 
     fn get_operator<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
@@ -70,12 +63,8 @@ impl BinaryStrategy {
         None
     }
 
-    fn resolve_operand<'a>(&self, operand: &Node<'a>, ctx: &Context<'a>) -> Value {
-        if let Some(ref resolver) = self.resolver {
-            return resolver.resolve(operand, ctx);
-        }
-        let resolver = Resolver::new();
-        resolver.resolve(operand, ctx)
+    fn resolve_operand<'a>(&self, operand: &Node<'a>, ctx: &Context<'a>) -> Rc<Value> {
+        ctx.resolve_shared(operand)
     }
 }
 
@@ -83,6 +72,14 @@ fn is_binary_operator(s: &str) -> bool {
     BinaryOp::parse(s).is_some()
 }
 
+/// Whether `+` of a string and a number gives a string in the language.
+fn joins_numbers(ctx: &Context) -> bool {
+    matches!(
+        ctx.node_types().map(|types| types.language()),
+        Some(Language::JavaScript | Language::TypeScript | Language::Java | Language::CSharp)
+    )
+}
+
 impl Strategy for BinaryStrategy {
     fn name(&self) -> &'static str {
         "binary"
@@ -111,6 +108,12 @@ impl Strategy for BinaryStrategy {
         let left_value = self.resolve_operand(&left_node, ctx);
         let right_value = self.resolve_operand(&right_node, ctx);
 
+        if operator == "+" {
+            if let Some(joined) = Value::concatenate(&left_value, &right_value, joins_numbers(ctx))
+            {
+                return joined;
+            }
+        }
         Value::binary_op(&left_value, &operator, &right_value)
     }
 }
@@ -0,0 +1,18 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CallStrategy;
+
+/// `return 4` and `return@label 4`, whose label is not the value.
+pub fn extract_return<'a>(
+    strategy: &CallStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Value> {
+    let label = node.child_by_field_name("label");
+    let mut cursor = node.walk();
+    let value = node
+        .named_children(&mut cursor)
+        .find(|child| Some(*child) != label)?;
+    Some(strategy.resolve_value_node(value, ctx))
+}
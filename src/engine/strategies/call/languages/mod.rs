@@ -2,12 +2,20 @@ pub mod c;
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod swift;
 
 pub use c::extract_return as c_extract_return;
 pub use go::extract_return as go_extract_return;
 pub use java::extract_return as java_extract_return;
 pub use javascript::extract_return as js_extract_return;
+pub use kotlin::extract_return as kotlin_extract_return;
+pub use php::extract_return as php_extract_return;
 pub use python::extract_return as python_extract_return;
+pub use ruby::extract_return as ruby_extract_return;
 pub use rust::extract_return as rust_extract_return;
+pub use swift::extract_return as swift_extract_return;
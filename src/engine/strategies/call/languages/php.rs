@@ -0,0 +1,14 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CallStrategy;
+
+/// `return 4;`
+pub fn extract_return<'a>(
+    strategy: &CallStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Value> {
+    let value = node.named_child(0)?;
+    Some(strategy.resolve_value_node(value, ctx))
+}
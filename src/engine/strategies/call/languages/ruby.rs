@@ -0,0 +1,18 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CallStrategy;
+
+/// `return 4`: the value is wrapped in an argument list.
+pub fn extract_return<'a>(
+    strategy: &CallStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Value> {
+    let value = node.named_child(0)?;
+    let value = match value.kind() {
+        "argument_list" => value.named_child(0)?,
+        _ => value,
+    };
+    Some(strategy.resolve_value_node(value, ctx))
+}
@@ -0,0 +1,15 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CallStrategy;
+
+/// `return 4`. `break`, `continue` and `throw` share the statement kind
+/// but have no result.
+pub fn extract_return<'a>(
+    strategy: &CallStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Value> {
+    let value = node.child_by_field_name("result")?;
+    Some(strategy.resolve_value_node(value, ctx))
+}
@@ -1,5 +1,10 @@
-use crate::engine::{Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
-use tree_sitter::Node;
+use crate::engine::{
+    file_cache::FunctionInfo,
+    sources::{is_config_loader, is_config_read, is_conversion, is_external_input},
+    symbol_index, Context, EvidenceKind, Language, NodeCategory, Strategy, UnresolvedSource, Value,
+};
+use std::path::Path;
+use tree_sitter::{Node, Parser};
 
 mod languages;
 
@@ -16,6 +21,53 @@ impl CallStrategy {
         Self
     }
 
+    /// The first argument of the call `node` when it is a string literal,
+    /// e.g. `ITERATIONS` for `os.Getenv("ITERATIONS")`.
+    fn literal_first_argument<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
+        let first = Self::first_argument(node)?;
+        ctx.is_node_category(first.kind(), NodeCategory::StringLiteral)
+            .then(|| ctx.unquote_string(&ctx.get_node_text(&first)))
+    }
+
+    /// The first argument of the call `node`. Kotlin wraps each argument,
+    /// named or not, in a `value_argument` of an unlabeled list.
+    fn first_argument<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        let arguments = node.child_by_field_name("arguments").or_else(|| {
+            let mut cursor = node.walk();
+            let list = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "value_arguments");
+            list
+        })?;
+        let first = arguments.named_child(0)?;
+        match first.kind() {
+            "value_argument" => first.named_child(first.named_child_count().checked_sub(1)?),
+            _ => Some(first),
+        }
+    }
+
+    /// The value of the conversion call `node`, e.g. `int(os.getenv("N"))`:
+    /// its argument's when that is unknown or already a number, or the
+    /// numbers its strings parse as. `None` when the strings are not
+    /// numbers.
+    fn resolve_conversion<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<Value> {
+        let argument = Self::first_argument(node)?;
+        let value = ctx.resolve(&argument);
+        if !value.is_resolved || !value.int_values.is_empty() {
+            return Some(value);
+        }
+        let ints = value
+            .string_values
+            .iter()
+            .map(|s| s.trim().parse::<i64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        (!ints.is_empty()).then(|| Value {
+            int_values: ints,
+            string_values: Vec::new(),
+            ..value
+        })
+    }
+
     fn get_function_name<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
         let lang = ctx.node_types()?.language();
 
@@ -29,12 +81,75 @@ impl CallStrategy {
             Language::JavaScript | Language::TypeScript => node
                 .child_by_field_name("function")
                 .map(|n| ctx.get_node_text(&n)),
-            Language::Java => node
-                .child_by_field_name("name")
+            // `new Rfc2898DeriveBytes(...)` is named by its type
+            Language::CSharp => node
+                .child_by_field_name("function")
+                .or_else(|| node.child_by_field_name("type"))
+                .map(|n| ctx.get_node_text(&n)),
+            // `OpenSSL::Cipher.new(...)` is named by its method and receiver
+            Language::Ruby => {
+                let name = ctx.get_node_text(&node.child_by_field_name("method")?);
+                match node.child_by_field_name("receiver") {
+                    Some(receiver) => Some(format!("{}.{name}", ctx.get_node_text(&receiver))),
+                    None => Some(name),
+                }
+            }
+            // `hash_pbkdf2(...)`, `\hash_pbkdf2(...)`, `$this->rounds()` and
+            // `Params::rounds()`
+            Language::Php => match node.child_by_field_name("function") {
+                Some(function) => Some(
+                    ctx.get_node_text(&function)
+                        .trim_start_matches('\\')
+                        .to_string(),
+                ),
+                None => {
+                    let name = ctx.get_node_text(&node.child_by_field_name("name")?);
+                    let receiver = node
+                        .child_by_field_name("object")
+                        .or_else(|| node.child_by_field_name("scope"))?;
+                    Some(format!("{}.{name}", ctx.get_node_text(&receiver)))
+                }
+            },
+            // `rounds()` and `Params.rounds()`: the callee is the first child
+            Language::Swift | Language::Kotlin => {
+                node.named_child(0).map(|callee| ctx.get_node_text(&callee))
+            }
+            Language::Java => {
+                let name = ctx.get_node_text(&node.child_by_field_name("name")?);
+                match node.child_by_field_name("object") {
+                    Some(object) => Some(format!("{}.{name}", ctx.get_node_text(&object))),
+                    None => Some(name),
+                }
+            }
+            // `new PBEKeySpec(...)` is named by its type
+            Language::Scala => node
+                .child_by_field_name("function")
+                .or_else(|| node.named_child(0))
                 .map(|n| ctx.get_node_text(&n)),
         }
     }
 
+    /// Why a call with no visible declaration stays unresolved: a bare
+    /// name was not found, a receiver that is an imported module (or a
+    /// `::` path) lives in another package, and any other receiver is a
+    /// value whose method depends on its runtime type.
+    fn classify_missing_function(func_name: &str, ctx: &Context) -> UnresolvedSource {
+        if func_name.contains("::") {
+            return UnresolvedSource::CrossPackage;
+        }
+
+        let receiver = func_name
+            .rsplit_once("->")
+            .or_else(|| func_name.rsplit_once('.'))
+            .map(|(receiver, _)| receiver);
+
+        match receiver {
+            None => UnresolvedSource::FunctionNotFound,
+            Some(receiver) if ctx.is_imported_name(receiver) => UnresolvedSource::CrossPackage,
+            Some(_) => UnresolvedSource::DynamicDispatch,
+        }
+    }
+
     fn find_function_declaration<'a>(
         &self,
         name: &str,
@@ -51,6 +166,57 @@ impl CallStrategy {
         self.find_function_in_tree(name, root, ctx)
     }
 
+    /// The return value of a function declared in another file of the
+    /// package: a bare `getIterations()`, or `params.get_iterations()` and
+    /// `params::get_iterations()` for a sibling module of Python,
+    /// JavaScript or Rust. Go calls through a package name are left to
+    /// that package.
+    fn resolve_in_package(&self, func_name: &str, ctx: &Context) -> Option<Value> {
+        let language = ctx.node_types()?.language();
+        let (module, name) = match func_name.rsplit_once("::") {
+            Some((path, name)) => (Some(path.rsplit("::").next()?), name),
+            None => match func_name.rsplit_once('.') {
+                Some((receiver, name)) => (Some(receiver), name),
+                None => (None, func_name),
+            },
+        };
+        let qualified = match language {
+            Language::Go => false,
+            Language::Python | Language::JavaScript | Language::TypeScript => {
+                module.is_some_and(|module| ctx.is_imported_name(module))
+            }
+            Language::Rust => func_name.contains("::"),
+            _ => return None,
+        };
+        if module.is_some() && !qualified {
+            return None;
+        }
+        let function = ctx.find_package_function(name, module)?;
+        self.resolve_in_file(&function, name, ctx)
+    }
+
+    /// The return value of `function`, parsed from its own file.
+    fn resolve_in_file(&self, function: &FunctionInfo, name: &str, ctx: &Context) -> Option<Value> {
+        let path = Path::new(&function.file_path);
+        let grammar = symbol_index::tree_sitter_language_for_path(path)?;
+        let source = std::fs::read_to_string(path).ok()?;
+        let mut parser = Parser::new();
+        parser.set_language(&grammar).ok()?;
+        let tree = parser.parse(&source, None)?;
+
+        let file_ctx = ctx.for_file(&tree, source.as_bytes(), function.file_path.clone());
+        let decl = file_ctx.find_node_at_position(function.start_byte, function.end_byte)?;
+        let body = self.get_function_body(&decl, &file_ctx)?;
+        let return_values = self.collect_return_values(body, &file_ctx);
+        if return_values.is_empty() {
+            return None;
+        }
+        Some(
+            self.merge_return_values(return_values)
+                .with_evidence(file_ctx.evidence(EvidenceKind::Return, name, &decl)),
+        )
+    }
+
     fn find_function_in_tree<'a>(
         &self,
         name: &str,
@@ -99,7 +265,13 @@ impl CallStrategy {
                 .child_by_field_name("declarator")
                 .and_then(|d| d.child_by_field_name("declarator"))
                 .map(|n| ctx.get_node_text(&n)),
-            Language::Java => node
+            Language::Java
+            | Language::CSharp
+            | Language::Ruby
+            | Language::Php
+            | Language::Swift
+            | Language::Kotlin
+            | Language::Scala => node
                 .child_by_field_name("name")
                 .map(|n| ctx.get_node_text(&n)),
         }
@@ -109,10 +281,24 @@ impl CallStrategy {
         let lang = ctx.node_types()?.language();
 
         match lang {
-            Language::Go | Language::Rust | Language::C | Language::Cpp | Language::Java => {
-                func.child_by_field_name("body")
-            }
+            Language::Go
+            | Language::Rust
+            | Language::C
+            | Language::Cpp
+            | Language::Java
+            | Language::CSharp
+            | Language::Ruby
+            | Language::Php
+            | Language::Swift
+            | Language::Scala => func.child_by_field_name("body"),
             Language::Python => func.child_by_field_name("body"),
+            Language::Kotlin => {
+                let mut cursor = func.walk();
+                let body = func
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "function_body");
+                body
+            }
             Language::JavaScript | Language::TypeScript => func
                 .child_by_field_name("body")
                 .or_else(|| func.named_child(func.named_child_count().saturating_sub(1))),
@@ -120,8 +306,48 @@ impl CallStrategy {
     }
 
     fn collect_return_values<'a>(&self, body: Node<'a>, ctx: &Context<'a>) -> Vec<Value> {
+        // C# expression-bodied members: `static int Rounds() => 4;`
+        if body.kind() == "arrow_expression_clause" {
+            return body
+                .named_child(0)
+                .map(|expression| self.resolve_value_node(expression, ctx))
+                .into_iter()
+                .collect();
+        }
+
+        // Kotlin expression bodies: `fun rounds() = 600_000`
+        if body.kind() == "function_body" && body.child(0).is_some_and(|eq| eq.kind() == "=") {
+            return body
+                .named_child(0)
+                .map(|expression| self.resolve_value_node(expression, ctx))
+                .into_iter()
+                .collect();
+        }
+
+        // Scala bodies are an expression: `def rounds() = 600000`, or a
+        // block ending in one
+        let language = ctx.node_types().map(|nt| nt.language());
+        if language == Some(Language::Scala) && body.kind() != "block" {
+            return vec![self.resolve_value_node(body, ctx)];
+        }
+
         let mut values = Vec::new();
         self.collect_returns_recursive(body, ctx, &mut values);
+
+        // Ruby methods also return their last expression, as Scala
+        // blocks do
+        if matches!(language, Some(Language::Ruby | Language::Scala)) {
+            let last = body.named_child(body.named_child_count().saturating_sub(1));
+            let is_value = |last: &Node| {
+                !matches!(
+                    last.kind(),
+                    "return" | "return_expression" | "val_definition" | "var_definition"
+                )
+            };
+            if let Some(last) = last.filter(is_value) {
+                values.push(self.resolve_value_node(last, ctx));
+            }
+        }
         values
     }
 
@@ -158,7 +384,13 @@ impl CallStrategy {
                 languages::js_extract_return(self, return_node, ctx)
             }
             Language::C | Language::Cpp => languages::c_extract_return(self, return_node, ctx),
-            Language::Java => languages::java_extract_return(self, return_node, ctx),
+            Language::Java | Language::CSharp | Language::Scala => {
+                languages::java_extract_return(self, return_node, ctx)
+            }
+            Language::Ruby => languages::ruby_extract_return(self, return_node, ctx),
+            Language::Php => languages::php_extract_return(self, return_node, ctx),
+            Language::Swift => languages::swift_extract_return(self, return_node, ctx),
+            Language::Kotlin => languages::kotlin_extract_return(self, return_node, ctx),
         }
     }
 
@@ -189,11 +421,15 @@ impl CallStrategy {
         let mut all_strings = Vec::new();
         let mut all_resolved = true;
 
-        for node in nodes {
-            let value = self.resolve_value_node(*node, ctx);
+        let values: Vec<Value> = nodes
+            .iter()
+            .map(|node| self.resolve_value_node(*node, ctx))
+            .collect();
+
+        for value in &values {
             if value.is_resolved {
-                all_ints.extend(value.int_values);
-                all_strings.extend(value.string_values);
+                all_ints.extend(value.int_values.iter().copied());
+                all_strings.extend(value.string_values.iter().cloned());
             } else {
                 all_resolved = false;
             }
@@ -206,7 +442,10 @@ impl CallStrategy {
                 string_values: all_strings,
                 source: String::new(),
                 expression: String::new(),
+                evidence: Vec::new(),
+                spans: Vec::new(),
             }
+            .with_provenance_of(&values)
         } else {
             let texts: Vec<_> = nodes.iter().map(|n| ctx.get_node_text(n)).collect();
             Value::partial_expression(texts.join(", "))
@@ -219,27 +458,27 @@ impl CallStrategy {
         if ctx.is_node_category(kind, NodeCategory::IntegerLiteral) {
             let text = ctx.get_node_text(&node);
             if let Some(value) = ctx.parse_int_literal(&text) {
-                return Value::resolved_int(value);
+                return Value::resolved_int(value).with_span(ctx.span(&node));
             }
         }
 
         if ctx.is_node_category(kind, NodeCategory::StringLiteral) {
             let text = ctx.get_node_text(&node);
             let unquoted = ctx.unquote_string(&text);
-            return Value::resolved_string(unquoted);
+            return Value::resolved_string(unquoted).with_span(ctx.span(&node));
         }
 
         if ctx.is_node_category(kind, NodeCategory::BooleanLiteral) {
             let text = ctx.get_node_text(&node);
             if text.eq_ignore_ascii_case("true") {
-                return Value::resolved_string("true".to_string());
+                return Value::resolved_string("true".to_string()).with_span(ctx.span(&node));
             } else if text.eq_ignore_ascii_case("false") {
-                return Value::resolved_string("false".to_string());
+                return Value::resolved_string("false".to_string()).with_span(ctx.span(&node));
             }
         }
 
         if ctx.is_node_category(kind, NodeCategory::NilLiteral) {
-            return Value::resolved_string(kind.to_string());
+            return Value::resolved_string(kind.to_string()).with_span(ctx.span(&node));
         }
 
         Value::partial_expression(ctx.get_node_text(&node))
@@ -259,22 +498,22 @@ impl CallStrategy {
         let mut any_unresolved = false;
         let mut expressions = Vec::new();
 
-        for value in values {
+        for value in &values {
             if value.is_resolved {
-                for i in value.int_values {
-                    if !all_ints.contains(&i) {
-                        all_ints.push(i);
+                for i in &value.int_values {
+                    if !all_ints.contains(i) {
+                        all_ints.push(*i);
                     }
                 }
-                for s in value.string_values {
-                    if !all_strings.contains(&s) {
-                        all_strings.push(s);
+                for s in &value.string_values {
+                    if !all_strings.contains(s) {
+                        all_strings.push(s.clone());
                     }
                 }
             } else {
                 any_unresolved = true;
                 if !value.expression.is_empty() {
-                    expressions.push(value.expression);
+                    expressions.push(value.expression.clone());
                 }
             }
         }
@@ -290,7 +529,10 @@ impl CallStrategy {
                 } else {
                     String::new()
                 },
+                evidence: Vec::new(),
+                spans: Vec::new(),
             }
+            .with_provenance_of(&values)
         } else if !expressions.is_empty() {
             Value::partial_expression(expressions.join(" | "))
         } else {
@@ -314,13 +556,41 @@ impl Strategy for CallStrategy {
             None => return Value::unextractable(UnresolvedSource::Unknown),
         };
 
+        if is_external_input(&func_name) {
+            return Value::external_input(
+                &func_name,
+                Self::literal_first_argument(node, ctx),
+                ctx.get_node_text(node),
+            );
+        }
+
+        if is_config_read(&func_name) {
+            return match Self::literal_first_argument(node, ctx) {
+                Some(key) => Value::config_value(Some(key)),
+                None => Value::unresolved_expression(
+                    UnresolvedSource::ConfigValue,
+                    ctx.get_node_text(node),
+                ),
+            };
+        }
+        if is_config_loader(&func_name) {
+            return Value::config_value(None);
+        }
+        if is_conversion(&func_name) {
+            if let Some(value) = Self::resolve_conversion(node, ctx) {
+                return value;
+            }
+        }
+
         let simple_name = func_name.split('.').next_back().unwrap_or(&func_name);
 
         let func_decl =
             match self.find_function_declaration(simple_name, ctx.tree().root_node(), ctx) {
                 Some(decl) => decl,
                 None => {
-                    return Value::unextractable(UnresolvedSource::FunctionNotFound);
+                    return self.resolve_in_package(&func_name, ctx).unwrap_or_else(|| {
+                        Value::unextractable(Self::classify_missing_function(&func_name, ctx))
+                    });
                 }
             };
 
@@ -336,12 +606,14 @@ impl Strategy for CallStrategy {
         }
 
         self.merge_return_values(return_values)
+            .with_evidence(ctx.evidence(EvidenceKind::Return, simple_name, &func_decl))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::SymbolIndex;
     use std::collections::HashMap;
     use tree_sitter::Tree;
 
@@ -548,6 +820,53 @@ func main() {
         assert_eq!(value.source, "function_not_found");
     }
 
+    #[test]
+    fn test_go_environment_read_names_variable() {
+        let source = r#"
+package main
+
+import "os"
+
+func main() {
+    x := os.Getenv("ITERATIONS")
+}"#;
+        let tree = parse_go(source);
+        let ctx = create_go_context(&tree, source.as_bytes());
+        let strategy = CallStrategy::new();
+
+        let call_node = find_call_by_name(tree.root_node(), "Getenv", &ctx).unwrap();
+        let value = strategy.resolve(&call_node, &ctx);
+
+        assert!(!value.is_resolved);
+        assert_eq!(value.source, "environment_variable");
+        assert_eq!(value.expression, "ITERATIONS");
+    }
+
+    #[test]
+    fn test_go_missing_function_by_receiver() {
+        let source = r#"
+package main
+
+import "example.com/params"
+
+func main() {
+    a := params.Iterations()
+    b := cfg.Iterations()
+}"#;
+        let tree = parse_go(source);
+        let ctx =
+            create_go_context(&tree, source.as_bytes()).with_imported_names(["params".to_string()]);
+        let strategy = CallStrategy::new();
+
+        let call_node = find_call_by_name(tree.root_node(), "params.Iterations", &ctx).unwrap();
+        let value = strategy.resolve(&call_node, &ctx);
+        assert_eq!(value.source, "cross_package");
+
+        let call_node = find_call_by_name(tree.root_node(), "cfg.Iterations", &ctx).unwrap();
+        let value = strategy.resolve(&call_node, &ctx);
+        assert_eq!(value.source, "dynamic_dispatch");
+    }
+
     // =========================================================================
     // Go - Multiple Return Paths (Control Flow)
     // =========================================================================
@@ -867,6 +1186,129 @@ const x = getKeySize(true);
         assert!(value.int_values.contains(&16));
     }
 
+    // =========================================================================
+    // Cross-file Tests
+    // =========================================================================
+
+    /// Resolves the call to `name` in `source`, a file of `language` next
+    /// to `sibling` saved as `sibling_file`, through the symbol index.
+    fn resolve_with_sibling(
+        language: &str,
+        parse: fn(&str) -> Tree,
+        (file, source): (&str, &str),
+        (sibling_file, sibling): (&str, &str),
+        name: &str,
+    ) -> Value {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(sibling_file), sibling).unwrap();
+        let tree = parse(source);
+        let ctx = Context::new(
+            &tree,
+            source.as_bytes(),
+            dir.path().join(file).to_string_lossy().to_string(),
+            language.to_string(),
+            HashMap::new(),
+        )
+        .with_symbol_index(SymbolIndex::shared())
+        .with_imported_names(["params".to_string()]);
+        let call_node = find_call_by_name(tree.root_node(), name, &ctx).unwrap();
+        CallStrategy::new().resolve(&call_node, &ctx)
+    }
+
+    #[test]
+    fn test_go_function_in_another_file() {
+        let params = "package main\n\nfunc getIterations() int {\n\treturn 600000\n}\n";
+        let value = resolve_with_sibling(
+            "go",
+            parse_go,
+            (
+                "main.go",
+                "package main\n\nfunc main() {\n\tx := getIterations()\n}\n",
+            ),
+            ("params.go", params),
+            "getIterations",
+        );
+        assert!(value.is_resolved);
+        assert_eq!(value.int_values, vec![600000]);
+        assert_eq!(value.evidence[0].kind, EvidenceKind::Return);
+        assert!(value.evidence[0].file.ends_with("params.go"));
+        assert_eq!(value.evidence[0].line, 3);
+
+        // `params.getIterations()` is a call into the params package
+        let value = resolve_with_sibling(
+            "go",
+            parse_go,
+            (
+                "main.go",
+                "package main\n\nfunc main() {\n\tx := params.getIterations()\n}\n",
+            ),
+            ("params.go", params),
+            "getIterations",
+        );
+        assert!(!value.is_resolved);
+        assert_eq!(value.source, UnresolvedSource::CrossPackage.as_str());
+    }
+
+    #[test]
+    fn test_python_function_in_another_module() {
+        let params = "def get_iterations():\n    return 310000\n";
+        for call in ["get_iterations()", "params.get_iterations()"] {
+            let value = resolve_with_sibling(
+                "python",
+                parse_python,
+                ("kdf.py", &format!("x = {call}\n")),
+                ("params.py", params),
+                "get_iterations",
+            );
+            assert_eq!(value.int_values, vec![310000], "{call}");
+        }
+
+        // Only the module the receiver names is searched
+        let value = resolve_with_sibling(
+            "python",
+            parse_python,
+            ("kdf.py", "x = settings.get_iterations()\n"),
+            ("params.py", params),
+            "get_iterations",
+        );
+        assert!(!value.is_resolved);
+    }
+
+    #[test]
+    fn test_js_function_in_another_module() {
+        let value = resolve_with_sibling(
+            "javascript",
+            parse_javascript,
+            ("kdf.js", "const x = params.getKeySize();\n"),
+            (
+                "params.js",
+                "export function getKeySize() {\n  return 32;\n}\n",
+            ),
+            "getKeySize",
+        );
+        assert!(value.is_resolved);
+        assert_eq!(value.int_values, vec![32]);
+    }
+
+    #[test]
+    fn test_rust_function_in_another_module() {
+        let value = resolve_with_sibling(
+            "rust",
+            parse_rust,
+            (
+                "main.rs",
+                "fn main() {\n    let x = params::iterations();\n}\n",
+            ),
+            (
+                "params.rs",
+                "pub fn iterations() -> u32 {\n    return 600_000;\n}\n",
+            ),
+            "iterations",
+        );
+        assert!(value.is_resolved);
+        assert_eq!(value.int_values, vec![600000]);
+    }
+
     // =========================================================================
     // Crypto-Relevant Tests
     // =========================================================================
@@ -0,0 +1,35 @@
+use crate::engine::{Context, UnresolvedSource, Value};
+use tree_sitter::Node;
+
+use super::super::CompositeStrategy;
+
+/// `{ 1, 2 }`, `[1, 2]`, and `new byte[] { 1, 2 }` or `new[] { 1, 2 }`
+/// through their initializer.
+pub fn resolve_array<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    match node.kind() {
+        "array_creation_expression" | "implicit_array_creation_expression" => {
+            let mut cursor = node.walk();
+            let initializer = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "initializer_expression");
+            match initializer {
+                Some(initializer) => strategy.collect_array_elements(&initializer, ctx),
+                // `new byte[16]` has a length but no elements
+                None => Value::partial_expression(ctx.get_node_text(node)),
+            }
+        }
+        _ => strategy.collect_array_elements(node, ctx),
+    }
+}
+
+pub fn resolve_object<'a>(
+    _strategy: &CompositeStrategy,
+    _node: &Node<'a>,
+    _ctx: &Context<'a>,
+) -> Value {
+    Value::unextractable(UnresolvedSource::NotImplemented)
+}
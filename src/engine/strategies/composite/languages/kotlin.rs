@@ -0,0 +1,13 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CompositeStrategy;
+
+/// `[16, 32]`, as annotation arguments write arrays.
+pub fn resolve_array<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    strategy.collect_array_elements(node, ctx)
+}
@@ -1,13 +1,25 @@
 pub mod c;
+pub mod csharp;
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod swift;
 
 pub use c::{resolve_array as c_resolve_array, resolve_initializer as c_resolve_initializer};
+pub use csharp::{resolve_array as csharp_resolve_array, resolve_object as csharp_resolve_object};
 pub use go::{resolve_array as go_resolve_array, resolve_struct as go_resolve_struct};
 pub use java::{resolve_array as java_resolve_array, resolve_object as java_resolve_object};
 pub use javascript::{resolve_array as js_resolve_array, resolve_object as js_resolve_object};
+pub use kotlin::resolve_array as kotlin_resolve_array;
+pub use php::resolve_array as php_resolve_array;
 pub use python::{resolve_array as python_resolve_array, resolve_dict as python_resolve_dict};
+pub use ruby::{resolve_array as ruby_resolve_array, resolve_hash as ruby_resolve_hash};
 pub use rust::{resolve_array as rust_resolve_array, resolve_struct as rust_resolve_struct};
+pub use swift::{
+    resolve_array as swift_resolve_array, resolve_dictionary as swift_resolve_dictionary,
+};
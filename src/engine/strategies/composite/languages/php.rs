@@ -0,0 +1,22 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CompositeStrategy;
+
+/// `[1, 2]` or `array(1, 2)`, and `['cost' => 12]`, which PHP spells the
+/// same way but keys.
+pub fn resolve_array<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    let mut cursor = node.walk();
+    let is_keyed = node
+        .named_children(&mut cursor)
+        .any(|element| element.named_child_count() == 2);
+    if is_keyed {
+        strategy.collect_dict_entries(node, ctx)
+    } else {
+        strategy.collect_array_elements(node, ctx)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CompositeStrategy;
+
+/// `[1, 2]`, and `%w[aes-128-gcm aes-256-gcm]` or `%i[gcm cbc]`, whose bare
+/// words are the values themselves.
+pub fn resolve_array<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    match node.kind() {
+        "string_array" | "symbol_array" => {
+            let mut cursor = node.walk();
+            let words: Vec<String> = node
+                .named_children(&mut cursor)
+                .map(|word| ctx.get_node_text(&word))
+                .collect();
+            Value::resolved_strings(words).with_span(ctx.span(node))
+        }
+        _ => strategy.collect_array_elements(node, ctx),
+    }
+}
+
+pub fn resolve_hash<'a>(strategy: &CompositeStrategy, node: &Node<'a>, ctx: &Context<'a>) -> Value {
+    strategy.collect_dict_entries(node, ctx)
+}
@@ -0,0 +1,22 @@
+use crate::engine::{Context, Value};
+use tree_sitter::Node;
+
+use super::super::CompositeStrategy;
+
+/// `[16, 32]`.
+pub fn resolve_array<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    strategy.collect_array_elements(node, ctx)
+}
+
+/// `["cost": 12]`, whose keys and values alternate unwrapped.
+pub fn resolve_dictionary<'a>(
+    strategy: &CompositeStrategy,
+    node: &Node<'a>,
+    ctx: &Context<'a>,
+) -> Value {
+    strategy.collect_dict_entries(node, ctx)
+}
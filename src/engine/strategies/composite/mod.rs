@@ -36,6 +36,13 @@ impl CompositeStrategy {
             }
             Some(Language::C | Language::Cpp) => languages::c_resolve_array(self, node, ctx),
             Some(Language::Java) => languages::java_resolve_array(self, node, ctx),
+            Some(Language::CSharp) => languages::csharp_resolve_array(self, node, ctx),
+            Some(Language::Ruby) => languages::ruby_resolve_array(self, node, ctx),
+            Some(Language::Php) => languages::php_resolve_array(self, node, ctx),
+            Some(Language::Swift) => languages::swift_resolve_array(self, node, ctx),
+            Some(Language::Kotlin) => languages::kotlin_resolve_array(self, node, ctx),
+            // Scala arrays and lists are built by calls, `Array(...)`
+            Some(Language::Scala) => Value::unextractable(UnresolvedSource::NotImplemented),
             None => Value::unextractable(UnresolvedSource::Unknown),
         }
     }
@@ -52,13 +59,21 @@ impl CompositeStrategy {
             }
             Some(Language::C | Language::Cpp) => languages::c_resolve_initializer(self, node, ctx),
             Some(Language::Java) => languages::java_resolve_object(self, node, ctx),
+            Some(Language::CSharp) => languages::csharp_resolve_object(self, node, ctx),
+            Some(Language::Ruby) => languages::ruby_resolve_hash(self, node, ctx),
+            Some(Language::Php) => languages::php_resolve_array(self, node, ctx),
+            Some(Language::Swift) => languages::swift_resolve_dictionary(self, node, ctx),
+            // Kotlin and Scala have no map literal; maps are built by
+            // `mapOf(...)` and `Map(...)`
+            Some(Language::Kotlin | Language::Scala) => {
+                Value::unextractable(UnresolvedSource::NotImplemented)
+            }
             None => Value::unextractable(UnresolvedSource::Unknown),
         }
     }
 
     pub(crate) fn collect_array_elements<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
-        let mut int_values = Vec::new();
-        let mut string_values = Vec::new();
+        let mut elements = Vec::new();
         let mut all_resolved = true;
         let mut expressions = Vec::new();
 
@@ -78,19 +93,26 @@ impl CompositeStrategy {
 
             let value = self.resolve_element(&child, ctx);
 
-            if value.is_resolved {
-                int_values.extend(value.int_values);
-                string_values.extend(value.string_values);
-            } else {
+            if !value.is_resolved {
                 all_resolved = false;
                 if !value.expression.is_empty() {
-                    expressions.push(value.expression);
+                    expressions.push(value.expression.clone());
                 } else {
                     expressions.push(ctx.get_node_text(&child));
                 }
             }
+            elements.push(value);
         }
 
+        let resolved = elements.iter().filter(|value| value.is_resolved);
+        let int_values: Vec<i64> = resolved
+            .clone()
+            .flat_map(|value| value.int_values.iter().copied())
+            .collect();
+        let string_values: Vec<String> = resolved
+            .flat_map(|value| value.string_values.iter().cloned())
+            .collect();
+
         if int_values.is_empty() && string_values.is_empty() && expressions.is_empty() {
             return Value::unextractable(UnresolvedSource::Unknown);
         }
@@ -106,7 +128,10 @@ impl CompositeStrategy {
                 } else {
                     expressions.join(", ")
                 },
+                evidence: Vec::new(),
+                spans: Vec::new(),
             }
+            .with_provenance_of(&elements)
         } else {
             Value::partial_expression(format!("[{}]", expressions.join(", ")))
         }
@@ -160,15 +185,48 @@ impl CompositeStrategy {
             string_values,
             source: String::new(),
             expression: format!("{{{}}}", field_strs.join(", ")),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
+        .with_provenance_of(fields.iter().map(|(_, value)| value))
     }
 
     pub(crate) fn collect_dict_entries<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
         let mut entries: Vec<(String, Value)> = Vec::new();
         let mut all_resolved = true;
 
+        // Swift `["rounds": 10_000]` lists keys and values as siblings
+        if node.kind() == "dictionary_literal" {
+            let mut keys = node.walk();
+            let mut values = node.walk();
+            for (key, value_node) in node
+                .children_by_field_name("key", &mut keys)
+                .zip(node.children_by_field_name("value", &mut values))
+            {
+                let key_clean = ctx.unquote_string(&ctx.get_node_text(&key));
+                let value = self.resolve_element(&value_node, ctx);
+                if !value.is_resolved {
+                    all_resolved = false;
+                }
+                entries.push((key_clean, value));
+            }
+        }
+
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
+            // PHP `'cost' => 12`
+            if child.kind() == "array_element_initializer" && child.named_child_count() == 2 {
+                if let (Some(key), Some(value_node)) = (child.named_child(0), child.named_child(1))
+                {
+                    let key_clean = ctx.unquote_string(&ctx.get_node_text(&key));
+                    let value = self.resolve_element(&value_node, ctx);
+                    if !value.is_resolved {
+                        all_resolved = false;
+                    }
+                    entries.push((key_clean, value));
+                }
+                continue;
+            }
             if child.kind() == "pair" {
                 if let Some(key) = child.child_by_field_name("key") {
                     if let Some(value_node) = child.child_by_field_name("value") {
@@ -201,7 +259,10 @@ impl CompositeStrategy {
             string_values,
             source: String::new(),
             expression: format!("dict with {} entries", entries.len()),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
+        .with_provenance_of(entries.iter().map(|(_, value)| value))
     }
 
     pub(crate) fn collect_object_properties<'a>(
@@ -241,7 +302,10 @@ impl CompositeStrategy {
             string_values,
             source: String::new(),
             expression: format!("object with {} properties", properties.len()),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
+        .with_provenance_of(properties.iter().map(|(_, value)| value))
     }
 
     pub(crate) fn collect_c_designated_initializers<'a>(
@@ -280,7 +344,10 @@ impl CompositeStrategy {
             string_values,
             source: String::new(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
+        .with_provenance_of(&values_found)
     }
 
     pub(crate) fn is_keyed_element(&self, node: &Node) -> bool {
@@ -356,6 +423,14 @@ impl CompositeStrategy {
     pub(crate) fn resolve_element<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
         let kind = node.kind();
 
+        // PHP wraps array elements in array_element_initializer, after the key
+        if kind == "array_element_initializer" {
+            return match node.named_child(node.named_child_count().saturating_sub(1)) {
+                Some(value) => self.resolve_element(&value, ctx),
+                None => Value::partial_expression(ctx.get_node_text(node)),
+            };
+        }
+
         // Go wraps array elements in literal_element - unwrap to get the actual value
         if kind == "literal_element" {
             let mut cursor = node.walk();
@@ -370,23 +445,23 @@ impl CompositeStrategy {
         if ctx.is_node_category(kind, NodeCategory::IntegerLiteral) {
             let text = ctx.get_node_text(node);
             if let Some(value) = ctx.parse_int_literal(&text) {
-                return Value::resolved_int(value);
+                return Value::resolved_int(value).with_span(ctx.span(node));
             }
         }
 
         if ctx.is_node_category(kind, NodeCategory::StringLiteral) {
             let text = ctx.get_node_text(node);
             let unquoted = ctx.unquote_string(&text);
-            return Value::resolved_string(unquoted);
+            return Value::resolved_string(unquoted).with_span(ctx.span(node));
         }
 
         if ctx.is_node_category(kind, NodeCategory::BooleanLiteral) {
             let text = ctx.get_node_text(node);
-            return Value::resolved_string(text);
+            return Value::resolved_string(text).with_span(ctx.span(node));
         }
 
         if ctx.is_node_category(kind, NodeCategory::NilLiteral) {
-            return Value::resolved_string(kind.to_string());
+            return Value::resolved_string(kind.to_string()).with_span(ctx.span(node));
         }
 
         if self.is_array_literal(node, ctx) {
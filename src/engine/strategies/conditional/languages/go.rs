@@ -0,0 +1,241 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// How statements assign a variable.
+enum Assigned<'a> {
+    Not,
+    /// On every path, with these values
+    Always(Vec<Node<'a>>),
+    /// With these values on some paths of an `if` chain; the others keep
+    /// the earlier value
+    Maybe(Vec<Node<'a>>),
+    /// On some paths, or in a way that is not followed (`x += 1`, `x++`)
+    Sometimes,
+}
+
+/// For a variable assigned in every branch of an `if`/`else` chain after
+/// its declaration, the values the branches assign:
+///
+/// ```go
+/// var iterations int
+/// if fips {
+///     iterations = 600000
+/// } else {
+///     iterations = 310000
+/// }
+/// pbkdf2.Key(pw, salt, iterations, 32, sha256.New)
+/// ```
+///
+/// A chain that assigns only on some paths, such as an `if` without `else`,
+/// adds its values to the one assigned or declared before it:
+/// `n := 4096; if fips { n = 8192 }` gives both. Anything else is left to
+/// the identifier strategy.
+pub fn get_branches<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<Vec<Node<'a>>> {
+    if node.kind() != "identifier" {
+        return None;
+    }
+    let name = ctx.get_node_text(node);
+    let mut current = *node;
+    // Values of chains that may have assigned the variable since
+    let mut maybe: Vec<Node<'a>> = Vec::new();
+    loop {
+        let parent = current.parent()?;
+        if matches!(
+            parent.kind(),
+            "function_declaration" | "method_declaration" | "func_literal"
+        ) {
+            return None;
+        }
+        let mut sibling = current.prev_named_sibling();
+        while let Some(statement) = sibling {
+            match assigned_by(statement, &name, ctx) {
+                Assigned::Not => {}
+                Assigned::Maybe(values) => maybe.extend(values),
+                Assigned::Always(mut values)
+                    if statement.kind() == "if_statement" || !maybe.is_empty() =>
+                {
+                    values.extend(maybe);
+                    return Some(values);
+                }
+                Assigned::Sometimes if !maybe.is_empty() => {
+                    let value = declared_value(statement, &name, ctx)?;
+                    maybe.push(value);
+                    return Some(maybe);
+                }
+                _ => return None,
+            }
+            sibling = statement.prev_named_sibling();
+        }
+        current = parent;
+    }
+}
+
+fn assigned_by<'a>(statement: Node<'a>, name: &str, ctx: &Context<'a>) -> Assigned<'a> {
+    match statement.kind() {
+        "assignment_statement" => match assigned_value(statement, name, ctx) {
+            Some(Some(value)) => Assigned::Always(vec![value]),
+            Some(None) => Assigned::Sometimes,
+            None => Assigned::Not,
+        },
+        "if_statement" => if_chain_assigns(statement, name, ctx),
+        // A declaration in a branch shadows the variable
+        _ if assigns_anywhere(statement, name, ctx) => Assigned::Sometimes,
+        _ => Assigned::Not,
+    }
+}
+
+fn if_chain_assigns<'a>(statement: Node<'a>, name: &str, ctx: &Context<'a>) -> Assigned<'a> {
+    let consequence = match statement.child_by_field_name("consequence") {
+        Some(block) => block_assigns(block, name, ctx),
+        None => Assigned::Not,
+    };
+    let alternative = match statement.child_by_field_name("alternative") {
+        Some(next) if next.kind() == "if_statement" => if_chain_assigns(next, name, ctx),
+        Some(block) => block_assigns(block, name, ctx),
+        None => Assigned::Not,
+    };
+    match (consequence, alternative) {
+        (Assigned::Not, Assigned::Not) => Assigned::Not,
+        (Assigned::Always(mut values), Assigned::Always(more)) => {
+            values.extend(more);
+            Assigned::Always(values)
+        }
+        (Assigned::Sometimes, _) | (_, Assigned::Sometimes) => Assigned::Sometimes,
+        (consequence, alternative) => {
+            let mut values = consequence.values();
+            values.extend(alternative.values());
+            Assigned::Maybe(values)
+        }
+    }
+}
+
+/// The last statement of `block` assigning `name` decides, together with
+/// the ones before it when it assigns only on some paths.
+fn block_assigns<'a>(block: Node<'a>, name: &str, ctx: &Context<'a>) -> Assigned<'a> {
+    let statements = match block.named_child(0) {
+        Some(list) if list.kind() == "statement_list" => list,
+        _ => block,
+    };
+    let mut cursor = statements.walk();
+    let children: Vec<Node<'a>> = statements.named_children(&mut cursor).collect();
+    let mut maybe = Vec::new();
+    for statement in children.into_iter().rev() {
+        match assigned_by(statement, name, ctx) {
+            Assigned::Not => {}
+            Assigned::Maybe(values) => maybe.extend(values),
+            Assigned::Always(mut values) => {
+                values.extend(maybe);
+                return Assigned::Always(values);
+            }
+            Assigned::Sometimes => return Assigned::Sometimes,
+        }
+    }
+    if maybe.is_empty() {
+        Assigned::Not
+    } else {
+        Assigned::Maybe(maybe)
+    }
+}
+
+impl<'a> Assigned<'a> {
+    fn values(self) -> Vec<Node<'a>> {
+        match self {
+            Assigned::Always(values) | Assigned::Maybe(values) => values,
+            Assigned::Not | Assigned::Sometimes => Vec::new(),
+        }
+    }
+}
+
+/// The value `statement` declares `name` with: `n := 4096` or
+/// `var n = 4096`.
+fn declared_value<'a>(statement: Node<'a>, name: &str, ctx: &Context<'a>) -> Option<Node<'a>> {
+    let (names, values) = match statement.kind() {
+        "short_var_declaration" => (
+            statement.child_by_field_name("left")?,
+            statement.child_by_field_name("right")?,
+        ),
+        "var_declaration" => {
+            let mut cursor = statement.walk();
+            let spec = statement.named_children(&mut cursor).find(|spec| {
+                let mut cursor = spec.walk();
+                let declares = spec
+                    .children_by_field_name("name", &mut cursor)
+                    .any(|target| ctx.get_node_text(&target) == name);
+                declares
+            })?;
+            let mut cursor = spec.walk();
+            let index = spec
+                .children_by_field_name("name", &mut cursor)
+                .position(|target| ctx.get_node_text(&target) == name)?;
+            let value = spec.child_by_field_name("value")?;
+            let mut cursor = value.walk();
+            let found = value.named_children(&mut cursor).nth(index);
+            return found;
+        }
+        _ => return None,
+    };
+    let mut cursor = names.walk();
+    let index = names
+        .named_children(&mut cursor)
+        .position(|target| ctx.get_node_text(&target) == name)?;
+    let mut cursor = values.walk();
+    let found = values.named_children(&mut cursor).nth(index);
+    found
+}
+
+/// The value a plain `=` assignment gives `name`: `None` when it does not
+/// assign it, `Some(None)` when the value is not known.
+fn assigned_value<'a>(
+    statement: Node<'a>,
+    name: &str,
+    ctx: &Context<'a>,
+) -> Option<Option<Node<'a>>> {
+    let left = statement.child_by_field_name("left")?;
+    let mut cursor = left.walk();
+    let index = left
+        .named_children(&mut cursor)
+        .position(|target| ctx.get_node_text(&target) == name)?;
+    let operator = statement
+        .child_by_field_name("operator")
+        .map(|op| ctx.get_node_text(&op));
+    if operator.as_deref() != Some("=") {
+        return Some(None);
+    }
+    let right = statement.child_by_field_name("right")?;
+    let mut cursor = right.walk();
+    let value = right.named_children(&mut cursor).nth(index);
+    Some(value)
+}
+
+fn assigns_anywhere(node: Node, name: &str, ctx: &Context) -> bool {
+    let assigns = match node.kind() {
+        "assignment_statement" | "short_var_declaration" => {
+            node.child_by_field_name("left").is_some_and(|left| {
+                let mut cursor = left.walk();
+                let found = left
+                    .named_children(&mut cursor)
+                    .any(|target| ctx.get_node_text(&target) == name);
+                found
+            })
+        }
+        "inc_statement" | "dec_statement" => node
+            .named_child(0)
+            .is_some_and(|target| ctx.get_node_text(&target) == name),
+        "var_spec" => {
+            let mut cursor = node.walk();
+            let found = node
+                .children_by_field_name("name", &mut cursor)
+                .any(|target| ctx.get_node_text(&target) == name);
+            found
+        }
+        _ => false,
+    };
+    if assigns {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+        .into_iter()
+        .any(|child| assigns_anywhere(child, name, ctx))
+}
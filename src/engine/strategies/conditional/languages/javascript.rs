@@ -0,0 +1,12 @@
+use tree_sitter::Node;
+
+/// `cond ? a : b`
+pub fn get_branches<'a>(node: &Node<'a>) -> Option<Vec<Node<'a>>> {
+    if node.kind() != "ternary_expression" {
+        return None;
+    }
+    Some(vec![
+        node.child_by_field_name("consequence")?,
+        node.child_by_field_name("alternative")?,
+    ])
+}
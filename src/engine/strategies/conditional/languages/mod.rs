@@ -0,0 +1,9 @@
+pub mod go;
+pub mod javascript;
+pub mod python;
+pub mod rust;
+
+pub use go::get_branches as go_get_branches;
+pub use javascript::get_branches as js_get_branches;
+pub use python::get_branches as python_get_branches;
+pub use rust::get_branches as rust_get_branches;
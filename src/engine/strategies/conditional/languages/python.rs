@@ -0,0 +1,9 @@
+use tree_sitter::Node;
+
+/// `a if cond else b`: the value and the `else` value.
+pub fn get_branches<'a>(node: &Node<'a>) -> Option<Vec<Node<'a>>> {
+    if node.kind() != "conditional_expression" {
+        return None;
+    }
+    Some(vec![node.named_child(0)?, node.named_child(2)?])
+}
@@ -0,0 +1,29 @@
+use tree_sitter::Node;
+
+/// `if cond { a } else if other { b } else { c }`: the tail expression of
+/// each block. An `if` without `else` has no value.
+pub fn get_branches<'a>(node: &Node<'a>) -> Option<Vec<Node<'a>>> {
+    if node.kind() != "if_expression" {
+        return None;
+    }
+    let mut branches = vec![tail_expression(node.child_by_field_name("consequence")?)?];
+    let alternative = node.child_by_field_name("alternative")?.named_child(0)?;
+    match alternative.kind() {
+        "if_expression" => branches.extend(get_branches(&alternative)?),
+        _ => branches.push(tail_expression(alternative)?),
+    }
+    Some(branches)
+}
+
+fn tail_expression(block: Node) -> Option<Node> {
+    let mut cursor = block.walk();
+    let last = block
+        .named_children(&mut cursor)
+        .filter(|child| !child.kind().ends_with("comment"))
+        .last()?;
+    let is_statement = matches!(
+        last.kind(),
+        "expression_statement" | "let_declaration" | "empty_statement" | "label"
+    ) || last.kind().ends_with("_item");
+    (!is_statement).then_some(last)
+}
@@ -0,0 +1,258 @@
+use crate::engine::{Context, EvidenceKind, Language, Strategy, UnresolvedSource, Value};
+use tree_sitter::Node;
+
+mod languages;
+
+/// Resolves a value chosen by a condition to the values of all its
+/// branches: Python `a if cond else b`, JavaScript and TypeScript
+/// `cond ? a : b`, Rust `if cond { a } else { b }`, and in Go a variable
+/// assigned in every branch of an `if`/`else` chain. The result is resolved
+/// when every branch is.
+pub struct ConditionalStrategy;
+
+impl Default for ConditionalStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConditionalStrategy {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn get_branches<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Option<Vec<Node<'a>>> {
+        let lang = ctx.node_types()?.language();
+
+        match lang {
+            Language::Go => languages::go_get_branches(node, ctx),
+            Language::Python => languages::python_get_branches(node),
+            Language::Rust => languages::rust_get_branches(node),
+            Language::JavaScript | Language::TypeScript => languages::js_get_branches(node),
+            _ => None,
+        }
+    }
+
+    fn resolve_branch<'a>(&self, branch: &Node<'a>, ctx: &Context<'a>) -> Value {
+        ctx.resolve(branch)
+    }
+}
+
+impl Strategy for ConditionalStrategy {
+    fn name(&self) -> &'static str {
+        "conditional"
+    }
+
+    fn can_handle<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> bool {
+        self.get_branches(node, ctx).is_some()
+    }
+
+    fn resolve<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
+        let Some(branches) = self.get_branches(node, ctx) else {
+            return Value::unextractable(UnresolvedSource::Unknown);
+        };
+
+        // A Go variable's branches are the assignments to it
+        let variable = (node.kind() == "identifier").then(|| ctx.get_node_text(node));
+        let values: Vec<Value> = branches
+            .iter()
+            .map(|branch| {
+                let value = self.resolve_branch(branch, ctx);
+                match &variable {
+                    Some(name) => {
+                        value.with_evidence(ctx.evidence(EvidenceKind::Local, name, branch))
+                    }
+                    None => value,
+                }
+            })
+            .collect();
+
+        if values.iter().all(|value| !value.is_resolved) {
+            return values
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| Value::unextractable(UnresolvedSource::Unknown));
+        }
+        Value::merge(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tree_sitter::Tree;
+
+    fn parse(source: &str, language: tree_sitter::Language) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&language).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    fn context<'a>(tree: &'a Tree, source: &'a [u8], file: &str, language: &str) -> Context<'a> {
+        Context::new(
+            tree,
+            source,
+            file.to_string(),
+            language.to_string(),
+            HashMap::new(),
+        )
+    }
+
+    fn find_node<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+        if node.kind() == kind {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.children(&mut cursor).collect();
+        children
+            .into_iter()
+            .find_map(|child| find_node(child, kind))
+    }
+
+    /// The last identifier named `name`, which is where it is used.
+    fn find_use<'a>(node: Node<'a>, name: &str, source: &[u8]) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.children(&mut cursor).collect();
+        children
+            .into_iter()
+            .rev()
+            .find_map(|child| find_use(child, name, source))
+            .or_else(|| {
+                (node.kind() == "identifier" && node.utf8_text(source).ok() == Some(name))
+                    .then_some(node)
+            })
+    }
+
+    #[test]
+    fn test_strategy_name() {
+        assert_eq!(ConditionalStrategy::new().name(), "conditional");
+    }
+
+    #[test]
+    fn test_python_conditional_expression() {
+        let source = "n = 600000 if fips else 310000\n";
+        let tree = parse(source, tree_sitter_python::LANGUAGE.into());
+        let ctx = context(&tree, source.as_bytes(), "test.py", "python");
+        let node = find_node(tree.root_node(), "conditional_expression").unwrap();
+
+        let strategy = ConditionalStrategy::new();
+        assert!(strategy.can_handle(&node, &ctx));
+        let value = strategy.resolve(&node, &ctx);
+        assert!(value.is_resolved);
+        assert_eq!(value.int_values, vec![310000, 600000]);
+    }
+
+    #[test]
+    fn test_javascript_ternary_with_unresolved_branch() {
+        let source = "const n = fips ? 600000 : process.env.ITERATIONS;\n";
+        let tree = parse(source, tree_sitter_javascript::LANGUAGE.into());
+        let ctx = context(&tree, source.as_bytes(), "test.js", "javascript");
+        let node = find_node(tree.root_node(), "ternary_expression").unwrap();
+
+        let value = ConditionalStrategy::new().resolve(&node, &ctx);
+        assert!(!value.is_resolved);
+        assert_eq!(value.source, UnresolvedSource::MixedResolution.as_str());
+    }
+
+    #[test]
+    fn test_rust_if_else_if_expression() {
+        let source = r#"
+fn main() {
+    let alg = if fips { "sha256" } else if legacy { "sha1" } else { "sha512" };
+}"#;
+        let tree = parse(source, tree_sitter_rust::LANGUAGE.into());
+        let ctx = context(&tree, source.as_bytes(), "test.rs", "rust");
+        let node = find_node(tree.root_node(), "if_expression").unwrap();
+
+        let value = ConditionalStrategy::new().resolve(&node, &ctx);
+        assert!(value.is_resolved);
+        assert_eq!(value.string_values, vec!["sha1", "sha256", "sha512"]);
+
+        let source = "fn main() { if fips { run(); } }";
+        let tree = parse(source, tree_sitter_rust::LANGUAGE.into());
+        let ctx = context(&tree, source.as_bytes(), "test.rs", "rust");
+        let node = find_node(tree.root_node(), "if_expression").unwrap();
+        assert!(!ConditionalStrategy::new().can_handle(&node, &ctx));
+    }
+
+    #[test]
+    fn test_go_if_else_assignments() {
+        let source = r#"
+package main
+
+func main() {
+    var iterations int
+    if fips {
+        iterations = 600000
+    } else if legacy {
+        iterations = 1000
+    } else {
+        iterations = 310000
+    }
+    use(iterations)
+}"#;
+        let tree = parse(source, tree_sitter_go::LANGUAGE.into());
+        let ctx = context(&tree, source.as_bytes(), "test.go", "go");
+        let node = find_use(tree.root_node(), "iterations", source.as_bytes()).unwrap();
+
+        let strategy = ConditionalStrategy::new();
+        assert!(strategy.can_handle(&node, &ctx));
+        let value = strategy.resolve(&node, &ctx);
+        assert!(value.is_resolved);
+        assert_eq!(value.int_values, vec![1000, 310000, 600000]);
+        assert_eq!(value.evidence.len(), 3);
+        assert!(value
+            .evidence
+            .iter()
+            .all(|step| step.kind == EvidenceKind::Local));
+    }
+
+    #[test]
+    fn test_go_if_without_else_keeps_prior_value() {
+        for (body, expected) in [
+            (
+                "n := 4096\n    if len(os.Args) > 1 {\n        n = 8192\n    }",
+                vec![4096, 8192],
+            ),
+            (
+                "var n = 4096\n    if fips {\n        n = 8192\n    } else if legacy {\n        n = 1000\n    }",
+                vec![1000, 4096, 8192],
+            ),
+            (
+                "var n int\n    n = 4096\n    if fips {\n        n = 8192\n    }",
+                vec![4096, 8192],
+            ),
+        ] {
+            let source = format!("package main\n\nfunc main() {{\n    {body}\n    use(n)\n}}");
+            let tree = parse(&source, tree_sitter_go::LANGUAGE.into());
+            let ctx = context(&tree, source.as_bytes(), "test.go", "go");
+            let node = find_use(tree.root_node(), "n", source.as_bytes()).unwrap();
+
+            let strategy = ConditionalStrategy::new();
+            assert!(strategy.can_handle(&node, &ctx), "{body}");
+            let value = strategy.resolve(&node, &ctx);
+            assert!(value.is_resolved, "{body}");
+            assert_eq!(value.int_values, expected, "{body}");
+        }
+    }
+
+    #[test]
+    fn test_go_partial_assignments_left_to_identifier() {
+        for body in [
+            // A branch declares its own variable
+            "iterations := 1000\n    if fips {\n        iterations = 600000\n    } else {\n        iterations := 5\n        _ = iterations\n    }",
+            // Assigned again after the chain
+            "var iterations int\n    if fips {\n        iterations = 600000\n    } else {\n        iterations = 310000\n    }\n    iterations = 42",
+        ] {
+            let source = format!("package main\n\nfunc main() {{\n    {body}\n    use(iterations)\n}}");
+            let tree = parse(&source, tree_sitter_go::LANGUAGE.into());
+            let ctx = context(&tree, source.as_bytes(), "test.go", "go");
+            let node = find_use(tree.root_node(), "iterations", source.as_bytes()).unwrap();
+            assert!(
+                !ConditionalStrategy::new().can_handle(&node, &ctx),
+                "{body}"
+            );
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use crate::engine::{Context, Language, NodeCategory, Resolver, Strategy, UnresolvedSource, Value};
+use crate::engine::{
+    Context, EvidenceKind, Language, NodeCategory, Strategy, UnresolvedSource, Value,
+};
 use tree_sitter::Node;
 
 mod languages;
 
-pub struct IdentifierStrategy {
-    resolver: Option<Resolver>,
-}
+pub struct IdentifierStrategy;
 
 impl Default for IdentifierStrategy {
     fn default() -> Self {
@@ -15,13 +15,7 @@ impl Default for IdentifierStrategy {
 
 impl IdentifierStrategy {
     pub fn new() -> Self {
-        Self { resolver: None }
-    }
-
-    pub fn with_resolver(resolver: Resolver) -> Self {
-        Self {
-            resolver: Some(resolver),
-        }
+        Self
     }
 
     fn find_enclosing_function<'a>(&self, node: Node<'a>, ctx: &Context<'a>) -> Option<Node<'a>> {
@@ -60,6 +54,12 @@ impl IdentifierStrategy {
                 self.find_c_declaration(name, search_node, use_position, ctx)
             }
             Language::Java => self.find_java_declaration(name, search_node, use_position, ctx),
+            Language::CSharp => self.find_csharp_declaration(name, search_node, use_position, ctx),
+            Language::Ruby => self.find_ruby_declaration(name, search_node, use_position, ctx),
+            Language::Php => self.find_php_declaration(name, search_node, use_position, ctx),
+            Language::Swift => self.find_swift_declaration(name, search_node, use_position, ctx),
+            Language::Kotlin => self.find_kotlin_declaration(name, search_node, use_position, ctx),
+            Language::Scala => self.find_scala_declaration(name, search_node, use_position, ctx),
         }
     }
 
@@ -379,16 +379,61 @@ impl IdentifierStrategy {
         name: &str,
         ctx: &Context<'a>,
     ) -> Option<Node<'a>> {
-        let declarator = node.child_by_field_name("declarator")?;
-
-        if declarator.kind() == "init_declarator" {
-            let decl_name = declarator.child_by_field_name("declarator")?;
+        // `int a = 1, iter = 2;` has a declarator per name
+        let mut cursor = node.walk();
+        let declarators: Vec<Node<'a>> = node
+            .children_by_field_name("declarator", &mut cursor)
+            .collect();
+        declarators.into_iter().find_map(|declarator| {
+            if declarator.kind() != "init_declarator" {
+                return None;
+            }
+            let decl_name = c_declared_name(declarator)?;
             if ctx.get_node_text(&decl_name) == name {
-                return declarator.child_by_field_name("value");
+                declarator.child_by_field_name("value")
+            } else {
+                None
             }
-        }
+        })
+    }
 
-        None
+    /// A `#define` or a declaration at file level, including in
+    /// conditional blocks and C++ namespaces. Macros are defined before use, like variables,
+    /// so both must precede `use_position`; the last one wins.
+    fn find_c_file_level_const<'a>(
+        &self,
+        name: &str,
+        root: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = root.walk();
+        let mut result = None;
+        for child in root.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                break;
+            }
+
+            let found = match child.kind() {
+                "preproc_def" => child
+                    .child_by_field_name("name")
+                    .filter(|n| ctx.get_node_text(n) == name)
+                    .and_then(|_| child.child_by_field_name("value")),
+                "declaration" => self.extract_c_declaration(child, name, ctx),
+                "preproc_ifdef" | "preproc_if" | "preproc_else" | "preproc_elif" => {
+                    self.find_c_file_level_const(name, child, use_position, ctx)
+                }
+                // C++ `namespace crypto { ... }`, named or anonymous
+                "namespace_definition" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_c_file_level_const(name, body, use_position, ctx)),
+                _ => None,
+            };
+            if found.is_some() {
+                result = found;
+            }
+        }
+        result
     }
 
     fn find_java_declaration<'a>(
@@ -451,6 +496,391 @@ impl IdentifierStrategy {
         None
     }
 
+    fn find_csharp_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "local_declaration_statement" => self.extract_csharp_var_decl(child, name, ctx),
+                "block" => self.find_csharp_declaration(name, child, use_position, ctx),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// The initial value of `name` in a `variable_declaration`, wrapped in
+    /// a local declaration statement or a field declaration.
+    fn extract_csharp_var_decl<'a>(
+        &self,
+        node: Node<'a>,
+        name: &str,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        let declaration = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "variable_declaration")?;
+
+        let mut cursor = declaration.walk();
+        let declarator = declaration
+            .named_children(&mut cursor)
+            .filter(|child| child.kind() == "variable_declarator")
+            .find(|declarator| {
+                declarator
+                    .child_by_field_name("name")
+                    .is_some_and(|n| ctx.get_node_text(&n) == name)
+            })?;
+
+        csharp_declarator_value(declarator)
+    }
+
+    /// A field or property of a type in `node`, or nested in a type or
+    /// namespace. Members may be declared after the methods that use them,
+    /// so position is ignored.
+    fn find_csharp_member<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "field_declaration" => self.extract_csharp_var_decl(child, name, ctx),
+                // `static int Rounds => 4;` or `static int Rounds { get; } = 4;`
+                "property_declaration" => child
+                    .child_by_field_name("name")
+                    .filter(|n| ctx.get_node_text(n) == name)
+                    .and_then(|_| child.child_by_field_name("value"))
+                    .map(|value| match value.kind() {
+                        "arrow_expression_clause" => value.named_child(0).unwrap_or(value),
+                        _ => value,
+                    }),
+                "class_declaration"
+                | "struct_declaration"
+                | "record_declaration"
+                | "interface_declaration"
+                | "namespace_declaration" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_csharp_member(name, body, ctx)),
+                "file_scoped_namespace_declaration" => self.find_csharp_member(name, child, ctx),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    fn find_ruby_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "assignment" => child
+                    .child_by_field_name("left")
+                    .filter(|left| left.kind() == "identifier" && ctx.get_node_text(left) == name)
+                    .and_then(|_| child.child_by_field_name("right")),
+                "begin" | "body_statement" => {
+                    self.find_ruby_declaration(name, child, use_position, ctx)
+                }
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// A constant assigned in `node` or in a module or class nested in it,
+    /// or a top-level local assigned before the use. Constants may be
+    /// assigned after the methods that use them, so their position is
+    /// ignored.
+    fn find_ruby_constant<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "assignment" => child
+                    .child_by_field_name("left")
+                    .filter(|left| match left.kind() {
+                        "constant" => true,
+                        "identifier" => {
+                            node.kind() == "program" && child.start_byte() < use_position
+                        }
+                        _ => false,
+                    })
+                    .filter(|left| ctx.get_node_text(left) == name)
+                    .and_then(|_| child.child_by_field_name("right")),
+                "module" | "class" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_ruby_constant(name, body, use_position, ctx)),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// The last assignment to the variable `name` (`$iv`) before the use.
+    fn find_php_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut result = None;
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "expression_statement" => child
+                    .named_child(0)
+                    .and_then(|assign| php_assignment_value(assign, name, ctx)),
+                "compound_statement" => self.find_php_declaration(name, child, use_position, ctx),
+                _ => None,
+            };
+            if found.is_some() {
+                result = found;
+            }
+        }
+        result
+    }
+
+    /// A constant declared with `const` or `define()` in `node` or in a
+    /// namespace or class nested in it, or a top-level variable assigned
+    /// before the use. Constants may be declared after the functions that
+    /// use them, so their position is ignored.
+    fn find_php_constant<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "const_declaration" => php_const_value(child, name, ctx),
+                "expression_statement" => child.named_child(0).and_then(|expr| match expr.kind() {
+                    "function_call_expression" => php_define_value(expr, name, ctx),
+                    _ if node.kind() == "program" && child.start_byte() < use_position => {
+                        php_assignment_value(expr, name, ctx)
+                    }
+                    _ => None,
+                }),
+                "namespace_definition" => match child.child_by_field_name("body") {
+                    Some(body) => self.find_php_constant(name, body, use_position, ctx),
+                    None => None,
+                },
+                "class_declaration" | "interface_declaration" | "enum_declaration" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_php_constant(name, body, use_position, ctx)),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    fn find_swift_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut result = None;
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "property_declaration" => swift_property_value(child, name, ctx),
+                "assignment" => swift_assignment_value(child, name, ctx),
+                "statements" => self.find_swift_declaration(name, child, use_position, ctx),
+                _ => None,
+            };
+            if found.is_some() {
+                result = found;
+            }
+        }
+        result
+    }
+
+    /// A property declared at the top level of `node` or in a type nested
+    /// in it. Globals and static properties are initialized on first use,
+    /// so their position is ignored.
+    fn find_swift_constant<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "property_declaration" => swift_property_value(child, name, ctx),
+                "class_declaration" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_swift_constant(name, body, ctx)),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    fn find_kotlin_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut result = None;
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "property_declaration" => kotlin_property_value(child, name, ctx),
+                "assignment" => kotlin_assignment_value(child, name, ctx),
+                "function_body" | "block" => {
+                    self.find_kotlin_declaration(name, child, use_position, ctx)
+                }
+                _ => None,
+            };
+            if found.is_some() {
+                result = found;
+            }
+        }
+        result
+    }
+
+    /// A property declared at the top level of `node`, or in an object,
+    /// companion object or class nested in it. Position is ignored, as
+    /// for Java fields.
+    fn find_kotlin_constant<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "property_declaration" => kotlin_property_value(child, name, ctx),
+                "class_declaration" | "object_declaration" | "companion_object" => {
+                    kotlin_class_body(child)
+                        .and_then(|body| self.find_kotlin_constant(name, body, ctx))
+                }
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    fn find_scala_declaration<'a>(
+        &self,
+        name: &str,
+        scope_node: Node<'a>,
+        use_position: usize,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut result = None;
+        let mut cursor = scope_node.walk();
+        for child in scope_node.children(&mut cursor) {
+            if child.start_byte() >= use_position {
+                continue;
+            }
+
+            let found = match child.kind() {
+                "val_definition" | "var_definition" => scala_definition_value(child, name, ctx),
+                "assignment_expression" => child
+                    .child_by_field_name("left")
+                    .filter(|left| ctx.get_node_text(left) == name)
+                    .and_then(|_| child.child_by_field_name("right")),
+                "block" => self.find_scala_declaration(name, child, use_position, ctx),
+                _ => None,
+            };
+            if found.is_some() {
+                result = found;
+            }
+        }
+        result
+    }
+
+    /// A `val` defined at the top level of `node`, or in an object, class
+    /// or trait nested in it. Position is ignored, as for Java fields.
+    fn find_scala_constant<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let found = match child.kind() {
+                "val_definition" => scala_definition_value(child, name, ctx),
+                "object_definition" | "class_definition" | "trait_definition" => child
+                    .child_by_field_name("body")
+                    .and_then(|body| self.find_scala_constant(name, body, ctx)),
+                _ => None,
+            };
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
     fn find_file_level_constant<'a>(
         &self,
         name: &str,
@@ -469,10 +899,57 @@ impl IdentifierStrategy {
             Language::JavaScript | Language::TypeScript => {
                 self.find_js_file_level_const(name, root, use_position, ctx)
             }
-            _ => None,
+            Language::Java => self.find_java_field(name, root, ctx),
+            Language::CSharp => self.find_csharp_member(name, root, ctx),
+            Language::Ruby => self.find_ruby_constant(name, root, use_position, ctx),
+            Language::Php => self.find_php_constant(name, root, use_position, ctx),
+            Language::Swift => self.find_swift_constant(name, root, ctx),
+            Language::Kotlin => self.find_kotlin_constant(name, root, ctx),
+            Language::Scala => self.find_scala_constant(name, root, ctx),
+            Language::C | Language::Cpp => {
+                self.find_c_file_level_const(name, root, use_position, ctx)
+            }
         }
     }
 
+    /// A field of a class in `node` or nested in one. Fields may be
+    /// declared after the methods that use them, so position is ignored.
+    fn find_java_field<'a>(
+        &self,
+        name: &str,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "field_declaration" | "constant_declaration" => {
+                    if let Some(value) = self.extract_java_var_decl(child, name, ctx) {
+                        return Some(value);
+                    }
+                }
+                "class_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+                | "record_declaration" => {
+                    let value = child
+                        .child_by_field_name("body")
+                        .and_then(|body| self.find_java_field(name, body, ctx));
+                    if value.is_some() {
+                        return value;
+                    }
+                }
+                "enum_body_declarations" => {
+                    if let Some(value) = self.find_java_field(name, child, ctx) {
+                        return Some(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
     fn find_python_file_level_const<'a>(
         &self,
         name: &str,
@@ -572,7 +1049,42 @@ impl IdentifierStrategy {
             Some(Language::Python) => "parameters",
             Some(Language::Rust) => "parameters",
             Some(Language::JavaScript | Language::TypeScript) => "parameters",
-            Some(Language::Java) => "parameters",
+            Some(Language::Java | Language::CSharp | Language::Scala) => "parameters",
+            Some(Language::Ruby | Language::Php) => "parameters",
+            // Swift lists its parameters on the declaration itself
+            Some(Language::Swift) => {
+                let mut cursor = function_node.walk();
+                let is_parameter = function_node
+                    .children(&mut cursor)
+                    .filter(|child| child.kind() == "parameter")
+                    .any(|param| self.extract_param_name(&param, ctx).as_deref() == Some(name));
+                return is_parameter;
+            }
+            // Kotlin keeps its parameters in an unlabeled list
+            Some(Language::Kotlin) => {
+                let mut cursor = function_node.walk();
+                let is_parameter = function_node
+                    .children(&mut cursor)
+                    .filter(|child| child.kind() == "function_value_parameters")
+                    .any(|params| self.check_param_list_for_name(params, name, ctx));
+                return is_parameter;
+            }
+            Some(Language::C | Language::Cpp) => {
+                // The parameters belong to the function_declarator, which
+                // may be nested in a pointer_declarator (`char *f(...)`)
+                let mut declarator = function_node.child_by_field_name("declarator");
+                while let Some(node) = declarator {
+                    if node.kind() == "function_declarator" {
+                        return node
+                            .child_by_field_name("parameters")
+                            .is_some_and(|params| {
+                                self.check_param_list_for_name(params, name, ctx)
+                            });
+                    }
+                    declarator = node.child_by_field_name("declarator");
+                }
+                return false;
+            }
             _ => return false,
         };
 
@@ -602,7 +1114,10 @@ impl IdentifierStrategy {
         match param.kind() {
             "identifier" => Some(ctx.get_node_text(param)),
             "parameter_declaration" => {
-                let name_node = param.child_by_field_name("name")?;
+                // Go names it, C wraps it in the declarator (`const char *pw`)
+                let name_node = param
+                    .child_by_field_name("name")
+                    .or_else(|| c_declared_name(*param))?;
                 Some(ctx.get_node_text(&name_node))
             }
             "typed_parameter" | "default_parameter" | "typed_default_parameter" => param
@@ -617,17 +1132,493 @@ impl IdentifierStrategy {
         }
     }
 
+    /// Whether `name` is bound by a loop enclosing the use, such as `i` in
+    /// `for i := 0; i < n; i++` or `alg` in `for alg in algorithms`.
+    fn is_loop_variable<'a>(&self, name: &str, node: Node<'a>, ctx: &Context<'a>) -> bool {
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            if ctx.is_node_category(ancestor.kind(), NodeCategory::FunctionDeclaration) {
+                break;
+            }
+            if LOOP_KINDS.contains(&ancestor.kind())
+                && loop_bindings(ancestor)
+                    .into_iter()
+                    .any(|binding| binds_name(binding, name, ctx))
+            {
+                return true;
+            }
+            current = ancestor.parent();
+        }
+        false
+    }
+
     fn resolve_value_node<'a>(&self, node: Node<'a>, ctx: &Context<'a>) -> Value {
-        // Use resolver if available for full strategy chain
-        if let Some(ref resolver) = self.resolver {
-            return resolver.resolve(&node, ctx);
+        ctx.resolve(&node)
+    }
+}
+
+const LOOP_KINDS: &[&str] = &[
+    "for_statement",
+    "foreach_statement",
+    "for_in_statement",
+    "for_expression",
+    "enhanced_for_statement",
+    "for_range_loop",
+];
+
+/// The parts of a loop header that declare its variables: Python, JS, Go
+/// range and C# foreach `left`, Rust `pattern`, C, JS and C# `initializer`,
+/// Java `init` and `name`, C++ range `declarator`, and PHP foreach `as`
+/// bindings.
+fn loop_bindings(loop_node: Node) -> Vec<Node> {
+    let mut bindings: Vec<Node> = [
+        "left",
+        "pattern",
+        "initializer",
+        "init",
+        "declarator",
+        "name",
+        "item",
+    ]
+    .iter()
+    .filter_map(|field| loop_node.child_by_field_name(field))
+    .collect();
+
+    // Go keeps the header in a for_clause or range_clause child
+    let mut cursor = loop_node.walk();
+    for child in loop_node.named_children(&mut cursor) {
+        let binding = match child.kind() {
+            "for_clause" => child.child_by_field_name("initializer"),
+            "range_clause" => child.child_by_field_name("left"),
+            _ => None,
+        };
+        bindings.extend(binding);
+    }
+
+    // PHP foreach keeps its bindings unlabeled after the iterated value
+    if loop_node.kind() == "foreach_statement" && bindings.is_empty() {
+        let body = loop_node.child_by_field_name("body");
+        let mut cursor = loop_node.walk();
+        bindings.extend(
+            loop_node
+                .named_children(&mut cursor)
+                .skip(1)
+                .filter(|child| Some(*child) != body),
+        );
+    }
+    bindings
+}
+
+/// Whether `binding` declares `name`, ignoring initial values and types.
+fn binds_name(binding: Node, name: &str, ctx: &Context) -> bool {
+    if matches!(
+        binding.kind(),
+        "identifier" | "variable_name" | "simple_identifier"
+    ) {
+        return ctx.get_node_text(&binding) == name;
+    }
+    // C# declarators keep their value unlabeled next to the name
+    if binding.kind() == "variable_declarator" {
+        if let Some(declared) = binding.child_by_field_name("name") {
+            return binds_name(declared, name, ctx);
+        }
+    }
+
+    let mut cursor = binding.walk();
+    let children: Vec<(Node, Option<&str>)> = binding
+        .children(&mut cursor)
+        .enumerate()
+        .map(|(i, child)| (child, binding.field_name_for_child(i as u32)))
+        .collect();
+    children.into_iter().any(|(child, field)| {
+        !matches!(field, Some("value" | "right" | "type")) && binds_name(child, name, ctx)
+    })
+}
+
+/// The value of the member `member` of the C# type named `type_name`
+/// declared in `node` or nested in it, as in `Params.Iterations`.
+pub(crate) fn csharp_type_member<'a>(
+    type_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let found = match child.kind() {
+            "class_declaration" | "struct_declaration" | "record_declaration" => {
+                let Some(body) = child.child_by_field_name("body") else {
+                    continue;
+                };
+                let is_type = child
+                    .child_by_field_name("name")
+                    .is_some_and(|name| ctx.get_node_text(&name) == type_name);
+                if is_type {
+                    IdentifierStrategy::new().find_csharp_member(member, body, ctx)
+                } else {
+                    csharp_type_member(type_name, member, body, ctx)
+                }
+            }
+            "namespace_declaration" => child
+                .child_by_field_name("body")
+                .and_then(|body| csharp_type_member(type_name, member, body, ctx)),
+            "file_scoped_namespace_declaration" => {
+                csharp_type_member(type_name, member, child, ctx)
+            }
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// The value of constant `member` of the Ruby module or class `module_name`
+/// (`Crypto` or `App::Crypto`) declared in `node` or nested in it.
+pub(crate) fn ruby_module_constant<'a>(
+    module_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let short_name = module_name.rsplit("::").next().unwrap_or(module_name);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !matches!(child.kind(), "module" | "class") {
+            continue;
+        }
+        let Some(body) = child.child_by_field_name("body") else {
+            continue;
+        };
+        let is_module = child
+            .child_by_field_name("name")
+            .is_some_and(|name| ctx.get_node_text(&name).rsplit("::").next() == Some(short_name));
+        let found = if is_module {
+            IdentifierStrategy::new().find_ruby_constant(member, body, 0, ctx)
+        } else {
+            ruby_module_constant(module_name, member, body, ctx)
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// The value of constant `member` of the PHP class, interface or enum
+/// `class_name` (`Params` or `\App\Params`) declared in `node` or nested in
+/// it, as in `Params::CIPHER`.
+pub(crate) fn php_class_constant<'a>(
+    class_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let short_name = class_name.rsplit('\\').next().unwrap_or(class_name);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let found = match child.kind() {
+            "class_declaration" | "interface_declaration" | "enum_declaration" => {
+                let Some(body) = child.child_by_field_name("body") else {
+                    continue;
+                };
+                let is_class = child
+                    .child_by_field_name("name")
+                    .is_some_and(|name| ctx.get_node_text(&name) == short_name);
+                if !is_class {
+                    continue;
+                }
+                let mut members = body.walk();
+                let value = body
+                    .named_children(&mut members)
+                    .filter(|member_node| member_node.kind() == "const_declaration")
+                    .find_map(|declaration| php_const_value(declaration, member, ctx));
+                value
+            }
+            "namespace_definition" => child
+                .child_by_field_name("body")
+                .and_then(|body| php_class_constant(class_name, member, body, ctx)),
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
         }
+    }
+    None
+}
+
+/// The value of the member `member` of the Swift type named `type_name`
+/// (`Params` or `AES.GCM`), a struct, class, enum or extension declared in
+/// `node` or nested in it, as in `Params.iterations`.
+pub(crate) fn swift_type_member<'a>(
+    type_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let short_name = type_name.rsplit('.').next().unwrap_or(type_name);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "class_declaration" {
+            continue;
+        }
+        let Some(body) = child.child_by_field_name("body") else {
+            continue;
+        };
+        let is_type = child
+            .child_by_field_name("name")
+            .is_some_and(|name| ctx.get_node_text(&name) == short_name);
+        let mut members = body.walk();
+        let found = if is_type {
+            body.named_children(&mut members)
+                .filter(|member_node| member_node.kind() == "property_declaration")
+                .find_map(|declaration| swift_property_value(declaration, member, ctx))
+        } else {
+            swift_type_member(type_name, member, body, ctx)
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// The initial value `declaration` gives `name` (`let rounds = 10`).
+fn swift_property_value<'a>(
+    declaration: Node<'a>,
+    name: &str,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let pattern = declaration.child_by_field_name("name")?;
+    let bound = pattern
+        .child_by_field_name("bound_identifier")
+        .unwrap_or(pattern);
+    if ctx.get_node_text(&bound) != name {
+        return None;
+    }
+    declaration.child_by_field_name("value")
+}
+
+/// The value a plain `name = value` assignment gives `name`.
+fn swift_assignment_value<'a>(assign: Node<'a>, name: &str, ctx: &Context<'a>) -> Option<Node<'a>> {
+    let operator = assign.child_by_field_name("operator")?;
+    let target = assign.child_by_field_name("target")?;
+    if ctx.get_node_text(&operator) != "=" || ctx.get_node_text(&target) != name {
+        return None;
+    }
+    assign.child_by_field_name("result")
+}
 
-        // Create a new resolver for full strategy chain resolution
-        // This enables proper chaining through composite/array strategies
-        let resolver = Resolver::new();
-        resolver.resolve(&node, ctx)
+/// The value of the member `member` of the Kotlin object or class named
+/// `type_name` (`Params` or `Vault.Params`), declared in `node` or nested in
+/// it, as in `Params.ITERATIONS`. A class's members are those of its
+/// companion object.
+pub(crate) fn kotlin_type_member<'a>(
+    type_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let short_name = type_name.rsplit('.').next().unwrap_or(type_name);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !matches!(child.kind(), "class_declaration" | "object_declaration") {
+            continue;
+        }
+        let Some(body) = kotlin_class_body(child) else {
+            continue;
+        };
+        let is_type = child
+            .child_by_field_name("name")
+            .is_some_and(|name| ctx.get_node_text(&name) == short_name);
+        let mut members = body.walk();
+        let found = if is_type {
+            body.named_children(&mut members)
+                .find_map(|declaration| match declaration.kind() {
+                    "property_declaration" => kotlin_property_value(declaration, member, ctx),
+                    "companion_object" => kotlin_class_body(declaration).and_then(|companion| {
+                        let mut cursor = companion.walk();
+                        let value = companion
+                            .named_children(&mut cursor)
+                            .filter(|property| property.kind() == "property_declaration")
+                            .find_map(|property| kotlin_property_value(property, member, ctx));
+                        value
+                    }),
+                    _ => None,
+                })
+        } else {
+            kotlin_type_member(type_name, member, body, ctx)
+        };
+        if found.is_some() {
+            return found;
+        }
     }
+    None
+}
+
+/// The value of the member `member` of the Scala object, class or trait
+/// named `type_name` (`Params` or `Vault.Params`), defined in `node` or
+/// nested in it, as in `Params.Iterations`.
+pub(crate) fn scala_type_member<'a>(
+    type_name: &str,
+    member: &str,
+    node: Node<'a>,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let short_name = type_name.rsplit('.').next().unwrap_or(type_name);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !matches!(
+            child.kind(),
+            "object_definition" | "class_definition" | "trait_definition"
+        ) {
+            continue;
+        }
+        let Some(body) = child.child_by_field_name("body") else {
+            continue;
+        };
+        let is_type = child
+            .child_by_field_name("name")
+            .is_some_and(|name| ctx.get_node_text(&name) == short_name);
+        let mut members = body.walk();
+        let found = if is_type {
+            body.named_children(&mut members)
+                .filter(|declaration| declaration.kind() == "val_definition")
+                .find_map(|declaration| scala_definition_value(declaration, member, ctx))
+        } else {
+            scala_type_member(type_name, member, body, ctx)
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// The value a `val` or `var` definition gives `name`, if it binds just
+/// that name (`val rounds = 10`).
+fn scala_definition_value<'a>(
+    definition: Node<'a>,
+    name: &str,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    definition
+        .child_by_field_name("pattern")
+        .filter(|pattern| pattern.kind() == "identifier" && ctx.get_node_text(pattern) == name)
+        .and_then(|_| definition.child_by_field_name("value"))
+}
+
+/// The body of a Kotlin class, object or companion object.
+fn kotlin_class_body(declaration: Node) -> Option<Node> {
+    let mut cursor = declaration.walk();
+    let body = declaration
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "class_body" | "enum_class_body"));
+    body
+}
+
+/// The initial value `declaration` gives `name` (`val rounds = 10`); the
+/// grammar labels neither.
+fn kotlin_property_value<'a>(
+    declaration: Node<'a>,
+    name: &str,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let mut cursor = declaration.walk();
+    let children: Vec<Node<'a>> = declaration.children(&mut cursor).collect();
+    let declares = children.iter().any(|child| {
+        child.kind() == "variable_declaration"
+            && child
+                .named_child(0)
+                .is_some_and(|bound| ctx.get_node_text(&bound) == name)
+    });
+    if !declares {
+        return None;
+    }
+    let equals = children.iter().position(|child| child.kind() == "=")?;
+    children[equals + 1..]
+        .iter()
+        .find(|child| child.is_named())
+        .copied()
+}
+
+/// The value a plain `name = value` assignment gives `name`.
+fn kotlin_assignment_value<'a>(
+    assign: Node<'a>,
+    name: &str,
+    ctx: &Context<'a>,
+) -> Option<Node<'a>> {
+    let operator = assign.child_by_field_name("operator")?;
+    let left = assign.child_by_field_name("left")?;
+    if ctx.get_node_text(&operator) != "=" || ctx.get_node_text(&left) != name {
+        return None;
+    }
+    assign.child_by_field_name("right")
+}
+
+/// The value assigned to the variable `name` by `assign`, if it is a plain
+/// assignment to it.
+fn php_assignment_value<'a>(assign: Node<'a>, name: &str, ctx: &Context<'a>) -> Option<Node<'a>> {
+    if assign.kind() != "assignment_expression" {
+        return None;
+    }
+    assign
+        .child_by_field_name("left")
+        .filter(|left| left.kind() == "variable_name" && ctx.get_node_text(left) == name)
+        .and_then(|_| assign.child_by_field_name("right"))
+}
+
+/// The value of `name` in a `const` declaration, whose elements keep their
+/// name and value unlabeled.
+fn php_const_value<'a>(declaration: Node<'a>, name: &str, ctx: &Context<'a>) -> Option<Node<'a>> {
+    let mut cursor = declaration.walk();
+    let found = declaration
+        .named_children(&mut cursor)
+        .filter(|element| element.kind() == "const_element")
+        .find(|element| {
+            element
+                .named_child(0)
+                .is_some_and(|declared| ctx.get_node_text(&declared) == name)
+        })
+        .and_then(|element| element.named_child(1));
+    found
+}
+
+/// The value of `name` in `define('NAME', value)`.
+fn php_define_value<'a>(call: Node<'a>, name: &str, ctx: &Context<'a>) -> Option<Node<'a>> {
+    let function = call.child_by_field_name("function")?;
+    if ctx.get_node_text(&function).trim_start_matches('\\') != "define" {
+        return None;
+    }
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let values: Vec<_> = arguments
+        .named_children(&mut cursor)
+        .filter(|arg| arg.kind() == "argument")
+        .filter_map(|arg| arg.named_child(arg.named_child_count().checked_sub(1)?))
+        .collect();
+    let declared = values.first()?;
+    (ctx.unquote_string(&ctx.get_node_text(declared)) == name)
+        .then(|| values.get(1).copied())
+        .flatten()
+}
+
+/// The initial value of a C# `variable_declarator`. Unlike Java, the value
+/// is not a field of the declarator but its last child.
+pub(crate) fn csharp_declarator_value(declarator: Node) -> Option<Node> {
+    let value = declarator.named_child(declarator.named_child_count().checked_sub(1)?)?;
+    (declarator.child_by_field_name("name") != Some(value)).then_some(value)
+}
+
+/// The identifier a C declarator declares, under any pointer or array
+/// declarators (`*p`, `salt[]`).
+pub(crate) fn c_declared_name(declarator: Node) -> Option<Node> {
+    let mut node = declarator.child_by_field_name("declarator")?;
+    while node.kind() != "identifier" {
+        node = node.child_by_field_name("declarator")?;
+    }
+    Some(node)
 }
 
 impl Strategy for IdentifierStrategy {
@@ -644,24 +1635,38 @@ impl Strategy for IdentifierStrategy {
         let use_position = node.start_byte();
 
         if name == "true" || name == "false" || name == "nil" || name == "null" || name == "None" {
-            return Value::resolved_string(name);
+            return Value::resolved_string(name).with_span(ctx.span(node));
         }
 
         if let Some(function_node) = self.find_enclosing_function(*node, ctx) {
             if self.is_function_parameter(&name, function_node, ctx) {
                 return Value::unextractable(UnresolvedSource::FunctionParameter);
             }
+        }
+
+        if self.is_loop_variable(&name, *node, ctx) {
+            return Value::unextractable(UnresolvedSource::LoopDependent);
+        }
 
+        if let Some(function_node) = self.find_enclosing_function(*node, ctx) {
             if let Some(value_node) =
                 self.find_declaration_in_scope(&name, function_node, use_position, ctx)
             {
-                return self.resolve_value_node(value_node, ctx);
+                return self
+                    .resolve_value_node(value_node, ctx)
+                    .with_evidence(ctx.evidence(EvidenceKind::Local, &name, &value_node));
             }
         }
 
         let root = ctx.tree().root_node();
         if let Some(value_node) = self.find_file_level_constant(&name, root, use_position, ctx) {
-            return self.resolve_value_node(value_node, ctx);
+            return self
+                .resolve_value_node(value_node, ctx)
+                .with_evidence(ctx.evidence(EvidenceKind::Constant, &name, &value_node));
+        }
+
+        if let Some(value) = ctx.find_imported_constant(&name) {
+            return value;
         }
 
         if let Some(value) = ctx.find_cross_file_constant(&name) {
@@ -851,6 +1856,46 @@ func test() {
         assert_eq!(value.int_values, vec![10000]);
     }
 
+    #[test]
+    fn test_go_range_variable_is_loop_dependent() {
+        let source = r#"
+package main
+
+func test() {
+    for _, alg := range algorithms {
+        use(alg)
+    }
+}"#;
+        let tree = parse_go(source);
+        let ctx = create_go_context(&tree, source.as_bytes());
+        let strategy = IdentifierStrategy::new();
+
+        let node = find_last_identifier_by_name(tree.root_node(), "alg", &ctx).unwrap();
+        let value = strategy.resolve(&node, &ctx);
+
+        assert!(!value.is_resolved);
+        assert_eq!(value.source, "loop_dependent");
+    }
+
+    #[test]
+    fn test_python_loop_variable_is_loop_dependent() {
+        let source = r#"
+rounds = 4
+for i in range(rounds):
+    use(i, rounds)
+"#;
+        let tree = parse_python(source);
+        let ctx = create_python_context(&tree, source.as_bytes());
+        let strategy = IdentifierStrategy::new();
+
+        let node = find_last_identifier_by_name(tree.root_node(), "i", &ctx).unwrap();
+        assert_eq!(strategy.resolve(&node, &ctx).source, "loop_dependent");
+
+        // The loop's other names still resolve
+        let node = find_last_identifier_by_name(tree.root_node(), "rounds", &ctx).unwrap();
+        assert_eq!(strategy.resolve(&node, &ctx).int_values, vec![4]);
+    }
+
     #[test]
     fn test_go_local_variable_var_decl() {
         let source = r#"
@@ -893,6 +1938,39 @@ func test() {
         assert_eq!(value.int_values, vec![100000]);
     }
 
+    #[test]
+    fn test_go_evidence_chain() {
+        let source = r#"
+package main
+
+const Base = 1000
+
+func test() {
+    n := Base * 10
+    use(n)
+}"#;
+        let tree = parse_go(source);
+        let ctx = create_go_context(&tree, source.as_bytes());
+        let strategy = IdentifierStrategy::new();
+
+        let node = find_last_identifier_by_name(tree.root_node(), "n", &ctx).unwrap();
+        let value = strategy.resolve(&node, &ctx);
+
+        assert_eq!(value.int_values, vec![10000]);
+        let chain: Vec<(EvidenceKind, &str, usize)> = value
+            .evidence
+            .iter()
+            .map(|step| (step.kind, step.name.as_str(), step.line))
+            .collect();
+        assert_eq!(
+            chain,
+            vec![
+                (EvidenceKind::Local, "n", 7),
+                (EvidenceKind::Constant, "Base", 4)
+            ]
+        );
+    }
+
     #[test]
     fn test_go_file_level_var() {
         let source = r#"
@@ -0,0 +1,9 @@
+use tree_sitter::Node;
+
+/// `arr[0]`: the index is wrapped in a bracketed argument list.
+pub fn get_object_index<'a>(node: &Node<'a>) -> Option<(Node<'a>, Node<'a>)> {
+    let expression = node.child_by_field_name("expression")?;
+    let argument = node.child_by_field_name("subscript")?.named_child(0)?;
+    let index = argument.named_child(argument.named_child_count().checked_sub(1)?)?;
+    Some((expression, index))
+}
@@ -0,0 +1,7 @@
+use tree_sitter::Node;
+
+/// `sizes[0]`: the indexed value and the index are its unlabeled
+/// children.
+pub fn get_object_index<'a>(node: &Node<'a>) -> Option<(Node<'a>, Node<'a>)> {
+    Some((node.named_child(0)?, node.named_child(1)?))
+}
@@ -0,0 +1,8 @@
+use tree_sitter::Node;
+
+/// `$sizes[0]` and `$options['cost']`: object and index are unlabeled.
+pub fn get_object_index<'a>(node: &Node<'a>) -> Option<(Node<'a>, Node<'a>)> {
+    let object = node.named_child(0)?;
+    let index = node.named_child(1)?;
+    Some((object, index))
+}
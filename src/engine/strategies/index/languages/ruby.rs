@@ -0,0 +1,8 @@
+use tree_sitter::Node;
+
+/// `sizes[0]`: the index follows the object unlabeled.
+pub fn get_object_index<'a>(node: &Node<'a>) -> Option<(Node<'a>, Node<'a>)> {
+    let object = node.child_by_field_name("object")?;
+    let index = node.named_child(1)?;
+    Some((object, index))
+}
@@ -1,4 +1,6 @@
-use crate::engine::{Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
+use crate::engine::{
+    sources::is_external_input, Context, Language, NodeCategory, Strategy, UnresolvedSource, Value,
+};
 use tree_sitter::Node;
 
 mod languages;
@@ -30,6 +32,12 @@ impl IndexStrategy {
             Language::JavaScript | Language::TypeScript => languages::js_get_object_index(node),
             Language::C | Language::Cpp => languages::c_get_object_index(node),
             Language::Java => languages::java_get_object_index(node),
+            Language::CSharp => languages::csharp_get_object_index(node),
+            Language::Ruby => languages::ruby_get_object_index(node),
+            Language::Php => languages::php_get_object_index(node),
+            // Subscripts (`sizes[0]`, `sizes(0)`) parse as calls
+            Language::Swift | Language::Scala => None,
+            Language::Kotlin => languages::kotlin_get_object_index(node),
         }
     }
 
@@ -121,6 +129,12 @@ impl IndexStrategy {
             Language::JavaScript | Language::TypeScript => kind == "array",
             Language::C | Language::Cpp => kind == "initializer_list",
             Language::Java => kind == "array_initializer",
+            Language::CSharp => kind == "initializer_expression" || kind == "collection_expression",
+            Language::Ruby => kind == "array",
+            Language::Php => kind == "array_creation_expression",
+            Language::Swift => kind == "array_literal",
+            Language::Kotlin => kind == "collection_literal",
+            Language::Scala => false,
         }
     }
 
@@ -134,7 +148,13 @@ impl IndexStrategy {
             Language::Rust => true,
             Language::JavaScript | Language::TypeScript => true,
             Language::C | Language::Cpp => true,
-            Language::Java => true,
+            Language::Java
+            | Language::CSharp
+            | Language::Ruby
+            | Language::Php
+            | Language::Swift
+            | Language::Kotlin
+            | Language::Scala => true,
         }
     }
 
@@ -144,14 +164,14 @@ impl IndexStrategy {
         if ctx.is_node_category(kind, NodeCategory::IntegerLiteral) {
             let text = ctx.get_node_text(node);
             if let Some(value) = ctx.parse_int_literal(&text) {
-                return Value::resolved_int(value);
+                return Value::resolved_int(value).with_span(ctx.span(node));
             }
         }
 
         if ctx.is_node_category(kind, NodeCategory::StringLiteral) {
             let text = ctx.get_node_text(node);
             let unquoted = ctx.unquote_string(&text);
-            return Value::resolved_string(unquoted);
+            return Value::resolved_string(unquoted).with_span(ctx.span(node));
         }
 
         if ctx.is_node_category(kind, NodeCategory::BooleanLiteral) {
@@ -160,7 +180,7 @@ impl IndexStrategy {
             } else {
                 "false"
             };
-            return Value::resolved_string(text.to_string());
+            return Value::resolved_string(text.to_string()).with_span(ctx.span(node));
         }
 
         Value::partial_expression(ctx.get_node_text(node))
@@ -276,10 +296,25 @@ impl Strategy for IndexStrategy {
             None => return Value::unextractable(UnresolvedSource::Unknown),
         };
 
+        // os.environ["KEY"], sys.argv[1] and similar reads
+        let object_text = ctx.get_node_text(&object_node);
+        if is_external_input(&object_text) {
+            return Value::external_input(
+                &object_text,
+                self.resolve_string_index(&index_node, ctx),
+                ctx.get_node_text(node),
+            );
+        }
+
         if let Some(string_key) = self.resolve_string_index(&index_node, ctx) {
             if let Some(value) = self.extract_map_value(&object_node, &string_key, ctx) {
                 return value;
             }
+            // cfg["crypto"]["iterations"] on a loaded configuration document
+            let object_value = ctx.resolve(&object_node);
+            if let Some(value) = object_value.config_key(&string_key) {
+                return value;
+            }
         }
 
         if let Some(index) = Self::resolve_index_value(&index_node, ctx) {
@@ -1,4 +1,4 @@
-use crate::engine::{Context, NodeCategory, Strategy, UnresolvedSource, Value};
+use crate::engine::{literals, Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
 use tree_sitter::Node;
 
 pub struct LiteralStrategy;
@@ -23,27 +23,46 @@ impl LiteralStrategy {
     }
 
     fn resolve_float(&self, node: &Node, ctx: &Context) -> Value {
-        let text = ctx.get_node_text(node).replace('_', "");
-        match text.parse::<f64>() {
-            Ok(value) => {
-                if value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
-                    Value::resolved_int(value as i64)
-                } else {
-                    Value::resolved_string(text)
-                }
-            }
-            Err(_) => Value::unextractable(UnresolvedSource::Unknown),
+        let text = ctx.get_node_text(node);
+        match ctx.parse_float_literal(&text) {
+            Some(value) => match literals::integral(value) {
+                Some(value) => Value::resolved_int(value),
+                None => Value::resolved_string(text.replace('_', "")),
+            },
+            None => Value::unextractable(UnresolvedSource::Unknown),
         }
     }
 
     fn resolve_string(&self, node: &Node, ctx: &Context) -> Value {
         let text = ctx.get_node_text(node);
-        let unquoted = ctx.unquote_string(&text);
-        Value::resolved_string(unquoted)
+        let language = ctx.node_types().map(|nt| nt.language());
+        // PHP "v=$x" and "{$cfg['cipher']}", Swift "v=\(x)" and Kotlin
+        // "v=$x" and "v=${x}" are only known at runtime
+        if ((node.kind() == "encapsed_string" || language == Some(Language::Swift))
+            && is_interpolated(node))
+            || (language == Some(Language::Kotlin) && is_kotlin_template(node, ctx))
+        {
+            return Value::partial_expression(text);
+        }
+        // Scala s"v=$x" and s"v=${x}"; s"plain" is just the string
+        if node.kind() == "interpolated_string_expression" {
+            return match node.named_child(1) {
+                Some(string) if !is_interpolated(&string) => {
+                    Value::resolved_string(ctx.unquote_string(&ctx.get_node_text(&string)))
+                }
+                _ => Value::partial_expression(text),
+            };
+        }
+        Value::resolved_string(ctx.unquote_string(&text))
     }
 
-    fn resolve_boolean(&self, node: &Node, _ctx: &Context) -> Value {
-        let kind = node.kind();
+    fn resolve_boolean(&self, node: &Node, ctx: &Context) -> Value {
+        // PHP wraps the keyword, in any case, in a `boolean` node
+        let text = ctx.get_node_text(node);
+        let kind = match node.kind() {
+            "boolean" => text.trim(),
+            kind => kind,
+        };
         if kind.eq_ignore_ascii_case("true") {
             Value::resolved_string("true".to_string())
         } else if kind.eq_ignore_ascii_case("false") {
@@ -57,6 +76,24 @@ impl LiteralStrategy {
         let kind = node.kind();
         Value::resolved_string(kind.to_string())
     }
+
+    /// The replacement text of a C `#define`, which the grammar leaves
+    /// unparsed: an integer or a string literal, possibly in parentheses.
+    fn resolve_macro(&self, node: &Node, ctx: &Context) -> Value {
+        let text = ctx.get_node_text(node);
+        let mut body = text.trim();
+        while let Some(inner) = body.strip_prefix('(').and_then(|b| b.strip_suffix(')')) {
+            body = inner.trim();
+        }
+
+        if let Some(value) = ctx.parse_int_literal(body) {
+            return Value::resolved_int(value);
+        }
+        if body.len() >= 2 && body.starts_with('"') && body.ends_with('"') {
+            return Value::resolved_string(ctx.unquote_string(body));
+        }
+        Value::partial_expression(body)
+    }
 }
 
 impl Strategy for LiteralStrategy {
@@ -71,9 +108,21 @@ impl Strategy for LiteralStrategy {
             || ctx.is_node_category(kind, NodeCategory::StringLiteral)
             || ctx.is_node_category(kind, NodeCategory::BooleanLiteral)
             || ctx.is_node_category(kind, NodeCategory::NilLiteral)
+            || kind == "preproc_arg"
     }
 
     fn resolve<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Value {
+        let value = self.resolve_literal(node, ctx);
+        if value.is_resolved {
+            value.with_span(ctx.span(node))
+        } else {
+            value
+        }
+    }
+}
+
+impl LiteralStrategy {
+    fn resolve_literal(&self, node: &Node, ctx: &Context) -> Value {
         let kind = node.kind();
 
         if ctx.is_node_category(kind, NodeCategory::IntegerLiteral) {
@@ -96,10 +145,48 @@ impl Strategy for LiteralStrategy {
             return self.resolve_nil(node, ctx);
         }
 
+        if kind == "preproc_arg" {
+            return self.resolve_macro(node, ctx);
+        }
+
         Value::unextractable(UnresolvedSource::NotImplemented)
     }
 }
 
+/// Whether a PHP or Swift double-quoted string interpolates anything.
+fn is_interpolated(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let interpolated = node.named_children(&mut cursor).any(|child| {
+        !matches!(
+            child.kind(),
+            "string_content"
+                | "escape_sequence"
+                | "line_str_text"
+                | "multi_line_str_text"
+                | "str_escaped_char"
+                | "raw_str_end_part"
+        )
+    });
+    interpolated
+}
+
+/// Whether a Kotlin string has a `${x}` template, or a `$x` one, which the
+/// grammar leaves as a `$` followed by the name.
+fn is_kotlin_template(node: &Node, ctx: &Context) -> bool {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children.iter().enumerate().any(|(i, child)| {
+        child.kind() == "interpolation"
+            || ctx.get_node_text(child) == "$"
+                && children.get(i + 1).is_some_and(|next| {
+                    next.kind() == "string_content"
+                        && ctx
+                            .get_node_text(next)
+                            .starts_with(|c: char| c.is_alphabetic() || c == '_')
+                })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +248,23 @@ mod tests {
         assert_eq!(value.int_values, vec![10000]);
     }
 
+    #[test]
+    fn test_literal_records_span() {
+        let source = "package main\n\nconst x = 10000";
+        let tree = parse_go(source);
+        let ctx = create_context(&tree, source.as_bytes());
+        let strategy = LiteralStrategy::new();
+
+        let node = find_first_node_of_kind(tree.root_node(), "int_literal").unwrap();
+        let value = strategy.resolve(&node, &ctx);
+
+        assert_eq!(value.spans.len(), 1);
+        let span = &value.spans[0];
+        assert_eq!(span.file, "test.go");
+        assert_eq!((span.start_line, span.end_line), (3, 3));
+        assert_eq!(&source[span.start_byte..span.end_byte], "10000");
+    }
+
     #[test]
     fn test_integer_hex() {
         let source = "package main\nconst x = 0xFF";
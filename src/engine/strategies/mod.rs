@@ -7,6 +7,7 @@
 // 1. Literal    - Direct values: 10000, "sha256", true
 // 2. Unary      - Unary operations: -x, !flag, &x
 // 3. Binary     - Binary operations: BASE + 10000, keyLen * 8
+//    Conditional - Branch values: fips ? 600000 : 310000
 // 4. Identifier - Variable lookup: iterations -> find declaration
 // 5. Call       - Function returns: getIterations() -> trace returns
 // 6. Selector   - Field/method access: cfg.Iterations, pkg.Constant
@@ -26,6 +27,9 @@ pub use unary::UnaryStrategy;
 pub mod binary;
 pub use binary::BinaryStrategy;
 
+pub mod conditional;
+pub use conditional::ConditionalStrategy;
+
 pub mod identifier;
 pub use identifier::IdentifierStrategy;
 
@@ -0,0 +1,8 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    let expression = node.child_by_field_name("expression")?;
+    let name = node.child_by_field_name("name")?;
+    Some((expression, ctx.get_node_text(&name)))
+}
@@ -6,3 +6,37 @@ pub fn get_selector<'a>(_node: &Node<'a>, _ctx: &Context<'a>) -> Option<(Node<'a
     let field = _node.child_by_field_name("field")?;
     Some((operand, _ctx.get_node_text(&field)))
 }
+
+/// Struct tags naming the configuration key a field is decoded from, in
+/// the order they are looked up.
+const CONFIG_TAGS: &[&str] = &["mapstructure", "yaml", "json", "toml"];
+
+/// The key a struct tag of a field named `field_name` in this file gives,
+/// e.g. `iterations` for `Iterations int \`json:"iterations"\``.
+pub fn tag_key(field_name: &str, ctx: &Context) -> Option<String> {
+    let mut stack = vec![ctx.tree().root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "field_declaration" {
+            let named = node
+                .child_by_field_name("name")
+                .is_some_and(|name| ctx.get_node_text(&name) == field_name);
+            if let Some(tag) = node.child_by_field_name("tag").filter(|_| named) {
+                return config_tag(&ctx.get_node_text(&tag));
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    None
+}
+
+/// The key of the first configuration tag in the raw struct tag `tag`.
+fn config_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim_matches('`');
+    CONFIG_TAGS.iter().find_map(|name| {
+        let start = tag.find(&format!("{name}:\""))? + name.len() + 2;
+        let value = &tag[start..];
+        let key = value[..value.find('"')?].split(',').next()?;
+        (!key.is_empty() && key != "-").then(|| key.to_string())
+    })
+}
@@ -0,0 +1,13 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `Params.ITERATIONS` and `config.salt`: the receiver, then the member
+/// after the `.` or `?.`.
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    let target = node.named_child(0)?;
+    let member = node.named_child(node.named_child_count().checked_sub(1)?)?;
+    if member == target {
+        return None;
+    }
+    Some((target, ctx.get_node_text(&member)))
+}
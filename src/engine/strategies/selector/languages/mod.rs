@@ -1,13 +1,26 @@
 pub mod c;
+pub mod csharp;
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod scala;
+pub mod swift;
 
 pub use c::get_selector as c_get_selector;
+pub use csharp::get_selector as csharp_get_selector;
 pub use go::get_selector as go_get_selector;
+pub use go::tag_key as go_tag_key;
 pub use java::get_selector as java_get_selector;
 pub use javascript::get_selector as js_get_selector;
+pub use kotlin::get_selector as kotlin_get_selector;
+pub use php::get_selector as php_get_selector;
 pub use python::get_selector as python_get_selector;
+pub use ruby::get_selector as ruby_get_selector;
 pub use rust::get_selector as rust_get_selector;
+pub use scala::get_selector as scala_get_selector;
+pub use swift::get_selector as swift_get_selector;
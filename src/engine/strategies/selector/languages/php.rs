@@ -0,0 +1,19 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `Params::ROUNDS` and `self::ROUNDS`, whose parts are unlabeled, and
+/// `$this->rounds`.
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    match node.kind() {
+        "class_constant_access_expression" => {
+            let scope = node.named_child(0)?;
+            let name = node.named_child(1)?;
+            Some((scope, ctx.get_node_text(&name)))
+        }
+        _ => {
+            let object = node.child_by_field_name("object")?;
+            let name = node.child_by_field_name("name")?;
+            Some((object, ctx.get_node_text(&name)))
+        }
+    }
+}
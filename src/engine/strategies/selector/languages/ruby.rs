@@ -0,0 +1,10 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `Config::ROUNDS`; a top-level `::ROUNDS` has no scope and is looked up
+/// as a plain constant.
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    let scope = node.child_by_field_name("scope")?;
+    let name = node.child_by_field_name("name")?;
+    Some((scope, ctx.get_node_text(&name)))
+}
@@ -0,0 +1,9 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `Params.Iterations` and `config.salt`.
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    let value = node.child_by_field_name("value")?;
+    let field = node.child_by_field_name("field")?;
+    Some((value, ctx.get_node_text(&field)))
+}
@@ -0,0 +1,12 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `Params.iterations` and `self.salt`: the member is wrapped in a
+/// navigation suffix.
+pub fn get_selector<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(Node<'a>, String)> {
+    let target = node.child_by_field_name("target")?;
+    let member = node
+        .child_by_field_name("suffix")?
+        .child_by_field_name("suffix")?;
+    Some((target, ctx.get_node_text(&member)))
+}
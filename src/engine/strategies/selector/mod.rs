@@ -1,11 +1,16 @@
-use crate::engine::{Context, Language, NodeCategory, Resolver, Strategy, UnresolvedSource, Value};
+use crate::engine::{
+    sources::{is_config_decoder, is_external_input},
+    strategies::identifier::{
+        csharp_type_member, kotlin_type_member, php_class_constant, ruby_module_constant,
+        scala_type_member, swift_type_member,
+    },
+    Context, EvidenceKind, Language, NodeCategory, Strategy, UnresolvedSource, Value,
+};
 use tree_sitter::Node;
 
 mod languages;
 
-pub struct SelectorStrategy {
-    resolver: Option<Resolver>,
-}
+pub struct SelectorStrategy;
 
 impl Default for SelectorStrategy {
     fn default() -> Self {
@@ -15,13 +20,7 @@ impl Default for SelectorStrategy {
 
 impl SelectorStrategy {
     pub fn new() -> Self {
-        Self { resolver: None }
-    }
-
-    pub fn with_resolver(resolver: Resolver) -> Self {
-        Self {
-            resolver: Some(resolver),
-        }
+        Self
     }
 
     fn get_object_and_field<'a>(
@@ -38,6 +37,12 @@ impl SelectorStrategy {
             Language::JavaScript | Language::TypeScript => languages::js_get_selector(node, ctx),
             Language::C | Language::Cpp => languages::c_get_selector(node, ctx),
             Language::Java => languages::java_get_selector(node, ctx),
+            Language::CSharp => languages::csharp_get_selector(node, ctx),
+            Language::Ruby => languages::ruby_get_selector(node, ctx),
+            Language::Php => languages::php_get_selector(node, ctx),
+            Language::Swift => languages::swift_get_selector(node, ctx),
+            Language::Kotlin => languages::kotlin_get_selector(node, ctx),
+            Language::Scala => languages::scala_get_selector(node, ctx),
         }
     }
 
@@ -75,18 +80,76 @@ impl SelectorStrategy {
 
     fn resolve_package_constant<'a>(
         &self,
-        _package: &Node<'a>,
+        package: &Node<'a>,
         field_name: &str,
         ctx: &Context<'a>,
     ) -> Value {
+        let package_name = ctx.get_node_text(package);
+
+        // A constant of another package of the project, by import path
+        if let Some(value) = ctx.find_package_constant(&package_name, field_name) {
+            return value;
+        }
+
         // Try to find cross-file constant with this name
         if let Some(value) = ctx.find_cross_file_constant(field_name) {
             return value;
         }
 
         // Return partial expression preserving the selector
-        let package_name = ctx.get_node_text(_package);
-        Value::partial_expression(format!("{package_name}.{field_name}"))
+        let expression = format!("{package_name}.{field_name}");
+        if ctx.is_imported_name(&package_name) {
+            return Value::unresolved_expression(UnresolvedSource::CrossPackage, expression);
+        }
+        Value::partial_expression(expression)
+    }
+
+    /// A static member of a type (or Ruby module constant) declared in this
+    /// file, or in another file of the package when the type is not
+    /// declared here.
+    fn resolve_type_member<'a>(
+        &self,
+        object: &Node<'a>,
+        field_name: &str,
+        ctx: &Context<'a>,
+    ) -> Option<Value> {
+        let language = ctx.node_types()?.language();
+        let is_type = match language {
+            Language::CSharp => object.kind() == "identifier",
+            Language::Ruby => matches!(object.kind(), "constant" | "scope_resolution"),
+            Language::Php => matches!(object.kind(), "name" | "qualified_name" | "relative_scope"),
+            Language::Swift => {
+                matches!(object.kind(), "simple_identifier" | "navigation_expression")
+            }
+            Language::Kotlin => matches!(object.kind(), "identifier" | "navigation_expression"),
+            Language::Scala => matches!(object.kind(), "identifier" | "field_expression"),
+            _ => false,
+        };
+        let type_name = match object.kind() {
+            // `self::CIPHER` and `static::CIPHER` name the enclosing class
+            "relative_scope" => php_enclosing_class(*object, ctx)?,
+            _ => ctx.get_node_text(object),
+        };
+        if !is_type || !type_name.starts_with(|c: char| c.is_uppercase()) {
+            return None;
+        }
+
+        let root = ctx.tree().root_node();
+        let member = match language {
+            Language::Ruby => ruby_module_constant(&type_name, field_name, root, ctx),
+            Language::Php => php_class_constant(&type_name, field_name, root, ctx),
+            Language::Swift => swift_type_member(&type_name, field_name, root, ctx),
+            Language::Kotlin => kotlin_type_member(&type_name, field_name, root, ctx),
+            Language::Scala => scala_type_member(&type_name, field_name, root, ctx),
+            _ => csharp_type_member(&type_name, field_name, root, ctx),
+        };
+        match member {
+            Some(value_node) => Some(
+                self.resolve_object(&value_node, ctx)
+                    .with_evidence(ctx.evidence(EvidenceKind::Constant, field_name, &value_node)),
+            ),
+            None => ctx.find_cross_file_constant(field_name),
+        }
     }
 
     fn resolve_field_access<'a>(
@@ -98,18 +161,24 @@ impl SelectorStrategy {
         // Try to resolve the object using the full resolver chain
         let object_value = self.resolve_object(object, ctx);
 
+        // cfg.crypto.iterations on a configuration document or section
+        if let Some(value) = object_value.config_key(&self.config_field_key(field_name, ctx)) {
+            return value;
+        }
+
         if object_value.is_resolved {
             // Check if this is tuple/array index access (e.g., cfg.0, cfg.1)
             if let Ok(index) = field_name.parse::<usize>() {
                 // Tuple/array index access - try to extract the element
                 if !object_value.int_values.is_empty() {
                     if let Some(&val) = object_value.int_values.get(index) {
-                        return Value::resolved_int(val);
+                        return Value::resolved_int(val).with_provenance_of([&object_value]);
                     }
                 }
                 if !object_value.string_values.is_empty() {
                     if let Some(val) = object_value.string_values.get(index) {
-                        return Value::resolved_string(val.clone());
+                        return Value::resolved_string(val.clone())
+                            .with_provenance_of([&object_value]);
                     }
                 }
             }
@@ -126,17 +195,89 @@ impl SelectorStrategy {
         Value::partial_expression(format!("{object_text}.{field_name}"))
     }
 
-    fn resolve_object<'a>(&self, object: &Node<'a>, ctx: &Context<'a>) -> Value {
-        // Use resolver if available for full strategy chain
-        if let Some(ref resolver) = self.resolver {
-            return resolver.resolve(object, ctx);
+    /// The configuration document `object` was decoded into, as in
+    /// `json.Unmarshal(data, &cfg)` in the function using it; for
+    /// `viper.UnmarshalKey("crypto", &cfg)`, the section under that key.
+    fn decoded_config<'a>(&self, object: &Node<'a>, ctx: &Context<'a>) -> Option<Value> {
+        // The decoders are Go functions
+        if object.kind() != "identifier" || ctx.node_types()?.language() != Language::Go {
+            return None;
         }
+        let name = ctx.get_node_text(object);
+        let mut scope = *object;
+        while !ctx.is_node_category(scope.kind(), NodeCategory::FunctionDeclaration) {
+            match scope.parent() {
+                Some(parent) => scope = parent,
+                None => break,
+            }
+        }
+
+        let mut stack = vec![scope];
+        while let Some(node) = stack.pop() {
+            if ctx.is_node_category(node.kind(), NodeCategory::CallExpression) {
+                if let Some(key) = decoded_key(&node, &name, ctx) {
+                    return Some(Value::config_value(key));
+                }
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        None
+    }
+
+    /// The configuration key `field_name` is decoded from: in Go, the key
+    /// a struct tag of the field gives (`json:"iterations"`), else the name.
+    fn config_field_key<'a>(&self, field_name: &str, ctx: &Context<'a>) -> String {
+        match ctx.node_types().map(|nt| nt.language()) {
+            Some(Language::Go) => languages::go_tag_key(field_name, ctx),
+            _ => None,
+        }
+        .unwrap_or_else(|| field_name.to_string())
+    }
+
+    fn resolve_object<'a>(&self, object: &Node<'a>, ctx: &Context<'a>) -> Value {
+        ctx.resolve(object)
+    }
+}
+
+/// When `call` decodes configuration into `name` (`&name` or `name`), the
+/// key it decodes from: `None` for the whole document.
+fn decoded_key(call: &Node, name: &str, ctx: &Context) -> Option<Option<String>> {
+    let function = ctx.get_node_text(&call.child_by_field_name("function")?);
+    if !is_config_decoder(&function) {
+        return None;
+    }
+    let arguments = ctx.get_named_children(&call.child_by_field_name("arguments")?);
+    let decodes_into = arguments.iter().any(|argument| {
+        let text = ctx.get_node_text(argument);
+        text.strip_prefix('&').unwrap_or(&text) == name
+    });
+    if !decodes_into {
+        return None;
+    }
+    let key = match arguments.first() {
+        Some(first) if arguments.len() > 1 && function.ends_with("UnmarshalKey") => ctx
+            .is_node_category(first.kind(), NodeCategory::StringLiteral)
+            .then(|| ctx.unquote_string(&ctx.get_node_text(first))),
+        _ => None,
+    };
+    Some(key)
+}
 
-        // Create a new resolver for full strategy chain resolution
-        // This enables proper chaining through identifier -> composite strategies
-        let resolver = Resolver::new();
-        resolver.resolve(object, ctx)
+/// The name of the PHP class, interface or enum declaring `node`.
+fn php_enclosing_class(node: Node, ctx: &Context) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if matches!(
+            ancestor.kind(),
+            "class_declaration" | "interface_declaration" | "enum_declaration"
+        ) {
+            let name = ancestor.child_by_field_name("name")?;
+            return Some(ctx.get_node_text(&name));
+        }
+        current = ancestor.parent();
     }
+    None
 }
 
 impl Strategy for SelectorStrategy {
@@ -154,6 +295,26 @@ impl Strategy for SelectorStrategy {
             None => return Value::unextractable(UnresolvedSource::Unknown),
         };
 
+        // process.env.KEY and similar reads are only known at runtime
+        let object_text = ctx.get_node_text(&object);
+        if is_external_input(&object_text) {
+            return Value::external_input(&object_text, Some(field_name), ctx.get_node_text(node));
+        }
+
+        // C# static members (Params.Iterations), Ruby module constants
+        // (Crypto::ITERATIONS), PHP class constants (Params::ROUNDS) and
+        // Kotlin and Scala object constants (Params.ITERATIONS)
+        if let Some(value) = self.resolve_type_member(&object, &field_name, ctx) {
+            return value;
+        }
+
+        // cfg.Crypto after json.Unmarshal(data, &cfg)
+        if let Some(config) = self.decoded_config(&object, ctx) {
+            if let Some(value) = config.config_key(&self.config_field_key(&field_name, ctx)) {
+                return value;
+            }
+        }
+
         // Check if this looks like a package-qualified constant (pkg.Constant)
         if self.is_package_identifier(&object, ctx) {
             // Check if the field name looks like a constant (starts with uppercase in Go)
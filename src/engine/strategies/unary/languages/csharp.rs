@@ -0,0 +1,10 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `prefix_unary_expression` has no fields: the operator token comes first,
+/// then the operand.
+pub fn get_unary<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(String, Node<'a>)> {
+    let op = node.child(0)?;
+    let operand = node.named_child(0)?;
+    Some((ctx.get_node_text(&op), operand))
+}
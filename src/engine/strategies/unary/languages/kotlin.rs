@@ -0,0 +1,9 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `-1` and `!strict`.
+pub fn get_unary<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(String, Node<'a>)> {
+    let op = node.child_by_field_name("operator")?;
+    let operand = node.child_by_field_name("argument")?;
+    Some((ctx.get_node_text(&op), operand))
+}
@@ -1,13 +1,25 @@
 pub mod c;
+pub mod csharp;
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod scala;
+pub mod swift;
 
 pub use c::get_unary as c_get_unary;
+pub use csharp::get_unary as csharp_get_unary;
 pub use go::get_unary as go_get_unary;
 pub use java::get_unary as java_get_unary;
 pub use javascript::get_unary as js_get_unary;
+pub use kotlin::get_unary as kotlin_get_unary;
+pub use php::get_unary as php_get_unary;
 pub use python::get_unary as python_get_unary;
+pub use ruby::get_unary as ruby_get_unary;
 pub use rust::get_unary as rust_get_unary;
+pub use scala::get_unary as scala_get_unary;
+pub use swift::get_unary as swift_get_unary;
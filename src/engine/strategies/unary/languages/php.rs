@@ -0,0 +1,13 @@
+use crate::engine::Context;
+use tree_sitter::Node;
+
+/// `-1` and `!$strict`.
+pub fn get_unary<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<(String, Node<'a>)> {
+    let op = node
+        .child_by_field_name("operator")
+        .or_else(|| node.child(0))?;
+    let operand = node
+        .child_by_field_name("argument")
+        .or_else(|| node.named_child(node.named_child_count().checked_sub(1)?))?;
+    Some((ctx.get_node_text(&op), operand))
+}
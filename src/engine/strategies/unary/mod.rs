@@ -1,4 +1,4 @@
-use crate::engine::{Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
+use crate::engine::{literals, Context, Language, NodeCategory, Strategy, UnresolvedSource, Value};
 use tree_sitter::Node;
 
 mod languages;
@@ -30,6 +30,12 @@ impl UnaryStrategy {
             Language::JavaScript | Language::TypeScript => languages::js_get_unary(node, ctx),
             Language::C | Language::Cpp => languages::c_get_unary(node, ctx),
             Language::Java => languages::java_get_unary(node, ctx),
+            Language::CSharp => languages::csharp_get_unary(node, ctx),
+            Language::Ruby => languages::ruby_get_unary(node, ctx),
+            Language::Php => languages::php_get_unary(node, ctx),
+            Language::Swift => languages::swift_get_unary(node, ctx),
+            Language::Kotlin => languages::kotlin_get_unary(node, ctx),
+            Language::Scala => languages::scala_get_unary(node, ctx),
         }
     }
 
@@ -39,16 +45,14 @@ impl UnaryStrategy {
         if ctx.is_node_category(kind, NodeCategory::IntegerLiteral) {
             let text = ctx.get_node_text(operand);
             if let Some(value) = ctx.parse_int_literal(&text) {
-                return Value::resolved_int(value);
+                return Value::resolved_int(value).with_span(ctx.span(operand));
             }
         }
 
         if ctx.is_node_category(kind, NodeCategory::FloatLiteral) {
-            let text = ctx.get_node_text(operand).replace('_', "");
-            if let Ok(value) = text.parse::<f64>() {
-                if value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
-                    return Value::resolved_int(value as i64);
-                }
+            let text = ctx.get_node_text(operand);
+            if let Some(value) = ctx.parse_float_literal(&text).and_then(literals::integral) {
+                return Value::resolved_int(value).with_span(ctx.span(operand));
             }
         }
 
@@ -73,19 +77,19 @@ impl UnaryStrategy {
         let kind = node.kind();
 
         if kind.eq_ignore_ascii_case("true") {
-            return Value::resolved_int(1);
+            return Value::resolved_int(1).with_span(ctx.span(node));
         }
 
         if kind.eq_ignore_ascii_case("false") {
-            return Value::resolved_int(0);
+            return Value::resolved_int(0).with_span(ctx.span(node));
         }
 
         if kind == "boolean_literal" {
             let text = ctx.get_node_text(node);
             if text.eq_ignore_ascii_case("true") {
-                return Value::resolved_int(1);
+                return Value::resolved_int(1).with_span(ctx.span(node));
             } else if text.eq_ignore_ascii_case("false") {
-                return Value::resolved_int(0);
+                return Value::resolved_int(0).with_span(ctx.span(node));
             }
         }
 
@@ -109,7 +113,8 @@ impl Strategy for UnaryStrategy {
             None => return Value::unextractable(UnresolvedSource::Unknown),
         };
 
-        if op_text == "&" || op_text == "*" {
+        // Swift implicit members (`.bits256`) name a case of the expected type
+        if op_text == "&" || op_text == "*" || op_text == "." {
             let operand_text = ctx.get_node_text(&operand);
             return Value::partial_expression(format!("{op_text}{operand_text}"));
         }
@@ -0,0 +1,834 @@
+//! Project-wide symbol index for cross-file resolution.
+//!
+//! Symbols (top-level constants and functions) are indexed per package
+//! directory the first time that package is queried, so resolving one file
+//! never requires indexing the whole project upfront. The index is
+//! `Send + Sync` and is meant to be shared between workers behind an `Arc`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use tree_sitter::{Node, Parser, Tree};
+
+use crate::grammars;
+
+use super::file_cache::FunctionInfo;
+use super::node_types::Language;
+use super::strategies::identifier::{c_declared_name, csharp_declarator_value};
+use super::{Context, EvidenceKind, Resolver, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Constant,
+    Function,
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub qualified_name: String,
+    pub kind: SymbolKind,
+    pub language: Language,
+    pub file_path: String,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub value: Option<Value>,
+}
+
+impl Symbol {
+    pub fn function_info(&self) -> FunctionInfo {
+        FunctionInfo {
+            file_path: self.file_path.clone(),
+            start_byte: self.start_byte,
+            end_byte: self.end_byte,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PackageSymbols {
+    symbols: HashMap<String, Vec<Symbol>>,
+}
+
+impl PackageSymbols {
+    pub fn get(&self, name: &str, language: Language) -> Option<&Symbol> {
+        self.symbols
+            .get(name)?
+            .iter()
+            .find(|s| s.language == language)
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values().flatten()
+    }
+
+    fn insert(&mut self, symbol: Symbol) {
+        self.symbols
+            .entry(symbol.name.clone())
+            .or_default()
+            .push(symbol);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    packages: RwLock<HashMap<PathBuf, Arc<PackageSymbols>>>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    /// Returns the symbols for `package_dir`, indexing the directory on first use.
+    ///
+    /// Indexing happens outside the lock; if two threads race on the same
+    /// package, the first result inserted wins and the other is discarded.
+    pub fn package(&self, package_dir: &Path) -> Arc<PackageSymbols> {
+        if let Some(symbols) = self.packages.read().unwrap().get(package_dir) {
+            return Arc::clone(symbols);
+        }
+
+        let indexed = Arc::new(index_package(package_dir));
+        let mut packages = self.packages.write().unwrap();
+        Arc::clone(packages.entry(package_dir.to_path_buf()).or_insert(indexed))
+    }
+
+    pub fn find(&self, package_dir: &Path, name: &str, language: Language) -> Option<Symbol> {
+        self.package(package_dir).get(name, language).cloned()
+    }
+
+    pub fn find_constant(
+        &self,
+        package_dir: &Path,
+        name: &str,
+        language: Language,
+    ) -> Option<Value> {
+        self.find(package_dir, name, language)
+            .filter(|s| s.kind == SymbolKind::Constant)
+            .and_then(|s| s.value)
+    }
+
+    pub fn find_function(
+        &self,
+        package_dir: &Path,
+        name: &str,
+        language: Language,
+    ) -> Option<FunctionInfo> {
+        self.find(package_dir, name, language)
+            .filter(|s| s.kind == SymbolKind::Function)
+            .map(|s| s.function_info())
+    }
+
+    pub fn is_indexed(&self, package_dir: &Path) -> bool {
+        self.packages.read().unwrap().contains_key(package_dir)
+    }
+
+    pub fn package_count(&self) -> usize {
+        self.packages.read().unwrap().len()
+    }
+
+    /// Drops a package so it is re-indexed on the next lookup.
+    pub fn invalidate(&self, package_dir: &Path) {
+        self.packages.write().unwrap().remove(package_dir);
+    }
+
+    pub fn clear(&self) {
+        self.packages.write().unwrap().clear();
+    }
+}
+
+pub fn language_for_path(path: &Path) -> Option<Language> {
+    match path.extension()?.to_str()? {
+        "go" => Some(Language::Go),
+        "py" => Some(Language::Python),
+        "rs" => Some(Language::Rust),
+        "js" | "jsx" | "mjs" | "cjs" => Some(Language::JavaScript),
+        "ts" | "tsx" | "mts" | "cts" => Some(Language::TypeScript),
+        "java" => Some(Language::Java),
+        "cs" => Some(Language::CSharp),
+        "rb" => Some(Language::Ruby),
+        "php" => Some(Language::Php),
+        "swift" => Some(Language::Swift),
+        "kt" | "kts" => Some(Language::Kotlin),
+        "scala" | "sc" => Some(Language::Scala),
+        "c" | "h" => Some(Language::C),
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Some(Language::Cpp),
+        _ => None,
+    }
+}
+
+/// The grammar to parse `path` with: TSX for `.tsx` files, whose JSX the
+/// plain TypeScript grammar rejects, otherwise that of its language.
+pub fn tree_sitter_language_for_path(path: &Path) -> Option<tree_sitter::Language> {
+    if path.extension().is_some_and(|ext| ext == "tsx") {
+        return grammars::tsx_grammar();
+    }
+    tree_sitter_language(language_for_path(path)?)
+}
+
+/// The language `path` is scanned as in a scan of `language`: JVM projects
+/// mix Java, Kotlin and Scala sources, each scanned as its own language.
+pub fn file_language(language: Language, path: &Path) -> Language {
+    let jvm = |lang| matches!(lang, Language::Java | Language::Kotlin | Language::Scala);
+    match language_for_path(path) {
+        Some(own) if jvm(language) && jvm(own) => own,
+        _ => language,
+    }
+}
+
+/// The grammar for `path` in a scan of `language`. JavaScript and TypeScript
+/// projects mix `.js`, `.jsx`, `.ts` and `.tsx` files, so those are parsed
+/// by their own extension, as are the Kotlin and Scala files of a Java
+/// project; other files take the grammar of `language`.
+pub fn file_grammar(language: Language, path: &Path) -> Option<tree_sitter::Language> {
+    let language = file_language(language, path);
+    let web = |lang| matches!(lang, Language::JavaScript | Language::TypeScript);
+    if web(language) && language_for_path(path).is_some_and(web) {
+        return tree_sitter_language_for_path(path);
+    }
+    tree_sitter_language(language)
+}
+
+/// The grammar of `language`, if compiled in, see [`crate::grammars`].
+pub fn tree_sitter_language(language: Language) -> Option<tree_sitter::Language> {
+    grammars::grammar(language)
+}
+
+fn index_package(package_dir: &Path) -> PackageSymbols {
+    let mut package = PackageSymbols::default();
+
+    let Ok(entries) = fs::read_dir(package_dir) else {
+        return package;
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+
+    for path in files {
+        if let Some(language) = language_for_path(&path) {
+            index_file(&path, language, package_dir, &mut package);
+        }
+    }
+
+    package
+}
+
+fn index_file(path: &Path, language: Language, package_dir: &Path, package: &mut PackageSymbols) {
+    for symbol in file_symbols(path, language, package_dir) {
+        package.insert(symbol);
+    }
+}
+
+/// The top-level constants and functions of the file at `path`, with
+/// constant values resolved within the file.
+pub(crate) fn file_symbols(path: &Path, language: Language, package_dir: &Path) -> Vec<Symbol> {
+    let Some(ts_language) = tree_sitter_language_for_path(path) else {
+        return Vec::new();
+    };
+    let Ok(source) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut parser = Parser::new();
+    if parser.set_language(&ts_language).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(&source, None) else {
+        return Vec::new();
+    };
+
+    let file_path = path.to_string_lossy().to_string();
+    let indexer = FileIndexer {
+        tree: &tree,
+        source: source.as_bytes(),
+        file_path: &file_path,
+        language,
+        package_name: package_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+
+    indexer.collect()
+}
+
+struct FileIndexer<'a> {
+    tree: &'a Tree,
+    source: &'a [u8],
+    file_path: &'a str,
+    language: Language,
+    package_name: String,
+}
+
+impl<'a> FileIndexer<'a> {
+    fn collect(&self) -> Vec<Symbol> {
+        let ctx = Context::new(
+            self.tree,
+            self.source,
+            self.file_path.to_string(),
+            self.language.tree_sitter_name().to_string(),
+            HashMap::new(),
+        );
+        let resolver = Resolver::new();
+        let mut symbols = Vec::new();
+
+        let root = self.tree.root_node();
+        let mut cursor = root.walk();
+        for child in root.children(&mut cursor) {
+            self.visit_top_level(child, &ctx, &resolver, &mut symbols);
+        }
+
+        symbols
+    }
+
+    fn visit_top_level(
+        &self,
+        node: Node<'a>,
+        ctx: &Context<'a>,
+        resolver: &Resolver,
+        out: &mut Vec<Symbol>,
+    ) {
+        match (self.language, node.kind()) {
+            (Language::Go, "const_declaration" | "var_declaration") => {
+                let mut cursor = node.walk();
+                for spec in node.named_children(&mut cursor) {
+                    self.add_go_spec(spec, ctx, resolver, out);
+                }
+            }
+            (Language::Go, "function_declaration")
+            | (Language::Python, "function_definition")
+            | (Language::Rust, "function_item")
+            | (Language::Php, "function_definition" | "method_declaration")
+            | (Language::Swift, "function_declaration")
+            | (Language::Kotlin, "function_declaration")
+            | (Language::Scala, "function_definition")
+            | (Language::JavaScript | Language::TypeScript, "function_declaration") => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    out.push(self.symbol(&name, node, SymbolKind::Function, None, ctx));
+                }
+            }
+            (Language::Python, "decorated_definition") => {
+                if let Some(def) = node.child_by_field_name("definition") {
+                    self.visit_top_level(def, ctx, resolver, out);
+                }
+            }
+            (Language::Python, "expression_statement") => {
+                let Some(assign) = node.named_child(0) else {
+                    return;
+                };
+                if assign.kind() != "assignment" {
+                    return;
+                }
+                if let (Some(left), Some(right)) = (
+                    assign.child_by_field_name("left"),
+                    assign.child_by_field_name("right"),
+                ) {
+                    if left.kind() == "identifier" {
+                        let value = resolver.resolve(&right, ctx);
+                        out.push(self.symbol(
+                            &left,
+                            assign,
+                            SymbolKind::Constant,
+                            Some(value),
+                            ctx,
+                        ));
+                    }
+                }
+            }
+            (Language::Rust, "const_item" | "static_item") => {
+                if let (Some(name), Some(value)) = (
+                    node.child_by_field_name("name"),
+                    node.child_by_field_name("value"),
+                ) {
+                    let value = resolver.resolve(&value, ctx);
+                    out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                }
+            }
+            (
+                Language::JavaScript | Language::TypeScript,
+                "lexical_declaration" | "variable_declaration",
+            ) => {
+                let mut cursor = node.walk();
+                for declarator in node.named_children(&mut cursor) {
+                    if declarator.kind() != "variable_declarator" {
+                        continue;
+                    }
+                    if let (Some(name), Some(value)) = (
+                        declarator.child_by_field_name("name"),
+                        declarator.child_by_field_name("value"),
+                    ) {
+                        if name.kind() == "identifier" {
+                            let value = resolver.resolve(&value, ctx);
+                            out.push(self.symbol(
+                                &name,
+                                declarator,
+                                SymbolKind::Constant,
+                                Some(value),
+                                ctx,
+                            ));
+                        }
+                    }
+                }
+            }
+            // Include guards and other conditional blocks
+            (
+                Language::C | Language::Cpp,
+                "preproc_ifdef" | "preproc_if" | "preproc_else" | "preproc_elif",
+            ) => {
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    self.visit_top_level(child, ctx, resolver, out);
+                }
+            }
+            (Language::Cpp, "namespace_definition") => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::C | Language::Cpp, "declaration") => {
+                let mut cursor = node.walk();
+                for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                    if declarator.kind() != "init_declarator" {
+                        continue;
+                    }
+                    if let (Some(name), Some(value)) = (
+                        c_declared_name(declarator),
+                        declarator.child_by_field_name("value"),
+                    ) {
+                        let value = resolver.resolve(&value, ctx);
+                        out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                    }
+                }
+            }
+            (Language::C | Language::Cpp, "preproc_def") => {
+                if let (Some(name), Some(value)) = (
+                    node.child_by_field_name("name"),
+                    node.child_by_field_name("value"),
+                ) {
+                    let value = resolver.resolve(&value, ctx);
+                    out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                }
+            }
+            // C# members live in types, optionally inside namespaces
+            (
+                Language::CSharp,
+                "namespace_declaration"
+                | "class_declaration"
+                | "struct_declaration"
+                | "record_declaration",
+            ) => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::CSharp, "file_scoped_namespace_declaration") => {
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    self.visit_top_level(child, ctx, resolver, out);
+                }
+            }
+            (Language::CSharp, "method_declaration") => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    out.push(self.symbol(&name, node, SymbolKind::Function, None, ctx));
+                }
+            }
+            (Language::CSharp, "field_declaration") => {
+                let mut cursor = node.walk();
+                let Some(declaration) = node
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "variable_declaration")
+                else {
+                    return;
+                };
+                let mut cursor = declaration.walk();
+                for declarator in declaration.named_children(&mut cursor) {
+                    if declarator.kind() != "variable_declarator" {
+                        continue;
+                    }
+                    if let (Some(name), Some(value)) = (
+                        declarator.child_by_field_name("name"),
+                        csharp_declarator_value(declarator),
+                    ) {
+                        let value = resolver.resolve(&value, ctx);
+                        out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                    }
+                }
+            }
+            // Ruby constants and methods are often wrapped in modules
+            (Language::Ruby, "module" | "class") => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::Ruby, "method" | "singleton_method") => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    out.push(self.symbol(&name, node, SymbolKind::Function, None, ctx));
+                }
+            }
+            (Language::Ruby, "assignment") => {
+                if let (Some(left), Some(right)) = (
+                    node.child_by_field_name("left"),
+                    node.child_by_field_name("right"),
+                ) {
+                    if left.kind() == "constant" {
+                        let value = resolver.resolve(&right, ctx);
+                        out.push(self.symbol(&left, node, SymbolKind::Constant, Some(value), ctx));
+                    }
+                }
+            }
+            // PHP constants live in namespaces and classes, or are defined
+            // with `define('NAME', value)`
+            (
+                Language::Php,
+                "namespace_definition" | "class_declaration" | "interface_declaration",
+            ) => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::Php, "const_declaration") => {
+                let mut cursor = node.walk();
+                for element in node.named_children(&mut cursor) {
+                    if element.kind() != "const_element" {
+                        continue;
+                    }
+                    if let (Some(name), Some(value)) =
+                        (element.named_child(0), element.named_child(1))
+                    {
+                        let value = resolver.resolve(&value, ctx);
+                        out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                    }
+                }
+            }
+            // Swift constants are often static properties of a type or
+            // an extension
+            (Language::Swift, "class_declaration") => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::Swift, "property_declaration") => {
+                let name = node
+                    .child_by_field_name("name")
+                    .and_then(|pattern| pattern.child_by_field_name("bound_identifier"));
+                if let (Some(name), Some(value)) = (name, node.child_by_field_name("value")) {
+                    let value = resolver.resolve(&value, ctx);
+                    out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                }
+            }
+            // Kotlin constants are top-level or members of an `object`
+            (Language::Kotlin, "object_declaration") => {
+                let mut cursor = node.walk();
+                let body = node
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "class_body");
+                if let Some(body) = body {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::Kotlin, "property_declaration") => {
+                let mut cursor = node.walk();
+                let children: Vec<Node<'a>> = node.children(&mut cursor).collect();
+                let name = children
+                    .iter()
+                    .find(|child| child.kind() == "variable_declaration")
+                    .and_then(|declaration| declaration.named_child(0));
+                let value = children
+                    .iter()
+                    .position(|child| child.kind() == "=")
+                    .and_then(|equals| children[equals + 1..].iter().find(|c| c.is_named()));
+                if let (Some(name), Some(value)) = (name, value) {
+                    let value = resolver.resolve(value, ctx);
+                    out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                }
+            }
+            // Scala constants are `val`s, top-level or members of an
+            // `object`
+            (Language::Scala, "object_definition") => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut cursor = body.walk();
+                    for child in body.named_children(&mut cursor) {
+                        self.visit_top_level(child, ctx, resolver, out);
+                    }
+                }
+            }
+            (Language::Scala, "val_definition") => {
+                if let (Some(name), Some(value)) = (
+                    node.child_by_field_name("pattern"),
+                    node.child_by_field_name("value"),
+                ) {
+                    if name.kind() == "identifier" {
+                        let value = resolver.resolve(&value, ctx);
+                        out.push(self.symbol(&name, node, SymbolKind::Constant, Some(value), ctx));
+                    }
+                }
+            }
+            (Language::JavaScript | Language::TypeScript, "export_statement") => {
+                if let Some(decl) = node.child_by_field_name("declaration") {
+                    self.visit_top_level(decl, ctx, resolver, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_go_spec(
+        &self,
+        spec: Node<'a>,
+        ctx: &Context<'a>,
+        resolver: &Resolver,
+        out: &mut Vec<Symbol>,
+    ) {
+        if spec.kind() != "const_spec" && spec.kind() != "var_spec" {
+            return;
+        }
+
+        let mut cursor = spec.walk();
+        // The grammar tags the separating commas with the `name` field too.
+        let names: Vec<Node<'a>> = spec
+            .children_by_field_name("name", &mut cursor)
+            .filter(|n| n.kind() == "identifier")
+            .collect();
+        let values: Vec<Node<'a>> = spec
+            .child_by_field_name("value")
+            .map(|list| {
+                let mut cursor = list.walk();
+                list.named_children(&mut cursor).collect()
+            })
+            .unwrap_or_default();
+
+        for (i, name) in names.iter().enumerate() {
+            let value = values.get(i).map(|v| resolver.resolve(v, ctx));
+            out.push(self.symbol(name, spec, SymbolKind::Constant, value, ctx));
+        }
+    }
+
+    fn symbol(
+        &self,
+        name_node: &Node<'a>,
+        decl: Node<'a>,
+        kind: SymbolKind,
+        value: Option<Value>,
+        ctx: &Context<'a>,
+    ) -> Symbol {
+        let name = ctx.get_node_text(name_node);
+        let value = match kind {
+            SymbolKind::Constant => {
+                value.map(|v| v.with_evidence(ctx.evidence(EvidenceKind::CrossFile, &name, &decl)))
+            }
+            SymbolKind::Function => value,
+        };
+        let qualified_name = if self.package_name.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", self.package_name, name)
+        };
+
+        Symbol {
+            name,
+            qualified_name,
+            kind,
+            language: self.language,
+            file_path: self.file_path.to_string(),
+            start_byte: decl.start_byte(),
+            end_byte: decl.end_byte(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn write_file(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_symbol_index_go_constants_and_functions() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(
+            dir.path(),
+            "consts.go",
+            "package crypto\n\nconst (\n\tKeySize = 32\n\tRounds, Salt = 10000, \"s\"\n)\n\nfunc derive() int { return 1 }\n",
+        );
+
+        let index = SymbolIndex::new();
+        let value = index
+            .find_constant(dir.path(), "KeySize", Language::Go)
+            .unwrap();
+        assert_eq!(value.int_values, vec![32]);
+
+        let salt = index
+            .find_constant(dir.path(), "Salt", Language::Go)
+            .unwrap();
+        assert_eq!(salt.string_values, vec!["s".to_string()]);
+        assert_eq!(value.evidence[0].kind, EvidenceKind::CrossFile);
+        assert_eq!(value.evidence[0].name, "KeySize");
+        assert!(value.evidence[0].file.ends_with("consts.go"));
+        assert_eq!(value.evidence[0].line, 4);
+
+        let func = index
+            .find_function(dir.path(), "derive", Language::Go)
+            .unwrap();
+        assert!(func.file_path.ends_with("consts.go"));
+        assert!(func.end_byte > func.start_byte);
+    }
+
+    #[test]
+    fn test_symbol_index_qualified_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("params");
+        fs::create_dir(&pkg).unwrap();
+        write_file(&pkg, "params.py", "ITERATIONS = 600000\n");
+
+        let index = SymbolIndex::new();
+        let symbol = index.find(&pkg, "ITERATIONS", Language::Python).unwrap();
+        assert_eq!(symbol.qualified_name, "params.ITERATIONS");
+        assert_eq!(symbol.value.unwrap().int_values, vec![600000]);
+    }
+
+    #[test]
+    fn test_symbol_index_rust_and_javascript() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(dir.path(), "lib.rs", "pub const ROUNDS: u32 = 12;\n");
+        write_file(dir.path(), "cfg.js", "export const KEY_LEN = 256;\n");
+
+        let index = SymbolIndex::new();
+        assert_eq!(
+            index
+                .find_constant(dir.path(), "ROUNDS", Language::Rust)
+                .unwrap()
+                .int_values,
+            vec![12]
+        );
+        assert_eq!(
+            index
+                .find_constant(dir.path(), "KEY_LEN", Language::JavaScript)
+                .unwrap()
+                .int_values,
+            vec![256]
+        );
+        assert!(index
+            .find_constant(dir.path(), "ROUNDS", Language::Go)
+            .is_none());
+    }
+
+    #[test]
+    fn test_symbol_index_is_lazy_per_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        fs::create_dir(&a).unwrap();
+        fs::create_dir(&b).unwrap();
+        write_file(&a, "a.go", "package a\nconst A = 1\n");
+        write_file(&b, "b.go", "package b\nconst B = 2\n");
+
+        let index = SymbolIndex::new();
+        assert_eq!(index.package_count(), 0);
+
+        index.find_constant(&a, "A", Language::Go);
+        assert!(index.is_indexed(&a));
+        assert!(!index.is_indexed(&b));
+        assert_eq!(index.package_count(), 1);
+    }
+
+    #[test]
+    fn test_symbol_index_invalidate_reindexes() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(dir.path(), "a.go", "package a\nconst A = 1\n");
+
+        let index = SymbolIndex::new();
+        assert_eq!(
+            index
+                .find_constant(dir.path(), "A", Language::Go)
+                .unwrap()
+                .int_values,
+            vec![1]
+        );
+
+        write_file(dir.path(), "a.go", "package a\nconst A = 2\n");
+        assert_eq!(
+            index
+                .find_constant(dir.path(), "A", Language::Go)
+                .unwrap()
+                .int_values,
+            vec![1]
+        );
+
+        index.invalidate(dir.path());
+        assert_eq!(
+            index
+                .find_constant(dir.path(), "A", Language::Go)
+                .unwrap()
+                .int_values,
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_symbol_index_shared_across_threads() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(dir.path(), "a.go", "package a\nconst A = 7\n");
+
+        let index = SymbolIndex::shared();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let index = Arc::clone(&index);
+                let path = dir.path().to_path_buf();
+                thread::spawn(move || index.find_constant(&path, "A", Language::Go))
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().unwrap().int_values, vec![7]);
+        }
+        assert_eq!(index.package_count(), 1);
+    }
+
+    #[test]
+    fn test_symbol_index_missing_package() {
+        let index = SymbolIndex::new();
+        let pkg = index.package(Path::new("/nonexistent/argflow/pkg"));
+        assert!(pkg.is_empty());
+    }
+}
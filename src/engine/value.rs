@@ -3,26 +3,72 @@ use serde::{Deserialize, Serialize};
 use super::operators::{BinaryOp, UnaryOp};
 use super::sources::{self, UnresolvedSource};
 
+/// Most strings [`Value::concatenate`] gives for operands with several
+/// values each; past it the expression is kept unevaluated.
+const MAX_CONCATENATIONS: usize = 64;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Value {
     /// Resolved integer values
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub int_values: Vec<i64>,
 
     /// Resolved string values
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub string_values: Vec<String>,
 
     /// Is this value fully resolved?
     pub is_resolved: bool,
 
     /// Source classification for unresolved values
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub source: String,
 
     /// Partially resolved expression (e.g., "iterations + 10000")
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub expression: String,
+
+    /// Declarations the value was traced through, outermost first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<Evidence>,
+
+    /// Source ranges of the literals the value was computed from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<Span>,
+}
+
+/// Where in a file a literal is, e.g. the `600000` of `ITERATIONS = 600000`.
+/// Lines are 1-based, bytes are offsets into the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub file: String,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// One hop of a resolution: where a name the value depends on is
+/// declared, e.g. `ITERATIONS` at `config.go:12`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    pub kind: EvidenceKind,
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    /// A variable declared or assigned in the enclosing function
+    Local,
+    /// A constant or variable at file level, or a class field
+    Constant,
+    /// A constant declared in another file of the package
+    CrossFile,
+    /// A called function in this file, whose return value it is
+    Return,
 }
 
 impl Value {
@@ -34,6 +80,8 @@ impl Value {
             is_resolved: true,
             source: String::new(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
@@ -45,6 +93,8 @@ impl Value {
             is_resolved: true,
             source: String::new(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
@@ -56,6 +106,8 @@ impl Value {
             is_resolved: true,
             source: String::new(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
@@ -67,6 +119,8 @@ impl Value {
             is_resolved: true,
             source: String::new(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
@@ -78,10 +132,58 @@ impl Value {
             is_resolved: false,
             source: source.into(),
             expression: String::new(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
     /// Create a partially resolved expression
+    /// An expression kept as written, with the reason it could not be
+    /// evaluated further.
+    pub fn unresolved_expression(source: UnresolvedSource, expression: impl Into<String>) -> Self {
+        Self {
+            source: source.to_string(),
+            ..Self::partial_expression(expression)
+        }
+    }
+
+    /// A read of external input through `reader`, e.g. `os.Getenv`, whose
+    /// source text is `text`. A read of the environment is named by its
+    /// `variable` when that is known.
+    pub fn external_input(reader: &str, variable: Option<String>, text: String) -> Self {
+        if sources::is_environment_read(reader) {
+            Value::unresolved_expression(
+                UnresolvedSource::EnvironmentVariable,
+                variable.unwrap_or(text),
+            )
+        } else {
+            Value::unresolved_expression(UnresolvedSource::ExternalInput, text)
+        }
+    }
+
+    /// A value read from configuration: the document itself without a
+    /// `key`, else the value under the key path `key`, e.g.
+    /// `crypto.iterations`.
+    pub fn config_value(key: Option<String>) -> Self {
+        match key {
+            Some(key) => Value::unresolved_expression(UnresolvedSource::ConfigValue, key),
+            None => Value::unextractable(UnresolvedSource::ConfigValue),
+        }
+    }
+
+    /// The value under `key` when this is a configuration document or a
+    /// section of one, `None` otherwise.
+    pub fn config_key(&self, key: &str) -> Option<Value> {
+        if self.is_resolved || self.source != UnresolvedSource::ConfigValue.as_str() {
+            return None;
+        }
+        let path = match self.expression.as_str() {
+            "" => key.to_string(),
+            section => format!("{section}.{key}"),
+        };
+        Some(Value::config_value(Some(path)).with_provenance_of([self]))
+    }
+
     pub fn partial_expression(expression: impl Into<String>) -> Self {
         Self {
             int_values: vec![],
@@ -89,7 +191,47 @@ impl Value {
             is_resolved: false,
             source: UnresolvedSource::PartiallyResolved.to_string(),
             expression: expression.into(),
+            evidence: Vec::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Records that the value was reached through `step`, ahead of the
+    /// steps it already has.
+    pub fn with_evidence(mut self, step: Evidence) -> Self {
+        if !self.evidence.contains(&step) {
+            self.evidence.insert(0, step);
+        }
+        self
+    }
+
+    /// Records that the value was computed from the literal at `span`.
+    pub fn with_span(mut self, span: Span) -> Self {
+        if !self.spans.contains(&span) {
+            self.spans.push(span);
+        }
+        self
+    }
+
+    /// Adds the evidence steps and spans of `values` after the ones this
+    /// value has, skipping repeats.
+    pub(crate) fn with_provenance_of<'a>(
+        mut self,
+        values: impl IntoIterator<Item = &'a Value>,
+    ) -> Self {
+        for value in values {
+            for step in &value.evidence {
+                if !self.evidence.contains(step) {
+                    self.evidence.push(step.clone());
+                }
+            }
+            for span in &value.spans {
+                if !self.spans.contains(span) {
+                    self.spans.push(span.clone());
+                }
+            }
         }
+        self
     }
 
     pub fn display(&self) -> String {
@@ -163,27 +305,64 @@ impl Value {
     }
 
     pub fn binary_op(left: &Value, op: &str, right: &Value) -> Value {
+        let expression = format!("{} {} {}", left.display(), op, right.display());
+        let Some(binary_op) = BinaryOp::parse(op) else {
+            return Value::unresolved_expression(UnresolvedSource::UnsupportedOperator, expression)
+                .with_provenance_of([left, right]);
+        };
+
         if let (Some(l), Some(r)) = (left.as_int(), right.as_int()) {
-            if let Some(binary_op) = BinaryOp::parse(op) {
-                if let Some(result) = binary_op.evaluate(l, r) {
-                    return Value::resolved_int(result);
-                }
+            if let Some(result) = binary_op.evaluate(l, r) {
+                return Value::resolved_int(result).with_provenance_of([left, right]);
             }
         }
 
-        Value::partial_expression(format!("{} {} {}", left.display(), op, right.display()))
+        Value::partial_expression(expression).with_provenance_of([left, right])
+    }
+
+    /// `left + right` as string concatenation: every combination of their
+    /// values, when both are resolved and at least one is a string. A
+    /// number is joined to a string only when `numbers` is set, as Java,
+    /// JavaScript and C# do; Go and Python reject it.
+    pub fn concatenate(left: &Value, right: &Value, numbers: bool) -> Option<Value> {
+        if !left.is_resolved
+            || !right.is_resolved
+            || (left.string_values.is_empty() && right.string_values.is_empty())
+        {
+            return None;
+        }
+        let parts = |value: &Value| match (&value.string_values[..], &value.int_values[..]) {
+            ([_, ..], []) => Some(value.string_values.clone()),
+            ([], [_, ..]) if numbers => Some(value.int_values.iter().map(i64::to_string).collect()),
+            _ => None,
+        };
+        let (lefts, rights) = (parts(left)?, parts(right)?);
+        if lefts.len() * rights.len() > MAX_CONCATENATIONS {
+            return None;
+        }
+        let mut joined: Vec<String> = lefts
+            .iter()
+            .flat_map(|l| rights.iter().map(move |r| format!("{l}{r}")))
+            .collect();
+        joined.sort();
+        joined.dedup();
+        Some(Value::resolved_strings(joined).with_provenance_of([left, right]))
     }
 
     pub fn unary_op(op: &str, operand: &Value) -> Value {
+        let expression = format!("{}{}", op, operand.display());
+        let Some(unary_op) = UnaryOp::parse(op) else {
+            return Value::unresolved_expression(UnresolvedSource::UnsupportedOperator, expression)
+                .with_provenance_of([operand]);
+        };
+
         if let Some(v) = operand.as_int() {
-            if let Some(unary_op) = UnaryOp::parse(op) {
-                if let Some(result) = unary_op.evaluate(v) {
-                    return Value::resolved_int(result);
-                }
+            if let Some(result) = unary_op.evaluate(v) {
+                return Value::resolved_int(result).with_provenance_of([operand]);
             }
         }
 
-        Value::partial_expression(format!("{}{}", op, operand.display()))
+        Value::partial_expression(expression).with_provenance_of([operand])
     }
 
     pub fn merge(values: Vec<Value>) -> Value {
@@ -191,10 +370,10 @@ impl Value {
         let mut all_strings: Vec<String> = Vec::new();
         let mut all_resolved = true;
 
-        for val in values {
+        for val in &values {
             if val.is_resolved {
-                all_ints.extend(val.int_values);
-                all_strings.extend(val.string_values);
+                all_ints.extend(&val.int_values);
+                all_strings.extend(val.string_values.iter().cloned());
             } else {
                 all_resolved = false;
             }
@@ -207,13 +386,13 @@ impl Value {
         if !all_ints.is_empty() && all_strings.is_empty() {
             all_ints.sort();
             all_ints.dedup();
-            return Value::resolved_ints(all_ints);
+            return Value::resolved_ints(all_ints).with_provenance_of(&values);
         }
 
         if !all_strings.is_empty() && all_ints.is_empty() {
             all_strings.sort();
             all_strings.dedup();
-            return Value::resolved_strings(all_strings);
+            return Value::resolved_strings(all_strings).with_provenance_of(&values);
         }
 
         Value::unextractable(UnresolvedSource::MixedTypes)
@@ -390,6 +569,24 @@ mod tests {
         assert_eq!(result.as_int(), Some(16));
     }
 
+    #[test]
+    fn test_concatenate() {
+        let prefix = Value::resolved_string("aes-".to_string());
+        let bits = Value::resolved_ints(vec![128, 256]);
+        let suffix = Value::resolved_string("-gcm".to_string());
+
+        let joined = Value::concatenate(&prefix, &bits, true).unwrap();
+        let joined = Value::concatenate(&joined, &suffix, true).unwrap();
+        assert!(joined.is_resolved);
+        assert_eq!(joined.string_values, vec!["aes-128-gcm", "aes-256-gcm"]);
+
+        assert!(Value::concatenate(&prefix, &bits, false).is_none());
+        assert!(Value::concatenate(&bits, &bits, true).is_none());
+        assert!(Value::concatenate(&prefix, &Value::unextractable("config_value"), true).is_none());
+        let many = Value::resolved_strings((0..9).map(|i| i.to_string()).collect());
+        assert!(Value::concatenate(&many, &many, true).is_none());
+    }
+
     #[test]
     fn test_binary_op_partial() {
         let left = Value::resolved_int(100000);
@@ -400,6 +597,53 @@ mod tests {
         assert_eq!(result.expression, "100000 + <config_value>");
     }
 
+    #[test]
+    fn test_binary_op_keeps_evidence() {
+        let step = |name: &str, line| Evidence {
+            kind: EvidenceKind::Constant,
+            name: name.to_string(),
+            file: "kdf.go".to_string(),
+            line,
+        };
+        let left = Value::resolved_int(300000).with_evidence(step("base", 3));
+        let right = Value::resolved_int(2).with_evidence(step("factor", 4));
+        let result = Value::binary_op(&left, "*", &right)
+            .with_evidence(step("iterations", 5))
+            .with_evidence(step("base", 3));
+
+        assert_eq!(result.as_int(), Some(600000));
+        let names: Vec<&str> = result.evidence.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["iterations", "base", "factor"]);
+    }
+
+    #[test]
+    fn test_binary_op_merges_spans() {
+        let span = |start_byte, end_byte| Span {
+            file: "kdf.go".to_string(),
+            start_byte,
+            end_byte,
+            start_line: 3,
+            end_line: 3,
+        };
+        let left = Value::resolved_int(1).with_span(span(10, 11));
+        let right = Value::resolved_int(16)
+            .with_span(span(15, 17))
+            .with_span(span(15, 17));
+        let result = Value::binary_op(&left, "<<", &right);
+
+        assert_eq!(result.as_int(), Some(65536));
+        assert_eq!(result.spans, vec![span(10, 11), span(15, 17)]);
+    }
+
+    #[test]
+    fn test_binary_op_unsupported_operator() {
+        let result = Value::binary_op(&Value::resolved_int(2), "**", &Value::resolved_int(10));
+
+        assert!(!result.is_resolved);
+        assert_eq!(result.source, "unsupported_operator");
+        assert_eq!(result.expression, "2 ** 10");
+    }
+
     #[test]
     fn test_unary_op_negation() {
         let operand = Value::resolved_int(42);
@@ -459,6 +703,20 @@ mod tests {
         assert_eq!(result.source, "mixed_resolution");
     }
 
+    #[test]
+    fn test_config_key() {
+        let document = Value::config_value(None);
+        let section = document.config_key("crypto").unwrap();
+        assert_eq!(section.expression, "crypto");
+        let value = section.config_key("iterations").unwrap();
+        assert_eq!(value.source, "config_value");
+        assert_eq!(value.expression, "crypto.iterations");
+        assert!(Value::partial_expression("cfg")
+            .config_key("crypto")
+            .is_none());
+        assert!(Value::resolved_int(1).config_key("crypto").is_none());
+    }
+
     #[test]
     fn test_partial_expression() {
         let val = Value::partial_expression("iterations + 10000");
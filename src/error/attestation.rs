@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AttestationError {
+    #[error("failed to read input '{path}': {message}")]
+    InputError { path: PathBuf, message: String },
+
+    #[error("failed to write '{path}': {message}")]
+    WriteError { path: PathBuf, message: String },
+
+    #[error("failed to run '{command}': {message} (is cosign installed?)")]
+    SignerUnavailable { command: String, message: String },
+
+    #[error("signing failed: {message}")]
+    SigningFailed { message: String },
+}
+
+impl AttestationError {
+    pub fn input_error(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::InputError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn write_error(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::WriteError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn signer_unavailable(command: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::SignerUnavailable {
+            command: command.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn signing_failed(message: impl Into<String>) -> Self {
+        Self::SigningFailed {
+            message: message.into(),
+        }
+    }
+}
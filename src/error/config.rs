@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config file '{path}': {message}")]
+    ReadError { path: PathBuf, message: String },
+
+    #[error("failed to parse config file '{path}': {message}")]
+    ParseError { path: PathBuf, message: String },
+
+    #[error("unsupported config format: {extension} (expected json, yaml, or yml)")]
+    UnsupportedFormat { extension: String },
+
+    #[error("unknown resolution strategy '{name}' (available: {available})")]
+    UnknownStrategy { name: String, available: String },
+}
+
+impl ConfigError {
+    pub fn read_error(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::ReadError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn parse_error(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self::ParseError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported_format(extension: impl Into<String>) -> Self {
+        Self::UnsupportedFormat {
+            extension: extension.into(),
+        }
+    }
+
+    pub fn unknown_strategy(name: impl Into<String>, available: &[&str]) -> Self {
+        Self::UnknownStrategy {
+            name: name.into(),
+            available: available.join(", "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_strategy_display() {
+        let err = ConfigError::unknown_strategy("magic", &["literal", "call"]);
+        assert_eq!(
+            err.to_string(),
+            "unknown resolution strategy 'magic' (available: literal, call)"
+        );
+    }
+}
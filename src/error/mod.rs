@@ -1,10 +1,16 @@
+mod attestation;
 mod classifier;
+mod config;
 mod io;
+mod notify;
 mod parser;
 mod query;
 
+pub use attestation::AttestationError;
 pub use classifier::ClassifierError;
+pub use config::ConfigError;
 pub use io::IoError;
+pub use notify::NotifyError;
 pub use parser::ParserError;
 pub use query::QueryError;
 
@@ -23,6 +29,19 @@ pub enum Error {
 
     #[error(transparent)]
     Query(#[from] QueryError),
+
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+
+    #[error(transparent)]
+    Attestation(#[from] AttestationError),
+
+    #[error(transparent)]
+    Notify(#[from] NotifyError),
+
+    #[cfg(feature = "discovery")]
+    #[error(transparent)]
+    Discovery(#[from] crate::discovery::loader::LoadError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NotifyError {
+    #[error("failed to run '{command}': {message} (is curl installed?)")]
+    SenderUnavailable { command: String, message: String },
+
+    #[error("posting to the webhook failed: {message}")]
+    PostFailed { message: String },
+
+    #[error("the webhook URL has a control character at byte {index}")]
+    InvalidWebhook { index: usize },
+}
+
+impl NotifyError {
+    pub fn sender_unavailable(command: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::SenderUnavailable {
+            command: command.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn post_failed(message: impl Into<String>) -> Self {
+        Self::PostFailed {
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_webhook(index: usize) -> Self {
+        Self::InvalidWebhook { index }
+    }
+}
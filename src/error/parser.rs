@@ -9,6 +9,12 @@ pub enum ParserError {
     #[error("failed to set parser language: {language}")]
     LanguageSetupFailed { language: String },
 
+    #[error("{language} support is not compiled into this build (enable the `{feature}` feature)")]
+    LanguageNotCompiled {
+        language: String,
+        feature: &'static str,
+    },
+
     #[error("failed to parse source code in {path}")]
     ParseFailed { path: PathBuf },
 
@@ -21,6 +27,16 @@ pub enum ParserError {
 
     #[error("invalid node type: expected {expected}, found {found}")]
     InvalidNodeType { expected: String, found: String },
+
+    #[error("no open document for {path}")]
+    UnknownDocument { path: PathBuf },
+
+    #[error("edit range {start}..{end} is outside the document ({len} bytes)")]
+    InvalidEdit {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
 }
 
 impl ParserError {
@@ -36,10 +52,26 @@ impl ParserError {
         }
     }
 
+    pub fn language_not_compiled(language: crate::engine::Language) -> Self {
+        let feature = crate::grammars::feature(language);
+        Self::LanguageNotCompiled {
+            language: feature.trim_start_matches("lang-").to_string(),
+            feature,
+        }
+    }
+
     pub fn parse_failed(path: impl Into<PathBuf>) -> Self {
         Self::ParseFailed { path: path.into() }
     }
 
+    pub fn unknown_document(path: impl Into<PathBuf>) -> Self {
+        Self::UnknownDocument { path: path.into() }
+    }
+
+    pub fn invalid_edit(start: usize, end: usize, len: usize) -> Self {
+        Self::InvalidEdit { start, end, len }
+    }
+
     pub fn syntax_error(line: usize, column: usize, message: impl Into<String>) -> Self {
         Self::SyntaxError {
             line,
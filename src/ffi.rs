@@ -0,0 +1,370 @@
+//! C API (`--features ffi`).
+//!
+//! Every scan returns a heap-allocated [`ArgflowResult`] that the caller must
+//! release with [`argflow_free_result`]. On success `json` holds the report
+//! (the same document the CLI prints); otherwise `error` holds a message.
+//! Callers scanning repeatedly with the same rules create an
+//! [`ArgflowScanner`] once and scan through it.
+//! The header is generated into `include/argflow.h` by the build script.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+use crate::api::{self, RulesScanner};
+use crate::classifier::RulesClassifier;
+use crate::output::JsonOutput;
+
+pub const ARGFLOW_OK: i32 = 0;
+pub const ARGFLOW_INVALID_ARGUMENT: i32 = 1;
+pub const ARGFLOW_SCAN_ERROR: i32 = 2;
+pub const ARGFLOW_PANIC: i32 = 3;
+
+#[repr(C)]
+pub struct ArgflowResult {
+    /// `ARGFLOW_OK` on success, otherwise one of the error codes.
+    pub status: i32,
+    /// JSON report, or null on failure.
+    pub json: *mut c_char,
+    /// Error message, or null on success.
+    pub error: *mut c_char,
+}
+
+impl ArgflowResult {
+    fn ok(json: String) -> Self {
+        Self {
+            status: ARGFLOW_OK,
+            json: to_c_string(json),
+            error: ptr::null_mut(),
+        }
+    }
+
+    fn error(status: i32, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            json: ptr::null_mut(),
+            error: to_c_string(message.into()),
+        }
+    }
+}
+
+/// Scans a single source string.
+///
+/// `rules_json` may be null, in which case no calls are matched.
+///
+/// # Safety
+///
+/// `source` and `language` must be valid NUL-terminated strings; `rules_json`
+/// must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scan_source(
+    source: *const c_char,
+    language: *const c_char,
+    rules_json: *const c_char,
+) -> *mut ArgflowResult {
+    run(|| {
+        let source = required_str(source, "source")?;
+        let language = required_str(language, "language")?;
+        let classifier = classifier_from(rules_json)?;
+
+        api::scan_source(source, language, &classifier)
+            .map_err(|e| ArgflowResult::error(ARGFLOW_SCAN_ERROR, e.to_string()))
+    })
+}
+
+/// Scans the user code under `path` for one language.
+///
+/// # Safety
+///
+/// `path` and `language` must be valid NUL-terminated strings; `rules_json`
+/// must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scan_directory(
+    path: *const c_char,
+    language: *const c_char,
+    rules_json: *const c_char,
+) -> *mut ArgflowResult {
+    run(|| {
+        let path = required_str(path, "path")?;
+        let language = required_str(language, "language")?;
+        let classifier = classifier_from(rules_json)?;
+
+        api::scan_directory(Path::new(path), language, &classifier)
+            .map_err(|e| ArgflowResult::error(ARGFLOW_SCAN_ERROR, e.to_string()))
+    })
+}
+
+/// A scanner built from one rules document, reused across scans. Opaque to
+/// C; create with [`argflow_scanner_new`] and release with
+/// [`argflow_scanner_free`].
+pub struct ArgflowScanner {
+    inner: RulesScanner,
+}
+
+/// Builds a scanner from `rules_json`, which may be null, in which case no
+/// calls are matched. Returns null when the rules are invalid.
+///
+/// # Safety
+///
+/// `rules_json` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scanner_new(rules_json: *const c_char) -> *mut ArgflowScanner {
+    let built = catch_unwind(AssertUnwindSafe(|| classifier_from(rules_json).ok()));
+    match built {
+        Ok(Some(classifier)) => Box::into_raw(Box::new(ArgflowScanner {
+            inner: RulesScanner::new(classifier),
+        })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Like [`argflow_scan_source`], with the rules of `scanner`.
+///
+/// # Safety
+///
+/// `scanner` must be a live pointer returned by [`argflow_scanner_new`];
+/// `source` and `language` must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scanner_scan_source(
+    scanner: *const ArgflowScanner,
+    source: *const c_char,
+    language: *const c_char,
+) -> *mut ArgflowResult {
+    run(|| {
+        let scanner = required_scanner(scanner)?;
+        let source = required_str(source, "source")?;
+        let language = required_str(language, "language")?;
+
+        scanner
+            .scan_source(source, language)
+            .map_err(|e| ArgflowResult::error(ARGFLOW_SCAN_ERROR, e.to_string()))
+    })
+}
+
+/// Like [`argflow_scan_directory`], with the rules of `scanner`.
+///
+/// # Safety
+///
+/// `scanner` must be a live pointer returned by [`argflow_scanner_new`];
+/// `path` and `language` must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scanner_scan_directory(
+    scanner: *const ArgflowScanner,
+    path: *const c_char,
+    language: *const c_char,
+) -> *mut ArgflowResult {
+    run(|| {
+        let scanner = required_scanner(scanner)?;
+        let path = required_str(path, "path")?;
+        let language = required_str(language, "language")?;
+
+        scanner
+            .scan_directory(Path::new(path), language)
+            .map_err(|e| ArgflowResult::error(ARGFLOW_SCAN_ERROR, e.to_string()))
+    })
+}
+
+/// Releases a scanner returned by [`argflow_scanner_new`].
+///
+/// # Safety
+///
+/// `scanner` must be null or a pointer returned by [`argflow_scanner_new`]
+/// that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_scanner_free(scanner: *mut ArgflowScanner) {
+    if !scanner.is_null() {
+        drop(Box::from_raw(scanner));
+    }
+}
+
+/// Releases a result returned by any `argflow_scan_*` function.
+///
+/// # Safety
+///
+/// `result` must be null or a pointer returned by this library that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn argflow_free_result(result: *mut ArgflowResult) {
+    if result.is_null() {
+        return;
+    }
+    let result = Box::from_raw(result);
+    if !result.json.is_null() {
+        drop(CString::from_raw(result.json));
+    }
+    if !result.error.is_null() {
+        drop(CString::from_raw(result.error));
+    }
+}
+
+/// Returns the library version as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn argflow_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+fn run<F>(scan: F) -> *mut ArgflowResult
+where
+    F: FnOnce() -> Result<JsonOutput, ArgflowResult>,
+{
+    let result = match catch_unwind(AssertUnwindSafe(scan)) {
+        Ok(Ok(output)) => match serde_json::to_string(&output) {
+            Ok(json) => ArgflowResult::ok(json),
+            Err(e) => ArgflowResult::error(ARGFLOW_SCAN_ERROR, e.to_string()),
+        },
+        Ok(Err(error)) => error,
+        Err(_) => ArgflowResult::error(ARGFLOW_PANIC, "internal error while scanning"),
+    };
+    Box::into_raw(Box::new(result))
+}
+
+unsafe fn required_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, ArgflowResult> {
+    if ptr.is_null() {
+        return Err(ArgflowResult::error(
+            ARGFLOW_INVALID_ARGUMENT,
+            format!("{name} must not be null"),
+        ));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| {
+        ArgflowResult::error(
+            ARGFLOW_INVALID_ARGUMENT,
+            format!("{name} is not valid UTF-8"),
+        )
+    })
+}
+
+unsafe fn required_scanner<'a>(
+    scanner: *const ArgflowScanner,
+) -> Result<&'a RulesScanner, ArgflowResult> {
+    scanner
+        .as_ref()
+        .map(|scanner| &scanner.inner)
+        .ok_or_else(|| ArgflowResult::error(ARGFLOW_INVALID_ARGUMENT, "scanner must not be null"))
+}
+
+unsafe fn classifier_from(rules_json: *const c_char) -> Result<RulesClassifier, ArgflowResult> {
+    if rules_json.is_null() {
+        return Ok(RulesClassifier::new());
+    }
+    let rules = required_str(rules_json, "rules_json")?;
+    RulesClassifier::from_json_str(rules)
+        .map_err(|e| ArgflowResult::error(ARGFLOW_INVALID_ARGUMENT, e.to_string()))
+}
+
+fn to_c_string(value: String) -> *mut c_char {
+    CString::new(value.replace('\0', ""))
+        .expect("interior NULs were removed")
+        .into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &CStr = c"{\"mappings\": {\"crypto/md5\": {\"Sum\": \"md5\"}}}";
+
+    unsafe fn take(result: *mut ArgflowResult) -> (i32, Option<String>, Option<String>) {
+        let r = &*result;
+        let read = |p: *mut c_char| {
+            (!p.is_null()).then(|| CStr::from_ptr(p).to_string_lossy().to_string())
+        };
+        let out = (r.status, read(r.json), read(r.error));
+        argflow_free_result(result);
+        out
+    }
+
+    #[test]
+    fn test_scan_source_returns_json() {
+        let source = c"package main\nimport \"crypto/md5\"\nfunc f(b []byte) { md5.Sum(b) }\n";
+        let (status, json, error) = unsafe {
+            take(argflow_scan_source(
+                source.as_ptr(),
+                c"go".as_ptr(),
+                RULES.as_ptr(),
+            ))
+        };
+
+        assert_eq!(status, ARGFLOW_OK);
+        assert!(error.is_none());
+        let report: serde_json::Value = serde_json::from_str(&json.unwrap()).unwrap();
+        assert_eq!(report["total_findings"], 1);
+        assert_eq!(report["findings"][0]["function"], "Sum");
+    }
+
+    #[test]
+    fn test_scan_source_reports_errors() {
+        let (status, json, error) = unsafe {
+            take(argflow_scan_source(
+                c"x".as_ptr(),
+                c"cobol".as_ptr(),
+                ptr::null(),
+            ))
+        };
+        assert_eq!(status, ARGFLOW_SCAN_ERROR);
+        assert!(json.is_none());
+        assert!(error.unwrap().contains("cobol"));
+
+        let (status, _, error) = unsafe {
+            take(argflow_scan_source(
+                ptr::null(),
+                c"go".as_ptr(),
+                ptr::null(),
+            ))
+        };
+        assert_eq!(status, ARGFLOW_INVALID_ARGUMENT);
+        assert_eq!(error.unwrap(), "source must not be null");
+    }
+
+    #[test]
+    fn test_scanner_is_reused_across_scans() {
+        let source = c"package main\nimport \"crypto/md5\"\nfunc f(b []byte) { md5.Sum(b) }\n";
+        let scanner = unsafe { argflow_scanner_new(RULES.as_ptr()) };
+        assert!(!scanner.is_null());
+
+        for _ in 0..2 {
+            let (status, json, _) = unsafe {
+                take(argflow_scanner_scan_source(
+                    scanner,
+                    source.as_ptr(),
+                    c"go".as_ptr(),
+                ))
+            };
+            assert_eq!(status, ARGFLOW_OK);
+            let report: serde_json::Value = serde_json::from_str(&json.unwrap()).unwrap();
+            assert_eq!(report["total_findings"], 1);
+        }
+        unsafe { argflow_scanner_free(scanner) };
+
+        let (status, _, error) = unsafe {
+            take(argflow_scanner_scan_source(
+                ptr::null(),
+                source.as_ptr(),
+                c"go".as_ptr(),
+            ))
+        };
+        assert_eq!(status, ARGFLOW_INVALID_ARGUMENT);
+        assert_eq!(error.unwrap(), "scanner must not be null");
+        assert!(unsafe { argflow_scanner_new(c"not json".as_ptr()) }.is_null());
+    }
+
+    #[test]
+    fn test_scan_directory_missing_path() {
+        let (status, _, error) = unsafe {
+            take(argflow_scan_directory(
+                c"/nonexistent/argflow".as_ptr(),
+                c"go".as_ptr(),
+                ptr::null(),
+            ))
+        };
+        assert_eq!(status, ARGFLOW_SCAN_ERROR);
+        assert!(error.unwrap().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_free_null_and_version() {
+        unsafe { argflow_free_result(ptr::null_mut()) };
+        let version = unsafe { CStr::from_ptr(argflow_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -0,0 +1,140 @@
+//! The tree-sitter grammars compiled into this build. Each language's
+//! grammar is behind a `lang-<language>` cargo feature, all enabled by
+//! default, so a library user can build with only the languages they scan.
+//! A language left out has no grammar here, and scanning it fails with
+//! [`ParserError::LanguageNotCompiled`] naming the feature to enable.
+
+use crate::engine::Language;
+use crate::error::ParserError;
+
+/// Every language argflow has a grammar for, compiled in or not.
+pub const ALL: [Language; 14] = [
+    Language::Go,
+    Language::Python,
+    Language::Rust,
+    Language::JavaScript,
+    Language::TypeScript,
+    Language::Java,
+    Language::C,
+    Language::Cpp,
+    Language::CSharp,
+    Language::Ruby,
+    Language::Php,
+    Language::Swift,
+    Language::Kotlin,
+    Language::Scala,
+];
+
+/// The grammar of `language`, if compiled in.
+pub fn grammar(language: Language) -> Option<tree_sitter::Language> {
+    match language {
+        #[cfg(feature = "lang-go")]
+        Language::Go => Some(tree_sitter_go::LANGUAGE.into()),
+        #[cfg(feature = "lang-python")]
+        Language::Python => Some(tree_sitter_python::LANGUAGE.into()),
+        #[cfg(feature = "lang-rust")]
+        Language::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
+        #[cfg(feature = "lang-javascript")]
+        Language::JavaScript => Some(tree_sitter_javascript::LANGUAGE.into()),
+        #[cfg(feature = "lang-typescript")]
+        Language::TypeScript => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        #[cfg(feature = "lang-java")]
+        Language::Java => Some(tree_sitter_java::LANGUAGE.into()),
+        #[cfg(feature = "lang-c")]
+        Language::C => Some(tree_sitter_c::LANGUAGE.into()),
+        #[cfg(feature = "lang-cpp")]
+        Language::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
+        #[cfg(feature = "lang-csharp")]
+        Language::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+        #[cfg(feature = "lang-ruby")]
+        Language::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+        #[cfg(feature = "lang-php")]
+        Language::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+        #[cfg(feature = "lang-swift")]
+        Language::Swift => Some(tree_sitter_swift::LANGUAGE.into()),
+        #[cfg(feature = "lang-kotlin")]
+        Language::Kotlin => Some(tree_sitter_kotlin_ng::LANGUAGE.into()),
+        #[cfg(feature = "lang-scala")]
+        Language::Scala => Some(tree_sitter_scala::LANGUAGE.into()),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// The TSX grammar, which comes with TypeScript's.
+pub fn tsx_grammar() -> Option<tree_sitter::Language> {
+    #[cfg(feature = "lang-typescript")]
+    return Some(tree_sitter_typescript::LANGUAGE_TSX.into());
+    #[cfg(not(feature = "lang-typescript"))]
+    None
+}
+
+/// The grammar of `language`, or the error telling which feature is
+/// missing.
+pub fn require(language: Language) -> Result<tree_sitter::Language, ParserError> {
+    grammar(language).ok_or_else(|| ParserError::language_not_compiled(language))
+}
+
+pub fn is_available(language: Language) -> bool {
+    grammar(language).is_some()
+}
+
+/// The languages compiled in, in [`ALL`] order.
+pub fn available() -> Vec<Language> {
+    ALL.into_iter().filter(|l| is_available(*l)).collect()
+}
+
+/// The cargo feature that compiles in the grammar of `language`.
+pub fn feature(language: Language) -> &'static str {
+    match language {
+        Language::Go => "lang-go",
+        Language::Python => "lang-python",
+        Language::Rust => "lang-rust",
+        Language::JavaScript => "lang-javascript",
+        Language::TypeScript => "lang-typescript",
+        Language::Java => "lang-java",
+        Language::C => "lang-c",
+        Language::Cpp => "lang-cpp",
+        Language::CSharp => "lang-csharp",
+        Language::Ruby => "lang-ruby",
+        Language::Php => "lang-php",
+        Language::Swift => "lang-swift",
+        Language::Kotlin => "lang-kotlin",
+        Language::Scala => "lang-scala",
+    }
+}
+
+#[cfg(all(test, feature = "all-languages"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_build_has_every_grammar() {
+        assert_eq!(available(), ALL.to_vec());
+        assert!(tsx_grammar().is_some());
+        assert_eq!(feature(Language::CSharp), "lang-csharp");
+        assert_eq!(
+            ParserError::language_not_compiled(Language::CSharp).to_string(),
+            "csharp support is not compiled into this build (enable the `lang-csharp` feature)"
+        );
+    }
+}
+
+/// Run with a slim build, e.g. `--no-default-features --features lang-go`.
+#[cfg(all(test, not(feature = "lang-csharp")))]
+mod slim_tests {
+    use super::*;
+
+    #[test]
+    fn test_grammar_left_out_is_not_compiled() {
+        assert!(!is_available(Language::CSharp));
+        assert!(!available().contains(&Language::CSharp));
+        match require(Language::CSharp) {
+            Err(ParserError::LanguageNotCompiled { language, feature }) => {
+                assert_eq!(language, "csharp");
+                assert_eq!(feature, "lang-csharp");
+            }
+            other => panic!("expected LanguageNotCompiled, got {:?}", other.map(|_| ())),
+        }
+    }
+}
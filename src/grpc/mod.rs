@@ -0,0 +1,230 @@
+//! gRPC scanning service (`--features grpc`).
+//!
+//! Implements `argflow.v1.ScanService` from `proto/argflow/v1/scan.proto`.
+//! Scans run on the blocking pool; `StreamFindings` sends each file's
+//! findings as soon as that file is scanned.
+
+pub mod proto;
+
+mod service {
+    include!(concat!(env!("OUT_DIR"), "/argflow.v1.ScanService.rs"));
+}
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::api::RulesScanner;
+use crate::classifier::RulesClassifier;
+use crate::engine::symbol_index::language_for_path;
+use crate::error::{Error, IoError, ParserError};
+use crate::output;
+
+use proto::{Finding, ScanFileRequest, ScanProjectRequest, ScanReport};
+pub use service::scan_service_client::ScanServiceClient;
+pub use service::scan_service_server::{ScanService, ScanServiceServer};
+
+/// Findings buffered per `StreamFindings` call before the scan waits on the client.
+const STREAM_BUFFER: usize = 128;
+
+/// `ScanService` implementation. Requests without `rules_json` are
+/// classified with the server's default rules, whose scanner is built once.
+#[derive(Clone)]
+pub struct ScanServer {
+    scanner: Arc<RulesScanner>,
+}
+
+impl ScanServer {
+    pub fn new(classifier: RulesClassifier) -> Self {
+        Self {
+            scanner: Arc::new(RulesScanner::new(classifier)),
+        }
+    }
+
+    pub fn into_service(self) -> ScanServiceServer<Self> {
+        ScanServiceServer::new(self)
+    }
+
+    fn scanner_for(&self, rules_json: &str) -> Result<Arc<RulesScanner>, Status> {
+        if rules_json.is_empty() {
+            return Ok(Arc::clone(&self.scanner));
+        }
+        RulesClassifier::from_json_str(rules_json)
+            .map(|classifier| Arc::new(RulesScanner::new(classifier)))
+            .map_err(|e| Status::invalid_argument(e.to_string()))
+    }
+}
+
+#[tonic::async_trait]
+impl ScanService for ScanServer {
+    async fn scan_file(
+        &self,
+        request: Request<ScanFileRequest>,
+    ) -> Result<Response<ScanReport>, Status> {
+        let request = request.into_inner();
+        let scanner = self.scanner_for(&request.rules_json)?;
+
+        let language = if request.language.is_empty() {
+            language_for_path(Path::new(&request.file_name))
+                .map(|lang| lang.tree_sitter_name().to_string())
+                .ok_or_else(|| {
+                    Status::invalid_argument(format!(
+                        "cannot infer language from '{}'",
+                        request.file_name
+                    ))
+                })?
+        } else {
+            request.language
+        };
+
+        let output = run_blocking(move || {
+            scanner
+                .scan_named_source(&request.source, &request.file_name, &language)
+                .map_err(Error::from)
+        })
+        .await?;
+        Ok(Response::new(output.into()))
+    }
+
+    async fn scan_project(
+        &self,
+        request: Request<ScanProjectRequest>,
+    ) -> Result<Response<ScanReport>, Status> {
+        let request = request.into_inner();
+        let scanner = self.scanner_for(&request.rules_json)?;
+
+        let output = run_blocking(move || {
+            scanner.scan_directory(Path::new(&request.path), &request.language)
+        })
+        .await?;
+        Ok(Response::new(output.into()))
+    }
+
+    type StreamFindingsStream = ReceiverStream<Result<Finding, Status>>;
+
+    async fn stream_findings(
+        &self,
+        request: Request<ScanProjectRequest>,
+    ) -> Result<Response<Self::StreamFindingsStream>, Status> {
+        let request = request.into_inner();
+        let scanner = self.scanner_for(&request.rules_json)?;
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        tokio::task::spawn_blocking(move || {
+            let root = PathBuf::from(&request.path);
+            let scanned = scanner.scan_directory_each(&root, &request.language, |result| {
+                for call in &result.calls {
+                    let finding = output::Finding::from_scanner_finding(call, scanner.classifier());
+                    // A closed channel means the client went away; the rest is dropped.
+                    let _ = tx.blocking_send(Ok(finding.into()));
+                }
+            });
+            if let Err(e) = scanned {
+                let _ = tx.blocking_send(Err(to_status(e)));
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+async fn run_blocking<F>(scan: F) -> Result<output::JsonOutput, Status>
+where
+    F: FnOnce() -> crate::error::Result<output::JsonOutput> + Send + 'static,
+{
+    tokio::task::spawn_blocking(scan)
+        .await
+        .map_err(|e| Status::internal(format!("scan task failed: {e}")))?
+        .map_err(to_status)
+}
+
+fn to_status(error: Error) -> Status {
+    let message = error.to_string();
+    match error {
+        Error::Parser(ParserError::UnsupportedLanguage { .. }) | Error::Classifier(_) => {
+            Status::invalid_argument(message)
+        }
+        Error::Io(IoError::FileNotFound { .. } | IoError::DirectoryNotFound { .. })
+        | Error::Discovery(_) => Status::not_found(message),
+        _ => Status::internal(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    const RULES: &str = r#"{
+        "classifications": {
+            "pbkdf2": {"algorithm": "PBKDF2", "findingType": "kdf", "operation": "keyderive"}
+        },
+        "mappings": {
+            "golang.org/x/crypto/pbkdf2": {"Key": "pbkdf2"}
+        }
+    }"#;
+
+    const SOURCE: &str = "package main\n\nimport \"golang.org/x/crypto/pbkdf2\"\n\nfunc f(pw, salt []byte) {\n\tpbkdf2.Key(pw, salt, 4096, 32, nil)\n}\n";
+
+    fn server() -> ScanServer {
+        ScanServer::new(RulesClassifier::from_json_str(RULES).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_scan_file_infers_language() {
+        let request = ScanFileRequest {
+            file_name: "kdf.go".to_string(),
+            source: SOURCE.to_string(),
+            ..Default::default()
+        };
+
+        let report = server()
+            .scan_file(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(report.findings.len(), 1);
+
+        let finding = &report.findings[0];
+        assert_eq!(finding.file, "kdf.go");
+        assert_eq!(finding.algorithm, "PBKDF2");
+        assert_eq!(finding.parameters["arg2"], "4096");
+    }
+
+    #[tokio::test]
+    async fn test_scan_file_rejects_unknown_language() {
+        let request = ScanFileRequest {
+            file_name: "kdf.cob".to_string(),
+            source: SOURCE.to_string(),
+            ..Default::default()
+        };
+
+        let status = server().scan_file(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_stream_findings_per_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.go"), SOURCE).unwrap();
+        std::fs::write(temp.path().join("b.go"), SOURCE).unwrap();
+
+        let request = ScanProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+            language: "go".to_string(),
+            rules_json: String::new(),
+        };
+        let stream = server()
+            .stream_findings(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let findings: Vec<Finding> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.algorithm == "PBKDF2"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Messages of `proto/argflow/v1/scan.proto`, written by hand so the build
+//! does not depend on `protoc`. Field tags must match the `.proto` file.
+
+use std::collections::HashMap;
+
+use crate::output;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScanFileRequest {
+    #[prost(string, tag = "1")]
+    pub file_name: String,
+    #[prost(string, tag = "2")]
+    pub source: String,
+    #[prost(string, tag = "3")]
+    pub language: String,
+    #[prost(string, tag = "4")]
+    pub rules_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScanProjectRequest {
+    #[prost(string, tag = "1")]
+    pub path: String,
+    #[prost(string, tag = "2")]
+    pub language: String,
+    #[prost(string, tag = "3")]
+    pub rules_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScanReport {
+    #[prost(uint64, tag = "1")]
+    pub files_scanned: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub findings: Vec<Finding>,
+    #[prost(message, repeated, tag = "3")]
+    pub configs: Vec<ConfigFinding>,
+    #[prost(uint32, tag = "4")]
+    pub risk_score: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Finding {
+    #[prost(string, tag = "1")]
+    pub file: String,
+    #[prost(uint64, tag = "2")]
+    pub line: u64,
+    #[prost(uint64, tag = "3")]
+    pub column: u64,
+    #[prost(string, tag = "4")]
+    pub function: String,
+    #[prost(string, tag = "5")]
+    pub package: String,
+    #[prost(string, tag = "6")]
+    pub import_path: String,
+    #[prost(string, tag = "7")]
+    pub full_name: String,
+    #[prost(string, tag = "8")]
+    pub algorithm: String,
+    #[prost(string, tag = "9")]
+    pub finding_type: String,
+    #[prost(string, tag = "10")]
+    pub operation: String,
+    #[prost(string, tag = "11")]
+    pub primitive: String,
+    #[prost(map = "string, string", tag = "12")]
+    pub parameters: HashMap<String, String>,
+    #[prost(string, tag = "13")]
+    pub raw_text: String,
+    #[prost(string, tag = "14")]
+    pub fingerprint: String,
+    #[prost(string, tag = "15")]
+    pub material_source: String,
+    #[prost(map = "string, string", tag = "16")]
+    pub keywords: HashMap<String, String>,
+    #[prost(message, optional, tag = "17")]
+    pub usage_context: Option<UsageContext>,
+    #[prost(string, tag = "18")]
+    pub rule_source: String,
+    #[prost(string, tag = "19")]
+    pub confidence: String,
+    #[prost(string, tag = "20")]
+    pub dynamic_name_json: String,
+    #[prost(map = "string, string", tag = "21")]
+    pub evidence_json: HashMap<String, String>,
+    #[prost(string, tag = "22")]
+    pub help_uri: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UsageContext {
+    #[prost(string, tag = "1")]
+    pub usage: String,
+    #[prost(string, tag = "2")]
+    pub confidence: String,
+    #[prost(string, tag = "3")]
+    pub reason: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ConfigFinding {
+    #[prost(string, tag = "1")]
+    pub file: String,
+    #[prost(uint64, tag = "2")]
+    pub line: u64,
+    #[prost(uint64, tag = "3")]
+    pub column: u64,
+    #[prost(string, tag = "4")]
+    pub struct_type: String,
+    #[prost(string, tag = "5")]
+    pub full_type: String,
+    #[prost(string, tag = "6")]
+    pub package: String,
+    #[prost(string, tag = "7")]
+    pub import_path: String,
+    #[prost(message, repeated, tag = "8")]
+    pub fields: Vec<ConfigField>,
+    #[prost(string, tag = "9")]
+    pub raw_text: String,
+    #[prost(string, tag = "10")]
+    pub fingerprint: String,
+    #[prost(string, tag = "11")]
+    pub call: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ConfigField {
+    #[prost(string, tag = "1")]
+    pub field_name: String,
+    #[prost(string, tag = "2")]
+    pub value_json: String,
+    #[prost(string, tag = "3")]
+    pub classification_key: String,
+}
+
+impl From<output::JsonOutput> for ScanReport {
+    fn from(output: output::JsonOutput) -> Self {
+        Self {
+            files_scanned: output.files_scanned as u64,
+            findings: output.findings.into_iter().map(Finding::from).collect(),
+            configs: output
+                .configs
+                .into_iter()
+                .map(ConfigFinding::from)
+                .collect(),
+            risk_score: output.risk_score,
+        }
+    }
+}
+
+impl From<output::Finding> for Finding {
+    fn from(finding: output::Finding) -> Self {
+        Self {
+            file: finding.file,
+            line: finding.line as u64,
+            column: finding.column as u64,
+            function: finding.function,
+            package: finding.package.unwrap_or_default(),
+            import_path: finding.import_path.unwrap_or_default(),
+            full_name: finding.full_name,
+            algorithm: finding.algorithm.unwrap_or_default(),
+            finding_type: finding.finding_type.unwrap_or_default(),
+            operation: finding.operation.unwrap_or_default(),
+            primitive: finding.primitive.unwrap_or_default(),
+            parameters: finding
+                .parameters
+                .into_iter()
+                .map(|(name, value)| (name, value.to_string()))
+                .collect(),
+            raw_text: finding.raw_text,
+            fingerprint: finding.fingerprint,
+            material_source: finding.material_source.unwrap_or_default(),
+            keywords: finding.keywords,
+            usage_context: finding.usage_context.map(|context| UsageContext {
+                usage: context.usage.as_str().to_string(),
+                confidence: context.confidence.as_str().to_string(),
+                reason: context.reason,
+            }),
+            rule_source: finding.rule_source.unwrap_or_default(),
+            confidence: finding
+                .confidence
+                .map(|confidence| confidence.as_str().to_string())
+                .unwrap_or_default(),
+            dynamic_name_json: finding
+                .dynamic_name
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+            evidence_json: finding
+                .evidence
+                .into_iter()
+                .map(|(name, steps)| {
+                    let json = serde_json::to_string(&steps).unwrap_or_default();
+                    (name, json)
+                })
+                .collect(),
+            help_uri: finding.help_uri.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<output::ConfigFinding> for ConfigFinding {
+    fn from(config: output::ConfigFinding) -> Self {
+        Self {
+            file: config.file,
+            line: config.line as u64,
+            column: config.column as u64,
+            struct_type: config.struct_type,
+            full_type: config.full_type,
+            package: config.package.unwrap_or_default(),
+            import_path: config.import_path.unwrap_or_default(),
+            fields: config
+                .fields
+                .into_iter()
+                .map(|field| ConfigField {
+                    field_name: field.field_name,
+                    value_json: field.value.to_string(),
+                    classification_key: field.classification_key.unwrap_or_default(),
+                })
+                .collect(),
+            raw_text: config.raw_text,
+            fingerprint: config.fingerprint,
+            call: config.call.unwrap_or_default(),
+        }
+    }
+}
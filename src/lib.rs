@@ -3,25 +3,44 @@
 /// Argument flow analyzer - traces where function arguments come from across
 /// multi-language codebases using Tree-sitter for parsing and a resolution
 /// engine that works across multiple languages.
+pub mod api;
+pub mod batch;
+pub mod cancel;
 pub mod classifier;
 pub mod cli;
+pub mod config;
+pub mod corpus;
+#[cfg(feature = "discovery")]
 pub mod discovery;
 pub mod engine;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod grammars;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod logging;
 pub mod mappings;
+#[cfg(feature = "node")]
+pub mod node;
 pub mod output;
+pub mod policy;
 pub mod presets;
 pub mod query;
 pub mod scanner;
+#[cfg(test)]
+mod test_support;
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub use api::{scan_source, RulesScanner};
 pub use classifier::{
     classify_call, Classification, ClassifiedCall, Classifier, ClassifierError, RulesClassifier,
 };
 pub use engine::{Context, Resolver, Value};
-pub use error::{Error, IoError, ParserError, QueryError};
-pub use logging::Verbosity;
+pub use error::{ConfigError, Error, IoError, ParserError, QueryError};
+pub use logging::{LogFilter, Verbosity};
 pub use output::{ConfigFinding, Finding, JsonOutput, OutputFormatter};
 pub use presets::{load_preset, load_presets, PresetMetadata};
 pub use scanner::{CallMatcher, ImportMap, PatternMatcher, ScanResult, Scanner};
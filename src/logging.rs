@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use tracing::Level;
+use tracing_subscriber::filter::{Directive, LevelFilter};
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,13 +42,72 @@ impl Verbosity {
     }
 }
 
-pub fn init(verbosity: Verbosity) {
-    let filter =
+/// Per-module levels from `--log-filter`, in `RUST_LOG` syntax with module
+/// paths relative to the crate: `scanner=debug,engine::strategies=trace`.
+/// A bare level applies to the whole crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    directives: Vec<String>,
+}
+
+impl LogFilter {
+    fn directives(&self) -> impl Iterator<Item = Directive> + '_ {
+        self.directives
+            .iter()
+            .map(|d| d.parse().expect("validated when parsed"))
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let directives = s
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| {
+                let qualified = qualify_directive(d);
+                qualified
+                    .parse::<Directive>()
+                    .map(|_| qualified)
+                    .map_err(|e| format!("invalid log filter directive `{d}`: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if directives.is_empty() {
+            return Err("empty log filter".to_string());
+        }
+        Ok(Self { directives })
+    }
+}
+
+/// `scanner=debug` -> `argflow::scanner=debug`, `debug` -> `argflow=debug`.
+/// Targets already starting with `argflow` are left alone.
+fn qualify_directive(directive: &str) -> String {
+    if LevelFilter::from_str(directive).is_ok() {
+        return format!("argflow={directive}");
+    }
+    if directive.starts_with("argflow") || directive.starts_with('[') {
+        return directive.to_string();
+    }
+    format!("argflow::{directive}")
+}
+
+/// Installs the global subscriber. `RUST_LOG`, when set, replaces the level
+/// chosen by `verbosity`; `log_filter` directives are added on top of
+/// either and show each event's module.
+pub fn init(verbosity: Verbosity, log_filter: Option<&LogFilter>) {
+    let mut filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(verbosity.to_filter()));
+    if let Some(log_filter) = log_filter {
+        for directive in log_filter.directives() {
+            filter = filter.add_directive(directive);
+        }
+    }
 
     let subscriber = fmt::Subscriber::builder()
         .with_env_filter(filter)
-        .with_target(false)
+        .with_target(log_filter.is_some())
         .with_thread_ids(false)
         .with_file(verbosity >= Verbosity::Debug)
         .with_line_number(verbosity >= Verbosity::Debug)
@@ -109,6 +171,26 @@ mod tests {
         assert_eq!(Verbosity::from_flags(3, true), Verbosity::Quiet);
     }
 
+    #[test]
+    fn test_log_filter_is_relative_to_the_crate() {
+        let filter: LogFilter = "scanner=debug, engine::strategies=trace,info"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter.directives,
+            vec![
+                "argflow::scanner=debug",
+                "argflow::engine::strategies=trace",
+                "argflow=info",
+            ]
+        );
+        let filter: LogFilter = "argflow::discovery=off".parse().unwrap();
+        assert_eq!(filter.directives, vec!["argflow::discovery=off"]);
+
+        assert!("scanner=loud".parse::<LogFilter>().is_err());
+        assert!(" , ".parse::<LogFilter>().is_err());
+    }
+
     #[test]
     fn test_verbosity_ordering() {
         assert!(Verbosity::Quiet < Verbosity::Normal);
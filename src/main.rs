@@ -1,57 +1,268 @@
 use anyhow::{Context as AnyhowContext, Result};
-use argflow::classifier::RulesClassifier;
+use argflow::batch::{BatchManifest, BatchRepo, BatchSummary, RepoSummary};
+use argflow::cancel;
+use argflow::classifier::{
+    is_crypto_capable, mapping_coverage, run_rule_tests, DependencyCoverage, Explanation,
+    RulesClassifier,
+};
 use argflow::cli::{self, OutputFormat};
+use argflow::config::Config;
+use argflow::corpus::{record_case, replay_corpus, ReplayOutcome};
+use argflow::discovery::archive::{ArchiveKind, UnpackedArchive};
+use argflow::discovery::budget::{dependency_name, load_dependencies, DepsBudget};
 use argflow::discovery::cache::DiscoveryCache;
-use argflow::discovery::filter::ImportFileFilter;
-use argflow::discovery::languages::go::{GoImportFilter, GoPackageLoader};
+use argflow::discovery::components::in_nested;
+use argflow::discovery::dry_run::{self, discover};
+use argflow::discovery::extensions::{self, ExtensionOverride};
+use argflow::discovery::filter::{FilterError, FilterTokens, ImportFileFilter};
+use argflow::discovery::git::{self, ClonedRepository};
+use argflow::discovery::image::ContainerImage;
+use argflow::discovery::languages::c::{CImportFilter, CPackageLoader};
+use argflow::discovery::languages::cpp::{CppImportFilter, CppPackageLoader};
+use argflow::discovery::languages::csharp::{CSharpImportFilter, CSharpPackageLoader};
+use argflow::discovery::languages::go::{artifacts, GoImportFilter, GoPackageLoader};
+use argflow::discovery::languages::java::{JavaImportFilter, JavaPackageLoader};
 use argflow::discovery::languages::javascript::{JavaScriptImportFilter, JavaScriptPackageLoader};
-use argflow::discovery::languages::python::{PythonImportFilter, PythonPackageLoader};
+use argflow::discovery::languages::php::{PhpImportFilter, PhpPackageLoader};
+use argflow::discovery::languages::python::{self, PythonImportFilter, PythonPackageLoader};
+use argflow::discovery::languages::ruby::{RubyImportFilter, RubyPackageLoader};
 use argflow::discovery::languages::rust::{RustImportFilter, RustPackageLoader};
+use argflow::discovery::languages::swift::{SwiftImportFilter, SwiftPackageLoader};
 use argflow::discovery::loader::PackageLoader;
+use argflow::discovery::manifest::declared_dependencies;
+use argflow::discovery::roots::{
+    compiled_roots, merge_root_reports, project_language, scan_roots, ScanRoot,
+};
+use argflow::discovery::tooling;
+use argflow::discovery::workspace::Workspace;
+use argflow::discovery::{FileMetadata, LanguageRegistry, SourceFile, SourceType};
+use argflow::engine::differential::{
+    constant_expressions, divergences, evaluate, ConstantExpression,
+};
+use argflow::engine::{
+    symbol_index, ExpressionCache, Language as EngineLanguage, ProjectIndex, ResolutionProfile,
+    Resolver, ResolverBuilder, ResolverConfig, SymbolIndex,
+};
+use argflow::grammars;
 use argflow::logging::{self, Verbosity};
-use argflow::output::OutputFormatter;
+use argflow::output::{
+    attest_report, cap_findings, compare_reports, digest_inputs, merge_report_files, millis,
+    post_notification, AgilityAnalyzer, AgilityReport, ArtifactReport, HotspotAnalyzer,
+    HotspotReport, JsonOutput, Notification, NotifyConfig, OutputFormatter, PhaseTimings,
+    ReportMetadata, ResultSpool, ScanPredicate, ScanTimings, SigningKey, SkipCategory, SkipLog,
+    ToolInfo, UnanalyzedArtifact, WarningCategory, WarningLog,
+};
+use argflow::policy::{
+    apply_waivers, check_gate, is_test_file, render_diff, risk_score, suggest_fixes, today,
+    FixPlan, PasswordPolicy, ReleaseManifest, Waiver,
+};
 use argflow::presets;
-use argflow::scanner::{ScanResult, Scanner};
+use argflow::scanner::templates::{self, TemplateKind};
+use argflow::scanner::units::{self, ScanUnit};
+use argflow::scanner::{binary, fallback, ScanResult, Scanner};
+use argflow::utils::Shard;
 use clap::Parser;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, trace, warn};
 
 struct ScanContext<'a> {
     scanner: &'a Scanner,
     classifier: &'a RulesClassifier,
+    preset_paths: &'a [PathBuf],
+    presets: &'a [String],
+    scan: ScanOptions<'a>,
+    report: ReportOptions<'a>,
+    /// Per-file warnings of the whole run, listed in each report's `errors`
+    warnings: &'a WarningLog,
+    /// Collects each path's report when several are scanned together,
+    /// instead of writing it.
+    reports: Option<&'a RefCell<Vec<JsonOutput>>>,
+}
+
+/// How the files of a path are found and scanned.
+#[derive(Clone, Copy)]
+struct ScanOptions<'a> {
+    max_memory: Option<usize>,
+    /// Workers scanning the project and its dependencies in parallel
+    jobs: usize,
+    shard: Option<Shard>,
+    scan_binaries: bool,
+    fallback_scan: bool,
+    /// List the files found but not scanned, with why
+    audit_coverage: bool,
+    deps_budget: DepsBudget,
+    record: Option<&'a PathBuf>,
+}
+
+impl Default for ScanOptions<'_> {
+    fn default() -> Self {
+        Self {
+            max_memory: None,
+            jobs: units::default_jobs(),
+            shard: None,
+            scan_binaries: false,
+            fallback_scan: false,
+            audit_coverage: false,
+            deps_budget: DepsBudget::default(),
+            record: None,
+        }
+    }
+}
+
+/// What is checked and added to a path's report, and where it goes.
+#[derive(Clone, Copy)]
+struct ReportOptions<'a> {
     output_format: OutputFormat,
     output_file: Option<&'a PathBuf>,
-    preset_paths: &'a [PathBuf],
+    /// Findings listed in the report, the rest only counted
+    max_findings: Option<usize>,
+    password_policy: Option<&'a PasswordPolicy>,
+    /// With `--suggest-fixes`, the file to write the diff to and the policy
+    /// whose minimum iteration counts are raised to.
+    suggest_fixes: Option<(&'a PathBuf, &'a PasswordPolicy)>,
+    /// Waivers of the config file, applied to the weaknesses found
+    waivers: &'a [Waiver],
+    /// Webhook of the config file posted the weaknesses worth a
+    /// notification, and the baseline report they are new against.
+    notify: Option<(&'a NotifyConfig, Option<&'a JsonOutput>)>,
+    /// Analyze whether crypto calls go through abstraction layers
+    agility: bool,
+    hotspots: bool,
+    /// Record phase timings in the report metadata
+    timings: bool,
+    sign: Option<&'a SigningKey>,
+    offline: bool,
+}
+
+impl Default for ReportOptions<'_> {
+    fn default() -> Self {
+        Self {
+            output_format: OutputFormat::Json,
+            output_file: None,
+            max_findings: None,
+            password_policy: None,
+            suggest_fixes: None,
+            waivers: &[],
+            notify: None,
+            agility: false,
+            hotspots: false,
+            timings: false,
+            sign: None,
+            offline: false,
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let args = cli::Args::parse();
 
     let verbosity = Verbosity::from_flags(args.verbose, args.quiet);
-    logging::init(verbosity);
+    logging::init(verbosity, args.log_filter.as_ref());
+    tooling::set_offline(args.offline);
 
-    info!(path = %args.path.display(), "starting argflow analysis");
+    if let Some(ref command) = args.command {
+        debug!(?command, "running subcommand");
+        return match command {
+            cli::Command::Merge(merge) => run_merge(merge),
+            cli::Command::Compare(compare) => run_compare(compare),
+            cli::Command::Rules(cli::RulesCommand::Test(test)) => run_rules_test(test),
+            cli::Command::Replay(replay) => run_replay(replay),
+            cli::Command::CheckConstants(check) => run_check_constants(check),
+            cli::Command::Discover(discover) => run_discover(discover),
+            cli::Command::Classify(classify) => run_classify(classify),
+            cli::Command::Coverage(coverage) => run_coverage(coverage),
+            cli::Command::Image(image) => run_image(image),
+            cli::Command::Version(version) => run_version(version),
+            cli::Command::Batch(batch) => run_batch(batch),
+            cli::Command::Fix(fix) => run_fix(fix),
+            cli::Command::Gate(gate) => run_gate(gate),
+        };
+    }
+
+    let paths = args.scan_paths();
+    info!(paths = paths.len(), "starting argflow analysis");
     debug!(?args, "parsed command line arguments");
 
     args.validate().context("Invalid arguments")?;
+    cancel::install_handlers();
+    tooling::set_timeout(Duration::from_secs(args.tool_timeout));
+    python::deps::set_python_env(args.python_env.clone());
+    let config = load_config(&args)?;
+    extensions::set_overrides(&config.extensions);
+    if !config.waivers.is_empty() && args.max_memory.is_some() {
+        anyhow::bail!("Waivers in the config file cannot be used with --max-memory");
+    }
+    if config.notify.is_some() {
+        if args.max_memory.is_some() {
+            anyhow::bail!("Notifications in the config file cannot be used with --max-memory");
+        }
+        if args.offline {
+            anyhow::bail!(
+                "Notifications in the config file need network access, which --offline forbids"
+            );
+        }
+    }
+    let baseline = match config.notify.as_ref().and_then(|n| n.baseline.as_ref()) {
+        Some(path) => Some(JsonOutput::load(path).context("Failed to load notification baseline")?),
+        None => None,
+    };
 
-    let language = args
-        .language
-        .or_else(|| {
-            if args.path.is_file() {
-                let detected = cli::detect_language(&args.path);
-                if let Some(lang) = detected {
-                    debug!(language = lang.as_str(), "auto-detected language");
-                }
-                detected
-            } else {
-                None
-            }
-        })
-        .context("Could not detect language. Please specify --language")?;
-
-    info!(language = language.as_str(), "using language");
+    // A spooled or signed report covers one path as a whole.
+    let split_components = args.max_memory.is_none() && args.sign.is_none();
+    let registry = LanguageRegistry::new();
+    // Archives are scanned unpacked, their files reported inside them.
+    let mut archives = Vec::new();
+    for path in paths {
+        if let Some(kind) = ArchiveKind::for_path(path).filter(|_| path.is_file()) {
+            info!(path = %path.display(), "unpacking archive");
+            archives.push((path, UnpackedArchive::unpack(path, kind)?));
+        }
+    }
+    let mut roots = Vec::new();
+    for path in paths {
+        let (path, language) = match archives.iter().find(|(archive, _)| *archive == path) {
+            Some((_, unpacked)) => (
+                unpacked.root(),
+                unpacked.scan_language(&registry, args.language),
+            ),
+            None => (path.as_path(), args.language),
+        };
+        roots.extend(scan_roots(&registry, path, language, split_components)?);
+    }
+    // A remote repository is scanned checked out, its files reported in it.
+    let cloned = match &args.git_url {
+        Some(url) => {
+            info!(url = %git::redact(url), "fetching repository");
+            let cloned = ClonedRepository::fetch(url, args.git_ref.as_deref())?;
+            info!(
+                url = cloned.url(),
+                commit = cloned.commit(),
+                "fetched repository"
+            );
+            roots.extend(scan_roots(
+                &registry,
+                cloned.dir(),
+                args.language,
+                split_components,
+            )?);
+            Some(cloned)
+        }
+        None => None,
+    };
+    let roots = compiled_roots(roots, args.language)?;
+    if roots.is_empty() {
+        anyhow::bail!("No project of a supported language found. Please specify --language");
+    }
+    for root in &roots {
+        info!(path = %root.path.display(), language = root.language.as_str(), "using language");
+    }
 
     // Load preset paths for both classifier and filters
     let preset_paths = get_preset_paths(&args)?;
@@ -64,33 +275,877 @@ fn main() -> Result<()> {
         "classifier loaded"
     );
 
+    let resolver_config = resolver_config(&args, &config);
+    let scanner = mapped_scanner(
+        &classifier,
+        &resolver_config,
+        args.resolver.profile_resolution,
+    )?;
+
+    if args.explain {
+        explain_resolver(&scanner, &resolver_config);
+    }
+
+    let warnings = WarningLog::new(args.max_warnings);
+    let ctx = ScanContext {
+        scanner: &scanner,
+        classifier: &classifier,
+        preset_paths: &preset_paths,
+        presets: &args.preset,
+        scan: ScanOptions {
+            max_memory: args.max_memory,
+            jobs: args
+                .jobs
+                .map_or_else(units::default_jobs, NonZeroUsize::get),
+            shard: args.shard,
+            scan_binaries: args.scan_binaries,
+            fallback_scan: args.fallback_scan,
+            audit_coverage: args.audit_coverage,
+            deps_budget: args.deps_budget,
+            record: args.record.as_ref(),
+        },
+        report: ReportOptions {
+            output_format: args.format,
+            output_file: args.output_file.as_ref(),
+            max_findings: args.max_findings,
+            password_policy: args.password_policy.then_some(&config.password_policy),
+            suggest_fixes: args
+                .suggest_fixes
+                .as_ref()
+                .map(|file| (file, &config.password_policy)),
+            waivers: &config.waivers,
+            notify: config
+                .notify
+                .as_ref()
+                .map(|notify| (notify, baseline.as_ref())),
+            agility: args.agility,
+            hotspots: args.hotspots,
+            timings: args.timings,
+            sign: args.sign.as_ref(),
+            offline: args.offline,
+        },
+        warnings: &warnings,
+        reports: None,
+    };
+
+    if let ([root], [], None) = (roots.as_slice(), archives.as_slice(), &cloned) {
+        scan_root(root, &ctx, args.include_deps)?;
+    } else {
+        let reports = RefCell::new(Vec::new());
+        let ctx = ScanContext {
+            reports: Some(&reports),
+            ..ctx
+        };
+        for root in &roots {
+            if cancel::is_cancelled() {
+                break;
+            }
+            info!(path = %root.path.display(), "scanning path");
+            scan_root(root, &ctx, args.include_deps)?;
+        }
+        let mut combined = merge_root_reports(&roots, reports.take());
+        combined.partial |= cancel::is_cancelled();
+        if let Some((file, _)) = ctx.report.suggest_fixes {
+            write_fix_diff(&combined, file)?;
+        }
+        for (_, unpacked) in &archives {
+            unpacked.rebase(&mut combined);
+        }
+        if let Some(cloned) = &cloned {
+            cloned.rebase(&mut combined);
+        }
+        if let Some(max_findings) = ctx.report.max_findings {
+            cap_findings(&mut combined, max_findings);
+        }
+        write_output(
+            &render_timed(&mut combined, ctx.report.output_format)?,
+            ctx.report.output_file,
+        )?;
+        send_notification(&combined, &ctx)?;
+    }
+    warnings.log_summary();
+
+    if let Some(profile) = scanner.resolver().profile() {
+        eprintln!("resolution profile:");
+        eprint!("{}", profile.render());
+    }
+
+    if cancel::is_cancelled() {
+        std::process::exit(cancel::EXIT_STATUS);
+    }
+    Ok(())
+}
+
+/// A scanner for the calls `classifier` maps, resolving their arguments as
+/// `resolver_config` says.
+fn mapped_scanner(
+    classifier: &RulesClassifier,
+    resolver_config: &ResolverConfig,
+    profile: bool,
+) -> Result<Scanner> {
+    let mut resolver_builder =
+        ResolverBuilder::from_config(resolver_config).context("Invalid resolver configuration")?;
+    if profile {
+        resolver_builder = resolver_builder.with_profile(ResolutionProfile::shared());
+    }
+    let resolver = resolver_builder.build();
+
     // Create scanner with classifier mappings and struct field detection
     // Only calls with explicit API mappings will be detected (high precision)
-    let scanner = Scanner::with_mappings_and_struct_fields(
+    let mut scanner = Scanner::with_mappings_and_struct_fields(
         classifier.get_mappings().clone(),
         classifier.get_struct_fields().clone(),
-    );
+    )
+    .with_clients(classifier.get_clients().to_vec())
+    .with_expression_cache(ExpressionCache::shared());
+    scanner.set_resolver(resolver);
+    if resolver_config.cross_file {
+        scanner = scanner
+            .with_symbol_index(SymbolIndex::shared())
+            .with_project_index(ProjectIndex::shared());
+    }
     trace!("scanner initialized with classifier mappings and struct fields");
+    Ok(scanner)
+}
+
+fn scan_root(root: &ScanRoot, ctx: &ScanContext, include_deps: bool) -> Result<()> {
+    if root.path.is_dir() {
+        scan_directory(
+            &root.path,
+            root.language,
+            &root.nested,
+            &root.workspace,
+            ctx,
+            include_deps,
+        )
+    } else {
+        if let Some(shard) = ctx.scan.shard {
+            warn!(%shard, "--shard only applies to directory scans; scanning the file");
+        }
+        scan_file(&root.path, root.language, ctx)
+    }
+}
+
+fn run_merge(args: &cli::MergeArgs) -> Result<()> {
+    info!(reports = args.reports.len(), "merging reports");
+    let merged = merge_report_files(&args.reports)?;
+    info!(
+        files = merged.files_scanned,
+        findings = merged.total_findings,
+        risk_score = merged.risk_score,
+        "merged reports"
+    );
+
+    let output = serde_json::to_string_pretty(&merged)?;
+    write_output(&output, args.output_file.as_ref())
+}
+
+fn run_compare(args: &cli::CompareArgs) -> Result<()> {
+    let diff = compare_reports(JsonOutput::load(&args.old)?, JsonOutput::load(&args.new)?);
+    info!(
+        added = diff.added.len() + diff.added_configs.len(),
+        removed = diff.removed.len() + diff.removed_configs.len(),
+        changed = diff.changed.len() + diff.changed_configs.len(),
+        "compared reports"
+    );
+
+    let output = diff.render(args.format)?;
+    write_output(output.trim_end(), args.output_file.as_ref())
+}
+
+fn run_gate(args: &cli::GateArgs) -> Result<()> {
+    let report = JsonOutput::load(&args.report)?;
+    let manifest = ReleaseManifest::load(&args.against)
+        .with_context(|| format!("Failed to load manifest {}", args.against.display()))?;
+
+    let gate = check_gate(&report.findings, &manifest);
+    info!(
+        entries = gate.entries.len(),
+        violations = gate.violations,
+        "checked report against release manifest"
+    );
+    let output = serde_json::to_string_pretty(&gate)?;
+    write_output(&output, args.output_file.as_ref())?;
+
+    if !gate.passed {
+        anyhow::bail!(
+            "{} algorithm(s) or key size(s) not in {}",
+            gate.violations,
+            args.against.display()
+        );
+    }
+    Ok(())
+}
+
+fn run_fix(args: &cli::FixArgs) -> Result<()> {
+    let report = JsonOutput::load(&args.report)?;
+    let plan = FixPlan::new(&report.fixes, &args.rules, read_source);
+    for (file, fixes) in &plan.unreadable {
+        warn!(file, fixes, "cannot read file, skipping its fixes");
+    }
+    for fix in &plan.stale {
+        warn!(
+            file = fix.file,
+            line = fix.line,
+            rule = %fix.rule,
+            "source changed since the scan, skipping fix"
+        );
+    }
+
+    if args.dry_run {
+        print!("{}", plan.diff());
+    } else {
+        plan.write(!args.no_backup)
+            .context("Failed to apply fixes")?;
+    }
+    info!(
+        fixes = plan.fixes().len(),
+        files = plan.files(),
+        skipped = plan.skipped(),
+        written = args.write,
+        "applied fixes"
+    );
+    Ok(())
+}
+
+fn run_rules_test(args: &cli::RulesTestArgs) -> Result<()> {
+    let preset_paths = if args.preset.is_empty() {
+        vec![]
+    } else {
+        presets::load_presets(&args.preset)?
+    };
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+
+    let (mut passed, mut failed) = (0, 0);
+    for rules_path in &args.rules {
+        let results = run_rule_tests(rules_path, &classifier)
+            .map_err(|e| anyhow::anyhow!("Failed to load rule tests: {e}"))?;
+        for result in results {
+            if result.passed() {
+                passed += 1;
+                println!("PASS {}: {}", rules_path.display(), result.name);
+            } else {
+                failed += 1;
+                println!("FAIL {}: {}", rules_path.display(), result.name);
+                for failure in &result.failures {
+                    println!("    {failure}");
+                }
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed");
+
+    if failed > 0 {
+        anyhow::bail!("{failed} rule test(s) failed");
+    }
+    Ok(())
+}
+
+fn run_replay(args: &cli::ReplayArgs) -> Result<()> {
+    let preset_paths = if args.preset.is_empty() {
+        vec![]
+    } else {
+        presets::load_presets(&args.preset)?
+    };
+    let classifier = if preset_paths.is_empty() && args.rules.is_empty() {
+        RulesClassifier::from_bundled()
+            .map_err(|e| anyhow::anyhow!("Failed to load classifier rules: {e}"))?
+    } else {
+        layered_classifier(&preset_paths, &args.rules)?
+    };
+
+    let results = replay_corpus(&args.corpus, &classifier)
+        .with_context(|| format!("Failed to replay corpus: {}", args.corpus.display()))?;
+    let failed = results.iter().filter(|result| !result.passed()).count();
+    for result in results {
+        let case = result.case.display();
+        match result.outcome {
+            ReplayOutcome::Diff(diff) if diff.is_empty() => println!("PASS {case}"),
+            ReplayOutcome::Diff(diff) => {
+                println!("FAIL {case}");
+                for line in diff.render(cli::CompareFormat::Text)?.lines() {
+                    println!("    {line}");
+                }
+            }
+            ReplayOutcome::Error(message) => println!("ERROR {case}: {message}"),
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} corpus case(s) failed");
+    }
+    Ok(())
+}
+
+fn run_check_constants(args: &cli::CheckConstantsArgs) -> Result<()> {
+    cli::validate_path(&args.path)?;
+    let files: Vec<(PathBuf, cli::Language)> = walkdir::WalkDir::new(&args.path)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_name().to_str().is_some_and(|name| {
+                    name.starts_with('.') || name == "node_modules" || name == "venv"
+                })
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let language = cli::detect_language(entry.path())?;
+            matches!(
+                language,
+                cli::Language::Python | cli::Language::Javascript | cli::Language::Typescript
+            )
+            .then(|| (entry.into_path(), language))
+        })
+        .collect();
+
+    // One batch per runtime.
+    let resolver = Resolver::new();
+    let mut batches: [(&str, Vec<ConstantExpression>); 2] =
+        [(&args.python, Vec::new()), (&args.node, Vec::new())];
+    for (path, language) in files {
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let tree = parse_source(&source, &path, language)?;
+        let engine_language =
+            EngineLanguage::parse(language.as_str()).context("Unsupported language")?;
+        let batch = match language {
+            cli::Language::Python => &mut batches[0].1,
+            _ => &mut batches[1].1,
+        };
+        batch.extend(constant_expressions(
+            &tree,
+            source.as_bytes(),
+            &path.to_string_lossy(),
+            engine_language,
+            &resolver,
+        ));
+    }
+
+    let (mut checked, mut diverged) = (0, 0);
+    for (runtime, batch) in batches {
+        if batch.is_empty() {
+            continue;
+        }
+        let texts: Vec<String> = batch.iter().map(|e| e.expression.clone()).collect();
+        let results = match evaluate(runtime, &texts) {
+            Ok(results) => results,
+            Err(error) => {
+                warn!(runtime, expressions = batch.len(), %error, "skipping expressions");
+                continue;
+            }
+        };
+        checked += batch.len();
+        for divergence in divergences(&batch, &results) {
+            diverged += 1;
+            println!(
+                "{}:{}:{}: {}: engine {}, {runtime} {}",
+                divergence.file,
+                divergence.line,
+                divergence.column,
+                divergence.expression,
+                divergence.engine,
+                divergence.runtime
+            );
+        }
+    }
+    println!("{checked} constant expressions checked, {diverged} diverged");
+
+    if diverged > 0 {
+        anyhow::bail!("{diverged} constant expression(s) diverged from the runtime");
+    }
+    Ok(())
+}
+
+fn run_discover(args: &cli::DiscoverArgs) -> Result<()> {
+    cli::validate_path(&args.path)?;
+    if !args.path.is_dir() {
+        anyhow::bail!("discover needs a directory: {}", args.path.display());
+    }
+    if args.preset.is_empty() {
+        anyhow::bail!("Discovery selects files by preset imports. Use --preset <name> (e.g., --preset crypto)");
+    }
+    let registry = LanguageRegistry::new();
+    let language = project_language(&registry, &args.path, args.language)?;
 
+    let preset_paths = presets::load_presets(&args.preset)?;
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+    let loader = registry
+        .get_module(language)
+        .context("Unsupported language")?
+        .create_loader();
+    let filter = import_filter(language, &preset_paths, &classifier)?;
+
+    let files = discover(
+        &args.path,
+        loader.as_ref(),
+        filter.as_ref(),
+        args.include_deps,
+        args.deps_budget,
+        &classifier,
+        args.shard,
+    )
+    .context("Failed to discover files")?;
+    let mut scanned = 0;
+    for file in &files {
+        let path = file.path.strip_prefix(&args.path).unwrap_or(&file.path);
+        let source_type = file
+            .source_type
+            .as_ref()
+            .map_or("-".to_string(), |t| t.to_string());
+        match &file.skipped {
+            None => {
+                scanned += 1;
+                println!(
+                    "scan\t{}\t{source_type}\t{}",
+                    file.language.as_str(),
+                    path.display()
+                );
+            }
+            Some(reason) => println!(
+                "skip\t{}\t{source_type}\t{}\t{reason}",
+                file.language.as_str(),
+                path.display()
+            ),
+        }
+    }
+    println!("{scanned} to scan, {} skipped", files.len() - scanned);
+    Ok(())
+}
+
+fn run_coverage(args: &cli::CoverageArgs) -> Result<()> {
+    cli::validate_path(&args.path)?;
+    if !args.path.is_dir() {
+        anyhow::bail!(
+            "coverage needs a project directory: {}",
+            args.path.display()
+        );
+    }
+    if args.preset.is_empty() && args.rules.is_empty() {
+        anyhow::bail!("No rules to measure. Use --preset <name> or --rules <file>");
+    }
+    let registry = LanguageRegistry::new();
+    let language = project_language(&registry, &args.path, args.language)?;
+    let preset_paths = presets::load_presets(&args.preset)?;
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+
+    let files = registry
+        .get_module(language)
+        .context("Unsupported language")?
+        .create_loader()
+        .load_user_code(&args.path)
+        .context("Failed to discover files")?;
+    // Every call, so the ones no mapping matches are counted too.
+    let scanner = Scanner::new().with_patterns(vec![String::new()]);
+    let warnings = WarningLog::default();
+    let mut calls = Vec::new();
+    for file in files {
+        let source = match std::fs::read_to_string(&file.path) {
+            Ok(source) => source,
+            Err(e) => {
+                warnings.record(WarningCategory::ReadFailed, &file.path, e);
+                continue;
+            }
+        };
+        let tree = parse_source(&source, &file.path, file.language)?;
+        let result = scanner.scan_tree(
+            &tree,
+            source.as_bytes(),
+            &file.path.to_string_lossy(),
+            scan_language(&file.path, file.language),
+        );
+        calls.extend(result.calls);
+    }
+    warnings.log_summary();
+
+    let declared = declared_dependencies(&args.path, language);
+    let coverage = mapping_coverage(&calls, &declared, language.as_str(), &classifier);
+    info!(
+        calls = calls.len(),
+        declared = declared.len(),
+        dependencies = coverage.len(),
+        "measured mapping coverage"
+    );
+    let output = match args.format {
+        cli::CoverageFormat::Json => serde_json::to_string_pretty(&coverage)?,
+        cli::CoverageFormat::Text => render_coverage(&coverage),
+    };
+    write_output(output.trim_end(), args.output_file.as_ref())
+}
+
+fn run_version(args: &cli::VersionArgs) -> Result<()> {
+    let classifier = if args.preset.is_empty() && args.rules.is_empty() {
+        RulesClassifier::from_bundled()
+            .map_err(|e| anyhow::anyhow!("Failed to load classifier rules: {e}"))?
+    } else {
+        layered_classifier(&presets::load_presets(&args.preset)?, &args.rules)?
+    };
+    let metadata = ReportMetadata::new(&classifier);
+    if args.json {
+        return write_output(&serde_json::to_string_pretty(&metadata)?, None);
+    }
+
+    let mut text = format!("{} {}\n", metadata.tool.name, metadata.tool.version);
+    match &metadata.rules {
+        Some(rules) => {
+            text.push_str(&format!("rules: {} ({} files", rules.sha256, rules.files));
+            if !rules.versions.is_empty() {
+                text.push_str(&format!(", version {}", rules.versions.join(", ")));
+            }
+            text.push_str(")\n");
+        }
+        None => text.push_str("rules: none\n"),
+    }
+    for (name, version) in &metadata.grammars {
+        text.push_str(&format!("{name} {version}\n"));
+    }
+    write_output(text.trim_end(), None)
+}
+
+fn run_image(args: &cli::ImageArgs) -> Result<()> {
+    for rules_path in &args.rules {
+        cli::validate_path(rules_path)?;
+    }
+    let image = ContainerImage::unpack(&args.image)
+        .with_context(|| format!("Failed to unpack image {}", args.image.display()))?;
+    info!(layers = image.layers().len(), "unpacked image");
+
+    let roots = image.scan_roots(&LanguageRegistry::new())?;
+
+    let preset_paths = presets::load_presets(&args.preset)?;
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+    let scanner = mapped_scanner(&classifier, &ResolverConfig::default(), false)?;
+    let reports = RefCell::new(Vec::new());
+    let warnings = WarningLog::default();
     let ctx = ScanContext {
         scanner: &scanner,
         classifier: &classifier,
-        output_format: args.format,
-        output_file: args.output_file.as_ref(),
         preset_paths: &preset_paths,
+        presets: &args.preset,
+        scan: ScanOptions {
+            scan_binaries: args.scan_binaries,
+            ..ScanOptions::default()
+        },
+        report: ReportOptions {
+            output_format: args.format,
+            ..ReportOptions::default()
+        },
+        warnings: &warnings,
+        reports: Some(&reports),
     };
+    for root in &roots {
+        info!(
+            path = %root.path.display(),
+            language = root.language.as_str(),
+            "scanning image component"
+        );
+        scan_root(root, &ctx, false)?;
+    }
+
+    warnings.log_summary();
+
+    let mut combined = merge_root_reports(&roots, reports.take());
+    image.attribute(&args.image.to_string_lossy(), &mut combined);
+    write_output(
+        &OutputFormatter::render(&combined, args.format)?,
+        args.output_file.as_ref(),
+    )
+}
 
-    if args.path.is_dir() {
-        scan_directory(&args.path, language, &ctx, args.include_deps)?;
+fn run_batch(args: &cli::BatchArgs) -> Result<()> {
+    let manifest = BatchManifest::load(&args.manifest)
+        .with_context(|| format!("Failed to load manifest {}", args.manifest.display()))?;
+    if manifest.repos.is_empty() {
+        anyhow::bail!("No repositories listed in {}", args.manifest.display());
+    }
+    std::fs::create_dir_all(&args.output_dir).with_context(|| {
+        format!(
+            "Failed to create output directory: {}",
+            args.output_dir.display()
+        )
+    })?;
+
+    // One classifier and scanner, and so one expression cache and symbol
+    // index, for every repository.
+    let preset_paths = presets::load_presets(&args.preset)?;
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+    let scanner = mapped_scanner(&classifier, &ResolverConfig::default(), false)?;
+
+    let concurrency = args
+        .concurrency
+        .map_or_else(units::default_jobs, NonZeroUsize::get)
+        .min(manifest.repos.len());
+    let jobs = (units::default_jobs() / concurrency).max(1);
+    info!(
+        repos = manifest.repos.len(),
+        concurrency, jobs, "starting batch scan"
+    );
+
+    let next = AtomicUsize::new(0);
+    let summaries: Vec<Mutex<Option<RepoSummary>>> =
+        manifest.repos.iter().map(|_| Mutex::default()).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(repo) = manifest.repos.get(index) else {
+                    break;
+                };
+                info!(repo = %repo.name(), path = %repo.path.display(), "scanning repository");
+                let ctx = ScanContext {
+                    scanner: &scanner,
+                    classifier: &classifier,
+                    preset_paths: &preset_paths,
+                    presets: &args.preset,
+                    scan: ScanOptions {
+                        jobs,
+                        ..ScanOptions::default()
+                    },
+                    report: ReportOptions {
+                        output_format: args.format,
+                        ..ReportOptions::default()
+                    },
+                    warnings: &WarningLog::default(),
+                    reports: None,
+                };
+                let summary = match scan_batch_repo(repo, &ctx, args) {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        warn!(repo = %repo.name(), error = %format!("{e:#}"), "repository scan failed");
+                        RepoSummary::failed(repo, &e)
+                    }
+                };
+                *summaries[index].lock().unwrap() = Some(summary);
+            });
+        }
+    });
+
+    let summary = BatchSummary::new(
+        summaries
+            .into_iter()
+            .filter_map(|summary| summary.into_inner().unwrap())
+            .collect(),
+    );
+    let path = args.output_dir.join("summary.json");
+    write_output(&serde_json::to_string_pretty(&summary)?, Some(&path))?;
+    info!(
+        repos = summary.total_repos,
+        findings = summary.total_findings,
+        "batch scan complete"
+    );
+    if summary.failed_repos > 0 {
+        anyhow::bail!(
+            "{} of {} repositories failed to scan; see {}",
+            summary.failed_repos,
+            summary.total_repos,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Scans one repository of a batch, each of its nested projects as a
+/// component, and writes its report to the output directory.
+fn scan_batch_repo(
+    repo: &BatchRepo,
+    ctx: &ScanContext,
+    args: &cli::BatchArgs,
+) -> Result<RepoSummary> {
+    cli::validate_path(&repo.path)?;
+    let roots = compiled_roots(
+        scan_roots(&LanguageRegistry::new(), &repo.path, repo.language, true)?,
+        repo.language,
+    )?;
+    if roots.is_empty() {
+        anyhow::bail!("No project of a supported language found");
+    }
+
+    let reports = RefCell::new(Vec::new());
+    let ctx = ScanContext {
+        reports: Some(&reports),
+        ..*ctx
+    };
+    for root in &roots {
+        scan_root(root, &ctx, args.include_deps)?;
+    }
+    ctx.warnings.log_summary();
+
+    let mut reports = reports.take();
+    let report = if let [_] = reports.as_slice() {
+        reports.remove(0)
     } else {
-        scan_file(&args.path, language, &ctx)?;
+        merge_root_reports(&roots, reports)
+    };
+
+    let extension = match args.format {
+        OutputFormat::Json => "json",
+        OutputFormat::Cbom => "cdx.json",
+        OutputFormat::Compact => "txt",
+    };
+    let output = args.output_dir.join(format!("{}.{extension}", repo.name()));
+    write_output(
+        &OutputFormatter::render(&report, args.format)?,
+        Some(&output),
+    )?;
+    Ok(RepoSummary::new(repo, &output, &report))
+}
+
+fn render_coverage(coverage: &[DependencyCoverage]) -> String {
+    let width = coverage
+        .iter()
+        .map(|c| c.dependency.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for dependency in coverage {
+        let declared = if dependency.declared {
+            ""
+        } else {
+            " (not declared)"
+        };
+        let line = match dependency.percent() {
+            Some(percent) => format!(
+                "{:width$}  {percent:>3}%  {}/{} called functions mapped{declared}",
+                dependency.dependency, dependency.mapped, dependency.called
+            ),
+            None => format!(
+                "{:width$}     -  not called, {} rule mappings{declared}",
+                dependency.dependency, dependency.rule_mappings
+            ),
+        };
+        out.push_str(&line);
+        out.push('\n');
+        if !dependency.unmapped.is_empty() {
+            out.push_str(&format!(
+                "{:width$}        unmapped: {}\n",
+                "",
+                dependency.unmapped.join(", ")
+            ));
+        }
     }
+    let blind = coverage.iter().filter(|c| c.mapped < c.called).count();
+    out.push_str(&format!(
+        "{} crypto-capable dependencies, {blind} with unmapped calls\n",
+        coverage.len()
+    ));
+    out
+}
 
+/// The import filter a directory scan of `language` uses.
+fn import_filter(
+    language: cli::Language,
+    preset_paths: &[PathBuf],
+    classifier: &RulesClassifier,
+) -> Result<Box<dyn ImportFileFilter>> {
+    let tokens = FilterTokens::from_classifier(classifier);
+    let filter: Box<dyn ImportFileFilter> = match language {
+        cli::Language::Go => Box::new(GoImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::Python => {
+            Box::new(PythonImportFilter::new(preset_paths)?.with_tokens(tokens))
+        }
+        cli::Language::Javascript | cli::Language::Typescript => {
+            Box::new(JavaScriptImportFilter::new(preset_paths)?.with_tokens(tokens))
+        }
+        cli::Language::Rust => Box::new(RustImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::Java => Box::new(JavaImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::C => Box::new(CImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::Cpp => Box::new(CppImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::CSharp => {
+            Box::new(CSharpImportFilter::new(preset_paths)?.with_tokens(tokens))
+        }
+        cli::Language::Ruby => Box::new(RubyImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::Php => Box::new(PhpImportFilter::new(preset_paths)?.with_tokens(tokens)),
+        cli::Language::Swift => Box::new(SwiftImportFilter::new(preset_paths)?.with_tokens(tokens)),
+    };
+    Ok(filter)
+}
+
+fn run_classify(args: &cli::ClassifyArgs) -> Result<()> {
+    if args.preset.is_empty() && args.rules.is_empty() {
+        anyhow::bail!("No rules to classify with. Use --preset <name> or --rules <file>");
+    }
+    let preset_paths = presets::load_presets(&args.preset)?;
+    let classifier = layered_classifier(&preset_paths, &args.rules)?;
+
+    let Some(at) = &args.at else {
+        let function = args.function.as_deref().unwrap_or_default();
+        let import_path = args.import_path.as_deref();
+        let package = match (&args.package, import_path) {
+            (Some(package), _) => package.as_str(),
+            (None, Some(path)) => path.rsplit('/').next().unwrap_or(path),
+            (None, None) => "",
+        };
+        let name = match import_path.or(Some(package).filter(|p| !p.is_empty())) {
+            Some(path) => format!("{path} {function}"),
+            None => function.to_string(),
+        };
+        print_explanation(&name, &classifier.explain(import_path, package, function))?;
+        return Ok(());
+    };
+
+    let language = cli::detect_language(&at.file)
+        .with_context(|| format!("Unsupported file type: {}", at.file.display()))?;
+    let source = std::fs::read_to_string(&at.file)
+        .with_context(|| format!("Failed to read {}", at.file.display()))?;
+    let tree = parse_source(&source, &at.file, language)?;
+    // Every call, so the ones no mapping matches can be explained too.
+    let result = Scanner::new().with_patterns(vec![String::new()]).scan_tree(
+        &tree,
+        source.as_bytes(),
+        &at.file.to_string_lossy(),
+        language.as_str(),
+    );
+    let calls: Vec<_> = result.calls.iter().filter(|c| c.line == at.line).collect();
+    if calls.is_empty() {
+        anyhow::bail!("No calls found at {}:{}", at.file.display(), at.line);
+    }
+    for (i, call) in calls.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let explanation = classifier.explain(
+            call.import_path.as_deref(),
+            call.package.as_deref().unwrap_or(""),
+            &call.function_name,
+        );
+        print_explanation(
+            &format!("{}:{}: {}", call.line, call.column, call.full_name()),
+            &explanation,
+        )?;
+    }
+    Ok(())
+}
+
+fn print_explanation(name: &str, explanation: &Explanation) -> Result<()> {
+    println!("{name}");
+    if explanation.steps.is_empty() {
+        println!("  no import path or package to look up");
+    }
+    for (i, step) in explanation.steps.iter().enumerate() {
+        println!("  {}. {}: {}", i + 1, step.path, step.outcome);
+    }
+    if !explanation.is_classified() {
+        println!("  unclassified");
+        return Ok(());
+    }
+    if let Some(operation) = explanation.refined_operation {
+        println!(
+            "  operation {} narrowed to {operation} by the function name",
+            explanation.classification.operation
+        );
+    }
+    let mut classification = explanation.classification.clone();
+    if let Some(operation) = explanation.refined_operation {
+        classification.operation = operation.to_string();
+    }
+    for line in serde_json::to_string_pretty(&classification)?.lines() {
+        println!("  {line}");
+    }
     Ok(())
 }
 
 fn get_preset_paths(args: &cli::Args) -> Result<Vec<PathBuf>> {
-    if args.preset.is_empty() && args.rules.is_none() {
+    if args.preset.is_empty() && args.rules.is_empty() {
         anyhow::bail!(
             "No preset or rules specified. Use --preset <name> (e.g., --preset crypto) or --rules <path>"
         );
@@ -105,20 +1160,64 @@ fn get_preset_paths(args: &cli::Args) -> Result<Vec<PathBuf>> {
     Ok(vec![])
 }
 
-fn load_classifier(args: &cli::Args, preset_paths: &[PathBuf]) -> Result<RulesClassifier> {
-    if let Some(ref rules_path) = args.rules {
-        info!(rules = %rules_path.display(), "loading custom rules");
-        return RulesClassifier::from_file(rules_path)
-            .map_err(|e| anyhow::anyhow!("Failed to load custom rules: {e}"));
+fn load_config(args: &cli::Args) -> Result<Config> {
+    match args.config {
+        Some(ref path) => Config::load(path).context("Failed to load config file"),
+        None => Ok(Config::default()),
     }
+}
+
+fn resolver_config(args: &cli::Args, config: &Config) -> ResolverConfig {
+    let mut resolver = config.resolver.clone();
+    args.resolver.apply_to(&mut resolver);
+    debug!(config = ?resolver, "effective resolver configuration");
+    resolver
+}
+
+fn explain_resolver(scanner: &Scanner, config: &ResolverConfig) {
+    let resolver = scanner.resolver();
+    let call_depth = resolver
+        .max_call_depth()
+        .map(|d| d.to_string())
+        .unwrap_or_else(|| "unlimited".to_string());
 
-    if let Some(preset_path) = preset_paths.first() {
-        return RulesClassifier::from_preset_path(preset_path)
-            .map_err(|e| anyhow::anyhow!("Failed to load preset: {e}"));
+    eprintln!("resolver:");
+    eprintln!("  chain: {}", resolver.strategy_names().join(" -> "));
+    eprintln!("  max depth: {}", resolver.max_depth());
+    eprintln!("  call depth: {call_depth}");
+    eprintln!(
+        "  cross-file: {}",
+        if config.cross_file {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+}
+
+fn load_classifier(args: &cli::Args, preset_paths: &[PathBuf]) -> Result<RulesClassifier> {
+    if preset_paths.is_empty() && args.rules.is_empty() {
+        return RulesClassifier::from_bundled()
+            .map_err(|e| anyhow::anyhow!("Failed to load classifier rules: {e}"));
     }
+    layered_classifier(preset_paths, &args.rules)
+}
 
-    RulesClassifier::from_bundled()
-        .map_err(|e| anyhow::anyhow!("Failed to load classifier rules: {e}"))
+/// Presets first, then each rules file layered over them.
+fn layered_classifier(preset_paths: &[PathBuf], rules: &[PathBuf]) -> Result<RulesClassifier> {
+    let mut classifier = RulesClassifier::new();
+    for preset_path in preset_paths {
+        classifier
+            .load_preset(preset_path)
+            .map_err(|e| anyhow::anyhow!("Failed to load preset: {e}"))?;
+    }
+    for rules_path in rules {
+        info!(rules = %rules_path.display(), "loading custom rules");
+        classifier
+            .load_user_rules(rules_path)
+            .map_err(|e| anyhow::anyhow!("Failed to load custom rules: {e}"))?;
+    }
+    Ok(classifier)
 }
 
 fn scan_file(path: &Path, language: cli::Language, ctx: &ScanContext) -> Result<()> {
@@ -127,30 +1226,57 @@ fn scan_file(path: &Path, language: cli::Language, ctx: &ScanContext) -> Result<
     let source = std::fs::read_to_string(path).context("Failed to read file")?;
     trace!(bytes = source.len(), "read source file");
 
-    let tree = parse_source(&source, language)?;
+    let parsing = Instant::now();
+    let tree = parse_source(&source, path, language)?;
     trace!("parsed source into AST");
 
-    let result = ctx.scanner.scan_tree(
+    let resolving = Instant::now();
+    let mut result = ctx.scanner.scan_tree(
         &tree,
         source.as_bytes(),
         &path.to_string_lossy(),
-        language.as_str(),
+        scan_language(path, language),
     );
 
+    result.test_code = is_test_below(path.parent().unwrap_or(path), path);
     info!(calls = result.call_count(), "scan complete");
 
-    output_results(
-        &[result],
-        ctx.classifier,
-        ctx.output_format,
-        ctx.output_file,
-    )?;
+    let mut artifacts = ArtifactReport::default();
+    if ctx.report.timings {
+        let mut timings = ScanTimings::default();
+        timings.add(
+            language.as_str(),
+            PhaseTimings {
+                parse_ms: millis(resolving - parsing),
+                resolve_ms: millis(resolving.elapsed()),
+                ..PhaseTimings::default()
+            },
+        );
+        artifacts.timings = Some(timings);
+    }
+    output_results(&[result], &artifacts, ctx)?;
+    sign_report(ctx, path.parent().unwrap_or(path), &[path.to_path_buf()])?;
+
+    if let Some(dir) = ctx.scan.record {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let case = record_case(dir, &source, language.as_str(), extension, ctx.classifier)
+            .context("Failed to record corpus case")?;
+        info!(case = %case.source.display(), "recorded corpus case");
+        if !case.faithful {
+            warn!(
+                case = %case.source.display(),
+                "the anonymized case does not reproduce the original findings; check it by hand"
+            );
+        }
+    }
     Ok(())
 }
 
 fn scan_directory(
     path: &Path,
     language: cli::Language,
+    nested: &[PathBuf],
+    workspace: &Workspace,
     ctx: &ScanContext,
     include_deps: bool,
 ) -> Result<()> {
@@ -164,60 +1290,313 @@ fn scan_directory(
         );
     }
 
+    let mut artifacts = ArtifactReport::default();
+    if ctx.scan.scan_binaries {
+        info!("scanning compiled artifacts");
+        artifacts.binary_findings = binary::scan_directory(path);
+        if let Some(shard) = ctx.scan.shard {
+            artifacts
+                .binary_findings
+                .retain(|f| shard.contains(Path::new(&f.file), path));
+        }
+        info!(
+            count = artifacts.binary_findings.len(),
+            "found crypto indicators in compiled artifacts"
+        );
+    }
+    if ctx.scan.fallback_scan {
+        info!("matching fallback patterns against unparsed files");
+        if ctx.classifier.fallback_rules().is_empty() {
+            warn!("--fallback-scan given but no rules file has fallback_patterns");
+        }
+        artifacts.fallback_findings =
+            fallback::scan_directory(path, ctx.classifier.fallback_rules());
+        if let Some(shard) = ctx.scan.shard {
+            artifacts
+                .fallback_findings
+                .retain(|f| shard.contains(Path::new(&f.file), path));
+        }
+        info!(
+            count = artifacts.fallback_findings.len(),
+            "found fallback pattern matches"
+        );
+    }
+
     match language {
         cli::Language::Go => {
             let loader = GoPackageLoader;
             let filter = GoImportFilter::new(ctx.preset_paths)
-                .context("Failed to create Go import filter")?;
-            scan_with_loader_and_filter(path, language, ctx, include_deps, &loader, &filter)?;
+                .context("Failed to create Go import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            artifacts.unanalyzed = find_go_artifacts(path, &filter);
+            if let Some(shard) = ctx.scan.shard {
+                artifacts
+                    .unanalyzed
+                    .retain(|a| shard.contains(Path::new(&a.file), path));
+            }
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
         }
         cli::Language::Python => {
             let loader = PythonPackageLoader;
             let filter = PythonImportFilter::new(ctx.preset_paths)
-                .context("Failed to create Python import filter")?;
-            scan_with_loader_and_filter(path, language, ctx, include_deps, &loader, &filter)?;
+                .context("Failed to create Python import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
         }
         cli::Language::Javascript | cli::Language::Typescript => {
             let loader = JavaScriptPackageLoader;
             let filter = JavaScriptImportFilter::new(ctx.preset_paths)
-                .context("Failed to create JavaScript import filter")?;
-            scan_with_loader_and_filter(path, language, ctx, include_deps, &loader, &filter)?;
+                .context("Failed to create JavaScript import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
         }
         cli::Language::Rust => {
             let loader = RustPackageLoader;
             let filter = RustImportFilter::new(ctx.preset_paths)
-                .context("Failed to create Rust import filter")?;
-            scan_with_loader_and_filter(path, language, ctx, include_deps, &loader, &filter)?;
+                .context("Failed to create Rust import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::Java => {
+            let loader = JavaPackageLoader;
+            let filter = JavaImportFilter::new(ctx.preset_paths)
+                .context("Failed to create Java import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::C => {
+            let loader = CPackageLoader;
+            let filter = CImportFilter::new(ctx.preset_paths)
+                .context("Failed to create C import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::Cpp => {
+            let loader = CppPackageLoader;
+            let filter = CppImportFilter::new(ctx.preset_paths)
+                .context("Failed to create C++ import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::CSharp => {
+            let loader = CSharpPackageLoader;
+            let filter = CSharpImportFilter::new(ctx.preset_paths)
+                .context("Failed to create C# import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::Ruby => {
+            let loader = RubyPackageLoader;
+            let filter = RubyImportFilter::new(ctx.preset_paths)
+                .context("Failed to create Ruby import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::Php => {
+            let loader = PhpPackageLoader;
+            let filter = PhpImportFilter::new(ctx.preset_paths)
+                .context("Failed to create PHP import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
+        }
+        cli::Language::Swift => {
+            let loader = SwiftPackageLoader;
+            let filter = SwiftImportFilter::new(ctx.preset_paths)
+                .context("Failed to create Swift import filter")?
+                .with_tokens(FilterTokens::from_classifier(ctx.classifier));
+            scan_with_loader_and_filter(
+                path,
+                language,
+                nested,
+                workspace,
+                ctx,
+                include_deps,
+                &loader,
+                &filter,
+                &artifacts,
+            )?;
         }
     }
 
     Ok(())
 }
 
+/// Whether `file` is test code by its path below `root`; files outside
+/// it, such as those of the module cache, go by their name.
+fn is_test_below(root: &Path, file: &Path) -> bool {
+    match file.strip_prefix(root) {
+        Ok(relative) => is_test_file(relative),
+        Err(_) => file
+            .file_name()
+            .is_some_and(|name| is_test_file(Path::new(name))),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn scan_with_loader_and_filter(
     path: &Path,
     language: cli::Language,
+    nested: &[PathBuf],
+    workspace: &Workspace,
     ctx: &ScanContext,
     include_deps: bool,
     loader: &dyn PackageLoader,
     filter: &dyn ImportFileFilter,
+    artifacts: &ArtifactReport,
 ) -> Result<()> {
     let mut cache = DiscoveryCache::default();
+    let discovery = Instant::now();
 
     // Discover user code files
     info!("discovering user code files");
     let mut all_files = loader
         .load_user_code(path)
         .context("Failed to discover user code files")?;
+    let skipped = SkipLog::new(ctx.scan.audit_coverage);
+    if skipped.is_enabled() {
+        record_unscanned(&skipped, &all_files, path, language, nested, ctx.scan.shard);
+    }
+    extensions::apply_to_user_code(&mut all_files, path, language);
+    add_templates(&mut all_files, path, language);
+    all_files.retain(|file| !in_nested(&file.path, nested));
     info!(count = all_files.len(), "found user code files");
+    // First pass: constants other packages use, before any file is scanned
+    if let Some(index) = ctx.scanner.project_index() {
+        let files: Vec<PathBuf> = all_files.iter().map(|file| file.path.clone()).collect();
+        let indexed = index.index_files(path, &files);
+        debug!(files = indexed, "indexed project constants");
+    }
+    let user_files: Vec<PathBuf> = match ctx.report.agility {
+        true => all_files.iter().map(|file| file.path.clone()).collect(),
+        false => Vec::new(),
+    };
 
     // Optionally include dependency files
+    let mut artifacts = artifacts.clone();
     if include_deps {
         info!("discovering dependency files");
-        match loader.load_dependencies(path, &mut cache) {
-            Ok(dep_files) => {
-                info!(count = dep_files.len(), "found dependency files");
-                all_files.extend(dep_files);
+        let loaded = load_dependencies(loader, path, &mut cache, ctx.scan.deps_budget, |package| {
+            is_crypto_capable(package, language.as_str(), ctx.classifier)
+                || filter.matches_package(package)
+        });
+        match loaded {
+            Ok(loaded) => {
+                info!(count = loaded.files.len(), "found dependency files");
+                artifacts.skipped_dependencies = loaded.skipped();
+                if !artifacts.skipped_dependencies.is_empty() {
+                    info!(
+                        budget = %ctx.scan.deps_budget,
+                        skipped = artifacts.skipped_dependencies.len(),
+                        files = loaded.over_budget.len(),
+                        "scanning only crypto-related dependencies"
+                    );
+                }
+                all_files.extend(
+                    loaded
+                        .files
+                        .into_iter()
+                        .filter(|file| extensions::keeps(&file.path, language)),
+                );
             }
             Err(e) => {
                 warn!(error = %e, "failed to load dependencies, continuing with user code only");
@@ -225,66 +1604,382 @@ fn scan_with_loader_and_filter(
         }
     }
 
+    if let Some(shard) = ctx.scan.shard {
+        all_files.retain(|file| shard.contains(&file.path, path));
+        info!(%shard, count = all_files.len(), "files in shard");
+    }
+
     info!(total = all_files.len(), "total files to scan");
+    let inputs: Vec<PathBuf> = match ctx.report.sign {
+        Some(_) => all_files.iter().map(|file| file.path.clone()).collect(),
+        None => Vec::new(),
+    };
+
+    let discovery_elapsed = discovery.elapsed();
+    let filtering = Instant::now();
 
     info!("filtering for matching imports");
     let matched_files: Vec<_> = all_files
         .into_iter()
-        .filter_map(|file| {
-            filter
-                .has_matching_imports(&file.path)
-                .ok()
-                .and_then(|has_match| has_match.then_some(file))
+        .filter_map(|file| match filter.has_matching_imports(&file.path) {
+            // Scripts in pages use globals more than imports.
+            _ if template_kind(&file.path).is_some() => Some(file),
+            Ok(true) => Some(file),
+            Ok(false) => {
+                skipped.record(&file.path, language.as_str(), SkipCategory::NotCrypto, "");
+                None
+            }
+            Err(e) => {
+                let (category, reason) = match e {
+                    FilterError::TooLarge { .. } => {
+                        (WarningCategory::TooLarge, SkipCategory::TooLarge)
+                    }
+                    _ => (WarningCategory::ReadFailed, SkipCategory::ReadFailed),
+                };
+                skipped.record(&file.path, language.as_str(), reason, &e);
+                ctx.warnings.record(category, &file.path, e);
+                None
+            }
         })
         .collect();
-    info!(count = matched_files.len(), "found files with matching imports");
-
-    let mut results = Vec::new();
-    for file in &matched_files {
-        trace!(file = %file.path.display(), "scanning file");
-        match std::fs::read_to_string(&file.path) {
-            Ok(source) => {
-                if let Ok(tree) = parse_source(&source, language) {
-                    let result = ctx.scanner.scan_tree(
-                        &tree,
-                        source.as_bytes(),
-                        &file.path.to_string_lossy(),
-                        language.as_str(),
-                    );
-                    if result.call_count() > 0 {
-                        debug!(
-                            file = %file.path.display(),
-                            calls = result.call_count(),
-                            "found matching calls"
-                        );
-                        results.push(result);
+    info!(
+        count = matched_files.len(),
+        "found files with matching imports"
+    );
+
+    // Each dependency is scanned as a unit of its own, in parallel
+    let units = ScanUnit::group(matched_files.into_iter().map(|file| {
+        let dependency = match &file.source_type {
+            SourceType::UserCode => None,
+            SourceType::Dependency { package, .. } => {
+                Some(dependency_name(&file).unwrap_or_else(|| package.clone()))
+            }
+            SourceType::Stdlib => Some("stdlib".to_string()),
+        };
+        (dependency, file.path)
+    }));
+    let filter_elapsed = filtering.elapsed();
+    let (scanner, warnings) = (ctx.scanner, ctx.warnings);
+    let (parse_nanos, resolve_nanos) = (AtomicU64::new(0), AtomicU64::new(0));
+    let scan_file = |parser: &mut tree_sitter::Parser, path: &Path| {
+        trace!(file = %path.display(), "scanning file");
+        let mut source = std::fs::read_to_string(path)
+            .inspect_err(|e| {
+                warnings.record(WarningCategory::ReadFailed, path, e);
+                skipped.record(path, language.as_str(), SkipCategory::ReadFailed, e);
+            })
+            .ok()?;
+        let mut file_language = language;
+        if let Some(kind) = template_kind(path) {
+            source = templates::extract(kind, &source);
+            file_language = kind.code_language();
+        }
+        let grammar = file_grammar(path, file_language)
+            .inspect_err(|e| {
+                debug!(file = %path.display(), error = %e, "skipping file");
+                skipped.record(
+                    path,
+                    language.as_str(),
+                    SkipCategory::UnsupportedLanguage,
+                    e,
+                );
+            })
+            .ok()?;
+        parser.set_language(&grammar).ok()?;
+        let parsing = Instant::now();
+        let Some(tree) = parser.parse(&source, None) else {
+            skipped.record(path, language.as_str(), SkipCategory::ParseError, "");
+            return None;
+        };
+        let resolving = Instant::now();
+        let result = scanner.scan_tree(
+            &tree,
+            source.as_bytes(),
+            &path.to_string_lossy(),
+            scan_language(path, file_language),
+        );
+        parse_nanos.fetch_add(nanos(resolving - parsing), Ordering::Relaxed);
+        resolve_nanos.fetch_add(nanos(resolving.elapsed()), Ordering::Relaxed);
+        if !result.has_findings() {
+            return None;
+        }
+        debug!(
+            file = %path.display(),
+            calls = result.call_count(),
+            configs = result.config_count(),
+            "found matching calls"
+        );
+        Some(result)
+    };
+
+    let mut results = ResultSpool::new(ctx.scan.max_memory);
+    let mut timings = Vec::new();
+    units::scan_units(
+        units,
+        ctx.scan.jobs,
+        &tree_sitter_language(language)?,
+        scan_file,
+        |scan| {
+            timings.push(scan.timing());
+            scan.results
+                .into_iter()
+                .try_for_each(|mut result| {
+                    result.dependency = scan.unit.dependency.clone();
+                    result.test_code = is_test_below(path, Path::new(&result.file_path));
+                    if result.dependency.is_none() {
+                        result.member = workspace
+                            .member_of(Path::new(&result.file_path), language)
+                            .map(|member| member.name.clone());
                     }
-                }
+                    results.push(result)
+                })
+                .context("Failed to spill scan results to disk")
+        },
+    )?;
+    artifacts.errors = ctx.warnings.take_errors();
+    if skipped.is_enabled() {
+        artifacts.skipped_files = skipped.take();
+        info!(
+            count = artifacts.skipped_files.len(),
+            "listed files not scanned"
+        );
+    }
+    if ctx.report.timings {
+        let mut timings = ScanTimings::default();
+        timings.add(
+            language.as_str(),
+            PhaseTimings {
+                discovery_ms: millis(discovery_elapsed),
+                filter_ms: millis(filter_elapsed),
+                parse_ms: millis(Duration::from_nanos(parse_nanos.into_inner())),
+                resolve_ms: millis(Duration::from_nanos(resolve_nanos.into_inner())),
+                ..PhaseTimings::default()
+            },
+        );
+        artifacts.timings = Some(timings);
+    }
+    if ctx.report.agility {
+        artifacts.agility = Some(analyze_agility(&mut results, &user_files, ctx)?);
+    }
+    if ctx.report.hotspots {
+        artifacts.hotspots = Some(analyze_hotspots(&mut results, ctx)?);
+    }
+
+    let totals = results.totals();
+    if cancel::is_cancelled() {
+        artifacts.partial = true;
+        warn!(
+            files = totals.files,
+            calls = totals.calls,
+            "scan cancelled, writing the results completed so far"
+        );
+    } else {
+        info!(files = totals.files, calls = totals.calls, "scan complete");
+    }
+    log_expression_cache_stats(ctx.scanner);
+    if ctx.scanner.resolver().profile().is_some() {
+        eprintln!("dependency profile:");
+        eprint!("{}", units::render_timings(&timings));
+    }
+
+    if results.has_spilled() {
+        info!(
+            spilled = results.spilled_count(),
+            "results exceeded --max-memory, streaming output from disk"
+        );
+        output_spooled(&mut results, &artifacts, ctx)?;
+    } else {
+        let results = results
+            .into_results()
+            .context("Failed to collect scan results")?;
+        output_results(&results, &artifacts, ctx)?;
+    }
+    sign_report(ctx, path, &inputs)
+}
+
+/// With `--audit-coverage`, records the files of a `language` project at
+/// `root` that no loader result stands for: the excluded directories, the
+/// files in languages no scan parses, and the `files` the config file's
+/// extension overrides skip. Under `--shard`, only those in the shard.
+fn record_unscanned(
+    skipped: &SkipLog,
+    files: &[SourceFile],
+    root: &Path,
+    language: cli::Language,
+    nested: &[PathBuf],
+    shard: Option<Shard>,
+) {
+    let kept = |path: &Path| {
+        !in_nested(path, nested) && shard.is_none_or(|shard| shard.contains(path, root))
+    };
+    let name = language.as_str();
+    for directory in dry_run::excluded_directories(root, language) {
+        if kept(&directory) {
+            skipped.record(&directory, name, SkipCategory::ExcludedDirectory, "");
+        }
+    }
+    for file in dry_run::unsupported_files(root, language) {
+        if kept(&file) {
+            skipped.record(&file, "", SkipCategory::UnsupportedLanguage, "");
+        }
+    }
+    for file in files {
+        if extensions::override_for(&file.path) == Some(ExtensionOverride::Skip) && kept(&file.path)
+        {
+            skipped.record(&file.path, name, SkipCategory::ExcludedExtension, "");
+        }
+    }
+}
+
+/// The kind of template at `path`, unless a config extension override maps
+/// its extension to a language to parse it as.
+fn template_kind(path: &Path) -> Option<TemplateKind> {
+    extensions::override_for(path)
+        .is_none()
+        .then(|| TemplateKind::for_path(path))
+        .flatten()
+}
+
+/// Adds the templates with code embedded that belong to a `language`
+/// project at `root` to its user code, unless a config extension override
+/// says otherwise.
+fn add_templates(files: &mut Vec<SourceFile>, root: &Path, language: cli::Language) {
+    let known: HashSet<PathBuf> = files.iter().map(|file| file.path.clone()).collect();
+    for template in templates::find_templates(root, language) {
+        if known.contains(&template) || extensions::override_for(&template).is_some() {
+            continue;
+        }
+        let metadata = std::fs::metadata(&template).ok();
+        files.push(SourceFile {
+            path: template,
+            language,
+            source_type: SourceType::UserCode,
+            package: None,
+            metadata: FileMetadata {
+                size: metadata.as_ref().map_or(0, |m| m.len()),
+                modified: metadata.and_then(|m| m.modified().ok()),
+                hash: None,
+            },
+        });
+    }
+}
+
+/// With `--agility`, finds the modules wrapping the crypto calls in
+/// `results` and counts the calls to them from `user_files`.
+fn analyze_agility(
+    results: &mut ResultSpool,
+    user_files: &[PathBuf],
+    ctx: &ScanContext,
+) -> Result<AgilityReport> {
+    let mut analyzer = AgilityAnalyzer::default();
+    results
+        .for_each(|result| {
+            analyzer.add_result(result, ctx.classifier);
+            Ok(())
+        })
+        .context("Failed to read spilled scan results")?;
+    if analyzer.has_wrappers() {
+        for path in user_files {
+            match std::fs::read_to_string(path) {
+                Ok(source) => analyzer.add_source(&path.to_string_lossy(), &source),
+                Err(e) => debug!(file = %path.display(), error = %e, "skipping file for agility"),
             }
-            Err(e) => warn!(file = %file.path.display(), error = %e, "failed to read file"),
         }
     }
+    let report = analyzer.finish();
+    info!(
+        score = report.score,
+        layers = report.layers.len(),
+        direct_calls = report.direct_calls.len(),
+        "analyzed crypto agility"
+    );
+    Ok(report)
+}
 
-    let total_calls: usize = results.iter().map(|r| r.call_count()).sum();
-    info!(files = results.len(), calls = total_calls, "scan complete");
+/// With `--hotspots`, ranks the files, functions and modules of `results`
+/// concentrating crypto calls.
+fn analyze_hotspots(results: &mut ResultSpool, ctx: &ScanContext) -> Result<HotspotReport> {
+    let mut analyzer = HotspotAnalyzer::default();
+    results
+        .for_each(|result| {
+            let lines = match std::fs::read_to_string(&result.file_path) {
+                Ok(source) => source.lines().count(),
+                Err(e) => {
+                    debug!(file = %result.file_path, error = %e, "counting no lines for hotspots");
+                    0
+                }
+            };
+            analyzer.add_result(result, lines, ctx.classifier);
+            Ok(())
+        })
+        .context("Failed to read spilled scan results")?;
+    let report = analyzer.finish();
+    info!(
+        call_sites = report.call_sites,
+        concentrated = report.concentrated_modules.len(),
+        "analyzed crypto hotspots"
+    );
+    Ok(report)
+}
+
+/// With `--sign`, signs an attestation binding the written report to a
+/// digest of `inputs`.
+fn sign_report(ctx: &ScanContext, root: &Path, inputs: &[PathBuf]) -> Result<()> {
+    let (Some(key), Some(report)) = (ctx.report.sign, ctx.report.output_file) else {
+        return Ok(());
+    };
 
-    output_results(&results, ctx.classifier, ctx.output_format, ctx.output_file)?;
+    let predicate = ScanPredicate {
+        tool: ToolInfo::default(),
+        inputs: digest_inputs(root, inputs).context("Failed to digest scanned files")?,
+        report_format: ctx.report.output_format.as_str().to_string(),
+        presets: ctx.presets.to_vec(),
+    };
+    debug!(files = predicate.inputs.files, digest = %predicate.inputs.sha256, "digested inputs");
+
+    let bundle = attest_report(report, &predicate, key, ctx.report.offline)
+        .context("Failed to sign report")?;
+    info!(bundle = %bundle.display(), "wrote signed attestation");
     Ok(())
 }
 
-fn parse_source(source: &str, language: cli::Language) -> Result<tree_sitter::Tree> {
-    let mut parser = tree_sitter::Parser::new();
-
-    let ts_language = match language {
-        cli::Language::Go => tree_sitter_go::LANGUAGE.into(),
-        cli::Language::Python => tree_sitter_python::LANGUAGE.into(),
-        cli::Language::Rust => tree_sitter_rust::LANGUAGE.into(),
-        cli::Language::Javascript => tree_sitter_javascript::LANGUAGE.into(),
-        cli::Language::Typescript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+/// Assembly and cgo files in packages that import crypto; these are reported
+/// as coverage gaps rather than silently skipped.
+fn find_go_artifacts(path: &Path, filter: &dyn ImportFileFilter) -> Vec<UnanalyzedArtifact> {
+    let found = match artifacts::find_artifacts(path) {
+        Ok(found) => found,
+        Err(e) => {
+            warn!(error = %e, "failed to look for Go assembly and cgo files");
+            return Vec::new();
+        }
     };
 
+    let adjacent = artifacts::crypto_adjacent(found, filter);
+    if !adjacent.is_empty() {
+        warn!(
+            count = adjacent.len(),
+            "found assembly/cgo files in crypto packages that cannot be analyzed"
+        );
+    }
+    adjacent.iter().map(UnanalyzedArtifact::from).collect()
+}
+
+fn log_expression_cache_stats(scanner: &Scanner) {
+    if let Some(stats) = scanner.expression_cache_stats() {
+        info!(
+            hits = stats.hits,
+            misses = stats.misses,
+            entries = stats.entries,
+            hit_rate = format!("{:.1}%", stats.hit_rate() * 100.0),
+            "expression cache"
+        );
+    }
+}
+
+fn parse_source(source: &str, path: &Path, language: cli::Language) -> Result<tree_sitter::Tree> {
+    let mut parser = tree_sitter::Parser::new();
     parser
-        .set_language(&ts_language)
+        .set_language(&file_grammar(path, language)?)
         .context("Failed to set parser language")?;
 
     parser
@@ -292,14 +1987,149 @@ fn parse_source(source: &str, language: cli::Language) -> Result<tree_sitter::Tr
         .context("Failed to parse source code")
 }
 
+/// The grammar for `path` in a scan of `language`, e.g. TSX for a `.tsx`
+/// file of a TypeScript project.
+fn file_grammar(path: &Path, language: cli::Language) -> Result<tree_sitter::Language> {
+    if let Some(ExtensionOverride::Language(mapped)) = extensions::override_for(path) {
+        return tree_sitter_language(mapped);
+    }
+    match EngineLanguage::parse(language.as_str())
+        .and_then(|engine_language| symbol_index::file_grammar(engine_language, path))
+    {
+        Some(grammar) => Ok(grammar),
+        None => tree_sitter_language(language),
+    }
+}
+
+/// The language the scanner reads `path` as in a scan of `language`: its
+/// own for the Kotlin files of a Java project.
+fn scan_language(path: &Path, language: cli::Language) -> &'static str {
+    let Some(engine_language) = EngineLanguage::parse(language.as_str()) else {
+        return language.as_str();
+    };
+    match symbol_index::file_language(engine_language, path) {
+        own if own != engine_language => own.tree_sitter_name(),
+        _ => language.as_str(),
+    }
+}
+
+/// The grammar of `language`, or an error naming the feature that compiles
+/// it in.
+fn tree_sitter_language(language: cli::Language) -> Result<tree_sitter::Language> {
+    let engine_language = EngineLanguage::parse(language.as_str())
+        .with_context(|| format!("No grammar for {}", language.as_str()))?;
+    Ok(grammars::require(engine_language)?)
+}
+
 fn output_results(
     results: &[ScanResult],
-    classifier: &RulesClassifier,
-    format: OutputFormat,
-    output_file: Option<&PathBuf>,
+    artifacts: &ArtifactReport,
+    ctx: &ScanContext,
 ) -> Result<()> {
-    let output = OutputFormatter::format(results, classifier, format)?;
+    let classifying = Instant::now();
+    let mut output = OutputFormatter::build_output_with_artifacts(
+        results,
+        artifacts,
+        ctx.classifier,
+        ctx.report.password_policy,
+    );
+    if let Some(timings) = output.metadata.as_mut().and_then(|m| m.timings.as_mut()) {
+        let classify_ms = millis(classifying.elapsed());
+        timings.total.classify_ms += classify_ms;
+        // A path's report is of one language.
+        for phases in timings.languages.values_mut() {
+            phases.classify_ms += classify_ms;
+        }
+    }
+    if !ctx.report.waivers.is_empty() {
+        let report = apply_waivers(&mut output.weaknesses, ctx.report.waivers, &today());
+        for expired in &report.expired {
+            let waiver = &expired.waiver;
+            warn!(
+                owner = %waiver.owner,
+                expires = waiver.expires.as_deref().unwrap_or_default(),
+                weaknesses = expired.weaknesses,
+                "waiver expired, its weaknesses are reported again"
+            );
+        }
+        output.risk_score = risk_score(&output.weaknesses);
+        output.waivers = Some(report);
+    }
+    if let Some((_, policy)) = ctx.report.suggest_fixes {
+        output.fixes = suggest_fixes(&output.findings, &output.configs, policy, read_source);
+    }
+    if let Some(reports) = ctx.reports {
+        reports.borrow_mut().push(output);
+        return Ok(());
+    }
+    if let Some((file, _)) = ctx.report.suggest_fixes {
+        write_fix_diff(&output, file)?;
+    }
+    if let Some(max_findings) = ctx.report.max_findings {
+        cap_findings(&mut output, max_findings);
+    }
+    write_output(
+        &render_timed(&mut output, ctx.report.output_format)?,
+        ctx.report.output_file,
+    )?;
+    send_notification(&output, ctx)
+}
 
+/// With `notify` in the config file, posts the weaknesses of `report` worth
+/// a notification to the webhook.
+fn send_notification(report: &JsonOutput, ctx: &ScanContext) -> Result<()> {
+    let Some((config, baseline)) = ctx.report.notify else {
+        return Ok(());
+    };
+    match Notification::for_report(report, config, baseline) {
+        Some(notification) => {
+            post_notification(&config.webhook, &notification)
+                .context("Failed to send notification")?;
+            info!(
+                weaknesses = notification.weaknesses.len(),
+                "posted notification"
+            );
+        }
+        None => debug!("no weaknesses to notify about"),
+    }
+    Ok(())
+}
+
+/// Renders `output`, recording the time rendering takes in its timings
+/// when it has some: it is rendered again with them.
+fn render_timed(output: &mut JsonOutput, format: OutputFormat) -> Result<String> {
+    let rendering = Instant::now();
+    let rendered = OutputFormatter::render(output, format)?;
+    match output.metadata.as_mut().and_then(|m| m.timings.as_mut()) {
+        Some(timings) => {
+            timings.total.output_ms = millis(rendering.elapsed());
+            OutputFormatter::render(output, format)
+        }
+        None => Ok(rendered),
+    }
+}
+
+fn read_source(file: &str) -> Option<String> {
+    std::fs::read_to_string(file).ok()
+}
+
+/// Writes the edits of the `fixes` of `report` to `file` as a unified diff.
+fn write_fix_diff(report: &JsonOutput, file: &Path) -> Result<()> {
+    std::fs::write(file, render_diff(&report.fixes, read_source))
+        .with_context(|| format!("Failed to write fixes: {}", file.display()))?;
+    info!(
+        path = %file.display(),
+        fixes = report.fixes.len(),
+        "wrote suggested fixes"
+    );
+    Ok(())
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn write_output(output: &str, output_file: Option<&PathBuf>) -> Result<()> {
     match output_file {
         Some(path) => {
             let mut file = std::fs::File::create(path)
@@ -315,3 +2145,45 @@ fn output_results(
 
     Ok(())
 }
+
+fn output_spooled(
+    spool: &mut ResultSpool,
+    artifacts: &ArtifactReport,
+    ctx: &ScanContext,
+) -> Result<()> {
+    match ctx.report.output_file {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+            let mut writer = std::io::BufWriter::new(file);
+            OutputFormatter::write_spooled(
+                spool,
+                artifacts,
+                ctx.classifier,
+                ctx.report.password_policy,
+                ctx.report.max_findings,
+                ctx.report.output_format,
+                &mut writer,
+            )
+            .with_context(|| format!("Failed to write to output file: {}", path.display()))?;
+            writer.flush()?;
+            info!(path = %path.display(), "wrote output to file");
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut writer = std::io::BufWriter::new(stdout.lock());
+            OutputFormatter::write_spooled(
+                spool,
+                artifacts,
+                ctx.classifier,
+                ctx.report.password_policy,
+                ctx.report.max_findings,
+                ctx.report.output_format,
+                &mut writer,
+            )?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
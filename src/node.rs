@@ -0,0 +1,119 @@
+//! Node.js addon entry points (`--features node`).
+//!
+//! Build the addon with `cargo build --lib --release --features node` and load
+//! the resulting shared library as `argflow.node`. Every function returns the
+//! same report object the CLI prints as JSON. Editors keep a [`Documents`]
+//! per workspace so each keystroke only resolves the calls it changed.
+
+use std::path::Path;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::classifier::RulesClassifier;
+use crate::output::{JsonOutput, OutputFormatter};
+use crate::scanner::{DocumentStore, TextEdit};
+
+/// Scans one file. `language` is inferred from the extension when omitted;
+/// `rules` is a user rules document (the `--rules` JSON format).
+#[napi(js_name = "scanFile")]
+pub fn scan_file(
+    path: String,
+    language: Option<String>,
+    rules: Option<String>,
+) -> Result<serde_json::Value> {
+    let classifier = classifier_from(rules)?;
+    let output = crate::api::scan_file(Path::new(&path), language.as_deref(), &classifier)
+        .map_err(to_napi_error)?;
+    to_js_value(output)
+}
+
+/// Scans the user code of a project directory for one language.
+#[napi(js_name = "scanProject")]
+pub fn scan_project(
+    path: String,
+    language: String,
+    rules: Option<String>,
+) -> Result<serde_json::Value> {
+    let classifier = classifier_from(rules)?;
+    let output = crate::api::scan_directory(Path::new(&path), &language, &classifier)
+        .map_err(to_napi_error)?;
+    to_js_value(output)
+}
+
+/// A replacement of the bytes `start..end` of a document with `text`.
+/// Offsets are UTF-8 byte offsets into the document as the previous edit
+/// left it.
+#[napi(object)]
+pub struct DocumentEdit {
+    pub start: u32,
+    pub end: u32,
+    pub text: String,
+}
+
+/// The documents open in an editor, reparsed and rescanned incrementally as
+/// they are edited.
+#[napi]
+pub struct Documents {
+    store: DocumentStore,
+    classifier: RulesClassifier,
+}
+
+#[napi]
+impl Documents {
+    /// `rules` is a user rules document (the `--rules` JSON format).
+    #[napi(constructor)]
+    pub fn new(rules: Option<String>) -> Result<Self> {
+        let classifier = classifier_from(rules)?;
+        Ok(Self {
+            store: DocumentStore::new(crate::api::scanner_for(&classifier)),
+            classifier,
+        })
+    }
+
+    /// Scans `source` as the document at `path`, whose extension gives its
+    /// language.
+    #[napi]
+    pub fn open(&mut self, path: String, source: String) -> Result<serde_json::Value> {
+        let doc = self.store.open(path, source).map_err(to_napi_error)?;
+        let output = OutputFormatter::build_output(&[doc.result().clone()], &self.classifier);
+        to_js_value(output)
+    }
+
+    /// Applies `edits` in order to the document at `path` and returns its
+    /// report.
+    #[napi]
+    pub fn edit(&mut self, path: String, edits: Vec<DocumentEdit>) -> Result<serde_json::Value> {
+        let edits: Vec<TextEdit> = edits
+            .into_iter()
+            .map(|edit| TextEdit::new(edit.start as usize..edit.end as usize, edit.text))
+            .collect();
+        let doc = self
+            .store
+            .edit(Path::new(&path), &edits)
+            .map_err(to_napi_error)?;
+        let output = OutputFormatter::build_output(&[doc.result().clone()], &self.classifier);
+        to_js_value(output)
+    }
+
+    /// Forgets the document at `path`; false when it was not open.
+    #[napi]
+    pub fn close(&mut self, path: String) -> bool {
+        self.store.close(Path::new(&path)).is_some()
+    }
+}
+
+fn classifier_from(rules: Option<String>) -> Result<RulesClassifier> {
+    match rules {
+        Some(rules) => RulesClassifier::from_json_str(&rules).map_err(to_napi_error),
+        None => Ok(RulesClassifier::new()),
+    }
+}
+
+fn to_js_value(output: JsonOutput) -> Result<serde_json::Value> {
+    serde_json::to_value(output).map_err(to_napi_error)
+}
+
+fn to_napi_error(error: impl std::fmt::Display) -> Error {
+    Error::from_reason(error.to_string())
+}
@@ -0,0 +1,335 @@
+//! How hard moving off an algorithm would be: whether the project's crypto
+//! calls go through an internal abstraction layer or are made directly all
+//! over the code.
+//!
+//! A module (the directory of a source file) is an abstraction layer when
+//! the functions wrapping its crypto calls are called from at least
+//! [`MIN_LAYER_REFERENCES`] places in other modules. Calls are found by
+//! name in the project's source, so a layer is recognized even when its
+//! callers import no crypto package. The agility score is the share of
+//! crypto call sites inside layers; every other one is a direct call to
+//! migrate on its own. Dependency and test code are left out.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::classifier::RulesClassifier;
+use crate::scanner::ScanResult;
+
+use super::Finding;
+
+/// Calls from other modules a module's wrappers need to be a layer.
+pub const MIN_LAYER_REFERENCES: usize = 3;
+
+/// Enclosing functions too common to tell a wrapper's callers apart.
+const GENERIC_FUNCTIONS: &[&str] = &["main", "init", "__init__", "constructor", "new", "New"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgilityReport {
+    /// Share of crypto call sites inside abstraction layers, 0 to 100;
+    /// 100 when the project makes no crypto calls.
+    pub score: u32,
+    pub call_sites: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<AbstractionLayer>,
+    /// Call sites outside any layer, by file and line.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub direct_calls: Vec<DirectCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbstractionLayer {
+    pub module: String,
+    pub call_sites: usize,
+    /// Functions of the module making the crypto calls.
+    pub wrappers: Vec<String>,
+    /// Calls to the wrappers from other modules.
+    pub references: usize,
+    pub referencing_modules: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DirectCall {
+    pub file: String,
+    pub line: usize,
+    pub function: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+}
+
+struct CallSite {
+    call: DirectCall,
+    module: String,
+    wrapper: Option<String>,
+}
+
+/// Collects the project's crypto call sites from scan results, then the
+/// calls to their wrappers from the project's source files.
+#[derive(Default)]
+pub struct AgilityAnalyzer {
+    sites: Vec<CallSite>,
+    /// Calls to each wrapper name, by calling module.
+    references: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl AgilityAnalyzer {
+    pub fn add_result(&mut self, result: &ScanResult, classifier: &RulesClassifier) {
+        if result.dependency.is_some() || result.test_code {
+            return;
+        }
+        for call in &result.calls {
+            let finding = Finding::from_scanner_finding(call, classifier);
+            let wrapper = call
+                .site
+                .enclosing_function
+                .clone()
+                .filter(|name| !GENERIC_FUNCTIONS.contains(&name.as_str()));
+            self.sites.push(CallSite {
+                call: DirectCall {
+                    file: finding.file,
+                    line: finding.line,
+                    function: finding.full_name,
+                    algorithm: finding.algorithm,
+                },
+                module: module_of(&result.file_path),
+                wrapper,
+            });
+        }
+    }
+
+    /// Whether any call site is in a function other code could call.
+    pub fn has_wrappers(&self) -> bool {
+        self.sites.iter().any(|site| site.wrapper.is_some())
+    }
+
+    /// Counts the calls to wrapper functions in `source`, a project file at
+    /// `file`.
+    pub fn add_source(&mut self, file: &str, source: &str) {
+        let wrappers: BTreeSet<&str> = self
+            .sites
+            .iter()
+            .filter_map(|site| site.wrapper.as_deref())
+            .collect();
+        let module = module_of(file);
+        for name in called_names(source) {
+            if wrappers.contains(name) {
+                *self
+                    .references
+                    .entry(name.to_string())
+                    .or_default()
+                    .entry(module.clone())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    pub fn finish(self) -> AgilityReport {
+        let call_sites = self.sites.len();
+        let mut modules: BTreeMap<&str, Vec<&CallSite>> = BTreeMap::new();
+        for site in &self.sites {
+            modules.entry(&site.module).or_default().push(site);
+        }
+
+        let mut layers = Vec::new();
+        for (module, sites) in &modules {
+            let wrappers: BTreeSet<&str> = sites
+                .iter()
+                .filter_map(|site| site.wrapper.as_deref())
+                .collect();
+            let mut callers = BTreeMap::new();
+            for wrapper in &wrappers {
+                let Some(references) = self.references.get(*wrapper) else {
+                    continue;
+                };
+                for (caller, count) in references.iter().filter(|(m, _)| m != module) {
+                    *callers.entry(caller.as_str()).or_insert(0) += count;
+                }
+            }
+            let references: usize = callers.values().sum();
+            if references >= MIN_LAYER_REFERENCES {
+                layers.push(AbstractionLayer {
+                    module: module.to_string(),
+                    call_sites: sites.len(),
+                    wrappers: wrappers.into_iter().map(str::to_string).collect(),
+                    references,
+                    referencing_modules: callers.len(),
+                });
+            }
+        }
+
+        let mut direct_calls: Vec<DirectCall> = self
+            .sites
+            .into_iter()
+            .filter(|site| !layers.iter().any(|layer| layer.module == site.module))
+            .map(|site| site.call)
+            .collect();
+        direct_calls.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        let mut report = AgilityReport {
+            score: 0,
+            call_sites,
+            layers,
+            direct_calls,
+        };
+        report.score = report.compute_score();
+        report
+    }
+}
+
+impl AgilityReport {
+    /// Combines the reports of separately scanned paths.
+    pub fn merge(reports: impl IntoIterator<Item = AgilityReport>) -> Self {
+        let mut merged = Self::default();
+        for report in reports {
+            merged.call_sites += report.call_sites;
+            merged.layers.extend(report.layers);
+            merged.direct_calls.extend(report.direct_calls);
+        }
+        merged.layers.sort_by(|a, b| a.module.cmp(&b.module));
+        merged.layers.dedup();
+        merged
+            .direct_calls
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        merged.direct_calls.dedup();
+        merged.score = merged.compute_score();
+        merged
+    }
+
+    fn compute_score(&self) -> u32 {
+        if self.call_sites == 0 {
+            return 100;
+        }
+        let layered = self.call_sites.saturating_sub(self.direct_calls.len());
+        (100.0 * layered as f64 / self.call_sites as f64).round() as u32
+    }
+}
+
+fn module_of(file: &str) -> String {
+    Path::new(file)
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Identifiers directly followed by `(`, e.g. `Encrypt` in
+/// `crypto.Encrypt(key, data)`.
+fn called_names(source: &str) -> impl Iterator<Item = &str> {
+    let bytes = source.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$';
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        while start < bytes.len() {
+            if !is_ident(bytes[start]) {
+                start += 1;
+                continue;
+            }
+            let begin = start;
+            while start < bytes.len() && is_ident(bytes[start]) {
+                start += 1;
+            }
+            let mut next = start;
+            while next < bytes.len() && bytes[next].is_ascii_whitespace() {
+                next += 1;
+            }
+            if bytes.get(next) == Some(&b'(') && !bytes[begin].is_ascii_digit() {
+                return Some(&source[begin..start]);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{CallSite as Site, Finding as Call};
+
+    fn result(file: &str, calls: &[(usize, &str, Option<&str>)]) -> ScanResult {
+        let mut result = ScanResult::new(file.to_string());
+        result.test_code = crate::policy::is_test_file(std::path::Path::new(file));
+        for (line, function, wrapper) in calls {
+            result.add_call(Call {
+                file_path: file.to_string(),
+                line: *line,
+                column: 1,
+                function_name: function.to_string(),
+                package: Some("crypto/aes".to_string()),
+                import_path: None,
+                arguments: Vec::new(),
+                keywords: Vec::new(),
+                raw_text: format!("aes.{function}()"),
+                language: "go".to_string(),
+                site: Site {
+                    enclosing_function: wrapper.map(str::to_string),
+                    ..Site::default()
+                },
+                dynamic_name: None,
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_called_names() {
+        let names: Vec<&str> =
+            called_names("x := cryptoutil.Seal (key, data)\nif ok(1) { f2(\"a(\") }").collect();
+        assert_eq!(names, vec!["Seal", "ok", "f2", "a"]);
+    }
+
+    #[test]
+    fn test_layer_and_direct_calls() {
+        let classifier = RulesClassifier::from_json_str(r#"{"mappings": {}}"#).unwrap();
+        let mut analyzer = AgilityAnalyzer::default();
+        analyzer.add_result(
+            &result(
+                "internal/cryptoutil/seal.go",
+                &[
+                    (10, "NewCipher", Some("Seal")),
+                    (20, "NewCipher", Some("Open")),
+                ],
+            ),
+            &classifier,
+        );
+        analyzer.add_result(
+            &result("cmd/legacy/main.go", &[(5, "NewCipher", Some("main"))]),
+            &classifier,
+        );
+        analyzer.add_result(
+            &result(
+                "cmd/legacy/main_test.go",
+                &[(7, "NewCipher", Some("TestSeal"))],
+            ),
+            &classifier,
+        );
+        assert!(analyzer.has_wrappers());
+
+        analyzer.add_source(
+            "internal/cryptoutil/seal.go",
+            "func Seal(k []byte) {}\nfunc Open(k []byte) { Seal(k) }",
+        );
+        analyzer.add_source("api/handler.go", "cryptoutil.Seal(k)\ncryptoutil.Open(k)");
+        analyzer.add_source("jobs/rotate.go", "cryptoutil.Open(k)");
+
+        let report = analyzer.finish();
+        assert_eq!(report.call_sites, 3);
+        assert_eq!(report.score, 67);
+        assert_eq!(report.layers.len(), 1);
+        let layer = &report.layers[0];
+        assert_eq!(layer.module, "internal/cryptoutil");
+        assert_eq!(layer.wrappers, vec!["Open", "Seal"]);
+        assert_eq!(layer.references, 3);
+        assert_eq!(layer.referencing_modules, 2);
+        assert_eq!(report.direct_calls.len(), 1);
+        assert_eq!(report.direct_calls[0].file, "cmd/legacy/main.go");
+        assert_eq!(report.direct_calls[0].function, "crypto/aes.NewCipher");
+    }
+
+    #[test]
+    fn test_no_calls_score_full() {
+        let report = AgilityAnalyzer::default().finish();
+        assert_eq!(report.score, 100);
+        assert_eq!(AgilityReport::merge([report.clone(), report]).score, 100);
+    }
+}
@@ -0,0 +1,227 @@
+//! Signed attestations for emitted reports.
+//!
+//! The report is the attestation subject; the predicate records which tool
+//! produced it and a digest of every input file, so a policy engine can check
+//! that a report belongs to the source tree it claims to describe. Signing is
+//! delegated to `cosign attest-blob`, which covers both Sigstore keyless
+//! signing and provided keys (files or KMS URIs).
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::AttestationError;
+
+const COSIGN_COMMAND: &str = "cosign";
+
+/// in-toto predicate type of argflow scan attestations.
+pub const PREDICATE_TYPE: &str = "https://github.com/smith-xyz/argflow/attestation/scan/v1";
+
+/// How to sign: Sigstore keyless (OIDC identity, Fulcio certificate, Rekor
+/// entry) or a key reference understood by cosign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigningKey {
+    Keyless,
+    Key(String),
+}
+
+impl SigningKey {
+    /// Whether signing reaches a service: Fulcio and Rekor for keyless, the
+    /// KMS for a key URI. A key file signs locally.
+    pub fn needs_network(&self) -> bool {
+        match self {
+            Self::Keyless => true,
+            Self::Key(key) => key.contains("://"),
+        }
+    }
+}
+
+impl FromStr for SigningKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("expected 'keyless' or a key reference".to_string()),
+            "keyless" => Ok(Self::Keyless),
+            key => Ok(Self::Key(key.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanPredicate {
+    pub tool: ToolInfo,
+    pub inputs: InputDigest,
+    pub report_format: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub version: String,
+}
+
+impl Default for ToolInfo {
+    fn default() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Digest over all scanned files.
+///
+/// `sha256` is the SHA-256 of a manifest with one `<file sha256>  <path>`
+/// line per file (the `sha256sum` format), sorted by path relative to the
+/// scan root, so it can be recomputed with standard tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputDigest {
+    pub files: usize,
+    pub sha256: String,
+}
+
+pub fn digest_inputs(root: &Path, files: &[PathBuf]) -> Result<InputDigest, AttestationError> {
+    let mut entries = files
+        .iter()
+        .map(|file| {
+            let content = std::fs::read(file)
+                .map_err(|e| AttestationError::input_error(file, e.to_string()))?;
+            let relative = file.strip_prefix(root).unwrap_or(file);
+            let name: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            Ok((name.join("/"), format!("{:x}", Sha256::digest(&content))))
+        })
+        .collect::<Result<Vec<_>, AttestationError>>()?;
+    entries.sort();
+    entries.dedup();
+
+    let manifest: String = entries
+        .iter()
+        .map(|(name, digest)| format!("{digest}  {name}\n"))
+        .collect();
+    Ok(InputDigest {
+        files: entries.len(),
+        sha256: format!("{:x}", Sha256::digest(manifest.as_bytes())),
+    })
+}
+
+/// Signs an attestation for `report` and returns the path of the Sigstore
+/// bundle, written next to the report as `<report>.sigstore.json`. Verify with
+/// `cosign verify-blob-attestation --bundle <bundle> --type <PREDICATE_TYPE>`.
+/// When `offline`, the signature is not uploaded to the Rekor transparency
+/// log, so verify with `--insecure-ignore-tlog`.
+pub fn attest_report(
+    report: &Path,
+    predicate: &ScanPredicate,
+    key: &SigningKey,
+    offline: bool,
+) -> Result<PathBuf, AttestationError> {
+    let predicate_path = sibling(report, "predicate.json");
+    let bundle_path = sibling(report, "sigstore.json");
+
+    let json = serde_json::to_string_pretty(predicate)
+        .map_err(|e| AttestationError::write_error(&predicate_path, e.to_string()))?;
+    std::fs::write(&predicate_path, json)
+        .map_err(|e| AttestationError::write_error(&predicate_path, e.to_string()))?;
+
+    let mut command = Command::new(COSIGN_COMMAND);
+    command
+        .args([
+            "attest-blob",
+            "--yes",
+            "--type",
+            PREDICATE_TYPE,
+            "--predicate",
+        ])
+        .arg(&predicate_path)
+        .arg("--bundle")
+        .arg(&bundle_path);
+    if let SigningKey::Key(key) = key {
+        command.args(["--key", key]);
+    }
+    if offline {
+        command.arg("--tlog-upload=false");
+    }
+    command.arg(report);
+
+    let output = command
+        .output()
+        .map_err(|e| AttestationError::signer_unavailable(COSIGN_COMMAND, e.to_string()))?;
+    if !output.status.success() {
+        return Err(AttestationError::signing_failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(bundle_path)
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signing_key() {
+        assert_eq!("keyless".parse::<SigningKey>(), Ok(SigningKey::Keyless));
+        assert_eq!(
+            "awskms:///alias/argflow".parse::<SigningKey>(),
+            Ok(SigningKey::Key("awskms:///alias/argflow".to_string()))
+        );
+        assert!("".parse::<SigningKey>().is_err());
+        assert!(SigningKey::Keyless.needs_network());
+        assert!(SigningKey::Key("gcpkms://projects/p/keys/k".to_string()).needs_network());
+        assert!(!SigningKey::Key("cosign.key".to_string()).needs_network());
+    }
+
+    #[test]
+    fn test_input_digest_matches_sha256sum_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("pkg")).unwrap();
+        std::fs::write(dir.path().join("main.go"), "package main\n").unwrap();
+        std::fs::write(dir.path().join("pkg/a.go"), "package pkg\n").unwrap();
+
+        let files = vec![dir.path().join("pkg/a.go"), dir.path().join("main.go")];
+        let digest = digest_inputs(dir.path(), &files).unwrap();
+
+        let manifest = format!(
+            "{:x}  main.go\n{:x}  pkg/a.go\n",
+            Sha256::digest(b"package main\n"),
+            Sha256::digest(b"package pkg\n")
+        );
+        assert_eq!(digest.files, 2);
+        assert_eq!(
+            digest.sha256,
+            format!("{:x}", Sha256::digest(manifest.as_bytes()))
+        );
+
+        let moved = tempfile::tempdir().unwrap();
+        std::fs::create_dir(moved.path().join("pkg")).unwrap();
+        std::fs::write(moved.path().join("main.go"), "package main\n").unwrap();
+        std::fs::write(moved.path().join("pkg/a.go"), "package pkg\n").unwrap();
+        let files = vec![moved.path().join("main.go"), moved.path().join("pkg/a.go")];
+        assert_eq!(digest_inputs(moved.path(), &files).unwrap(), digest);
+    }
+
+    #[test]
+    fn test_sibling_paths() {
+        assert_eq!(
+            sibling(Path::new("out/report.json"), "sigstore.json"),
+            PathBuf::from("out/report.json.sigstore.json")
+        );
+    }
+}
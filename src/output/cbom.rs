@@ -0,0 +1,388 @@
+//! CycloneDX cryptography bill of materials (CBOM) for a report.
+//!
+//! Every algorithm the findings use is a `cryptographic-asset` component,
+//! with the calls evidencing it as occurrences. The project, or each
+//! scanned path of a multi-path report, and every dependency with findings
+//! are components too, and the `dependencies` graph links each of them to
+//! the assets its files use, so "which component uses 3DES" is answered by
+//! the BOM alone.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::{Finding, JsonOutput, ReportMetadata, ToolInfo};
+
+pub const SPEC_VERSION: &str = "1.6";
+
+/// Reference of the project's component when no paths are listed.
+const APPLICATION_REF: &str = "application";
+
+/// Crypto functions CycloneDX knows; others are reported as `other`.
+const CRYPTO_FUNCTIONS: &[&str] = &[
+    "generate",
+    "keygen",
+    "encrypt",
+    "decrypt",
+    "digest",
+    "tag",
+    "keyderive",
+    "sign",
+    "verify",
+    "encapsulate",
+    "decapsulate",
+];
+
+/// Primitives CycloneDX knows; others are reported as `other`.
+const PRIMITIVES: &[&str] = &[
+    "drbg",
+    "mac",
+    "block-cipher",
+    "stream-cipher",
+    "signature",
+    "hash",
+    "pke",
+    "xof",
+    "kdf",
+    "key-agree",
+    "kem",
+    "ae",
+    "combiner",
+];
+
+/// An algorithm and the calls using it.
+#[derive(Debug, Default)]
+struct Asset {
+    name: String,
+    primitives: BTreeSet<String>,
+    functions: BTreeSet<String>,
+    occurrences: Vec<Value>,
+}
+
+/// Collects findings, one at a time so a spooled report need not be held
+/// in memory, into a CBOM.
+#[derive(Debug, Default)]
+pub struct CbomBuilder {
+    /// Scanned paths, each an application of its own.
+    roots: Vec<String>,
+    assets: BTreeMap<String, Asset>,
+    /// Dependencies with findings.
+    libraries: BTreeSet<String>,
+    /// Assets each component's files use, by component reference.
+    uses: BTreeMap<String, BTreeSet<String>>,
+    metadata: Option<ReportMetadata>,
+}
+
+impl CbomBuilder {
+    /// A builder for a report of `roots`, the paths of a multi-path scan,
+    /// or none for a single project.
+    pub fn new(roots: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            roots: roots.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Records the producing tool, rules and grammars of `metadata` in the
+    /// BOM's metadata, the rules, grammars and any timings as `argflow:`
+    /// properties.
+    pub fn with_metadata(mut self, metadata: ReportMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn add(&mut self, finding: &Finding) {
+        let name = finding
+            .algorithm
+            .clone()
+            .unwrap_or_else(|| finding.full_name.clone());
+        let asset_ref = format!("crypto/algorithm/{name}");
+        let asset = self
+            .assets
+            .entry(asset_ref.clone())
+            .or_insert_with(|| Asset {
+                name,
+                ..Asset::default()
+            });
+        if let Some(primitive) = &finding.primitive {
+            asset
+                .primitives
+                .insert(cdx_primitive(primitive).to_string());
+        }
+        if let Some(operation) = &finding.operation {
+            asset.functions.insert(cdx_function(operation).to_string());
+        }
+        asset.occurrences.push(json!({
+            "location": finding.file,
+            "line": finding.line,
+            "symbol": finding.full_name,
+        }));
+
+        let component = match &finding.dependency {
+            Some(dependency) => {
+                self.libraries.insert(dependency.clone());
+                library_ref(dependency)
+            }
+            None => self.application_of(&finding.file),
+        };
+        self.uses.entry(component).or_default().insert(asset_ref);
+    }
+
+    /// The application `file` belongs to: the longest scanned path it lies
+    /// in.
+    fn application_of(&self, file: &str) -> String {
+        self.roots
+            .iter()
+            .filter(|root| Path::new(file).starts_with(root))
+            .max_by_key(|root| root.len())
+            .or(self.roots.first())
+            .map_or_else(|| APPLICATION_REF.to_string(), |root| application_ref(root))
+    }
+
+    pub fn build(self) -> Value {
+        let applications: Vec<(String, String)> = if self.roots.is_empty() {
+            vec![(APPLICATION_REF.to_string(), APPLICATION_REF.to_string())]
+        } else {
+            self.roots
+                .iter()
+                .map(|root| (application_ref(root), root.clone()))
+                .collect()
+        };
+
+        let mut components: Vec<Value> = applications
+            .iter()
+            .map(|(bom_ref, name)| json!({"type": "application", "bom-ref": bom_ref, "name": name}))
+            .collect();
+        components.extend(
+            self.libraries
+                .iter()
+                .map(|name| json!({"type": "library", "bom-ref": library_ref(name), "name": name})),
+        );
+        components.extend(self.assets.iter().map(|(bom_ref, asset)| {
+            let primitive = match asset.primitives.len() {
+                1 => asset.primitives.first().cloned(),
+                _ => None,
+            };
+            json!({
+                "type": "cryptographic-asset",
+                "bom-ref": bom_ref,
+                "name": asset.name,
+                "cryptoProperties": {
+                    "assetType": "algorithm",
+                    "algorithmProperties": {
+                        "primitive": primitive.as_deref().unwrap_or("unknown"),
+                        "cryptoFunctions": asset.functions,
+                    },
+                },
+                "evidence": {"occurrences": asset.occurrences},
+            })
+        }));
+
+        // Applications depend on the libraries, and every component on the
+        // assets its files use
+        let libraries: Vec<String> = self
+            .libraries
+            .iter()
+            .map(|name| library_ref(name))
+            .collect();
+        let mut dependencies: Vec<Value> = applications
+            .iter()
+            .map(|(bom_ref, _)| {
+                let mut depends_on = libraries.clone();
+                depends_on.extend(self.uses.get(bom_ref).into_iter().flatten().cloned());
+                json!({"ref": bom_ref, "dependsOn": depends_on})
+            })
+            .collect();
+        dependencies.extend(libraries.iter().map(|bom_ref| {
+            let depends_on: Vec<&String> = self.uses.get(bom_ref).into_iter().flatten().collect();
+            json!({"ref": bom_ref, "dependsOn": depends_on})
+        }));
+
+        let tool = self
+            .metadata
+            .as_ref()
+            .map_or_else(ToolInfo::default, |metadata| metadata.tool.clone());
+        let mut metadata = json!({
+            "tools": {"components": [{
+                "type": "application",
+                "name": tool.name,
+                "version": tool.version,
+            }]},
+        });
+        let properties = self.metadata.as_ref().map(properties).unwrap_or_default();
+        if !properties.is_empty() {
+            metadata["properties"] = json!(properties);
+        }
+        if let [(bom_ref, name)] = applications.as_slice() {
+            metadata["component"] =
+                json!({"type": "application", "bom-ref": bom_ref, "name": name});
+        }
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": SPEC_VERSION,
+            "version": 1,
+            "metadata": metadata,
+            "components": components,
+            "dependencies": dependencies,
+        })
+    }
+}
+
+/// The CBOM of `output`.
+pub fn build_cbom(output: &JsonOutput) -> Value {
+    let mut builder = CbomBuilder::new(output.roots.iter().map(|root| root.path.clone()));
+    if let Some(metadata) = &output.metadata {
+        builder = builder.with_metadata(metadata.clone());
+    }
+    for finding in &output.findings {
+        builder.add(finding);
+    }
+    builder.build()
+}
+
+/// `argflow:rules:*` and `argflow:grammar:<crate>` name-value properties.
+fn properties(metadata: &ReportMetadata) -> Vec<Value> {
+    let mut properties = Vec::new();
+    if let Some(rules) = &metadata.rules {
+        properties.push(json!({"name": "argflow:rules:sha256", "value": rules.sha256}));
+        for version in &rules.versions {
+            properties.push(json!({"name": "argflow:rules:version", "value": version}));
+        }
+    }
+    properties.extend(metadata.grammars.iter().map(
+        |(name, version)| json!({"name": format!("argflow:grammar:{name}"), "value": version}),
+    ));
+    if let Some(timings) = &metadata.timings {
+        properties.extend(timings.total.phases().map(
+            |(phase, ms)| json!({"name": format!("argflow:timings:{phase}"), "value": ms.to_string()}),
+        ));
+    }
+    properties
+}
+
+fn application_ref(root: &str) -> String {
+    format!("application/{root}")
+}
+
+fn library_ref(name: &str) -> String {
+    format!("library/{name}")
+}
+
+/// The CycloneDX crypto function for an operation: `hash` is `digest`.
+fn cdx_function(operation: &str) -> &str {
+    match operation {
+        "hash" => "digest",
+        operation if CRYPTO_FUNCTIONS.contains(&operation) => operation,
+        _ => "other",
+    }
+}
+
+fn cdx_primitive(primitive: &str) -> &str {
+    if PRIMITIVES.contains(&primitive) {
+        primitive
+    } else {
+        "other"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RootSummary;
+    use crate::test_support;
+
+    fn finding(file: &str, algorithm: &str, dependency: Option<&str>) -> Finding {
+        Finding {
+            line: 7,
+            algorithm: Some(algorithm.to_string()),
+            operation: Some("encrypt".to_string()),
+            primitive: Some("block-cipher".to_string()),
+            dependency: dependency.map(str::to_string),
+            ..test_support::finding(file, 7, "cipher.NewTripleDESCipher")
+        }
+    }
+
+    fn depends_on<'a>(cbom: &'a Value, bom_ref: &str) -> Vec<&'a str> {
+        cbom["dependencies"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|dependency| dependency["ref"] == bom_ref)
+            .unwrap()["dependsOn"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r.as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_components_depend_on_the_assets_their_files_use() {
+        let output = test_support::report(
+            vec![
+                finding("main.go", "AES", None),
+                finding("vendor/legacy/des.go", "3DES", Some("example.com/legacy")),
+                finding("vendor/legacy/aes.go", "AES", Some("example.com/legacy")),
+            ],
+            Vec::new(),
+        );
+
+        let cbom = build_cbom(&output);
+
+        assert_eq!(cbom["bomFormat"], "CycloneDX");
+        assert_eq!(cbom["specVersion"], "1.6");
+        assert_eq!(cbom["metadata"]["component"]["bom-ref"], "application");
+        assert_eq!(
+            depends_on(&cbom, "application"),
+            vec!["library/example.com/legacy", "crypto/algorithm/AES"]
+        );
+        assert_eq!(
+            depends_on(&cbom, "library/example.com/legacy"),
+            vec!["crypto/algorithm/3DES", "crypto/algorithm/AES"]
+        );
+
+        let des = &cbom["components"][2];
+        assert_eq!(des["type"], "cryptographic-asset");
+        assert_eq!(des["name"], "3DES");
+        assert_eq!(
+            des["cryptoProperties"]["algorithmProperties"]["primitive"],
+            "block-cipher"
+        );
+        assert_eq!(
+            des["evidence"]["occurrences"],
+            json!([{"location": "vendor/legacy/des.go", "line": 7, "symbol": "cipher.NewTripleDESCipher"}])
+        );
+    }
+
+    #[test]
+    fn test_each_scanned_path_is_an_application() {
+        let report = JsonOutput::default();
+        let output = JsonOutput {
+            roots: vec![
+                RootSummary::new("api", "go", &report),
+                RootSummary::new("web", "javascript", &report),
+            ],
+            ..test_support::report(vec![finding("web/src/hash.js", "MD5", None)], Vec::new())
+        };
+
+        let cbom = build_cbom(&output);
+
+        assert!(cbom["metadata"].get("component").is_none());
+        assert!(depends_on(&cbom, "application/api").is_empty());
+        assert_eq!(
+            depends_on(&cbom, "application/web"),
+            vec!["crypto/algorithm/MD5"]
+        );
+    }
+
+    #[test]
+    fn test_operations_map_to_cyclonedx_functions() {
+        assert_eq!(cdx_function("hash"), "digest");
+        assert_eq!(cdx_function("keyderive"), "keyderive");
+        assert_eq!(cdx_function("wrap"), "other");
+        assert_eq!(cdx_primitive("kdf"), "kdf");
+        assert_eq!(cdx_primitive("rng"), "other");
+    }
+}
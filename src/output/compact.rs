@@ -0,0 +1,214 @@
+//! `--format compact`: one line per finding in the
+//! `path:line:col: severity: message [rule]` shape compiler errors use, so
+//! editor problem matchers (Vim `errorformat`, Emacs compilation mode,
+//! VS Code `$gcc`) pick the results up without configuration.
+//!
+//! Weaknesses are `error`, `warning` or `info` by severity, and password
+//! hashing calls below the policy are warnings. Every other finding and
+//! config is an `info` line, so the output is also a crypto inventory.
+//! A line whose rule or classification has a guidance page ends with it,
+//! as `<uri>`.
+
+use std::fmt;
+use std::io::Write;
+
+use crate::policy::{PasswordHashCheck, PolicyStatus, Severity, Weakness};
+
+use super::{ConfigFinding, Finding, JsonOutput};
+
+/// One line of compact output.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompactLine {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub severity: &'static str,
+    pub message: String,
+    pub rule: String,
+    pub help_uri: Option<String>,
+}
+
+impl fmt::Display for CompactLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {} [{}]",
+            self.file, self.line, self.column, self.severity, self.message, self.rule
+        )?;
+        match &self.help_uri {
+            Some(uri) => write!(f, " <{uri}>"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<&Finding> for CompactLine {
+    fn from(finding: &Finding) -> Self {
+        let message = match &finding.algorithm {
+            Some(algorithm) => format!("{} uses {algorithm}", finding.full_name),
+            None => finding.full_name.clone(),
+        };
+        Self {
+            file: finding.file.clone(),
+            line: finding.line,
+            column: finding.column,
+            severity: "info",
+            message,
+            rule: finding
+                .finding_type
+                .clone()
+                .unwrap_or_else(|| "crypto_call".to_string()),
+            help_uri: finding.help_uri.clone(),
+        }
+    }
+}
+
+impl From<&ConfigFinding> for CompactLine {
+    fn from(config: &ConfigFinding) -> Self {
+        let fields: Vec<&str> = config
+            .fields
+            .iter()
+            .map(|f| f.field_name.as_str())
+            .collect();
+        Self {
+            file: config.file.clone(),
+            line: config.line,
+            column: config.column,
+            severity: "info",
+            message: format!("{} sets {}", config.full_type, fields.join(", ")),
+            rule: "config".to_string(),
+            help_uri: None,
+        }
+    }
+}
+
+impl From<&Weakness> for CompactLine {
+    fn from(weakness: &Weakness) -> Self {
+        Self {
+            file: weakness.file.clone(),
+            line: weakness.line,
+            column: weakness.column,
+            severity: match weakness.severity {
+                Severity::High => "error",
+                Severity::Medium => "warning",
+                Severity::Low => "info",
+            },
+            message: weakness.message.clone(),
+            rule: weakness.rule.clone(),
+            help_uri: weakness.help_uri.clone(),
+        }
+    }
+}
+
+impl CompactLine {
+    /// A line for `check` when a cost factor is below the policy.
+    fn from_password_check(check: &PasswordHashCheck) -> Option<Self> {
+        let failed: Vec<String> = check
+            .parameters
+            .iter()
+            .filter(|p| p.status == PolicyStatus::Fail)
+            .map(|p| {
+                let lowest = p.values.iter().min().copied().unwrap_or_default();
+                format!("{} {lowest} below {}", p.name, p.minimum)
+            })
+            .collect();
+        if failed.is_empty() {
+            return None;
+        }
+        Some(Self {
+            file: check.file.clone(),
+            line: check.line,
+            column: check.column,
+            severity: "warning",
+            message: format!(
+                "{} {}: {}",
+                format!("{:?}", check.scheme).to_lowercase(),
+                check.function,
+                failed.join(", ")
+            ),
+            rule: "password_storage".to_string(),
+            help_uri: None,
+        })
+    }
+}
+
+/// The lines for the results of one file or report, ordered by location.
+pub fn compact_lines(
+    findings: &[Finding],
+    configs: &[ConfigFinding],
+    weaknesses: &[Weakness],
+    password_storage: &[PasswordHashCheck],
+) -> Vec<CompactLine> {
+    let mut lines: Vec<CompactLine> = weaknesses
+        .iter()
+        .map(CompactLine::from)
+        .chain(
+            password_storage
+                .iter()
+                .filter_map(CompactLine::from_password_check),
+        )
+        .chain(findings.iter().map(CompactLine::from))
+        .chain(configs.iter().map(CompactLine::from))
+        .collect();
+    lines.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    lines
+}
+
+/// `output` as compact lines, without a trailing newline.
+pub fn render_compact(output: &JsonOutput) -> String {
+    compact_lines(
+        &output.findings,
+        &output.configs,
+        &output.weaknesses,
+        &output.password_storage,
+    )
+    .iter()
+    .map(ToString::to_string)
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+pub(super) fn write_lines<W: Write>(out: &mut W, lines: &[CompactLine]) -> std::io::Result<()> {
+    lines.iter().try_for_each(|line| writeln!(out, "{line}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn finding(line: usize, algorithm: Option<&str>) -> Finding {
+        Finding {
+            column: 9,
+            algorithm: algorithm.map(str::to_string),
+            finding_type: Some("hash".to_string()),
+            ..test_support::finding("src/auth.go", line, "crypto/md5.Sum")
+        }
+    }
+
+    #[test]
+    fn test_compact_lines() {
+        let weakness = Weakness {
+            line: 12,
+            column: 9,
+            message: "MD5 is broken".to_string(),
+            help_uri: Some("https://wiki.example.com/crypto/weak-hash".to_string()),
+            ..test_support::weakness("src/auth.go", "weak_hash", Severity::Medium)
+        };
+        let lines = compact_lines(
+            &[finding(12, Some("MD5")), finding(3, None)],
+            &[],
+            &[weakness],
+            &[],
+        );
+        let rendered: Vec<String> = lines.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "src/auth.go:3:9: info: crypto/md5.Sum [hash]",
+                "src/auth.go:12:9: warning: MD5 is broken [weak_hash] <https://wiki.example.com/crypto/weak-hash>",
+                "src/auth.go:12:9: info: crypto/md5.Sum uses MD5 [hash]",
+            ]
+        );
+    }
+}
@@ -0,0 +1,485 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::CompareFormat;
+use crate::policy::risk_score;
+
+use super::fingerprint::ensure_fingerprints;
+use super::{ConfigFinding, Finding, JsonOutput};
+
+/// Differences between two reports, matched by fingerprint.
+///
+/// A finding that only moved to another line is unchanged. One whose
+/// algorithm or argument values differ (e.g. PBKDF2 iterations raised from
+/// 10000 to 600000) is reported in `changed`.
+#[derive(Debug, Default, Serialize)]
+pub struct ReportDiff {
+    pub added: Vec<Finding>,
+    pub removed: Vec<Finding>,
+    pub changed: Vec<ChangedEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_configs: Vec<ConfigFinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_configs: Vec<ConfigFinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_configs: Vec<ChangedEntry>,
+    /// Risk scores of the old and new report, recomputed from their
+    /// weaknesses so reports without a score compare too.
+    pub risk_score: RiskScoreChange,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RiskScoreChange {
+    pub old: u32,
+    pub new: u32,
+}
+
+/// A finding or config present in both reports with different values.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedEntry {
+    pub fingerprint: String,
+    pub file: String,
+    /// `full_name` of a finding or `full_type` of a config.
+    pub name: String,
+    pub old_line: usize,
+    pub line: usize,
+    pub changes: Vec<ValueChange>,
+}
+
+/// One differing value: `algorithm`, `parameters.<arg>` or `fields.<name>`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueChange {
+    pub field: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+/// Compares `old` against `new`. Reports without fingerprints (written by
+/// older versions) get them assigned first.
+pub fn compare_reports(mut old: JsonOutput, mut new: JsonOutput) -> ReportDiff {
+    ensure_fingerprints(&mut old);
+    ensure_fingerprints(&mut new);
+    let risk_score = RiskScoreChange {
+        old: risk_score(&old.weaknesses),
+        new: risk_score(&new.weaknesses),
+    };
+
+    let (added, removed, changed) = diff(
+        old.findings,
+        new.findings,
+        |f| &f.fingerprint,
+        |old, new| ChangedEntry {
+            fingerprint: new.fingerprint.clone(),
+            file: new.file.clone(),
+            name: new.full_name.clone(),
+            old_line: old.line,
+            line: new.line,
+            changes: finding_changes(old, new),
+        },
+    );
+    let (added_configs, removed_configs, changed_configs) = diff(
+        old.configs,
+        new.configs,
+        |c| &c.fingerprint,
+        |old, new| ChangedEntry {
+            fingerprint: new.fingerprint.clone(),
+            file: new.file.clone(),
+            name: new.full_type.clone(),
+            old_line: old.line,
+            line: new.line,
+            changes: config_changes(old, new),
+        },
+    );
+
+    ReportDiff {
+        added,
+        removed,
+        changed,
+        added_configs,
+        removed_configs,
+        changed_configs,
+        risk_score,
+    }
+}
+
+fn diff<T>(
+    old: Vec<T>,
+    new: Vec<T>,
+    fingerprint: impl Fn(&T) -> &String,
+    compare: impl Fn(&T, &T) -> ChangedEntry,
+) -> (Vec<T>, Vec<T>, Vec<ChangedEntry>) {
+    let mut old_by_fingerprint: HashMap<String, T> = old
+        .into_iter()
+        .map(|item| (fingerprint(&item).clone(), item))
+        .collect();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for item in new {
+        match old_by_fingerprint.remove(fingerprint(&item)) {
+            Some(previous) => {
+                let entry = compare(&previous, &item);
+                if !entry.changes.is_empty() {
+                    changed.push(entry);
+                }
+            }
+            None => added.push(item),
+        }
+    }
+
+    let removed = old_by_fingerprint.into_values().collect();
+    (added, removed, changed)
+}
+
+fn finding_changes(old: &Finding, new: &Finding) -> Vec<ValueChange> {
+    let mut changes = Vec::new();
+    if old.algorithm != new.algorithm {
+        changes.push(ValueChange {
+            field: "algorithm".to_string(),
+            old: old.algorithm.clone().map(serde_json::Value::String),
+            new: new.algorithm.clone().map(serde_json::Value::String),
+        });
+    }
+    changes.extend(map_changes("parameters", &old.parameters, &new.parameters));
+    changes
+}
+
+fn config_changes(old: &ConfigFinding, new: &ConfigFinding) -> Vec<ValueChange> {
+    let fields = |config: &ConfigFinding| -> HashMap<String, serde_json::Value> {
+        config
+            .fields
+            .iter()
+            .map(|f| (f.field_name.clone(), f.value.clone()))
+            .collect()
+    };
+    map_changes("fields", &fields(old), &fields(new))
+}
+
+fn map_changes(
+    prefix: &str,
+    old: &HashMap<String, serde_json::Value>,
+    new: &HashMap<String, serde_json::Value>,
+) -> Vec<ValueChange> {
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    names
+        .into_iter()
+        .filter(|name| old.get(*name) != new.get(*name))
+        .map(|name| ValueChange {
+            field: format!("{prefix}.{name}"),
+            old: old.get(name).cloned(),
+            new: new.get(name).cloned(),
+        })
+        .collect()
+}
+
+impl ReportDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.added_configs.is_empty()
+            && self.removed_configs.is_empty()
+            && self.changed_configs.is_empty()
+    }
+
+    /// Sorts every list by file and line so output is reproducible.
+    fn sort(&mut self) {
+        let finding_key = |f: &Finding| (f.file.clone(), f.line, f.column);
+        let config_key = |c: &ConfigFinding| (c.file.clone(), c.line, c.column);
+        let entry_key = |e: &ChangedEntry| (e.file.clone(), e.line);
+        self.added.sort_by_key(finding_key);
+        self.removed.sort_by_key(finding_key);
+        self.changed.sort_by_key(entry_key);
+        self.added_configs.sort_by_key(config_key);
+        self.removed_configs.sort_by_key(config_key);
+        self.changed_configs.sort_by_key(entry_key);
+    }
+
+    pub fn render(mut self, format: CompareFormat) -> Result<String> {
+        self.sort();
+        match format {
+            CompareFormat::Json => Ok(serde_json::to_string_pretty(&self)?),
+            CompareFormat::Text => Ok(self.to_text()),
+            CompareFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{} added, {} removed, {} changed, risk score {} -> {}",
+            self.added.len() + self.added_configs.len(),
+            self.removed.len() + self.removed_configs.len(),
+            self.changed.len() + self.changed_configs.len(),
+            self.risk_score.old,
+            self.risk_score.new,
+        );
+        for f in &self.added {
+            let _ = writeln!(out, "+ {}:{} {}", f.file, f.line, describe_finding(f));
+        }
+        for c in &self.added_configs {
+            let _ = writeln!(out, "+ {}:{} {}", c.file, c.line, c.full_type);
+        }
+        for f in &self.removed {
+            let _ = writeln!(out, "- {}:{} {}", f.file, f.line, describe_finding(f));
+        }
+        for c in &self.removed_configs {
+            let _ = writeln!(out, "- {}:{} {}", c.file, c.line, c.full_type);
+        }
+        for entry in self.changed.iter().chain(&self.changed_configs) {
+            let _ = writeln!(out, "~ {}:{} {}", entry.file, entry.line, entry.name);
+            for change in &entry.changes {
+                let _ = writeln!(
+                    out,
+                    "    {}: {} -> {}",
+                    change.field,
+                    display_value(change.old.as_ref()),
+                    display_value(change.new.as_ref()),
+                );
+            }
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::from("# Crypto findings comparison\n\n");
+        let _ = writeln!(out, "| | Count |\n|---|---|");
+        let _ = writeln!(
+            out,
+            "| Added | {} |",
+            self.added.len() + self.added_configs.len()
+        );
+        let _ = writeln!(
+            out,
+            "| Removed | {} |",
+            self.removed.len() + self.removed_configs.len()
+        );
+        let _ = writeln!(
+            out,
+            "| Changed | {} |",
+            self.changed.len() + self.changed_configs.len()
+        );
+        let _ = writeln!(
+            out,
+            "| Risk score | {} -> {} |",
+            self.risk_score.old, self.risk_score.new
+        );
+
+        let section = |out: &mut String, title: &str, rows: Vec<(String, usize, String)>| {
+            if rows.is_empty() {
+                return;
+            }
+            let _ = writeln!(
+                out,
+                "\n## {title}\n\n| File | Line | Finding |\n|---|---|---|"
+            );
+            for (file, line, what) in rows {
+                let _ = writeln!(out, "| `{file}` | {line} | {what} |");
+            }
+        };
+        let rows = |findings: &[Finding], configs: &[ConfigFinding]| {
+            findings
+                .iter()
+                .map(|f| (f.file.clone(), f.line, describe_finding_markdown(f)))
+                .chain(
+                    configs
+                        .iter()
+                        .map(|c| (c.file.clone(), c.line, format!("`{}`", c.full_type))),
+                )
+                .collect::<Vec<_>>()
+        };
+        section(&mut out, "Added", rows(&self.added, &self.added_configs));
+        section(
+            &mut out,
+            "Removed",
+            rows(&self.removed, &self.removed_configs),
+        );
+
+        let changed: Vec<&ChangedEntry> =
+            self.changed.iter().chain(&self.changed_configs).collect();
+        if !changed.is_empty() {
+            let _ = writeln!(
+                out,
+                "\n## Changed\n\n| File | Line | Finding | Field | Old | New |\n|---|---|---|---|---|---|"
+            );
+            for entry in changed {
+                for change in &entry.changes {
+                    let _ = writeln!(
+                        out,
+                        "| `{}` | {} | `{}` | `{}` | `{}` | `{}` |",
+                        entry.file,
+                        entry.line,
+                        entry.name,
+                        change.field,
+                        display_value(change.old.as_ref()),
+                        display_value(change.new.as_ref()),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+fn describe_finding(finding: &Finding) -> String {
+    match &finding.algorithm {
+        Some(algorithm) => format!("{} ({algorithm})", finding.full_name),
+        None => finding.full_name.clone(),
+    }
+}
+
+/// [`describe_finding`] in Markdown, with the algorithm (or else the name)
+/// linked to the finding's guidance page if it has one.
+fn describe_finding_markdown(finding: &Finding) -> String {
+    let link = |text: &str| match &finding.help_uri {
+        Some(uri) => format!("[{text}]({uri})"),
+        None => text.to_string(),
+    };
+    match &finding.algorithm {
+        Some(algorithm) => format!("{} ({})", finding.full_name, link(algorithm)),
+        None => link(&finding.full_name),
+    }
+}
+
+/// Shows a parameter as its resolved value, partial expression, or
+/// `<source>` when unresolved.
+fn display_value(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None => "(none)".to_string(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Object(map)) => match map.get("value") {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Null) | None => {
+                let source = map
+                    .get("source")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("unknown");
+                format!("<{source}>")
+            }
+            Some(other) => other.to_string(),
+        },
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn report(findings: Vec<Finding>) -> JsonOutput {
+        test_support::report(findings, Vec::new())
+    }
+
+    fn pbkdf2(line: usize, iterations: u64) -> Finding {
+        Finding {
+            column: 9,
+            algorithm: Some("PBKDF2".to_string()),
+            parameters: test_support::parameters(
+                serde_json::json!({"arg2": iterations, "arg3": 32}),
+            ),
+            ..test_support::finding("auth.go", line, "pbkdf2.Key")
+        }
+    }
+
+    fn md5(line: usize) -> Finding {
+        Finding {
+            column: 2,
+            algorithm: Some("MD5".to_string()),
+            ..test_support::finding("auth.go", line, "md5.Sum")
+        }
+    }
+
+    #[test]
+    fn test_parameter_change_on_same_fingerprint() {
+        let old = report(vec![pbkdf2(10, 10000), md5(20)]);
+        let new = report(vec![pbkdf2(14, 600000), md5(25)]);
+
+        let diff = compare_reports(old, new);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+
+        let changed = &diff.changed[0];
+        assert_eq!(changed.name, "pbkdf2.Key");
+        assert_eq!((changed.old_line, changed.line), (10, 14));
+        assert_eq!(
+            changed.changes,
+            vec![ValueChange {
+                field: "parameters.arg2".to_string(),
+                old: Some(10000.into()),
+                new: Some(600000.into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed() {
+        let old = report(vec![md5(20)]);
+        let new = report(vec![pbkdf2(10, 600000)]);
+
+        let diff = compare_reports(old, new);
+        assert_eq!(diff.added[0].full_name, "pbkdf2.Key");
+        assert_eq!(diff.removed[0].full_name, "md5.Sum");
+        assert!(diff.changed.is_empty());
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let old = report(vec![pbkdf2(10, 10000), md5(20)]);
+        let new = report(vec![pbkdf2(10, 600000)]);
+
+        let text = compare_reports(old, new)
+            .render(CompareFormat::Text)
+            .unwrap();
+        assert!(text.starts_with("0 added, 1 removed, 1 changed, risk score 0 -> 0\n"));
+        assert!(text.contains("- auth.go:20 md5.Sum (MD5)"));
+        assert!(text.contains("parameters.arg2: 10000 -> 600000"));
+
+        let old = report(vec![pbkdf2(10, 10000)]);
+        let new = report(vec![pbkdf2(10, 600000)]);
+        let markdown = compare_reports(old, new)
+            .render(CompareFormat::Markdown)
+            .unwrap();
+        assert!(markdown.contains(
+            "| `auth.go` | 10 | `pbkdf2.Key` | `parameters.arg2` | `10000` | `600000` |"
+        ));
+
+        let linked = Finding {
+            help_uri: Some("https://wiki.example.com/crypto/md5".to_string()),
+            ..md5(20)
+        };
+        let markdown = compare_reports(report(vec![]), report(vec![linked]))
+            .render(CompareFormat::Markdown)
+            .unwrap();
+        assert!(markdown
+            .contains("| `auth.go` | 20 | md5.Sum ([MD5](https://wiki.example.com/crypto/md5)) |"));
+    }
+
+    #[test]
+    fn test_display_value() {
+        assert_eq!(display_value(Some(&serde_json::json!(32))), "32");
+        assert_eq!(
+            display_value(Some(
+                &serde_json::json!({"value": "n * 2", "source": "partial_expression"})
+            )),
+            "n * 2"
+        );
+        assert_eq!(
+            display_value(Some(
+                &serde_json::json!({"value": null, "source": "function_parameter"})
+            )),
+            "<function_parameter>"
+        );
+        assert_eq!(
+            display_value(Some(&serde_json::json!("SHA-256"))),
+            "SHA-256"
+        );
+        assert_eq!(display_value(None), "(none)");
+    }
+}
@@ -1,11 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::classifier::RulesClassifier;
-use crate::engine::Value;
+use crate::classifier::{hash_usage, Confidence, RulesClassifier, UsageContext};
+#[cfg(feature = "discovery")]
+use crate::discovery::languages::go::artifacts::GoArtifact;
+use crate::engine::{Evidence, UnresolvedSource, Value};
 use crate::scanner::{ConfigFinding as ScannerConfigFinding, Finding as ScannerFinding};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
     pub file: String,
     pub line: usize,
@@ -24,11 +26,56 @@ pub struct Finding {
     pub operation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primitive: Option<String>,
+    /// Where key material lives, e.g. `external_kms` for calls into a key
+    /// management service or HSM rather than in-process keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material_source: Option<String>,
+    /// For hash calls, what the hash is used for when the names around the
+    /// call tell, see [`crate::classifier::hash_usage`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_context: Option<UsageContext>,
+    /// Namespace of the rules whose mapping produced the finding, e.g.
+    /// `bundled` or a rules file's `namespace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_source: Option<String>,
+    /// Guidance page for the finding, the `helpUri` of its classification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    /// How sure the finding is, when lower than for a mapped call: `low`
+    /// for `dynamic_crypto_dispatch`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+    /// For `dynamic_crypto_dispatch`, the value of the looked-up name, e.g.
+    /// `"md5"` for `getattr(hashlib, "md5")`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_name: Option<serde_json::Value>,
     pub parameters: HashMap<String, serde_json::Value>,
+    /// Keyword of each parameter passed by name, e.g. `arg3` -> `iterations`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub keywords: HashMap<String, String>,
+    /// For each resolved parameter traced through declarations, where
+    /// those are, e.g. `arg2` -> the `ITERATIONS` constant at `kdf.go:8`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub evidence: HashMap<String, Vec<Evidence>>,
     pub raw_text: String,
+    /// The dependency whose files the call is in; `None` for the
+    /// project's own code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<String>,
+    /// Whether the call is in test code, see
+    /// [`crate::policy::is_test_file`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test_code: bool,
+    /// The workspace member package whose files the call is in, e.g.
+    /// `@shop/api` or the module path of a `go.work` member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    /// Stable identity across runs, see [`super::assign_fingerprints`].
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigFinding {
     pub file: String,
     pub line: usize,
@@ -40,10 +87,15 @@ pub struct ConfigFinding {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub import_path: Option<String>,
     pub fields: Vec<ConfigFieldValue>,
+    /// The service client call the settings belong to, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call: Option<String>,
     pub raw_text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigFieldValue {
     pub field_name: String,
     pub value: serde_json::Value,
@@ -51,6 +103,46 @@ pub struct ConfigFieldValue {
     pub classification_key: Option<String>,
 }
 
+/// A file that could not be analyzed but may contain crypto, reported so
+/// coverage gaps are explicit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnanalyzedArtifact {
+    pub file: String,
+    pub language: String,
+    pub kind: String,
+    pub reason: String,
+}
+
+/// A dependency left out of an `--include-deps` scan under a
+/// `--deps-budget` because nothing suggests it does cryptography. Packages
+/// turned down by name are never walked, so their size is unknown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedDependency {
+    pub package: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+}
+
+#[cfg(feature = "discovery")]
+impl From<&GoArtifact> for UnanalyzedArtifact {
+    fn from(artifact: &GoArtifact) -> Self {
+        Self {
+            file: artifact.path.to_string_lossy().to_string(),
+            language: "go".to_string(),
+            kind: artifact.kind.as_str().to_string(),
+            reason: artifact.kind.reason().to_string(),
+        }
+    }
+}
+
+/// Finding type of a crypto function looked up by name, whose target is
+/// only known when the name resolves.
+pub const DYNAMIC_DISPATCH: &str = "dynamic_crypto_dispatch";
+
 impl Finding {
     pub fn from_scanner_finding(call: &ScannerFinding, classifier: &RulesClassifier) -> Self {
         let classification = crate::classifier::classify_call(call, classifier);
@@ -66,6 +158,39 @@ impl Finding {
             })
             .collect();
 
+        let evidence = call
+            .arguments
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_resolved && !v.evidence.is_empty())
+            .map(|(i, v)| (format!("arg{i}"), v.evidence.clone()))
+            .collect();
+
+        let is_hash = classification.finding_type == "hash"
+            || classification.primitive.as_deref() == Some("hash");
+        let usage_context = is_hash.then(|| hash_usage(&call.site)).flatten();
+
+        let rule_source = classifier
+            .rule_source(
+                call.import_path.as_deref(),
+                call.package.as_deref().unwrap_or(""),
+                &call.function_name,
+            )
+            .map(|source| source.namespace.clone());
+
+        let keywords = call
+            .keywords
+            .iter()
+            .enumerate()
+            .filter_map(|(i, k)| Some((format!("arg{i}"), k.clone()?)))
+            .collect();
+
+        let finding_type = match &call.dynamic_name {
+            Some(_) => Some(DYNAMIC_DISPATCH.to_string()),
+            None if classification.finding_type.is_empty() => None,
+            None => Some(classification.finding_type),
+        };
+
         Finding {
             file: call.file_path.clone(),
             line: call.line,
@@ -75,20 +200,72 @@ impl Finding {
             import_path: call.import_path.clone(),
             full_name: call.full_name(),
             algorithm: classification.algorithm,
-            finding_type: if classification.finding_type.is_empty() {
-                None
-            } else {
-                Some(classification.finding_type)
-            },
+            finding_type,
             operation: if classification.operation.is_empty() {
                 None
             } else {
                 Some(classification.operation)
             },
             primitive: classification.primitive,
+            material_source: classification.material_source,
+            usage_context,
+            rule_source,
+            help_uri: classification.help_uri,
+            confidence: call.dynamic_name.as_ref().map(|_| Confidence::Low),
+            dynamic_name: call.dynamic_name.as_ref().map(value_to_json),
             parameters,
+            keywords,
+            evidence,
             raw_text: call.raw_text.clone(),
+            dependency: None,
+            test_code: false,
+            member: None,
+            fingerprint: String::new(),
+        }
+    }
+}
+
+impl Finding {
+    /// Whether this is a call to one of `functions` imported from one of
+    /// `modules`. `node:` module specifiers and Rust paths below a module
+    /// also match.
+    pub fn calls(&self, modules: &[&str], functions: &[&str]) -> bool {
+        let Some(import_path) = self.import_path.as_deref() else {
+            return false;
+        };
+        let module = import_path.strip_prefix("node:").unwrap_or(import_path);
+        functions.contains(&self.function.as_str())
+            && modules.iter().any(|m| {
+                module == *m
+                    || module
+                        .strip_prefix(m)
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+    }
+
+    /// The argument passed as `keyword`, or else the one at `position` when
+    /// it was passed positionally.
+    pub fn argument(
+        &self,
+        position: Option<usize>,
+        keyword: Option<&str>,
+    ) -> Option<&serde_json::Value> {
+        if let Some(keyword) = keyword {
+            let by_keyword = self
+                .keywords
+                .iter()
+                .find(|(_, k)| k.as_str() == keyword)
+                .and_then(|(arg, _)| self.parameters.get(arg));
+            if by_keyword.is_some() {
+                return by_keyword;
+            }
+        }
+
+        let arg = format!("arg{}", position?);
+        if self.keywords.contains_key(&arg) {
+            return None;
         }
+        self.parameters.get(&arg)
     }
 }
 
@@ -113,7 +290,9 @@ impl ConfigFinding {
             package: config.package.clone(),
             import_path: config.import_path.clone(),
             fields,
+            call: config.call.clone(),
             raw_text: config.raw_text.clone(),
+            fingerprint: String::new(),
         }
     }
 }
@@ -146,9 +325,16 @@ fn value_to_json(value: &Value) -> serde_json::Value {
         }
     // Partial: return value with source
     } else if !value.expression.is_empty() {
+        let source = if value.source.is_empty()
+            || value.source == UnresolvedSource::PartiallyResolved.as_str()
+        {
+            "partial_expression"
+        } else {
+            &value.source
+        };
         serde_json::json!({
             "value": value.expression,
-            "source": "partial_expression"
+            "source": source
         })
     // Unresolved: return source only
     } else if !value.source.is_empty() {
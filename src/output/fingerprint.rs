@@ -0,0 +1,127 @@
+//! Stable finding identities.
+//!
+//! A fingerprint identifies "the nth call to `full_name` in `file`" rather
+//! than a line and column, so it survives unrelated edits that move code
+//! around and stays the same when only an argument value changes. Reports
+//! from separate runs can then be merged and compared finding by finding.
+
+use std::collections::HashMap;
+
+use crate::utils::stable_hash;
+
+use super::{ConfigFinding, Finding, JsonOutput};
+
+/// Fills in `fingerprint` for every finding.
+pub fn assign_fingerprints(findings: &mut [Finding]) {
+    assign(
+        findings,
+        |f| (f.file.as_str(), f.full_name.as_str(), f.line, f.column),
+        |f, fingerprint| f.fingerprint = fingerprint,
+    );
+}
+
+/// Fills in `fingerprint` for every config finding.
+pub fn assign_config_fingerprints(configs: &mut [ConfigFinding]) {
+    assign(
+        configs,
+        |c| (c.file.as_str(), c.full_type.as_str(), c.line, c.column),
+        |c, fingerprint| c.fingerprint = fingerprint,
+    );
+}
+
+/// Assigns fingerprints to a report written before they existed.
+pub(crate) fn ensure_fingerprints(report: &mut JsonOutput) {
+    if report.findings.iter().any(|f| f.fingerprint.is_empty()) {
+        assign_fingerprints(&mut report.findings);
+    }
+    if report.configs.iter().any(|c| c.fingerprint.is_empty()) {
+        assign_config_fingerprints(&mut report.configs);
+    }
+}
+
+fn assign<T>(
+    items: &mut [T],
+    key: impl Fn(&T) -> (&str, &str, usize, usize),
+    set: impl Fn(&mut T, String),
+) {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&a, &b| key(&items[a]).cmp(&key(&items[b])));
+
+    let mut ordinals: HashMap<(String, String), usize> = HashMap::new();
+    let fingerprints: Vec<(usize, String)> = order
+        .into_iter()
+        .map(|i| {
+            let (file, name, _, _) = key(&items[i]);
+            let ordinal = ordinals
+                .entry((file.to_string(), name.to_string()))
+                .or_default();
+            let fingerprint = fingerprint(file, name, *ordinal);
+            *ordinal += 1;
+            (i, fingerprint)
+        })
+        .collect();
+
+    for (i, fingerprint) in fingerprints {
+        set(&mut items[i], fingerprint);
+    }
+}
+
+fn fingerprint(file: &str, name: &str, ordinal: usize) -> String {
+    let key = format!("{file}\0{name}\0{ordinal}");
+    format!("{:016x}", stable_hash(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn finding(file: &str, full_name: &str, line: usize, raw_text: &str) -> Finding {
+        Finding {
+            raw_text: raw_text.to_string(),
+            ..test_support::finding(file, line, full_name)
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_survives_line_shifts_and_argument_changes() {
+        let mut before = vec![
+            finding(
+                "a.go",
+                "pbkdf2.Key",
+                10,
+                "pbkdf2.Key(pw, salt, 10000, 32, nil)",
+            ),
+            finding("a.go", "md5.Sum", 20, "md5.Sum(b)"),
+        ];
+        let mut after = vec![
+            finding("a.go", "md5.Sum", 42, "md5.Sum(b)"),
+            finding(
+                "a.go",
+                "pbkdf2.Key",
+                31,
+                "pbkdf2.Key(pw, salt, 600000, 32, nil)",
+            ),
+        ];
+        assign_fingerprints(&mut before);
+        assign_fingerprints(&mut after);
+
+        assert_eq!(before[0].fingerprint, after[1].fingerprint);
+        assert_eq!(before[1].fingerprint, after[0].fingerprint);
+        assert_eq!(before[0].fingerprint.len(), 16);
+    }
+
+    #[test]
+    fn test_repeated_calls_get_distinct_fingerprints() {
+        let mut findings = vec![
+            finding("a.go", "md5.Sum", 5, "md5.Sum(a)"),
+            finding("a.go", "md5.Sum", 3, "md5.Sum(b)"),
+            finding("b.go", "md5.Sum", 3, "md5.Sum(b)"),
+        ];
+        assign_fingerprints(&mut findings);
+
+        assert_ne!(findings[0].fingerprint, findings[1].fingerprint);
+        assert_ne!(findings[1].fingerprint, findings[2].fingerprint);
+        assert_eq!(findings[1].fingerprint, fingerprint("a.go", "md5.Sum", 0));
+    }
+}
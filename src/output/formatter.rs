@@ -1,20 +1,151 @@
-use anyhow::Result;
-use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
 use crate::classifier::RulesClassifier;
 use crate::cli::OutputFormat;
+use crate::policy::{
+    check_password_storage, check_weaknesses, risk_score, Fix, PasswordHashCheck, PasswordPolicy,
+    WaiverReport, Weakness,
+};
+use crate::scanner::binary::BinaryFinding;
+use crate::scanner::fallback::FallbackFinding;
 use crate::scanner::ScanResult;
 
-use super::{ConfigFinding, Finding};
+use super::compact::write_lines;
+use super::{
+    assign_config_fingerprints, assign_fingerprints, build_cbom, compact_lines, render_compact,
+    AgilityReport, CbomBuilder, ConfigFinding, Finding, FindingsOverflow, HotspotReport,
+    ImageSummary, ReportMetadata, ResultSpool, ScanError, ScanTimings, SkippedDependency,
+    SkippedFile, UnanalyzedArtifact,
+};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct JsonOutput {
+    /// The tool, rules and grammar versions that produced the report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ReportMetadata>,
     pub files_scanned: usize,
     pub total_findings: usize,
     pub total_configs: usize,
+    /// Set when the scan was cancelled: the report only covers the files
+    /// scanned before it stopped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// 0 to 100 from the weaknesses, see [`risk_score`].
+    #[serde(default)]
+    pub risk_score: u32,
+    /// Per-path totals when several `--path`s were scanned together; the
+    /// findings of all of them are listed below.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<RootSummary>,
+    /// The layers of a scanned container image, see `argflow image`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageSummary>,
     pub findings: Vec<Finding>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// Findings left out of `findings` by `--max-findings`, counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overflow: Option<FindingsOverflow>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub configs: Vec<ConfigFinding>,
+    /// Misconfigurations such as JWT `alg: none` or ECB mode, see
+    /// [`check_weaknesses`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weaknesses: Vec<Weakness>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unanalyzed: Vec<UnanalyzedArtifact>,
+    /// Dependencies not scanned because the dependency tree was over the
+    /// `--deps-budget`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_dependencies: Vec<SkippedDependency>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binary_findings: Vec<BinaryFinding>,
+    /// Pattern matches in files no grammar parses, with `--fallback-scan`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_findings: Vec<FallbackFinding>,
+    /// Password hashing calls checked against `--password-policy`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub password_storage: Vec<PasswordHashCheck>,
+    /// Edits fixing some findings, with `--suggest-fixes`, see
+    /// [`crate::policy::suggest_fixes`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+    /// Parameters left unresolved, counted by reason (`function_parameter`,
+    /// `external_input`, `dynamic_dispatch`, ...).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unresolved: BTreeMap<String, usize>,
+    /// Files skipped because they could not be read, with the reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ScanError>,
+    /// Every file found but not parsed, with why, with `--audit-coverage`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFile>,
+    /// Whether crypto calls go through abstraction layers, with `--agility`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agility: Option<AgilityReport>,
+    /// The files, functions and modules concentrating crypto calls, with
+    /// `--hotspots`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hotspots: Option<HotspotReport>,
+    /// Weaknesses left out by waivers in the config file, and the waivers
+    /// that have expired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waivers: Option<WaiverReport>,
+}
+
+impl JsonOutput {
+    /// Reads a JSON report written by an earlier scan.
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open report: {}", path.display()))?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to parse report: {}", path.display()))
+    }
+}
+
+/// Totals for one scanned path of a multi-path report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RootSummary {
+    pub path: String,
+    pub language: String,
+    pub files_scanned: usize,
+    pub total_findings: usize,
+    pub total_configs: usize,
+    pub risk_score: u32,
+}
+
+impl RootSummary {
+    pub fn new(path: &str, language: &str, report: &JsonOutput) -> Self {
+        Self {
+            path: path.to_string(),
+            language: language.to_string(),
+            files_scanned: report.files_scanned,
+            total_findings: report.total_findings,
+            total_configs: report.total_configs,
+            risk_score: report.risk_score,
+        }
+    }
+}
+
+/// Results reported alongside the source findings that do not come from
+/// parsing source files.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactReport {
+    pub unanalyzed: Vec<UnanalyzedArtifact>,
+    pub skipped_dependencies: Vec<SkippedDependency>,
+    pub binary_findings: Vec<BinaryFinding>,
+    pub fallback_findings: Vec<FallbackFinding>,
+    pub errors: Vec<ScanError>,
+    pub skipped_files: Vec<SkippedFile>,
+    pub agility: Option<AgilityReport>,
+    pub hotspots: Option<HotspotReport>,
+    /// Whether the scan was cancelled before every file was scanned.
+    pub partial: bool,
+    /// With `--timings`, the phases of the scan so far.
+    pub timings: Option<ScanTimings>,
 }
 
 pub struct OutputFormatter;
@@ -25,41 +156,619 @@ impl OutputFormatter {
         classifier: &RulesClassifier,
         format: OutputFormat,
     ) -> Result<String> {
-        let output = Self::build_output(results, classifier);
+        Self::format_with_artifacts(
+            results,
+            &ArtifactReport::default(),
+            classifier,
+            None,
+            format,
+        )
+    }
+
+    /// Formats a report with `artifacts`, adding a `password_storage`
+    /// section when a password policy is given.
+    pub fn format_with_artifacts(
+        results: &[ScanResult],
+        artifacts: &ArtifactReport,
+        classifier: &RulesClassifier,
+        password_policy: Option<&PasswordPolicy>,
+        format: OutputFormat,
+    ) -> Result<String> {
+        let output =
+            Self::build_output_with_artifacts(results, artifacts, classifier, password_policy);
+        Self::render(&output, format)
+    }
+
+    /// The report [`OutputFormatter::format_with_artifacts`] formats.
+    pub fn build_output_with_artifacts(
+        results: &[ScanResult],
+        artifacts: &ArtifactReport,
+        classifier: &RulesClassifier,
+        password_policy: Option<&PasswordPolicy>,
+    ) -> JsonOutput {
+        let mut output = Self::build_output(results, classifier);
+        output.unanalyzed = artifacts.unanalyzed.clone();
+        output.skipped_dependencies = artifacts.skipped_dependencies.clone();
+        output.binary_findings = artifacts.binary_findings.clone();
+        output.fallback_findings = artifacts.fallback_findings.clone();
+        output.errors = artifacts.errors.clone();
+        output.skipped_files = artifacts.skipped_files.clone();
+        output.agility = artifacts.agility.clone();
+        output.hotspots = artifacts.hotspots.clone();
+        output.partial = artifacts.partial;
+        if let Some(metadata) = &mut output.metadata {
+            metadata.timings = artifacts.timings.clone();
+        }
+        if let Some(policy) = password_policy {
+            output.password_storage = check_password_storage(&output.findings, policy);
+        }
+        output
+    }
 
+    pub fn render(output: &JsonOutput, format: OutputFormat) -> Result<String> {
         match format {
             OutputFormat::Json => Ok(serde_json::to_string_pretty(&output)?),
-            OutputFormat::Cbom => {
-                tracing::warn!("CBOM output not yet implemented, using JSON");
-                Ok(serde_json::to_string_pretty(&output)?)
-            }
+            OutputFormat::Cbom => Ok(serde_json::to_string_pretty(&build_cbom(output))?),
+            OutputFormat::Compact => Ok(render_compact(output)),
         }
     }
 
     pub fn build_output(results: &[ScanResult], classifier: &RulesClassifier) -> JsonOutput {
-        let findings: Vec<Finding> = results
+        let mut findings: Vec<Finding> = results
             .iter()
             .flat_map(|r| {
-                r.calls
-                    .iter()
-                    .map(|call| Finding::from_scanner_finding(call, classifier))
+                r.calls.iter().map(|call| Finding {
+                    dependency: r.dependency.clone(),
+                    test_code: r.test_code,
+                    member: r.member.clone(),
+                    ..Finding::from_scanner_finding(call, classifier)
+                })
             })
             .collect();
 
-        let configs: Vec<ConfigFinding> = results
+        let mut configs: Vec<ConfigFinding> = results
             .iter()
             .flat_map(|r| r.configs.iter().map(ConfigFinding::from_scanner_config))
             .collect();
 
+        assign_fingerprints(&mut findings);
+        assign_config_fingerprints(&mut configs);
+        let weaknesses = linked_weaknesses(&findings, &configs, classifier);
+        let risk_score = risk_score(&weaknesses);
+
         let total_findings = findings.len();
         let total_configs = configs.len();
+        let unresolved = unresolved_sources(&findings);
 
         JsonOutput {
+            metadata: Some(ReportMetadata::new(classifier)),
             files_scanned: results.len(),
             total_findings,
             total_configs,
+            partial: false,
+            risk_score,
+            roots: Vec::new(),
+            image: None,
             findings,
+            overflow: None,
             configs,
+            weaknesses,
+            unanalyzed: Vec::new(),
+            skipped_dependencies: Vec::new(),
+            binary_findings: Vec::new(),
+            fallback_findings: Vec::new(),
+            password_storage: Vec::new(),
+            fixes: Vec::new(),
+            unresolved,
+            errors: Vec::new(),
+            skipped_files: Vec::new(),
+            agility: None,
+            hotspots: None,
+            waivers: None,
+        }
+    }
+
+    /// Writes the same document as [`OutputFormatter::format`] from a spool,
+    /// holding only one file's results in memory at a time.
+    pub fn write_spooled<W: Write>(
+        spool: &mut ResultSpool,
+        artifacts: &ArtifactReport,
+        classifier: &RulesClassifier,
+        password_policy: Option<&PasswordPolicy>,
+        max_findings: Option<usize>,
+        format: OutputFormat,
+        out: &mut W,
+    ) -> Result<()> {
+        let mut remaining = max_findings.unwrap_or(usize::MAX);
+        if format == OutputFormat::Cbom {
+            let mut cbom =
+                CbomBuilder::default().with_metadata(spooled_metadata(artifacts, classifier));
+            spool.for_each(|result| {
+                let findings = result_findings(result, classifier);
+                let listed = findings.len().min(remaining);
+                remaining -= listed;
+                findings[..listed]
+                    .iter()
+                    .for_each(|finding| cbom.add(finding));
+                Ok(())
+            })?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&cbom.build())?)?;
+            return Ok(());
+        }
+        if format == OutputFormat::Compact {
+            spool.for_each(|result| {
+                let findings = result_findings(result, classifier);
+                let configs = result_configs(result);
+                let password_storage = match password_policy {
+                    Some(policy) => check_password_storage(&findings, policy),
+                    None => Vec::new(),
+                };
+                let weaknesses = linked_weaknesses(&findings, &configs, classifier);
+                let listed = findings.len().min(remaining);
+                remaining -= listed;
+                let lines = compact_lines(
+                    &findings[..listed],
+                    &configs,
+                    &weaknesses,
+                    &password_storage,
+                );
+                write_lines(out, &lines)
+            })?;
+            return Ok(());
+        }
+
+        let totals = spool.totals();
+        let metadata = serde_json::to_string_pretty(&spooled_metadata(artifacts, classifier))?;
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"metadata\": {},", metadata.replace('\n', "\n  "))?;
+        writeln!(out, "  \"files_scanned\": {},", totals.files)?;
+        writeln!(out, "  \"total_findings\": {},", totals.calls)?;
+        writeln!(out, "  \"total_configs\": {},", totals.configs)?;
+        if artifacts.partial {
+            writeln!(out, "  \"partial\": true,")?;
+        }
+        write!(out, "  \"findings\": [")?;
+
+        let mut first = true;
+        let mut unresolved: BTreeMap<String, usize> = BTreeMap::new();
+        let mut overflow = max_findings.map(FindingsOverflow::new);
+        spool.for_each(|result| {
+            let findings = result_findings(result, classifier);
+            for (source, count) in unresolved_sources(&findings) {
+                *unresolved.entry(source).or_default() += count;
+            }
+            let listed = findings.len().min(remaining);
+            remaining -= listed;
+            for finding in &findings[..listed] {
+                write_array_item(out, finding, &mut first)?;
+            }
+            if let Some(overflow) = &mut overflow {
+                findings[listed..].iter().for_each(|f| overflow.add(f));
+            }
+            Ok(())
+        })?;
+        close_array(out, first)?;
+
+        if totals.configs > 0 {
+            write!(out, ",\n  \"configs\": [")?;
+            let mut first = true;
+            spool.for_each(|result| {
+                for config in &result_configs(result) {
+                    write_array_item(out, config, &mut first)?;
+                }
+                Ok(())
+            })?;
+            close_array(out, first)?;
+        }
+
+        let mut weaknesses = Vec::new();
+        let mut password_storage = Vec::new();
+        let mut remaining = max_findings.unwrap_or(usize::MAX);
+        spool.for_each(|result| {
+            let findings = result_findings(result, classifier);
+            let linked = linked_weaknesses(&findings, &result_configs(result), classifier);
+            let listed = findings.len().min(remaining);
+            remaining -= listed;
+            if let Some(overflow) = &mut overflow {
+                let omitted: HashSet<&str> = findings[listed..]
+                    .iter()
+                    .map(|f| f.fingerprint.as_str())
+                    .collect();
+                linked
+                    .iter()
+                    .filter(|w| omitted.contains(w.finding.as_str()))
+                    .for_each(|w| overflow.add_weakness(w));
+            }
+            weaknesses.extend(linked);
+            if let Some(policy) = password_policy {
+                password_storage.extend(check_password_storage(&findings, policy));
+            }
+            Ok(())
+        })?;
+
+        write_array_field(out, "weaknesses", &weaknesses)?;
+        write_array_field(out, "unanalyzed", &artifacts.unanalyzed)?;
+        write_array_field(out, "skipped_dependencies", &artifacts.skipped_dependencies)?;
+        write_array_field(out, "binary_findings", &artifacts.binary_findings)?;
+        write_array_field(out, "fallback_findings", &artifacts.fallback_findings)?;
+        write_array_field(out, "password_storage", &password_storage)?;
+        write_array_field(out, "errors", &artifacts.errors)?;
+        write_array_field(out, "skipped_files", &artifacts.skipped_files)?;
+        write!(out, ",\n  \"risk_score\": {}", risk_score(&weaknesses))?;
+        if let Some(overflow) = overflow.filter(|o| o.omitted > 0) {
+            let json = serde_json::to_string_pretty(&overflow)?;
+            write!(out, ",\n  \"overflow\": {}", json.replace('\n', "\n  "))?;
+        }
+        if !unresolved.is_empty() {
+            let json = serde_json::to_string_pretty(&unresolved)?;
+            write!(out, ",\n  \"unresolved\": {}", json.replace('\n', "\n  "))?;
+        }
+        if let Some(agility) = &artifacts.agility {
+            let json = serde_json::to_string_pretty(agility)?;
+            write!(out, ",\n  \"agility\": {}", json.replace('\n', "\n  "))?;
+        }
+        if let Some(hotspots) = &artifacts.hotspots {
+            let json = serde_json::to_string_pretty(hotspots)?;
+            write!(out, ",\n  \"hotspots\": {}", json.replace('\n', "\n  "))?;
         }
+
+        writeln!(out, "\n}}")?;
+        Ok(())
+    }
+}
+
+/// Unresolved parameters of `findings` counted by the reason they were not
+/// resolved.
+pub(super) fn unresolved_sources(findings: &[Finding]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for finding in findings {
+        for value in finding.parameters.values() {
+            if let Some(source) = value.get("source").and_then(|s| s.as_str()) {
+                *counts.entry(source.to_string()).or_default() += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// The metadata of a streamed report, whose calls are classified while it
+/// is written: its timings have no classification or output time.
+fn spooled_metadata(artifacts: &ArtifactReport, classifier: &RulesClassifier) -> ReportMetadata {
+    ReportMetadata {
+        timings: artifacts.timings.clone(),
+        ..ReportMetadata::new(classifier)
+    }
+}
+
+fn result_findings(result: &ScanResult, classifier: &RulesClassifier) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = result
+        .calls
+        .iter()
+        .map(|call| Finding {
+            dependency: result.dependency.clone(),
+            test_code: result.test_code,
+            member: result.member.clone(),
+            ..Finding::from_scanner_finding(call, classifier)
+        })
+        .collect();
+    assign_fingerprints(&mut findings);
+    findings
+}
+
+/// The weaknesses of `findings` and `configs`, each with the guidance page
+/// the rules give for its rule.
+fn linked_weaknesses(
+    findings: &[Finding],
+    configs: &[ConfigFinding],
+    classifier: &RulesClassifier,
+) -> Vec<Weakness> {
+    let mut weaknesses = check_weaknesses(findings, configs);
+    for weakness in &mut weaknesses {
+        weakness.help_uri = classifier.help_uri(&weakness.rule).map(str::to_string);
+    }
+    weaknesses
+}
+
+fn result_configs(result: &ScanResult) -> Vec<ConfigFinding> {
+    let mut configs: Vec<ConfigFinding> = result
+        .configs
+        .iter()
+        .map(ConfigFinding::from_scanner_config)
+        .collect();
+    assign_config_fingerprints(&mut configs);
+    configs
+}
+
+fn write_array_item<W: Write, T: Serialize>(
+    out: &mut W,
+    item: &T,
+    first: &mut bool,
+) -> std::io::Result<()> {
+    if !*first {
+        write!(out, ",")?;
+    }
+    *first = false;
+
+    let json = serde_json::to_string_pretty(item)?;
+    for line in json.lines() {
+        write!(out, "\n    {line}")?;
+    }
+    Ok(())
+}
+
+fn write_array_field<W: Write, T: Serialize>(
+    out: &mut W,
+    name: &str,
+    items: &[T],
+) -> std::io::Result<()> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    write!(out, ",\n  \"{name}\": [")?;
+    let mut first = true;
+    for item in items {
+        write_array_item(out, item, &mut first)?;
+    }
+    close_array(out, first)
+}
+
+fn close_array<W: Write>(out: &mut W, empty: bool) -> std::io::Result<()> {
+    if empty {
+        write!(out, "]")
+    } else {
+        write!(out, "\n  ]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Value;
+    use crate::output::{cap_findings, PhaseTimings, SkipCategory};
+    use crate::scanner::binary::{scan_bytes, ArtifactType};
+    use crate::scanner::fallback::{self, FallbackPattern, FallbackRule};
+    use crate::scanner::Finding as ScannerFinding;
+
+    fn result_with_call(file: &str) -> ScanResult {
+        let mut result = ScanResult::new(file.to_string());
+        result.add_call(ScannerFinding {
+            file_path: file.to_string(),
+            line: 3,
+            column: 5,
+            function_name: "Key".to_string(),
+            package: Some("pbkdf2".to_string()),
+            import_path: Some("golang.org/x/crypto/pbkdf2".to_string()),
+            arguments: vec![
+                Value::unextractable("identifier"),
+                Value::unextractable("identifier"),
+                Value::resolved_int(4096),
+            ],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
+            raw_text: "pbkdf2.Key(pw, salt, 4096, 32, sha256.New)".to_string(),
+            language: "go".to_string(),
+        });
+        result
+    }
+
+    #[test]
+    fn test_spooled_output_matches_in_memory_output() {
+        let classifier = RulesClassifier::new();
+        let results = vec![result_with_call("a.go"), result_with_call("b.go")];
+
+        let artifacts = ArtifactReport {
+            unanalyzed: vec![UnanalyzedArtifact {
+                file: "sha_amd64.s".to_string(),
+                language: "go".to_string(),
+                kind: "go_assembly".to_string(),
+                reason: "assembly is not analyzed".to_string(),
+            }],
+            skipped_dependencies: vec![SkippedDependency {
+                package: "github.com/spf13/cobra".to_string(),
+                version: Some("v1.8.0".to_string()),
+                files: Some(41),
+                bytes: Some(380_000),
+            }],
+            binary_findings: scan_bytes(
+                "bin/tool",
+                ArtifactType::Elf,
+                b"\x7fELF\0\0OpenSSL 3.0.2 15 Mar 2022\0",
+            ),
+            fallback_findings: fallback::scan_source(
+                "sum.pl",
+                "pl",
+                "use Digest::MD5;\n",
+                &[FallbackRule::compile(FallbackPattern {
+                    name: "perl_md5".to_string(),
+                    pattern: "Digest::MD5".to_string(),
+                    algorithm: Some("MD5".to_string()),
+                    extensions: Vec::new(),
+                })
+                .unwrap()],
+            ),
+            errors: vec![ScanError {
+                file: "c.go".to_string(),
+                category: "read_failed".to_string(),
+                message: "permission denied".to_string(),
+            }],
+            skipped_files: vec![SkippedFile {
+                file: "d.go".to_string(),
+                language: "go".to_string(),
+                reason: SkipCategory::NotCrypto,
+                detail: String::new(),
+            }],
+            agility: Some(AgilityReport {
+                score: 0,
+                call_sites: 2,
+                layers: Vec::new(),
+                direct_calls: Vec::new(),
+            }),
+            hotspots: Some(HotspotReport {
+                call_sites: 2,
+                ..HotspotReport::default()
+            }),
+            partial: true,
+            timings: Some(ScanTimings {
+                languages: [("go".to_string(), PhaseTimings::default())].into(),
+                ..ScanTimings::default()
+            }),
+        };
+        let expected = OutputFormatter::format_with_artifacts(
+            &results,
+            &artifacts,
+            &classifier,
+            Some(&PasswordPolicy::default()),
+            OutputFormat::Json,
+        )
+        .unwrap();
+
+        let mut spool = ResultSpool::new(Some(1));
+        for result in results {
+            spool.push(result).unwrap();
+        }
+        assert!(spool.has_spilled());
+
+        let mut buf = Vec::new();
+        OutputFormatter::write_spooled(
+            &mut spool,
+            &artifacts,
+            &classifier,
+            Some(&PasswordPolicy::default()),
+            None,
+            OutputFormat::Json,
+            &mut buf,
+        )
+        .unwrap();
+
+        let actual: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(&expected).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual["unanalyzed"][0]["kind"], "go_assembly");
+        assert_eq!(actual["skipped_dependencies"][0]["files"], 41);
+        assert_eq!(actual["binary_findings"][0]["indicator"], "OpenSSL 3.0.2");
+        assert_eq!(actual["binary_findings"][0]["artifact_type"], "elf");
+        assert_eq!(actual["password_storage"][1]["file"], "b.go");
+        assert_eq!(actual["password_storage"][1]["status"], "fail");
+        assert_eq!(actual["unresolved"]["identifier"], 4);
+        assert_eq!(actual["metadata"]["tool"]["name"], "argflow");
+        assert_eq!(actual["agility"]["call_sites"], 2);
+        assert_eq!(actual["errors"][0]["category"], "read_failed");
+        assert_eq!(actual["skipped_files"][0]["reason"], "not_crypto");
+        assert_eq!(actual["hotspots"]["call_sites"], 2);
+        assert_eq!(actual["partial"], true);
+        assert_eq!(
+            actual["metadata"]["timings"]["languages"]["go"]["parse_ms"],
+            0
+        );
+    }
+
+    #[test]
+    fn test_spooled_cbom_matches_in_memory_cbom() {
+        let classifier = RulesClassifier::new();
+        let mut vendored = result_with_call("vendor/x/kdf.go");
+        vendored.dependency = Some("golang.org/x/crypto".to_string());
+        let results = vec![result_with_call("a.go"), vendored];
+
+        let expected = OutputFormatter::format(&results, &classifier, OutputFormat::Cbom).unwrap();
+
+        let mut spool = ResultSpool::new(Some(1));
+        for result in results {
+            spool.push(result).unwrap();
+        }
+        let mut buf = Vec::new();
+        OutputFormatter::write_spooled(
+            &mut spool,
+            &ArtifactReport::default(),
+            &classifier,
+            None,
+            None,
+            OutputFormat::Cbom,
+            &mut buf,
+        )
+        .unwrap();
+
+        let actual: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(&expected).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual["dependencies"][1]["ref"],
+            "library/golang.org/x/crypto"
+        );
+    }
+
+    #[test]
+    fn test_spooled_compact_matches_in_memory_compact() {
+        let classifier = RulesClassifier::new();
+        let results = vec![result_with_call("a.go"), result_with_call("b.go")];
+        let expected = OutputFormatter::format_with_artifacts(
+            &results,
+            &ArtifactReport::default(),
+            &classifier,
+            Some(&PasswordPolicy::default()),
+            OutputFormat::Compact,
+        )
+        .unwrap();
+
+        let mut spool = ResultSpool::new(Some(1));
+        for result in results {
+            spool.push(result).unwrap();
+        }
+        let mut buf = Vec::new();
+        OutputFormatter::write_spooled(
+            &mut spool,
+            &ArtifactReport::default(),
+            &classifier,
+            Some(&PasswordPolicy::default()),
+            None,
+            OutputFormat::Compact,
+            &mut buf,
+        )
+        .unwrap();
+
+        let actual = String::from_utf8(buf).unwrap();
+        assert_eq!(actual.trim_end(), expected);
+        assert!(expected
+            .lines()
+            .any(|line| line.starts_with("b.go:") && line.ends_with("[password_storage]")));
+    }
+
+    #[test]
+    fn test_spooled_cap_matches_in_memory_cap() {
+        let classifier = RulesClassifier::new();
+        let results = vec![
+            result_with_call("a.go"),
+            result_with_call("b.go"),
+            result_with_call("c.go"),
+        ];
+        let mut output = OutputFormatter::build_output_with_artifacts(
+            &results,
+            &ArtifactReport::default(),
+            &classifier,
+            None,
+        );
+        cap_findings(&mut output, 1);
+
+        let mut spool = ResultSpool::new(Some(1));
+        for result in results {
+            spool.push(result).unwrap();
+        }
+        let mut buf = Vec::new();
+        OutputFormatter::write_spooled(
+            &mut spool,
+            &ArtifactReport::default(),
+            &classifier,
+            None,
+            Some(1),
+            OutputFormat::Json,
+            &mut buf,
+        )
+        .unwrap();
+
+        let actual: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(actual, serde_json::to_value(&output).unwrap());
+        assert_eq!(actual["findings"].as_array().unwrap().len(), 1);
+        assert_eq!(actual["total_findings"], 3);
+        assert_eq!(actual["overflow"]["omitted"], 2);
     }
 }
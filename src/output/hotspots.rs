@@ -0,0 +1,369 @@
+//! Where the project's crypto logic is concentrated: the files densest in
+//! crypto calls, the functions making the most of them, and the modules
+//! holding a large share of all call sites across many algorithms.
+//!
+//! Files are ranked by call sites per 100 lines, functions by call sites,
+//! both with the number of distinct algorithms breaking ties. A module (the
+//! directory of a source file, as for agility) concentrating at least
+//! [`CONCENTRATED_SHARE`] percent of the call sites with at least
+//! [`CONCENTRATED_ALGORITHMS`] algorithms is listed as concentrated: the
+//! place to review first, and the one a migration touches most. Dependency
+//! and test code are left out.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::classifier::RulesClassifier;
+use crate::scanner::ScanResult;
+
+use super::Finding;
+
+/// Files and functions listed in each ranking.
+pub const MAX_HOTSPOTS: usize = 10;
+
+/// Percentage of all call sites a module needs to be concentrated.
+pub const CONCENTRATED_SHARE: u32 = 25;
+
+/// Distinct algorithms a module needs to be concentrated.
+pub const CONCENTRATED_ALGORITHMS: usize = 3;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HotspotReport {
+    pub call_sites: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileHotspot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<FunctionHotspot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub concentrated_modules: Vec<ModuleHotspot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileHotspot {
+    pub file: String,
+    pub call_sites: usize,
+    pub lines: usize,
+    /// Call sites per 100 lines, to two decimals.
+    pub density: f64,
+    pub algorithms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionHotspot {
+    pub file: String,
+    pub function: String,
+    pub call_sites: usize,
+    pub algorithms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleHotspot {
+    pub module: String,
+    pub call_sites: usize,
+    /// Percentage of all call sites.
+    pub share: u32,
+    pub files: usize,
+    pub algorithms: Vec<String>,
+}
+
+#[derive(Default)]
+struct Tally {
+    call_sites: usize,
+    algorithms: BTreeSet<String>,
+}
+
+impl Tally {
+    fn add(&mut self, algorithm: Option<&str>) {
+        self.call_sites += 1;
+        self.algorithms.extend(algorithm.map(str::to_string));
+    }
+}
+
+/// Counts the project's crypto call sites by file, function and module.
+#[derive(Default)]
+pub struct HotspotAnalyzer {
+    files: BTreeMap<String, (Tally, usize)>,
+    functions: BTreeMap<(String, String), Tally>,
+    modules: BTreeMap<String, (Tally, BTreeSet<String>)>,
+    call_sites: usize,
+}
+
+impl HotspotAnalyzer {
+    /// Adds the calls of `result`, a file of `lines` lines.
+    pub fn add_result(&mut self, result: &ScanResult, lines: usize, classifier: &RulesClassifier) {
+        if result.dependency.is_some() || result.test_code || result.calls.is_empty() {
+            return;
+        }
+        let file = &result.file_path;
+        let module = module_of(file);
+        for call in &result.calls {
+            let finding = Finding::from_scanner_finding(call, classifier);
+            let algorithm = finding.algorithm.as_deref();
+            self.call_sites += 1;
+
+            let (tally, file_lines) = self.files.entry(file.clone()).or_default();
+            tally.add(algorithm);
+            *file_lines = lines;
+            if let Some(function) = &call.site.enclosing_function {
+                self.functions
+                    .entry((file.clone(), function.clone()))
+                    .or_default()
+                    .add(algorithm);
+            }
+            let (tally, files) = self.modules.entry(module.clone()).or_default();
+            tally.add(algorithm);
+            files.insert(file.clone());
+        }
+    }
+
+    pub fn finish(self) -> HotspotReport {
+        let files = self
+            .files
+            .into_iter()
+            .map(|(file, (tally, lines))| FileHotspot {
+                file,
+                call_sites: tally.call_sites,
+                lines,
+                density: density(tally.call_sites, lines),
+                algorithms: tally.algorithms.into_iter().collect(),
+            })
+            .collect();
+        let functions = self
+            .functions
+            .into_iter()
+            .map(|((file, function), tally)| FunctionHotspot {
+                file,
+                function,
+                call_sites: tally.call_sites,
+                algorithms: tally.algorithms.into_iter().collect(),
+            })
+            .collect();
+        let modules = self
+            .modules
+            .into_iter()
+            .map(|(module, (tally, files))| ModuleHotspot {
+                module,
+                call_sites: tally.call_sites,
+                share: 0,
+                files: files.len(),
+                algorithms: tally.algorithms.into_iter().collect(),
+            })
+            .collect();
+        HotspotReport::ranked(self.call_sites, files, functions, modules)
+    }
+}
+
+impl HotspotReport {
+    /// Combines the reports of separately scanned paths. Only the files and
+    /// functions each report ranked are known, and a module concentrated in
+    /// one path is listed if it still is across all of them.
+    pub fn merge(reports: impl IntoIterator<Item = HotspotReport>) -> Self {
+        let (mut call_sites, mut files, mut functions, mut modules) =
+            (0, Vec::new(), Vec::new(), Vec::new());
+        for report in reports {
+            call_sites += report.call_sites;
+            files.extend(report.files);
+            functions.extend(report.functions);
+            modules.extend(report.concentrated_modules);
+        }
+        Self::ranked(call_sites, files, functions, modules)
+    }
+
+    fn ranked(
+        call_sites: usize,
+        mut files: Vec<FileHotspot>,
+        mut functions: Vec<FunctionHotspot>,
+        mut modules: Vec<ModuleHotspot>,
+    ) -> Self {
+        files.sort_by(|a, b| {
+            b.density
+                .total_cmp(&a.density)
+                .then(b.algorithms.len().cmp(&a.algorithms.len()))
+                .then_with(|| a.file.cmp(&b.file))
+        });
+        files.dedup_by(|a, b| a.file == b.file);
+        files.truncate(MAX_HOTSPOTS);
+
+        functions.sort_by(|a, b| {
+            (b.call_sites, b.algorithms.len())
+                .cmp(&(a.call_sites, a.algorithms.len()))
+                .then_with(|| (&a.file, &a.function).cmp(&(&b.file, &b.function)))
+        });
+        functions.dedup_by(|a, b| a.file == b.file && a.function == b.function);
+        functions.truncate(MAX_HOTSPOTS);
+
+        for module in &mut modules {
+            module.share = share(module.call_sites, call_sites);
+        }
+        modules.retain(|module| {
+            module.share >= CONCENTRATED_SHARE && module.algorithms.len() >= CONCENTRATED_ALGORITHMS
+        });
+        modules.sort_by(|a, b| {
+            (b.algorithms.len(), b.share)
+                .cmp(&(a.algorithms.len(), a.share))
+                .then_with(|| a.module.cmp(&b.module))
+        });
+
+        Self {
+            call_sites,
+            files,
+            functions,
+            concentrated_modules: modules,
+        }
+    }
+}
+
+fn density(call_sites: usize, lines: usize) -> f64 {
+    if lines == 0 {
+        return 0.0;
+    }
+    (10_000.0 * call_sites as f64 / lines as f64).round() / 100.0
+}
+
+fn share(part: usize, total: usize) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    (100.0 * part as f64 / total as f64).round() as u32
+}
+
+fn module_of(file: &str) -> String {
+    Path::new(file)
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{CallSite, Finding as Call};
+
+    fn result(file: &str, calls: &[(&str, &str, Option<&str>)]) -> ScanResult {
+        let mut result = ScanResult::new(file.to_string());
+        result.test_code = crate::policy::is_test_file(std::path::Path::new(file));
+        for (i, (package, function, enclosing)) in calls.iter().enumerate() {
+            result.add_call(Call {
+                file_path: file.to_string(),
+                line: i + 1,
+                column: 1,
+                function_name: function.to_string(),
+                package: Some(package.to_string()),
+                import_path: None,
+                arguments: Vec::new(),
+                keywords: Vec::new(),
+                raw_text: format!("{function}()"),
+                language: "go".to_string(),
+                site: CallSite {
+                    enclosing_function: enclosing.map(str::to_string),
+                    ..CallSite::default()
+                },
+                dynamic_name: None,
+            });
+        }
+        result
+    }
+
+    fn classifier() -> RulesClassifier {
+        RulesClassifier::from_json_str(
+            r#"{"mappings": {
+                "crypto/md5": {"Sum": "md5"},
+                "crypto/sha1": {"Sum": "sha1"},
+                "crypto/sha256": {"Sum256": "sha256"}
+            }, "classifications": {
+                "md5": {"algorithm": "MD5", "findingType": "hash", "operation": "hash"},
+                "sha1": {"algorithm": "SHA-1", "findingType": "hash", "operation": "hash"},
+                "sha256": {"algorithm": "SHA-256", "findingType": "hash", "operation": "hash"}
+            }}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rankings_and_concentrated_modules() {
+        let classifier = classifier();
+        let mut analyzer = HotspotAnalyzer::default();
+        analyzer.add_result(
+            &result(
+                "internal/legacy/digest.go",
+                &[
+                    ("crypto/md5", "Sum", Some("Fingerprint")),
+                    ("crypto/sha1", "Sum", Some("Fingerprint")),
+                    ("crypto/sha256", "Sum256", Some("Checksum")),
+                ],
+            ),
+            30,
+            &classifier,
+        );
+        analyzer.add_result(
+            &result(
+                "api/upload.go",
+                &[("crypto/sha256", "Sum256", Some("Handle"))],
+            ),
+            200,
+            &classifier,
+        );
+        analyzer.add_result(
+            &result(
+                "api/upload_test.go",
+                &[("crypto/md5", "Sum", Some("TestHandle"))],
+            ),
+            10,
+            &classifier,
+        );
+
+        let report = analyzer.finish();
+        assert_eq!(report.call_sites, 4);
+        let files: Vec<(&str, f64)> = report
+            .files
+            .iter()
+            .map(|f| (f.file.as_str(), f.density))
+            .collect();
+        assert_eq!(
+            files,
+            vec![("internal/legacy/digest.go", 10.0), ("api/upload.go", 0.5)]
+        );
+        assert_eq!(report.files[0].algorithms, vec!["MD5", "SHA-1", "SHA-256"]);
+
+        assert_eq!(report.functions[0].function, "Fingerprint");
+        assert_eq!(report.functions[0].call_sites, 2);
+        assert_eq!(report.functions.len(), 3);
+
+        assert_eq!(report.concentrated_modules.len(), 1);
+        let module = &report.concentrated_modules[0];
+        assert_eq!(module.module, "internal/legacy");
+        assert_eq!(module.share, 75);
+        assert_eq!(module.files, 1);
+    }
+
+    #[test]
+    fn test_merge_recomputes_shares() {
+        let classifier = classifier();
+        let mut analyzer = HotspotAnalyzer::default();
+        analyzer.add_result(
+            &result(
+                "svc/a/crypto.go",
+                &[
+                    ("crypto/md5", "Sum", None),
+                    ("crypto/sha1", "Sum", None),
+                    ("crypto/sha256", "Sum256", None),
+                ],
+            ),
+            100,
+            &classifier,
+        );
+        let first = analyzer.finish();
+        assert_eq!(first.concentrated_modules[0].share, 100);
+        assert!(first.functions.is_empty());
+
+        let mut analyzer = HotspotAnalyzer::default();
+        let many: Vec<_> = (0..10).map(|_| ("crypto/sha256", "Sum256", None)).collect();
+        analyzer.add_result(&result("svc/b/hash.go", &many), 100, &classifier);
+        let merged = HotspotReport::merge([first, analyzer.finish()]);
+        assert_eq!(merged.call_sites, 13);
+        assert!(merged.concentrated_modules.is_empty());
+        assert_eq!(merged.files[0].file, "svc/b/hash.go");
+    }
+}
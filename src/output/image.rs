@@ -0,0 +1,174 @@
+//! Container image reports (`argflow image`): paths as they are inside the
+//! image, and the layer each finding comes from.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::fingerprint::{assign_config_fingerprints, assign_fingerprints};
+use super::JsonOutput;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageSummary {
+    /// The tarball or OCI layout that was scanned.
+    pub reference: String,
+    /// Every layer, bottom first.
+    pub layers: Vec<LayerSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerSummary {
+    pub digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    pub total_findings: usize,
+    pub total_configs: usize,
+    /// Files with findings whose last version this layer wrote.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+impl ImageSummary {
+    /// Attributes the findings and configs of `report` to `layers` (digest
+    /// and history entry, bottom first), by the index `layer_of` gives for
+    /// each file.
+    pub fn new(
+        reference: &str,
+        layers: impl IntoIterator<Item = (String, Option<String>)>,
+        report: &JsonOutput,
+        layer_of: impl Fn(&str) -> Option<usize>,
+    ) -> Self {
+        let mut layers: Vec<LayerSummary> = layers
+            .into_iter()
+            .map(|(digest, created_by)| LayerSummary {
+                digest,
+                created_by,
+                total_findings: 0,
+                total_configs: 0,
+                files: Vec::new(),
+            })
+            .collect();
+        let mut files: Vec<BTreeSet<&str>> = vec![BTreeSet::new(); layers.len()];
+        let located = report
+            .findings
+            .iter()
+            .map(|f| (f.file.as_str(), true))
+            .chain(report.configs.iter().map(|c| (c.file.as_str(), false)));
+        for (file, is_finding) in located {
+            let Some(index) = layer_of(file).filter(|&i| i < layers.len()) else {
+                continue;
+            };
+            if is_finding {
+                layers[index].total_findings += 1;
+            } else {
+                layers[index].total_configs += 1;
+            }
+            files[index].insert(file);
+        }
+        for (layer, files) in layers.iter_mut().zip(files) {
+            layer.files = files.into_iter().map(str::to_string).collect();
+        }
+        Self {
+            reference: reference.to_string(),
+            layers,
+        }
+    }
+}
+
+/// Rewrites every path in `report` under `root` (an unpacked image or
+/// archive) to its path under `base`: `""` for absolute paths in an image,
+/// `app.jar!` for `app.jar!/com/example/Keys.java`. The findings are
+/// fingerprinted again so they do not depend on where `root` was.
+pub fn rebase_paths(report: &mut JsonOutput, root: &str, base: &str) {
+    let rebase = |path: &mut String| {
+        if let Some(rest) = path.strip_prefix(root) {
+            if rest.is_empty() || rest.starts_with('/') {
+                *path = format!("{base}/{}", rest.trim_start_matches('/'));
+            }
+        }
+    };
+    for finding in &mut report.findings {
+        rebase(&mut finding.file);
+        finding
+            .evidence
+            .values_mut()
+            .flatten()
+            .for_each(|step| rebase(&mut step.file));
+    }
+    report.configs.iter_mut().for_each(|c| rebase(&mut c.file));
+    report
+        .weaknesses
+        .iter_mut()
+        .for_each(|w| rebase(&mut w.file));
+    report
+        .unanalyzed
+        .iter_mut()
+        .for_each(|a| rebase(&mut a.file));
+    report
+        .binary_findings
+        .iter_mut()
+        .for_each(|b| rebase(&mut b.file));
+    report
+        .fallback_findings
+        .iter_mut()
+        .for_each(|f| rebase(&mut f.file));
+    report
+        .password_storage
+        .iter_mut()
+        .for_each(|p| rebase(&mut p.file));
+    report.fixes.iter_mut().for_each(|f| rebase(&mut f.file));
+    report.errors.iter_mut().for_each(|e| rebase(&mut e.file));
+    if let Some(agility) = &mut report.agility {
+        agility
+            .layers
+            .iter_mut()
+            .for_each(|l| rebase(&mut l.module));
+        agility
+            .direct_calls
+            .iter_mut()
+            .for_each(|c| rebase(&mut c.file));
+    }
+    report.roots.iter_mut().for_each(|r| rebase(&mut r.path));
+    assign_fingerprints(&mut report.findings);
+    assign_config_fingerprints(&mut report.configs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn test_image_summary() {
+        let mut report = JsonOutput {
+            files_scanned: 2,
+            ..test_support::report(
+                vec![
+                    test_support::finding("/tmp/x/rootfs/app/a.py", 1, "hashlib.md5"),
+                    test_support::finding("/tmp/x/rootfs/app/b.py", 1, "hashlib.md5"),
+                ],
+                Vec::new(),
+            )
+        };
+        let before = report.findings[0].fingerprint.clone();
+        rebase_paths(&mut report, "/tmp/x/rootfs", "");
+        assert_eq!(report.findings[0].file, "/app/a.py");
+        assert_ne!(report.findings[0].fingerprint, before);
+
+        let summary = ImageSummary::new(
+            "image.tar",
+            [
+                ("sha256:aaa".to_string(), Some("COPY . /app".to_string())),
+                ("sha256:bbb".to_string(), None),
+            ],
+            &report,
+            |file| Some(usize::from(file == "/app/b.py")),
+        );
+        assert_eq!(summary.layers[0].total_findings, 1);
+        assert_eq!(summary.layers[0].files, vec!["/app/a.py"]);
+        assert_eq!(summary.layers[1].files, vec!["/app/b.py"]);
+
+        rebase_paths(&mut report, "/app", "dist/app.jar!");
+        assert_eq!(report.findings[1].file, "dist/app.jar!/b.py");
+    }
+}
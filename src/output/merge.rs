@@ -0,0 +1,388 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::fingerprint::ensure_fingerprints;
+use super::formatter::unresolved_sources;
+use super::{
+    AgilityReport, ConfigFinding, Finding, FindingsOverflow, HotspotReport, JsonOutput, ScanTimings,
+};
+use crate::policy::{risk_score, WaiverReport};
+
+/// Loads the JSON reports at `paths` and combines them with
+/// [`merge_reports`].
+pub fn merge_report_files(paths: &[PathBuf]) -> anyhow::Result<JsonOutput> {
+    let reports = paths
+        .iter()
+        .map(|path| JsonOutput::load(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(merge_reports(reports))
+}
+
+/// Combines JSON reports into one report: `--shard` runs, scans of
+/// different languages, or scans of different subtrees.
+///
+/// Entries are sorted by location so the result does not depend on input
+/// order. Entries reported by more than one input share a fingerprint and
+/// are kept once, and files seen in several inputs are counted once in
+/// `files_scanned`. Reports written before fingerprints existed get them
+/// assigned here. Per-path `roots` totals and the `image` layers are
+/// dropped, since the shards of one path would each repeat them, and so is
+/// the `metadata` unless all inputs have the same, timings aside: those
+/// are summed. The `agility`,
+/// `hotspots` and `waivers` sections are combined as they are; a layer
+/// whose callers were in another shard is not found again. The result is `partial` if any
+/// input is. Findings an input left out under `--max-findings` stay counted
+/// in the `overflow`, `total_findings` and `unresolved` of the result, but
+/// cannot be told apart from those of another input.
+pub fn merge_reports(reports: impl IntoIterator<Item = JsonOutput>) -> JsonOutput {
+    let mut merged = JsonOutput::default();
+    let mut seen_in: HashMap<String, usize> = HashMap::new();
+    let mut metadata = Vec::new();
+    let mut timings = Vec::new();
+    let mut agility = Vec::new();
+    let mut hotspots = Vec::new();
+    let mut waivers = Vec::new();
+    let mut overflows = Vec::new();
+    let mut omitted_unresolved: BTreeMap<String, usize> = BTreeMap::new();
+    for mut report in reports {
+        ensure_fingerprints(&mut report);
+        let mut report_metadata = report.metadata.take();
+        timings.extend(report_metadata.as_mut().and_then(|m| m.timings.take()));
+        metadata.push(report_metadata);
+
+        let files: HashSet<&str> = report
+            .findings
+            .iter()
+            .map(|f| f.file.as_str())
+            .chain(report.configs.iter().map(|c| c.file.as_str()))
+            .collect();
+        for file in files {
+            *seen_in.entry(file.to_string()).or_default() += 1;
+        }
+
+        if report.overflow.is_some() {
+            let listed = unresolved_sources(&report.findings);
+            for (source, count) in &report.unresolved {
+                let omitted = count.saturating_sub(listed.get(source).copied().unwrap_or(0));
+                if omitted > 0 {
+                    *omitted_unresolved.entry(source.clone()).or_default() += omitted;
+                }
+            }
+        }
+        merged.files_scanned += report.files_scanned;
+        merged.partial |= report.partial;
+        merged.findings.extend(report.findings);
+        merged.configs.extend(report.configs);
+        merged.weaknesses.extend(report.weaknesses);
+        merged.unanalyzed.extend(report.unanalyzed);
+        merged
+            .skipped_dependencies
+            .extend(report.skipped_dependencies);
+        merged.binary_findings.extend(report.binary_findings);
+        merged.fallback_findings.extend(report.fallback_findings);
+        merged.password_storage.extend(report.password_storage);
+        merged.fixes.extend(report.fixes);
+        merged.errors.extend(report.errors);
+        merged.skipped_files.extend(report.skipped_files);
+        agility.extend(report.agility);
+        hotspots.extend(report.hotspots);
+        waivers.extend(report.waivers);
+        overflows.extend(report.overflow);
+    }
+
+    let overlap: usize = seen_in.values().map(|n| n - 1).sum();
+    merged.files_scanned = merged.files_scanned.saturating_sub(overlap);
+
+    merged
+        .findings
+        .sort_by(|a, b| finding_key(a).cmp(&finding_key(b)));
+    let mut seen = HashSet::new();
+    merged
+        .findings
+        .retain(|f| seen.insert((f.file.clone(), f.fingerprint.clone())));
+
+    merged
+        .configs
+        .sort_by(|a, b| config_key(a).cmp(&config_key(b)));
+    let mut seen = HashSet::new();
+    merged
+        .configs
+        .retain(|c| seen.insert((c.file.clone(), c.fingerprint.clone())));
+
+    merged.weaknesses.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.rule).cmp(&(&b.file, b.line, b.column, &b.rule))
+    });
+    merged.weaknesses.dedup();
+
+    merged
+        .unanalyzed
+        .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.kind.cmp(&b.kind)));
+    merged.unanalyzed.dedup();
+
+    merged
+        .skipped_dependencies
+        .sort_by(|a, b| (&a.package, &a.version).cmp(&(&b.package, &b.version)));
+    merged.skipped_dependencies.dedup();
+
+    merged
+        .binary_findings
+        .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.offset.cmp(&b.offset)));
+    merged.binary_findings.dedup();
+
+    merged
+        .fallback_findings
+        .sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    merged.fallback_findings.dedup();
+
+    merged
+        .password_storage
+        .sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    merged.password_storage.dedup();
+
+    merged.fixes.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.rule).cmp(&(&b.file, b.line, b.column, &b.rule))
+    });
+    merged.fixes.dedup();
+
+    merged
+        .errors
+        .sort_by(|a, b| (&a.file, &a.category).cmp(&(&b.file, &b.category)));
+    merged.errors.dedup();
+
+    merged.skipped_files.sort();
+    merged
+        .skipped_files
+        .dedup_by(|a, b| a.file == b.file && a.reason == b.reason);
+
+    merged.risk_score = risk_score(&merged.weaknesses);
+    merged.overflow = FindingsOverflow::merge(overflows);
+    merged.total_findings =
+        merged.findings.len() + merged.overflow.as_ref().map_or(0, |o| o.omitted);
+    merged.total_configs = merged.configs.len();
+    merged.unresolved = unresolved_sources(&merged.findings);
+    for (source, count) in omitted_unresolved {
+        *merged.unresolved.entry(source).or_default() += count;
+    }
+    if !agility.is_empty() {
+        merged.agility = Some(AgilityReport::merge(agility));
+    }
+    if !hotspots.is_empty() {
+        merged.hotspots = Some(HotspotReport::merge(hotspots));
+    }
+    if !waivers.is_empty() {
+        merged.waivers = Some(WaiverReport::merge(waivers));
+    }
+    // Kept only when every input was produced by the same build and rules
+    if metadata.windows(2).all(|pair| pair[0] == pair[1]) {
+        merged.metadata = metadata.into_iter().next().flatten();
+    }
+    if let Some(metadata) = merged.metadata.as_mut().filter(|_| !timings.is_empty()) {
+        metadata.timings = Some(ScanTimings::merge(timings));
+    }
+    merged
+}
+
+fn finding_key(finding: &Finding) -> (&str, usize, usize, &str) {
+    (
+        &finding.file,
+        finding.line,
+        finding.column,
+        &finding.full_name,
+    )
+}
+
+fn config_key(config: &ConfigFinding) -> (&str, usize, usize, &str) {
+    (&config.file, config.line, config.column, &config.full_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{PhaseTimings, ReportMetadata, ToolInfo, UnanalyzedArtifact};
+    use crate::test_support::{self, finding};
+
+    fn report(files_scanned: usize, findings: Vec<Finding>) -> JsonOutput {
+        JsonOutput {
+            files_scanned,
+            ..test_support::report(findings, Vec::new())
+        }
+    }
+
+    fn assembly() -> UnanalyzedArtifact {
+        UnanalyzedArtifact {
+            file: "x.s".to_string(),
+            language: "go".to_string(),
+            kind: "go_assembly".to_string(),
+            reason: "r".to_string(),
+        }
+    }
+
+    fn metadata(version: &str, timings: Option<ScanTimings>) -> ReportMetadata {
+        ReportMetadata {
+            tool: ToolInfo {
+                name: "argflow".to_string(),
+                version: version.to_string(),
+            },
+            rules: None,
+            grammars: BTreeMap::new(),
+            timings,
+        }
+    }
+
+    #[test]
+    fn test_merge_reports_combines_and_sorts() {
+        let first = report(2, vec![finding("b.go", 3, "md5.Sum")]);
+        let second = JsonOutput {
+            unanalyzed: vec![assembly()],
+            ..report(
+                1,
+                vec![Finding {
+                    column: 2,
+                    algorithm: Some("SHA-256".to_string()),
+                    parameters: test_support::parameters(serde_json::json!({"arg0": 1})),
+                    ..finding("a.go", 9, "sha256.New")
+                }],
+            )
+        };
+
+        let merged = merge_reports([first, second]);
+        assert_eq!(merged.files_scanned, 3);
+        assert_eq!(merged.total_findings, 2);
+        assert_eq!(merged.findings[0].file, "a.go");
+        assert_eq!(merged.findings[0].algorithm.as_deref(), Some("SHA-256"));
+        assert_eq!(merged.findings[1].file, "b.go");
+        assert_eq!(merged.unanalyzed.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_report_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = [("a.json", "a.go"), ("b.json", "b.go")]
+            .iter()
+            .map(|(name, file)| {
+                let path = dir.path().join(name);
+                let report = report(1, vec![finding(file, 1, "md5.Sum")]);
+                std::fs::write(&path, serde_json::to_string(&report).unwrap()).unwrap();
+                path
+            })
+            .collect();
+
+        let merged = merge_report_files(&paths).unwrap();
+        assert_eq!(merged.files_scanned, 2);
+        assert_eq!(merged.total_findings, 2);
+
+        std::fs::write(&paths[1], "not json").unwrap();
+        let err = merge_report_files(&paths).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to parse report"));
+    }
+
+    #[test]
+    fn test_merge_reports_keeps_metadata_shared_by_all_inputs() {
+        let built = |version: &str| JsonOutput {
+            metadata: Some(metadata(version, None)),
+            ..report(0, Vec::new())
+        };
+
+        let merged = merge_reports([built("0.1.0"), built("0.1.0")]);
+        assert_eq!(merged.metadata.unwrap().tool.version, "0.1.0");
+        assert!(merge_reports([built("0.1.0"), built("0.2.0")])
+            .metadata
+            .is_none());
+
+        let timed = |language: &str, parse_ms: u64| {
+            let mut timings = ScanTimings::default();
+            timings.add(
+                language,
+                PhaseTimings {
+                    parse_ms,
+                    ..PhaseTimings::default()
+                },
+            );
+            JsonOutput {
+                metadata: Some(metadata("0.1.0", Some(timings))),
+                ..report(0, Vec::new())
+            }
+        };
+        let timings = merge_reports([timed("go", 3), timed("python", 4)])
+            .metadata
+            .unwrap()
+            .timings
+            .unwrap();
+        assert_eq!(timings.total.parse_ms, 7);
+        assert_eq!(timings.languages.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_reports_drops_duplicates() {
+        let scanned = || JsonOutput {
+            unanalyzed: vec![assembly()],
+            ..report(1, vec![finding("a.go", 1, "md5.Sum")])
+        };
+
+        let merged = merge_reports([scanned(), scanned()]);
+        assert_eq!(merged.total_findings, 1);
+        assert_eq!(merged.files_scanned, 1);
+        assert_eq!(merged.unanalyzed.len(), 1);
+        assert_eq!(merged.findings[0].fingerprint.len(), 16);
+    }
+
+    #[test]
+    fn test_merge_reports_from_different_languages() {
+        let fingerprinted = |finding: Finding, fingerprint: &str| Finding {
+            fingerprint: fingerprint.to_string(),
+            ..finding
+        };
+        let go = report(
+            4,
+            vec![fingerprinted(
+                Finding {
+                    column: 3,
+                    ..finding("svc/main.go", 7, "md5.Sum")
+                },
+                "00000000000000aa",
+            )],
+        );
+        let python = report(
+            2,
+            vec![
+                fingerprinted(
+                    finding("tools/gen.py", 1, "hashlib.md5"),
+                    "00000000000000aa",
+                ),
+                fingerprinted(
+                    finding("tools/gen.py", 2, "hashlib.sha1"),
+                    "00000000000000bb",
+                ),
+            ],
+        );
+
+        let merged = merge_reports([go, python]);
+        assert_eq!(merged.files_scanned, 6);
+        assert_eq!(merged.total_findings, 3);
+        assert!(!merged.partial);
+
+        let cancelled = JsonOutput {
+            partial: true,
+            ..report(0, Vec::new())
+        };
+        assert!(merge_reports([report(0, Vec::new()), cancelled]).partial);
+    }
+
+    #[test]
+    fn test_merge_overlapping_subtrees_keeps_moved_finding_once() {
+        let whole = report(2, vec![finding("lib/a.go", 10, "md5.Sum")]);
+        let subtree = report(
+            1,
+            vec![
+                finding("lib/a.go", 12, "md5.Sum"),
+                finding("lib/a.go", 30, "sha1.New"),
+            ],
+        );
+
+        let merged = merge_reports([whole, subtree]);
+        assert_eq!(merged.files_scanned, 2);
+        assert_eq!(merged.total_findings, 2);
+        assert_eq!(merged.findings[0].line, 10);
+        assert_eq!(merged.findings[1].full_name, "sha1.New");
+    }
+}
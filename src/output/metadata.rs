@@ -0,0 +1,191 @@
+//! Which build of the detector produced a report: the tool version, the
+//! rules it classified with and the grammars it parsed with, so findings
+//! from different builds can be told apart downstream. With `--timings`,
+//! also where the scan spent its time.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::ToolInfo;
+use crate::classifier::{RulesClassifier, RulesInfo};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportMetadata {
+    pub tool: ToolInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<RulesInfo>,
+    /// Version of each tree-sitter crate, by crate name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub grammars: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<ScanTimings>,
+}
+
+/// Milliseconds spent in each phase of a scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhaseTimings {
+    /// Finding the files of the project and of its dependencies.
+    pub discovery_ms: u64,
+    /// Keeping the files that import a mapped package.
+    pub filter_ms: u64,
+    /// Parsing, summed over the files: above the wall time with several jobs.
+    pub parse_ms: u64,
+    /// Matching calls and resolving their arguments, summed likewise.
+    pub resolve_ms: u64,
+    /// Classifying the calls into findings and checking them for weaknesses.
+    pub classify_ms: u64,
+    /// Rendering the report.
+    pub output_ms: u64,
+}
+
+impl PhaseTimings {
+    /// Each phase by its field name.
+    pub fn phases(&self) -> [(&'static str, u64); 6] {
+        [
+            ("discovery_ms", self.discovery_ms),
+            ("filter_ms", self.filter_ms),
+            ("parse_ms", self.parse_ms),
+            ("resolve_ms", self.resolve_ms),
+            ("classify_ms", self.classify_ms),
+            ("output_ms", self.output_ms),
+        ]
+    }
+
+    fn add(&mut self, other: &PhaseTimings) {
+        self.discovery_ms += other.discovery_ms;
+        self.filter_ms += other.filter_ms;
+        self.parse_ms += other.parse_ms;
+        self.resolve_ms += other.resolve_ms;
+        self.classify_ms += other.classify_ms;
+        self.output_ms += other.output_ms;
+    }
+}
+
+/// The phase timings of a whole report, and of the scans of each language
+/// in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanTimings {
+    #[serde(flatten)]
+    pub total: PhaseTimings,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub languages: BTreeMap<String, PhaseTimings>,
+}
+
+impl ScanTimings {
+    /// Adds `phases` of a `language` scan to the total and to its language.
+    pub fn add(&mut self, language: &str, phases: PhaseTimings) {
+        self.total.add(&phases);
+        self.languages
+            .entry(language.to_string())
+            .or_default()
+            .add(&phases);
+    }
+
+    /// Sums the timings of separately scanned paths.
+    pub fn merge(timings: impl IntoIterator<Item = ScanTimings>) -> Self {
+        let mut merged = Self::default();
+        for timings in timings {
+            merged.total.add(&timings.total);
+            for (language, phases) in timings.languages {
+                merged.languages.entry(language).or_default().add(&phases);
+            }
+        }
+        merged
+    }
+}
+
+/// `duration` in whole milliseconds.
+pub fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+impl ReportMetadata {
+    /// Metadata of this build classifying with `classifier`.
+    pub fn new(classifier: &RulesClassifier) -> Self {
+        Self {
+            tool: ToolInfo::default(),
+            rules: classifier.rules_info(),
+            grammars: grammar_versions(),
+            timings: None,
+        }
+    }
+}
+
+/// The tree-sitter crates this build was compiled with and their versions.
+pub fn grammar_versions() -> BTreeMap<String, String> {
+    env!("ARGFLOW_GRAMMARS")
+        .split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(name, version)| (name.to_string(), version.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_records_tool_rules_and_grammars() {
+        let classifier =
+            RulesClassifier::from_json_str(r#"{"mappings": {"hashlib": {"md5": "weak_hash"}}}"#)
+                .unwrap();
+
+        let metadata = ReportMetadata::new(&classifier);
+
+        assert_eq!(metadata.tool.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata.rules.as_ref().unwrap().files, 1);
+        assert_eq!(metadata.rules.unwrap().sha256.len(), 64);
+        assert!(metadata.grammars.contains_key("tree-sitter"));
+        assert!(metadata.grammars.contains_key("tree-sitter-go"));
+        assert!(!metadata.grammars.contains_key("tree-sitter-language"));
+    }
+
+    #[test]
+    fn test_timings_merge_by_language() {
+        let phases = |parse_ms| PhaseTimings {
+            discovery_ms: 2,
+            parse_ms,
+            ..PhaseTimings::default()
+        };
+        let mut go = ScanTimings::default();
+        go.add("go", phases(10));
+        let mut python = ScanTimings::default();
+        python.add("python", phases(5));
+        let mut more_go = ScanTimings::default();
+        more_go.add("go", phases(1));
+
+        let merged = ScanTimings::merge([go, python, more_go]);
+        assert_eq!(merged.total.parse_ms, 16);
+        assert_eq!(merged.total.discovery_ms, 6);
+        assert_eq!(merged.languages["go"].parse_ms, 11);
+        assert_eq!(merged.languages["python"].parse_ms, 5);
+
+        let json = serde_json::to_value(&merged).unwrap();
+        assert_eq!(json["parse_ms"], 16);
+        assert_eq!(json["languages"]["go"]["discovery_ms"], 4);
+    }
+
+    #[test]
+    fn test_rules_digest_changes_with_the_rules() {
+        let digest = |rules: &str| {
+            RulesClassifier::from_json_str(rules)
+                .unwrap()
+                .rules_info()
+                .unwrap()
+                .sha256
+        };
+        let md5 = digest(r#"{"mappings": {"hashlib": {"md5": "weak_hash"}}}"#);
+        assert_eq!(
+            md5,
+            digest(r#"{"mappings": {"hashlib": {"md5": "weak_hash"}}}"#)
+        );
+        assert_ne!(
+            md5,
+            digest(r#"{"mappings": {"hashlib": {"sha1": "weak_hash"}}}"#)
+        );
+        assert!(RulesClassifier::new().rules_info().is_none());
+    }
+}
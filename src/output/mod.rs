@@ -1,5 +1,45 @@
+mod agility;
+mod attestation;
+mod cbom;
+mod compact;
+mod compare;
 mod finding;
+mod fingerprint;
 mod formatter;
+mod hotspots;
+mod image;
+mod merge;
+mod metadata;
+mod notify;
+mod overflow;
+mod skipped_files;
+mod spool;
+mod warnings;
 
-pub use finding::{ConfigFieldValue, ConfigFinding, Finding};
-pub use formatter::{JsonOutput, OutputFormatter};
+pub use agility::{
+    AbstractionLayer, AgilityAnalyzer, AgilityReport, DirectCall, MIN_LAYER_REFERENCES,
+};
+pub use attestation::{
+    attest_report, digest_inputs, InputDigest, ScanPredicate, SigningKey, ToolInfo, PREDICATE_TYPE,
+};
+pub use cbom::{build_cbom, CbomBuilder};
+pub use compact::{compact_lines, render_compact, CompactLine};
+pub use compare::{compare_reports, ChangedEntry, ReportDiff, RiskScoreChange, ValueChange};
+pub use finding::{
+    ConfigFieldValue, ConfigFinding, Finding, SkippedDependency, UnanalyzedArtifact,
+    DYNAMIC_DISPATCH,
+};
+pub use fingerprint::{assign_config_fingerprints, assign_fingerprints};
+pub use formatter::{ArtifactReport, JsonOutput, OutputFormatter, RootSummary};
+pub use hotspots::{
+    FileHotspot, FunctionHotspot, HotspotAnalyzer, HotspotReport, ModuleHotspot,
+    CONCENTRATED_ALGORITHMS, CONCENTRATED_SHARE, MAX_HOTSPOTS,
+};
+pub use image::{rebase_paths, ImageSummary, LayerSummary};
+pub use merge::{merge_report_files, merge_reports};
+pub use metadata::{grammar_versions, millis, PhaseTimings, ReportMetadata, ScanTimings};
+pub use notify::{post_notification, Notification, NotifyConfig, MAX_LISTED_WEAKNESSES};
+pub use overflow::{cap_findings, FindingsOverflow};
+pub use skipped_files::{SkipCategory, SkipLog, SkippedFile};
+pub use spool::{parse_byte_size, ResultSpool, SpoolTotals};
+pub use warnings::{ScanError, WarningCategory, WarningLog, DEFAULT_WARNING_LIMIT};
@@ -0,0 +1,348 @@
+//! Notifications after a scan, configured under `notify` in the config
+//! file: a summary of the weaknesses at or above a severity, only those not
+//! in a baseline report when one is given, posted to a webhook as
+//! Slack-compatible JSON (`{"text": ...}`). Posting is delegated to `curl`,
+//! which is given the webhook URL on stdin and the payload in a temporary
+//! file, so neither shows up in the process list.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::error::NotifyError;
+use crate::policy::{Severity, Weakness};
+
+use super::JsonOutput;
+
+const CURL_COMMAND: &str = "curl";
+
+/// Seconds `curl` may take to post a notification.
+const POST_TIMEOUT_SECS: u64 = 30;
+
+/// Weaknesses listed one per line in a notification; the rest are counted.
+pub const MAX_LISTED_WEAKNESSES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifyConfig {
+    /// URL the summary is posted to, e.g. a Slack incoming webhook.
+    pub webhook: String,
+    /// Least severe weakness notified about.
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+    /// Earlier report of the project; only weaknesses not in it are
+    /// notified about.
+    #[serde(default)]
+    pub baseline: Option<PathBuf>,
+}
+
+fn default_min_severity() -> Severity {
+    Severity::High
+}
+
+/// The weaknesses of a report worth a notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub weaknesses: Vec<Weakness>,
+    pub min_severity: Severity,
+    /// Whether `weaknesses` are only those missing from a baseline.
+    pub new_only: bool,
+    pub risk_score: u32,
+    pub files_scanned: usize,
+    pub partial: bool,
+}
+
+impl Notification {
+    /// The weaknesses of `report` at or above `config.min_severity`, less
+    /// those in `baseline`, or `None` when there are none.
+    pub fn for_report(
+        report: &JsonOutput,
+        config: &NotifyConfig,
+        baseline: Option<&JsonOutput>,
+    ) -> Option<Self> {
+        let known: HashSet<WeaknessKey> = baseline
+            .map(|baseline| baseline.weaknesses.iter().map(WeaknessKey::of).collect())
+            .unwrap_or_default();
+        let weaknesses: Vec<Weakness> = report
+            .weaknesses
+            .iter()
+            .filter(|w| w.severity >= config.min_severity)
+            .filter(|w| !known.contains(&WeaknessKey::of(w)))
+            .cloned()
+            .collect();
+        if weaknesses.is_empty() {
+            return None;
+        }
+        Some(Self {
+            weaknesses,
+            min_severity: config.min_severity,
+            new_only: baseline.is_some(),
+            risk_score: report.risk_score,
+            files_scanned: report.files_scanned,
+            partial: report.partial,
+        })
+    }
+
+    /// The message: a headline, then one line per weakness.
+    pub fn text(&self) -> String {
+        let count = self.weaknesses.len();
+        let plural = if count == 1 { "weakness" } else { "weaknesses" };
+        let new = if self.new_only { "new " } else { "" };
+        let files = if self.files_scanned == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        let mut text = format!(
+            "argflow: {count} {new}{plural} of {} severity or above{} (risk score {}, {} {files} scanned{})",
+            self.min_severity.as_str(),
+            if self.new_only { " since the baseline" } else { "" },
+            self.risk_score,
+            self.files_scanned,
+            if self.partial { ", scan cancelled" } else { "" },
+        );
+        for weakness in self.weaknesses.iter().take(MAX_LISTED_WEAKNESSES) {
+            text.push_str(&format!(
+                "\n• {} {} in {}:{} ({})",
+                weakness.severity.as_str(),
+                weakness.rule,
+                weakness.file,
+                weakness.line,
+                weakness.function
+            ));
+        }
+        if count > MAX_LISTED_WEAKNESSES {
+            text.push_str(&format!("\n… and {} more", count - MAX_LISTED_WEAKNESSES));
+        }
+        text
+    }
+
+    /// The Slack-compatible JSON body.
+    pub fn payload(&self) -> serde_json::Value {
+        serde_json::json!({ "text": self.text() })
+    }
+}
+
+/// Identifies a weakness across reports: by the fingerprint of its finding,
+/// which survives moved lines, or by where it is for reports without one.
+#[derive(PartialEq, Eq, Hash)]
+enum WeaknessKey<'a> {
+    Finding(&'a str, &'a str),
+    Location(&'a str, &'a str, &'a str),
+}
+
+impl<'a> WeaknessKey<'a> {
+    fn of(weakness: &'a Weakness) -> Self {
+        if weakness.finding.is_empty() {
+            Self::Location(&weakness.rule, &weakness.file, &weakness.function)
+        } else {
+            Self::Finding(&weakness.rule, &weakness.finding)
+        }
+    }
+}
+
+/// Posts `notification` to `webhook` with `curl`. A webhook with control
+/// characters is rejected, since they would end the curl config line.
+pub fn post_notification(webhook: &str, notification: &Notification) -> Result<(), NotifyError> {
+    if let Some(index) = webhook.find(char::is_control) {
+        return Err(NotifyError::invalid_webhook(index));
+    }
+    let mut payload = tempfile::Builder::new()
+        .prefix("argflow-notify-")
+        .suffix(".json")
+        .tempfile()
+        .map_err(|e| NotifyError::post_failed(e.to_string()))?;
+    serde_json::to_writer(&mut payload, &notification.payload())
+        .map_err(|e| NotifyError::post_failed(e.to_string()))?;
+    payload
+        .flush()
+        .map_err(|e| NotifyError::post_failed(e.to_string()))?;
+
+    let mut child = Command::new(CURL_COMMAND)
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--max-time",
+            &POST_TIMEOUT_SECS.to_string(),
+            "--header",
+            "Content-Type: application/json",
+            "--data-binary",
+            &format!("@{}", payload.path().display()),
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| NotifyError::sender_unavailable(CURL_COMMAND, e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "url = \"{}\"", curl_quoted(webhook))
+            .map_err(|e| NotifyError::post_failed(e.to_string()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| NotifyError::sender_unavailable(CURL_COMMAND, e.to_string()))?;
+    if !output.status.success() {
+        return Err(NotifyError::post_failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `value` escaped for a double-quoted curl config parameter.
+fn curl_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn weakness(file: &str, rule: &str, severity: Severity, finding: &str) -> Weakness {
+        Weakness {
+            line: 12,
+            function: "tls.Config".to_string(),
+            finding: finding.to_string(),
+            ..test_support::weakness(file, rule, severity)
+        }
+    }
+
+    fn report(weaknesses: Vec<Weakness>) -> JsonOutput {
+        JsonOutput {
+            files_scanned: 40,
+            risk_score: 35,
+            ..test_support::report(Vec::new(), weaknesses)
+        }
+    }
+
+    fn config(baseline: bool) -> NotifyConfig {
+        NotifyConfig {
+            webhook: "https://hooks.example.com/T000".to_string(),
+            min_severity: Severity::High,
+            baseline: baseline.then(|| PathBuf::from("main.json")),
+        }
+    }
+
+    #[test]
+    fn test_notification_above_threshold() {
+        let found = report(vec![
+            weakness(
+                "client.go",
+                "tls_verification_disabled",
+                Severity::High,
+                "a1",
+            ),
+            weakness("kdf.go", "cipher_ecb_mode", Severity::Medium, "b2"),
+        ]);
+
+        let notification = Notification::for_report(&found, &config(false), None).unwrap();
+        assert_eq!(notification.weaknesses.len(), 1);
+        assert_eq!(
+            notification.text(),
+            "argflow: 1 weakness of high severity or above (risk score 35, 40 files scanned)\n\
+             • high tls_verification_disabled in client.go:12 (tls.Config)"
+        );
+        assert_eq!(notification.payload()["text"], notification.text());
+
+        let medium_only = report(vec![weakness(
+            "kdf.go",
+            "cipher_ecb_mode",
+            Severity::Medium,
+            "b2",
+        )]);
+        assert!(Notification::for_report(&medium_only, &config(false), None).is_none());
+    }
+
+    #[test]
+    fn test_notification_only_new_since_baseline() {
+        let baseline = report(vec![
+            weakness(
+                "client.go",
+                "tls_verification_disabled",
+                Severity::High,
+                "a1",
+            ),
+            weakness("old.go", "jwt_alg_none", Severity::High, ""),
+        ]);
+        let mut moved = weakness(
+            "client.go",
+            "tls_verification_disabled",
+            Severity::High,
+            "a1",
+        );
+        moved.line = 80;
+        let current = report(vec![
+            moved,
+            weakness("old.go", "jwt_alg_none", Severity::High, ""),
+            weakness("new.go", "jwt_alg_none", Severity::High, "c3"),
+        ]);
+
+        let notification =
+            Notification::for_report(&current, &config(true), Some(&baseline)).unwrap();
+        let files: Vec<&str> = notification
+            .weaknesses
+            .iter()
+            .map(|w| w.file.as_str())
+            .collect();
+        assert_eq!(files, ["new.go"]);
+        assert!(notification
+            .text()
+            .starts_with("argflow: 1 new weakness of high severity or above since the baseline"));
+
+        assert!(Notification::for_report(&baseline, &config(true), Some(&baseline)).is_none());
+    }
+
+    #[test]
+    fn test_notification_lists_a_limited_number() {
+        let weaknesses = (0..MAX_LISTED_WEAKNESSES + 3)
+            .map(|i| weakness(&format!("f{i}.go"), "jwt_alg_none", Severity::High, ""))
+            .collect();
+        let text = Notification::for_report(&report(weaknesses), &config(false), None)
+            .unwrap()
+            .text();
+        assert_eq!(text.lines().count(), MAX_LISTED_WEAKNESSES + 2);
+        assert!(text.ends_with("… and 3 more"));
+    }
+
+    #[test]
+    fn test_notify_config_defaults() {
+        let config: NotifyConfig =
+            serde_yaml::from_str("webhook: https://hooks.example.com/T000\n").unwrap();
+        assert_eq!(config.min_severity, Severity::High);
+        assert!(config.baseline.is_none());
+        assert!(serde_yaml::from_str::<NotifyConfig>("webhok: x\n").is_err());
+    }
+
+    #[test]
+    fn test_webhook_with_control_characters_rejected() {
+        let notification = Notification::for_report(
+            &report(vec![weakness(
+                "client.go",
+                "tls_verification_disabled",
+                Severity::High,
+                "a1",
+            )]),
+            &config(false),
+            None,
+        )
+        .unwrap();
+        let err = post_notification(
+            "https://hooks.example.com/T000\noutput = \"/tmp/x\"",
+            &notification,
+        )
+        .unwrap_err();
+        assert!(matches!(err, NotifyError::InvalidWebhook { index: 30 }));
+    }
+
+    #[test]
+    fn test_curl_quoted() {
+        assert_eq!(curl_quoted(r#"https://a/"b"\c"#), r#"https://a/\"b\"\\c"#);
+    }
+}
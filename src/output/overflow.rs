@@ -0,0 +1,183 @@
+//! `--max-findings`: a cap on the findings listed in a report, so the
+//! report of an enormous repository stays a manageable size. The findings
+//! past the cap are only counted, in the report's `overflow`, together
+//! with their weaknesses by rule and severity, while `total_findings`, the
+//! weaknesses, the risk score and the `unresolved` summary still cover
+//! every finding.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Finding, JsonOutput};
+use crate::policy::{Severity, Weakness};
+
+/// Counted under for findings without a finding type or algorithm.
+const UNCLASSIFIED: &str = "unclassified";
+
+/// The findings left out of a report by `--max-findings`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingsOverflow {
+    /// The cap the findings were cut to.
+    pub max_findings: usize,
+    /// Findings left out.
+    pub omitted: usize,
+    /// Findings left out by finding type, e.g. `hash`.
+    pub by_type: BTreeMap<String, usize>,
+    /// Findings left out by algorithm, e.g. `MD5`.
+    pub by_algorithm: BTreeMap<String, usize>,
+    /// Weaknesses of the findings left out by rule, e.g. `ecb_mode`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weaknesses_by_rule: BTreeMap<String, usize>,
+    /// Weaknesses of the findings left out by severity.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weaknesses_by_severity: BTreeMap<Severity, usize>,
+}
+
+impl FindingsOverflow {
+    pub fn new(max_findings: usize) -> Self {
+        Self {
+            max_findings,
+            ..Self::default()
+        }
+    }
+
+    /// Counts `finding` as left out.
+    pub fn add(&mut self, finding: &Finding) {
+        self.omitted += 1;
+        let key = |value: &Option<String>| value.as_deref().unwrap_or(UNCLASSIFIED).to_string();
+        *self.by_type.entry(key(&finding.finding_type)).or_default() += 1;
+        *self
+            .by_algorithm
+            .entry(key(&finding.algorithm))
+            .or_default() += 1;
+    }
+
+    /// Counts `weakness` as one of a finding left out.
+    pub fn add_weakness(&mut self, weakness: &Weakness) {
+        *self
+            .weaknesses_by_rule
+            .entry(weakness.rule.clone())
+            .or_default() += 1;
+        *self
+            .weaknesses_by_severity
+            .entry(weakness.severity)
+            .or_default() += 1;
+    }
+
+    /// Combines the overflow of several reports.
+    pub fn merge(overflows: impl IntoIterator<Item = FindingsOverflow>) -> Option<Self> {
+        overflows.into_iter().reduce(|mut merged, overflow| {
+            merged.max_findings = merged.max_findings.max(overflow.max_findings);
+            merged.omitted += overflow.omitted;
+            for (key, count) in overflow.by_type {
+                *merged.by_type.entry(key).or_default() += count;
+            }
+            for (key, count) in overflow.by_algorithm {
+                *merged.by_algorithm.entry(key).or_default() += count;
+            }
+            for (rule, count) in overflow.weaknesses_by_rule {
+                *merged.weaknesses_by_rule.entry(rule).or_default() += count;
+            }
+            for (severity, count) in overflow.weaknesses_by_severity {
+                *merged.weaknesses_by_severity.entry(severity).or_default() += count;
+            }
+            merged
+        })
+    }
+}
+
+/// Keeps the first `max_findings` findings of `report`, counting the rest
+/// and their weaknesses in its `overflow`. The weaknesses stay listed.
+pub fn cap_findings(report: &mut JsonOutput, max_findings: usize) {
+    if report.findings.len() <= max_findings {
+        return;
+    }
+    let overflow = report
+        .overflow
+        .get_or_insert_with(|| FindingsOverflow::new(max_findings));
+    overflow.max_findings = max_findings;
+    let mut omitted = HashSet::new();
+    for finding in report.findings.drain(max_findings..) {
+        overflow.add(&finding);
+        omitted.insert(finding.fingerprint);
+    }
+    report
+        .weaknesses
+        .iter()
+        .filter(|w| omitted.contains(&w.finding))
+        .for_each(|w| overflow.add_weakness(w));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn finding(line: usize, algorithm: Option<&str>) -> Finding {
+        Finding {
+            algorithm: algorithm.map(str::to_string),
+            finding_type: Some("hash".to_string()),
+            fingerprint: format!("f{line}"),
+            ..test_support::finding("a.py", line, "hashlib.new")
+        }
+    }
+
+    fn weakness(finding: &str) -> Weakness {
+        Weakness {
+            finding: finding.to_string(),
+            ..test_support::weakness("a.py", "weak_hash", Severity::High)
+        }
+    }
+
+    #[test]
+    fn test_cap_findings() {
+        let mut report = test_support::report(
+            vec![
+                finding(1, Some("MD5")),
+                finding(2, Some("MD5")),
+                finding(3, Some("MD5")),
+                finding(4, None),
+            ],
+            vec![weakness("f1"), weakness("f3")],
+        );
+
+        cap_findings(&mut report, 2);
+        assert_eq!(report.findings.len(), 2);
+        assert_eq!(report.total_findings, 4);
+        let overflow = report.overflow.as_ref().unwrap();
+        assert_eq!(overflow.max_findings, 2);
+        assert_eq!(overflow.omitted, 2);
+        assert_eq!(overflow.by_type, BTreeMap::from([("hash".to_string(), 2)]));
+        assert_eq!(
+            overflow.by_algorithm,
+            BTreeMap::from([("MD5".to_string(), 1), ("unclassified".to_string(), 1)])
+        );
+        assert_eq!(
+            overflow.weaknesses_by_rule,
+            BTreeMap::from([("weak_hash".to_string(), 1)])
+        );
+        assert_eq!(
+            overflow.weaknesses_by_severity,
+            BTreeMap::from([(Severity::High, 1)])
+        );
+        assert_eq!(report.weaknesses.len(), 2);
+
+        cap_findings(&mut report, 5);
+        assert_eq!(report.overflow.unwrap().omitted, 2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut first = FindingsOverflow::new(10);
+        first.add(&finding(1, Some("MD5")));
+        let mut second = FindingsOverflow::new(10);
+        second.add(&finding(1, Some("MD5")));
+        second.add(&finding(2, Some("SHA-1")));
+
+        let merged = FindingsOverflow::merge([first, second]).unwrap();
+        assert_eq!(merged.omitted, 3);
+        assert_eq!(merged.by_algorithm["MD5"], 2);
+        assert_eq!(FindingsOverflow::merge([]), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! The coverage manifest for `--audit-coverage`: every file a directory
+//! scan found but did not parse, with why, so coverage can be attested
+//! from the report instead of inferred from the absence of findings.
+
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipCategory {
+    /// Imports no mapped package and names no weak algorithm.
+    NotCrypto,
+    /// Below a directory the loader does not enter, e.g. `testdata`; listed
+    /// by directory.
+    ExcludedDirectory,
+    /// Its extension is set to `skip` in the config file.
+    ExcludedExtension,
+    /// Over the size the import filter reads.
+    TooLarge,
+    ReadFailed,
+    /// The grammar could not parse it.
+    ParseError,
+    /// Written in a language no scan parses, or with no grammar for its
+    /// extension.
+    UnsupportedLanguage,
+}
+
+/// A file listed in the report's `skipped_files`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub file: String,
+    /// The language of the scan that skipped it.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub language: String,
+    pub reason: SkipCategory,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+/// Collects the files a scan skips from all workers. Disabled, it records
+/// nothing, so a scan without `--audit-coverage` keeps no list.
+#[derive(Debug, Default)]
+pub struct SkipLog {
+    enabled: bool,
+    files: Mutex<Vec<SkippedFile>>,
+}
+
+impl SkipLog {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            files: Mutex::default(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn record(&self, file: &Path, language: &str, reason: SkipCategory, detail: impl Display) {
+        if !self.enabled {
+            return;
+        }
+        self.files
+            .lock()
+            .expect("skip log poisoned")
+            .push(SkippedFile {
+                file: file.to_string_lossy().to_string(),
+                language: language.to_string(),
+                reason,
+                detail: detail.to_string(),
+            });
+    }
+
+    /// Files recorded since the last call, ordered by file, each listed
+    /// once per reason.
+    pub fn take(&self) -> Vec<SkippedFile> {
+        let mut files = std::mem::take(&mut *self.files.lock().expect("skip log poisoned"));
+        files.sort();
+        files.dedup_by(|a, b| a.file == b.file && a.reason == b.reason);
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_log_orders_and_dedups() {
+        let log = SkipLog::new(true);
+        log.record(Path::new("b.go"), "go", SkipCategory::NotCrypto, "");
+        log.record(
+            Path::new("a.go"),
+            "go",
+            SkipCategory::TooLarge,
+            "2000000 bytes",
+        );
+        log.record(Path::new("b.go"), "go", SkipCategory::NotCrypto, "");
+
+        let files = log.take();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file, "a.go");
+        assert_eq!(files[0].detail, "2000000 bytes");
+        assert_eq!(files[1].reason, SkipCategory::NotCrypto);
+        assert!(log.take().is_empty());
+
+        let json = serde_json::to_value(&files[1]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"file": "b.go", "language": "go", "reason": "not_crypto"})
+        );
+    }
+
+    #[test]
+    fn test_disabled_skip_log_records_nothing() {
+        let log = SkipLog::new(false);
+        log.record(Path::new("a.go"), "go", SkipCategory::NotCrypto, "");
+        assert!(log.take().is_empty());
+    }
+}
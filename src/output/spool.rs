@@ -0,0 +1,306 @@
+//! Bounded-memory result collection (`--max-memory`).
+//!
+//! Completed per-file results are kept in memory until their approximate
+//! size exceeds the budget; after that the retained results are written to a
+//! temporary JSON Lines file (one `ScanResult` per line) and read back one at
+//! a time when the output is produced.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use tempfile::NamedTempFile;
+use tracing::debug;
+
+use crate::scanner::ScanResult;
+
+/// The spill file, created under a fresh random name so nothing planted in
+/// the temporary directory is written through; removed when dropped.
+struct SpillFile {
+    file: NamedTempFile,
+    writer: BufWriter<File>,
+}
+
+impl SpillFile {
+    fn create() -> io::Result<Self> {
+        let file = tempfile::Builder::new()
+            .prefix("argflow-spill-")
+            .suffix(".jsonl")
+            .tempfile()?;
+        let writer = BufWriter::new(file.reopen()?);
+        Ok(Self { file, writer })
+    }
+
+    fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    fn write(&mut self, result: &ScanResult) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, result)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpoolTotals {
+    pub files: usize,
+    pub calls: usize,
+    pub configs: usize,
+}
+
+pub struct ResultSpool {
+    max_bytes: Option<usize>,
+    retained: Vec<ScanResult>,
+    retained_bytes: usize,
+    spill: Option<SpillFile>,
+    spilled: usize,
+    totals: SpoolTotals,
+}
+
+impl ResultSpool {
+    /// Creates a spool; `None` keeps every result in memory.
+    pub fn new(max_bytes: Option<usize>) -> Self {
+        Self {
+            max_bytes,
+            retained: Vec::new(),
+            retained_bytes: 0,
+            spill: None,
+            spilled: 0,
+            totals: SpoolTotals::default(),
+        }
+    }
+
+    pub fn push(&mut self, result: ScanResult) -> io::Result<()> {
+        self.totals.files += 1;
+        self.totals.calls += result.call_count();
+        self.totals.configs += result.config_count();
+        self.retained_bytes += result.approx_bytes();
+        self.retained.push(result);
+
+        if self.max_bytes.is_some_and(|max| self.retained_bytes > max) {
+            self.spill_retained()?;
+        }
+        Ok(())
+    }
+
+    fn spill_retained(&mut self) -> io::Result<()> {
+        if self.spill.is_none() {
+            self.spill = Some(SpillFile::create()?);
+        }
+        let spill = self.spill.as_mut().unwrap();
+
+        for result in self.retained.drain(..) {
+            spill.write(&result)?;
+            self.spilled += 1;
+        }
+        spill.writer.flush()?;
+
+        debug!(
+            bytes = self.retained_bytes,
+            spilled = self.spilled,
+            path = %spill.path().display(),
+            "memory budget exceeded, spilled results to disk"
+        );
+        self.retained_bytes = 0;
+        Ok(())
+    }
+
+    pub fn totals(&self) -> SpoolTotals {
+        self.totals
+    }
+
+    pub fn len(&self) -> usize {
+        self.totals.files
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.files == 0
+    }
+
+    pub fn has_spilled(&self) -> bool {
+        self.spilled > 0
+    }
+
+    pub fn spilled_count(&self) -> usize {
+        self.spilled
+    }
+
+    pub fn retained_bytes(&self) -> usize {
+        self.retained_bytes
+    }
+
+    pub fn spill_path(&self) -> Option<&Path> {
+        self.spill.as_ref().map(SpillFile::path)
+    }
+
+    /// Visits every result in insertion order, reading spilled ones back from disk.
+    pub fn for_each<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&ScanResult) -> io::Result<()>,
+    {
+        if let Some(spill) = self.spill.as_mut() {
+            spill.writer.flush()?;
+            let reader = BufReader::new(spill.file.reopen()?);
+            for line in reader.lines() {
+                let line = line?;
+                if line.is_empty() {
+                    continue;
+                }
+                let result: ScanResult = serde_json::from_str(&line)?;
+                f(&result)?;
+            }
+        }
+
+        for result in &self.retained {
+            f(result)?;
+        }
+        Ok(())
+    }
+
+    /// Collects all results into memory.
+    pub fn into_results(mut self) -> io::Result<Vec<ScanResult>> {
+        if self.spill.is_none() {
+            return Ok(std::mem::take(&mut self.retained));
+        }
+
+        let mut results = Vec::with_capacity(self.totals.files);
+        self.for_each(|r| {
+            results.push(r.clone());
+            Ok(())
+        })?;
+        Ok(results)
+    }
+}
+
+/// Parses sizes like `512M`, `2G`, `64k`, or a plain byte count.
+pub fn parse_byte_size(input: &str) -> Result<usize, String> {
+    let trimmed = input.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let digits_end = upper
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(upper.len());
+    let (number, unit) = upper.split_at(digits_end);
+
+    let value: usize = number
+        .parse()
+        .map_err(|_| format!("invalid size '{trimmed}'"))?;
+    let multiplier: usize = match unit.trim_end_matches("IB").trim_end_matches('B') {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        _ => return Err(format!("invalid size unit in '{trimmed}' (use K, M, or G)")),
+    };
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size '{trimmed}' is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Value;
+    use crate::scanner::Finding;
+
+    fn result_with_call(file: &str) -> ScanResult {
+        let mut result = ScanResult::new(file.to_string());
+        result.add_call(Finding {
+            file_path: file.to_string(),
+            line: 1,
+            column: 1,
+            function_name: "Key".to_string(),
+            package: Some("pbkdf2".to_string()),
+            import_path: Some("golang.org/x/crypto/pbkdf2".to_string()),
+            arguments: vec![Value::resolved_int(600000)],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
+            raw_text: "pbkdf2.Key(pw, salt, 600000, 32, sha256.New)".to_string(),
+            language: "go".to_string(),
+        });
+        result
+    }
+
+    #[test]
+    fn test_spool_without_budget_stays_in_memory() {
+        let mut spool = ResultSpool::new(None);
+        spool.push(result_with_call("a.go")).unwrap();
+        spool.push(result_with_call("b.go")).unwrap();
+
+        assert!(!spool.has_spilled());
+        assert_eq!(spool.totals().calls, 2);
+        let results = spool.into_results().unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_spool_spills_over_budget_and_preserves_order() {
+        let mut spool = ResultSpool::new(Some(1));
+        for name in ["a.go", "b.go", "c.go"] {
+            spool.push(result_with_call(name)).unwrap();
+        }
+
+        assert!(spool.has_spilled());
+        assert_eq!(spool.spilled_count(), 3);
+        assert_eq!(spool.retained_bytes(), 0);
+        assert!(spool.spill_path().unwrap().exists());
+
+        let mut seen = Vec::new();
+        spool
+            .for_each(|r| {
+                seen.push(r.file_path.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(seen, vec!["a.go", "b.go", "c.go"]);
+
+        let results = spool.into_results().unwrap();
+        assert_eq!(results[0].calls[0].arguments[0].int_values, vec![600000]);
+    }
+
+    #[test]
+    fn test_spill_file_removed_on_drop() {
+        let mut spool = ResultSpool::new(Some(1));
+        spool.push(result_with_call("a.go")).unwrap();
+        let path = spool.spill_path().unwrap().to_path_buf();
+
+        drop(spool);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_spill_does_not_write_through_existing_files() {
+        // The names spill files once had, predictable from the process ID
+        let planted: Vec<_> = (0..4)
+            .map(|n| {
+                std::env::temp_dir().join(format!("argflow-spill-{}-{n}.jsonl", std::process::id()))
+            })
+            .collect();
+        for path in &planted {
+            std::fs::write(path, "keep").unwrap();
+        }
+
+        let mut spool = ResultSpool::new(Some(1));
+        spool.push(result_with_call("a.go")).unwrap();
+        let spill_path = spool.spill_path().unwrap().to_path_buf();
+        drop(spool);
+
+        for path in &planted {
+            assert_ne!(path, &spill_path);
+            assert_eq!(std::fs::read_to_string(path).unwrap(), "keep");
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("1024"), Ok(1024));
+        assert_eq!(parse_byte_size("64k"), Ok(64 * 1024));
+        assert_eq!(parse_byte_size("512M"), Ok(512 * 1024 * 1024));
+        assert_eq!(parse_byte_size("2GB"), Ok(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_byte_size("1GiB"), Ok(1024 * 1024 * 1024));
+        assert!(parse_byte_size("lots").is_err());
+        assert!(parse_byte_size("5T").is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Per-file scan warnings, aggregated so a broken tree does not flood the
+//! log: the first few of each category are logged, the rest only counted,
+//! and every one is listed in the report's `errors`.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Warnings of each category logged before the rest are only counted.
+pub const DEFAULT_WARNING_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WarningCategory {
+    /// The file could not be read.
+    ReadFailed,
+    /// The file is over the size the import filter reads.
+    TooLarge,
+}
+
+impl WarningCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadFailed => "read_failed",
+            Self::TooLarge => "too_large",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            Self::ReadFailed => "failed to read file",
+            Self::TooLarge => "skipping file too large to filter",
+        }
+    }
+}
+
+/// A file the scan skipped, listed in the report's `errors`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanError {
+    pub file: String,
+    pub category: String,
+    pub message: String,
+}
+
+/// Collects the warnings of a scan from all workers, logging the first
+/// `limit` of each category as they happen.
+#[derive(Debug)]
+pub struct WarningLog {
+    limit: usize,
+    state: Mutex<WarningState>,
+}
+
+#[derive(Debug, Default)]
+struct WarningState {
+    counts: BTreeMap<WarningCategory, usize>,
+    errors: Vec<ScanError>,
+}
+
+impl Default for WarningLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_WARNING_LIMIT)
+    }
+}
+
+impl WarningLog {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn record(&self, category: WarningCategory, file: &Path, error: impl Display) {
+        let message = error.to_string();
+        let mut state = self.state.lock().expect("warning log poisoned");
+        let count = state.counts.entry(category).or_default();
+        *count += 1;
+        if *count <= self.limit {
+            warn!(file = %file.display(), error = %message, "{}", category.message());
+        }
+        state.errors.push(ScanError {
+            file: file.to_string_lossy().to_string(),
+            category: category.as_str().to_string(),
+            message,
+        });
+    }
+
+    /// Warnings recorded since the last call, ordered by file. The counts
+    /// for [`WarningLog::log_summary`] are kept.
+    pub fn take_errors(&self) -> Vec<ScanError> {
+        let mut errors =
+            std::mem::take(&mut self.state.lock().expect("warning log poisoned").errors);
+        errors.sort_by(|a, b| (&a.file, &a.category).cmp(&(&b.file, &b.category)));
+        errors
+    }
+
+    /// Warnings recorded so far, by category.
+    pub fn counts(&self) -> BTreeMap<WarningCategory, usize> {
+        self.state
+            .lock()
+            .expect("warning log poisoned")
+            .counts
+            .clone()
+    }
+
+    /// Logs one line per category that went over the limit.
+    pub fn log_summary(&self) {
+        for (category, count) in self.counts() {
+            if count > self.limit {
+                warn!(
+                    category = category.as_str(),
+                    total = count,
+                    suppressed = count - self.limit,
+                    "{} for {count} files; see the report's errors for all of them",
+                    category.message()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_log_keeps_every_warning_past_the_limit() {
+        let log = WarningLog::new(1);
+        log.record(WarningCategory::ReadFailed, Path::new("b.go"), "denied");
+        log.record(WarningCategory::ReadFailed, Path::new("a.go"), "denied");
+        log.record(WarningCategory::TooLarge, Path::new("c.go"), "big");
+
+        let counts = log.counts();
+        assert_eq!(counts[&WarningCategory::ReadFailed], 2);
+        assert_eq!(counts[&WarningCategory::TooLarge], 1);
+
+        let errors = log.take_errors();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].file, "a.go");
+        assert_eq!(errors[0].category, "read_failed");
+        assert_eq!(errors[0].message, "denied");
+        assert!(log.take_errors().is_empty());
+        assert_eq!(log.counts()[&WarningCategory::ReadFailed], 2);
+    }
+}
@@ -0,0 +1,430 @@
+//! Block cipher mode misuse: ECB, CBC with a constant IV, GCM nonces that
+//! are not 96 bits, and stream ciphers whose key and nonce are both
+//! constants.
+//!
+//! "Constant" means the argument resolved to a literal. Values that come
+//! from a call, a parameter or anything else the resolver could not follow
+//! are assumed to vary.
+
+use serde_json::json;
+
+use crate::output::Finding;
+
+use super::weakness::{Severity, Weakness};
+
+const ECB_RULE: &str = "cipher_ecb_mode";
+const CBC_STATIC_IV_RULE: &str = "cipher_cbc_static_iv";
+const GCM_NONCE_LENGTH_RULE: &str = "cipher_gcm_nonce_length";
+const STREAM_NONCE_REUSE_RULE: &str = "cipher_stream_nonce_reuse";
+
+/// GCM nonce length in bytes that is used directly as the counter block.
+/// Other lengths are hashed with GHASH first.
+const GCM_NONCE_BYTES: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Ecb,
+    Cbc,
+    Gcm,
+    /// CTR, OFB, CFB and stream ciphers such as ChaCha20: the keystream
+    /// depends only on the key and nonce, so a repeated pair repeats it.
+    Stream,
+}
+
+/// Position and keyword of an argument.
+type Arg = (Option<usize>, Option<&'static str>);
+
+enum ModeSource {
+    /// The API itself fixes the mode.
+    Fixed(Mode),
+    /// The mode is named by an argument, e.g. `"aes-256-cbc"` or
+    /// `AES.MODE_ECB`.
+    Named(Arg),
+}
+
+struct CipherApi {
+    modules: &'static [&'static str],
+    functions: &'static [&'static str],
+    mode: ModeSource,
+    /// Where the key is passed, when the call takes one.
+    key: Option<Arg>,
+    /// Where the IV or nonce may be passed, tried in order.
+    iv: &'static [Arg],
+}
+
+const PYCRYPTODOME_MODULES: &[&str] = &[
+    "Crypto.Cipher.AES",
+    "Crypto.Cipher.DES",
+    "Crypto.Cipher.DES3",
+    "Crypto.Cipher.Blowfish",
+    "Cryptodome.Cipher.AES",
+    "Cryptodome.Cipher.DES",
+    "Cryptodome.Cipher.DES3",
+    "Cryptodome.Cipher.Blowfish",
+];
+
+const CIPHER_APIS: &[CipherApi] = &[
+    // Go crypto/cipher
+    CipherApi {
+        modules: &["crypto/cipher"],
+        functions: &["NewCBCEncrypter", "NewCBCDecrypter"],
+        mode: ModeSource::Fixed(Mode::Cbc),
+        key: None,
+        iv: &[(Some(1), None)],
+    },
+    CipherApi {
+        modules: &["crypto/cipher"],
+        functions: &["NewCTR", "NewOFB", "NewCFBEncrypter", "NewCFBDecrypter"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: None,
+        iv: &[(Some(1), None)],
+    },
+    CipherApi {
+        modules: &["golang.org/x/crypto/chacha20"],
+        functions: &["NewUnauthenticatedCipher"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: Some((Some(0), None)),
+        iv: &[(Some(1), None)],
+    },
+    CipherApi {
+        modules: &["golang.org/x/crypto/salsa20"],
+        functions: &["XORKeyStream"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: Some((Some(3), None)),
+        iv: &[(Some(2), None)],
+    },
+    // cryptography
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.modes"],
+        functions: &["ECB"],
+        mode: ModeSource::Fixed(Mode::Ecb),
+        key: None,
+        iv: &[],
+    },
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.modes"],
+        functions: &["CBC"],
+        mode: ModeSource::Fixed(Mode::Cbc),
+        key: None,
+        iv: &[(Some(0), Some("initialization_vector"))],
+    },
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.modes"],
+        functions: &["GCM"],
+        mode: ModeSource::Fixed(Mode::Gcm),
+        key: None,
+        iv: &[(Some(0), Some("initialization_vector"))],
+    },
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.modes"],
+        functions: &["CTR"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: None,
+        iv: &[(Some(0), Some("nonce"))],
+    },
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.modes"],
+        functions: &["OFB", "CFB", "CFB8"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: None,
+        iv: &[(Some(0), Some("initialization_vector"))],
+    },
+    CipherApi {
+        modules: &["cryptography.hazmat.primitives.ciphers.algorithms"],
+        functions: &["ChaCha20"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: Some((Some(0), Some("key"))),
+        iv: &[(Some(1), Some("nonce"))],
+    },
+    // pycryptodome
+    CipherApi {
+        modules: PYCRYPTODOME_MODULES,
+        functions: &["new"],
+        mode: ModeSource::Named((Some(1), Some("mode"))),
+        key: Some((Some(0), Some("key"))),
+        iv: &[(Some(2), Some("iv")), (None, Some("nonce"))],
+    },
+    CipherApi {
+        modules: &[
+            "Crypto.Cipher.ChaCha20",
+            "Crypto.Cipher.Salsa20",
+            "Cryptodome.Cipher.ChaCha20",
+            "Cryptodome.Cipher.Salsa20",
+        ],
+        functions: &["new"],
+        mode: ModeSource::Fixed(Mode::Stream),
+        key: Some((None, Some("key"))),
+        iv: &[(None, Some("nonce"))],
+    },
+    // Node.js crypto
+    CipherApi {
+        modules: &["crypto"],
+        functions: &[
+            "createCipheriv",
+            "createDecipheriv",
+            "createCipher",
+            "createDecipher",
+        ],
+        mode: ModeSource::Named((Some(0), None)),
+        key: Some((Some(1), None)),
+        iv: &[(Some(2), None)],
+    },
+];
+
+/// Mode misuse findings for one call.
+pub(super) fn check_cipher(call: &Finding) -> Vec<Weakness> {
+    let api = CIPHER_APIS
+        .iter()
+        .find(|api| call.calls(api.modules, api.functions));
+
+    let mode = match api.map(|api| &api.mode) {
+        Some(ModeSource::Fixed(mode)) => Some(*mode),
+        Some(ModeSource::Named((position, keyword))) => call
+            .argument(*position, *keyword)
+            .and_then(text)
+            .and_then(mode_in),
+        None => None,
+    };
+    // A classification naming ECB (e.g. `AES-128-ECB`) counts for any call.
+    let mode = mode.or_else(|| {
+        call.algorithm
+            .as_deref()
+            .and_then(mode_in)
+            .filter(|mode| *mode == Mode::Ecb)
+    });
+
+    let Some(mode) = mode else {
+        return Vec::new();
+    };
+    if mode == Mode::Ecb {
+        return vec![Weakness::at(
+            call,
+            ECB_RULE,
+            Severity::High,
+            "ECB mode encrypts equal plaintext blocks to equal ciphertext blocks",
+            json!("ECB"),
+        )];
+    }
+
+    let Some(api) = api else {
+        return Vec::new();
+    };
+    let Some(iv) = api
+        .iv
+        .iter()
+        .find_map(|(position, keyword)| call.argument(*position, *keyword))
+    else {
+        return Vec::new();
+    };
+    let Some(iv_len) = constant_len(iv) else {
+        return Vec::new();
+    };
+
+    match mode {
+        Mode::Cbc => vec![Weakness::at(
+            call,
+            CBC_STATIC_IV_RULE,
+            Severity::Medium,
+            "CBC mode uses a constant IV, so equal message prefixes encrypt identically",
+            iv.clone(),
+        )],
+        Mode::Gcm => match iv_len {
+            Some(len) if len != GCM_NONCE_BYTES => vec![Weakness::at(
+                call,
+                GCM_NONCE_LENGTH_RULE,
+                Severity::Low,
+                format!("GCM nonce is {len} bytes instead of {GCM_NONCE_BYTES}"),
+                iv.clone(),
+            )],
+            _ => Vec::new(),
+        },
+        Mode::Stream => {
+            let key_constant = match api.key {
+                Some((position, keyword)) => call
+                    .argument(position, keyword)
+                    .is_some_and(|key| constant_len(key).is_some()),
+                None => true,
+            };
+            if !key_constant {
+                return Vec::new();
+            }
+            vec![Weakness::at(
+                call,
+                STREAM_NONCE_REUSE_RULE,
+                Severity::High,
+                "Stream cipher key and nonce are constants, so every message reuses the same keystream",
+                iv.clone(),
+            )]
+        }
+        Mode::Ecb => Vec::new(),
+    }
+}
+
+/// The mode named in a cipher name or mode constant, such as
+/// `aes-256-cbc`, `AES/GCM/NoPadding` or `AES.MODE_ECB`.
+fn mode_in(name: &str) -> Option<Mode> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .find_map(|token| match token.to_ascii_lowercase().as_str() {
+            "ecb" => Some(Mode::Ecb),
+            "cbc" => Some(Mode::Cbc),
+            "gcm" => Some(Mode::Gcm),
+            "ctr" | "ofb" | "cfb" | "cfb8" | "chacha20" | "salsa20" => Some(Mode::Stream),
+            _ => None,
+        })
+}
+
+/// A string value, or the expression text of a partially resolved one.
+fn text(value: &serde_json::Value) -> Option<&str> {
+    match value {
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Object(map) => map.get("value").and_then(|v| v.as_str()),
+        _ => None,
+    }
+}
+
+/// `Some` when `value` resolved to a literal, holding its length in bytes
+/// when that is known.
+fn constant_len(value: &serde_json::Value) -> Option<Option<usize>> {
+    match value {
+        serde_json::Value::String(s)
+            if matches!(s.as_str(), "null" | "None" | "nil" | "undefined") =>
+        {
+            None
+        }
+        serde_json::Value::String(s) => Some(Some(literal_len(s))),
+        serde_json::Value::Number(_) => Some(None),
+        serde_json::Value::Array(items) if items.iter().all(|item| item.is_number()) => {
+            Some(Some(items.len()))
+        }
+        _ => None,
+    }
+}
+
+/// Byte length of a resolved string. Python bytes literals keep their
+/// `b"..."` prefix and quotes and count each `\xNN` escape as one byte.
+fn literal_len(s: &str) -> usize {
+    let Some(body) = s.strip_prefix('b').and_then(|rest| {
+        rest.strip_prefix('"')
+            .and_then(|r| r.strip_suffix('"'))
+            .or_else(|| rest.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')))
+    }) else {
+        return s.len();
+    };
+
+    let bytes = body.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        i += match bytes[i..] {
+            [b'\\', b'x', ..] => 4,
+            [b'\\', ..] => 2,
+            _ => 1,
+        };
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn call(import_path: &str, function: &str, parameters: serde_json::Value) -> Finding {
+        Finding {
+            column: 5,
+            import_path: Some(import_path.to_string()),
+            parameters: test_support::parameters(parameters),
+            ..test_support::finding("crypt.py", 7, function)
+        }
+    }
+
+    fn rules(findings: &[Weakness]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_ecb_mode() {
+        let node = call(
+            "crypto",
+            "createCipheriv",
+            json!({"arg0": "aes-128-ecb", "arg1": {"source": "identifier", "value": null}, "arg2": "null"}),
+        );
+        let found = check_cipher(&node);
+        assert_eq!(rules(&found), vec![ECB_RULE]);
+        assert_eq!(found[0].severity, Severity::High);
+
+        let pycryptodome = call(
+            "Crypto.Cipher.AES",
+            "new",
+            json!({"arg0": {"source": "identifier", "value": null}, "arg1": {"source": "partial_expression", "value": "AES.MODE_ECB"}}),
+        );
+        assert_eq!(rules(&check_cipher(&pycryptodome)), vec![ECB_RULE]);
+    }
+
+    #[test]
+    fn test_cbc_static_iv() {
+        let mut cbc = call(
+            "cryptography.hazmat.primitives.ciphers.modes",
+            "CBC",
+            json!({"arg0": "b\"0123456789abcdef\""}),
+        );
+        cbc.keywords
+            .insert("arg0".into(), "initialization_vector".into());
+        let found = check_cipher(&cbc);
+        assert_eq!(rules(&found), vec![CBC_STATIC_IV_RULE]);
+        assert_eq!(found[0].severity, Severity::Medium);
+
+        let random = call(
+            "crypto/cipher",
+            "NewCBCEncrypter",
+            json!({"arg0": {"source": "identifier", "value": null}, "arg1": {"source": "function_not_found", "value": null}}),
+        );
+        assert!(check_cipher(&random).is_empty());
+    }
+
+    #[test]
+    fn test_gcm_nonce_length() {
+        let short = call(
+            "crypto",
+            "createCipheriv",
+            json!({"arg0": "aes-256-gcm", "arg1": {"source": "identifier", "value": null}, "arg2": "12345678"}),
+        );
+        let found = check_cipher(&short);
+        assert_eq!(rules(&found), vec![GCM_NONCE_LENGTH_RULE]);
+        assert_eq!(found[0].message, "GCM nonce is 8 bytes instead of 12");
+
+        let standard = call(
+            "cryptography.hazmat.primitives.ciphers.modes",
+            "GCM",
+            json!({"arg0": "b\"\\x00\\x01\\x02\\x03\\x04\\x05\\x06\\x07\\x08\\x09\\x0a\\x0b\""}),
+        );
+        assert!(check_cipher(&standard).is_empty());
+    }
+
+    #[test]
+    fn test_stream_key_and_nonce_constants() {
+        let reuse = call(
+            "golang.org/x/crypto/chacha20",
+            "NewUnauthenticatedCipher",
+            json!({"arg0": [1, 2, 3, 4], "arg1": [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]}),
+        );
+        let found = check_cipher(&reuse);
+        assert_eq!(rules(&found), vec![STREAM_NONCE_REUSE_RULE]);
+        assert_eq!(found[0].severity, Severity::High);
+
+        let derived_key = call(
+            "golang.org/x/crypto/chacha20",
+            "NewUnauthenticatedCipher",
+            json!({"arg0": {"source": "function_not_found", "value": null}, "arg1": [0, 0, 0, 0]}),
+        );
+        assert!(check_cipher(&derived_key).is_empty());
+
+        let ctr = call(
+            "crypto/cipher",
+            "NewCTR",
+            json!({"arg1": "0000000000000000"}),
+        );
+        assert_eq!(rules(&check_cipher(&ctr)), vec![STREAM_NONCE_REUSE_RULE]);
+    }
+}
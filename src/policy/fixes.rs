@@ -0,0 +1,1126 @@
+//! Machine-applicable fixes for a curated few findings, computed from the
+//! span each call or config literal was found at:
+//!
+//! - `pbkdf2_iterations`: a literal PBKDF2 iteration count below the
+//!   password policy is raised to its minimum.
+//! - `sha1_new`: Go `sha1.New()` becomes `sha256.New()`, importing
+//!   `crypto/sha256` next to `crypto/sha1`, or in its place when nothing
+//!   else in the file uses it.
+//! - `tls_min_version`: a Go `tls.Config` literal without `MinVersion`, or
+//!   with one below TLS 1.2, gets `MinVersion: tls.VersionTLS12`.
+//!
+//! A fix is only suggested when the finding's text is in the source where
+//! the finding says, so its edits apply to the scanned files as they are.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::output::{ConfigFinding, Finding};
+
+use super::password::pbkdf2_iterations_argument;
+use super::PasswordPolicy;
+
+const PBKDF2_RULE: &str = "pbkdf2_iterations";
+const SHA1_RULE: &str = "sha1_new";
+const TLS_RULE: &str = "tls_min_version";
+
+/// Every rule a fix can be suggested by.
+pub const FIX_RULES: &[&str] = &[PBKDF2_RULE, SHA1_RULE, TLS_RULE];
+
+const SHA1_IMPORT: &str = "\"crypto/sha1\"";
+const SHA256_IMPORT: &str = "\"crypto/sha256\"";
+const WEAK_TLS_VERSIONS: &[&str] = &["VersionSSL30", "VersionTLS10", "VersionTLS11"];
+
+/// Lines of unchanged source around each change in a diff.
+const CONTEXT_LINES: usize = 3;
+
+/// Bytes of a source file and the text replacing them.
+type Replacement<'a> = (Range<usize>, &'a str);
+
+/// The edits fixing one finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// Which fix, e.g. `tls_min_version`.
+    pub rule: String,
+    pub message: String,
+    /// Made together. Fixes of the same rule in a file can share an edit,
+    /// such as the import they all need, which is made once.
+    pub edits: Vec<TextEdit>,
+    /// Fingerprint of the finding or config this fixes.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub finding: String,
+}
+
+/// Replaces the text from the start position up to the end position with
+/// `replacement`. Lines and byte columns are 1-based and the end is
+/// exclusive, so an insertion starts and ends at the same position.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// The text replaced, which the file must still have for the edit to
+    /// be made; empty for an insertion.
+    #[serde(default)]
+    pub original: String,
+    pub replacement: String,
+}
+
+/// The fixes for `findings` and `configs`, reading each file they are in
+/// with `read_source`. Iteration counts are raised to the minimum of
+/// `policy`.
+pub fn suggest_fixes(
+    findings: &[Finding],
+    configs: &[ConfigFinding],
+    policy: &PasswordPolicy,
+    read_source: impl Fn(&str) -> Option<String>,
+) -> Vec<Fix> {
+    let mut sources = Sources {
+        read: read_source,
+        files: HashMap::new(),
+    };
+    let mut fixes = Vec::new();
+
+    let mut sha1_calls: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for call in findings {
+        if !sources.has_at(&call.file, call.line, call.column, &call.raw_text) {
+            continue;
+        }
+        if is_sha1_new(call) {
+            sha1_calls.entry(&call.file).or_default().push(call);
+        }
+        fixes.extend(pbkdf2_fix(call, policy));
+    }
+    for (file, calls) in sha1_calls {
+        let Some(import) = sources
+            .get(file)
+            .and_then(|s| sha1_import_edits(s, calls.len()))
+        else {
+            continue;
+        };
+        fixes.extend(calls.into_iter().map(|call| sha1_fix(call, &import)));
+    }
+    for config in configs {
+        if sources.has_at(&config.file, config.line, config.column, &config.raw_text) {
+            fixes.extend(tls_fix(config));
+        }
+    }
+
+    fixes.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.rule).cmp(&(&b.file, b.line, b.column, &b.rule))
+    });
+    fixes
+}
+
+/// `source` with `edits` made, or `None` when one lies outside it,
+/// overlaps another or would replace text other than its `original`. An
+/// edit repeated is made once.
+pub fn apply_edits(source: &str, edits: &[TextEdit]) -> Option<String> {
+    let mut fixed = String::with_capacity(source.len());
+    let mut copied = 0;
+    for (range, replacement) in edit_ranges(source, edits)? {
+        fixed.push_str(&source[copied..range.start]);
+        fixed.push_str(replacement);
+        copied = range.end;
+    }
+    fixed.push_str(&source[copied..]);
+    Some(fixed)
+}
+
+/// The edits of `fixes` as a unified diff, with the paths of the report
+/// (`patch -p0` applies it from where the scan ran). Files that cannot be
+/// read, or whose edits overlap, are left out.
+pub fn render_diff(fixes: &[Fix], read_source: impl Fn(&str) -> Option<String>) -> String {
+    let mut by_file: BTreeMap<&str, Vec<TextEdit>> = BTreeMap::new();
+    for fix in fixes {
+        by_file
+            .entry(&fix.file)
+            .or_default()
+            .extend(fix.edits.iter().cloned());
+    }
+    by_file
+        .into_iter()
+        .filter_map(|(file, edits)| file_diff(file, &read_source(file)?, &edits))
+        .collect()
+}
+
+/// Splits the fixes of one file into those whose edits can be made to
+/// `source` together, in order, and those that cannot: a fix whose text
+/// has changed since the scan, or whose edits overlap one already taken,
+/// is skipped rather than made in part.
+pub fn applicable_fixes<'a>(source: &str, fixes: &[&'a Fix]) -> (Vec<&'a Fix>, Vec<&'a Fix>) {
+    let mut edits = Vec::new();
+    let (mut applicable, mut skipped) = (Vec::new(), Vec::new());
+    for &fix in fixes {
+        let taken = edits.len();
+        edits.extend(fix.edits.iter().cloned());
+        if edit_ranges(source, &edits).is_some() {
+            applicable.push(fix);
+        } else {
+            edits.truncate(taken);
+            skipped.push(fix);
+        }
+    }
+    (applicable, skipped)
+}
+
+#[derive(Error, Debug)]
+#[error("cannot write {file}: {source}")]
+pub struct FixWriteError {
+    pub file: String,
+    #[source]
+    source: io::Error,
+}
+
+/// One file's fixes that apply, with the file as read and as fixed.
+struct PlannedFile<'a> {
+    file: &'a str,
+    source: String,
+    fixed: String,
+    edits: Vec<TextEdit>,
+}
+
+/// The fixes of a report for some rules, checked against the files as they
+/// are now. Fixes that no longer apply are set aside rather than made in
+/// part.
+pub struct FixPlan<'a> {
+    files: Vec<PlannedFile<'a>>,
+    fixes: Vec<&'a Fix>,
+    /// Files that cannot be read, with how many fixes each had.
+    pub unreadable: Vec<(&'a str, usize)>,
+    /// Fixes whose text has changed since the scan, or whose edits overlap
+    /// one already taken.
+    pub stale: Vec<&'a Fix>,
+}
+
+impl<'a> FixPlan<'a> {
+    /// Plans the `fixes` of the given `rules`, reading each file they are in
+    /// with `read_source`.
+    pub fn new(
+        fixes: &'a [Fix],
+        rules: &[String],
+        read_source: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let mut by_file: BTreeMap<&str, Vec<&Fix>> = BTreeMap::new();
+        for fix in fixes.iter().filter(|f| rules.contains(&f.rule)) {
+            by_file.entry(&fix.file).or_default().push(fix);
+        }
+        let mut plan = Self {
+            files: Vec::new(),
+            fixes: Vec::new(),
+            unreadable: Vec::new(),
+            stale: Vec::new(),
+        };
+        for (file, fixes) in by_file {
+            let Some(source) = read_source(file) else {
+                plan.unreadable.push((file, fixes.len()));
+                continue;
+            };
+            let (applicable, stale) = applicable_fixes(&source, &fixes);
+            plan.stale.extend(stale);
+            if applicable.is_empty() {
+                continue;
+            }
+            let edits: Vec<TextEdit> = applicable
+                .iter()
+                .flat_map(|f| f.edits.iter().cloned())
+                .collect();
+            // The edits of applicable fixes are known to apply together.
+            let Some(fixed) = apply_edits(&source, &edits) else {
+                continue;
+            };
+            plan.fixes.extend(applicable);
+            plan.files.push(PlannedFile {
+                file,
+                source,
+                fixed,
+                edits,
+            });
+        }
+        plan
+    }
+
+    /// The fixes that apply.
+    pub fn fixes(&self) -> &[&'a Fix] {
+        &self.fixes
+    }
+
+    /// How many files the fixes that apply are in.
+    pub fn files(&self) -> usize {
+        self.files.len()
+    }
+
+    /// How many fixes are skipped, in files that cannot be read or stale.
+    pub fn skipped(&self) -> usize {
+        self.unreadable.iter().map(|(_, n)| n).sum::<usize>() + self.stale.len()
+    }
+
+    /// The fixes that apply as a unified diff, as [`render_diff`] gives it.
+    pub fn diff(&self) -> String {
+        self.files
+            .iter()
+            .filter_map(|f| file_diff(f.file, &f.source, &f.edits))
+            .collect()
+    }
+
+    /// Writes each file fixed, first copying it to `<file>.orig` when
+    /// `backup` is set.
+    pub fn write(&self, backup: bool) -> Result<(), FixWriteError> {
+        let write = |file: String, contents: &str| {
+            fs::write(&file, contents).map_err(|source| FixWriteError { file, source })
+        };
+        for planned in &self.files {
+            if backup {
+                write(format!("{}.orig", planned.file), &planned.source)?;
+            }
+            write(planned.file.to_string(), &planned.fixed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Source files read once each.
+struct Sources<F> {
+    read: F,
+    files: HashMap<String, Option<String>>,
+}
+
+impl<F: Fn(&str) -> Option<String>> Sources<F> {
+    fn get(&mut self, file: &str) -> Option<&str> {
+        if !self.files.contains_key(file) {
+            self.files.insert(file.to_string(), (self.read)(file));
+        }
+        self.files.get(file)?.as_deref()
+    }
+
+    /// Whether `text` is in `file` at `line` and `column`.
+    fn has_at(&mut self, file: &str, line: usize, column: usize, text: &str) -> bool {
+        self.get(file).is_some_and(|source| {
+            offset(source, line, column).is_some_and(|start| source[start..].starts_with(text))
+        })
+    }
+}
+
+fn pbkdf2_fix(call: &Finding, policy: &PasswordPolicy) -> Option<Fix> {
+    let (position, keyword) = pbkdf2_iterations_argument(call)?;
+    let by_keyword = keyword.and_then(|keyword| {
+        call.keywords
+            .iter()
+            .find(|(_, k)| k.as_str() == keyword)
+            .and_then(|(arg, _)| arg.strip_prefix("arg")?.parse::<usize>().ok())
+    });
+    let index = by_keyword
+        .or(position.filter(|position| !call.keywords.contains_key(&format!("arg{position}"))))?;
+    let mut range = call_arguments(&call.raw_text).get(index)?.clone();
+    if by_keyword.is_some() {
+        let equals = call.raw_text[range.clone()].find('=')?;
+        range = trimmed(&call.raw_text, range.start + equals + 1..range.end);
+    }
+
+    let text = &call.raw_text[range.clone()];
+    if !text.bytes().all(|b| b.is_ascii_digit() || b == b'_') {
+        return None;
+    }
+    let iterations: i64 = text.replace('_', "").parse().ok()?;
+    let minimum = policy.pbkdf2_iterations;
+    if iterations >= minimum {
+        return None;
+    }
+    let replacement = match text.contains('_') {
+        true => grouped(minimum),
+        false => minimum.to_string(),
+    };
+    Some(Fix {
+        file: call.file.clone(),
+        line: call.line,
+        column: call.column,
+        rule: PBKDF2_RULE.to_string(),
+        message: format!("raise the PBKDF2 iteration count from {iterations} to {minimum}"),
+        edits: vec![edit_in(
+            call.line,
+            call.column,
+            &call.raw_text,
+            range,
+            replacement,
+        )],
+        finding: call.fingerprint.clone(),
+    })
+}
+
+fn is_sha1_new(call: &Finding) -> bool {
+    call.calls(&["crypto/sha1"], &["New"])
+        && callee(&call.raw_text)
+            .is_some_and(|callee| call.raw_text[callee].trim_end() == "sha1.New")
+}
+
+fn sha1_fix(call: &Finding, import: &[TextEdit]) -> Fix {
+    let callee = callee(&call.raw_text).unwrap_or_default();
+    let callee = trimmed(&call.raw_text, callee);
+    let mut edits = vec![edit_in(
+        call.line,
+        call.column,
+        &call.raw_text,
+        callee,
+        "sha256.New".to_string(),
+    )];
+    edits.extend(import.iter().cloned());
+    Fix {
+        file: call.file.clone(),
+        line: call.line,
+        column: call.column,
+        rule: SHA1_RULE.to_string(),
+        message: "replace sha1.New with sha256.New".to_string(),
+        edits,
+        finding: call.fingerprint.clone(),
+    }
+}
+
+/// The import changes rewriting `fixed` calls of `sha1.New` needs:
+/// `crypto/sha256` imported, and `crypto/sha1` dropped when nothing else
+/// uses it. `None` when `crypto/sha1` is not imported under its own name.
+fn sha1_import_edits(source: &str, fixed: usize) -> Option<Vec<TextEdit>> {
+    let (index, line) = source.lines().enumerate().find(|(_, line)| {
+        let line = line.trim();
+        line == SHA1_IMPORT || line.strip_prefix("import").map(str::trim) == Some(SHA1_IMPORT)
+    })?;
+    let number = index + 1;
+    let quote = line.find('"')?;
+    let imports_sha256 = source.contains(SHA256_IMPORT);
+    let still_used = qualified_uses(source, "sha1") > fixed;
+
+    let edit =
+        |start: (usize, usize), end: (usize, usize), original: String, replacement: String| {
+            TextEdit {
+                start_line: start.0,
+                start_column: start.1,
+                end_line: end.0,
+                end_column: end.1,
+                original,
+                replacement,
+            }
+        };
+    let end_of_line = (number, line.len() + 1);
+    Some(match (still_used, imports_sha256) {
+        (true, true) => Vec::new(),
+        (true, false) => vec![edit(
+            end_of_line,
+            end_of_line,
+            String::new(),
+            format!("\n{}{SHA256_IMPORT}", &line[..quote]),
+        )],
+        (false, false) => vec![edit(
+            (number, quote + 1),
+            (number, quote + 1 + SHA1_IMPORT.len()),
+            SHA1_IMPORT.to_string(),
+            SHA256_IMPORT.to_string(),
+        )],
+        (false, true) => vec![edit(
+            (number, 1),
+            (number + 1, 1),
+            format!("{line}\n"),
+            String::new(),
+        )],
+    })
+}
+
+/// References to members of package `name` outside comments, like
+/// `sha1.Size`.
+fn qualified_uses(source: &str, name: &str) -> usize {
+    let qualified = format!("{name}.");
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .map(|line| {
+            line.match_indices(&qualified)
+                .filter(|(i, _)| {
+                    !line[..*i]
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                })
+                .count()
+        })
+        .sum()
+}
+
+fn tls_fix(config: &ConfigFinding) -> Option<Fix> {
+    if config.full_type != "crypto/tls.Config" {
+        return None;
+    }
+    let text = &config.raw_text;
+    let brace = text.find('{')?;
+    let qualifier = text[..brace]
+        .trim_start_matches('&')
+        .strip_suffix(".Config")?;
+    if qualifier.is_empty() || !qualifier.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let tls12 = format!("{qualifier}.VersionTLS12");
+    let chars = structure(text);
+    let depth_at = |offset: usize| {
+        chars
+            .iter()
+            .find(|(i, _, _)| *i == offset)
+            .map(|(_, _, depth)| *depth)
+    };
+
+    let key = text.match_indices("MinVersion").map(|(i, _)| i).find(|&i| {
+        depth_at(i) == Some(1)
+            && !text[..i]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && text[i + "MinVersion".len()..].trim_start().starts_with(':')
+    });
+    let (range, replacement, message) = match key {
+        Some(key) => {
+            let colon = key + text[key..].find(':')?;
+            let end = chars
+                .iter()
+                .find(|(i, c, depth)| {
+                    *i > colon
+                        && ((*depth == 1 && matches!(c, ',' | '\n')) || (*depth == 0 && *c == '}'))
+                })
+                .map(|(i, _, _)| *i)?;
+            let range = trimmed(text, colon + 1..end);
+            let value = &text[range.clone()];
+            if !WEAK_TLS_VERSIONS.iter().any(|version| {
+                value
+                    .strip_suffix(version)
+                    .is_some_and(|q| q.is_empty() || q.ends_with('.'))
+            }) {
+                return None;
+            }
+            let message = format!("raise MinVersion from {value} to {tls12}");
+            (range, tls12, message)
+        }
+        None => {
+            let close = chars
+                .iter()
+                .rev()
+                .find(|(_, c, depth)| *c == '}' && *depth == 0)
+                .map(|(i, _, _)| *i)?;
+            let body = &text[brace + 1..close];
+            let setting = format!("MinVersion: {tls12}");
+            let (range, replacement) = if body.trim().is_empty() {
+                (brace + 1..close, setting)
+            } else if body.starts_with(['\n', '\r']) {
+                let indent: String = body
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+                    .unwrap_or_default();
+                (brace + 1..brace + 1, format!("\n{indent}{setting},"))
+            } else {
+                (brace + 1..brace + 1, format!("{setting}, "))
+            };
+            (range, replacement, format!("set MinVersion: {tls12}"))
+        }
+    };
+    Some(Fix {
+        file: config.file.clone(),
+        line: config.line,
+        column: config.column,
+        rule: TLS_RULE.to_string(),
+        message,
+        edits: vec![edit_in(
+            config.line,
+            config.column,
+            &config.raw_text,
+            range,
+            replacement,
+        )],
+        finding: config.fingerprint.clone(),
+    })
+}
+
+/// An edit of the bytes `range` of `text`, which starts at `line` and
+/// `column`.
+fn edit_in(
+    line: usize,
+    column: usize,
+    text: &str,
+    range: Range<usize>,
+    replacement: String,
+) -> TextEdit {
+    let (start_line, start_column) = position(text, line, column, range.start);
+    let (end_line, end_column) = position(text, line, column, range.end);
+    TextEdit {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+        original: text[range].to_string(),
+        replacement,
+    }
+}
+
+/// The line and column of byte `offset` of `text`, which starts at `line`
+/// and `column`.
+fn position(text: &str, line: usize, column: usize, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    match before.rfind('\n') {
+        Some(newline) => (line + before.matches('\n').count(), offset - newline),
+        None => (line, column + offset),
+    }
+}
+
+/// The byte offset of `line` and `column` in `source`.
+fn offset(source: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = match line.checked_sub(2) {
+        None if line == 1 => 0,
+        None => return None,
+        Some(newlines) => source.match_indices('\n').nth(newlines)?.0 + 1,
+    };
+    let offset = line_start + column.checked_sub(1)?;
+    (offset <= source.len() && source.is_char_boundary(offset)).then_some(offset)
+}
+
+/// The byte ranges `edits` replace in `source`, in order, each with its
+/// replacement.
+fn edit_ranges<'a>(source: &str, edits: &'a [TextEdit]) -> Option<Vec<Replacement<'a>>> {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort();
+    edits.dedup();
+    let mut ranges: Vec<Replacement> = Vec::new();
+    for edit in edits {
+        let start = offset(source, edit.start_line, edit.start_column)?;
+        let end = offset(source, edit.end_line, edit.end_column)?;
+        if end < start
+            || source[start..end] != edit.original
+            || ranges.last().is_some_and(|(last, _)| start < last.end)
+        {
+            return None;
+        }
+        ranges.push((start..end, edit.replacement.as_str()));
+    }
+    Some(ranges)
+}
+
+/// The byte offset, character and bracket depth of each character of
+/// `text` outside string and character literals. An opening bracket has
+/// the depth around it, and so does its closing one.
+fn structure(text: &str) -> Vec<(usize, char, usize)> {
+    let mut chars = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if let Some(open) = quote {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if c == open => quote = None,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => {
+                chars.push((i, c, depth));
+                depth += 1;
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                chars.push((i, c, depth));
+            }
+            _ => chars.push((i, c, depth)),
+        }
+    }
+    chars
+}
+
+/// The byte range of the callee of the call `raw_text`, before its
+/// argument list.
+fn callee(raw_text: &str) -> Option<Range<usize>> {
+    Some(0..raw_text.find('(')?)
+}
+
+/// The byte ranges of the arguments in the last argument list of the call
+/// `raw_text`, without the whitespace around them.
+fn call_arguments(raw_text: &str) -> Vec<Range<usize>> {
+    let chars = structure(raw_text);
+    let Some(open) = chars
+        .iter()
+        .rev()
+        .find(|(_, c, depth)| *c == '(' && *depth == 0)
+        .map(|(i, _, _)| *i)
+    else {
+        return Vec::new();
+    };
+    let Some(close) = chars
+        .iter()
+        .find(|(i, c, depth)| *i > open && *c == ')' && *depth == 0)
+        .map(|(i, _, _)| *i)
+    else {
+        return Vec::new();
+    };
+    let commas: Vec<usize> = chars
+        .iter()
+        .filter(|(i, c, depth)| *i > open && *i < close && *c == ',' && *depth == 1)
+        .map(|(i, _, _)| *i)
+        .collect();
+    let starts = std::iter::once(open + 1).chain(commas.iter().map(|comma| comma + 1));
+    let ends = commas.iter().copied().chain(std::iter::once(close));
+    starts
+        .zip(ends)
+        .map(|(start, end)| trimmed(raw_text, start..end))
+        .filter(|range| !range.is_empty())
+        .collect()
+}
+
+/// `range` of `text` without the whitespace at either end.
+fn trimmed(text: &str, range: Range<usize>) -> Range<usize> {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    start..end.max(start)
+}
+
+/// `n` with its digits grouped by `_`, like `600_000`.
+fn grouped(n: i64) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// The unified diff of `edits` to `source`.
+fn file_diff(file: &str, source: &str, edits: &[TextEdit]) -> Option<String> {
+    let ranges = edit_ranges(source, edits)?;
+    if ranges.is_empty() {
+        return None;
+    }
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut starts = Vec::with_capacity(lines.len() + 1);
+    let mut start = 0;
+    for line in &lines {
+        starts.push(start);
+        start += line.len();
+    }
+    starts.push(source.len());
+    let line_of = |offset: usize| starts.partition_point(|&start| start <= offset) - 1;
+
+    // Changed lines: the old lines first..end and the edits made to them,
+    // merging edits on the same line.
+    let mut changes: Vec<(usize, usize, Vec<Replacement>)> = Vec::new();
+    for (range, replacement) in ranges {
+        let first = line_of(range.start);
+        let end = if range.end > range.start && starts.binary_search(&range.end).is_ok() {
+            line_of(range.end)
+        } else {
+            line_of(range.end) + 1
+        }
+        .min(lines.len());
+        if first >= lines.len() {
+            return None;
+        }
+        match changes.last_mut() {
+            Some((_, last_end, edits)) if first < *last_end => {
+                *last_end = (*last_end).max(end);
+                edits.push((range, replacement));
+            }
+            _ => changes.push((first, end, vec![(range, replacement)])),
+        }
+    }
+    let changes: Vec<(usize, usize, String)> = changes
+        .into_iter()
+        .map(|(first, end, edits)| {
+            let block = starts[first]..starts[end];
+            let mut new = String::new();
+            let mut copied = block.start;
+            for (range, replacement) in edits {
+                new.push_str(&source[copied..range.start]);
+                new.push_str(replacement);
+                copied = range.end;
+            }
+            new.push_str(&source[copied..block.end]);
+            (first, end, new)
+        })
+        .collect();
+
+    let mut diff = format!("--- {file}\n+++ {file}\n");
+    let mut shift: isize = 0;
+    let mut i = 0;
+    while i < changes.len() {
+        // Changes whose context lines meet go in one hunk.
+        let mut j = i + 1;
+        while j < changes.len() && changes[j].0 <= changes[j - 1].1 + 2 * CONTEXT_LINES {
+            j += 1;
+        }
+        let hunk_start = changes[i].0.saturating_sub(CONTEXT_LINES);
+        let hunk_end = (changes[j - 1].1 + CONTEXT_LINES).min(lines.len());
+        let mut body = String::new();
+        let mut cursor = hunk_start;
+        let mut added = 0;
+        let mut removed = 0;
+        for (first, end, new) in &changes[i..j] {
+            for line in &lines[cursor..*first] {
+                push_line(&mut body, ' ', line);
+            }
+            for line in &lines[*first..*end] {
+                push_line(&mut body, '-', line);
+                removed += 1;
+            }
+            for line in new.split_inclusive('\n') {
+                push_line(&mut body, '+', line);
+                added += 1;
+            }
+            cursor = *end;
+        }
+        for line in &lines[cursor..hunk_end] {
+            push_line(&mut body, ' ', line);
+        }
+        let old_count = hunk_end - hunk_start;
+        let new_count = old_count + added - removed;
+        let new_start = hunk_start.checked_add_signed(shift).unwrap_or(0);
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(hunk_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        diff.push_str(&body);
+        shift += added as isize - removed as isize;
+        i = j;
+    }
+    Some(diff)
+}
+
+fn push_line(body: &mut String, marker: char, line: &str) {
+    body.push(marker);
+    body.push_str(line);
+    if !line.ends_with('\n') {
+        body.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// A hunk's line range: `start` is 0-based, the range 1-based, and an
+/// empty range names the line before it.
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{count}", start + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_support;
+
+    fn call(file: &str, line: usize, column: usize, import_path: &str, raw_text: &str) -> Finding {
+        let function = raw_text
+            .split('(')
+            .next()
+            .unwrap()
+            .rsplit('.')
+            .next()
+            .unwrap();
+        Finding {
+            column,
+            import_path: Some(import_path.to_string()),
+            raw_text: raw_text.to_string(),
+            fingerprint: "abc".to_string(),
+            ..test_support::finding(file, line, function)
+        }
+    }
+
+    fn config(file: &str, line: usize, column: usize, raw_text: &str) -> ConfigFinding {
+        serde_json::from_value(json!({
+            "file": file, "line": line, "column": column, "struct_type": "Config",
+            "full_type": "crypto/tls.Config",
+            "fields": [{"field_name": "InsecureSkipVerify", "value": "false"}],
+            "raw_text": raw_text
+        }))
+        .unwrap()
+    }
+
+    fn fixed(source: &str, fixes: &[Fix]) -> String {
+        let edits: Vec<TextEdit> = fixes.iter().flat_map(|f| f.edits.clone()).collect();
+        apply_edits(source, &edits).unwrap()
+    }
+
+    #[test]
+    fn test_pbkdf2_iterations() {
+        let go = "package kdf\n\nfunc derive(pw, salt []byte) []byte {\n\treturn pbkdf2.Key(pw, salt, 4096, 32, sha256.New)\n}\n";
+        let py =
+            "key = hashlib.pbkdf2_hmac(\n    'sha256', pw, salt,\n    iterations=100_000,\n)\n";
+        let sources = [("kdf.go", go), ("kdf.py", py)];
+        let read = |file: &str| {
+            sources
+                .iter()
+                .find(|(name, _)| *name == file)
+                .map(|(_, source)| source.to_string())
+        };
+        let go_call = call(
+            "kdf.go",
+            4,
+            9,
+            "golang.org/x/crypto/pbkdf2",
+            "pbkdf2.Key(pw, salt, 4096, 32, sha256.New)",
+        );
+        let mut py_call = call(
+            "kdf.py",
+            1,
+            7,
+            "hashlib",
+            "hashlib.pbkdf2_hmac(\n    'sha256', pw, salt,\n    iterations=100_000,\n)",
+        );
+        py_call
+            .keywords
+            .insert("arg3".to_string(), "iterations".to_string());
+
+        let fixes = suggest_fixes(&[go_call, py_call], &[], &PasswordPolicy::default(), read);
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].rule, PBKDF2_RULE);
+        assert_eq!(
+            fixes[0].message,
+            "raise the PBKDF2 iteration count from 4096 to 600000"
+        );
+        assert_eq!(
+            fixed(go, &fixes[..1]),
+            go.replace("salt, 4096, 32", "salt, 600000, 32")
+        );
+        assert_eq!(
+            fixes[1].edits,
+            vec![TextEdit {
+                start_line: 3,
+                start_column: 16,
+                end_line: 3,
+                end_column: 23,
+                original: "100_000".to_string(),
+                replacement: "600_000".to_string(),
+            }]
+        );
+        assert_eq!(fixed(py, &fixes[1..]), py.replace("100_000", "600_000"));
+    }
+
+    #[test]
+    fn test_moved_or_resolved_arguments_are_not_fixed() {
+        let source = "k := pbkdf2.Key(pw, salt, iterations, 32, sha256.New)\n";
+        let read = |_: &str| Some(source.to_string());
+        let by_name = call(
+            "kdf.go",
+            1,
+            6,
+            "golang.org/x/crypto/pbkdf2",
+            "pbkdf2.Key(pw, salt, iterations, 32, sha256.New)",
+        );
+        let moved = Finding {
+            line: 2,
+            ..by_name.clone()
+        };
+        assert!(suggest_fixes(&[by_name, moved], &[], &PasswordPolicy::default(), read).is_empty());
+    }
+
+    #[test]
+    fn test_sha1_new() {
+        let swapped = "package sum\n\nimport (\n\t\"crypto/sha1\"\n\t\"hash\"\n)\n\nfunc digest() hash.Hash {\n\treturn sha1.New()\n}\n";
+        let kept = "package sum\n\nimport \"crypto/sha1\"\n\nconst size = sha1.Size\n\nvar h = sha1.New()\n";
+        let sources = [("swapped.go", swapped), ("kept.go", kept)];
+        let read = |file: &str| {
+            sources
+                .iter()
+                .find(|(name, _)| *name == file)
+                .map(|(_, source)| source.to_string())
+        };
+        let calls = [
+            call("swapped.go", 9, 9, "crypto/sha1", "sha1.New()"),
+            call("kept.go", 7, 9, "crypto/sha1", "sha1.New()"),
+        ];
+
+        let fixes = suggest_fixes(&calls, &[], &PasswordPolicy::default(), read);
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].file, "kept.go");
+        assert_eq!(
+            fixed(kept, &fixes[..1]),
+            "package sum\n\nimport \"crypto/sha1\"\nimport \"crypto/sha256\"\n\nconst size = sha1.Size\n\nvar h = sha256.New()\n"
+        );
+        assert_eq!(
+            fixed(swapped, &fixes[1..]),
+            swapped
+                .replace("\"crypto/sha1\"", "\"crypto/sha256\"")
+                .replace("sha1.New()", "sha256.New()")
+        );
+    }
+
+    #[test]
+    fn test_applicable_fixes() {
+        let source =
+            "package sum\n\nimport \"crypto/sha1\"\n\nvar a = sha1.New()\nvar b = sha1.New()\n";
+        let calls = [
+            call("sum.go", 5, 9, "crypto/sha1", "sha1.New()"),
+            call("sum.go", 6, 9, "crypto/sha1", "sha1.New()"),
+        ];
+        let suggested = suggest_fixes(&calls, &[], &PasswordPolicy::default(), |_| {
+            Some(source.to_string())
+        });
+        let fixes: Vec<&Fix> = suggested.iter().collect();
+
+        let (applicable, skipped) = applicable_fixes(source, &fixes);
+        assert_eq!(applicable.len(), 2);
+        assert!(skipped.is_empty());
+
+        let edited = source.replace("var b = sha1.New()", "var b = sha1.Sum(nil)");
+        let (applicable, skipped) = applicable_fixes(&edited, &fixes);
+        assert_eq!(applicable, vec![fixes[0]]);
+        assert_eq!(skipped, vec![fixes[1]]);
+        assert_eq!(
+            fixed(&edited, &suggested[..1]),
+            "package sum\n\nimport \"crypto/sha256\"\n\nvar a = sha256.New()\nvar b = sha1.Sum(nil)\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_plan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sum = dir.path().join("sum.go");
+        let sum = sum.to_str().unwrap();
+        let source = "package sum\n\nimport \"crypto/sha1\"\n\nvar a = sha1.New()\n";
+        fs::write(sum, source).unwrap();
+        let calls = [
+            call(sum, 5, 9, "crypto/sha1", "sha1.New()"),
+            call("gone.go", 5, 9, "crypto/sha1", "sha1.New()"),
+        ];
+        let read = |file: &str| fs::read_to_string(file).ok();
+        let mut suggested = suggest_fixes(&calls[..1], &[], &PasswordPolicy::default(), read);
+        suggested.push(Fix {
+            file: "gone.go".to_string(),
+            ..suggested[0].clone()
+        });
+        let rules = vec![SHA1_RULE.to_string()];
+
+        let plan = FixPlan::new(&suggested, &rules, read);
+        assert_eq!(plan.fixes(), &[&suggested[0]]);
+        assert_eq!((plan.files(), plan.skipped()), (1, 1));
+        assert_eq!(plan.unreadable, vec![("gone.go", 1)]);
+        assert!(plan.diff().contains("+var a = sha256.New()"));
+        assert!(FixPlan::new(&suggested, &[], read).fixes().is_empty());
+
+        plan.write(true).unwrap();
+        let fixed = "package sum\n\nimport \"crypto/sha256\"\n\nvar a = sha256.New()\n";
+        assert_eq!(fs::read_to_string(sum).unwrap(), fixed);
+        assert_eq!(fs::read_to_string(format!("{sum}.orig")).unwrap(), source);
+
+        // Fixed already, so the plan has nothing to make.
+        let again = FixPlan::new(&suggested, &rules, read);
+        assert!(again.fixes().is_empty());
+        assert_eq!(again.stale.len(), 1);
+    }
+
+    #[test]
+    fn test_tls_min_version() {
+        let source = "var a = tls.Config{\n\t\tInsecureSkipVerify: false,\n\t}\nvar b = &tls.Config{MinVersion: tls.VersionTLS10, InsecureSkipVerify: false}\nvar c = tls.Config{InsecureSkipVerify: false, MinVersion: tls.VersionTLS13}\nvar d = tls.Config{InsecureSkipVerify: false}\n";
+        let read = |_: &str| Some(source.to_string());
+        let configs = [
+            config(
+                "tls.go",
+                1,
+                9,
+                "tls.Config{\n\t\tInsecureSkipVerify: false,\n\t}",
+            ),
+            config(
+                "tls.go",
+                4,
+                10,
+                "tls.Config{MinVersion: tls.VersionTLS10, InsecureSkipVerify: false}",
+            ),
+            config(
+                "tls.go",
+                5,
+                9,
+                "tls.Config{InsecureSkipVerify: false, MinVersion: tls.VersionTLS13}",
+            ),
+            config("tls.go", 6, 9, "tls.Config{InsecureSkipVerify: false}"),
+        ];
+
+        let fixes = suggest_fixes(&[], &configs, &PasswordPolicy::default(), read);
+        let messages: Vec<&str> = fixes.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "set MinVersion: tls.VersionTLS12",
+                "raise MinVersion from tls.VersionTLS10 to tls.VersionTLS12",
+                "set MinVersion: tls.VersionTLS12",
+            ]
+        );
+        assert_eq!(
+            fixed(source, &fixes),
+            "var a = tls.Config{\n\t\tMinVersion: tls.VersionTLS12,\n\t\tInsecureSkipVerify: false,\n\t}\nvar b = &tls.Config{MinVersion: tls.VersionTLS12, InsecureSkipVerify: false}\nvar c = tls.Config{InsecureSkipVerify: false, MinVersion: tls.VersionTLS13}\nvar d = tls.Config{MinVersion: tls.VersionTLS12, InsecureSkipVerify: false}\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_rejects_overlaps() {
+        let source = "md5(data)\n";
+        let edit = |start_column: usize, end_column: usize, replacement: &str| TextEdit {
+            start_line: 1,
+            start_column,
+            end_line: 1,
+            end_column,
+            original: source
+                .get(start_column - 1..end_column - 1)
+                .unwrap_or_default()
+                .to_string(),
+            replacement: replacement.to_string(),
+        };
+        assert_eq!(
+            apply_edits(source, &[edit(1, 4, "sha256"), edit(1, 4, "sha256")]).as_deref(),
+            Some("sha256(data)\n")
+        );
+        assert_eq!(
+            apply_edits(source, &[edit(1, 4, "a"), edit(2, 5, "b")]),
+            None
+        );
+        assert_eq!(apply_edits(source, &[edit(1, 40, "a")]), None);
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let source: String = (1..=14).map(|n| format!("line {n}\n")).collect();
+        let fix = |line: usize, replacement: &str| Fix {
+            file: "a.go".to_string(),
+            line,
+            column: 1,
+            rule: TLS_RULE.to_string(),
+            message: String::new(),
+            edits: vec![TextEdit {
+                start_line: line,
+                start_column: 6,
+                end_line: line,
+                end_column: 6 + line.to_string().len(),
+                original: line.to_string(),
+                replacement: replacement.to_string(),
+            }],
+            finding: String::new(),
+        };
+        let fixes = [fix(2, "two"), fix(5, "five\nline 5b"), fix(13, "thirteen")];
+
+        let diff = render_diff(&fixes, |_| Some(source.clone()));
+        assert_eq!(
+            diff,
+            "--- a.go\n+++ a.go\n\
+             @@ -1,8 +1,9 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n-line 5\n+line five\n+line 5b\n line 6\n line 7\n line 8\n\
+             @@ -10,5 +11,5 @@\n line 10\n line 11\n line 12\n-line 13\n+line thirteen\n line 14\n"
+        );
+        assert_eq!(render_diff(&fixes, |_| None), "");
+    }
+}
@@ -0,0 +1,271 @@
+//! Release gate: checks a report against the crypto approved for a
+//! release, listed in a manifest of algorithms and key sizes. Anything the
+//! scan finds outside it fails the gate, and the result lists every
+//! algorithm and key size in use with the calls behind it, so the release
+//! can be signed off or the manifest extended on purpose.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ConfigError;
+use crate::output::Finding;
+
+/// Operation of the findings whose integer arguments are key sizes.
+const KEYGEN: &str = "keygen";
+
+/// The crypto approved for a release.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleaseManifest {
+    /// Algorithms as reported, e.g. `AES-GCM` or `RSA`.
+    #[serde(default)]
+    pub algorithms: Vec<String>,
+    /// Key sizes by algorithm, e.g. `RSA` -> `[3072, 4096]`. Key sizes of
+    /// an algorithm missing here are not checked.
+    #[serde(default)]
+    pub key_sizes: BTreeMap<String, Vec<i64>>,
+}
+
+impl ReleaseManifest {
+    /// Loads the JSON manifest at `path`.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content =
+            fs::read_to_string(path).map_err(|e| ConfigError::read_error(path, e.to_string()))?;
+        serde_json::from_str(&content).map_err(|e| ConfigError::parse_error(path, e.to_string()))
+    }
+
+    fn approves_algorithm(&self, algorithm: &str) -> bool {
+        self.algorithms
+            .iter()
+            .any(|approved| approved.eq_ignore_ascii_case(algorithm))
+    }
+
+    fn approves_key_size(&self, algorithm: &str, key_size: i64) -> bool {
+        self.key_sizes
+            .iter()
+            .find(|(approved, _)| approved.eq_ignore_ascii_case(algorithm))
+            .is_none_or(|(_, sizes)| sizes.contains(&key_size))
+    }
+}
+
+/// Outcome of [`check_gate`], written as the sign-off report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GateReport {
+    pub passed: bool,
+    /// Entries not in the manifest.
+    pub violations: usize,
+    /// Every algorithm, and key size of an algorithm, the scan found.
+    pub entries: Vec<GateEntry>,
+    /// Manifest algorithms the scan did not find, which could be dropped
+    /// from it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unused: Vec<String>,
+}
+
+/// One algorithm, or key size of an algorithm, in use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateEntry {
+    pub algorithm: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_size: Option<i64>,
+    pub approved: bool,
+    /// The calls using it.
+    pub findings: Vec<GateFinding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateFinding {
+    pub file: String,
+    pub line: usize,
+    pub function: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
+}
+
+impl From<&Finding> for GateFinding {
+    fn from(finding: &Finding) -> Self {
+        Self {
+            file: finding.file.clone(),
+            line: finding.line,
+            function: finding.full_name.clone(),
+            fingerprint: finding.fingerprint.clone(),
+        }
+    }
+}
+
+/// Checks the algorithms of `findings`, and the key sizes of their key
+/// generation calls, against `manifest`. Findings without an algorithm are
+/// left out.
+pub fn check_gate(findings: &[Finding], manifest: &ReleaseManifest) -> GateReport {
+    let mut used: BTreeMap<(String, Option<i64>), Vec<GateFinding>> = BTreeMap::new();
+    for finding in findings {
+        let Some(algorithm) = &finding.algorithm else {
+            continue;
+        };
+        used.entry((algorithm.clone(), None))
+            .or_default()
+            .push(finding.into());
+        for key_size in key_sizes(finding) {
+            used.entry((algorithm.clone(), Some(key_size)))
+                .or_default()
+                .push(finding.into());
+        }
+    }
+
+    let entries: Vec<GateEntry> = used
+        .into_iter()
+        .map(|((algorithm, key_size), findings)| {
+            let approved = match key_size {
+                None => manifest.approves_algorithm(&algorithm),
+                Some(size) => manifest.approves_key_size(&algorithm, size),
+            };
+            GateEntry {
+                algorithm,
+                key_size,
+                approved,
+                findings,
+            }
+        })
+        .collect();
+
+    let found: BTreeSet<String> = entries
+        .iter()
+        .map(|e| e.algorithm.to_ascii_lowercase())
+        .collect();
+    let unused = manifest
+        .algorithms
+        .iter()
+        .filter(|a| !found.contains(&a.to_ascii_lowercase()))
+        .cloned()
+        .collect();
+
+    let violations = entries.iter().filter(|e| !e.approved).count();
+    GateReport {
+        passed: violations == 0,
+        violations,
+        entries,
+        unused,
+    }
+}
+
+/// Resolved integer arguments of a key generation call, leaving out those
+/// passed as an exponent (`public_exponent=65537`).
+fn key_sizes(finding: &Finding) -> BTreeSet<i64> {
+    if finding.operation.as_deref() != Some(KEYGEN) {
+        return BTreeSet::new();
+    }
+    finding
+        .parameters
+        .iter()
+        .filter(|(arg, _)| {
+            finding
+                .keywords
+                .get(*arg)
+                .is_none_or(|keyword| !keyword.to_ascii_lowercase().contains("exponent"))
+        })
+        .flat_map(|(_, value)| match value {
+            serde_json::Value::Array(items) => items.iter().filter_map(|v| v.as_i64()).collect(),
+            value => value.as_i64().into_iter().collect::<Vec<_>>(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_support;
+
+    fn finding(
+        line: usize,
+        algorithm: &str,
+        operation: &str,
+        parameters: serde_json::Value,
+    ) -> Finding {
+        Finding {
+            algorithm: Some(algorithm.to_string()),
+            operation: Some(operation.to_string()),
+            parameters: test_support::parameters(parameters),
+            keywords: [("arg0".to_string(), "public_exponent".to_string())].into(),
+            ..test_support::finding("crypto.py", line, "lib.f")
+        }
+    }
+
+    fn manifest() -> ReleaseManifest {
+        serde_json::from_value(json!({
+            "algorithms": ["AES-GCM", "RSA", "SHA-256"],
+            "key_sizes": {"RSA": [3072, 4096]}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_load_manifest() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("release.json");
+        fs::write(&path, r#"{"algorithms": ["AES-GCM"]}"#).unwrap();
+        let loaded = ReleaseManifest::load(&path).unwrap();
+        assert_eq!(loaded.algorithms, vec!["AES-GCM".to_string()]);
+
+        fs::write(&path, r#"{"algorithm": ["AES-GCM"]}"#).unwrap();
+        assert!(matches!(
+            ReleaseManifest::load(&path),
+            Err(ConfigError::ParseError { .. })
+        ));
+        assert!(matches!(
+            ReleaseManifest::load(&dir.path().join("missing.json")),
+            Err(ConfigError::ReadError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_gate() {
+        let findings = [
+            finding(1, "AES-GCM", "encrypt", json!({"arg0": "key"})),
+            finding(2, "RSA", "keygen", json!({"arg0": 65537, "arg1": 4096})),
+            finding(
+                3,
+                "rsa",
+                "keygen",
+                json!({"arg0": 65537, "arg1": [2048, 4096]}),
+            ),
+        ];
+        let report = check_gate(&findings, &manifest());
+
+        assert!(!report.passed);
+        assert_eq!(report.violations, 1);
+        let violation = report.entries.iter().find(|e| !e.approved).unwrap();
+        assert_eq!(violation.algorithm, "rsa");
+        assert_eq!(violation.key_size, Some(2048));
+        assert_eq!(violation.findings[0].line, 3);
+        assert!(report.entries.iter().all(|e| e.key_size != Some(65537)));
+        assert_eq!(report.unused, vec!["SHA-256"]);
+    }
+
+    #[test]
+    fn test_unapproved_algorithm() {
+        let findings = [
+            finding(1, "MD5", "hash", json!({})),
+            finding(2, "SHA-256", "hash", json!({})),
+        ];
+        let report = check_gate(&findings, &manifest());
+
+        assert!(!report.passed);
+        assert_eq!(report.violations, 1);
+        assert_eq!(report.entries[0].algorithm, "MD5");
+        assert!(!report.entries[0].approved);
+    }
+
+    #[test]
+    fn test_key_sizes_of_unlisted_algorithm_not_checked() {
+        let findings = [finding(1, "AES-GCM", "keygen", json!({"arg1": 128}))];
+        let report = check_gate(&findings, &manifest());
+
+        assert!(report.passed);
+        assert_eq!(report.entries.len(), 2);
+    }
+}
@@ -0,0 +1,251 @@
+//! JWT verification checks: `alg: none`, HMAC/asymmetric algorithm confusion
+//! and weak entries in the allowed-algorithm list.
+
+use serde_json::json;
+
+use crate::output::Finding;
+
+use super::weakness::{Severity, Weakness};
+
+/// Weak entries for an allowed-algorithm list: RSA PKCS#1 v1.5 key
+/// encryption (JWE) and non-standard SHA-1 signature variants.
+const WEAK_ALGORITHMS: &[&str] = &["RSA1_5", "HS1", "RS1"];
+
+struct JwtApi {
+    modules: &'static [&'static str],
+    functions: &'static [&'static str],
+    /// Position and keyword of the verification key.
+    key: Option<(usize, Option<&'static str>)>,
+    /// Position and keyword of the allowed algorithms, or of an options
+    /// object carrying them.
+    algorithms: (usize, Option<&'static str>),
+}
+
+const JWT_APIS: &[JwtApi] = &[
+    // PyJWT
+    JwtApi {
+        modules: &["jwt"],
+        functions: &["decode", "decode_complete"],
+        key: Some((1, Some("key"))),
+        algorithms: (2, Some("algorithms")),
+    },
+    // python-jose
+    JwtApi {
+        modules: &["jose.jwt", "jose.jws"],
+        functions: &["decode", "verify"],
+        key: Some((1, Some("key"))),
+        algorithms: (2, Some("algorithms")),
+    },
+    // jsonwebtoken
+    JwtApi {
+        modules: &["jsonwebtoken"],
+        functions: &["verify"],
+        key: Some((1, None)),
+        algorithms: (2, None),
+    },
+    // jose (JavaScript)
+    JwtApi {
+        modules: &["jose"],
+        functions: &[
+            "jwtVerify",
+            "compactVerify",
+            "flattenedVerify",
+            "generalVerify",
+        ],
+        key: Some((1, None)),
+        algorithms: (2, None),
+    },
+    // golang-jwt
+    JwtApi {
+        modules: &[
+            "github.com/golang-jwt/jwt/v5",
+            "github.com/golang-jwt/jwt/v4",
+            "github.com/golang-jwt/jwt",
+            "github.com/dgrijalva/jwt-go",
+        ],
+        functions: &["WithValidMethods"],
+        key: None,
+        algorithms: (0, None),
+    },
+];
+
+/// Weaknesses for one JWT verification call.
+pub(super) fn check_jwt(call: &Finding) -> Vec<Weakness> {
+    let Some(api) = JWT_APIS
+        .iter()
+        .find(|api| call.calls(api.modules, api.functions))
+    else {
+        return Vec::new();
+    };
+
+    let (position, keyword) = api.algorithms;
+    let mut algorithms = Vec::new();
+    if let Some(value) = call.argument(Some(position), keyword) {
+        collect_algorithms(value, &mut algorithms);
+    }
+    if algorithms.is_empty() {
+        return Vec::new();
+    }
+    let evidence = json!(algorithms);
+    let mut found = Vec::new();
+
+    if algorithms.iter().any(|a| a.eq_ignore_ascii_case("none")) {
+        found.push(Weakness::at(
+            call,
+            "jwt_alg_none",
+            Severity::High,
+            "JWT verification accepts unsigned tokens (alg \"none\")",
+            evidence.clone(),
+        ));
+    }
+
+    let hmac = algorithms.iter().any(|a| a.starts_with("HS"));
+    let asymmetric = algorithms.iter().any(|a| is_asymmetric(a));
+    let public_key = api
+        .key
+        .and_then(|(position, keyword)| call.argument(Some(position), keyword))
+        .and_then(|key| key.as_str())
+        .is_some_and(is_public_key);
+    if hmac && (asymmetric || public_key) {
+        let message = if asymmetric {
+            "JWT verification allows both HMAC and public-key algorithms, so a public key can be used as an HMAC secret"
+        } else {
+            "JWT verification uses a public key with an HMAC algorithm"
+        };
+        found.push(Weakness::at(
+            call,
+            "jwt_algorithm_confusion",
+            Severity::High,
+            message,
+            evidence.clone(),
+        ));
+    }
+
+    let weak: Vec<&String> = algorithms
+        .iter()
+        .filter(|a| WEAK_ALGORITHMS.contains(&a.as_str()))
+        .collect();
+    if !weak.is_empty() {
+        found.push(Weakness::at(
+            call,
+            "jwt_weak_algorithm",
+            Severity::High,
+            format!(
+                "JWT allowed algorithms include weak entries: {}",
+                weak.iter()
+                    .map(|a| a.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            evidence,
+        ));
+    }
+
+    found
+}
+
+/// Algorithm names among the resolved value. Options objects are resolved
+/// to their property values, so other strings are ignored.
+fn collect_algorithms(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) if is_algorithm(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_algorithms(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_algorithm(name: &str) -> bool {
+    if name.eq_ignore_ascii_case("none") || name == "EdDSA" || WEAK_ALGORITHMS.contains(&name) {
+        return true;
+    }
+    ["HS", "RS", "PS", "ES"].iter().any(|prefix| {
+        name.strip_prefix(prefix).is_some_and(|bits| {
+            let bits = bits.strip_suffix('K').unwrap_or(bits);
+            !bits.is_empty() && bits.bytes().all(|b| b.is_ascii_digit())
+        })
+    })
+}
+
+fn is_asymmetric(name: &str) -> bool {
+    name == "EdDSA" || ["RS", "PS", "ES"].iter().any(|p| name.starts_with(p))
+}
+
+fn is_public_key(key: &str) -> bool {
+    key.contains("PUBLIC KEY") || key.contains("BEGIN CERTIFICATE") || key.starts_with("ssh-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn call(import_path: &str, function: &str, parameters: serde_json::Value) -> Finding {
+        Finding {
+            import_path: Some(import_path.to_string()),
+            parameters: test_support::parameters(parameters),
+            ..test_support::finding("auth.py", 4, function)
+        }
+    }
+
+    fn rules(findings: &[Weakness]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_alg_none_in_options_object() {
+        let verify = call(
+            "jsonwebtoken",
+            "verify",
+            json!({"arg2": ["none", "my-audience"]}),
+        );
+
+        let found = check_jwt(&verify);
+        assert_eq!(rules(&found), vec!["jwt_alg_none"]);
+        assert_eq!(found[0].severity, Severity::High);
+        assert_eq!(found[0].evidence, json!(["none"]));
+    }
+
+    #[test]
+    fn test_mixed_hmac_and_rsa_is_confusion() {
+        let mut decode = call(
+            "jwt",
+            "decode",
+            json!({"arg1": {"source": "identifier", "value": null}, "arg2": ["HS256", "RS256"]}),
+        );
+        decode.keywords.insert("arg2".into(), "algorithms".into());
+
+        assert_eq!(rules(&check_jwt(&decode)), vec!["jwt_algorithm_confusion"]);
+    }
+
+    #[test]
+    fn test_public_key_with_hmac_is_confusion() {
+        let decode = call(
+            "jose.jwt",
+            "decode",
+            json!({"arg1": "-----BEGIN PUBLIC KEY-----\nMIIB", "arg2": "HS256"}),
+        );
+
+        assert_eq!(rules(&check_jwt(&decode)), vec!["jwt_algorithm_confusion"]);
+    }
+
+    #[test]
+    fn test_weak_and_safe_lists() {
+        let weak = call(
+            "github.com/golang-jwt/jwt/v5",
+            "WithValidMethods",
+            json!({"arg0": ["RS256", "RS1"]}),
+        );
+        assert_eq!(rules(&check_jwt(&weak)), vec!["jwt_weak_algorithm"]);
+
+        let safe = call(
+            "github.com/golang-jwt/jwt/v5",
+            "WithValidMethods",
+            json!({"arg0": ["ES256"]}),
+        );
+        assert!(check_jwt(&safe).is_empty());
+    }
+}
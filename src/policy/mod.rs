@@ -0,0 +1,24 @@
+//! Policy checks run over a finished report.
+
+mod cipher;
+mod fixes;
+mod gate;
+mod jwt;
+mod password;
+mod risk;
+mod tls;
+mod waiver;
+mod weakness;
+
+pub use fixes::{
+    applicable_fixes, apply_edits, render_diff, suggest_fixes, Fix, FixPlan, FixWriteError,
+    TextEdit, FIX_RULES,
+};
+pub use gate::{check_gate, GateEntry, GateFinding, GateReport, ReleaseManifest};
+pub use password::{
+    check_password_storage, CostCheck, PasswordHashCheck, PasswordPolicy, PasswordScheme,
+    PolicyStatus,
+};
+pub use risk::{is_test_file, risk_score};
+pub use waiver::{apply_waivers, today, ExpiredWaiver, Waiver, WaiverReport};
+pub use weakness::{check_weaknesses, Severity, Weakness};
@@ -0,0 +1,467 @@
+//! Password storage policy.
+//!
+//! Recognizes the common password hashing APIs (PBKDF2, bcrypt, scrypt,
+//! Argon2), reads their cost factors from the resolved arguments and checks
+//! them against minimums that default to the current OWASP Password Storage
+//! Cheat Sheet recommendations.
+
+use serde::{Deserialize, Serialize};
+
+use crate::output::Finding;
+
+/// Minimum cost factors, configured under `password_policy` in `--config`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasswordPolicy {
+    /// PBKDF2 iterations (OWASP figure for HMAC-SHA256).
+    pub pbkdf2_iterations: i64,
+    pub bcrypt_cost: i64,
+    pub scrypt_n: i64,
+    pub scrypt_r: i64,
+    pub scrypt_p: i64,
+    pub argon2_memory_kib: i64,
+    pub argon2_iterations: i64,
+    pub argon2_parallelism: i64,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            pbkdf2_iterations: 600_000,
+            bcrypt_cost: 10,
+            scrypt_n: 1 << 17,
+            scrypt_r: 8,
+            scrypt_p: 1,
+            argon2_memory_kib: 19_456,
+            argon2_iterations: 2,
+            argon2_parallelism: 1,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    fn minimum(&self, scheme: PasswordScheme, param: &str) -> Option<i64> {
+        match (scheme, param) {
+            (PasswordScheme::Pbkdf2, "iterations") => Some(self.pbkdf2_iterations),
+            (PasswordScheme::Bcrypt, "cost") => Some(self.bcrypt_cost),
+            (PasswordScheme::Scrypt, "n") => Some(self.scrypt_n),
+            (PasswordScheme::Scrypt, "r") => Some(self.scrypt_r),
+            (PasswordScheme::Scrypt, "p") => Some(self.scrypt_p),
+            (PasswordScheme::Argon2, "memory_kib") => Some(self.argon2_memory_kib),
+            (PasswordScheme::Argon2, "iterations") => Some(self.argon2_iterations),
+            (PasswordScheme::Argon2, "parallelism") => Some(self.argon2_parallelism),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordScheme {
+    Pbkdf2,
+    Bcrypt,
+    Scrypt,
+    Argon2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyStatus {
+    Pass,
+    /// A cost factor could not be resolved to a number.
+    Unknown,
+    Fail,
+}
+
+/// One password hashing call and how its cost factors compare to the policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordHashCheck {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub function: String,
+    pub scheme: PasswordScheme,
+    pub status: PolicyStatus,
+    pub parameters: Vec<CostCheck>,
+    /// Fingerprint of the finding for this call.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub finding: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostCheck {
+    pub name: String,
+    /// Every value the argument may take; empty when it is unresolved.
+    pub values: Vec<i64>,
+    pub minimum: i64,
+    pub status: PolicyStatus,
+    /// The argument is omitted and `values` is the library default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub library_default: bool,
+}
+
+struct CostParam {
+    name: &'static str,
+    position: Option<usize>,
+    keyword: Option<&'static str>,
+    default: Option<i64>,
+}
+
+const fn at(name: &'static str, position: usize) -> CostParam {
+    CostParam {
+        name,
+        position: Some(position),
+        keyword: None,
+        default: None,
+    }
+}
+
+const fn named(name: &'static str, position: Option<usize>, keyword: &'static str) -> CostParam {
+    CostParam {
+        name,
+        position,
+        keyword: Some(keyword),
+        default: None,
+    }
+}
+
+const fn defaulted(param: CostParam, default: i64) -> CostParam {
+    CostParam {
+        default: Some(default),
+        ..param
+    }
+}
+
+struct PasswordApi {
+    modules: &'static [&'static str],
+    functions: &'static [&'static str],
+    scheme: PasswordScheme,
+    params: &'static [CostParam],
+}
+
+const PASSWORD_APIS: &[PasswordApi] = &[
+    // Go
+    PasswordApi {
+        modules: &["golang.org/x/crypto/pbkdf2"],
+        functions: &["Key"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[at("iterations", 2)],
+    },
+    PasswordApi {
+        modules: &["crypto/pbkdf2"],
+        functions: &["Key"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[at("iterations", 3)],
+    },
+    PasswordApi {
+        modules: &["golang.org/x/crypto/bcrypt"],
+        functions: &["GenerateFromPassword"],
+        scheme: PasswordScheme::Bcrypt,
+        params: &[at("cost", 1)],
+    },
+    PasswordApi {
+        modules: &["golang.org/x/crypto/scrypt"],
+        functions: &["Key"],
+        scheme: PasswordScheme::Scrypt,
+        params: &[at("n", 2), at("r", 3), at("p", 4)],
+    },
+    PasswordApi {
+        modules: &["golang.org/x/crypto/argon2"],
+        functions: &["IDKey", "Key"],
+        scheme: PasswordScheme::Argon2,
+        params: &[
+            at("iterations", 2),
+            at("memory_kib", 3),
+            at("parallelism", 4),
+        ],
+    },
+    // Python
+    PasswordApi {
+        modules: &["hashlib"],
+        functions: &["pbkdf2_hmac"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[named("iterations", Some(3), "iterations")],
+    },
+    PasswordApi {
+        modules: &["hashlib"],
+        functions: &["scrypt"],
+        scheme: PasswordScheme::Scrypt,
+        params: &[
+            named("n", None, "n"),
+            named("r", None, "r"),
+            named("p", None, "p"),
+        ],
+    },
+    PasswordApi {
+        modules: &["cryptography.hazmat.primitives.kdf.pbkdf2"],
+        functions: &["PBKDF2HMAC"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[named("iterations", Some(3), "iterations")],
+    },
+    PasswordApi {
+        modules: &["cryptography.hazmat.primitives.kdf.scrypt"],
+        functions: &["Scrypt"],
+        scheme: PasswordScheme::Scrypt,
+        params: &[
+            named("n", Some(2), "n"),
+            named("r", Some(3), "r"),
+            named("p", Some(4), "p"),
+        ],
+    },
+    PasswordApi {
+        modules: &["cryptography.hazmat.primitives.kdf.argon2"],
+        functions: &["Argon2id"],
+        scheme: PasswordScheme::Argon2,
+        params: &[
+            named("iterations", None, "iterations"),
+            named("memory_kib", None, "memory_cost"),
+            named("parallelism", None, "lanes"),
+        ],
+    },
+    PasswordApi {
+        modules: &["bcrypt"],
+        functions: &["gensalt"],
+        scheme: PasswordScheme::Bcrypt,
+        params: &[defaulted(named("cost", Some(0), "rounds"), 12)],
+    },
+    PasswordApi {
+        modules: &["argon2"],
+        functions: &["PasswordHasher"],
+        scheme: PasswordScheme::Argon2,
+        params: &[
+            defaulted(named("iterations", Some(0), "time_cost"), 3),
+            defaulted(named("memory_kib", Some(1), "memory_cost"), 65_536),
+            defaulted(named("parallelism", Some(2), "parallelism"), 4),
+        ],
+    },
+    // JavaScript
+    PasswordApi {
+        modules: &["crypto"],
+        functions: &["pbkdf2", "pbkdf2Sync"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[at("iterations", 2)],
+    },
+    PasswordApi {
+        modules: &["bcrypt", "bcryptjs"],
+        functions: &["hash", "hashSync"],
+        scheme: PasswordScheme::Bcrypt,
+        params: &[at("cost", 1)],
+    },
+    PasswordApi {
+        modules: &["bcrypt", "bcryptjs"],
+        functions: &["genSalt", "genSaltSync"],
+        scheme: PasswordScheme::Bcrypt,
+        params: &[defaulted(at("cost", 0), 10)],
+    },
+    // Rust
+    PasswordApi {
+        modules: &["pbkdf2"],
+        functions: &["pbkdf2", "pbkdf2_hmac", "pbkdf2_hmac_array"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[at("iterations", 2)],
+    },
+    PasswordApi {
+        modules: &["ring::pbkdf2"],
+        functions: &["derive"],
+        scheme: PasswordScheme::Pbkdf2,
+        params: &[at("iterations", 1)],
+    },
+];
+
+/// Checks every password hashing call among `findings` against `policy`.
+pub fn check_password_storage(
+    findings: &[Finding],
+    policy: &PasswordPolicy,
+) -> Vec<PasswordHashCheck> {
+    findings
+        .iter()
+        .filter_map(|finding| {
+            let api = find_api(finding)?;
+            let parameters: Vec<CostCheck> = api
+                .params
+                .iter()
+                .filter_map(|param| check_param(finding, api.scheme, param, policy))
+                .collect();
+            let status = parameters
+                .iter()
+                .map(|p| p.status)
+                .max()
+                .unwrap_or(PolicyStatus::Unknown);
+
+            Some(PasswordHashCheck {
+                file: finding.file.clone(),
+                line: finding.line,
+                column: finding.column,
+                function: finding.full_name.clone(),
+                scheme: api.scheme,
+                status,
+                parameters,
+                finding: finding.fingerprint.clone(),
+            })
+        })
+        .collect()
+}
+
+/// The position and keyword of the iteration count of a PBKDF2 call.
+pub(super) fn pbkdf2_iterations_argument(
+    finding: &Finding,
+) -> Option<(Option<usize>, Option<&'static str>)> {
+    let api = find_api(finding).filter(|api| api.scheme == PasswordScheme::Pbkdf2)?;
+    let param = api.params.iter().find(|param| param.name == "iterations")?;
+    Some((param.position, param.keyword))
+}
+
+fn find_api(finding: &Finding) -> Option<&'static PasswordApi> {
+    PASSWORD_APIS
+        .iter()
+        .find(|api| finding.calls(api.modules, api.functions))
+}
+
+fn check_param(
+    finding: &Finding,
+    scheme: PasswordScheme,
+    param: &CostParam,
+    policy: &PasswordPolicy,
+) -> Option<CostCheck> {
+    let minimum = policy.minimum(scheme, param.name)?;
+
+    let (values, library_default) = match finding.argument(param.position, param.keyword) {
+        Some(value) => (int_values(value), false),
+        None => match param.default {
+            Some(default) => (vec![default], true),
+            None => (Vec::new(), false),
+        },
+    };
+    let status = if values.is_empty() {
+        PolicyStatus::Unknown
+    } else if values.iter().all(|&v| v >= minimum) {
+        PolicyStatus::Pass
+    } else {
+        PolicyStatus::Fail
+    };
+
+    Some(CostCheck {
+        name: param.name.to_string(),
+        values,
+        minimum,
+        status,
+        library_default,
+    })
+}
+
+fn int_values(value: &serde_json::Value) -> Vec<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64().into_iter().collect(),
+        serde_json::Value::Array(items) => {
+            let values: Vec<i64> = items.iter().filter_map(|v| v.as_i64()).collect();
+            if values.len() == items.len() {
+                values
+            } else {
+                Vec::new()
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_support;
+
+    fn finding(import_path: &str, function: &str, parameters: serde_json::Value) -> Finding {
+        Finding {
+            import_path: Some(import_path.to_string()),
+            parameters: test_support::parameters(parameters),
+            fingerprint: "abc".to_string(),
+            ..test_support::finding("auth.go", 10, function)
+        }
+    }
+
+    #[test]
+    fn test_go_pbkdf2_below_policy_fails() {
+        let findings = vec![finding(
+            "golang.org/x/crypto/pbkdf2",
+            "Key",
+            json!({"arg0": {"source": "identifier", "value": null}, "arg2": 10000}),
+        )];
+
+        let checks = check_password_storage(&findings, &PasswordPolicy::default());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].scheme, PasswordScheme::Pbkdf2);
+        assert_eq!(checks[0].status, PolicyStatus::Fail);
+        assert_eq!(checks[0].finding, "abc");
+        assert_eq!(checks[0].parameters[0].values, vec![10000]);
+        assert_eq!(checks[0].parameters[0].minimum, 600_000);
+    }
+
+    #[test]
+    fn test_argon2_checks_every_cost_factor() {
+        let findings = vec![finding(
+            "golang.org/x/crypto/argon2",
+            "IDKey",
+            json!({"arg2": 1, "arg3": 65536, "arg4": {"source": "call", "value": null}}),
+        )];
+
+        let checks = check_password_storage(&findings, &PasswordPolicy::default());
+        let statuses: Vec<_> = checks[0]
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("iterations", PolicyStatus::Fail),
+                ("memory_kib", PolicyStatus::Pass),
+                ("parallelism", PolicyStatus::Unknown),
+            ]
+        );
+        assert_eq!(checks[0].status, PolicyStatus::Fail);
+    }
+
+    #[test]
+    fn test_python_keywords_and_library_defaults() {
+        let mut scrypt = finding(
+            "hashlib",
+            "scrypt",
+            json!({"arg0": "pw", "arg1": "salt", "arg2": 16384, "arg3": 8, "arg4": 1}),
+        );
+        scrypt.keywords = [
+            ("arg1", "salt"),
+            ("arg2", "n"),
+            ("arg3", "r"),
+            ("arg4", "p"),
+        ]
+        .into_iter()
+        .map(|(a, k)| (a.to_string(), k.to_string()))
+        .collect();
+        let gensalt = finding("bcrypt", "gensalt", json!({}));
+
+        let policy = PasswordPolicy {
+            scrypt_n: 16384,
+            ..PasswordPolicy::default()
+        };
+        let checks = check_password_storage(&[scrypt, gensalt], &policy);
+
+        assert_eq!(checks[0].status, PolicyStatus::Pass);
+        assert_eq!(checks[0].parameters[0].values, vec![16384]);
+        assert_eq!(checks[1].status, PolicyStatus::Pass);
+        assert!(checks[1].parameters[0].library_default);
+        assert_eq!(checks[1].parameters[0].values, vec![12]);
+    }
+
+    #[test]
+    fn test_ignores_other_calls() {
+        let findings = vec![
+            finding("crypto/aes", "NewCipher", json!({})),
+            finding("node:crypto", "pbkdf2Sync", json!({"arg2": [1000, 700000]})),
+        ];
+
+        let checks = check_password_storage(&findings, &PasswordPolicy::default());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, PolicyStatus::Fail);
+    }
+}
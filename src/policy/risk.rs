@@ -0,0 +1,165 @@
+//! One number per report for dashboards that track crypto risk over time.
+//!
+//! Each weakness adds points by severity. Weaknesses in test code count a
+//! tenth, since tests rarely ship. Weaknesses in dependency code count
+//! half, since only the parts the application calls are reachable. Both
+//! are taken from what the scan recorded about the file; reports written
+//! before it did fall back to the path. The
+//! total is mapped onto 0 (no weaknesses) to 100 with a saturating curve,
+//! so the score keeps moving as weaknesses are fixed in large repositories
+//! without one finding dominating a small one.
+
+use std::path::{Component, Path};
+
+use super::{Severity, Weakness};
+
+/// Points at which the score reaches 63; a high severity weakness in
+/// application code is 10 points.
+const SCALE: f64 = 50.0;
+
+/// Directories holding third-party code, as written by the package
+/// managers: the Go module cache is `pkg/mod`, the Maven repository
+/// `.m2/repository` and the Cargo one `.cargo/registry`.
+const DEPENDENCY_DIRS: &[&[&str]] = &[
+    &["vendor"],
+    &["node_modules"],
+    &["site-packages"],
+    &["pkg", "mod"],
+    &[".m2", "repository"],
+    &[".cargo", "registry"],
+];
+
+/// The risk score of a report with `weaknesses`, from 0 to 100.
+pub fn risk_score(weaknesses: &[Weakness]) -> u32 {
+    let points: f64 = weaknesses
+        .iter()
+        .map(|w| severity_points(w.severity) * context_weight(w))
+        .sum();
+    (100.0 * (1.0 - (-points / SCALE).exp())).round() as u32
+}
+
+fn severity_points(severity: Severity) -> f64 {
+    match severity {
+        Severity::High => 10.0,
+        Severity::Medium => 4.0,
+        Severity::Low => 1.0,
+    }
+}
+
+fn context_weight(weakness: &Weakness) -> f64 {
+    if weakness.test_code || is_test_name(&weakness.file) {
+        0.1
+    } else if weakness.dependency.is_some() || is_dependency_file(&weakness.file) {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Whether `file`, a path relative to the scan root, is test code by the
+/// conventions of the supported languages: `_test.go`, `test_*.py`,
+/// `*.test.js`, `tests/` and so on. Directories above the root, such as a
+/// CI workspace under `/home/ci/tests`, do not count.
+pub fn is_test_file(file: &Path) -> bool {
+    let in_test_dir = file.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            matches!(c, Component::Normal(name)
+                if matches!(name.to_str(), Some("test" | "tests" | "__tests__" | "testdata")))
+        })
+    });
+    in_test_dir || file.to_str().is_some_and(is_test_name)
+}
+
+/// Whether the file name of `file` alone marks it as test code; unlike
+/// the directories, it holds for paths not relative to the scan root.
+fn is_test_name(file: &str) -> bool {
+    let name = Path::new(file)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let stem = name.split('.').next().unwrap_or("");
+    stem.ends_with("_test")
+        || stem.starts_with("test_")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+fn is_dependency_file(file: &str) -> bool {
+    let names: Vec<&str> = Path::new(file)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    DEPENDENCY_DIRS
+        .iter()
+        .any(|dir| names.windows(dir.len()).any(|window| window == *dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn weakness(file: &str, severity: Severity) -> Weakness {
+        test_support::weakness(file, "jwt_alg_none", severity)
+    }
+
+    #[test]
+    fn test_risk_score_weights() {
+        assert_eq!(risk_score(&[]), 0);
+        assert_eq!(risk_score(&[weakness("src/auth.py", Severity::High)]), 18);
+        let test_code = Weakness {
+            test_code: true,
+            ..weakness("tests/login.py", Severity::High)
+        };
+        assert_eq!(risk_score(&[test_code]), 2);
+        let dependency = Weakness {
+            dependency: Some("github.com/x/jwt".to_string()),
+            ..weakness("/srv/cache/github.com/x/jwt/parse.go", Severity::High)
+        };
+        assert_eq!(risk_score(&[dependency]), 10);
+        let many: Vec<Weakness> = (0..40)
+            .map(|_| weakness("src/auth.py", Severity::High))
+            .collect();
+        assert_eq!(risk_score(&many), 100);
+    }
+
+    #[test]
+    fn test_risk_score_path_fallback() {
+        // Reports without the scan's context go by the path
+        assert_eq!(
+            risk_score(&[weakness("pkg/auth/login_test.go", Severity::High)]),
+            2
+        );
+        for file in [
+            "vendor/github.com/x/jwt/parse.go",
+            "/home/dev/go/pkg/mod/github.com/x/jwt@v1.2.0/parse.go",
+            "/home/dev/.m2/repository/io/jsonwebtoken/Jwts.java",
+            "/home/dev/.cargo/registry/src/jsonwebtoken-9.2.0/src/decoding.rs",
+        ] {
+            assert_eq!(risk_score(&[weakness(file, Severity::High)]), 10, "{file}");
+        }
+        // First-party code named like a package manager's directory, and
+        // test directories above the scan root
+        for file in [
+            "src/registry/client.go",
+            "internal/pkg/modules/jwt.go",
+            "/home/ci/tests/project/src/main.go",
+        ] {
+            assert_eq!(risk_score(&[weakness(file, Severity::High)]), 18, "{file}");
+        }
+    }
+
+    #[test]
+    fn test_is_test_file() {
+        assert!(is_test_file(Path::new("pkg/crypto/hash_test.go")));
+        assert!(is_test_file(Path::new("tests/test_login.py")));
+        assert!(is_test_file(Path::new("tests/login.py")));
+        assert!(is_test_file(Path::new("src/login.spec.ts")));
+        assert!(is_test_file(Path::new("src/__tests__/hash.js")));
+        assert!(!is_test_file(Path::new("src/attestation.go")));
+        assert!(!is_test_file(Path::new("contest/main.py")));
+    }
+}
@@ -0,0 +1,256 @@
+//! TLS certificate verification being turned off.
+//!
+//! Settings come from config findings (`struct_fields` such as Go
+//! `tls.Config`, Python keyword arguments and JavaScript options objects)
+//! and from call arguments: keyword arguments, libcurl `setopt` calls and
+//! reqwest's `danger_accept_invalid_*` builder methods.
+
+use serde_json::json;
+
+use crate::output::{ConfigFinding, Finding};
+
+use super::weakness::{Severity, Weakness};
+
+const RULE: &str = "tls_verification_disabled";
+
+/// Classification key of a setting that turns verification off when true.
+const TLS_SKIP_VERIFY: &str = "tls_skip_verify";
+/// Classification key of a setting that turns verification off when false.
+const TLS_VERIFY: &str = "tls_verify";
+
+/// Well-known setting names and whether `true` disables verification, used
+/// when the preset gives the field no TLS classification key.
+const SETTINGS: &[(&str, bool)] = &[
+    ("InsecureSkipVerify", true),
+    ("rejectUnauthorized", false),
+    ("verify", false),
+    ("verify_ssl", false),
+];
+
+const CURL_SETOPT: &[&str] = &["setopt", "curl_setopt", "curl_easy_setopt"];
+const CURL_VERIFY_OPTIONS: &[&str] = &["SSL_VERIFYPEER", "SSL_VERIFYHOST"];
+const DANGER_METHODS: &[&str] = &[
+    "danger_accept_invalid_certs",
+    "danger_accept_invalid_hostnames",
+];
+
+/// Verification settings in one config finding.
+pub(super) fn check_tls_config(config: &ConfigFinding) -> Vec<Weakness> {
+    config
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let disabled_when = match field.classification_key.as_deref() {
+                Some(TLS_SKIP_VERIFY) => true,
+                Some(TLS_VERIFY) => false,
+                _ => setting_polarity(&field.field_name)?,
+            };
+            let value = disabling_value(&field.value, disabled_when)?;
+            Some(Weakness::at_config(
+                config,
+                RULE,
+                Severity::High,
+                format!(
+                    "TLS certificate verification is disabled ({}: {value})",
+                    field.field_name
+                ),
+                json!(value),
+            ))
+        })
+        .collect()
+}
+
+/// Verification settings passed as arguments of one call.
+pub(super) fn check_tls_call(call: &Finding) -> Vec<Weakness> {
+    let mut found = Vec::new();
+
+    let mut keywords: Vec<_> = call.keywords.iter().collect();
+    keywords.sort();
+    for (arg, keyword) in keywords {
+        let Some(disabled_when) = setting_polarity(keyword) else {
+            continue;
+        };
+        if let Some(value) = call
+            .parameters
+            .get(arg)
+            .and_then(|v| disabling_value(v, disabled_when))
+        {
+            found.push(Weakness::at(
+                call,
+                RULE,
+                Severity::High,
+                format!("TLS certificate verification is disabled ({keyword}={value})"),
+                json!(value),
+            ));
+        }
+    }
+
+    if CURL_SETOPT.contains(&call.function.as_str()) {
+        if let Some(option) = CURL_VERIFY_OPTIONS
+            .iter()
+            .find(|option| call.raw_text.contains(*option))
+        {
+            let last = call
+                .parameters
+                .len()
+                .checked_sub(1)
+                .and_then(|i| call.parameters.get(&format!("arg{i}")));
+            if let Some(value) = last.and_then(|v| disabling_value(v, false)) {
+                found.push(Weakness::at(
+                    call,
+                    RULE,
+                    Severity::High,
+                    format!("TLS certificate verification is disabled ({option} = 0)"),
+                    json!(value),
+                ));
+            }
+        }
+    }
+
+    if DANGER_METHODS.contains(&call.function.as_str()) {
+        if let Some(value) = call
+            .parameters
+            .get("arg0")
+            .and_then(|v| disabling_value(v, true))
+        {
+            found.push(Weakness::at(
+                call,
+                RULE,
+                Severity::High,
+                format!(
+                    "TLS certificate verification is disabled ({}(true))",
+                    call.function
+                ),
+                json!(value),
+            ));
+        }
+    }
+
+    found
+}
+
+fn setting_polarity(name: &str) -> Option<bool> {
+    SETTINGS
+        .iter()
+        .find(|(setting, _)| *setting == name)
+        .map(|(_, disabled_when)| *disabled_when)
+}
+
+/// `disabled_when` if any resolved value of `value` equals it.
+fn disabling_value(value: &serde_json::Value, disabled_when: bool) -> Option<bool> {
+    let matches = match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .any(|item| as_bool(item) == Some(disabled_when)),
+        _ => as_bool(value) == Some(disabled_when),
+    };
+    matches.then_some(disabled_when)
+}
+
+fn as_bool(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(b) => Some(*b),
+        serde_json::Value::Number(n) => n.as_i64().map(|n| n != 0),
+        serde_json::Value::String(s) => match s.to_ascii_lowercase().as_str() {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn config(full_type: &str, fields: serde_json::Value) -> ConfigFinding {
+        serde_json::from_value(json!({
+            "file": "client.go", "line": 3, "column": 9, "struct_type": "Config",
+            "full_type": full_type, "fields": fields, "raw_text": ""
+        }))
+        .unwrap()
+    }
+
+    fn call(function: &str, parameters: serde_json::Value, raw_text: &str) -> Finding {
+        Finding {
+            parameters: test_support::parameters(parameters),
+            raw_text: raw_text.to_string(),
+            ..test_support::finding("client.py", 2, function)
+        }
+    }
+
+    #[test]
+    fn test_insecure_skip_verify() {
+        let tls = config(
+            "crypto/tls.Config",
+            json!([
+                {"field_name": "InsecureSkipVerify", "value": "true"},
+                {"field_name": "MinVersion", "value": {"source": "selector", "value": null}}
+            ]),
+        );
+
+        let found = check_tls_config(&tls);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rule, RULE);
+        assert_eq!(found[0].function, "crypto/tls.Config");
+        assert_eq!(found[0].evidence, json!(true));
+
+        let safe = config(
+            "crypto/tls.Config",
+            json!([{"field_name": "InsecureSkipVerify", "value": "false"}]),
+        );
+        assert!(check_tls_config(&safe).is_empty());
+    }
+
+    #[test]
+    fn test_classification_key_sets_polarity() {
+        let agent = config(
+            "https.Agent",
+            json!([{"field_name": "checkServerCert", "value": "false", "classification_key": "tls_verify"}]),
+        );
+
+        let found = check_tls_config(&agent);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].evidence, json!(false));
+    }
+
+    #[test]
+    fn test_verify_keyword_argument() {
+        let mut get = call(
+            "get",
+            json!({"arg1": "false"}),
+            "requests.get(url, verify=False)",
+        );
+        get.keywords.insert("arg1".into(), "verify".into());
+
+        let found = check_tls_call(&get);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].evidence, json!(false));
+    }
+
+    #[test]
+    fn test_curl_setopt_and_reqwest() {
+        let setopt = call(
+            "setopt",
+            json!({"arg0": {"source": "selector", "value": null}, "arg1": 0}),
+            "c.setopt(pycurl.SSL_VERIFYPEER, 0)",
+        );
+        assert_eq!(check_tls_call(&setopt).len(), 1);
+
+        let other = call(
+            "setopt",
+            json!({"arg0": {"source": "selector", "value": null}, "arg1": 0}),
+            "c.setopt(pycurl.VERBOSE, 0)",
+        );
+        assert!(check_tls_call(&other).is_empty());
+
+        let danger = call(
+            "danger_accept_invalid_certs",
+            json!({"arg0": "true"}),
+            "builder.danger_accept_invalid_certs(true)",
+        );
+        assert_eq!(check_tls_call(&danger)[0].evidence, json!(true));
+    }
+}
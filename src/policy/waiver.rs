@@ -0,0 +1,274 @@
+//! Waivers: exceptions to the policy for weaknesses a team has accepted,
+//! kept in the config file with an owner, a reason and usually an expiry
+//! date. A waiver in force leaves the weaknesses it matches out of the
+//! report. Once it has expired they are reported again, and the waiver is
+//! listed under `waivers.expired` so it gets renewed or the weakness fixed
+//! instead of staying a silent exception.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::Weakness;
+
+/// Matches the weaknesses that meet every selector given: at least one of
+/// `finding`, `rule` and `file`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Waiver {
+    /// Fingerprint of the finding or config the weakness is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finding: Option<String>,
+    /// Weakness rule, e.g. `tls_verification_disabled`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// File the weakness is in as reported, or a directory ending in `/`
+    /// for every file below it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Who accepted the exception and answers for it.
+    pub owner: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reason: String,
+    /// Last day the waiver is in force, as `YYYY-MM-DD`; without it the
+    /// waiver does not expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+impl Waiver {
+    /// Why the waiver cannot be used, if it cannot.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.finding.is_none() && self.rule.is_none() && self.file.is_none() {
+            return Err("a waiver needs a finding, rule or file to match".to_string());
+        }
+        if self.owner.trim().is_empty() {
+            return Err("a waiver needs an owner".to_string());
+        }
+        match &self.expires {
+            Some(date) if !is_date(date) => Err(format!(
+                "waiver expiry '{date}' is not a date in YYYY-MM-DD form"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn matches(&self, weakness: &Weakness) -> bool {
+        self.finding.as_ref().is_none_or(|f| *f == weakness.finding)
+            && self.rule.as_ref().is_none_or(|r| *r == weakness.rule)
+            && self
+                .file
+                .as_ref()
+                .is_none_or(|file| match file.strip_suffix('/') {
+                    Some(dir) => weakness
+                        .file
+                        .strip_prefix(dir)
+                        .is_some_and(|rest| rest.starts_with('/')),
+                    None => *file == weakness.file,
+                })
+    }
+
+    /// Whether the waiver is past its expiry date on `today`.
+    pub fn is_expired(&self, today: &str) -> bool {
+        // Dates in YYYY-MM-DD form order as strings do.
+        self.expires
+            .as_deref()
+            .is_some_and(|expires| expires < today)
+    }
+}
+
+/// How waivers changed a report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WaiverReport {
+    /// Weaknesses left out by a waiver in force.
+    pub waived: usize,
+    /// Waivers past their expiry date, whose weaknesses are reported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expired: Vec<ExpiredWaiver>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpiredWaiver {
+    pub waiver: Waiver,
+    /// Weaknesses it matches, reported again.
+    pub weaknesses: usize,
+}
+
+impl WaiverReport {
+    /// Combines the waiver reports of several reports, counting the
+    /// weaknesses of an expired waiver across all of them.
+    pub fn merge(reports: impl IntoIterator<Item = WaiverReport>) -> Self {
+        let mut merged = WaiverReport::default();
+        for report in reports {
+            merged.waived += report.waived;
+            for expired in report.expired {
+                match merged
+                    .expired
+                    .iter_mut()
+                    .find(|e| e.waiver == expired.waiver)
+                {
+                    Some(e) => e.weaknesses += expired.weaknesses,
+                    None => merged.expired.push(expired),
+                }
+            }
+        }
+        merged
+    }
+}
+
+/// Leaves the `weaknesses` that a waiver in force on `today` (`YYYY-MM-DD`)
+/// matches out, keeping those only expired waivers match.
+pub fn apply_waivers(
+    weaknesses: &mut Vec<Weakness>,
+    waivers: &[Waiver],
+    today: &str,
+) -> WaiverReport {
+    let (expired, in_force): (Vec<&Waiver>, Vec<&Waiver>) =
+        waivers.iter().partition(|w| w.is_expired(today));
+    let before = weaknesses.len();
+    weaknesses.retain(|weakness| !in_force.iter().any(|w| w.matches(weakness)));
+    WaiverReport {
+        waived: before - weaknesses.len(),
+        expired: expired
+            .into_iter()
+            .map(|waiver| ExpiredWaiver {
+                waiver: waiver.clone(),
+                weaknesses: weaknesses.iter().filter(|w| waiver.matches(w)).count(),
+            })
+            .collect(),
+    }
+}
+
+/// The current date in UTC as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    civil_date(days as i64)
+}
+
+/// The date `days` after 1970-01-01, by Howard Hinnant's `civil_from_days`.
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let number = |part: &str, len: usize| {
+        (part.len() == len && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse::<u32>().ok())
+            .flatten()
+    };
+    number(year, 4).is_some()
+        && number(month, 2).is_some_and(|m| (1..=12).contains(&m))
+        && number(day, 2).is_some_and(|d| (1..=31).contains(&d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Severity;
+    use crate::test_support;
+
+    fn weakness(file: &str, rule: &str, finding: &str) -> Weakness {
+        Weakness {
+            finding: finding.to_string(),
+            ..test_support::weakness(file, rule, Severity::High)
+        }
+    }
+
+    fn waiver(rule: Option<&str>, file: Option<&str>, expires: Option<&str>) -> Waiver {
+        Waiver {
+            finding: None,
+            rule: rule.map(str::to_string),
+            file: file.map(str::to_string),
+            owner: "platform-team".to_string(),
+            reason: String::new(),
+            expires: expires.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_apply_waivers() {
+        let mut weaknesses = vec![
+            weakness(
+                "internal/legacy/client.go",
+                "tls_verification_disabled",
+                "a1",
+            ),
+            weakness("internal/legacy/token.go", "jwt_alg_none", "b2"),
+            weakness(
+                "internal/legacyx/client.go",
+                "tls_verification_disabled",
+                "c3",
+            ),
+            weakness("cmd/main.go", "jwt_alg_none", "d4"),
+        ];
+        let waivers = [
+            waiver(
+                Some("tls_verification_disabled"),
+                Some("internal/legacy/"),
+                Some("2026-06-30"),
+            ),
+            waiver(Some("jwt_alg_none"), None, Some("2026-01-31")),
+            waiver(None, Some("cmd/main.go"), None),
+        ];
+
+        let report = apply_waivers(&mut weaknesses, &waivers, "2026-02-01");
+        let left: Vec<&str> = weaknesses.iter().map(|w| w.finding.as_str()).collect();
+        assert_eq!(left, ["b2", "c3"]);
+        assert_eq!(report.waived, 2);
+        assert_eq!(
+            report.expired,
+            vec![ExpiredWaiver {
+                waiver: waivers[1].clone(),
+                weaknesses: 1,
+            }]
+        );
+
+        // In force through its expiry date
+        let mut weaknesses = vec![weakness("cmd/kdf.go", "jwt_alg_none", "e5")];
+        let report = apply_waivers(&mut weaknesses, &waivers[1..2], "2026-01-31");
+        assert!(weaknesses.is_empty());
+        assert!(report.expired.is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(waiver(Some("jwt_alg_none"), None, Some("2026-12-01"))
+            .validate()
+            .is_ok());
+        assert!(waiver(None, None, None).validate().is_err());
+        assert!(waiver(Some("jwt_alg_none"), None, Some("2026-13-01"))
+            .validate()
+            .is_err());
+        assert!(waiver(Some("jwt_alg_none"), None, Some("1 Dec 2026"))
+            .validate()
+            .is_err());
+        let unowned = Waiver {
+            owner: " ".to_string(),
+            ..waiver(Some("jwt_alg_none"), None, None)
+        };
+        assert!(unowned.validate().is_err());
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(20_743), "2026-10-17");
+    }
+}
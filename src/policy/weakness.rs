@@ -0,0 +1,126 @@
+//! Weaknesses visible in the arguments of recognized calls and in config
+//! settings, such as JWT verification that accepts unsigned tokens, TLS
+//! without certificate verification or ECB mode encryption.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::output::{ConfigFinding, Finding};
+
+use super::{cipher, jwt, tls};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Weakness {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub function: String,
+    /// What is wrong, e.g. `jwt_alg_none`.
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    /// The resolved argument value the finding is based on.
+    pub evidence: serde_json::Value,
+    /// Fingerprint of the finding or config this is based on.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub finding: String,
+    /// Guidance page the rules give for `rule`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    /// The dependency whose files the weakness is in; `None` for the
+    /// project's own code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<String>,
+    /// Whether the weakness is in test code, see [`super::is_test_file`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test_code: bool,
+}
+
+impl Weakness {
+    pub(super) fn at(
+        call: &Finding,
+        rule: &str,
+        severity: Severity,
+        message: impl Into<String>,
+        evidence: serde_json::Value,
+    ) -> Self {
+        Self {
+            file: call.file.clone(),
+            line: call.line,
+            column: call.column,
+            function: call.full_name.clone(),
+            rule: rule.to_string(),
+            severity,
+            message: message.into(),
+            evidence,
+            finding: call.fingerprint.clone(),
+            help_uri: None,
+            dependency: call.dependency.clone(),
+            test_code: call.test_code,
+        }
+    }
+
+    pub(super) fn at_config(
+        config: &ConfigFinding,
+        rule: &str,
+        severity: Severity,
+        message: impl Into<String>,
+        evidence: serde_json::Value,
+    ) -> Self {
+        Self {
+            file: config.file.clone(),
+            line: config.line,
+            column: config.column,
+            function: config
+                .call
+                .clone()
+                .unwrap_or_else(|| config.full_type.clone()),
+            rule: rule.to_string(),
+            severity,
+            message: message.into(),
+            evidence,
+            finding: config.fingerprint.clone(),
+            help_uri: None,
+            dependency: None,
+            test_code: false,
+        }
+    }
+}
+
+/// Runs every weakness check over `findings` and `configs`. A setting seen
+/// both as a keyword argument and as a config is reported once.
+pub fn check_weaknesses(findings: &[Finding], configs: &[ConfigFinding]) -> Vec<Weakness> {
+    let mut found: Vec<Weakness> = findings
+        .iter()
+        .flat_map(|call| {
+            let mut found = jwt::check_jwt(call);
+            found.extend(tls::check_tls_call(call));
+            found.extend(cipher::check_cipher(call));
+            found
+        })
+        .chain(configs.iter().flat_map(tls::check_tls_config))
+        .collect();
+
+    let mut seen = HashSet::new();
+    found.retain(|f| seen.insert((f.file.clone(), f.line, f.column, f.rule.clone())));
+    found
+}
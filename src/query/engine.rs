@@ -1,5 +1,8 @@
+use crate::engine::Language as EngineLanguage;
 use crate::error::QueryError;
+use crate::grammars::{self, grammar};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use tracing::{trace, warn};
 use tree_sitter::{Language, Node, Query, QueryCursor, StreamingIterator};
 
@@ -28,22 +31,37 @@ impl Match {
     }
 }
 
+/// Tree-sitter queries by language. A language's queries are compiled
+/// when it is first queried, so a scan only pays for the grammars it reads.
 pub struct QueryEngine {
-    queries: HashMap<String, HashMap<String, Query>>,
+    queries: HashMap<EngineLanguage, OnceLock<HashMap<String, Query>>>,
 }
 
 impl QueryEngine {
     pub fn new() -> Self {
-        let mut engine = Self {
-            queries: HashMap::new(),
-        };
+        Self {
+            queries: grammars::available()
+                .into_iter()
+                .map(|language| (language, OnceLock::new()))
+                .collect(),
+        }
+    }
 
-        engine.load_go_queries();
-        engine.load_python_queries();
-        engine.load_rust_queries();
-        engine.load_javascript_queries();
+    /// The engine every [`Scanner`](crate::scanner::Scanner) shares, so a
+    /// language's queries are compiled once per process.
+    pub fn shared() -> &'static Self {
+        static SHARED: OnceLock<QueryEngine> = OnceLock::new();
+        SHARED.get_or_init(Self::new)
+    }
 
-        engine
+    /// The queries of `language`, compiled on first use; `None` when its
+    /// grammar is not compiled in or has no queries.
+    fn language_queries(&self, language: EngineLanguage) -> Option<&HashMap<String, Query>> {
+        let queries = self
+            .queries
+            .get(&language)?
+            .get_or_init(|| QuerySet::compile(language).queries);
+        (!queries.is_empty()).then_some(queries)
     }
 
     pub fn query<'a>(
@@ -55,9 +73,8 @@ impl QueryEngine {
     ) -> Result<Vec<Match>, QueryError> {
         trace!(language, query_name, "executing query");
 
-        let lang_queries = self
-            .queries
-            .get(language)
+        let lang_queries = EngineLanguage::parse(language)
+            .and_then(|engine_language| self.language_queries(engine_language))
             .ok_or_else(|| QueryError::language_not_supported(language))?;
 
         let query = lang_queries
@@ -69,10 +86,20 @@ impl QueryEngine {
         let mut results = Vec::new();
 
         while let Some(m) = matches.next() {
-            let mut captures = HashMap::new();
+            let mut captures: HashMap<String, Capture> = HashMap::new();
 
             for capture in m.captures {
                 let name = query.capture_names()[capture.index as usize].to_string();
+                // A repeated capture (`(identifier)* @path`) spans all its
+                // nodes: `javax.crypto.Cipher` for a Scala import path
+                if let Some(first) = captures.get_mut(&name) {
+                    first.end_byte = capture.node.end_byte();
+                    first.text = source
+                        .get(first.start_byte..first.end_byte)
+                        .unwrap_or("")
+                        .to_string();
+                    continue;
+                }
                 let text = capture
                     .node
                     .utf8_text(source.as_bytes())
@@ -98,6 +125,41 @@ impl QueryEngine {
 
         Ok(results)
     }
+}
+
+impl Default for QueryEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The compiled queries of one language, by name.
+#[derive(Default)]
+struct QuerySet {
+    queries: HashMap<String, Query>,
+}
+
+impl QuerySet {
+    fn compile(language: EngineLanguage) -> Self {
+        let mut set = Self::default();
+        match language {
+            EngineLanguage::Go => set.load_go_queries(),
+            EngineLanguage::Python => set.load_python_queries(),
+            EngineLanguage::Rust => set.load_rust_queries(),
+            EngineLanguage::JavaScript => set.load_javascript_queries(),
+            EngineLanguage::Java => set.load_java_queries(),
+            EngineLanguage::C => set.load_c_queries(),
+            EngineLanguage::Cpp => set.load_cpp_queries(),
+            EngineLanguage::CSharp => set.load_csharp_queries(),
+            EngineLanguage::Ruby => set.load_ruby_queries(),
+            EngineLanguage::Php => set.load_php_queries(),
+            EngineLanguage::Swift => set.load_swift_queries(),
+            EngineLanguage::Kotlin => set.load_kotlin_queries(),
+            EngineLanguage::Scala => set.load_scala_queries(),
+            EngineLanguage::TypeScript => {}
+        }
+        set
+    }
 
     fn add_query(&mut self, language: &str, name: &str, ts_lang: &Language, pattern: &str) {
         trace!(language, name, "compiling query");
@@ -110,14 +172,13 @@ impl QueryEngine {
             }
         };
 
-        self.queries
-            .entry(language.to_string())
-            .or_default()
-            .insert(name.to_string(), query);
+        self.queries.insert(name.to_string(), query);
     }
 
     fn load_go_queries(&mut self) {
-        let lang: Language = tree_sitter_go::LANGUAGE.into();
+        let Some(lang) = grammar(EngineLanguage::Go) else {
+            return;
+        };
 
         self.add_query(
             "go",
@@ -149,7 +210,9 @@ impl QueryEngine {
     }
 
     fn load_python_queries(&mut self) {
-        let lang: Language = tree_sitter_python::LANGUAGE.into();
+        let Some(lang) = grammar(EngineLanguage::Python) else {
+            return;
+        };
 
         self.add_query(
             "python",
@@ -193,7 +256,9 @@ impl QueryEngine {
     }
 
     fn load_rust_queries(&mut self) {
-        let lang: Language = tree_sitter_rust::LANGUAGE.into();
+        let Some(lang) = grammar(EngineLanguage::Rust) else {
+            return;
+        };
 
         self.add_query(
             "rust",
@@ -226,7 +291,9 @@ impl QueryEngine {
     }
 
     fn load_javascript_queries(&mut self) {
-        let lang: Language = tree_sitter_javascript::LANGUAGE.into();
+        let Some(lang) = grammar(EngineLanguage::JavaScript) else {
+            return;
+        };
 
         self.add_query(
             "javascript",
@@ -260,11 +327,318 @@ impl QueryEngine {
             "#,
         );
     }
-}
 
-impl Default for QueryEngine {
-    fn default() -> Self {
-        Self::new()
+    fn load_java_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Java) else {
+            return;
+        };
+
+        self.add_query(
+            "java",
+            "imports",
+            &lang,
+            r#"
+            (import_declaration
+              (scoped_identifier) @path
+              (asterisk)? @wildcard)
+            "#,
+        );
+
+        self.add_query(
+            "java",
+            "calls",
+            &lang,
+            r#"
+            (method_invocation
+              object: (identifier) @package
+              name: (identifier) @function
+              arguments: (argument_list) @args)
+            "#,
+        );
+    }
+
+    fn load_c_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::C) else {
+            return;
+        };
+
+        self.add_query(
+            "c",
+            "imports",
+            &lang,
+            r#"
+            (preproc_include
+              path: [(system_lib_string) (string_literal)] @header)
+            "#,
+        );
+
+        self.add_query(
+            "c",
+            "calls",
+            &lang,
+            r#"
+            (call_expression
+              function: (identifier) @function
+              arguments: (argument_list) @args)
+            "#,
+        );
+    }
+
+    fn load_cpp_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Cpp) else {
+            return;
+        };
+
+        self.add_query(
+            "cpp",
+            "imports",
+            &lang,
+            r#"
+            (preproc_include
+              path: [(system_lib_string) (string_literal)] @header)
+            "#,
+        );
+
+        self.add_query(
+            "cpp",
+            "calls",
+            &lang,
+            r#"
+            (call_expression
+              function: [(identifier) (qualified_identifier) (field_expression)] @function
+              arguments: (argument_list) @args)
+            "#,
+        );
+    }
+
+    fn load_csharp_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::CSharp) else {
+            return;
+        };
+
+        // `using System.Security.Cryptography;` brings in every type of the
+        // namespace, like a Java wildcard import
+        self.add_query(
+            "csharp",
+            "imports",
+            &lang,
+            r#"
+            (using_directive
+              name: (identifier) @alias
+              [(qualified_name) (identifier)] @path)
+            (using_directive
+              !name
+              [(qualified_name) (identifier)] @path) @wildcard
+            "#,
+        );
+
+        self.add_query(
+            "csharp",
+            "calls",
+            &lang,
+            r#"
+            (invocation_expression
+              function: (member_access_expression
+                expression: [(identifier) (member_access_expression)] @package
+                name: [(identifier) (generic_name)] @function)
+              arguments: (argument_list) @args)
+            "#,
+        );
+    }
+
+    fn load_ruby_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Ruby) else {
+            return;
+        };
+
+        // Constants are global once required, so `require` only records
+        // which libraries a file loads
+        self.add_query(
+            "ruby",
+            "imports",
+            &lang,
+            r#"
+            (call
+              method: (identifier) @_require
+              arguments: (argument_list (string (string_content) @path))
+              (#eq? @_require "require"))
+            "#,
+        );
+
+        self.add_query(
+            "ruby",
+            "calls",
+            &lang,
+            r#"
+            (call
+              receiver: [(constant) (scope_resolution) (identifier)] @package
+              method: (identifier) @function
+              arguments: (argument_list)? @args)
+            "#,
+        );
+    }
+
+    fn load_php_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Php) else {
+            return;
+        };
+
+        // Built-in functions are global; `use` only names classes and
+        // namespaces
+        self.add_query(
+            "php",
+            "imports",
+            &lang,
+            r#"
+            (namespace_use_clause
+              (qualified_name) @path
+              alias: (name)? @alias)
+            "#,
+        );
+
+        self.add_query(
+            "php",
+            "calls",
+            &lang,
+            r#"
+            [
+              (function_call_expression
+                function: [(name) (qualified_name)] @function
+                arguments: (arguments) @args)
+              (member_call_expression
+                object: (_) @package
+                name: (name) @function
+                arguments: (arguments) @args)
+              (scoped_call_expression
+                scope: (_) @package
+                name: (name) @function
+                arguments: (arguments) @args)
+            ]
+            "#,
+        );
+    }
+
+    fn load_swift_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Swift) else {
+            return;
+        };
+
+        // `import CryptoKit` brings in every type and function of the
+        // module, like a Java wildcard import
+        self.add_query(
+            "swift",
+            "imports",
+            &lang,
+            r#"
+            (import_declaration
+              (identifier) @path) @wildcard
+            "#,
+        );
+
+        self.add_query(
+            "swift",
+            "calls",
+            &lang,
+            r#"
+            [
+              (call_expression
+                (simple_identifier) @function
+                (call_suffix (value_arguments) @args))
+              (call_expression
+                (navigation_expression
+                  target: (_) @package
+                  suffix: (navigation_suffix
+                    suffix: (simple_identifier) @function))
+                (call_suffix (value_arguments) @args))
+            ]
+            "#,
+        );
+    }
+
+    fn load_kotlin_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Kotlin) else {
+            return;
+        };
+
+        // `import javax.crypto.Cipher`, `import javax.crypto.*` or
+        // `import javax.crypto.Cipher as C`
+        self.add_query(
+            "kotlin",
+            "imports",
+            &lang,
+            r#"
+            (import
+              (qualified_identifier) @path
+              "*"? @wildcard
+              (identifier)? @alias)
+            "#,
+        );
+
+        self.add_query(
+            "kotlin",
+            "calls",
+            &lang,
+            r#"
+            (call_expression
+              (navigation_expression
+                (_) @package
+                (identifier) @function)
+              (value_arguments) @args)
+            "#,
+        );
+    }
+
+    fn load_scala_queries(&mut self) {
+        let Some(lang) = grammar(EngineLanguage::Scala) else {
+            return;
+        };
+
+        // `import javax.crypto.Cipher`, `import javax.crypto._` and
+        // `import javax.crypto.{Cipher, Mac => HMac}`, one match per
+        // selector. A path is a run of identifiers whose capture spans the
+        // whole run; the trailing anchor only holds after a single node.
+        self.add_query(
+            "scala",
+            "imports",
+            &lang,
+            r#"
+            [
+              (import_declaration
+                . (identifier) @path (identifier)* @path (identifier) @path .)
+              (import_declaration
+                . (identifier) @path (identifier)* @path
+                (namespace_wildcard) @wildcard)
+              (import_declaration
+                . (identifier) @module (identifier)* @module
+                (namespace_selectors (identifier) @name))
+              (import_declaration
+                . (identifier) @module (identifier)* @module
+                (namespace_selectors
+                  (arrow_renamed_identifier
+                    name: (identifier) @name
+                    alias: (identifier) @alias)))
+            ]
+            "#,
+        );
+
+        self.add_query(
+            "scala",
+            "calls",
+            &lang,
+            r#"
+            [
+              (call_expression
+                function: (field_expression
+                  value: (_) @package
+                  field: (identifier) @function)
+                arguments: (arguments) @args)
+              (infix_expression
+                left: (_) @package
+                operator: (identifier) @function
+                right: (_) @args)
+            ]
+            "#,
+        );
     }
 }
 
@@ -353,6 +727,31 @@ func main() {
         assert_eq!(matches[1].get("function"), Some("Key"));
     }
 
+    #[test]
+    fn test_queries_compile_on_first_use() {
+        let source = "package main\n\nimport \"crypto/sha256\"\n";
+        let tree = parse_go(source);
+        let engine = QueryEngine::new();
+        let compiled = |language| {
+            engine
+                .queries
+                .get(&language)
+                .is_some_and(|queries| queries.get().is_some())
+        };
+        assert!(!compiled(EngineLanguage::Go));
+
+        engine
+            .query("go", "imports", tree.root_node(), source)
+            .unwrap();
+        assert!(compiled(EngineLanguage::Go));
+        assert!(!compiled(EngineLanguage::Python));
+
+        // TypeScript has no queries of its own
+        assert!(engine
+            .query("typescript", "imports", tree.root_node(), source)
+            .is_err());
+    }
+
     #[test]
     fn test_python_imports_simple() {
         let source = "import hashlib";
@@ -385,6 +784,243 @@ func main() {
         assert_eq!(matches[0].get("name"), Some("hashes"));
     }
 
+    #[test]
+    fn test_java_imports() {
+        let source = "import javax.crypto.Cipher;\nimport java.security.*;\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_java::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let matches = engine
+            .query("java", "imports", tree.root_node(), source)
+            .unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].get("path"), Some("javax.crypto.Cipher"));
+        assert_eq!(matches[0].get("wildcard"), None);
+        assert_eq!(matches[1].get("path"), Some("java.security"));
+        assert_eq!(matches[1].get("wildcard"), Some("*"));
+    }
+
+    #[test]
+    fn test_kotlin_imports() {
+        let source =
+            "import javax.crypto.Cipher\nimport java.security.*\nimport javax.crypto.Mac as HMac\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_kotlin_ng::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let matches = engine
+            .query("kotlin", "imports", tree.root_node(), source)
+            .unwrap();
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].get("path"), Some("javax.crypto.Cipher"));
+        assert_eq!(matches[0].get("wildcard"), None);
+        assert_eq!(matches[1].get("path"), Some("java.security"));
+        assert_eq!(matches[1].get("wildcard"), Some("*"));
+        assert_eq!(matches[2].get("path"), Some("javax.crypto.Mac"));
+        assert_eq!(matches[2].get("alias"), Some("HMac"));
+    }
+
+    #[test]
+    fn test_scala_imports() {
+        let source = "import javax.crypto.Cipher\nimport java.security._\nimport javax.crypto.{Mac, KeyGenerator => KG}\nimport a.b.c.D\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_scala::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let matches = engine
+            .query("scala", "imports", tree.root_node(), source)
+            .unwrap();
+
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| {
+                (
+                    m.get("path").or(m.get("module")),
+                    m.get("wildcard").or(m.get("name")),
+                    m.get("alias"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some("javax.crypto.Cipher"), None, None),
+                (Some("java.security"), Some("_"), None),
+                (Some("javax.crypto"), Some("Mac"), None),
+                (Some("javax.crypto"), Some("KeyGenerator"), Some("KG")),
+                (Some("a.b.c.D"), None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_c_includes() {
+        let source = "#include <openssl/evp.h>\n#include \"crypto/local.h\"\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_c::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let matches = engine
+            .query("c", "imports", tree.root_node(), source)
+            .unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].get("header"), Some("<openssl/evp.h>"));
+        assert_eq!(matches[1].get("header"), Some("\"crypto/local.h\""));
+    }
+
+    #[test]
+    fn test_cpp_qualified_calls() {
+        let source =
+            "#include <botan/hash.h>\nvoid f() { Botan::HashFunction::create(\"SHA-256\"); }\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_cpp::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let imports = engine
+            .query("cpp", "imports", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(imports[0].get("header"), Some("<botan/hash.h>"));
+
+        let calls = engine
+            .query("cpp", "calls", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].get("function"),
+            Some("Botan::HashFunction::create")
+        );
+    }
+
+    #[test]
+    fn test_csharp_usings() {
+        let source = "using System.Security.Cryptography;\nusing Hash = System.Security.Cryptography.SHA256;\nclass C { void F() { Aes.Create(); } }\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_c_sharp::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let imports = engine
+            .query("csharp", "imports", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].get("path"), Some("System.Security.Cryptography"));
+        assert!(imports[0].get("wildcard").is_some());
+        assert_eq!(imports[1].get("alias"), Some("Hash"));
+        assert_eq!(
+            imports[1].get("path"),
+            Some("System.Security.Cryptography.SHA256")
+        );
+        assert_eq!(imports[1].get("wildcard"), None);
+
+        let calls = engine
+            .query("csharp", "calls", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].get("package"), Some("Aes"));
+        assert_eq!(calls[0].get("function"), Some("Create"));
+    }
+
+    #[test]
+    fn test_ruby_requires() {
+        let source =
+            "require 'openssl'\nrequire \"digest\"\ncipher = OpenSSL::Cipher.new('aes-256-gcm')\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_ruby::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let imports = engine
+            .query("ruby", "imports", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].get("path"), Some("openssl"));
+        assert_eq!(imports[1].get("path"), Some("digest"));
+
+        let calls = engine
+            .query("ruby", "calls", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].get("package"), Some("OpenSSL::Cipher"));
+        assert_eq!(calls[0].get("function"), Some("new"));
+    }
+
+    #[test]
+    fn test_php_uses() {
+        let source = "<?php\nuse phpseclib3\\Crypt\\AES;\nuse App\\Params as P;\n$c = openssl_encrypt($d, 'aes-256-gcm', $k);\nAES::createKey();\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_php::LANGUAGE_PHP.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let imports = engine
+            .query("php", "imports", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].get("path"), Some("phpseclib3\\Crypt\\AES"));
+        assert_eq!(imports[1].get("path"), Some("App\\Params"));
+        assert_eq!(imports[1].get("alias"), Some("P"));
+
+        let calls = engine
+            .query("php", "calls", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].get("function"), Some("openssl_encrypt"));
+        assert_eq!(calls[1].get("package"), Some("AES"));
+        assert_eq!(calls[1].get("function"), Some("createKey"));
+    }
+
+    #[test]
+    fn test_swift_imports() {
+        let source = "import CryptoKit\nimport CommonCrypto\nlet d = SHA256.hash(data: data)\nlet k = SymmetricKey(size: .bits256)\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_swift::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let engine = QueryEngine::new();
+
+        let imports = engine
+            .query("swift", "imports", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].get("path"), Some("CryptoKit"));
+        assert!(imports[0].get("wildcard").is_some());
+        assert_eq!(imports[1].get("path"), Some("CommonCrypto"));
+
+        let calls = engine
+            .query("swift", "calls", tree.root_node(), source)
+            .unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].get("package"), Some("SHA256"));
+        assert_eq!(calls[0].get("function"), Some("hash"));
+        assert_eq!(calls[1].get("function"), Some("SymmetricKey"));
+    }
+
     #[test]
     fn test_python_calls() {
         let source = r#"
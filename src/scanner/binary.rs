@@ -0,0 +1,547 @@
+//! Heuristic scanning of compiled artifacts (`--scan-binaries`).
+//!
+//! Prebuilt blobs (ELF/Mach-O executables, wasm modules, jars, `.pyc`) can
+//! carry crypto the source scanner never sees. This module looks for three
+//! kinds of indicators in their raw bytes: embedded library version strings,
+//! algorithm name constants, and well-known constant tables (S-boxes, round
+//! constants, initial hash values). Matches are reported as low-confidence
+//! findings together with artifact metadata.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "discovery")]
+use walkdir::WalkDir;
+
+/// Files larger than this are skipped.
+pub const MAX_ARTIFACT_SIZE: u64 = 256 * 1024 * 1024;
+
+const MIN_STRING_LEN: usize = 4;
+const MAX_MATCHES_PER_INDICATOR: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactType {
+    Elf,
+    MachO,
+    Wasm,
+    Jar,
+    Pyc,
+}
+
+impl ArtifactType {
+    /// Identifies an artifact from its extension and leading magic bytes.
+    pub fn detect(path: &Path, header: &[u8]) -> Option<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match ext.as_deref() {
+            Some("jar") | Some("war") | Some("ear") if header.starts_with(b"PK\x03\x04") => {
+                return Some(Self::Jar)
+            }
+            Some("pyc") => return Some(Self::Pyc),
+            _ => {}
+        }
+
+        if header.starts_with(b"\x7fELF") {
+            Some(Self::Elf)
+        } else if header.starts_with(b"\0asm") {
+            Some(Self::Wasm)
+        } else if header.len() >= 4
+            && matches!(
+                header[..4],
+                [0xfe, 0xed, 0xfa, 0xce]
+                    | [0xfe, 0xed, 0xfa, 0xcf]
+                    | [0xce, 0xfa, 0xed, 0xfe]
+                    | [0xcf, 0xfa, 0xed, 0xfe]
+            )
+        {
+            Some(Self::MachO)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndicatorKind {
+    /// An embedded crypto library version string, e.g. `OpenSSL 1.1.1k`.
+    LibraryVersion,
+    /// An algorithm or library identifier such as `aes-256-gcm`.
+    AlgorithmName,
+    /// A known constant table such as the AES S-box.
+    ConstantTable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinaryFinding {
+    pub file: String,
+    pub artifact_type: ArtifactType,
+    pub size: u64,
+    pub kind: IndicatorKind,
+    pub indicator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    pub offset: usize,
+    pub confidence: String,
+}
+
+struct ConstantPattern {
+    name: &'static str,
+    algorithm: &'static str,
+    bytes: &'static [u8],
+}
+
+/// Leading bytes of well-known tables, in the byte orders they are stored in.
+const CONSTANT_PATTERNS: &[ConstantPattern] = &[
+    ConstantPattern {
+        name: "AES S-box",
+        algorithm: "AES",
+        bytes: &[
+            0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7,
+            0xab, 0x76,
+        ],
+    },
+    ConstantPattern {
+        name: "AES inverse S-box",
+        algorithm: "AES",
+        bytes: &[
+            0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3,
+            0xd7, 0xfb,
+        ],
+    },
+    ConstantPattern {
+        name: "SHA-256 round constants (LE)",
+        algorithm: "SHA-256",
+        bytes: &[0x98, 0x2f, 0x8a, 0x42, 0x91, 0x44, 0x37, 0x71],
+    },
+    ConstantPattern {
+        name: "SHA-256 round constants (BE)",
+        algorithm: "SHA-256",
+        bytes: &[0x42, 0x8a, 0x2f, 0x98, 0x71, 0x37, 0x44, 0x91],
+    },
+    ConstantPattern {
+        name: "SHA-256 initial hash (LE)",
+        algorithm: "SHA-256",
+        bytes: &[0x67, 0xe6, 0x09, 0x6a, 0x85, 0xae, 0x67, 0xbb],
+    },
+    ConstantPattern {
+        name: "SHA-512 round constants (LE)",
+        algorithm: "SHA-512",
+        bytes: &[0x22, 0xae, 0x28, 0xd7, 0x98, 0x2f, 0x8a, 0x42],
+    },
+    ConstantPattern {
+        name: "MD5 sine table (LE)",
+        algorithm: "MD5",
+        bytes: &[0x78, 0xa4, 0x6a, 0xd7, 0x56, 0xb7, 0xc7, 0xe8],
+    },
+    ConstantPattern {
+        name: "SHA-1 initial hash (LE)",
+        algorithm: "SHA-1",
+        bytes: &[
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98,
+        ],
+    },
+    ConstantPattern {
+        name: "Blowfish P-array (LE)",
+        algorithm: "Blowfish",
+        bytes: &[0x88, 0x6a, 0x3f, 0x24, 0xd3, 0x08, 0xa3, 0x85],
+    },
+    ConstantPattern {
+        name: "ChaCha20 sigma",
+        algorithm: "ChaCha20",
+        bytes: b"expand 32-byte k",
+    },
+];
+
+/// Library version prefixes; a match must be followed by a version number.
+const VERSION_PREFIXES: &[(&str, &str)] = &[
+    ("OpenSSL ", "OpenSSL"),
+    ("LibreSSL ", "LibreSSL"),
+    ("BoringSSL", "BoringSSL"),
+    ("mbed TLS ", "mbed TLS"),
+    ("wolfSSL ", "wolfSSL"),
+    ("libsodium", "libsodium"),
+    ("GnuTLS ", "GnuTLS"),
+];
+
+/// Algorithm identifiers as they appear in library name tables and jar entries.
+const ALGORITHM_NAMES: &[(&str, &str)] = &[
+    ("aes-128-cbc", "AES"),
+    ("aes-256-cbc", "AES"),
+    ("aes-128-gcm", "AES"),
+    ("aes-256-gcm", "AES"),
+    ("AES/CBC/PKCS5Padding", "AES"),
+    ("AES/GCM/NoPadding", "AES"),
+    ("AES/ECB/PKCS5Padding", "AES"),
+    ("des-ede3-cbc", "3DES"),
+    ("DESede", "3DES"),
+    ("chacha20-poly1305", "ChaCha20-Poly1305"),
+    ("sha256WithRSAEncryption", "RSA"),
+    ("sha1WithRSAEncryption", "RSA"),
+    ("md5WithRSAEncryption", "RSA"),
+    ("ecdsa-with-SHA256", "ECDSA"),
+    ("RSA/ECB/PKCS1Padding", "RSA"),
+    ("PBKDF2WithHmacSHA256", "PBKDF2"),
+    ("PBKDF2WithHmacSHA1", "PBKDF2"),
+    ("HmacSHA256", "HMAC"),
+    ("org/bouncycastle/", "BouncyCastle"),
+    ("javax/crypto/", "JCA"),
+    ("Crypto.Cipher", "PyCryptodome"),
+    ("cryptography.hazmat", "cryptography"),
+];
+
+/// Scans one file; returns `None` if it is not a recognised artifact.
+pub fn scan_artifact(path: &Path) -> std::io::Result<Option<Vec<BinaryFinding>>> {
+    let metadata = fs::metadata(path)?;
+    if metadata.len() > MAX_ARTIFACT_SIZE {
+        return Ok(None);
+    }
+
+    let mut header = [0u8; 8];
+    let read = fs::File::open(path)?.read(&mut header)?;
+    let Some(artifact_type) = ArtifactType::detect(path, &header[..read]) else {
+        return Ok(None);
+    };
+
+    let bytes = fs::read(path)?;
+    Ok(Some(scan_bytes(
+        &path.to_string_lossy(),
+        artifact_type,
+        &bytes,
+    )))
+}
+
+/// Walks `root` and scans every recognised artifact, skipping hidden dirs.
+#[cfg(feature = "discovery")]
+pub fn scan_directory(root: &Path) -> Vec<BinaryFinding> {
+    let mut findings = Vec::new();
+
+    let walker = WalkDir::new(root).into_iter().filter_entry(|e| {
+        e.depth() == 0
+            || !(e.file_type().is_dir() && e.file_name().to_string_lossy().starts_with('.'))
+    });
+    for entry in walker.flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match scan_artifact(entry.path()) {
+            Ok(Some(found)) => findings.extend(found),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::debug!(file = %entry.path().display(), error = %e, "failed to read artifact")
+            }
+        }
+    }
+
+    findings
+}
+
+/// Scans the bytes of an artifact. The entries of a JAR are inflated and
+/// scanned one by one; a JAR that cannot be read is scanned as it is.
+pub fn scan_bytes(file: &str, artifact_type: ArtifactType, bytes: &[u8]) -> Vec<BinaryFinding> {
+    #[cfg(feature = "discovery")]
+    if artifact_type == ArtifactType::Jar {
+        use crate::discovery::languages::java::jar;
+
+        match jar::read_entries(bytes, MAX_ARTIFACT_SIZE) {
+            Ok(entries) => return scan_jar_entries(file, bytes.len() as u64, &entries),
+            Err(e) => tracing::debug!(file, error = %e, "failed to read jar entries"),
+        }
+    }
+    scan_contents(file, artifact_type, bytes.len() as u64, bytes)
+}
+
+/// Scans the name and contents of every JAR entry, reporting matches under
+/// `file!/name`. An indicator found in several entries is reported once.
+#[cfg(feature = "discovery")]
+fn scan_jar_entries(
+    file: &str,
+    size: u64,
+    entries: &[(std::path::PathBuf, Vec<u8>)],
+) -> Vec<BinaryFinding> {
+    let mut findings: Vec<BinaryFinding> = Vec::new();
+    for (name, contents) in entries {
+        let name = name.to_string_lossy();
+        let entry = format!("{file}!/{name}");
+        let found = scan_contents(&entry, ArtifactType::Jar, size, name.as_bytes())
+            .into_iter()
+            .chain(scan_contents(&entry, ArtifactType::Jar, size, contents));
+        for finding in found {
+            if !findings
+                .iter()
+                .any(|f| f.kind == finding.kind && f.indicator == finding.indicator)
+            {
+                findings.push(finding);
+            }
+        }
+    }
+    findings
+}
+
+fn scan_contents(
+    file: &str,
+    artifact_type: ArtifactType,
+    size: u64,
+    bytes: &[u8],
+) -> Vec<BinaryFinding> {
+    let finding = |kind, indicator: String, algorithm: Option<&str>, offset| BinaryFinding {
+        file: file.to_string(),
+        artifact_type,
+        size,
+        kind,
+        indicator,
+        algorithm: algorithm.map(str::to_string),
+        offset,
+        confidence: "low".to_string(),
+    };
+
+    let mut findings = Vec::new();
+
+    for pattern in CONSTANT_PATTERNS {
+        for offset in find_all(bytes, pattern.bytes).take(MAX_MATCHES_PER_INDICATOR) {
+            findings.push(finding(
+                IndicatorKind::ConstantTable,
+                pattern.name.to_string(),
+                Some(pattern.algorithm),
+                offset,
+            ));
+        }
+    }
+
+    let mut seen_versions = Vec::new();
+    for (offset, text) in printable_strings(bytes) {
+        for (prefix, library) in VERSION_PREFIXES {
+            if let Some(pos) = text.find(prefix) {
+                let version = version_after(&text[pos + prefix.len()..]);
+                if let Some(version) = version {
+                    let indicator = format!("{library} {version}");
+                    if !seen_versions.contains(&indicator) {
+                        findings.push(finding(
+                            IndicatorKind::LibraryVersion,
+                            indicator.clone(),
+                            None,
+                            offset + pos,
+                        ));
+                        seen_versions.push(indicator);
+                    }
+                }
+            }
+        }
+    }
+
+    for (name, algorithm) in ALGORITHM_NAMES {
+        if let Some(offset) = find_all(bytes, name.as_bytes()).next() {
+            findings.push(finding(
+                IndicatorKind::AlgorithmName,
+                name.to_string(),
+                Some(algorithm),
+                offset,
+            ));
+        }
+    }
+
+    findings.sort_by_key(|f| f.offset);
+    findings
+}
+
+fn find_all<'a>(haystack: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .filter(move |(_, window)| *window == needle)
+        .map(|(offset, _)| offset)
+}
+
+/// Runs of printable ASCII at least `MIN_STRING_LEN` long, like `strings(1)`.
+fn printable_strings(bytes: &[u8]) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = None;
+    let mut runs = Vec::new();
+    for (i, &b) in bytes.iter().enumerate() {
+        let printable = b == b' ' || b.is_ascii_graphic();
+        match (printable, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                if i - s >= MIN_STRING_LEN {
+                    runs.push(s..i);
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        if bytes.len() - s >= MIN_STRING_LEN {
+            runs.push(s..bytes.len());
+        }
+    }
+
+    runs.into_iter().map(move |r| {
+        let start = r.start;
+        (start, std::str::from_utf8(&bytes[r]).unwrap_or(""))
+    })
+}
+
+/// Returns the version number at the start of `text` (after optional spaces).
+fn version_after(text: &str) -> Option<&str> {
+    let text = text.trim_start_matches([' ', '/', '-']);
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .unwrap_or(text.len());
+    let candidate = &text[..end];
+    (candidate.starts_with(|c: char| c.is_ascii_digit()) && candidate.contains('.'))
+        .then_some(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf_with(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x7fELF\x02\x01\x01\0".to_vec();
+        bytes.extend_from_slice(&[0u8; 24]);
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(&[0u8; 8]);
+        bytes
+    }
+
+    #[test]
+    fn test_detect_artifact_types() {
+        assert_eq!(
+            ArtifactType::detect(Path::new("app"), b"\x7fELF\x02"),
+            Some(ArtifactType::Elf)
+        );
+        assert_eq!(
+            ArtifactType::detect(Path::new("m.wasm"), b"\0asm\x01\0\0\0"),
+            Some(ArtifactType::Wasm)
+        );
+        assert_eq!(
+            ArtifactType::detect(Path::new("tool"), &[0xcf, 0xfa, 0xed, 0xfe]),
+            Some(ArtifactType::MachO)
+        );
+        assert_eq!(
+            ArtifactType::detect(Path::new("lib.jar"), b"PK\x03\x04"),
+            Some(ArtifactType::Jar)
+        );
+        assert_eq!(
+            ArtifactType::detect(Path::new("mod.cpython-311.pyc"), b"\xa7\r\r\n"),
+            Some(ArtifactType::Pyc)
+        );
+        assert_eq!(
+            ArtifactType::detect(Path::new("a.zip"), b"PK\x03\x04"),
+            None
+        );
+        assert_eq!(ArtifactType::detect(Path::new("main.go"), b"package"), None);
+    }
+
+    #[test]
+    fn test_scan_bytes_finds_openssl_version() {
+        let bytes = elf_with(b"OpenSSL 1.1.1k  25 Mar 2021\0");
+        let findings = scan_bytes("app", ArtifactType::Elf, &bytes);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, IndicatorKind::LibraryVersion);
+        assert_eq!(findings[0].indicator, "OpenSSL 1.1.1k");
+        assert_eq!(findings[0].offset, 32);
+        assert_eq!(findings[0].confidence, "low");
+    }
+
+    #[test]
+    fn test_scan_bytes_finds_constant_tables() {
+        let mut payload = vec![
+            0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7,
+            0xab, 0x76,
+        ];
+        payload.extend_from_slice(&[0x98, 0x2f, 0x8a, 0x42, 0x91, 0x44, 0x37, 0x71]);
+        let findings = scan_bytes("app", ArtifactType::Elf, &elf_with(&payload));
+
+        let algorithms: Vec<_> = findings
+            .iter()
+            .filter(|f| f.kind == IndicatorKind::ConstantTable)
+            .map(|f| f.algorithm.as_deref().unwrap())
+            .collect();
+        assert_eq!(algorithms, vec!["AES", "SHA-256"]);
+    }
+
+    #[test]
+    fn test_scan_bytes_finds_algorithm_names_in_jar_entries() {
+        let bytes = b"PK\x03\x04....org/bouncycastle/crypto/engines/AESEngine.class....";
+        let findings = scan_bytes("lib.jar", ArtifactType::Jar, bytes);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].indicator, "org/bouncycastle/");
+        assert_eq!(findings[0].algorithm.as_deref(), Some("BouncyCastle"));
+    }
+
+    #[test]
+    #[cfg(feature = "discovery")]
+    fn test_scan_bytes_inflates_jar_entries() {
+        use crate::discovery::languages::java::jar;
+
+        let bytes = jar::zip(&[
+            ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n"),
+            (
+                "com/example/Vault.class",
+                "AES/GCM/NoPad PBKDF2WithHmac AES/GCM/NoPadding PBKDF2WithHmacSHA256",
+            ),
+        ]);
+        // Deflate stores the repeated names as back-references
+        assert!(!bytes
+            .windows(b"AES/GCM/NoPadding".len())
+            .any(|w| w == b"AES/GCM/NoPadding"));
+
+        let findings = scan_bytes("lib.jar", ArtifactType::Jar, &bytes);
+        let indicators: Vec<_> = findings.iter().map(|f| f.indicator.as_str()).collect();
+        assert_eq!(
+            indicators,
+            vec!["AES/GCM/NoPadding", "PBKDF2WithHmacSHA256", "HmacSHA256"]
+        );
+        assert_eq!(findings[0].file, "lib.jar!/com/example/Vault.class");
+        assert_eq!(findings[0].offset, 29);
+        assert_eq!(findings[0].size, bytes.len() as u64);
+    }
+
+    #[test]
+    fn test_scan_bytes_ignores_plain_mentions() {
+        let bytes = elf_with(b"uses OpenSSL for transport\0");
+        assert!(scan_bytes("app", ArtifactType::Elf, &bytes).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "discovery")]
+    fn test_scan_directory_skips_sources_and_hidden_dirs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join("main.go"), "package main // OpenSSL 3.0.2").unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(
+            root.join(".git").join("blob"),
+            elf_with(b"OpenSSL 3.0.2 15 Mar 2022\0"),
+        )
+        .unwrap();
+        fs::create_dir(root.join("bin")).unwrap();
+        fs::write(
+            root.join("bin").join("tool"),
+            elf_with(b"OpenSSL 3.0.2 15 Mar 2022\0"),
+        )
+        .unwrap();
+
+        let findings = scan_directory(root);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].file.ends_with("tool"));
+        assert_eq!(findings[0].indicator, "OpenSSL 3.0.2");
+    }
+
+    #[test]
+    fn test_version_after() {
+        assert_eq!(version_after(" 3.0.2 15 Mar"), Some("3.0.2"));
+        assert_eq!(version_after("/1.0.18"), Some("1.0.18"));
+        assert_eq!(version_after(" for transport"), None);
+        assert_eq!(version_after(" 3"), None);
+    }
+}
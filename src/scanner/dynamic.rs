@@ -0,0 +1,51 @@
+//! Crypto functions looked up by name at run time: `getattr(hashlib, algo)`
+//! in Python, `crypto[method]` in JavaScript. The called name never appears
+//! in the source, so mappings cannot match these calls; a lookup on a mapped
+//! module is reported instead, with whatever the name resolves to.
+//!
+//! Go and Rust cannot look up package functions by name.
+
+use tree_sitter::Node;
+
+use crate::engine::{Context, NodeCategory};
+
+/// A by-name lookup of `name` on `object`.
+pub(super) struct Lookup<'a> {
+    pub object: Node<'a>,
+    pub name: Node<'a>,
+}
+
+/// The lookup at `node`: a `getattr(object, name)` call or an
+/// `object[name]` subscript.
+pub(super) fn lookup<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<Lookup<'a>> {
+    match (ctx.language(), node.kind()) {
+        ("python", "call") => {
+            let function = node.child_by_field_name("function")?;
+            if function.kind() != "identifier" || ctx.get_node_text(&function) != "getattr" {
+                return None;
+            }
+            let arguments = node.child_by_field_name("arguments")?;
+            let mut cursor = arguments.walk();
+            let mut arguments = arguments.named_children(&mut cursor);
+            let object = arguments.next()?;
+            let name = arguments.next()?;
+            (name.kind() != "keyword_argument").then_some(Lookup { object, name })
+        }
+        ("javascript" | "typescript", "subscript_expression") => Some(Lookup {
+            object: node.child_by_field_name("object")?,
+            name: node.child_by_field_name("index")?,
+        }),
+        _ => None,
+    }
+}
+
+/// The call invoking the function looked up at `node`, when its result is
+/// called directly: `getattr(hashlib, algo)(data)`.
+pub(super) fn invocation<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<Node<'a>> {
+    let parent = node.parent()?;
+    let is_callee = ctx.is_node_category(parent.kind(), NodeCategory::CallExpression)
+        && parent
+            .child_by_field_name("function")
+            .is_some_and(|function| function.id() == node.id());
+    is_callee.then_some(parent)
+}
@@ -0,0 +1,233 @@
+//! Pattern scanning of files no grammar parses (`--fallback-scan`).
+//!
+//! Perl scripts, COBOL copybooks or in-house DSLs can call crypto the
+//! source scanner never sees. Rules files list `fallback_patterns`,
+//! regular expressions matched line by line against such files: those in
+//! languages no scan parses, and any other extension a pattern names. Each
+//! match is reported as a low-confidence finding with its line and column.
+
+#[cfg(feature = "discovery")]
+use std::fs;
+#[cfg(feature = "discovery")]
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "discovery")]
+use walkdir::WalkDir;
+
+#[cfg(feature = "discovery")]
+use crate::discovery::dry_run::UNSUPPORTED_EXTENSIONS;
+#[cfg(feature = "discovery")]
+use crate::discovery::extensions::override_for;
+
+/// Files larger than this are skipped.
+pub const MAX_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A `fallback_patterns` entry of a rules file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FallbackPattern {
+    /// Name reported on matches; defaults to the pattern.
+    #[serde(default)]
+    pub name: String,
+    /// Regular expression matched against each line.
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// Extensions the pattern applies to, e.g. `cpy`; without any, every
+    /// file in a language no scan parses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+}
+
+/// A [`FallbackPattern`] with its expression compiled.
+#[derive(Debug, Clone)]
+pub struct FallbackRule {
+    pub pattern: FallbackPattern,
+    regex: Regex,
+}
+
+impl FallbackRule {
+    pub fn compile(pattern: FallbackPattern) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&pattern.pattern)?;
+        Ok(Self { pattern, regex })
+    }
+
+    fn name(&self) -> &str {
+        if self.pattern.name.is_empty() {
+            &self.pattern.pattern
+        } else {
+            &self.pattern.name
+        }
+    }
+
+    fn applies_to(&self, extension: &str) -> bool {
+        self.pattern.extensions.is_empty()
+            || self
+                .pattern
+                .extensions
+                .iter()
+                .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(extension))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FallbackFinding {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// Name of the pattern that matched.
+    pub pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// The matched text.
+    pub text: String,
+    pub confidence: String,
+}
+
+/// Matches `rules` against each line of `source`, a file with the given
+/// `extension`. A pattern is reported once per line, at its first match.
+pub fn scan_source(
+    file: &str,
+    extension: &str,
+    source: &str,
+    rules: &[FallbackRule],
+) -> Vec<FallbackFinding> {
+    let rules: Vec<&FallbackRule> = rules.iter().filter(|r| r.applies_to(extension)).collect();
+    let mut findings = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        for rule in &rules {
+            if let Some(found) = rule.regex.find(line) {
+                findings.push(FallbackFinding {
+                    file: file.to_string(),
+                    line: index + 1,
+                    column: found.start() + 1,
+                    pattern: rule.name().to_string(),
+                    algorithm: rule.pattern.algorithm.clone(),
+                    text: found.as_str().to_string(),
+                    confidence: "low".to_string(),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Walks `root` and scans the files `rules` apply to that no scan parses:
+/// those in [`UNSUPPORTED_EXTENSIONS`] and with an extension a pattern
+/// names. Hidden directories and files the config file maps to a language
+/// or skips are left out.
+#[cfg(feature = "discovery")]
+pub fn scan_directory(root: &Path, rules: &[FallbackRule]) -> Vec<FallbackFinding> {
+    let mut findings = Vec::new();
+    if rules.is_empty() {
+        return findings;
+    }
+
+    let walker = WalkDir::new(root).into_iter().filter_entry(|e| {
+        e.depth() == 0
+            || !(e.file_type().is_dir() && e.file_name().to_string_lossy().starts_with('.'))
+    });
+    for entry in walker.flatten() {
+        if !entry.file_type().is_file() || override_for(entry.path()).is_some() {
+            continue;
+        }
+        let Some(extension) = fallback_extension(entry.path(), rules) else {
+            continue;
+        };
+        match read_source(entry.path()) {
+            Ok(Some(source)) => findings.extend(scan_source(
+                &entry.path().to_string_lossy(),
+                &extension,
+                &source,
+                rules,
+            )),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::debug!(file = %entry.path().display(), error = %e, "failed to read file")
+            }
+        }
+    }
+
+    findings
+}
+
+/// The lowercase extension of `path` if it is one the fallback scans.
+#[cfg(feature = "discovery")]
+fn fallback_extension(path: &Path, rules: &[FallbackRule]) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    let named = rules.iter().any(|rule| {
+        rule.pattern
+            .extensions
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(&extension))
+    });
+    (named || UNSUPPORTED_EXTENSIONS.contains(&extension.as_str())).then_some(extension)
+}
+
+/// The text of `path`, or `None` when it is too large or not UTF-8.
+#[cfg(feature = "discovery")]
+fn read_source(path: &Path) -> std::io::Result<Option<String>> {
+    if fs::metadata(path)?.len() > MAX_FILE_SIZE {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(fs::read(path)?).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, pattern: &str, algorithm: &str, extensions: &[&str]) -> FallbackRule {
+        FallbackRule::compile(FallbackPattern {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            algorithm: Some(algorithm.to_string()),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scan_source() {
+        let rules = [
+            rule("perl_md5", r"Digest::MD5|md5_hex\(", "MD5", &[]),
+            rule("cobol_des", r"(?i)\bDES-ENCRYPT\b", "DES", &["cpy"]),
+        ];
+        let source = "use Digest::MD5 qw(md5_hex);\nmy $sum = md5_hex($data);\nprint $sum;\n";
+
+        let findings = scan_source("sum.pl", "pl", source, &rules);
+        assert_eq!(findings.len(), 2);
+        assert_eq!((findings[0].line, findings[0].column), (1, 5));
+        assert_eq!(findings[0].text, "Digest::MD5");
+        assert_eq!((findings[1].line, findings[1].column), (2, 11));
+        assert_eq!(findings[1].pattern, "perl_md5");
+        assert_eq!(findings[1].algorithm.as_deref(), Some("MD5"));
+        assert_eq!(findings[1].confidence, "low");
+
+        let findings = scan_source("CRYPT.cpy", "cpy", "  CALL 'des-encrypt'.\n", &rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].algorithm.as_deref(), Some("DES"));
+    }
+
+    #[cfg(feature = "discovery")]
+    #[test]
+    fn test_scan_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("sum.pl"), "use Digest::MD5;\n").unwrap();
+        std::fs::write(temp.path().join("KEYS.CPY"), "DES-ENCRYPT\n").unwrap();
+        std::fs::write(temp.path().join("app.py"), "import Digest::MD5\n").unwrap();
+        let rules = [
+            rule("", r"Digest::MD5", "MD5", &[]),
+            rule("cobol_des", r"DES-ENCRYPT", "DES", &["cpy"]),
+        ];
+
+        let mut findings = scan_directory(temp.path(), &rules);
+        findings.sort_by(|a, b| a.file.cmp(&b.file));
+        assert_eq!(findings.len(), 2);
+        assert!(findings[0].file.ends_with("KEYS.CPY"));
+        assert!(findings[1].file.ends_with("sum.pl"));
+        assert_eq!(findings[1].pattern, "Digest::MD5");
+    }
+}
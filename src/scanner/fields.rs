@@ -0,0 +1,142 @@
+//! Go struct types named in type expressions and inferred for receivers,
+//! so that a field set after construction (`cfg.MinVersion = ...`) is only
+//! taken for a setting of the struct the receiver was declared as.
+
+use tree_sitter::Node;
+
+use crate::engine::Context;
+
+/// Declarations that give a variable its type or initial value.
+const DECLARATION_KINDS: &[&str] = &["parameter_declaration", "var_spec", "short_var_declaration"];
+
+/// The struct named by a type expression, as its name and package:
+/// `tls.Config`, `*tls.Config`, `Config` or `vault.Options[T]`, whose type
+/// arguments are dropped.
+pub(crate) fn type_name(node: Node, ctx: &Context) -> Option<(String, Option<String>)> {
+    match node.kind() {
+        "selector_expression" | "qualified_type" => {
+            let package = node
+                .child_by_field_name("operand")
+                .or_else(|| node.child_by_field_name("package"))?;
+            let name = node
+                .child_by_field_name("field")
+                .or_else(|| node.child_by_field_name("name"))?;
+            Some((ctx.get_node_text(&name), Some(ctx.get_node_text(&package))))
+        }
+        "identifier" | "type_identifier" => Some((ctx.get_node_text(&node), None)),
+        "generic_type" => type_name(node.child_by_field_name("type")?, ctx),
+        "pointer_type" | "parenthesized_type" => type_name(node.named_child(0)?, ctx),
+        _ => None,
+    }
+}
+
+/// The struct the variable `name` holds where it is used at
+/// `use_position`, from its closest declaration before the use: a typed
+/// parameter or `var`, or one initialized with a composite literal
+/// (`&tls.Config{}`) or `new(tls.Config)`.
+pub(crate) fn receiver_type(
+    root: Node,
+    name: &str,
+    use_position: usize,
+    ctx: &Context,
+) -> Option<(String, Option<String>)> {
+    let mut declared = None;
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.start_byte() >= use_position {
+            continue;
+        }
+        if node.kind() == "identifier" && ctx.get_node_text(&node) == name {
+            if let Some(declaration) = declaring(node) {
+                let is_closer = declared
+                    .as_ref()
+                    .is_none_or(|(position, _)| node.start_byte() > *position);
+                if is_closer {
+                    declared = Some((node.start_byte(), declared_type(declaration, node, ctx)));
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    declared.and_then(|(_, declared_type)| declared_type)
+}
+
+/// The declaration `identifier` is a name declared by, if any; not one it
+/// is merely used in (`b := a`).
+fn declaring(identifier: Node) -> Option<Node> {
+    let parent = identifier.parent()?;
+    if DECLARATION_KINDS.contains(&parent.kind()) {
+        return Some(parent);
+    }
+    let declaration = parent.parent()?;
+    let names = declaration.child_by_field_name("left")?;
+    (declaration.kind() == "short_var_declaration" && names == parent).then_some(declaration)
+}
+
+/// The type `declaration` gives `variable`, written out or inferred from
+/// its value.
+fn declared_type(
+    declaration: Node,
+    variable: Node,
+    ctx: &Context,
+) -> Option<(String, Option<String>)> {
+    if let Some(declared) = declaration.child_by_field_name("type") {
+        return type_name(declared, ctx);
+    }
+
+    // The value at the variable's position in `a, b := x, y`
+    let names = declaration
+        .child_by_field_name("left")
+        .unwrap_or(declaration);
+    let values = declaration
+        .child_by_field_name("right")
+        .or_else(|| declaration.child_by_field_name("value"))?;
+    let mut cursor = names.walk();
+    let position = names
+        .named_children(&mut cursor)
+        .filter(|name| name.kind() == "identifier")
+        .position(|name| name == variable)?;
+    value_type(values.named_child(position)?, ctx)
+}
+
+/// The struct a value is: `tls.Config{...}`, `&tls.Config{...}` or
+/// `new(tls.Config)`.
+fn value_type(value: Node, ctx: &Context) -> Option<(String, Option<String>)> {
+    match value.kind() {
+        "composite_literal" => type_name(value.child_by_field_name("type")?, ctx),
+        "unary_expression" => value_type(value.child_by_field_name("operand")?, ctx),
+        "call_expression" => {
+            let function = value.child_by_field_name("function")?;
+            if ctx.get_node_text(&function) != "new" {
+                return None;
+            }
+            let arguments = value.child_by_field_name("arguments")?;
+            type_name(arguments.named_child(0)?, ctx)
+        }
+        _ => None,
+    }
+}
+
+/// The fields an assignment sets on a variable, with the values they are
+/// given: `cfg.MinVersion` and `tls.VersionTLS13` in
+/// `cfg.MinVersion = tls.VersionTLS13`.
+pub(crate) fn field_assignments(node: Node) -> Vec<(Node, Node, Node)> {
+    let (Some(left), Some(right)) = (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return Vec::new();
+    };
+    let mut targets = left.walk();
+    let mut values = right.walk();
+    left.named_children(&mut targets)
+        .zip(right.named_children(&mut values))
+        .filter(|(target, _)| target.kind() == "selector_expression")
+        .filter_map(|(target, value)| {
+            let receiver = target.child_by_field_name("operand")?;
+            let field = target.child_by_field_name("field")?;
+            (receiver.kind() == "identifier").then_some((receiver, field, value))
+        })
+        .collect()
+}
@@ -3,15 +3,55 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Default)]
 pub struct ImportMap {
     imports: HashMap<String, String>,
+    /// Packages whose names are all imported (`import javax.crypto.*;`).
+    wildcards: Vec<String>,
+    /// Headers whose functions are called unqualified
+    /// (`#include <openssl/evp.h>`).
+    headers: Vec<String>,
 }
 
 impl ImportMap {
     pub fn new() -> Self {
         Self {
             imports: HashMap::new(),
+            wildcards: Vec::new(),
+            headers: Vec::new(),
         }
     }
 
+    pub fn insert_header(&mut self, header: String) {
+        if !self.headers.contains(&header) {
+            self.headers.push(header);
+        }
+    }
+
+    /// Included headers, in include order.
+    pub fn headers(&self) -> impl Iterator<Item = &str> {
+        self.headers.iter().map(String::as_str)
+    }
+
+    pub fn insert_wildcard(&mut self, package: String) {
+        if !self.wildcards.contains(&package) {
+            self.wildcards.push(package);
+        }
+    }
+
+    /// Packages imported with a wildcard, in import order.
+    pub fn wildcards(&self) -> impl Iterator<Item = &str> {
+        self.wildcards.iter().map(String::as_str)
+    }
+
+    /// Full paths `short_name` could stand for through a wildcard import,
+    /// in import order.
+    pub fn wildcard_candidates<'a>(
+        &'a self,
+        short_name: &'a str,
+    ) -> impl Iterator<Item = String> + 'a {
+        self.wildcards
+            .iter()
+            .map(move |package| format!("{package}.{short_name}"))
+    }
+
     pub fn insert(&mut self, short_name: String, full_path: String) {
         self.imports.insert(short_name, full_path);
     }
@@ -25,16 +65,31 @@ impl ImportMap {
     }
 
     pub fn len(&self) -> usize {
-        self.imports.len()
+        self.imports.len() + self.wildcards.len() + self.headers.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.imports.is_empty()
+        self.imports.is_empty() && self.wildcards.is_empty() && self.headers.is_empty()
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
         self.imports.iter()
     }
+
+    /// Local names bound to `module` or anything under it (`module.Name`,
+    /// `module/sub`), ignoring case.
+    pub fn aliases_of(&self, module: &str) -> Vec<&str> {
+        let module = module.to_lowercase();
+        self.imports
+            .iter()
+            .filter(|(_, path)| {
+                path.to_lowercase()
+                    .strip_prefix(&module)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '/']))
+            })
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -51,6 +106,57 @@ mod tests {
         assert_eq!(imports.resolve("sha256"), Some("crypto/sha256".to_string()));
     }
 
+    #[test]
+    fn test_aliases_of_module() {
+        let mut imports = ImportMap::new();
+        imports.insert(
+            "CryptographyClient".to_string(),
+            "@azure/keyvault-keys.CryptographyClient".to_string(),
+        );
+        imports.insert("kms".to_string(), "google.cloud.kms".to_string());
+
+        assert_eq!(
+            imports.aliases_of("@Azure/keyvault-keys"),
+            vec!["CryptographyClient"]
+        );
+        assert_eq!(imports.aliases_of("google.cloud.kms"), vec!["kms"]);
+        assert_eq!(imports.aliases_of("google.cloud"), vec!["kms"]);
+        assert!(imports.aliases_of("google.cloud.km").is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_candidates() {
+        let mut imports = ImportMap::new();
+        imports.insert_wildcard("javax.crypto".to_string());
+        imports.insert_wildcard("java.security".to_string());
+        imports.insert_wildcard("javax.crypto".to_string());
+
+        assert_eq!(imports.len(), 2);
+        assert_eq!(
+            imports.wildcard_candidates("Cipher").collect::<Vec<_>>(),
+            vec!["javax.crypto.Cipher", "java.security.Cipher"]
+        );
+        assert_eq!(
+            imports.wildcards().collect::<Vec<_>>(),
+            vec!["javax.crypto", "java.security"]
+        );
+        assert_eq!(imports.resolve("Cipher"), None);
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut imports = ImportMap::new();
+        imports.insert_header("openssl/evp.h".to_string());
+        imports.insert_header("stdio.h".to_string());
+        imports.insert_header("openssl/evp.h".to_string());
+
+        assert_eq!(imports.len(), 2);
+        assert_eq!(
+            imports.headers().collect::<Vec<_>>(),
+            vec!["openssl/evp.h", "stdio.h"]
+        );
+    }
+
     #[test]
     fn test_import_map_not_found() {
         let imports = ImportMap::new();
@@ -0,0 +1,776 @@
+//! Incremental re-scanning for long-lived documents (editor / watch use).
+//!
+//! A [`DocumentStore`] keeps the parsed `Tree` for every open file. Edits are
+//! applied to the previous tree as tree-sitter `InputEdit`s and the file is
+//! reparsed against it, so unchanged subtrees are reused instead of parsing
+//! from scratch. Only the calls an edit can have changed have their
+//! arguments resolved again: those in the function (or top-level statement)
+//! an edit touched, those whose values were read from an edited range, and
+//! those with an unresolved argument. The others keep the arguments of the
+//! previous scan. The affected byte ranges are kept so callers can limit
+//! what they republish.
+//!
+//! The Node.js addon's `Documents` class (`--features node`) is built on it.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use tree_sitter::{InputEdit, Node, Parser, Point, Tree};
+
+use super::{ScanResult, Scanner};
+use crate::engine::symbol_index::{language_for_path, tree_sitter_language_for_path};
+use crate::engine::{Language, NodeCategory, NodeTypes, Value};
+use crate::error::ParserError;
+
+/// A replacement of `start_byte..old_end_byte` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start_byte: usize,
+    pub old_end_byte: usize,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, new_text: impl Into<String>) -> Self {
+        Self {
+            start_byte: range.start,
+            old_end_byte: range.end,
+            new_text: new_text.into(),
+        }
+    }
+
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        Self::new(at..at, text)
+    }
+
+    pub fn delete(range: Range<usize>) -> Self {
+        Self::new(range, "")
+    }
+}
+
+pub struct Document {
+    language: Language,
+    source: String,
+    tree: Tree,
+    result: ScanResult,
+    version: u64,
+    affected: Vec<Range<usize>>,
+    reresolved: usize,
+}
+
+impl Document {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    pub fn result(&self) -> &ScanResult {
+        &self.result
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Incremented on every applied batch of edits.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Byte ranges (in the current source) touched by the last batch of edits,
+    /// including any ranges whose syntax tree structure changed.
+    pub fn affected_ranges(&self) -> &[Range<usize>] {
+        &self.affected
+    }
+
+    /// Calls whose arguments the last scan resolved, rather than keeping
+    /// them from the scan before.
+    pub fn reresolved_calls(&self) -> usize {
+        self.reresolved
+    }
+}
+
+pub struct DocumentStore {
+    scanner: Scanner,
+    parser: Parser,
+    documents: HashMap<PathBuf, Document>,
+}
+
+impl DocumentStore {
+    pub fn new(scanner: Scanner) -> Self {
+        Self {
+            scanner,
+            parser: Parser::new(),
+            documents: HashMap::new(),
+        }
+    }
+
+    pub fn scanner(&self) -> &Scanner {
+        &self.scanner
+    }
+
+    /// Parses and scans `source`, replacing any document already open at `path`.
+    pub fn open(
+        &mut self,
+        path: impl Into<PathBuf>,
+        source: impl Into<String>,
+    ) -> Result<&Document, ParserError> {
+        let path = path.into();
+        let source = source.into();
+        let language = language_for_path(&path)
+            .ok_or_else(|| ParserError::unsupported_language(path.display().to_string()))?;
+
+        self.set_language(&path, language)?;
+        let tree = self
+            .parser
+            .parse(&source, None)
+            .ok_or_else(|| ParserError::parse_failed(&path))?;
+        let result = self.scan(&path, &tree, &source, language);
+        let affected = std::iter::once(0..source.len()).collect();
+        let reresolved = result.calls.len();
+
+        self.documents.insert(
+            path.clone(),
+            Document {
+                language,
+                source,
+                tree,
+                result,
+                version: 0,
+                affected,
+                reresolved,
+            },
+        );
+        Ok(&self.documents[&path])
+    }
+
+    /// Applies `edits` in order (each in the coordinates produced by the
+    /// previous one), reparses against the old tree and rescans the file,
+    /// resolving again only the calls the edits can have changed. The
+    /// document is left untouched if any edit is out of bounds.
+    pub fn edit(&mut self, path: &Path, edits: &[TextEdit]) -> Result<&Document, ParserError> {
+        let language = self
+            .documents
+            .get(path)
+            .ok_or_else(|| ParserError::unknown_document(path))?
+            .language;
+        self.set_language(path, language)?;
+
+        let file = path.to_string_lossy();
+        let doc = self.documents.get_mut(path).unwrap();
+        let mut source = doc.source.clone();
+        let mut tree = doc.tree.clone();
+        let node_types = NodeTypes::new(language);
+        let mut reusable = reusable_calls(doc, &file);
+        let mut edited: Vec<Range<usize>> = Vec::with_capacity(edits.len());
+        let mut changed = false;
+
+        for edit in edits {
+            if edit.start_byte > edit.old_end_byte
+                || edit.old_end_byte > source.len()
+                || !source.is_char_boundary(edit.start_byte)
+                || !source.is_char_boundary(edit.old_end_byte)
+            {
+                return Err(ParserError::invalid_edit(
+                    edit.start_byte,
+                    edit.old_end_byte,
+                    source.len(),
+                ));
+            }
+            if source[edit.start_byte..edit.old_end_byte] == edit.new_text {
+                continue;
+            }
+            changed = true;
+
+            let input_edit = input_edit(&source, edit);
+            source.replace_range(edit.start_byte..edit.old_end_byte, &edit.new_text);
+            let top_level = is_top_level(&tree, &input_edit, &node_types);
+            tree.edit(&input_edit);
+            shift_ranges(&mut edited, &input_edit);
+            edited.push(input_edit.start_byte..input_edit.new_end_byte);
+            reusable.retain_mut(|call| call.apply(&input_edit, top_level, &file));
+        }
+
+        if !changed {
+            doc.affected.clear();
+            doc.reresolved = 0;
+            return Ok(doc);
+        }
+
+        let new_tree = self
+            .parser
+            .parse(&source, Some(&tree))
+            .ok_or_else(|| ParserError::parse_failed(path))?;
+
+        let mut affected: Vec<Range<usize>> = tree
+            .changed_ranges(&new_tree)
+            .map(|r| r.start_byte..r.end_byte)
+            .collect();
+        affected.extend(edited);
+
+        let reused: HashMap<(usize, usize), ReusableCall> = reusable
+            .into_iter()
+            .map(|call| ((call.call.start, call.call.end), call))
+            .collect();
+        let hits = std::cell::Cell::new(0);
+        doc.result = self.scanner.rescan_tree(
+            &new_tree,
+            source.as_bytes(),
+            &file,
+            language.tree_sitter_name(),
+            &|node| {
+                let call = reused.get(&(node.start_byte(), node.end_byte()))?;
+                hits.set(hits.get() + 1);
+                Some((call.arguments.clone(), call.keywords.clone()))
+            },
+        );
+        doc.reresolved = doc.result.calls.len().saturating_sub(hits.get());
+        doc.source = source;
+        doc.tree = new_tree;
+        doc.affected = merge_ranges(affected);
+        doc.version += 1;
+
+        Ok(doc)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&Document> {
+        self.documents.get(path)
+    }
+
+    pub fn close(&mut self, path: &Path) -> Option<Document> {
+        self.documents.remove(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    fn set_language(&mut self, path: &Path, language: Language) -> Result<(), ParserError> {
+        let ts_language = tree_sitter_language_for_path(path)
+            .ok_or_else(|| ParserError::unsupported_language(language.tree_sitter_name()))?;
+        self.parser
+            .set_language(&ts_language)
+            .map_err(|_| ParserError::language_setup_failed(language.tree_sitter_name()))
+    }
+
+    fn scan(&self, path: &Path, tree: &Tree, source: &str, language: Language) -> ScanResult {
+        self.scanner.scan_tree(
+            tree,
+            source.as_bytes(),
+            &path.to_string_lossy(),
+            language.tree_sitter_name(),
+        )
+    }
+}
+
+/// Unresolved sources that only depend on the code around the call, not on
+/// names an edit elsewhere in the file could declare.
+const LOCAL_SOURCES: &[&str] = &[
+    "function_parameter",
+    "environment_variable",
+    "external_input",
+    "config_value",
+    "runtime_value",
+    "loop_dependent",
+];
+
+/// A call of the previous scan whose arguments can be kept, with the byte
+/// ranges they depend on tracked through each edit.
+struct ReusableCall {
+    call: Range<usize>,
+    /// The call's scope and those of the declarations and literals its
+    /// arguments were read from; an edit in any of them can change them.
+    depends: Vec<Range<usize>>,
+    /// Whether an argument names something not found, which a declaration
+    /// added outside any function could supply.
+    looks_up_names: bool,
+    arguments: Vec<Value>,
+    keywords: Vec<Option<String>>,
+}
+
+impl ReusableCall {
+    /// Moves the call past `edit`, or returns false when the edit can have
+    /// changed its arguments. `top_level` is whether the edit lies outside
+    /// every function.
+    fn apply(&mut self, edit: &InputEdit, top_level: bool, file: &str) -> bool {
+        let touches =
+            |range: &Range<usize>| range.start <= edit.old_end_byte && edit.start_byte <= range.end;
+        if (top_level && self.looks_up_names) || self.depends.iter().any(touches) {
+            return false;
+        }
+
+        let shift_byte = |byte: usize| {
+            if byte >= edit.old_end_byte {
+                byte - edit.old_end_byte + edit.new_end_byte
+            } else {
+                byte
+            }
+        };
+        let shift_line = |line: usize| {
+            if line > edit.old_end_position.row + 1 {
+                line - edit.old_end_position.row + edit.new_end_position.row
+            } else {
+                line
+            }
+        };
+        self.call = shift_byte(self.call.start)..shift_byte(self.call.end);
+        for range in &mut self.depends {
+            *range = shift_byte(range.start)..shift_byte(range.end);
+        }
+        for argument in &mut self.arguments {
+            for span in argument.spans.iter_mut().filter(|span| span.file == file) {
+                span.start_byte = shift_byte(span.start_byte);
+                span.end_byte = shift_byte(span.end_byte);
+                span.start_line = shift_line(span.start_line);
+                span.end_line = shift_line(span.end_line);
+            }
+            for evidence in argument.evidence.iter_mut().filter(|e| e.file == file) {
+                evidence.line = shift_line(evidence.line);
+            }
+        }
+        true
+    }
+}
+
+/// The calls of `doc`'s last scan whose arguments an edit may leave as
+/// they are, i.e. those not looked up by name.
+fn reusable_calls(doc: &Document, file: &str) -> Vec<ReusableCall> {
+    let node_types = NodeTypes::new(doc.language);
+    let root = doc.tree.root_node();
+    let scope_at = |start: usize, end: usize| {
+        let node = root.descendant_for_byte_range(start, end)?;
+        let scope = scope_of(node, &node_types);
+        Some(scope.start_byte()..scope.end_byte())
+    };
+
+    doc.result
+        .calls
+        .iter()
+        .filter(|call| call.file_path == file && call.dynamic_name.is_none())
+        .filter_map(|call| {
+            let start = byte_at(&doc.source, call.line - 1, call.column - 1)?;
+            let end = start + call.raw_text.len();
+            let node = root.descendant_for_byte_range(start, end)?;
+            if node.start_byte() != start || node.end_byte() != end {
+                return None;
+            }
+
+            let mut depends = vec![scope_at(start, end)?];
+            for argument in &call.arguments {
+                for span in argument.spans.iter().filter(|span| span.file == file) {
+                    depends.push(span.start_byte..span.end_byte);
+                }
+                for evidence in argument.evidence.iter().filter(|e| e.file == file) {
+                    let line = byte_at(&doc.source, evidence.line - 1, 0)?;
+                    let indent = doc.source[line..]
+                        .find(|c: char| !c.is_whitespace())
+                        .unwrap_or(0);
+                    depends.push(scope_at(line + indent, line + indent)?);
+                }
+            }
+            let looks_up_names = call.arguments.iter().any(|argument| {
+                !argument.is_resolved && !LOCAL_SOURCES.contains(&argument.source.as_str())
+            });
+
+            Some(ReusableCall {
+                call: start..end,
+                depends,
+                looks_up_names,
+                arguments: call.arguments.clone(),
+                keywords: call.keywords.clone(),
+            })
+        })
+        .collect()
+}
+
+/// The outermost function around `node`, or else the top-level statement
+/// it is in.
+fn scope_of<'t>(node: Node<'t>, node_types: &NodeTypes) -> Node<'t> {
+    let mut scope = node;
+    let mut function = None;
+    while let Some(parent) = scope.parent() {
+        if node_types.is_category(parent.kind(), NodeCategory::FunctionDeclaration) {
+            function = Some(parent);
+        }
+        if parent.parent().is_none() {
+            break;
+        }
+        scope = parent;
+    }
+    if node_types.is_category(scope.kind(), NodeCategory::FunctionDeclaration) {
+        function = Some(scope);
+    }
+    function.unwrap_or(scope)
+}
+
+/// Whether the bytes `edit` replaces in `tree` lie outside every function.
+fn is_top_level(tree: &Tree, edit: &InputEdit, node_types: &NodeTypes) -> bool {
+    let Some(node) = tree
+        .root_node()
+        .descendant_for_byte_range(edit.start_byte, edit.old_end_byte)
+    else {
+        return true;
+    };
+    !node_types.is_category(
+        scope_of(node, node_types).kind(),
+        NodeCategory::FunctionDeclaration,
+    )
+}
+
+/// The byte offset of 0-based `row` and byte `column` in `source`.
+fn byte_at(source: &str, row: usize, column: usize) -> Option<usize> {
+    let line_start = if row == 0 {
+        0
+    } else {
+        source.match_indices('\n').nth(row - 1)?.0 + 1
+    };
+    let byte = line_start + column;
+    (byte <= source.len()).then_some(byte)
+}
+
+fn point_at(source: &str, byte: usize) -> Point {
+    let before = &source.as_bytes()[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    Point::new(row, byte - line_start)
+}
+
+fn input_edit(source: &str, edit: &TextEdit) -> InputEdit {
+    let start_position = point_at(source, edit.start_byte);
+    let old_end_position = point_at(source, edit.old_end_byte);
+
+    let inserted = edit.new_text.as_bytes();
+    let new_end_position = match inserted.iter().rposition(|&b| b == b'\n') {
+        Some(last_newline) => Point::new(
+            start_position.row + inserted.iter().filter(|&&b| b == b'\n').count(),
+            inserted.len() - last_newline - 1,
+        ),
+        None => Point::new(start_position.row, start_position.column + inserted.len()),
+    };
+
+    InputEdit {
+        start_byte: edit.start_byte,
+        old_end_byte: edit.old_end_byte,
+        new_end_byte: edit.start_byte + inserted.len(),
+        start_position,
+        old_end_position,
+        new_end_position,
+    }
+}
+
+/// Moves ranges recorded before `edit` into the post-edit coordinates.
+fn shift_ranges(ranges: &mut [Range<usize>], edit: &InputEdit) {
+    let shift = |pos: usize| {
+        if pos >= edit.old_end_byte {
+            pos - edit.old_end_byte + edit.new_end_byte
+        } else if pos > edit.start_byte {
+            edit.new_end_byte
+        } else {
+            pos
+        }
+    };
+    for range in ranges {
+        *range = shift(range.start)..shift(range.end);
+    }
+}
+
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|r| (r.start, r.end));
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Value;
+
+    const GO_SOURCE: &str = r#"package main
+
+import "golang.org/x/crypto/pbkdf2"
+
+func derive(pw, salt []byte) []byte {
+	return pbkdf2.Key(pw, salt, 4096, 32, nil)
+}
+"#;
+
+    fn scanner() -> Scanner {
+        Scanner::new().with_patterns(vec!["pbkdf2".to_string()])
+    }
+
+    fn first_arg(doc: &Document, index: usize) -> Value {
+        doc.result().calls[0].arguments[index].clone()
+    }
+
+    #[test]
+    fn test_open_scans_document() {
+        let mut store = DocumentStore::new(scanner());
+
+        let doc = store.open("main.go", GO_SOURCE).unwrap();
+        assert_eq!(doc.version(), 0);
+        assert_eq!(doc.result().call_count(), 1);
+        assert_eq!(first_arg(doc, 2).int_values, vec![4096]);
+    }
+
+    #[test]
+    fn test_edit_reparses_and_rescans() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", GO_SOURCE).unwrap();
+
+        let start = GO_SOURCE.find("4096").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::new(start..start + 4, "600000")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.version(), 1);
+        assert!(doc.source().contains("600000"));
+        assert_eq!(first_arg(doc, 2).int_values, vec![600000]);
+        assert!(doc
+            .affected_ranges()
+            .iter()
+            .any(|r| r.start <= start && r.end >= start + 6));
+        assert!(!doc.tree().root_node().has_error());
+    }
+
+    const TWO_FUNCTIONS: &str = r#"package main
+
+import "golang.org/x/crypto/pbkdf2"
+
+const iterations = 4096
+
+func derive(pw []byte, salt []byte) []byte {
+	return pbkdf2.Key(pw, salt, iterations, 32, nil)
+}
+
+func legacy(pw []byte, salt []byte) []byte {
+	return pbkdf2.Key(pw, salt, 1000, 20, nil)
+}
+"#;
+
+    /// Asserts the document's result is what scanning it afresh gives.
+    fn assert_matches_fresh_scan(doc: &Document) {
+        let fresh = scanner().scan_tree(
+            doc.tree(),
+            doc.source().as_bytes(),
+            "main.go",
+            doc.language().tree_sitter_name(),
+        );
+        assert_eq!(
+            serde_json::to_value(doc.result()).unwrap(),
+            serde_json::to_value(&fresh).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_edit_reresolves_only_calls_it_touches() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", TWO_FUNCTIONS).unwrap();
+
+        let start = TWO_FUNCTIONS.find("1000").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::new(start..start + 4, "310000")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.reresolved_calls(), 1);
+        assert_eq!(first_arg(doc, 2).int_values, vec![4096]);
+        assert_eq!(doc.result().calls[1].arguments[2].int_values, vec![310000]);
+        assert_matches_fresh_scan(doc);
+    }
+
+    #[test]
+    fn test_edit_to_declaration_reresolves_its_readers() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", TWO_FUNCTIONS).unwrap();
+
+        let start = TWO_FUNCTIONS.find("4096").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::new(start..start + 4, "600000")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.reresolved_calls(), 1);
+        assert_eq!(first_arg(doc, 2).int_values, vec![600000]);
+        assert_matches_fresh_scan(doc);
+    }
+
+    #[test]
+    fn test_edit_in_scope_reresolves_shadowed_names() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", TWO_FUNCTIONS).unwrap();
+
+        let at = TWO_FUNCTIONS
+            .find("\treturn pbkdf2.Key(pw, salt, iterations")
+            .unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::insert(at, "\titerations := 100000\n")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.reresolved_calls(), 1);
+        assert_eq!(first_arg(doc, 2).int_values, vec![100000]);
+        assert_matches_fresh_scan(doc);
+    }
+
+    #[test]
+    fn test_reused_calls_move_with_the_source() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", TWO_FUNCTIONS).unwrap();
+
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::insert(0, "// Key derivation.\n\n")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.reresolved_calls(), 0);
+        assert_eq!(doc.result().calls[1].line, 14);
+        assert_matches_fresh_scan(doc);
+    }
+
+    #[test]
+    fn test_top_level_edit_reresolves_names_not_found() {
+        let source = TWO_FUNCTIONS.replace("1000, 20", "1000, keyLen");
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", source.as_str()).unwrap();
+
+        let at = source.find("func derive").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::insert(at - 1, "const keyLen = 20\n")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.reresolved_calls(), 1);
+        assert_eq!(doc.result().calls[1].arguments[3].int_values, vec![20]);
+        assert_matches_fresh_scan(doc);
+    }
+
+    #[test]
+    fn test_sequential_edits_use_updated_coordinates() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", GO_SOURCE).unwrap();
+
+        let start = GO_SOURCE.find("4096").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[
+                    TextEdit::delete(start..start + 4),
+                    TextEdit::insert(start, "1 << 16"),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(first_arg(doc, 2).int_values, vec![65536]);
+        assert_eq!(doc.affected_ranges().len(), 1);
+    }
+
+    #[test]
+    fn test_noop_edit_keeps_version() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", GO_SOURCE).unwrap();
+
+        let start = GO_SOURCE.find("4096").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::new(start..start + 4, "4096")],
+            )
+            .unwrap();
+
+        assert_eq!(doc.version(), 0);
+        assert!(doc.affected_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_multiline_insert_tracks_positions() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", GO_SOURCE).unwrap();
+
+        let at = GO_SOURCE.find("func derive").unwrap();
+        let doc = store
+            .edit(
+                Path::new("main.go"),
+                &[TextEdit::insert(at, "const iterations = 10000\n\n")],
+            )
+            .unwrap();
+
+        let call = &doc.result().calls[0];
+        assert_eq!(call.line, 8);
+        assert_eq!(call.arguments[2].int_values, vec![4096]);
+    }
+
+    #[test]
+    fn test_invalid_edit_leaves_document_untouched() {
+        let mut store = DocumentStore::new(scanner());
+        store.open("main.go", GO_SOURCE).unwrap();
+
+        let err = store
+            .edit(
+                Path::new("main.go"),
+                &[
+                    TextEdit::insert(0, "// header\n"),
+                    TextEdit::delete(0..GO_SOURCE.len() + 100),
+                ],
+            )
+            .err()
+            .unwrap();
+        assert!(matches!(err, ParserError::InvalidEdit { .. }));
+
+        let doc = store.get(Path::new("main.go")).unwrap();
+        assert_eq!(doc.source(), GO_SOURCE);
+        assert_eq!(doc.version(), 0);
+    }
+
+    #[test]
+    fn test_unknown_document_and_unsupported_language() {
+        let mut store = DocumentStore::new(scanner());
+
+        assert!(matches!(
+            store.edit(Path::new("missing.go"), &[]),
+            Err(ParserError::UnknownDocument { .. })
+        ));
+        assert!(matches!(
+            store.open("notes.txt", "hello"),
+            Err(ParserError::UnsupportedLanguage { .. })
+        ));
+    }
+
+    #[test]
+    fn test_point_at_and_merge_ranges() {
+        assert_eq!(point_at("ab\ncd", 4), Point::new(1, 1));
+        assert_eq!(point_at("abc", 0), Point::new(0, 0));
+        assert_eq!(merge_ranges(vec![5..8, 0..2, 1..4, 8..9]), vec![0..4, 5..9]);
+        assert_eq!(byte_at("ab\ncd", 1, 1), Some(4));
+        assert_eq!(byte_at("ab", 1, 0), None);
+    }
+}
@@ -1,13 +1,28 @@
+pub mod binary;
+mod dynamic;
+pub mod fallback;
+mod fields;
 mod imports;
+pub mod incremental;
+mod site;
+pub mod templates;
+pub mod units;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, trace, warn};
 use tree_sitter::{Node, Tree};
 
-use crate::engine::{Context, NodeCategory, Resolver, Value};
+use crate::engine::{
+    Context, ExpressionCache, ExpressionCacheStats, NodeCategory, ProjectIndex, Resolver,
+    SymbolIndex, Value,
+};
 use crate::query::QueryEngine;
-use crate::utils::{extract_last_segment, unquote_string};
+use crate::utils::{extract_last_segment, go_package_name, unquote_string};
 pub use imports::ImportMap;
+pub use incremental::{Document, DocumentStore, TextEdit};
+pub use site::CallSite;
 
 /// Trait for matching function calls to preset patterns.
 ///
@@ -21,6 +36,12 @@ pub trait CallMatcher: Send + Sync {
         package: Option<&str>,
         import_path: Option<&str>,
     ) -> bool;
+
+    /// Whether any function of the module is matched, for calls that look
+    /// the function up by name.
+    fn matches_module(&self, _package: Option<&str>, _import_path: Option<&str>) -> bool {
+        false
+    }
 }
 
 /// Mapping type: import_path -> (function_name -> classification_key)
@@ -69,6 +90,13 @@ impl CallMatcher for MappingMatcher {
 
         false
     }
+
+    fn matches_module(&self, package: Option<&str>, import_path: Option<&str>) -> bool {
+        import_path
+            .into_iter()
+            .chain(package)
+            .any(|module| self.mappings.contains_key(&module.to_lowercase()))
+    }
 }
 
 /// Pattern-based matcher. Matches against a list of known terms.
@@ -115,9 +143,18 @@ impl CallMatcher for PatternMatcher {
 
         false
     }
+
+    fn matches_module(&self, package: Option<&str>, import_path: Option<&str>) -> bool {
+        import_path.into_iter().chain(package).any(|module| {
+            let module = module.to_lowercase();
+            self.patterns
+                .iter()
+                .any(|p| module.contains(&p.to_lowercase()))
+        })
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
     pub file_path: String,
     pub line: usize,
@@ -126,27 +163,112 @@ pub struct Finding {
     pub package: Option<String>,
     pub import_path: Option<String>,
     pub arguments: Vec<Value>,
+    /// Keyword each argument was passed with (Python `n=16384`), parallel to
+    /// `arguments`; empty when every argument is positional.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<Option<String>>,
     pub raw_text: String,
     pub language: String,
+    /// Names around the call, used to tell what a hash is for.
+    #[serde(default, skip_serializing_if = "CallSite::is_empty")]
+    pub site: CallSite,
+    /// For a function looked up by name (`getattr(hashlib, algo)`), what
+    /// the name resolved to; `function_name` is the name when it resolved
+    /// to one string and the name expression otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_name: Option<Value>,
 }
 
 impl Finding {
     pub fn full_name(&self) -> String {
-        match &self.package {
-            Some(pkg) => format!("{}.{}", pkg, self.function_name),
-            None => self.function_name.clone(),
+        qualified_name(&self.function_name, self.package.as_deref())
+    }
+
+    /// Keyword of the argument at `index`, if it was passed by name.
+    pub fn keyword(&self, index: usize) -> Option<&str> {
+        self.keywords.get(index).and_then(|k| k.as_deref())
+    }
+}
+
+/// Nodes that bind a name: parameters, declarations and assignments.
+const BINDING_KINDS: &[&str] = &[
+    "parameter_declaration",
+    "short_var_declaration",
+    "var_spec",
+    "assignment_statement",
+    "assignment",
+    "typed_parameter",
+    "variable_declarator",
+    "assignment_expression",
+    "required_parameter",
+    "let_declaration",
+    "parameter",
+];
+
+/// Text of every binding of the identifier `name` in the tree.
+fn receiver_bindings(root: Node, name: &str, ctx: &Context) -> Vec<String> {
+    let mut bindings = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "identifier" && ctx.get_node_text(&node) == name {
+            let binding = std::iter::successors(node.parent(), |n| n.parent())
+                .take(2)
+                .find(|n| BINDING_KINDS.contains(&n.kind()));
+            if let Some(binding) = binding {
+                bindings.push(ctx.get_node_text(&binding));
+            }
         }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    bindings
+}
+
+/// Whether `text` uses `alias` as a qualifier or callee (`alias.X`, `alias(`).
+fn mentions_alias(text: &str, alias: &str) -> bool {
+    text.match_indices(alias).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + alias.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && matches!(after, Some('.') | Some('('))
+    })
+}
+
+/// The name of a C# generic type or method (`HashData` for
+/// `HashData<byte>`), or `node` itself.
+fn unwrap_generic_name(node: Node) -> Node {
+    if node.kind() == "generic_name" {
+        node.named_child(0).unwrap_or(node)
+    } else {
+        node
+    }
+}
+
+/// Whether `node` is a Scala method called infix, `Mac getInstance "x"`,
+/// rather than an operator such as `+`.
+fn is_infix_call(node: &Node, ctx: &Context) -> bool {
+    node.kind() == "infix_expression"
+        && ctx.language() == "scala"
+        && node
+            .child_by_field_name("operator")
+            .is_some_and(|operator| operator.kind() == "identifier")
+}
+
+fn qualified_name(name: &str, package: Option<&str>) -> String {
+    match package {
+        Some(pkg) => format!("{pkg}.{name}"),
+        None => name.to_string(),
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigField {
     pub field_name: String,
     pub value: Value,
     pub classification_key: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigFinding {
     pub file_path: String,
     pub line: usize,
@@ -157,6 +279,9 @@ pub struct ConfigFinding {
     pub fields: Vec<ConfigField>,
     pub raw_text: String,
     pub language: String,
+    /// Full name of the call the settings belong to: the call a struct
+    /// literal is passed to, or the call itself for keyword/object arguments.
+    pub call: Option<String>,
 }
 
 impl ConfigFinding {
@@ -171,9 +296,21 @@ impl ConfigFinding {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScanResult {
     pub file_path: String,
+    /// The dependency the file belongs to; `None` for the project's own
+    /// files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<String>,
+    /// Whether the file is test code by its path below the scan root, see
+    /// [`crate::policy::is_test_file`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test_code: bool,
+    /// The workspace member the file belongs to, see
+    /// [`crate::discovery::workspace`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
     pub calls: Vec<Finding>,
     pub configs: Vec<ConfigFinding>,
     pub errors: Vec<String>,
@@ -183,6 +320,9 @@ impl ScanResult {
     pub fn new(file_path: String) -> Self {
         Self {
             file_path,
+            dependency: None,
+            test_code: false,
+            member: None,
             calls: Vec::new(),
             configs: Vec::new(),
             errors: Vec::new(),
@@ -209,18 +349,85 @@ impl ScanResult {
         self.configs.len()
     }
 
+    /// Whether the file produced any call or config finding.
+    pub fn has_findings(&self) -> bool {
+        !self.calls.is_empty() || !self.configs.is_empty()
+    }
+
     pub fn has_errors(&self) -> bool {
         !self.errors.is_empty()
     }
+
+    /// Rough heap footprint, used for memory budgeting rather than accounting.
+    pub fn approx_bytes(&self) -> usize {
+        fn value_bytes(value: &Value) -> usize {
+            std::mem::size_of::<Value>()
+                + value.int_values.len() * std::mem::size_of::<i64>()
+                + value
+                    .string_values
+                    .iter()
+                    .map(|s| s.len() + std::mem::size_of::<String>())
+                    .sum::<usize>()
+                + value.source.len()
+                + value.expression.len()
+        }
+
+        let calls: usize = self
+            .calls
+            .iter()
+            .map(|c| {
+                std::mem::size_of::<Finding>()
+                    + c.file_path.len()
+                    + c.function_name.len()
+                    + c.package.as_ref().map_or(0, String::len)
+                    + c.import_path.as_ref().map_or(0, String::len)
+                    + c.raw_text.len()
+                    + c.language.len()
+                    + c.arguments.iter().map(value_bytes).sum::<usize>()
+            })
+            .sum();
+
+        let configs: usize = self
+            .configs
+            .iter()
+            .map(|c| {
+                std::mem::size_of::<ConfigFinding>()
+                    + c.file_path.len()
+                    + c.struct_type.len()
+                    + c.package.as_ref().map_or(0, String::len)
+                    + c.import_path.as_ref().map_or(0, String::len)
+                    + c.raw_text.len()
+                    + c.language.len()
+                    + c.fields
+                        .iter()
+                        .map(|f| f.field_name.len() + value_bytes(&f.value))
+                        .sum::<usize>()
+            })
+            .sum();
+
+        std::mem::size_of::<Self>()
+            + self.file_path.len()
+            + self.errors.iter().map(String::len).sum::<usize>()
+            + calls
+            + configs
+    }
 }
 
 pub type StructFieldsMap = HashMap<String, HashMap<String, String>>;
 
+/// The arguments and keywords of a call node resolved by an earlier scan,
+/// see [`Scanner::rescan_tree`].
+pub type ReuseArguments<'r> = &'r dyn Fn(&Node) -> Option<(Vec<Value>, Vec<Option<String>>)>;
+
 pub struct Scanner {
     resolver: Resolver,
     matcher: Box<dyn CallMatcher>,
-    query_engine: QueryEngine,
+    query_engine: &'static QueryEngine,
     struct_fields: StructFieldsMap,
+    clients: Vec<String>,
+    expression_cache: Option<Arc<ExpressionCache>>,
+    symbol_index: Option<Arc<SymbolIndex>>,
+    project_index: Option<Arc<ProjectIndex>>,
 }
 
 impl Scanner {
@@ -228,8 +435,12 @@ impl Scanner {
         Self {
             resolver: Resolver::new(),
             matcher: Box::new(PatternMatcher::new(vec![])),
-            query_engine: QueryEngine::new(),
+            query_engine: QueryEngine::shared(),
             struct_fields: HashMap::new(),
+            clients: Vec::new(),
+            expression_cache: None,
+            symbol_index: None,
+            project_index: None,
         }
     }
 
@@ -237,8 +448,12 @@ impl Scanner {
         Self {
             resolver,
             matcher: Box::new(PatternMatcher::new(vec![])),
-            query_engine: QueryEngine::new(),
+            query_engine: QueryEngine::shared(),
             struct_fields: HashMap::new(),
+            clients: Vec::new(),
+            expression_cache: None,
+            symbol_index: None,
+            project_index: None,
         }
     }
 
@@ -255,8 +470,12 @@ impl Scanner {
         Self {
             resolver: Resolver::new(),
             matcher: Box::new(MappingMatcher::new(mappings)),
-            query_engine: QueryEngine::new(),
+            query_engine: QueryEngine::shared(),
             struct_fields: HashMap::new(),
+            clients: Vec::new(),
+            expression_cache: None,
+            symbol_index: None,
+            project_index: None,
         }
     }
 
@@ -265,6 +484,17 @@ impl Scanner {
         self
     }
 
+    /// Modules whose mapped functions are also called as methods on client
+    /// objects (`kms_client.GenerateDataKey(...)`). A method call on a
+    /// receiver that is not an import is attributed to such a module when
+    /// the module maps the method and the receiver is bound to the module in
+    /// the same file.
+    pub fn with_clients(mut self, clients: impl IntoIterator<Item = String>) -> Self {
+        self.clients = clients.into_iter().collect();
+        self.clients.sort();
+        self
+    }
+
     pub fn with_mappings_and_struct_fields(
         mappings: MappingsMap,
         struct_fields: StructFieldsMap,
@@ -272,34 +502,102 @@ impl Scanner {
         Self {
             resolver: Resolver::new(),
             matcher: Box::new(MappingMatcher::new(mappings)),
-            query_engine: QueryEngine::new(),
+            query_engine: QueryEngine::shared(),
             struct_fields,
+            clients: Vec::new(),
+            expression_cache: None,
+            symbol_index: None,
+            project_index: None,
         }
     }
 
+    /// Shares resolved values of context-free expressions across every file
+    /// scanned with this scanner.
+    pub fn with_expression_cache(mut self, cache: Arc<ExpressionCache>) -> Self {
+        self.expression_cache = Some(cache);
+        self
+    }
+
+    /// Enables cross-file constant lookups through a shared symbol index.
+    pub fn with_symbol_index(mut self, index: Arc<SymbolIndex>) -> Self {
+        self.symbol_index = Some(index);
+        self
+    }
+
+    /// Resolves constants other packages of the project declare through
+    /// `index`, which [`ProjectIndex::index_files`] fills before scanning.
+    pub fn with_project_index(mut self, index: Arc<ProjectIndex>) -> Self {
+        self.project_index = Some(index);
+        self
+    }
+
+    pub fn project_index(&self) -> Option<&Arc<ProjectIndex>> {
+        self.project_index.as_ref()
+    }
+
+    pub fn set_resolver(&mut self, resolver: Resolver) {
+        self.resolver = resolver;
+    }
+
+    pub fn resolver(&self) -> &Resolver {
+        &self.resolver
+    }
+
+    pub fn expression_cache_stats(&self) -> Option<ExpressionCacheStats> {
+        self.expression_cache.as_ref().map(|c| c.stats())
+    }
+
     pub fn scan_tree<'a>(
         &self,
         tree: &'a Tree,
         source: &'a [u8],
         file_path: &str,
         language: &str,
+    ) -> ScanResult {
+        self.rescan_tree(tree, source, file_path, language, &|_| None)
+    }
+
+    /// Like [`Scanner::scan_tree`], but a call `reuse` has arguments for
+    /// keeps them instead of resolving its arguments again. Everything
+    /// else about the call is read from `tree`.
+    pub fn rescan_tree<'a>(
+        &self,
+        tree: &'a Tree,
+        source: &'a [u8],
+        file_path: &str,
+        language: &str,
+        reuse: ReuseArguments<'_>,
     ) -> ScanResult {
         trace!(file_path, language, "scanning tree");
 
         let source_str = std::str::from_utf8(source).unwrap_or("");
-        let ctx = Context::new(
+        let imports = self.extract_imports_via_query(tree, source_str, language);
+        trace!(import_count = imports.len(), "extracted imports");
+
+        let mut ctx = Context::new(
             tree,
             source,
             file_path.to_string(),
             language.to_string(),
             HashMap::new(),
+        )
+        .with_import_paths(
+            imports
+                .iter()
+                .map(|(name, path)| (name.clone(), path.clone())),
         );
-
-        let imports = self.extract_imports_via_query(tree, source_str, language);
-        trace!(import_count = imports.len(), "extracted imports");
+        if let Some(cache) = &self.expression_cache {
+            ctx = ctx.with_expression_cache(Arc::clone(cache));
+        }
+        if let Some(index) = &self.symbol_index {
+            ctx = ctx.with_symbol_index(Arc::clone(index));
+        }
+        if let Some(index) = &self.project_index {
+            ctx = ctx.with_project_index(Arc::clone(index));
+        }
 
         let mut result = ScanResult::new(file_path.to_string());
-        self.traverse_node(tree.root_node(), &ctx, &imports, &mut result);
+        self.traverse_node(tree.root_node(), &ctx, &imports, reuse, &mut result);
 
         debug!(
             file_path,
@@ -325,7 +623,18 @@ impl Scanner {
         };
 
         for m in matches {
+            // #include <openssl/evp.h> (C)
+            if let Some(header) = m.get("header") {
+                let header = header.trim_matches(|c| matches!(c, '<' | '>' | '"'));
+                imports.insert_header(header.to_string());
+                continue;
+            }
             let path = m.get("path").map(unquote_string);
+            // import javax.crypto.*; (Java)
+            if let (Some(package), Some(_)) = (&path, m.get("wildcard")) {
+                imports.insert_wildcard(package.clone());
+                continue;
+            }
             let alias = m.get("alias");
             let module = m.get("module");
             let name = m.get("name");
@@ -341,9 +650,17 @@ impl Scanner {
                 }
                 // Simple import: import "path" or import alias "path"
                 (Some(p), None, None, alias_opt) => {
-                    let short_name = alias_opt
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| extract_last_segment(&p));
+                    let short_name = alias_opt.map(|s| s.to_string()).unwrap_or_else(|| {
+                        if language == "go" {
+                            go_package_name(&p)
+                        } else {
+                            extract_last_segment(&p)
+                        }
+                    });
+                    // `import nacl.secret` is used as `nacl.secret.SecretBox`
+                    if language == "python" && alias_opt.is_none() && p.contains('.') {
+                        imports.insert(p.clone(), p.clone());
+                    }
                     imports.insert(short_name, p);
                 }
                 _ => {}
@@ -358,12 +675,24 @@ impl Scanner {
         node: Node<'a>,
         ctx: &Context<'a>,
         imports: &ImportMap,
+        reuse: ReuseArguments<'_>,
         result: &mut ScanResult,
     ) {
-        // Detect function calls
-        if ctx.is_node_category(node.kind(), NodeCategory::CallExpression) {
-            if let Some(call) = self.process_call_node(&node, ctx, imports) {
+        // Detect functions looked up by name on a matched module
+        if let Some(call) = self.process_dynamic_lookup(&node, ctx, imports) {
+            result.add_call(call);
+        }
+
+        // Detect function calls; a looked-up callee was reported above
+        let calls_lookup = node
+            .child_by_field_name("function")
+            .is_some_and(|function| dynamic::lookup(&function, ctx).is_some());
+        let is_call = ctx.is_node_category(node.kind(), NodeCategory::CallExpression)
+            || is_infix_call(&node, ctx);
+        if is_call && !calls_lookup {
+            if let Some(mut call) = self.process_call_node(&node, ctx, imports, reuse) {
                 if self.is_match(&call) {
+                    call.site = site::call_site(&node, ctx);
                     result.add_call(call);
                 }
             }
@@ -376,9 +705,24 @@ impl Scanner {
             }
         }
 
+        // Detect fields set on a struct after construction (Go: `cfg.MinVersion = ...`)
+        if node.kind() == "assignment_statement" && ctx.language() == "go" {
+            if let Some(config) = self.process_field_assignment(&node, ctx, imports) {
+                result.add_config(config);
+            }
+        }
+
+        // Detect settings passed as keyword arguments (Python) or an object
+        // literal argument (JS/TS), e.g. boto3 `put_object(ServerSideEncryption=...)`
+        if self.has_call_site_settings(node.kind(), ctx) {
+            if let Some(config) = self.process_call_settings(&node, ctx, imports) {
+                result.add_config(config);
+            }
+        }
+
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            self.traverse_node(child, ctx, imports, result);
+            self.traverse_node(child, ctx, imports, reuse, result);
         }
     }
 
@@ -410,20 +754,11 @@ impl Scanner {
         };
 
         // Check if we have mappings for this struct type
-        let type_lower = full_type.to_lowercase();
-        if !self.struct_fields.contains_key(&type_lower) {
-            // Also try with just package.Type (without full import path)
-            let short_type = match &package {
-                Some(pkg) => format!("{pkg}.{struct_type}").to_lowercase(),
-                None => return None,
-            };
-            if !self.struct_fields.contains_key(&short_type) {
-                return None;
-            }
-        }
+        let field_mappings =
+            self.lookup_struct_fields(&full_type, package.as_deref(), &struct_type)?;
 
         // Extract field values
-        let fields = self.extract_struct_fields(node, ctx, &full_type);
+        let fields = self.extract_struct_fields(node, ctx, field_mappings);
         if fields.is_empty() {
             return None;
         }
@@ -441,43 +776,242 @@ impl Scanner {
             fields,
             raw_text,
             language: ctx.language().to_string(),
+            call: self.enclosing_call(node, ctx),
         })
     }
 
-    fn extract_struct_type<'a>(
+    /// Mapped fields assigned on a variable whose struct type is known from
+    /// its declaration (`cfg := &tls.Config{}; cfg.MinVersion = ...`). Only
+    /// fields mapped for that type are reported, so the same field name set
+    /// on an unrelated struct, or on a variable of unknown type, is not.
+    fn process_field_assignment<'a>(
         &self,
         node: &Node<'a>,
         ctx: &Context<'a>,
-    ) -> Option<(String, Option<String>)> {
-        // Go: composite_literal has a type child
-        // The type can be a selector_expression (pkg.Type) or identifier (Type)
-        let type_node = node.child_by_field_name("type")?;
+        imports: &ImportMap,
+    ) -> Option<ConfigFinding> {
+        let scope = std::iter::successors(node.parent(), |n| n.parent())
+            .find(|n| {
+                matches!(
+                    n.kind(),
+                    "function_declaration" | "method_declaration" | "func_literal"
+                )
+            })
+            .or_else(|| std::iter::successors(Some(*node), |n| n.parent()).last())?;
+
+        let mut finding: Option<ConfigFinding> = None;
+        for (receiver, field, value) in fields::field_assignments(*node) {
+            let receiver_name = ctx.get_node_text(&receiver);
+            let Some((struct_type, package)) =
+                fields::receiver_type(scope, &receiver_name, node.start_byte(), ctx)
+            else {
+                continue;
+            };
+            let import_path = package.as_ref().and_then(|pkg| imports.resolve(pkg));
+            let full_type = match &import_path {
+                Some(path) => format!("{path}.{struct_type}"),
+                None => qualified_name(&struct_type, package.as_deref()),
+            };
+            let Some(field_mappings) =
+                self.lookup_struct_fields(&full_type, package.as_deref(), &struct_type)
+            else {
+                continue;
+            };
+            let field_name = ctx.get_node_text(&field);
+            let Some(classification_key) = field_mappings.get(&field_name.to_lowercase()) else {
+                continue;
+            };
+            let field = ConfigField {
+                value: self.resolver.resolve(&value, ctx),
+                field_name,
+                classification_key: Some(classification_key.clone()),
+            };
+            match &mut finding {
+                Some(finding) if finding.struct_type == struct_type => finding.fields.push(field),
+                Some(_) => {}
+                None => {
+                    let start = node.start_position();
+                    finding = Some(ConfigFinding {
+                        file_path: ctx.file_path().to_string(),
+                        line: start.row + 1,
+                        column: start.column + 1,
+                        struct_type,
+                        package,
+                        import_path,
+                        fields: vec![field],
+                        raw_text: ctx.get_node_text(node),
+                        language: ctx.language().to_string(),
+                        call: None,
+                    });
+                }
+            }
+        }
+        finding
+    }
 
-        match type_node.kind() {
-            "selector_expression" | "qualified_type" => {
-                let operand = type_node
-                    .child_by_field_name("operand")
-                    .or_else(|| type_node.child_by_field_name("package"))?;
-                let field = type_node
-                    .child_by_field_name("field")
-                    .or_else(|| type_node.child_by_field_name("name"))?;
-                let package = ctx.get_node_text(&operand);
-                let name = ctx.get_node_text(&field);
-                Some((name, Some(package)))
+    /// Field mappings for a struct type or settings call, by full type, then
+    /// `package.Type`, then a `*.Type` wildcard for calls on client objects
+    /// whose variable name is arbitrary (`s3.put_object`, `client.put_object`).
+    fn lookup_struct_fields(
+        &self,
+        full_type: &str,
+        package: Option<&str>,
+        name: &str,
+    ) -> Option<&HashMap<String, String>> {
+        let mut candidates = vec![full_type.to_lowercase()];
+        if let Some(pkg) = package {
+            candidates.push(format!("{pkg}.{name}").to_lowercase());
+        }
+        candidates.push(format!("*.{name}").to_lowercase());
+        candidates
+            .iter()
+            .find_map(|key| self.struct_fields.get(key))
+    }
+
+    /// The call a struct literal is an argument of, looking through `&`.
+    fn enclosing_call<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
+        let mut current = node.parent()?;
+        if matches!(current.kind(), "unary_expression" | "reference_expression") {
+            current = current.parent()?;
+        }
+        if !matches!(current.kind(), "argument_list" | "arguments") {
+            return None;
+        }
+        let call = current.parent()?;
+        if !ctx.is_node_category(call.kind(), NodeCategory::CallExpression) {
+            return None;
+        }
+        let (name, package) = self.extract_function_name(&call, ctx)?;
+        Some(qualified_name(&name, package.as_deref()))
+    }
+
+    fn has_call_site_settings(&self, node_kind: &str, ctx: &Context) -> bool {
+        match ctx.language() {
+            "python" => node_kind == "call",
+            "javascript" | "typescript" => {
+                node_kind == "call_expression" || node_kind == "new_expression"
             }
-            "identifier" | "type_identifier" => {
-                let name = ctx.get_node_text(&type_node);
-                Some((name, None))
+            _ => false,
+        }
+    }
+
+    fn process_call_settings<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+        imports: &ImportMap,
+    ) -> Option<ConfigFinding> {
+        let (name, package) = match node.child_by_field_name("constructor") {
+            Some(constructor) => self.split_callee(&constructor, ctx)?,
+            None => self.extract_function_name(node, ctx)?,
+        };
+        let import_path = package.as_ref().and_then(|pkg| imports.resolve(pkg));
+        let full_type = match &import_path {
+            Some(path) => format!("{path}.{name}"),
+            None => qualified_name(&name, package.as_deref()),
+        };
+        let field_mappings = self.lookup_struct_fields(&full_type, package.as_deref(), &name)?;
+
+        let args = node
+            .child_by_field_name("arguments")
+            .or_else(|| self.find_arguments_child(node))?;
+        let mut fields = Vec::new();
+        let mut cursor = args.walk();
+        for arg in args.named_children(&mut cursor) {
+            match arg.kind() {
+                "keyword_argument" => {
+                    if let Some(field) =
+                        self.extract_named_setting(&arg, "name", ctx, field_mappings)
+                    {
+                        fields.push(field);
+                    }
+                }
+                "object" => {
+                    let mut pairs = arg.walk();
+                    for pair in arg.named_children(&mut pairs) {
+                        if pair.kind() == "pair" {
+                            if let Some(field) =
+                                self.extract_named_setting(&pair, "key", ctx, field_mappings)
+                            {
+                                fields.push(field);
+                            }
+                        }
+                    }
+                }
+                _ => {}
             }
-            _ => None,
         }
+        if fields.is_empty() {
+            return None;
+        }
+
+        let start = node.start_position();
+        Some(ConfigFinding {
+            file_path: ctx.file_path().to_string(),
+            line: start.row + 1,
+            column: start.column + 1,
+            struct_type: name,
+            package,
+            import_path,
+            fields,
+            raw_text: ctx.get_node_text(node),
+            language: ctx.language().to_string(),
+            call: Some(full_type),
+        })
+    }
+
+    /// `name` of `Foo` / `pkg.Foo` in a JS `new` expression.
+    fn split_callee<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<(String, Option<String>)> {
+        match node.kind() {
+            "member_expression" => {
+                let obj = node.child_by_field_name("object")?;
+                let prop = node.child_by_field_name("property")?;
+                Some((ctx.get_node_text(&prop), Some(ctx.get_node_text(&obj))))
+            }
+            _ => Some((ctx.get_node_text(node), None)),
+        }
+    }
+
+    /// A `name=value` keyword argument or `key: value` object property.
+    fn extract_named_setting<'a>(
+        &self,
+        node: &Node<'a>,
+        name_field: &str,
+        ctx: &Context<'a>,
+        field_mappings: &HashMap<String, String>,
+    ) -> Option<ConfigField> {
+        let name_node = node.child_by_field_name(name_field)?;
+        let value_node = node.child_by_field_name("value")?;
+        let field_name = unquote_string(&ctx.get_node_text(&name_node));
+        let classification_key = field_mappings.get(&field_name.to_lowercase()).cloned();
+
+        Some(ConfigField {
+            value: self.resolver.resolve(&value_node, ctx),
+            field_name,
+            classification_key,
+        })
+    }
+
+    fn extract_struct_type<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+    ) -> Option<(String, Option<String>)> {
+        // Go: composite_literal has a type child: `pkg.Type`, `Type` or a
+        // generic `pkg.Type[T]`
+        let type_node = node.child_by_field_name("type")?;
+        fields::type_name(type_node, ctx)
     }
 
     fn extract_struct_fields<'a>(
         &self,
         node: &Node<'a>,
         ctx: &Context<'a>,
-        struct_type: &str,
+        field_mappings: &HashMap<String, String>,
     ) -> Vec<ConfigField> {
         let mut fields = Vec::new();
 
@@ -491,9 +1025,6 @@ impl Scanner {
             None => return fields,
         };
 
-        let type_lower = struct_type.to_lowercase();
-        let field_mappings = self.struct_fields.get(&type_lower);
-
         let mut cursor = body.walk();
         for child in body.children(&mut cursor) {
             // Go uses keyed_element for field: value
@@ -540,7 +1071,7 @@ impl Scanner {
         &self,
         node: &Node<'a>,
         ctx: &Context<'a>,
-        field_mappings: Option<&HashMap<String, String>>,
+        field_mappings: &HashMap<String, String>,
     ) -> Option<ConfigField> {
         // Go keyed_element: field_name: value
         // Note: In Go, both key and value are wrapped in literal_element nodes
@@ -558,11 +1089,7 @@ impl Scanner {
 
         let value = self.resolver.resolve(&actual_value_node, ctx);
 
-        let classification_key = field_mappings.and_then(|mappings| {
-            mappings
-                .get(&field_name.to_lowercase())
-                .map(|s| s.to_string())
-        });
+        let classification_key = field_mappings.get(&field_name.to_lowercase()).cloned();
 
         Some(ConfigField {
             field_name,
@@ -576,12 +1103,43 @@ impl Scanner {
         node: &Node<'a>,
         ctx: &Context<'a>,
         imports: &ImportMap,
+        reuse: ReuseArguments<'_>,
     ) -> Option<Finding> {
         let (function_name, package) = self.extract_function_name(node, ctx)?;
-        let arguments = self.extract_arguments(node, ctx);
+        let (arguments, keywords) =
+            reuse(node).unwrap_or_else(|| self.extract_arguments(node, ctx));
         let raw_text = ctx.get_node_text(node);
 
-        let import_path = package.as_ref().and_then(|pkg| imports.resolve(pkg));
+        let import_path = match &package {
+            Some(pkg) => imports
+                .resolve(pkg)
+                .or_else(|| self.wildcard_module(pkg, &function_name, imports))
+                .or_else(|| self.client_module(pkg, &function_name, ctx, imports))
+                .or_else(|| self.qualified_module(pkg, &function_name, ctx)),
+            None if node.kind() == "object_creation_expression"
+                || matches!(ctx.language(), "swift" | "kotlin" | "scala") =>
+            {
+                self.namespace_module(&function_name, imports)
+            }
+            None => self
+                .header_module(&function_name, imports)
+                .or_else(|| self.builtin_module(&function_name, ctx)),
+        };
+
+        // C++ members are mapped by the class they are called through
+        // (`HashFunction::create` for `Botan::HashFunction::create`)
+        let is_scoped = node
+            .child_by_field_name("function")
+            .is_some_and(|function| function.kind() == "qualified_identifier");
+        let (function_name, import_path) = match (&package, import_path) {
+            (Some(scope), None) if is_scoped => {
+                match self.scoped_header_member(scope, &function_name, imports) {
+                    Some((header, member)) => (member, Some(header)),
+                    None => (function_name, None),
+                }
+            }
+            (_, import_path) => (function_name, import_path),
+        };
 
         let start = node.start_position();
 
@@ -593,11 +1151,174 @@ impl Scanner {
             package,
             import_path,
             arguments,
+            keywords,
             raw_text,
             language: ctx.language().to_string(),
+            site: CallSite::default(),
+            dynamic_name: None,
+        })
+    }
+
+    fn process_dynamic_lookup<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+        imports: &ImportMap,
+    ) -> Option<Finding> {
+        let lookup = dynamic::lookup(node, ctx)?;
+        let package = ctx.get_node_text(&lookup.object);
+        let import_path = imports.resolve(&package)?;
+        if !self
+            .matcher
+            .matches_module(Some(&package), Some(&import_path))
+        {
+            return None;
+        }
+
+        let name = self.resolver.resolve(&lookup.name, ctx);
+        let function_name = match (name.is_resolved, name.string_values.as_slice()) {
+            (true, [single]) => single.clone(),
+            _ => ctx.get_node_text(&lookup.name),
+        };
+        let call = dynamic::invocation(node, ctx);
+        let (arguments, keywords) = match &call {
+            Some(call) => self.extract_arguments(call, ctx),
+            None => Default::default(),
+        };
+        let reported = call.unwrap_or(*node);
+        let start = reported.start_position();
+
+        Some(Finding {
+            file_path: ctx.file_path().to_string(),
+            line: start.row + 1,
+            column: start.column + 1,
+            function_name,
+            package: Some(package),
+            import_path: Some(import_path),
+            arguments,
+            keywords,
+            raw_text: ctx.get_node_text(&reported),
+            language: ctx.language().to_string(),
+            site: site::call_site(&reported, ctx),
+            dynamic_name: Some(name),
         })
     }
 
+    /// The module a wildcard import brings `receiver` in from: the first
+    /// candidate (`javax.crypto.Cipher` for `Cipher` under
+    /// `import javax.crypto.*;`) that maps `method`.
+    fn wildcard_module(&self, receiver: &str, method: &str, imports: &ImportMap) -> Option<String> {
+        imports
+            .wildcard_candidates(receiver)
+            .find(|candidate| self.matcher.matches(method, None, Some(candidate)))
+    }
+
+    /// The namespace a C# type constructed by its short name is imported
+    /// from: the first `using` namespace (`System.Security.Cryptography` for
+    /// `new Rfc2898DeriveBytes(...)`) that maps it. Swift modules likewise
+    /// bring in their types and functions (`SymmetricKey(size:)`,
+    /// `CCCrypt(...)`) unqualified, as Kotlin and Scala wildcard imports do
+    /// their classes (`PBEKeySpec(...)`). A class imported by name is looked
+    /// up in its package.
+    fn namespace_module(&self, type_name: &str, imports: &ImportMap) -> Option<String> {
+        let package = imports
+            .get(type_name)
+            .and_then(|path| path.rsplit_once('.'))
+            .map(|(package, _)| package);
+        package
+            .into_iter()
+            .chain(imports.wildcards())
+            .find(|namespace| self.matcher.matches(type_name, None, Some(namespace)))
+            .map(str::to_string)
+    }
+
+    /// A fully qualified C# receiver (`System.Security.Cryptography.RSA`)
+    /// that maps `method` without any `using`, a Ruby constant
+    /// (`OpenSSL::Cipher`), which is global once its library is required,
+    /// or a PHP class or namespace, which the autoloader finds by name.
+    fn qualified_module(&self, receiver: &str, method: &str, ctx: &Context) -> Option<String> {
+        let qualified = match ctx.language() {
+            "csharp" => receiver.contains('.'),
+            "ruby" => receiver.starts_with(|c: char| c.is_ascii_uppercase()),
+            "php" => !receiver.starts_with('$'),
+            _ => false,
+        };
+        (qualified && self.matcher.matches(method, None, Some(receiver)))
+            .then(|| receiver.to_string())
+    }
+
+    /// The module PHP's built-in functions (`openssl_encrypt`,
+    /// `sodium_crypto_secretbox`) are mapped under. They are global, so
+    /// calling one needs no import.
+    fn builtin_module(&self, function: &str, ctx: &Context) -> Option<String> {
+        (ctx.language() == "php" && self.matcher.matches(function, None, Some("php")))
+            .then(|| "php".to_string())
+    }
+
+    /// The included header that declares `function`, called unqualified:
+    /// the first one (`openssl/evp.h` for `EVP_EncryptInit_ex`) that maps it.
+    fn header_module(&self, function: &str, imports: &ImportMap) -> Option<String> {
+        imports
+            .headers()
+            .find(|header| self.matcher.matches(function, None, Some(header)))
+            .map(str::to_string)
+    }
+
+    /// The included header declaring `function` called through `scope`,
+    /// with the name it is mapped under: the most qualified of
+    /// `Botan::HashFunction::create`, `HashFunction::create` and `create`
+    /// that a header maps.
+    fn scoped_header_member(
+        &self,
+        scope: &str,
+        function: &str,
+        imports: &ImportMap,
+    ) -> Option<(String, String)> {
+        let segments: Vec<&str> = scope.split("::").collect();
+        (0..=segments.len()).find_map(|skip| {
+            let member = segments[skip..]
+                .iter()
+                .chain([&function])
+                .copied()
+                .collect::<Vec<_>>()
+                .join("::");
+            self.header_module(&member, imports)
+                .map(|header| (header, member))
+        })
+    }
+
+    /// The client module a method call on `receiver` belongs to: one that
+    /// maps `method` and that the receiver is bound to in this file, either
+    /// directly (`boto3.client("kms").encrypt`) or through a declaration
+    /// mentioning one of the module's import names (`client *kms.Client`,
+    /// `kms = boto3.client("kms")`).
+    fn client_module(
+        &self,
+        receiver: &str,
+        method: &str,
+        ctx: &Context,
+        imports: &ImportMap,
+    ) -> Option<String> {
+        self.clients
+            .iter()
+            .find(|module| {
+                if !self.matcher.matches(method, None, Some(module)) {
+                    return false;
+                }
+                let aliases = imports.aliases_of(module);
+                if aliases.is_empty() {
+                    return false;
+                }
+                let mentions_module =
+                    |text: &str| aliases.iter().any(|alias| mentions_alias(text, alias));
+                mentions_module(receiver)
+                    || receiver_bindings(ctx.tree().root_node(), receiver, ctx)
+                        .iter()
+                        .any(|binding| mentions_module(binding))
+            })
+            .cloned()
+    }
+
     fn extract_function_name<'a>(
         &self,
         node: &Node<'a>,
@@ -607,6 +1328,147 @@ impl Scanner {
         // Go: call_expression -> function (selector_expression or identifier)
         // Python: call -> function (attribute or identifier)
 
+        // Java: method_invocation -> object (optional) and name
+        if node.kind() == "method_invocation" {
+            let name = ctx.get_node_text(&node.child_by_field_name("name")?);
+            let object = node
+                .child_by_field_name("object")
+                .map(|object| ctx.get_node_text(&object));
+            return Some((name, object));
+        }
+
+        // C#: new Rfc2898DeriveBytes(...) or new System.Security.Cryptography.Aes...,
+        // with the namespace as the package
+        if node.kind() == "object_creation_expression" {
+            let type_node = node.child_by_field_name("type")?;
+            if type_node.kind() == "qualified_name" {
+                let qualifier = type_node.child_by_field_name("qualifier")?;
+                let name = type_node.child_by_field_name("name")?;
+                return Some((
+                    ctx.get_node_text(&unwrap_generic_name(name)),
+                    Some(ctx.get_node_text(&qualifier)),
+                ));
+            }
+            return Some((ctx.get_node_text(&unwrap_generic_name(type_node)), None));
+        }
+
+        // PHP: $cipher->encrypt(...) or AES::createKey(...), with the object
+        // or class as the package
+        if matches!(
+            node.kind(),
+            "member_call_expression" | "scoped_call_expression"
+        ) {
+            let name = ctx.get_node_text(&node.child_by_field_name("name")?);
+            let receiver = node
+                .child_by_field_name("object")
+                .or_else(|| node.child_by_field_name("scope"))
+                .map(|receiver| {
+                    ctx.get_node_text(&receiver)
+                        .trim_start_matches('\\')
+                        .to_string()
+                });
+            return Some((name, receiver));
+        }
+
+        // Swift: SHA256.hash(data:) or CCCrypt(...), with the receiver as
+        // the package; subscripts (`bytes[0]`) parse as calls too
+        if node.kind() == "call_expression" && ctx.language() == "swift" {
+            let callee = node.named_child(0)?;
+            let arguments = self.find_arguments_child(node);
+            if arguments
+                .and_then(|args| args.child(0))
+                .is_some_and(|open| open.kind() == "[")
+            {
+                return None;
+            }
+            return match callee.kind() {
+                "navigation_expression" => {
+                    let target = callee.child_by_field_name("target")?;
+                    let suffix = callee
+                        .child_by_field_name("suffix")?
+                        .child_by_field_name("suffix")?;
+                    Some((ctx.get_node_text(&suffix), Some(ctx.get_node_text(&target))))
+                }
+                "simple_identifier" => Some((ctx.get_node_text(&callee), None)),
+                _ => None,
+            };
+        }
+
+        // Kotlin: Cipher.getInstance(...) or SecretKeySpec(...), with the
+        // receiver as the package; the grammar labels neither
+        if node.kind() == "call_expression" && ctx.language() == "kotlin" {
+            let callee = node.named_child(0)?;
+            return match callee.kind() {
+                "navigation_expression" => {
+                    let receiver = callee.named_child(0)?;
+                    let member = callee.named_child(callee.named_child_count().checked_sub(1)?)?;
+                    (member.kind() == "identifier").then(|| {
+                        (
+                            ctx.get_node_text(&member),
+                            Some(ctx.get_node_text(&receiver)),
+                        )
+                    })
+                }
+                "identifier" => Some((ctx.get_node_text(&callee), None)),
+                _ => None,
+            };
+        }
+
+        // Scala: Mac.getInstance(...), Mac getInstance "HmacSHA256",
+        // PBEKeySpec(...) and new PBEKeySpec(...), with the receiver as the
+        // package; `Mac.apply(...)` is `Mac(...)`
+        if ctx.language() == "scala" {
+            return match node.kind() {
+                "infix_expression" => {
+                    let left = node.child_by_field_name("left")?;
+                    let operator = node.child_by_field_name("operator")?;
+                    Some((ctx.get_node_text(&operator), Some(ctx.get_node_text(&left))))
+                }
+                "instance_expression" => {
+                    let mut type_node = node.named_child(0)?;
+                    if type_node.kind() == "generic_type" {
+                        type_node = type_node.child_by_field_name("type")?;
+                    }
+                    match ctx.get_node_text(&type_node).rsplit_once('.') {
+                        Some((package, name)) => {
+                            Some((name.to_string(), Some(package.to_string())))
+                        }
+                        None => Some((ctx.get_node_text(&type_node), None)),
+                    }
+                }
+                _ => {
+                    let mut callee = node.child_by_field_name("function")?;
+                    if callee.kind() == "field_expression"
+                        && callee
+                            .child_by_field_name("field")
+                            .is_some_and(|field| ctx.get_node_text(&field) == "apply")
+                    {
+                        callee = callee.child_by_field_name("value")?;
+                    }
+                    match callee.kind() {
+                        "field_expression" => {
+                            let value = callee.child_by_field_name("value")?;
+                            let field = callee.child_by_field_name("field")?;
+                            Some((ctx.get_node_text(&field), Some(ctx.get_node_text(&value))))
+                        }
+                        "identifier" => Some((ctx.get_node_text(&callee), None)),
+                        _ => None,
+                    }
+                }
+            };
+        }
+
+        // Ruby: OpenSSL::Cipher.new(...) or digest.update(...), with the
+        // receiver as the package
+        if let Some(method) = node.child_by_field_name("method") {
+            let receiver = node.child_by_field_name("receiver").map(|receiver| {
+                ctx.get_node_text(&receiver)
+                    .trim_start_matches("::")
+                    .to_string()
+            });
+            return Some((ctx.get_node_text(&method), receiver));
+        }
+
         let func_node = node.child_by_field_name("function")?;
 
         match func_node.kind() {
@@ -631,6 +1493,50 @@ impl Scanner {
                 let name = ctx.get_node_text(&func_node);
                 Some((name, None))
             }
+            // C++: Botan::HashFunction::create or ::EVP_sha256, with the
+            // scope as the package
+            "qualified_identifier" => {
+                let mut scope = Vec::new();
+                let mut name = func_node;
+                while name.kind() == "qualified_identifier" {
+                    if let Some(segment) = name.child_by_field_name("scope") {
+                        scope.push(ctx.get_node_text(&segment));
+                    }
+                    name = name.child_by_field_name("name")?;
+                }
+                // make_unique<T>: the template arguments are not part of the name
+                if name.kind() == "template_function" {
+                    name = name.child_by_field_name("name")?;
+                }
+                let package = (!scope.is_empty()).then(|| scope.join("::"));
+                Some((ctx.get_node_text(&name), package))
+            }
+            // PHP: \hash_pbkdf2 or \Sodium\crypto_box, with the namespace
+            // as the package
+            "qualified_name" => {
+                let name = func_node.named_child(func_node.named_child_count().checked_sub(1)?)?;
+                let package = func_node
+                    .child_by_field_name("prefix")
+                    .map(|prefix| ctx.get_node_text(&prefix).trim_matches('\\').to_string())
+                    .filter(|prefix| !prefix.is_empty());
+                Some((ctx.get_node_text(&name), package))
+            }
+            // C/C++: ctx->method or obj.method
+            "field_expression" => {
+                let obj = func_node.child_by_field_name("argument")?;
+                let field = func_node.child_by_field_name("field")?;
+                let package = ctx.get_node_text(&obj);
+                let name = ctx.get_node_text(&field);
+                Some((name, Some(package)))
+            }
+            // C#: Type.Method, namespace.Type.Method or Type.Method<T>
+            "member_access_expression" => {
+                let obj = func_node.child_by_field_name("expression")?;
+                let name = func_node.child_by_field_name("name")?;
+                let package = ctx.get_node_text(&obj);
+                let name = ctx.get_node_text(&unwrap_generic_name(name));
+                Some((name, Some(package)))
+            }
             // Member expression (JS): obj.method
             "member_expression" => {
                 let obj = func_node.child_by_field_name("object")?;
@@ -647,8 +1553,34 @@ impl Scanner {
         }
     }
 
-    fn extract_arguments<'a>(&self, node: &Node<'a>, ctx: &Context<'a>) -> Vec<Value> {
+    fn extract_arguments<'a>(
+        &self,
+        node: &Node<'a>,
+        ctx: &Context<'a>,
+    ) -> (Vec<Value>, Vec<Option<String>>) {
         let mut arguments = Vec::new();
+        let mut keywords = Vec::new();
+
+        // Scala infix calls take one argument, or a tuple of them:
+        // `Cipher getInstance ("AES", "BC")`
+        if node.kind() == "infix_expression" {
+            let Some(right) = node.child_by_field_name("right") else {
+                return (arguments, keywords);
+            };
+            let values = match right.kind() {
+                "tuple_expression" => {
+                    let mut cursor = right.walk();
+                    let elements: Vec<Node<'a>> = right.named_children(&mut cursor).collect();
+                    elements
+                }
+                _ => vec![right],
+            };
+            arguments = values
+                .iter()
+                .map(|value| self.resolver.resolve(value, ctx))
+                .collect();
+            return (arguments, keywords);
+        }
 
         // Find the arguments node - try field name first, then search children
         let args_node = node
@@ -661,25 +1593,88 @@ impl Scanner {
                 // Skip punctuation (commas, parens)
                 if child.is_named() {
                     // For Python keyword arguments (name=value), extract just the value
-                    let value_node = if child.kind() == "keyword_argument" {
-                        child.child_by_field_name("value").unwrap_or(child)
+                    let (value_node, keyword) = if child.kind() == "keyword_argument" {
+                        (
+                            child.child_by_field_name("value").unwrap_or(child),
+                            child
+                                .child_by_field_name("name")
+                                .map(|name| ctx.get_node_text(&name)),
+                        )
+                    } else if child.kind() == "argument" {
+                        // C# wraps every argument, named ones as `iterations: 100000`
+                        let count = child.named_child_count();
+                        (
+                            child.named_child(count.saturating_sub(1)).unwrap_or(child),
+                            child
+                                .child_by_field_name("name")
+                                .map(|name| ctx.get_node_text(&name)),
+                        )
+                    } else if child.kind() == "value_argument" {
+                        // Swift labels arguments (`SHA256.hash(data: data)`);
+                        // Kotlin names them (`iterations = 10_000`) with no
+                        // fields, the value last
+                        let count = child.named_child_count();
+                        let is_named = || {
+                            let mut cursor = child.walk();
+                            let named = child.children(&mut cursor).any(|c| c.kind() == "=");
+                            named
+                        };
+                        (
+                            child
+                                .child_by_field_name("value")
+                                .or_else(|| child.named_child(count.saturating_sub(1)))
+                                .unwrap_or(child),
+                            child
+                                .child_by_field_name("name")
+                                .or_else(|| child.named_child(0).filter(|_| is_named()))
+                                .map(|name| ctx.get_node_text(&name)),
+                        )
+                    } else if child.kind() == "assignment_expression" && ctx.language() == "scala" {
+                        // Scala names arguments (`iterations = 10_000`)
+                        (
+                            child.child_by_field_name("right").unwrap_or(child),
+                            child
+                                .child_by_field_name("left")
+                                .map(|name| ctx.get_node_text(&name)),
+                        )
+                    } else if child.kind() == "pair"
+                        && child
+                            .child_by_field_name("key")
+                            .is_some_and(|key| key.kind() == "hash_key_symbol")
+                    {
+                        // Ruby keyword arguments (`iterations: 20_000`)
+                        (
+                            child.child_by_field_name("value").unwrap_or(child),
+                            child
+                                .child_by_field_name("key")
+                                .map(|key| ctx.get_node_text(&key)),
+                        )
                     } else {
-                        child
+                        (child, None)
                     };
                     let value = self.resolver.resolve(&value_node, ctx);
                     arguments.push(value);
+                    keywords.push(keyword);
                 }
             }
         }
 
-        arguments
+        if keywords.iter().all(Option::is_none) {
+            keywords.clear();
+        }
+        (arguments, keywords)
     }
 
     fn find_arguments_child<'a>(&self, node: &Node<'a>) -> Option<Node<'a>> {
         let mut cursor = node.walk();
         let result = node
             .children(&mut cursor)
-            .find(|child| child.kind() == "argument_list" || child.kind() == "arguments");
+            .find_map(|child| match child.kind() {
+                "argument_list" | "arguments" | "value_arguments" => Some(child),
+                // Swift: the arguments follow the callee in a call suffix
+                "call_suffix" => self.find_arguments_child(&child),
+                _ => None,
+            });
         result
     }
 
@@ -742,6 +1737,9 @@ func main() { pbkdf2.Key() }"#;
             package: Some("pbkdf2".to_string()),
             import_path: Some("golang.org/x/crypto/pbkdf2".to_string()),
             arguments: vec![],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
             raw_text: "pbkdf2.Key(...)".to_string(),
             language: "go".to_string(),
         };
@@ -758,6 +1756,9 @@ func main() { pbkdf2.Key() }"#;
             package: None,
             import_path: None,
             arguments: vec![],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
             raw_text: "encrypt(...)".to_string(),
             language: "go".to_string(),
         };
@@ -778,6 +1779,9 @@ func main() { pbkdf2.Key() }"#;
             package: None,
             import_path: None,
             arguments: vec![],
+            keywords: vec![],
+            site: Default::default(),
+            dynamic_name: None,
             raw_text: "test()".to_string(),
             language: "go".to_string(),
         });
@@ -1000,6 +2004,26 @@ key = hashlib.pbkdf2_hmac('sha256', password, salt, 100000)
         assert_eq!(call.import_path, Some("hashlib".to_string()));
     }
 
+    #[test]
+    fn test_import_tracking_python_dotted() {
+        let source = r#"
+import nacl.secret
+
+box = nacl.secret.SecretBox(key)
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let scanner = Scanner::new().with_patterns(vec!["secretbox".to_string()]);
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "test.py", "python");
+
+        assert_eq!(result.call_count(), 1);
+        assert_eq!(result.calls[0].import_path, Some("nacl.secret".to_string()));
+    }
+
     #[test]
     fn test_import_tracking_python_from() {
         let source = r#"
@@ -1060,4 +2084,328 @@ func main() {
         assert_eq!(field.value.expression, "tls.VersionTLS12");
         assert!(!field.value.is_resolved);
     }
+
+    fn tls_fields() -> StructFieldsMap {
+        let fields: HashMap<String, String> = [
+            ("minversion", "tls_config_min_version"),
+            ("ciphersuites", "tls_config_cipher_suites"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        HashMap::from([("crypto/tls.config".to_string(), fields)])
+    }
+
+    #[test]
+    fn test_fields_assigned_on_typed_receiver() {
+        let source = r#"package main
+import "crypto/tls"
+type Server struct{ MinVersion int }
+func configure(cfg *tls.Config, srv *Server) {
+    cfg.MinVersion = tls.VersionTLS13
+    srv.MinVersion = 1
+}
+func build() *tls.Config {
+    conf := &tls.Config{}
+    copied := conf
+    conf.CipherSuites, conf.NextProtos = suites, protos
+    other := new(Server)
+    other.MinVersion = 2
+    return copied
+}"#;
+        let tree = parse_go(source);
+        let scanner = Scanner::new().with_struct_fields(tls_fields());
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "tls.go", "go");
+
+        let fields: Vec<(&str, Option<&str>, usize)> = result
+            .configs
+            .iter()
+            .flat_map(|config| {
+                config.fields.iter().map(move |field| {
+                    (
+                        field.field_name.as_str(),
+                        config.import_path.as_deref(),
+                        config.line,
+                    )
+                })
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("MinVersion", Some("crypto/tls"), 5),
+                ("CipherSuites", Some("crypto/tls"), 11),
+            ]
+        );
+        assert_eq!(result.configs[0].struct_type, "Config");
+        assert_eq!(
+            result.configs[0].fields[0].value.expression,
+            "tls.VersionTLS13"
+        );
+    }
+
+    #[test]
+    fn test_field_assigned_on_variable_of_unknown_type() {
+        let source = r#"package main
+import "crypto/tls"
+func configure() {
+    cfg := loadConfig()
+    cfg.MinVersion = tls.VersionTLS10
+}"#;
+        let tree = parse_go(source);
+        let scanner = Scanner::new().with_struct_fields(tls_fields());
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "tls.go", "go");
+        assert!(result.configs.is_empty());
+    }
+
+    #[test]
+    fn test_generic_struct_literal() {
+        let source = r#"package main
+import "example.com/vault"
+func open() {
+    opts := vault.Options[string]{Cipher: "aes-256-gcm"}
+    _ = opts
+}"#;
+        let tree = parse_go(source);
+        let fields = HashMap::from([("cipher".to_string(), "vault_cipher".to_string())]);
+        let scanner = Scanner::new()
+            .with_struct_fields(HashMap::from([("vault.options".to_string(), fields)]));
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "vault.go", "go");
+
+        assert_eq!(result.configs.len(), 1);
+        assert_eq!(result.configs[0].struct_type, "Options");
+        assert_eq!(
+            result.configs[0].fields[0].classification_key.as_deref(),
+            Some("vault_cipher")
+        );
+    }
+
+    fn sse_fields() -> StructFieldsMap {
+        let fields: HashMap<String, String> = [
+            ("serversideencryption", "storage_sse"),
+            ("ssekmskeyid", "storage_kms_key"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        [
+            "s3.putobjectinput",
+            "*.put_object",
+            "client-s3.putobjectcommand",
+            "*.putobjectcommand",
+        ]
+        .into_iter()
+        .map(|key| (key.to_string(), fields.clone()))
+        .collect()
+    }
+
+    #[test]
+    fn test_struct_literal_records_enclosing_call() {
+        let source = r#"package main
+import "github.com/aws/aws-sdk-go-v2/service/s3"
+func upload(svc *s3.Client) {
+    svc.PutObject(ctx, &s3.PutObjectInput{
+        Bucket:               aws.String("b"),
+        ServerSideEncryption: "aws:kms",
+    })
+}"#;
+        let tree = parse_go(source);
+        let scanner = Scanner::new().with_struct_fields(sse_fields());
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "s3.go", "go");
+
+        assert_eq!(result.configs.len(), 1);
+        let config = &result.configs[0];
+        assert_eq!(config.call.as_deref(), Some("svc.PutObject"));
+        let sse = &config.fields[1];
+        assert_eq!(sse.field_name, "ServerSideEncryption");
+        assert_eq!(sse.classification_key.as_deref(), Some("storage_sse"));
+        assert_eq!(sse.value.string_values, vec!["aws:kms"]);
+        assert_eq!(config.fields[0].classification_key, None);
+    }
+
+    #[test]
+    fn test_python_keyword_argument_settings() {
+        let source = r#"import boto3
+s3 = boto3.client("s3")
+s3.put_object(Bucket="b", Key="k", ServerSideEncryption="aws:kms", SSEKMSKeyId=KEY)
+s3.get_object(Bucket="b", Key="k")
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let scanner = Scanner::new().with_struct_fields(sse_fields());
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "upload.py", "python");
+
+        assert_eq!(result.configs.len(), 1);
+        let config = &result.configs[0];
+        assert_eq!(config.struct_type, "put_object");
+        assert_eq!(config.call.as_deref(), Some("s3.put_object"));
+        assert_eq!(config.fields.len(), 4);
+        let sse = config
+            .fields
+            .iter()
+            .find(|f| f.classification_key.as_deref() == Some("storage_sse"))
+            .unwrap();
+        assert_eq!(sse.value.string_values, vec!["aws:kms"]);
+    }
+
+    #[test]
+    fn test_javascript_object_argument_settings() {
+        let source = r#"import { PutObjectCommand } from "@aws-sdk/client-s3";
+await client.send(new PutObjectCommand({ Bucket: "b", ServerSideEncryption: "aws:kms" }));
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_javascript::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let scanner = Scanner::new().with_struct_fields(sse_fields());
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "upload.js", "javascript");
+
+        assert_eq!(result.configs.len(), 1);
+        let config = &result.configs[0];
+        assert_eq!(config.struct_type, "PutObjectCommand");
+        assert_eq!(config.fields.len(), 2);
+        assert_eq!(config.fields[1].field_name, "ServerSideEncryption");
+        assert_eq!(
+            config.fields[1].classification_key.as_deref(),
+            Some("storage_sse")
+        );
+    }
+
+    #[test]
+    fn test_method_on_client_object_is_attributed_to_imported_module() {
+        let source = r#"package main
+import "github.com/aws/aws-sdk-go-v2/service/kms"
+func seal(client *kms.Client, block cipher.Block) {
+    client.GenerateDataKey(ctx, &kms.GenerateDataKeyInput{KeyId: id})
+    client.Encrypt(ctx, input)
+    block.Encrypt(dst, src)
+}"#;
+        let tree = parse_go(source);
+        let mut mappings = HashMap::new();
+        mappings.insert(
+            "github.com/aws/aws-sdk-go-v2/service/kms".to_string(),
+            [("generatedatakey", "kms_gdk"), ("encrypt", "kms_encrypt")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+
+        let without_clients = Scanner::with_mappings(mappings.clone());
+        let result = without_clients.scan_tree(&tree, source.as_bytes(), "kms.go", "go");
+        assert_eq!(result.call_count(), 0);
+
+        let scanner = Scanner::with_mappings(mappings)
+            .with_clients(["github.com/aws/aws-sdk-go-v2/service/kms".to_string()]);
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "kms.go", "go");
+        let names: Vec<String> = result.calls.iter().map(Finding::full_name).collect();
+        assert_eq!(names, vec!["client.GenerateDataKey", "client.Encrypt"]);
+        assert!(mentions_alias("kms = boto3.client(\"kms\")", "boto3"));
+        assert!(!mentions_alias("myboto3.client()", "boto3"));
+        assert_eq!(
+            result.calls[0].import_path.as_deref(),
+            Some("github.com/aws/aws-sdk-go-v2/service/kms")
+        );
+    }
+
+    #[test]
+    fn test_python_keyword_arguments_are_recorded() {
+        let source = r#"import hashlib
+hashlib.scrypt(pw, salt=salt, n=16384, r=8, p=1)
+hashlib.pbkdf2_hmac("sha256", pw, salt, 600000)
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let mut mappings = HashMap::new();
+        mappings.insert(
+            "hashlib".to_string(),
+            [("scrypt", "scrypt"), ("pbkdf2_hmac", "pbkdf2")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        let result = Scanner::with_mappings(mappings).scan_tree(
+            &tree,
+            source.as_bytes(),
+            "auth.py",
+            "python",
+        );
+
+        let scrypt = &result.calls[0];
+        assert_eq!(scrypt.keyword(0), None);
+        assert_eq!(scrypt.keyword(2), Some("n"));
+        assert_eq!(scrypt.arguments[2].int_values, vec![16384]);
+        assert!(result.calls[1].keywords.is_empty());
+    }
+
+    #[test]
+    fn test_call_site_names() {
+        let source = r#"package main
+
+func signMessage(key *rsa.PrivateKey, msg []byte) {
+	hashed := sha256.Sum256(msg)
+	rsa.SignPKCS1v15(nil, key, crypto.SHA256, hashed[:])
+}
+"#;
+        let tree = parse_go(source);
+        let scanner = Scanner::new().with_patterns(vec!["sha256".to_string()]);
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "sign.go", "go");
+
+        let site = &result.calls[0].site;
+        assert_eq!(site.enclosing_function.as_deref(), Some("signMessage"));
+        assert_eq!(site.assigned_to.as_deref(), Some("hashed"));
+        assert_eq!(site.argument_names, vec!["msg"]);
+        assert_eq!(site.consumers, vec!["SignPKCS1v15"]);
+    }
+
+    #[test]
+    fn test_functions_looked_up_by_name() {
+        let mut mappings = HashMap::new();
+        mappings.insert(
+            "hashlib".to_string(),
+            HashMap::from([("md5".to_string(), "md5".to_string())]),
+        );
+        mappings.insert(
+            "crypto".to_string(),
+            HashMap::from([("createhash".to_string(), "hash".to_string())]),
+        );
+        let scanner = Scanner::with_mappings(mappings);
+
+        let source = r#"import hashlib
+ALGO = "md5"
+getattr(hashlib, ALGO)(data)
+h = getattr(hashlib, name)
+getattr(config, "md5")
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "a.py", "python");
+        let names: Vec<String> = result.calls.iter().map(Finding::full_name).collect();
+        assert_eq!(names, vec!["hashlib.md5", "hashlib.name"]);
+        assert_eq!(result.calls[0].raw_text, "getattr(hashlib, ALGO)(data)");
+        assert_eq!(result.calls[0].arguments.len(), 1);
+        assert!(!result.calls[1].dynamic_name.as_ref().unwrap().is_resolved);
+        assert!(result.calls[1].arguments.is_empty());
+
+        let source =
+            "const crypto = require('crypto');\ncrypto[method](data);\nrows[method](data);\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_javascript::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let result = scanner.scan_tree(&tree, source.as_bytes(), "a.js", "javascript");
+        assert_eq!(result.call_count(), 1);
+        assert_eq!(result.calls[0].full_name(), "crypto.method");
+        assert_eq!(result.calls[0].line, 2);
+    }
 }
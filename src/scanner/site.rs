@@ -0,0 +1,179 @@
+//! Names around a call that hint at what its result is used for: the
+//! function it is made in, the variable it is assigned to, the identifiers
+//! it is passed and the calls its result flows into.
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+use crate::engine::{Context, NodeCategory};
+
+/// Later calls followed from an assigned variable, per call site.
+const MAX_CONSUMERS: usize = 8;
+
+const IDENTIFIER_KINDS: &[&str] = &[
+    "identifier",
+    "field_identifier",
+    "property_identifier",
+    "shorthand_property_identifier",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallSite {
+    /// Function or method the call is made in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosing_function: Option<String>,
+    /// Variable the result is assigned to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    /// Identifiers in the call's arguments and in those of methods chained
+    /// on its result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub argument_names: Vec<String>,
+    /// Calls the result is passed to: the ones the call is nested in, then
+    /// later calls in the same function that are given the assigned
+    /// variable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumers: Vec<String>,
+}
+
+impl CallSite {
+    pub fn is_empty(&self) -> bool {
+        self.enclosing_function.is_none()
+            && self.assigned_to.is_none()
+            && self.argument_names.is_empty()
+            && self.consumers.is_empty()
+    }
+}
+
+pub(super) fn call_site<'a>(node: &Node<'a>, ctx: &Context<'a>) -> CallSite {
+    let mut site = CallSite::default();
+
+    if let Some(arguments) = node.child_by_field_name("arguments") {
+        collect_identifiers(arguments, ctx, &mut site.argument_names);
+    }
+
+    let mut child = *node;
+    let mut in_statement = true;
+    let mut function = None;
+    while let Some(parent) = child.parent() {
+        let kind = parent.kind();
+        if ctx.is_node_category(kind, NodeCategory::FunctionDeclaration) {
+            site.enclosing_function = function_name(&parent, ctx);
+            function = Some(parent);
+            break;
+        }
+        if ctx.is_node_category(kind, NodeCategory::Block) {
+            in_statement = false;
+        }
+        if in_statement {
+            if ctx.is_node_category(kind, NodeCategory::CallExpression)
+                && parent
+                    .child_by_field_name("arguments")
+                    .is_some_and(|args| args.id() == child.id())
+            {
+                site.consumers.extend(callee_name(&parent, ctx));
+            } else if ctx.is_node_category(kind, NodeCategory::CallExpression) {
+                // A method chained on the result, e.g. `.update(contents)`.
+                if let Some(arguments) = parent.child_by_field_name("arguments") {
+                    collect_identifiers(arguments, ctx, &mut site.argument_names);
+                }
+            } else if is_binding(&parent, ctx) {
+                site.assigned_to = bound_name(&parent, ctx);
+                in_statement = false;
+            }
+        }
+        child = parent;
+    }
+
+    if let (Some(variable), Some(function)) = (&site.assigned_to, function) {
+        if let Some(body) = function.child_by_field_name("body") {
+            collect_consumers(body, node.end_byte(), variable, ctx, &mut site.consumers);
+        }
+    }
+
+    site
+}
+
+fn collect_identifiers<'a>(node: Node<'a>, ctx: &Context<'a>, out: &mut Vec<String>) {
+    if IDENTIFIER_KINDS.contains(&node.kind()) {
+        let name = ctx.get_node_text(&node);
+        if !out.contains(&name) {
+            out.push(name);
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_identifiers(child, ctx, out);
+    }
+}
+
+fn is_binding(node: &Node, ctx: &Context) -> bool {
+    ctx.is_node_category(node.kind(), NodeCategory::Assignment)
+        || ctx.is_node_category(node.kind(), NodeCategory::VariableDeclaration)
+        || node.kind() == "variable_declarator"
+}
+
+/// The left-hand side of an assignment or declaration, when it is a
+/// single name.
+fn bound_name<'a>(node: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
+    let target = ["left", "name", "pattern"]
+        .iter()
+        .find_map(|field| node.child_by_field_name(field))?;
+    let target = match target.kind() {
+        "expression_list" if target.named_child_count() == 1 => target.named_child(0)?,
+        _ => target,
+    };
+    IDENTIFIER_KINDS
+        .contains(&target.kind())
+        .then(|| ctx.get_node_text(&target))
+}
+
+fn function_name<'a>(function: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
+    if let Some(name) = function.child_by_field_name("name") {
+        return Some(ctx.get_node_text(&name));
+    }
+    // const hashPassword = (pw) => ...
+    let parent = function.parent()?;
+    (parent.kind() == "variable_declarator")
+        .then(|| parent.child_by_field_name("name"))
+        .flatten()
+        .map(|name| ctx.get_node_text(&name))
+}
+
+fn callee_name<'a>(call: &Node<'a>, ctx: &Context<'a>) -> Option<String> {
+    let function = call.child_by_field_name("function")?;
+    let text = ctx.get_node_text(&function);
+    text.rsplit(['.', ':'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// Calls after `after` whose arguments mention `variable`.
+fn collect_consumers<'a>(
+    node: Node<'a>,
+    after: usize,
+    variable: &str,
+    ctx: &Context<'a>,
+    out: &mut Vec<String>,
+) {
+    if out.len() >= MAX_CONSUMERS || node.end_byte() <= after {
+        return;
+    }
+    if node.start_byte() >= after && ctx.is_node_category(node.kind(), NodeCategory::CallExpression)
+    {
+        let mentions = node.child_by_field_name("arguments").is_some_and(|args| {
+            let mut names = Vec::new();
+            collect_identifiers(args, ctx, &mut names);
+            names.iter().any(|name| name == variable)
+        });
+        if mentions {
+            out.extend(callee_name(&node, ctx));
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_consumers(child, after, variable, ctx, out);
+    }
+}
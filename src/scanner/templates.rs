@@ -0,0 +1,314 @@
+//! Code embedded in template files: the scripts of HTML and Go
+//! `html/template` pages, the tags of EJS and ERB templates, and the
+//! import/export statements and JSX blocks of MDX documents.
+//!
+//! A template is scanned as the language of its embedded code. Everything
+//! else in the file is blanked to spaces, keeping the newlines, so the
+//! extracted code sits at its original line and column and findings point
+//! into the template itself. The end of each template tag becomes a `;`,
+//! so neighbouring tags do not run into one statement.
+
+use std::path::Path;
+#[cfg(feature = "discovery")]
+use std::path::PathBuf;
+
+use crate::cli::Language;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateKind {
+    /// Go `html/template` pages (`.gohtml`, `.tmpl`, `.gotmpl`): the
+    /// `<script>` bodies, with `{{ }}` actions replaced by `null`.
+    GoHtml,
+    /// Plain HTML pages: the `<script>` bodies.
+    Html,
+    /// EJS: the JavaScript of `<% %>` tags and of `<script>` bodies.
+    Ejs,
+    /// ERB: the Ruby of `<% %>` tags.
+    Erb,
+    /// MDX: `import`/`export` statements and JSX blocks.
+    Mdx,
+}
+
+impl TemplateKind {
+    pub fn for_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gohtml" | "tmpl" | "gotmpl" => Some(Self::GoHtml),
+            "html" | "htm" => Some(Self::Html),
+            "ejs" => Some(Self::Ejs),
+            "erb" => Some(Self::Erb),
+            "mdx" => Some(Self::Mdx),
+            _ => None,
+        }
+    }
+
+    /// The language of the projects the template belongs to, and whose
+    /// directory scans take it in.
+    pub fn host_language(self) -> Language {
+        match self {
+            Self::GoHtml => Language::Go,
+            Self::Html | Self::Ejs | Self::Mdx => Language::Javascript,
+            Self::Erb => Language::Ruby,
+        }
+    }
+
+    /// The language of the embedded code.
+    pub fn code_language(self) -> Language {
+        match self {
+            Self::Erb => Language::Ruby,
+            _ => Language::Javascript,
+        }
+    }
+
+    #[cfg(feature = "discovery")]
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::GoHtml => &["gohtml", "tmpl", "gotmpl"],
+            Self::Html => &["html", "htm"],
+            Self::Ejs => &["ejs"],
+            Self::Erb => &["erb"],
+            Self::Mdx => &["mdx"],
+        }
+    }
+
+    #[cfg(feature = "discovery")]
+    const ALL: [Self; 5] = [Self::GoHtml, Self::Html, Self::Ejs, Self::Erb, Self::Mdx];
+}
+
+/// The embedded code of a `kind` template, at its original positions.
+pub fn extract(kind: TemplateKind, source: &str) -> String {
+    let mut code: Vec<u8> = source
+        .bytes()
+        .map(|b| if b == b'\n' { b'\n' } else { b' ' })
+        .collect();
+    match kind {
+        TemplateKind::GoHtml => {
+            for body in script_bodies(source) {
+                copy(&mut code, source, body.clone());
+                replace_actions(&mut code, source, body);
+            }
+        }
+        TemplateKind::Html => {
+            for body in script_bodies(source) {
+                copy(&mut code, source, body);
+            }
+        }
+        TemplateKind::Ejs => {
+            for body in script_bodies(source) {
+                copy(&mut code, source, body);
+            }
+            copy_tags(&mut code, source);
+        }
+        TemplateKind::Erb => copy_tags(&mut code, source),
+        TemplateKind::Mdx => copy_mdx_blocks(&mut code, source),
+    }
+    // Only whole ASCII-delimited ranges are copied, so the bytes stay UTF-8.
+    String::from_utf8(code).unwrap_or_default()
+}
+
+/// The templates under `root` that a scan of `language` takes in, outside
+/// the directories its loader skips.
+#[cfg(feature = "discovery")]
+pub fn find_templates(root: &Path, language: Language) -> Vec<PathBuf> {
+    use crate::discovery::languages::excluded_dirs;
+    use crate::discovery::utils::walk_source_files;
+
+    let web = |l| matches!(l, Language::Javascript | Language::Typescript);
+    let mut templates = Vec::new();
+    for kind in TemplateKind::ALL {
+        let host = kind.host_language();
+        if host != language && !(web(host) && web(language)) {
+            continue;
+        }
+        for extension in kind.extensions() {
+            templates.extend(
+                walk_source_files(root, extension, excluded_dirs(language), false)
+                    .unwrap_or_default(),
+            );
+        }
+    }
+    templates.sort();
+    templates
+}
+
+fn copy(code: &mut [u8], source: &str, range: std::ops::Range<usize>) {
+    code[range.clone()].copy_from_slice(&source.as_bytes()[range]);
+}
+
+/// The byte ranges of the bodies of the `<script>` elements holding
+/// JavaScript: no `type`, or a JavaScript, module or JSX one.
+fn script_bodies(source: &str) -> Vec<std::ops::Range<usize>> {
+    let lower = source.to_ascii_lowercase();
+    let mut bodies = Vec::new();
+    let mut rest = 0;
+    while let Some(found) = lower[rest..].find("<script") {
+        let open = rest + found;
+        let Some(tag_end) = lower[open..].find('>').map(|i| open + i + 1) else {
+            break;
+        };
+        let Some(close) = lower[tag_end..].find("</script").map(|i| tag_end + i) else {
+            break;
+        };
+        let tag = &lower[open..tag_end];
+        let is_script = match tag.find("type=") {
+            None => true,
+            Some(at) => ["javascript", "module", "jsx", "babel"]
+                .iter()
+                .any(|kind| tag[at..].contains(kind)),
+        };
+        if is_script {
+            bodies.push(tag_end..close);
+        }
+        rest = close;
+    }
+    bodies
+}
+
+/// Replaces each `{{ ... }}` action in `range` with `null`, which is valid
+/// wherever the action stands for a value.
+fn replace_actions(code: &mut [u8], source: &str, range: std::ops::Range<usize>) {
+    let mut at = range.start;
+    while let Some(found) = source[at..range.end].find("{{") {
+        let open = at + found;
+        let Some(close) = source[open..range.end].find("}}").map(|i| open + i + 2) else {
+            break;
+        };
+        for (i, byte) in code[open..close].iter_mut().enumerate() {
+            if *byte != b'\n' {
+                *byte = b"null".get(i).copied().unwrap_or(b' ');
+            }
+        }
+        at = close;
+    }
+}
+
+/// Copies the code of the `<% %>` tags of EJS and ERB: `<%=`, `<%-` and
+/// `<%_` output tags included, `<%#` comments and `<%%` literals left out.
+fn copy_tags(code: &mut [u8], source: &str) {
+    let mut rest = 0;
+    while let Some(found) = source[rest..].find("<%") {
+        let body_start = rest + found + 2;
+        let Some(close) = source[body_start..].find("%>").map(|i| body_start + i) else {
+            break;
+        };
+        let body = &source[body_start..close];
+        if !body.starts_with(['#', '%']) {
+            let start = close - body.trim_start_matches(['=', '-', '_']).len();
+            let end = close - usize::from(body.ends_with(['-', '_']));
+            if start < end {
+                copy(code, source, start..end);
+            }
+            code[close] = b';';
+        }
+        rest = close + 2;
+    }
+}
+
+/// Copies the paragraphs of an MDX document that are ESM (`import`,
+/// `export`) or JSX (starting with `<`), outside fenced code blocks.
+fn copy_mdx_blocks(code: &mut [u8], source: &str) {
+    let mut in_fence = false;
+    let mut in_block = false;
+    let mut at_paragraph_start = true;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            in_block = false;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if trimmed.is_empty() {
+            in_block = false;
+            at_paragraph_start = true;
+            continue;
+        }
+        if at_paragraph_start {
+            in_block = trimmed.starts_with("import ")
+                || trimmed.starts_with("export ")
+                || trimmed.starts_with('<');
+            at_paragraph_start = false;
+        }
+        if in_block {
+            copy(code, source, start..offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The non-blank lines of `code`, by line number.
+    fn lines(code: &str) -> Vec<(usize, &str)> {
+        code.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i + 1, line.trim_end()))
+            .collect()
+    }
+
+    #[test]
+    fn test_go_html_scripts() {
+        let source = "<h1>{{.Title}}</h1>\n<script>\n  const key = \"{{.Key}}\";\n  const id = {{.ID}};\n</script>\n<script type=\"text/template\"><b>x</b></script>\n";
+        let code = extract(TemplateKind::GoHtml, source);
+        assert_eq!(code.len(), source.len());
+        assert_eq!(
+            lines(&code),
+            vec![
+                (3, "  const key = \"null    \";"),
+                (4, "  const id = null   ;")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ejs_and_erb_tags() {
+        let source = "<p><%= hash(user.password) %></p>\n<%# md5 in a comment %>\n<% if (legacy) { -%>\n<%- md5(x) %>\n<% } %>\n";
+        let code = extract(TemplateKind::Ejs, source);
+        assert_eq!(
+            lines(&code),
+            vec![
+                (1, "       hash(user.password) ;"),
+                (3, "   if (legacy) {  ;"),
+                (4, "    md5(x) ;"),
+                (5, "   } ;"),
+            ]
+        );
+
+        let code = extract(TemplateKind::Erb, "<%= Digest::MD5.hexdigest(token) %>\n");
+        assert_eq!(
+            lines(&code),
+            vec![(1, "    Digest::MD5.hexdigest(token) ;")]
+        );
+    }
+
+    #[test]
+    fn test_mdx_blocks() {
+        let source = "import { sha1 } from 'crypto-js'\n\n# Hashing <b>docs</b>\n\nSome prose with sha1(x).\n\n<Demo digest={sha1('x')} />\n\n```js\nsha1('in a fence')\n```\n";
+        let code = extract(TemplateKind::Mdx, source);
+        assert_eq!(
+            lines(&code),
+            vec![
+                (1, "import { sha1 } from 'crypto-js'"),
+                (7, "<Demo digest={sha1('x')} />"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kinds() {
+        let kind = TemplateKind::for_path(Path::new("views/login.gohtml")).unwrap();
+        assert_eq!(kind.host_language(), Language::Go);
+        assert_eq!(kind.code_language(), Language::Javascript);
+        assert_eq!(
+            TemplateKind::for_path(Path::new("app/views/show.html.erb")),
+            Some(TemplateKind::Erb)
+        );
+        assert_eq!(TemplateKind::for_path(Path::new("main.go")), None);
+    }
+}
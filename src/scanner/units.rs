@@ -0,0 +1,304 @@
+//! Scanning dependencies as independent units on a pool of workers.
+//!
+//! The project's own files form one unit and each dependency another. Units
+//! share nothing but the scanner, whose classifier mappings and expression
+//! cache are read-only or synchronized, so workers only need their own
+//! parser. Each unit's results are collected in a shard of their own and
+//! handed back in unit order, keeping reports identical to a serial scan.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tree_sitter::Parser;
+
+use super::ScanResult;
+use crate::cancel;
+
+/// Files scanned together: the project's own (`dependency` is `None`) or
+/// those of one dependency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanUnit {
+    pub dependency: Option<String>,
+    pub files: Vec<PathBuf>,
+}
+
+impl ScanUnit {
+    /// Groups files by the dependency they belong to, in order of first
+    /// appearance, with the project's files first.
+    pub fn group(files: impl IntoIterator<Item = (Option<String>, PathBuf)>) -> Vec<Self> {
+        let mut units = vec![Self {
+            dependency: None,
+            files: Vec::new(),
+        }];
+        let mut positions = HashMap::from([(None, 0)]);
+        for (dependency, path) in files {
+            let position = *positions.entry(dependency.clone()).or_insert_with(|| {
+                units.push(Self {
+                    dependency,
+                    files: Vec::new(),
+                });
+                units.len() - 1
+            });
+            units[position].files.push(path);
+        }
+        units.retain(|unit| !unit.files.is_empty());
+        units
+    }
+
+    /// The label the unit is reported under.
+    pub fn label(&self) -> &str {
+        self.dependency.as_deref().unwrap_or("(project)")
+    }
+}
+
+/// The results of scanning one unit.
+#[derive(Debug)]
+pub struct UnitScan {
+    pub unit: ScanUnit,
+    /// Results of the files with findings, in file order.
+    pub results: Vec<ScanResult>,
+    pub elapsed: Duration,
+}
+
+impl UnitScan {
+    pub fn timing(&self) -> UnitTiming {
+        UnitTiming {
+            label: self.unit.label().to_string(),
+            files: self.unit.files.len(),
+            findings: self.results.iter().map(|r| r.call_count()).sum(),
+            elapsed: self.elapsed,
+        }
+    }
+}
+
+/// How long a unit took to scan, for the end-of-run summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitTiming {
+    pub label: String,
+    pub files: usize,
+    pub findings: usize,
+    pub elapsed: Duration,
+}
+
+/// Renders a plain-text table of unit timings, slowest first.
+pub fn render_timings(timings: &[UnitTiming]) -> String {
+    let mut sorted: Vec<_> = timings.iter().collect();
+    sorted.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then(a.label.cmp(&b.label)));
+
+    let width = sorted
+        .iter()
+        .map(|timing| timing.label.len())
+        .max()
+        .unwrap_or(0)
+        .max("dependency".len());
+    let mut out = format!(
+        "{:<width$} {:>8} {:>9} {:>12}\n",
+        "dependency", "files", "findings", "total"
+    );
+    for timing in sorted {
+        out.push_str(&format!(
+            "{:<width$} {:>8} {:>9} {:>12}\n",
+            timing.label,
+            timing.files,
+            timing.findings,
+            format!("{:.3}ms", timing.elapsed.as_secs_f64() * 1000.0),
+        ));
+    }
+    out
+}
+
+/// The number of workers to use when none is requested.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Scans `units` on up to `jobs` workers, each with its own parser for
+/// `language`. `scan_file` returns the results of one file, if it has any;
+/// `sink` receives each unit's scan on the calling thread, in unit order.
+/// An error from `sink` stops the workers and is returned. Once the scan is
+/// [cancelled](cancel::cancel), no new file is started, and the units begun
+/// are passed to `sink` with the results of the files already scanned.
+pub fn scan_units<S, K, E>(
+    units: Vec<ScanUnit>,
+    jobs: usize,
+    language: &tree_sitter::Language,
+    scan_file: S,
+    mut sink: K,
+) -> Result<(), E>
+where
+    S: Fn(&mut Parser, &Path) -> Option<ScanResult> + Sync,
+    K: FnMut(UnitScan) -> Result<(), E>,
+{
+    let workers = jobs.clamp(1, units.len().max(1));
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let queue: Vec<_> = units
+        .into_iter()
+        .map(|unit| Mutex::new(Some(unit)))
+        .collect();
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next, stop, queue, scan_file) = (&next, &stop, &queue, &scan_file);
+            scope.spawn(move || {
+                let mut parser = Parser::new();
+                if parser.set_language(language).is_err() {
+                    return;
+                }
+                while !stop.load(Ordering::Relaxed) && !cancel::is_cancelled() {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(slot) = queue.get(index) else {
+                        break;
+                    };
+                    let Some(unit) = slot.lock().unwrap().take() else {
+                        continue;
+                    };
+
+                    let started = Instant::now();
+                    let results = unit
+                        .files
+                        .iter()
+                        .take_while(|_| !cancel::is_cancelled())
+                        .filter_map(|path| scan_file(&mut parser, path))
+                        .collect();
+                    let scan = UnitScan {
+                        unit,
+                        results,
+                        elapsed: started.elapsed(),
+                    };
+                    if sender.send((index, scan)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Units finish out of order; hold the early ones until their turn
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (index, scan) in receiver {
+            pending.insert(index, scan);
+            while let Some(scan) = pending.remove(&emitted) {
+                emitted += 1;
+                if let Err(e) = sink(scan) {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn go() -> tree_sitter::Language {
+        tree_sitter_go::LANGUAGE.into()
+    }
+
+    fn units(count: usize) -> Vec<ScanUnit> {
+        (0..count)
+            .map(|i| ScanUnit {
+                dependency: (i > 0).then(|| format!("dep{i}")),
+                files: (0..3)
+                    .map(|f| PathBuf::from(format!("{i}/{f}.go")))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn scan_path(parser: &mut Parser, path: &Path) -> Option<ScanResult> {
+        let tree = parser.parse("package main", None)?;
+        (tree.root_node().kind() == "source_file")
+            .then(|| ScanResult::new(path.to_string_lossy().into_owned()))
+    }
+
+    #[test]
+    fn test_group_by_dependency() {
+        let files = vec![
+            (Some("b".to_string()), PathBuf::from("b/1")),
+            (None, PathBuf::from("main")),
+            (Some("a".to_string()), PathBuf::from("a/1")),
+            (Some("b".to_string()), PathBuf::from("b/2")),
+        ];
+
+        let units = ScanUnit::group(files);
+
+        let labels: Vec<_> = units.iter().map(ScanUnit::label).collect();
+        assert_eq!(labels, vec!["(project)", "b", "a"]);
+        assert_eq!(
+            units[1].files,
+            vec![PathBuf::from("b/1"), PathBuf::from("b/2")]
+        );
+    }
+
+    #[test]
+    fn test_group_without_project_files() {
+        let units = ScanUnit::group(vec![(Some("a".to_string()), PathBuf::from("a/1"))]);
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].label(), "a");
+    }
+
+    #[test]
+    fn test_scan_units_in_unit_order() {
+        for jobs in [1, 4] {
+            let mut seen = Vec::new();
+            let result: Result<(), ()> = scan_units(units(8), jobs, &go(), scan_path, |scan| {
+                seen.push(scan.unit.label().to_string());
+                assert_eq!(scan.results.len(), 3);
+                Ok(())
+            });
+
+            assert!(result.is_ok());
+            let expected: Vec<_> = units(8).iter().map(|u| u.label().to_string()).collect();
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn test_scan_units_stops_on_sink_error() {
+        let mut calls = 0;
+        let result = scan_units(units(8), 2, &go(), scan_path, |_| {
+            calls += 1;
+            Err("spool full")
+        });
+
+        assert_eq!(result, Err("spool full"));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_render_timings_slowest_first() {
+        let timings = vec![
+            UnitTiming {
+                label: "(project)".to_string(),
+                files: 2,
+                findings: 1,
+                elapsed: Duration::from_millis(1),
+            },
+            UnitTiming {
+                label: "golang.org/x/crypto".to_string(),
+                files: 40,
+                findings: 6,
+                elapsed: Duration::from_millis(9),
+            },
+        ];
+
+        let rendered = render_timings(&timings);
+
+        let lines: Vec<_> = rendered.lines().collect();
+        assert!(lines[0].starts_with("dependency"));
+        assert!(lines[1].starts_with("golang.org/x/crypto"));
+        assert!(lines[1].contains("9.000ms"));
+        assert!(lines[2].starts_with("(project)"));
+    }
+}
@@ -0,0 +1,55 @@
+//! Fixtures shared by the unit tests of the report and policy modules.
+//! Each gives the least a value needs to deserialize; tests set the fields
+//! they look at with struct update syntax.
+
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::output::{Finding, JsonOutput};
+use crate::policy::{Severity, Weakness};
+
+/// A call to `full_name` in `file` at `line`, unclassified and without
+/// arguments.
+pub fn finding(file: &str, line: usize, full_name: &str) -> Finding {
+    let function = full_name.rsplit(['.', ':']).next().unwrap_or(full_name);
+    serde_json::from_value(json!({
+        "file": file, "line": line, "column": 1, "function": function,
+        "full_name": full_name, "parameters": {}, "raw_text": format!("{function}()")
+    }))
+    .unwrap()
+}
+
+/// Finding parameters from a JSON object, e.g. `{"arg0": 65537}`.
+pub fn parameters(parameters: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    serde_json::from_value(parameters).unwrap()
+}
+
+/// A weakness of `rule` in `file` on line 1, not linked to a finding.
+pub fn weakness(file: &str, rule: &str, severity: Severity) -> Weakness {
+    Weakness {
+        file: file.to_string(),
+        line: 1,
+        column: 1,
+        function: "f".to_string(),
+        rule: rule.to_string(),
+        severity,
+        message: String::new(),
+        evidence: serde_json::Value::Null,
+        finding: String::new(),
+        help_uri: None,
+        dependency: None,
+        test_code: false,
+    }
+}
+
+/// A report of one scanned file with `findings` and `weaknesses`.
+pub fn report(findings: Vec<Finding>, weaknesses: Vec<Weakness>) -> JsonOutput {
+    JsonOutput {
+        files_scanned: 1,
+        total_findings: findings.len(),
+        findings,
+        weaknesses,
+        ..JsonOutput::default()
+    }
+}
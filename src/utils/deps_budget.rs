@@ -0,0 +1,111 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::output::parse_byte_size;
+
+/// Dependency files scanned in full by default.
+pub const DEFAULT_DEPS_FILES: usize = 20_000;
+/// Dependency bytes scanned in full by default.
+pub const DEFAULT_DEPS_BYTES: u64 = 256 * 1024 * 1024;
+
+/// The most dependency files and bytes a scan takes in full, parsed from
+/// `--deps-budget`: comma-separated limits, each a file count (`5000`) or a
+/// size (`512M`), or `unlimited`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepsBudget {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+impl DepsBudget {
+    pub const UNLIMITED: Self = Self {
+        files: usize::MAX,
+        bytes: u64::MAX,
+    };
+
+    #[cfg(feature = "discovery")]
+    pub(crate) fn admits(&self, files: usize, bytes: u64) -> bool {
+        files <= self.files && bytes <= self.bytes
+    }
+}
+
+impl Default for DepsBudget {
+    fn default() -> Self {
+        Self {
+            files: DEFAULT_DEPS_FILES,
+            bytes: DEFAULT_DEPS_BYTES,
+        }
+    }
+}
+
+impl FromStr for DepsBudget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("unlimited") {
+            return Ok(Self::UNLIMITED);
+        }
+        let mut budget = Self::default();
+        for limit in s.split(',').map(str::trim) {
+            if limit.chars().all(|c| c.is_ascii_digit()) && !limit.is_empty() {
+                budget.files = limit
+                    .parse()
+                    .map_err(|_| format!("invalid file count '{limit}'"))?;
+            } else {
+                budget.bytes = parse_byte_size(limit)? as u64;
+            }
+        }
+        Ok(budget)
+    }
+}
+
+impl fmt::Display for DepsBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::UNLIMITED {
+            return write!(f, "unlimited");
+        }
+        let (size, unit) = [(1 << 30, "G"), (1 << 20, "M"), (1 << 10, "K")]
+            .into_iter()
+            .find(|(unit, _)| self.bytes.is_multiple_of(*unit))
+            .map_or((self.bytes, "B"), |(unit, suffix)| {
+                (self.bytes / unit, suffix)
+            });
+        write!(f, "{},{size}{unit}", self.files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deps_budget() {
+        assert_eq!(
+            "5000".parse::<DepsBudget>().unwrap(),
+            DepsBudget {
+                files: 5000,
+                bytes: DEFAULT_DEPS_BYTES
+            }
+        );
+        assert_eq!(
+            "100, 1M".parse::<DepsBudget>().unwrap(),
+            DepsBudget {
+                files: 100,
+                bytes: 1024 * 1024
+            }
+        );
+        assert_eq!(
+            "unlimited".parse::<DepsBudget>().unwrap(),
+            DepsBudget::UNLIMITED
+        );
+        assert!("lots".parse::<DepsBudget>().is_err());
+
+        let budget = DepsBudget {
+            files: 300,
+            bytes: 1500,
+        };
+        assert_eq!(DepsBudget::default().to_string(), "20000,256M");
+        assert_eq!(budget.to_string(), "300,1500B");
+        assert_eq!(budget.to_string().parse::<DepsBudget>().unwrap(), budget);
+    }
+}
@@ -1,3 +1,9 @@
+mod deps_budget;
+mod hash;
+mod shard;
 mod string;
 
-pub use string::{extract_last_segment, unquote_string};
+pub use deps_budget::{DepsBudget, DEFAULT_DEPS_BYTES, DEFAULT_DEPS_FILES};
+pub use hash::stable_hash;
+pub use shard::Shard;
+pub use string::{extract_last_segment, go_package_name, identifier_words, unquote_string};
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::stable_hash;
+
+/// One of `count` deterministic partitions of a scan (`--shard 2/4`).
+///
+/// Files are assigned by a stable hash of their path relative to the scan
+/// root, so every worker given the same tree agrees on the split regardless
+/// of where the tree is checked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    /// 1-based shard number.
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Shard {
+    pub fn new(index: usize, count: usize) -> Result<Self, String> {
+        if count == 0 {
+            return Err("shard count must be at least 1".to_string());
+        }
+        if index == 0 || index > count {
+            return Err(format!("shard index must be between 1 and {count}"));
+        }
+        Ok(Self { index, count })
+    }
+
+    /// Whether `path` belongs to this shard. Paths outside `root` (dependency
+    /// caches) are hashed as given.
+    pub fn contains(&self, path: &Path, root: &Path) -> bool {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let key: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        (stable_hash(key.join("/").as_bytes()) % self.count as u64) as usize == self.index - 1
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected <index>/<count>, got '{s}'"))?;
+        let index = index
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid shard index '{index}'"))?;
+        let count = count
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid shard count '{count}'"))?;
+        Self::new(index, count)
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!(
+            "2/4".parse::<Shard>().unwrap(),
+            Shard { index: 2, count: 4 }
+        );
+        assert_eq!("1/1".parse::<Shard>().unwrap().to_string(), "1/1");
+        assert!("0/4".parse::<Shard>().is_err());
+        assert!("5/4".parse::<Shard>().is_err());
+        assert!("1/0".parse::<Shard>().is_err());
+        assert!("3".parse::<Shard>().is_err());
+    }
+
+    #[test]
+    fn test_shards_partition_files() {
+        let files: Vec<PathBuf> = (0..200)
+            .map(|i| PathBuf::from(format!("/repo/pkg{}/file{i}.go", i % 7)))
+            .collect();
+        let shards: Vec<Shard> = (1..=3).map(|i| Shard::new(i, 3).unwrap()).collect();
+
+        for file in &files {
+            let owners = shards
+                .iter()
+                .filter(|s| s.contains(file, Path::new("/repo")))
+                .count();
+            assert_eq!(owners, 1, "{} must be in exactly one shard", file.display());
+        }
+        for shard in &shards {
+            assert!(files.iter().any(|f| shard.contains(f, Path::new("/repo"))));
+        }
+    }
+
+    #[test]
+    fn test_assignment_ignores_checkout_location() {
+        let shard = Shard::new(1, 4).unwrap();
+        for i in 0..50 {
+            let rel = format!("src/file{i}.py");
+            assert_eq!(
+                shard.contains(&Path::new("/home/ci/a").join(&rel), Path::new("/home/ci/a")),
+                shard.contains(&Path::new("/tmp/b").join(&rel), Path::new("/tmp/b")),
+            );
+        }
+    }
+}
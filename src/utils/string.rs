@@ -11,12 +11,59 @@ pub fn unquote_string(s: &str) -> String {
 }
 
 pub fn extract_last_segment(path: &str) -> String {
-    path.rsplit(['/', '.', ':'])
+    path.rsplit(['/', '.', ':', '\\'])
         .next()
         .unwrap_or(path)
         .to_string()
 }
 
+/// The package name a Go import path is referred to by when it has no
+/// alias: the last element, skipping a major version suffix
+/// (`github.com/golang-jwt/jwt/v5` -> `jwt`, `gopkg.in/yaml.v3` -> `yaml`).
+pub fn go_package_name(path: &str) -> String {
+    let is_version = |s: &str| {
+        s.strip_prefix('v')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+
+    let mut segments = path.rsplit('/');
+    let last = segments.next().unwrap_or(path);
+    let name = match segments.next() {
+        Some(previous) if is_version(last) => previous,
+        _ => last,
+    };
+    match name.rsplit_once('.') {
+        Some((base, version)) if is_version(version) => base.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Lowercase words of a camelCase or snake_case name. An acronym ends
+/// before the capital that starts the next word (`CBCDecrypter`).
+pub fn identifier_words(name: &str) -> impl Iterator<Item = String> + '_ {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|part| {
+            let mut words = Vec::new();
+            let mut start = 0;
+            let bytes = part.as_bytes();
+            for i in 1..bytes.len() {
+                let lower_to_upper =
+                    bytes[i].is_ascii_uppercase() && !bytes[i - 1].is_ascii_uppercase();
+                let acronym_end = bytes[i].is_ascii_uppercase()
+                    && bytes[i - 1].is_ascii_uppercase()
+                    && bytes.get(i + 1).is_some_and(|b| b.is_ascii_lowercase());
+                if lower_to_upper || acronym_end {
+                    words.push(&part[start..i]);
+                    start = i;
+                }
+            }
+            words.push(&part[start..]);
+            words
+        })
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +109,25 @@ mod tests {
     fn test_extract_last_segment_simple() {
         assert_eq!(extract_last_segment("hashlib"), "hashlib");
     }
+
+    #[test]
+    fn test_go_package_name() {
+        assert_eq!(go_package_name("crypto/sha256"), "sha256");
+        assert_eq!(go_package_name("github.com/golang-jwt/jwt/v5"), "jwt");
+        assert_eq!(go_package_name("gopkg.in/yaml.v3"), "yaml");
+        assert_eq!(
+            go_package_name("golang.org/x/crypto/chacha20poly1305"),
+            "chacha20poly1305"
+        );
+        assert_eq!(go_package_name("v2"), "v2");
+    }
+
+    #[test]
+    fn test_identifier_words() {
+        let words: Vec<String> = identifier_words("NewCBCDecrypter").collect();
+        assert_eq!(words, ["new", "cbc", "decrypter"]);
+
+        let words: Vec<String> = identifier_words("hash_userPassword").collect();
+        assert_eq!(words, ["hash", "user", "password"]);
+    }
 }
@@ -0,0 +1,51 @@
+//! wasm-bindgen entry points (`--features wasm`).
+//!
+//! Build for the browser or serverless runtimes with:
+//!
+//! ```text
+//! cargo build --lib --target wasm32-unknown-unknown --no-default-features --features wasm
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::api::RulesScanner;
+use crate::classifier::RulesClassifier;
+
+/// Scans `source` and returns the JSON report as a string.
+///
+/// `rules` is a user rules document (the `--rules` JSON format); without it no
+/// calls are matched. Each call builds a scanner from `rules`; a [`Scanner`]
+/// kept across calls builds it once.
+#[wasm_bindgen(js_name = scanSource)]
+pub fn scan_source(source: &str, language: &str, rules: Option<String>) -> Result<String, JsError> {
+    Scanner::new(rules)?.scan_source(source, language)
+}
+
+/// A scanner for one rules document, reused across scans.
+#[wasm_bindgen]
+pub struct Scanner {
+    inner: RulesScanner,
+}
+
+#[wasm_bindgen]
+impl Scanner {
+    /// `rules` is a user rules document (the `--rules` JSON format); without
+    /// it no calls are matched.
+    #[wasm_bindgen(constructor)]
+    pub fn new(rules: Option<String>) -> Result<Scanner, JsError> {
+        let classifier = match rules {
+            Some(rules) => RulesClassifier::from_json_str(&rules)?,
+            None => RulesClassifier::new(),
+        };
+        Ok(Self {
+            inner: RulesScanner::new(classifier),
+        })
+    }
+
+    /// Scans `source` and returns the JSON report as a string.
+    #[wasm_bindgen(js_name = scanSource)]
+    pub fn scan_source(&self, source: &str, language: &str) -> Result<String, JsError> {
+        let output = self.inner.scan_source(source, language)?;
+        Ok(serde_json::to_string(&output)?)
+    }
+}
@@ -61,7 +61,7 @@ fn test_cli_invalid_language() {
             "--path",
             file_path.to_str().unwrap(),
             "--language",
-            "java",
+            "cobol",
         ])
         .output()
         .expect("Failed to execute command");
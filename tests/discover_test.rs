@@ -4,5 +4,7 @@
 //! modular discovery_tests structure. New tests should be added to the
 //! appropriate module in `discovery_tests/`.
 
+#![cfg(feature = "discovery")]
+
 mod discovery_tests;
 mod fixtures;
@@ -43,3 +43,42 @@ fn test_rust_crypto_filter() {
         println!("NOTE: No crypto files found - this may be expected if presets/crypto/rust/mappings.json doesn't contain 'ring'");
     }
 }
+
+#[test]
+fn test_rust_filter_qualified_paths_and_grouped_imports() {
+    use argflow::discovery::filter::ImportFileFilter;
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let presets = root.join("presets");
+    fs::create_dir_all(presets.join("rust")).unwrap();
+    fs::write(
+        presets.join("rust/mappings.json"),
+        r#"{"mappings": {"ring::digest": {"digest": "digest"}, "aes_gcm": {"Aes256Gcm": "aes"}}}"#,
+    )
+    .unwrap();
+
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/qualified.rs"),
+        "fn id(x: &[u8]) -> ring::digest::Digest { ring::digest::digest(&ring::digest::SHA256, x) }",
+    )
+    .unwrap();
+    fs::write(root.join("src/grouped.rs"), "use ring::{digest, rand};").unwrap();
+    fs::write(root.join("src/other.rs"), "use serde::Serialize;").unwrap();
+
+    let filter = RustImportFilter::new(&[presets]).expect("Failed to create filter");
+    let all_files = RustPackageLoader
+        .load_user_code(root)
+        .expect("Failed to load user code");
+    let file_names = get_file_names(&filter_matching_files(all_files, &filter), root);
+    assert_file_found(&file_names, "qualified.rs");
+    assert_file_found(&file_names, "grouped.rs");
+    assert_file_not_found(&file_names, "other.rs");
+
+    assert!(filter.matches_package("ring"));
+    assert!(filter.matches_package("aes-gcm"));
+    assert!(!filter.matches_package("serde"));
+}
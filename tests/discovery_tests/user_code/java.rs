@@ -0,0 +1,50 @@
+//! Java-specific user code discovery tests
+
+use super::test_utils::*;
+use argflow::discovery::languages::java::JavaPackageLoader;
+use argflow::discovery::loader::PackageLoader;
+
+#[test]
+fn test_java_multi_module_source_roots() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    fs::write(
+        root.join("pom.xml"),
+        "<project><modules><module>core</module><module>api</module></modules></project>",
+    )
+    .unwrap();
+    for (file, source) in [
+        (
+            "core/src/main/java/com/example/Crypto.java",
+            "class Crypto {}",
+        ),
+        (
+            "core/src/test/java/com/example/CryptoTest.java",
+            "class CryptoTest {}",
+        ),
+        ("api/src/main/java/com/example/Api.java", "class Api {}"),
+        ("api/target/generated-sources/Gen.java", "class Gen {}"),
+        ("scripts/Tool.java", "class Tool {}"),
+    ] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+
+    let loader = JavaPackageLoader;
+    let user_files = loader
+        .load_user_code(root)
+        .expect("Failed to load user code");
+
+    let file_names = get_file_names(&user_files, root);
+
+    assert_file_found(&file_names, "core/src/main/java/com/example/Crypto.java");
+    assert_file_found(&file_names, "api/src/main/java/com/example/Api.java");
+    assert_file_not_found(&file_names, "CryptoTest.java");
+    assert_file_not_found(&file_names, "Gen.java");
+    assert_file_not_found(&file_names, "Tool.java");
+}
@@ -19,6 +19,7 @@
 pub mod test_utils;
 
 pub mod go;
+pub mod java;
 pub mod javascript;
 pub mod python;
 pub mod rust;
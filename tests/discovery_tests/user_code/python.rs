@@ -77,3 +77,37 @@ fn test_python_excluded_directories() {
     assert_file_not_found(&file_names, ".git");
     assert_file_not_found(&file_names, "__pycache__");
 }
+
+#[test]
+fn test_python_virtualenvs_are_not_user_code() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    let site_packages = root.join(".venv/lib/python3.12/site-packages/nacl");
+    fs::create_dir_all(&site_packages).unwrap();
+    fs::write(root.join(".venv/pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+    fs::write(site_packages.join("__init__.py"), "").unwrap();
+
+    fs::create_dir_all(root.join("py311/bin")).unwrap();
+    fs::write(root.join("py311/pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+    fs::write(root.join("py311/bin/activate_this.py"), "").unwrap();
+
+    fs::create_dir_all(root.join("env")).unwrap();
+    fs::write(root.join("env/settings.py"), "DEBUG = False").unwrap();
+    fs::write(root.join("main.py"), "import nacl").unwrap();
+
+    let loader = PythonPackageLoader;
+    let user_files = loader
+        .load_user_code(root)
+        .expect("Failed to load user code");
+
+    let file_names = get_file_names(&user_files, root);
+
+    assert_file_found(&file_names, "main.py");
+    assert_file_found(&file_names, "env/settings.py");
+    assert_file_not_found(&file_names, "site-packages");
+    assert_file_not_found(&file_names, "py311");
+}
@@ -83,3 +83,37 @@ fn test_rust_excluded_directories() {
     assert_file_not_found(&file_names, ".git");
     assert_file_not_found(&file_names, "target");
 }
+
+#[test]
+fn test_rust_workspace_members() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\n    \"crates/*\",\n]\n",
+    )
+    .unwrap();
+    for member in ["crates/core", "crates/cli"] {
+        fs::create_dir_all(root.join(member).join("src")).unwrap();
+        fs::write(root.join(member).join("Cargo.toml"), "[package]\n").unwrap();
+    }
+    fs::write(root.join("crates/core/src/seal.rs"), "pub fn seal() {}").unwrap();
+    fs::write(root.join("crates/cli/src/main.rs"), "fn main() {}").unwrap();
+    fs::create_dir_all(root.join("vendor/ring/src")).unwrap();
+    fs::write(root.join("vendor/ring/src/aead.rs"), "pub fn seal() {}").unwrap();
+
+    let loader = RustPackageLoader;
+    let user_files = loader
+        .load_user_code(root)
+        .expect("Failed to load user code");
+
+    let file_names = get_file_names(&user_files, root);
+
+    assert_file_found(&file_names, "crates/core/src/seal.rs");
+    assert_file_found(&file_names, "crates/cli/src/main.rs");
+    assert_file_not_found(&file_names, "vendor");
+}
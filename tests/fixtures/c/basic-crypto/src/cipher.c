@@ -0,0 +1,13 @@
+#include <openssl/evp.h>
+#include <openssl/rand.h>
+
+int encrypt_gcm(const unsigned char *key, unsigned char *iv) {
+    EVP_CIPHER_CTX *ctx = EVP_CIPHER_CTX_new();
+    RAND_bytes(iv, 12);
+    return EVP_EncryptInit_ex(ctx, EVP_aes_256_gcm(), NULL, key, iv);
+}
+
+int encrypt_legacy(const unsigned char *key) {
+    EVP_CIPHER_CTX *ctx = EVP_CIPHER_CTX_new();
+    return EVP_EncryptInit_ex(ctx, EVP_aes_128_ecb(), NULL, key, NULL);
+}
@@ -0,0 +1,11 @@
+#include <string.h>
+#include <openssl/md5.h>
+#include "openssl/sha.h"
+
+void fingerprint(const char *data, unsigned char *out) {
+    SHA256((const unsigned char *)data, strlen(data), out);
+}
+
+void legacy_etag(const char *data, unsigned char *out) {
+    MD5((const unsigned char *)data, strlen(data), out);
+}
@@ -0,0 +1,12 @@
+#include <openssl/evp.h>
+#include "params.h"
+
+#define SALT "static-salt"
+
+static const int SALT_LEN = 11;
+
+int derive_key(const char *password, int password_len, unsigned char *key) {
+    int iterations = KDF_ITERATIONS;
+    return PKCS5_PBKDF2_HMAC(password, password_len, (const unsigned char *)SALT,
+                             SALT_LEN, iterations, EVP_sha256(), KDF_KEY_LEN, key);
+}
@@ -0,0 +1,7 @@
+#ifndef PARAMS_H
+#define PARAMS_H
+
+#define KDF_ITERATIONS 600000
+#define KDF_KEY_LEN (32)
+
+#endif
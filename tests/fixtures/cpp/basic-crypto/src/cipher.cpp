@@ -0,0 +1,13 @@
+#include <openssl/evp.h>
+
+class Encryptor {
+public:
+    Encryptor() : ctx_(EVP_CIPHER_CTX_new()) {}
+
+    int init(const unsigned char* key, const unsigned char* iv) {
+        return EVP_EncryptInit_ex(ctx_, EVP_aes_256_gcm(), nullptr, key, iv);
+    }
+
+private:
+    EVP_CIPHER_CTX* ctx_;
+};
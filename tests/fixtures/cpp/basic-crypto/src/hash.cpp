@@ -0,0 +1,16 @@
+#include <botan/hash.h>
+#include <vector>
+
+#include "params.hpp"
+
+std::vector<uint8_t> digest(const std::vector<uint8_t>& data) {
+    auto hash = Botan::HashFunction::create_or_throw(crypto::kHashName);
+    hash->update(data);
+    return hash->final_stdvec();
+}
+
+std::vector<uint8_t> legacy_digest(const std::vector<uint8_t>& data) {
+    auto md5 = Botan::HashFunction::create("MD5");
+    md5->update(data);
+    return md5->final_stdvec();
+}
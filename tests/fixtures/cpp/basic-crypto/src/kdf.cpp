@@ -0,0 +1,21 @@
+#include <botan/pwdhash.h>
+#include <openssl/evp.h>
+
+#include "params.hpp"
+
+namespace {
+const char kSalt[] = "0123456789abcdef";
+}
+
+void derive_botan(const std::string& password, uint8_t* out) {
+    auto family = Botan::PasswordHashFamily::create("PBKDF2(SHA-256)");
+    auto pbkdf = family->from_params(crypto::kIterations);
+    pbkdf->derive_key(out, crypto::kKeyLength, password.data(), password.size(),
+                      reinterpret_cast<const uint8_t*>(kSalt), sizeof(kSalt));
+}
+
+int derive_openssl(const char* password, unsigned char* out) {
+    return ::PKCS5_PBKDF2_HMAC(password, -1,
+                               reinterpret_cast<const unsigned char*>(kSalt), sizeof(kSalt) - 1,
+                               crypto::kIterations, EVP_sha256(), crypto::kKeyLength, out);
+}
@@ -0,0 +1,11 @@
+#pragma once
+
+#include <cstddef>
+
+namespace crypto {
+
+constexpr std::size_t kKeyLength = 32;
+constexpr int kIterations = 600000;
+inline const char* const kHashName = "SHA-256";
+
+}  // namespace crypto
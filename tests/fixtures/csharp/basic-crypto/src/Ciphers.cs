@@ -0,0 +1,25 @@
+using System.Security.Cryptography;
+using Hash = System.Security.Cryptography.SHA256;
+
+namespace Example.Crypto
+{
+    public class Ciphers
+    {
+        public Aes NewAes()
+        {
+            var aes = Aes.Create();
+            aes.KeySize = 256;
+            return aes;
+        }
+
+        public byte[] Digest(byte[] data)
+        {
+            return Hash.HashData(data);
+        }
+
+        public RSA NewRsa()
+        {
+            return System.Security.Cryptography.RSA.Create(Params.KeySize);
+        }
+    }
+}
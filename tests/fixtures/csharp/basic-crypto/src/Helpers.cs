@@ -0,0 +1,17 @@
+using System;
+using System.Text;
+
+namespace Example.Crypto;
+
+public static class Helpers
+{
+    public static string Encode(byte[] data)
+    {
+        return Convert.ToBase64String(data);
+    }
+
+    public static byte[] Bytes(string text)
+    {
+        return Encoding.UTF8.GetBytes(text);
+    }
+}
@@ -0,0 +1,19 @@
+using System.Security.Cryptography;
+
+namespace Example.Crypto;
+
+public class KeyDerivation
+{
+    private const int KeyLength = 32;
+
+    public byte[] Derive(string password, byte[] salt)
+    {
+        using var kdf = new Rfc2898DeriveBytes(password, salt, Params.Iterations, HashAlgorithmName.SHA256);
+        return kdf.GetBytes(KeyLength);
+    }
+
+    public byte[] DeriveStatic(byte[] password, byte[] salt)
+    {
+        return Rfc2898DeriveBytes.Pbkdf2(password, salt, iterations: 100000, HashAlgorithmName.SHA512, outputLength: KeyLength);
+    }
+}
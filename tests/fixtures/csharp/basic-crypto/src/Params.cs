@@ -0,0 +1,7 @@
+namespace Example.Crypto;
+
+public static class Params
+{
+    public const int Iterations = 600000;
+    public const int KeySize = 2048;
+}
@@ -0,0 +1,20 @@
+package com.example.crypto;
+
+import javax.crypto.Cipher;
+import javax.crypto.SecretKey;
+
+public class CipherUtil {
+    private static final String TRANSFORMATION = "AES/GCM/NoPadding";
+
+    public byte[] encryptGcm(SecretKey key, byte[] plaintext) throws Exception {
+        Cipher cipher = Cipher.getInstance(TRANSFORMATION);
+        cipher.init(Cipher.ENCRYPT_MODE, key);
+        return cipher.doFinal(plaintext);
+    }
+
+    public byte[] encryptEcb(SecretKey key, byte[] plaintext) throws Exception {
+        Cipher cipher = Cipher.getInstance("AES/ECB/PKCS5Padding");
+        cipher.init(Cipher.ENCRYPT_MODE, key);
+        return cipher.doFinal(plaintext);
+    }
+}
@@ -0,0 +1,14 @@
+package com.example.crypto;
+
+import java.security.*;
+
+public class HashUtil {
+    public byte[] sha256(byte[] data) throws NoSuchAlgorithmException {
+        return MessageDigest.getInstance("SHA-256").digest(data);
+    }
+
+    public byte[] md5(byte[] data) throws NoSuchAlgorithmException {
+        MessageDigest digest = MessageDigest.getInstance("MD5");
+        return digest.digest(data);
+    }
+}
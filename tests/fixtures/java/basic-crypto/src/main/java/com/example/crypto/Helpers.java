@@ -0,0 +1,9 @@
+package com.example.crypto;
+
+import java.util.Base64;
+
+public class Helpers {
+    public static String encode(byte[] data) {
+        return Base64.getEncoder().encodeToString(data);
+    }
+}
@@ -0,0 +1,15 @@
+package com.example.crypto;
+
+import javax.crypto.SecretKeyFactory;
+import javax.crypto.spec.PBEKeySpec;
+
+public class KeyDerivation {
+    private static final int ITERATIONS = 600000;
+    private static final int KEY_LENGTH = 256;
+
+    public byte[] derive(char[] password, byte[] salt) throws Exception {
+        SecretKeyFactory factory = SecretKeyFactory.getInstance("PBKDF2WithHmacSHA256");
+        PBEKeySpec spec = new PBEKeySpec(password, salt, ITERATIONS, KEY_LENGTH);
+        return factory.generateSecret(spec).getEncoded();
+    }
+}
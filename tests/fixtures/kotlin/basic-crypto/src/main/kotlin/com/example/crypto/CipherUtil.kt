@@ -0,0 +1,20 @@
+package com.example.crypto
+
+import javax.crypto.Cipher
+import javax.crypto.SecretKey
+
+private const val TRANSFORMATION = "AES/GCM/NoPadding"
+
+class CipherUtil {
+    fun encryptGcm(key: SecretKey, plaintext: ByteArray): ByteArray {
+        val cipher = Cipher.getInstance(TRANSFORMATION)
+        cipher.init(Cipher.ENCRYPT_MODE, key)
+        return cipher.doFinal(plaintext)
+    }
+
+    fun encryptEcb(key: SecretKey, plaintext: ByteArray): ByteArray {
+        val cipher = Cipher.getInstance("AES/ECB/PKCS5Padding")
+        cipher.init(Cipher.ENCRYPT_MODE, key)
+        return cipher.doFinal(plaintext)
+    }
+}
@@ -0,0 +1,12 @@
+package com.example.crypto
+
+import java.security.*
+
+object HashUtil {
+    fun sha256(data: ByteArray): ByteArray = MessageDigest.getInstance("SHA-256").digest(data)
+
+    fun md5(data: ByteArray): ByteArray {
+        val digest = MessageDigest.getInstance("MD5")
+        return digest.digest(data)
+    }
+}
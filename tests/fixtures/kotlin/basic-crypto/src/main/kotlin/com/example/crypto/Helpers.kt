@@ -0,0 +1,7 @@
+package com.example.crypto
+
+import java.util.Base64
+
+object Helpers {
+    fun encode(data: ByteArray): String = Base64.getEncoder().encodeToString(data)
+}
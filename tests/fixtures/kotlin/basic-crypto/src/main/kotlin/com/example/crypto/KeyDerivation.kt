@@ -0,0 +1,18 @@
+package com.example.crypto
+
+import javax.crypto.SecretKeyFactory
+import javax.crypto.spec.PBEKeySpec
+
+object Params {
+    const val ALGORITHM = "PBKDF2WithHmacSHA256"
+    const val ITERATIONS = 600_000
+    const val KEY_LENGTH = 256
+}
+
+class KeyDerivation {
+    fun derive(password: CharArray, salt: ByteArray): ByteArray {
+        val factory = SecretKeyFactory.getInstance(Params.ALGORITHM)
+        val spec = PBEKeySpec(password, salt, Params.ITERATIONS, Params.KEY_LENGTH)
+        return factory.generateSecret(spec).encoded
+    }
+}
@@ -0,0 +1,38 @@
+<?php
+
+namespace Example;
+
+use phpseclib3\Crypt\RSA;
+
+const LEGACY_CIPHER = 'des-ede3-cbc';
+
+class Ciphers
+{
+    const CIPHER = 'aes-256-gcm';
+
+    public function encrypt(string $data, string $key): string
+    {
+        $iv = random_bytes(12);
+        return openssl_encrypt($data, self::CIPHER, $key, OPENSSL_RAW_DATA, $iv, $tag);
+    }
+
+    public function legacyEncrypt(string $data, string $key): string
+    {
+        return \openssl_encrypt($data, LEGACY_CIPHER, $key);
+    }
+
+    public function seal(string $message, string $nonce, string $key): string
+    {
+        return sodium_crypto_secretbox($message, $nonce, $key);
+    }
+
+    public function fingerprint(string $data): string
+    {
+        return hash('sha256', $data);
+    }
+
+    public function keypair()
+    {
+        return RSA::createKey(Params::KEY_BITS);
+    }
+}
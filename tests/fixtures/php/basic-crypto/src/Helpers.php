@@ -0,0 +1,16 @@
+<?php
+
+namespace Example;
+
+class Helpers
+{
+    public function slug(string $title): string
+    {
+        return strtolower(str_replace(' ', '-', $title));
+    }
+
+    public function encode(array $data): string
+    {
+        return json_encode($data);
+    }
+}
@@ -0,0 +1,24 @@
+<?php
+
+namespace Example;
+
+define('KEY_LENGTH', 32);
+
+class KeyDerivation
+{
+    public function derive(string $password, string $salt): string
+    {
+        return hash_pbkdf2('sha256', $password, $salt, Params::ITERATIONS, KEY_LENGTH, true);
+    }
+
+    public function store(string $password): string
+    {
+        $options = ['cost' => 12];
+        return password_hash($password, PASSWORD_BCRYPT, $options);
+    }
+
+    public function storeArgon(string $password): string
+    {
+        return password_hash(password: $password, algo: PASSWORD_ARGON2ID);
+    }
+}
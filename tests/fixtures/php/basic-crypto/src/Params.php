@@ -0,0 +1,9 @@
+<?php
+
+namespace Example;
+
+class Params
+{
+    const ITERATIONS = 600000;
+    const KEY_BITS = 2048;
+}
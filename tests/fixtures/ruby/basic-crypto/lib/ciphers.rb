@@ -0,0 +1,32 @@
+require "openssl"
+require "digest"
+
+module Example
+  class Ciphers
+    CIPHER = "aes-256-gcm"
+
+    def encrypt(data, key)
+      cipher = OpenSSL::Cipher.new(CIPHER)
+      cipher.encrypt
+      cipher.key = key
+      cipher.update(data) + cipher.final
+    end
+
+    def legacy_cipher
+      OpenSSL::Cipher.new("des-ede3-cbc")
+    end
+
+    def fingerprint(data)
+      Digest::SHA256.hexdigest(data)
+    end
+
+    def keypair
+      OpenSSL::PKey::RSA.new(Params::KEY_SIZE)
+    end
+
+    def ec_key
+      curve = :prime256v1
+      OpenSSL::PKey.generate_key("EC", ec_paramgen_curve: curve)
+    end
+  end
+end
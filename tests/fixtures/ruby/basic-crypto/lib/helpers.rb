@@ -0,0 +1,11 @@
+module Example
+  module Helpers
+    def self.format_key(key)
+      key.unpack1("H*")
+    end
+
+    def self.cipher_name(name)
+      Cipher.new(name).to_s
+    end
+  end
+end
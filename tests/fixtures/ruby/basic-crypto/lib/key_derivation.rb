@@ -0,0 +1,17 @@
+require "openssl"
+
+module Example
+  class KeyDerivation
+    KEY_LENGTH = 32
+
+    def derive(password, salt)
+      OpenSSL::KDF.pbkdf2_hmac(
+        password,
+        salt: salt,
+        iterations: Params::ITERATIONS,
+        length: KEY_LENGTH,
+        hash: "sha256"
+      )
+    end
+  end
+end
@@ -0,0 +1,6 @@
+module Example
+  module Params
+    ITERATIONS = 600_000
+    KEY_SIZE = 2048
+  end
+end
@@ -0,0 +1,20 @@
+package com.example.crypto
+
+import javax.crypto.Cipher
+import javax.crypto.SecretKey
+
+val Transformation = "AES/GCM/NoPadding"
+
+class CipherUtil {
+  def encryptGcm(key: SecretKey, plaintext: Array[Byte]): Array[Byte] = {
+    val cipher = Cipher.getInstance(Transformation)
+    cipher.init(Cipher.ENCRYPT_MODE, key)
+    cipher.doFinal(plaintext)
+  }
+
+  def encryptEcb(key: SecretKey, plaintext: Array[Byte]): Array[Byte] = {
+    val cipher = Cipher getInstance "AES/ECB/PKCS5Padding"
+    cipher.init(Cipher.ENCRYPT_MODE, key)
+    cipher.doFinal(plaintext)
+  }
+}
@@ -0,0 +1,12 @@
+package com.example.crypto
+
+import java.security._
+
+object HashUtil {
+  def sha256(data: Array[Byte]): Array[Byte] = MessageDigest.getInstance("SHA-256").digest(data)
+
+  def md5(data: Array[Byte]): Array[Byte] = {
+    val digest = MessageDigest.getInstance("MD5")
+    digest.digest(data)
+  }
+}
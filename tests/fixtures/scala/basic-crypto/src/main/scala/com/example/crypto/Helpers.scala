@@ -0,0 +1,9 @@
+package com.example.crypto
+
+import java.util.Base64
+
+object Helpers {
+  def encode(data: Array[Byte]): String = Base64.getEncoder.encodeToString(data)
+
+  def total(values: Seq[Int]): Int = values reduce (_ + _)
+}
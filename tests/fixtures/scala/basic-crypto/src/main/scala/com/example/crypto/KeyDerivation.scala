@@ -0,0 +1,18 @@
+package com.example.crypto
+
+import javax.crypto.SecretKeyFactory
+import javax.crypto.spec.PBEKeySpec
+
+object Params {
+  val Algorithm = "PBKDF2WithHmacSHA256"
+  val Iterations = 600_000
+  val KeyLength = 256
+}
+
+class KeyDerivation {
+  def derive(password: Array[Char], salt: Array[Byte]): Array[Byte] = {
+    val factory = SecretKeyFactory.getInstance(Params.Algorithm)
+    val spec = new PBEKeySpec(password, salt, Params.Iterations, Params.KeyLength)
+    factory.generateSecret(spec).getEncoded
+  }
+}
@@ -0,0 +1,11 @@
+package com.example.crypto
+
+import javax.crypto.{KeyGenerator, Mac => HMac}
+
+object MacUtil {
+  def tag(data: Array[Byte]): Array[Byte] = {
+    val mac = HMac getInstance "HmacSHA256"
+    mac.init(KeyGenerator.getInstance("HmacSHA256").generateKey())
+    mac.doFinal(data)
+  }
+}
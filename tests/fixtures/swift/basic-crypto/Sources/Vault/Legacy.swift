@@ -0,0 +1,36 @@
+import CommonCrypto
+import Foundation
+
+let pbkdfRounds: UInt32 = 100_000
+let derivedKeyLength = Params.keySize / 8
+
+enum Legacy {
+    static func encrypt(_ data: Data, key: Data, iv: Data) -> Data {
+        var out = Data(count: data.count + kCCBlockSizeAES128)
+        var moved = 0
+        let status = CCCrypt(
+            CCOperation(kCCEncrypt),
+            CCAlgorithm(kCCAlgorithmAES),
+            CCOptions(kCCOptionPKCS7Padding),
+            key.bytes, kCCKeySizeAES256,
+            iv.bytes,
+            data.bytes, data.count,
+            &out, out.count,
+            &moved
+        )
+        return status == kCCSuccess ? out : Data()
+    }
+
+    static func deriveKey(password: String, salt: [UInt8]) -> [UInt8] {
+        var derived = [UInt8](repeating: 0, count: derivedKeyLength)
+        CCKeyDerivationPBKDF(
+            CCPBKDFAlgorithm(kCCPBKDF2),
+            password, password.utf8.count,
+            salt, salt.count,
+            CCPseudoRandomAlgorithm(kCCPRFHmacAlgSHA256),
+            pbkdfRounds,
+            &derived, derivedKeyLength
+        )
+        return derived
+    }
+}
@@ -0,0 +1,39 @@
+import CryptoKit
+import Foundation
+
+struct Params {
+    static let keySize = 256
+}
+
+let legacyDigest = true
+
+final class Sealer {
+    let key = SymmetricKey(size: .bits256)
+
+    func digest(_ data: Data) -> SHA256.Digest {
+        return SHA256.hash(data: data)
+    }
+
+    func seal(_ plaintext: Data) throws -> Data? {
+        let box = try AES.GCM.seal(plaintext, using: key, nonce: nil)
+        return box.combined
+    }
+
+    func open(_ combined: Data) throws -> Data {
+        let box = try AES.GCM.SealedBox(combined: combined)
+        return try AES.GCM.open(box, using: key)
+    }
+
+    func sign() -> P256.Signing.PrivateKey {
+        return P256.Signing.PrivateKey()
+    }
+
+    func fingerprint(_ data: Data) -> String {
+        let digest = Insecure.MD5.hash(data: data)
+        return digest.map { String(format: "%02x", $0) }.joined()
+    }
+
+    func wrappingKey() -> SymmetricKey {
+        return SymmetricKey(size: SymmetricKeySize(bitCount: Params.keySize))
+    }
+}
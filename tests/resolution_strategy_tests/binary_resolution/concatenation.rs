@@ -0,0 +1,141 @@
+//! String concatenation tests
+//!
+//! Algorithm names are often assembled from parts, e.g. `"aes-" + bits + "-gcm"`.
+//! `+` joins resolved strings in every language, and a string with a number
+//! only where the language does (Java, JavaScript).
+
+use super::test_utils::{
+    get_arg_expression, get_first_arg_strings, is_arg_unresolved, scan_go, scan_java,
+    scan_javascript, scan_python,
+};
+
+fn strings(values: &[&str]) -> Option<Vec<String>> {
+    Some(values.iter().map(|v| v.to_string()).collect())
+}
+
+#[test]
+fn test_go_literals() {
+    let result = scan_go(
+        r#"
+package main
+
+func main() {
+    aes.NewCipher("aes-" + "256" + "-gcm")
+}
+"#,
+    );
+    assert_eq!(get_first_arg_strings(&result, 0), strings(&["aes-256-gcm"]));
+}
+
+#[test]
+fn test_go_constant_parts() {
+    let result = scan_go(
+        r#"
+package main
+
+const prefix = "aes-"
+const bits = "128"
+
+func main() {
+    aes.NewCipher(prefix + bits + "-gcm")
+}
+"#,
+    );
+    assert_eq!(get_first_arg_strings(&result, 0), strings(&["aes-128-gcm"]));
+}
+
+#[test]
+fn test_python_variable_parts() {
+    let result = scan_python(
+        r#"
+mode = "gcm"
+cipher.new("aes-256-" + mode)
+"#,
+    );
+    assert_eq!(get_first_arg_strings(&result, 0), strings(&["aes-256-gcm"]));
+}
+
+#[test]
+fn test_python_string_and_number_not_joined() {
+    // TypeError in Python
+    let result = scan_python(
+        r#"
+cipher.new("aes-" + 256)
+"#,
+    );
+    assert!(is_arg_unresolved(&result, 0));
+    assert_eq!(
+        get_arg_expression(&result, 0).as_deref(),
+        Some(r#""aes-" + 256"#)
+    );
+}
+
+#[test]
+fn test_javascript_string_and_number() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+const bits = 256;
+crypto.createCipheriv('aes-' + bits + '-gcm', key, iv);
+"#,
+    );
+    assert_eq!(get_first_arg_strings(&result, 0), strings(&["aes-256-gcm"]));
+}
+
+#[test]
+fn test_javascript_each_branch_value() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+crypto.createCipheriv('aes-' + (fips ? 256 : 128) + '-gcm', key, iv);
+"#,
+    );
+    assert_eq!(
+        get_first_arg_strings(&result, 0),
+        strings(&["aes-128-gcm", "aes-256-gcm"])
+    );
+}
+
+#[test]
+fn test_javascript_unresolved_part() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+crypto.createCipheriv('aes-' + process.env.BITS + '-gcm', key, iv);
+"#,
+    );
+    assert!(is_arg_unresolved(&result, 0));
+}
+
+#[test]
+fn test_java_transformation() {
+    let result = scan_java(
+        r#"
+class Crypto {
+    static final String MODE = "GCM";
+
+    void encrypt() throws Exception {
+        Cipher c = Cipher.getInstance("AES/" + MODE + "/NoPadding");
+    }
+}
+"#,
+    );
+    assert_eq!(
+        get_first_arg_strings(&result, 0),
+        strings(&["AES/GCM/NoPadding"])
+    );
+}
+
+#[test]
+fn test_java_string_and_number() {
+    let result = scan_java(
+        r#"
+class Crypto {
+    void digest() throws Exception {
+        MessageDigest.getInstance("SHA-" + 256);
+    }
+}
+"#,
+    );
+    assert_eq!(get_first_arg_strings(&result, 0), strings(&["SHA-256"]));
+}
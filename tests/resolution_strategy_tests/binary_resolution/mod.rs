@@ -11,9 +11,11 @@
 //! - `rust_lang` - Rust binary tests
 //! - `javascript` - JavaScript binary tests
 //! - `cross_language` - Cross-language consistency tests
+//! - `concatenation` - String concatenation with `+` in Go, Python, JavaScript and Java
 
 pub mod test_utils;
 
+pub mod concatenation;
 pub mod cross_language;
 pub mod go;
 pub mod javascript;
@@ -36,6 +36,14 @@ pub fn parse_javascript(source: &str) -> tree_sitter::Tree {
     parser.parse(source, None).unwrap()
 }
 
+pub fn parse_java(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_java::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
 fn create_scanner() -> Scanner {
     Scanner::new().with_patterns(fixtures::test_patterns())
 }
@@ -60,6 +68,11 @@ pub fn scan_javascript(source: &str) -> ScanResult {
     create_scanner().scan_tree(&tree, source.as_bytes(), "test.js", "javascript")
 }
 
+pub fn scan_java(source: &str) -> ScanResult {
+    let tree = parse_java(source);
+    create_scanner().scan_tree(&tree, source.as_bytes(), "Test.java", "java")
+}
+
 pub fn get_first_arg_int(result: &ScanResult, arg_idx: usize) -> Option<i64> {
     result
         .calls
@@ -69,6 +82,15 @@ pub fn get_first_arg_int(result: &ScanResult, arg_idx: usize) -> Option<i64> {
         .and_then(|a| a.int_values.first().copied())
 }
 
+pub fn get_first_arg_strings(result: &ScanResult, arg_idx: usize) -> Option<Vec<String>> {
+    result
+        .calls
+        .first()
+        .and_then(|c| c.arguments.get(arg_idx))
+        .filter(|a| a.is_resolved)
+        .map(|a| a.string_values.clone())
+}
+
 pub fn is_arg_resolved(result: &ScanResult, arg_idx: usize) -> bool {
     result
         .calls
@@ -0,0 +1,113 @@
+//! A disabled call strategy is never reached through nested resolution:
+//! not through an identifier, binary, conditional, index or selector
+//! expression, whose operands resolve through the configured chain.
+
+use argflow::engine::EvidenceKind;
+use argflow::scanner::{ScanResult, Scanner};
+use argflow::Resolver;
+
+use super::test_utils::{
+    get_arg_source, get_first_arg_int, parse_go, parse_javascript, parse_python, scan_go,
+    scan_javascript, scan_python,
+};
+use crate::fixtures;
+
+fn scanner_without_call() -> Scanner {
+    Scanner::with_resolver(Resolver::builder().disable_strategy("call").build())
+        .with_patterns(fixtures::test_patterns())
+}
+
+fn scan_go_without_call(source: &str) -> ScanResult {
+    let tree = parse_go(source);
+    scanner_without_call().scan_tree(&tree, source.as_bytes(), "test.go", "go")
+}
+
+fn scan_python_without_call(source: &str) -> ScanResult {
+    let tree = parse_python(source);
+    scanner_without_call().scan_tree(&tree, source.as_bytes(), "test.py", "python")
+}
+
+fn scan_javascript_without_call(source: &str) -> ScanResult {
+    let tree = parse_javascript(source);
+    scanner_without_call().scan_tree(&tree, source.as_bytes(), "test.js", "javascript")
+}
+
+#[test]
+fn test_disabled_call_through_identifier() {
+    let source = r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func iterations() int { return 4096 }
+func main() {
+    n := iterations()
+    pbkdf2.Key(pw, salt, n, 32, nil)
+}
+"#;
+    assert_eq!(get_first_arg_int(&scan_go(source), 2), Some(4096));
+
+    let result = scan_go_without_call(source);
+    let arg = &result.calls[0].arguments[2];
+    assert!(!arg.is_resolved);
+    assert!(arg.evidence.iter().all(|e| e.kind != EvidenceKind::Return));
+}
+
+#[test]
+fn test_disabled_call_through_binary() {
+    let source = r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func iterations() int { return 4096 }
+func main() {
+    pbkdf2.Key(pw, salt, iterations() * 2, 32, nil)
+}
+"#;
+    assert_eq!(get_first_arg_int(&scan_go(source), 2), Some(8192));
+    assert_eq!(get_first_arg_int(&scan_go_without_call(source), 2), None);
+}
+
+#[test]
+fn test_disabled_call_through_conditional() {
+    let source = r#"
+import hashlib
+
+def iterations():
+    return 4096
+
+def derive():
+    hashlib.pbkdf2_hmac('sha256', p, s, iterations() if fips else iterations())
+"#;
+    assert_eq!(get_first_arg_int(&scan_python(source), 3), Some(4096));
+    assert_eq!(
+        get_first_arg_int(&scan_python_without_call(source), 3),
+        None
+    );
+}
+
+#[test]
+fn test_disabled_call_through_index() {
+    let source = r#"
+import hashlib
+import yaml
+
+def derive():
+    hashlib.pbkdf2_hmac('sha256', p, s, yaml.safe_load(f)["rounds"])
+"#;
+    let config = Some("config_value".to_string());
+    assert_eq!(get_arg_source(&scan_python(source), 3), config);
+    assert_ne!(get_arg_source(&scan_python_without_call(source), 3), config);
+}
+
+#[test]
+fn test_disabled_call_through_selector() {
+    let source = r#"
+function derive() {
+    crypto.pbkdf2Sync(p, s, yaml.load(f).rounds, 32, 'sha256');
+}
+"#;
+    let config = Some("config_value".to_string());
+    assert_eq!(get_arg_source(&scan_javascript(source), 2), config);
+    assert_ne!(
+        get_arg_source(&scan_javascript_without_call(source), 2),
+        config
+    );
+}
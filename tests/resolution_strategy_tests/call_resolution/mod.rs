@@ -16,10 +16,12 @@
 //! - `rust_lang` - Rust call resolution tests
 //! - `javascript` - JavaScript call resolution tests
 //! - `cross_language` - Cross-language consistency tests
+//! - `disabled` - Nested resolution without the call strategy
 
 pub mod test_utils;
 
 pub mod cross_language;
+pub mod disabled;
 pub mod go;
 pub mod javascript;
 pub mod python;
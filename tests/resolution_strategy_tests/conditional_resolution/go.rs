@@ -0,0 +1,107 @@
+//! Go conditional resolution tests
+
+use super::test_utils::{get_arg_ints, get_arg_source, scan_go};
+
+#[test]
+fn test_if_else_assignments() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {
+    var iterations int
+    if fips {
+        iterations = 600000
+    } else {
+        iterations = 310000
+    }
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 2), Some(vec![310000, 600000]));
+}
+
+#[test]
+fn test_else_if_chain() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {
+    keyLen := 0
+    if strong {
+        keyLen = 64
+    } else if legacy {
+        keyLen = 16
+    } else {
+        keyLen = 32
+    }
+    pbkdf2.Key(p, s, 4096, keyLen, h)
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 3), Some(vec![16, 32, 64]));
+}
+
+#[test]
+fn test_branch_values_through_constants() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+const FIPSIterations = 600000
+func main() {
+    var iterations int
+    if fips {
+        iterations = FIPSIterations
+    } else {
+        iterations = 310000 * 2
+    }
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 2), Some(vec![600000, 620000]));
+}
+
+#[test]
+fn test_branch_from_parameter_is_mixed() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func derive(custom int) {
+    var iterations int
+    if custom > 0 {
+        iterations = custom
+    } else {
+        iterations = 600000
+    }
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(
+        get_arg_source(&result, 2).as_deref(),
+        Some("mixed_resolution")
+    );
+}
+
+#[test]
+fn test_if_without_else_keeps_last_assignment() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {
+    iterations := 310000
+    if fips {
+        log("fips")
+    }
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 2), Some(vec![310000]));
+}
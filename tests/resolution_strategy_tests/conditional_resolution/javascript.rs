@@ -0,0 +1,40 @@
+//! JavaScript conditional resolution tests
+
+use super::test_utils::{get_arg_ints, get_arg_strings, scan_javascript};
+
+#[test]
+fn test_ternary_argument() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+crypto.pbkdf2Sync(password, salt, fips ? 600000 : 310000, 32, 'sha256');
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 2), Some(vec![310000, 600000]));
+}
+
+#[test]
+fn test_ternary_through_constant() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+const digest = strong ? 'sha512' : 'sha256';
+crypto.pbkdf2Sync(password, salt, 600000, 32, digest);
+"#,
+    );
+    assert_eq!(
+        get_arg_strings(&result, 4),
+        Some(vec!["sha256".to_string(), "sha512".to_string()])
+    );
+}
+
+#[test]
+fn test_nested_ternary() {
+    let result = scan_javascript(
+        r#"
+const crypto = require('crypto');
+crypto.pbkdf2Sync(password, salt, 600000, strong ? 64 : legacy ? 16 : 32, 'sha256');
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 3), Some(vec![16, 32, 64]));
+}
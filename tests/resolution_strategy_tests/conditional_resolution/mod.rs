@@ -0,0 +1,24 @@
+//! Exhaustive conditional resolution tests
+//!
+//! These tests prove that values chosen by a condition resolve to the values
+//! of all their branches.
+//!
+//! ## Coverage
+//! - Go variables assigned in every branch of an if/else chain
+//! - Python conditional expressions (a if cond else b)
+//! - JavaScript ternaries (cond ? a : b)
+//! - Rust if expressions
+//!
+//! ## Structure
+//! - `test_utils` - Shared test helpers
+//! - `go` - Go conditional tests
+//! - `python` - Python conditional tests
+//! - `rust_lang` - Rust conditional tests
+//! - `javascript` - JavaScript conditional tests
+
+pub mod test_utils;
+
+pub mod go;
+pub mod javascript;
+pub mod python;
+pub mod rust_lang;
@@ -0,0 +1,56 @@
+//! Python conditional resolution tests
+
+use super::test_utils::{get_arg_ints, get_arg_source, get_arg_strings, scan_python};
+
+#[test]
+fn test_conditional_expression_argument() {
+    let result = scan_python(
+        r#"
+import hashlib
+hashlib.pbkdf2_hmac("sha256", pw, salt, 600000 if fips else 310000)
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 3), Some(vec![310000, 600000]));
+}
+
+#[test]
+fn test_conditional_expression_through_variable() {
+    let result = scan_python(
+        r#"
+import hashlib
+def derive(pw, salt):
+    algorithm = "sha512" if strong else "sha256"
+    return hashlib.pbkdf2_hmac(algorithm, pw, salt, 600000)
+"#,
+    );
+    assert_eq!(
+        get_arg_strings(&result, 0),
+        Some(vec!["sha256".to_string(), "sha512".to_string()])
+    );
+}
+
+#[test]
+fn test_nested_conditional_expression() {
+    let result = scan_python(
+        r#"
+import hashlib
+hashlib.pbkdf2_hmac("sha256", pw, salt, 600000 if fips else 1000 if legacy else 310000)
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 3), Some(vec![1000, 310000, 600000]));
+}
+
+#[test]
+fn test_unresolved_branch_is_mixed() {
+    let result = scan_python(
+        r#"
+import hashlib
+def derive(pw, salt, rounds):
+    return hashlib.pbkdf2_hmac("sha256", pw, salt, rounds if rounds else 600000)
+"#,
+    );
+    assert_eq!(
+        get_arg_source(&result, 3).as_deref(),
+        Some("mixed_resolution")
+    );
+}
@@ -0,0 +1,55 @@
+//! Rust conditional resolution tests
+
+use super::test_utils::{get_arg_ints, scan_rust};
+
+#[test]
+fn test_if_expression_argument() {
+    let result = scan_rust(
+        r#"
+use ring::pbkdf2;
+fn main() {
+    pbkdf2::derive(algorithm, if fips { 600000 } else { 310000 }, salt, password, &mut out);
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 1), Some(vec![310000, 600000]));
+}
+
+#[test]
+fn test_if_expression_through_let() {
+    let result = scan_rust(
+        r#"
+use ring::pbkdf2;
+fn main() {
+    let iterations = if fips {
+        600000
+    } else if legacy {
+        1000
+    } else {
+        310000
+    };
+    pbkdf2::derive(algorithm, iterations, salt, password, &mut out);
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 1), Some(vec![1000, 310000, 600000]));
+}
+
+#[test]
+fn test_block_with_statements_uses_tail_expression() {
+    let result = scan_rust(
+        r#"
+use ring::pbkdf2;
+fn main() {
+    let iterations = if fips {
+        log("fips");
+        600000
+    } else {
+        310000
+    };
+    pbkdf2::derive(algorithm, iterations, salt, password, &mut out);
+}
+"#,
+    );
+    assert_eq!(get_arg_ints(&result, 1), Some(vec![310000, 600000]));
+}
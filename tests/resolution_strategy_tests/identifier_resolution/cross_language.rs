@@ -4,8 +4,8 @@
 //! all supported languages for common patterns.
 
 use super::test_utils::{
-    get_arg_source, get_first_arg_int, get_first_arg_string, is_arg_resolved, scan_go,
-    scan_javascript, scan_python, scan_rust,
+    get_arg_expression, get_arg_source, get_first_arg_int, get_first_arg_string, is_arg_resolved,
+    scan_go, scan_javascript, scan_python, scan_rust,
 };
 
 // =============================================================================
@@ -289,3 +289,266 @@ function hash() {
         "JavaScript string variable"
     );
 }
+
+// =============================================================================
+// Environment Variables
+// =============================================================================
+
+#[test]
+fn test_environment_variable_across_languages() {
+    let env = |result, arg| {
+        (
+            get_arg_source(&result, arg),
+            get_arg_expression(&result, arg),
+        )
+    };
+    let expected = (
+        Some("environment_variable".to_string()),
+        Some("PBKDF2_ITERS".to_string()),
+    );
+
+    // Go
+    let go_result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {
+    iterations := os.Getenv("PBKDF2_ITERS")
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(env(go_result, 2), expected, "Go os.Getenv");
+
+    // Python
+    let py_result = scan_python(
+        r#"
+import hashlib
+import os
+
+def derive():
+    iterations = os.environ["PBKDF2_ITERS"]
+    hashlib.pbkdf2_hmac('sha256', p, s, iterations)
+"#,
+    );
+    assert_eq!(env(py_result, 3), expected, "Python os.environ");
+
+    // Rust
+    let rs_result = scan_rust(
+        r#"
+fn derive() {
+    let iterations = std::env::var("PBKDF2_ITERS");
+    pbkdf2::derive(iterations);
+}
+"#,
+    );
+    assert_eq!(env(rs_result, 0), expected, "Rust std::env::var");
+
+    // JavaScript
+    let js_result = scan_javascript(
+        r#"
+function derive() {
+    const iterations = process.env.PBKDF2_ITERS;
+    crypto.pbkdf2Sync(p, s, iterations, 32, 'sha256');
+}
+"#,
+    );
+    assert_eq!(env(js_result, 2), expected, "JavaScript process.env");
+}
+
+#[test]
+fn test_command_line_is_external_input() {
+    let result = scan_python(
+        r#"
+import hashlib
+import sys
+
+def derive():
+    iterations = sys.argv[1]
+    hashlib.pbkdf2_hmac('sha256', p, s, iterations)
+"#,
+    );
+    assert_eq!(
+        get_arg_source(&result, 3),
+        Some("external_input".to_string())
+    );
+    assert_eq!(
+        get_arg_expression(&result, 3),
+        Some("sys.argv[1]".to_string())
+    );
+}
+
+// =============================================================================
+// Configuration Values
+// =============================================================================
+
+#[test]
+fn test_config_value_across_languages() {
+    let config = |result, arg| {
+        (
+            get_arg_source(&result, arg),
+            get_arg_expression(&result, arg),
+        )
+    };
+    let expected = (
+        Some("config_value".to_string()),
+        Some("crypto.iterations".to_string()),
+    );
+
+    // Go, a viper getter
+    let go_result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {
+    iterations := viper.GetInt("crypto.iterations")
+    pbkdf2.Key(p, s, iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(config(go_result, 2), expected, "Go viper.GetInt");
+
+    // Go, a struct decoded with json.Unmarshal
+    let go_result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+type Crypto struct {
+    Iterations int `json:"iterations"`
+}
+type Config struct {
+    Crypto Crypto `json:"crypto"`
+}
+func main() {
+    var cfg Config
+    json.Unmarshal(data, &cfg)
+    pbkdf2.Key(p, s, cfg.Crypto.Iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(config(go_result, 2), expected, "Go json.Unmarshal");
+
+    // Python
+    let py_result = scan_python(
+        r#"
+import hashlib
+import yaml
+
+def derive():
+    cfg = yaml.safe_load(open("config.yaml"))
+    hashlib.pbkdf2_hmac('sha256', p, s, cfg["crypto"]["iterations"])
+"#,
+    );
+    assert_eq!(config(py_result, 3), expected, "Python yaml.safe_load");
+
+    // JavaScript
+    let js_result = scan_javascript(
+        r#"
+function derive() {
+    const iterations = config.get("crypto.iterations");
+    crypto.pbkdf2Sync(p, s, iterations, 32, 'sha256');
+}
+"#,
+    );
+    assert_eq!(config(js_result, 2), expected, "JavaScript config.get");
+}
+
+#[test]
+fn test_config_key_of_viper_unmarshal_key() {
+    let result = scan_go(
+        r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+type Crypto struct {
+    Iterations int
+}
+func main() {
+    var c Crypto
+    viper.UnmarshalKey("crypto", &c)
+    pbkdf2.Key(p, s, c.Iterations, 32, h)
+}
+"#,
+    );
+    assert_eq!(get_arg_source(&result, 2), Some("config_value".to_string()));
+    assert_eq!(
+        get_arg_expression(&result, 2),
+        Some("crypto.Iterations".to_string())
+    );
+}
+
+#[test]
+fn test_environment_variable_through_conversions() {
+    let env = |result, arg| {
+        (
+            get_arg_source(&result, arg),
+            get_arg_expression(&result, arg),
+        )
+    };
+    let expected = (
+        Some("environment_variable".to_string()),
+        Some("PBKDF2_ITERS".to_string()),
+    );
+
+    // Go
+    for conversion in [
+        r#"strconv.Atoi(os.Getenv("PBKDF2_ITERS"))"#,
+        r#"strconv.ParseInt(os.Getenv("PBKDF2_ITERS"), 10, 64)"#,
+    ] {
+        let go_result = scan_go(&format!(
+            r#"
+package main
+import "golang.org/x/crypto/pbkdf2"
+func main() {{
+    iterations, _ := {conversion}
+    pbkdf2.Key(p, s, iterations, 32, h)
+}}
+"#
+        ));
+        assert_eq!(env(go_result, 2), expected, "Go {conversion}");
+    }
+
+    // Python
+    for conversion in ["int", "float"] {
+        let py_result = scan_python(&format!(
+            r#"
+import hashlib
+import os
+
+def derive():
+    iterations = {conversion}(os.getenv("PBKDF2_ITERS"))
+    hashlib.pbkdf2_hmac('sha256', p, s, iterations)
+"#
+        ));
+        assert_eq!(env(py_result, 3), expected, "Python {conversion}");
+    }
+
+    // JavaScript
+    for conversion in [
+        "parseInt(process.env.PBKDF2_ITERS, 10)",
+        "Number(process.env.PBKDF2_ITERS)",
+    ] {
+        let js_result = scan_javascript(&format!(
+            r#"
+function derive() {{
+    const iterations = {conversion};
+    crypto.pbkdf2Sync(p, s, iterations, 32, 'sha256');
+}}
+"#
+        ));
+        assert_eq!(env(js_result, 2), expected, "JavaScript {conversion}");
+    }
+}
+
+#[test]
+fn test_conversion_of_literal_resolves() {
+    let result = scan_python(
+        r#"
+import hashlib
+
+def derive():
+    iterations = int("600000")
+    hashlib.pbkdf2_hmac('sha256', p, s, iterations)
+"#,
+    );
+    assert_eq!(get_first_arg_int(&result, 3), Some(600000));
+}
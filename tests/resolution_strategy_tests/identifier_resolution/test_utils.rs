@@ -96,3 +96,12 @@ pub fn get_arg_source(result: &ScanResult, arg_idx: usize) -> Option<String> {
         .filter(|a| !a.source.is_empty())
         .map(|a| a.source.clone())
 }
+
+pub fn get_arg_expression(result: &ScanResult, arg_idx: usize) -> Option<String> {
+    result
+        .calls
+        .first()
+        .and_then(|c| c.arguments.get(arg_idx))
+        .filter(|a| !a.expression.is_empty())
+        .map(|a| a.expression.clone())
+}
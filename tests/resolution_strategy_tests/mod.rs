@@ -1,6 +1,7 @@
 pub mod binary_resolution;
 pub mod call_resolution;
 pub mod composite_resolution;
+pub mod conditional_resolution;
 pub mod identifier_resolution;
 pub mod index_resolution;
 pub mod literal_resolution;
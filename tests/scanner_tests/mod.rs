@@ -1,2 +1,12 @@
+pub mod scanner_c_test;
+pub mod scanner_cpp_test;
+pub mod scanner_csharp_test;
 pub mod scanner_go_test;
+pub mod scanner_java_test;
+pub mod scanner_javascript_test;
+pub mod scanner_kotlin_test;
+pub mod scanner_php_test;
 pub mod scanner_python_test;
+pub mod scanner_ruby_test;
+pub mod scanner_scala_test;
+pub mod scanner_swift_test;
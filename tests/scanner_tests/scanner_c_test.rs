@@ -0,0 +1,161 @@
+//! C-specific scanner e2e tests
+//!
+//! Tests OpenSSL detection through included headers and parameter
+//! resolution for C code, including `#define` constants.
+//! Fixtures: tests/fixtures/c/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_c(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_c::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn openssl_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("c", Some("openssl-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(openssl_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_c_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("c", Some(project))
+        .join("src")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_c(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), &full_path.to_string_lossy(), "c")
+}
+
+fn scan_c_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_c(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "inline.c", "c")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_c_basic_crypto_cipher() {
+    let result = scan_c_file("basic-crypto", "cipher.c");
+
+    // EVP_CIPHER_CTX_new is not mapped
+    assert_eq!(
+        functions(&result),
+        vec![
+            "RAND_bytes",
+            "EVP_EncryptInit_ex",
+            "EVP_aes_256_gcm",
+            "EVP_EncryptInit_ex",
+            "EVP_aes_128_ecb"
+        ]
+    );
+    for call in &result.calls {
+        let header = match call.function_name.as_str() {
+            "RAND_bytes" => "openssl/rand.h",
+            _ => "openssl/evp.h",
+        };
+        assert_eq!(call.import_path.as_deref(), Some(header));
+        assert_eq!(call.package, None);
+    }
+
+    let ecb = classify_call(&result.calls[4], &openssl_classifier());
+    assert_eq!(ecb.algorithm.as_deref(), Some("AES-128-ECB"));
+}
+
+#[test]
+fn test_c_basic_crypto_quoted_include() {
+    let result = scan_c_file("basic-crypto", "hash.c");
+
+    assert_eq!(functions(&result), vec!["SHA256", "MD5"]);
+    assert_eq!(
+        result.calls[0].import_path.as_deref(),
+        Some("openssl/sha.h")
+    );
+    assert_eq!(
+        result.calls[1].import_path.as_deref(),
+        Some("openssl/md5.h")
+    );
+}
+
+#[test]
+fn test_c_basic_crypto_kdf_parameters() {
+    let result = scan_c_file("basic-crypto", "kdf.c");
+
+    assert_eq!(functions(&result), vec!["PKCS5_PBKDF2_HMAC", "EVP_sha256"]);
+    let pbkdf2 = &result.calls[0];
+
+    // SALT is a #define in this file, SALT_LEN a static const
+    assert_eq!(pbkdf2.arguments[2].string_values, vec!["static-salt"]);
+    assert_eq!(pbkdf2.arguments[3].int_values, vec![11]);
+    // iterations = KDF_ITERATIONS, defined in params.h next to it
+    assert_eq!(pbkdf2.arguments[4].int_values, vec![600000]);
+    assert_eq!(pbkdf2.arguments[6].int_values, vec![32]);
+    assert!(!pbkdf2.arguments[0].is_resolved, "password is a parameter");
+
+    let classification = classify_call(pbkdf2, &openssl_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+}
+
+// =============================================================================
+// Inline tests for C-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_c_inline_unincluded_header_is_ignored() {
+    let result = scan_c_inline(
+        r#"
+#include "my_sha.h"
+
+void run(const unsigned char *d, unsigned char *out) {
+    SHA256(d, 3, out);
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
+
+#[test]
+fn test_c_inline_redefined_macro() {
+    let result = scan_c_inline(
+        r#"
+#include <openssl/rand.h>
+
+#define NONCE_LEN 12
+#undef NONCE_LEN
+#define NONCE_LEN 24UL
+
+void run(unsigned char *nonce) {
+    unsigned char buf[NONCE_LEN], *p = buf;
+    RAND_bytes(nonce, NONCE_LEN);
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 1);
+    assert_eq!(result.calls[0].arguments[1].int_values, vec![24]);
+}
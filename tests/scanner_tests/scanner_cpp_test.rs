@@ -0,0 +1,174 @@
+//! C++-specific scanner e2e tests
+//!
+//! Tests Botan detection through `::`-qualified calls, OpenSSL detection
+//! through included headers and parameter resolution through namespaced
+//! constants.
+//! Fixtures: tests/fixtures/cpp/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_cpp(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_cpp::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn botan_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("cpp", Some("botan-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(botan_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_cpp_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("cpp", Some(project))
+        .join("src")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_cpp(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "cpp",
+    )
+}
+
+fn scan_cpp_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_cpp(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "inline.cpp", "cpp")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_cpp_basic_crypto_qualified_calls() {
+    let result = scan_cpp_file("basic-crypto", "hash.cpp");
+
+    // hash->update and final_stdvec are not mapped
+    assert_eq!(
+        functions(&result),
+        vec!["HashFunction::create_or_throw", "HashFunction::create"]
+    );
+    for call in &result.calls {
+        assert_eq!(call.package.as_deref(), Some("Botan::HashFunction"));
+        assert_eq!(call.import_path.as_deref(), Some("botan/hash.h"));
+    }
+
+    // crypto::kHashName is declared in params.hpp next to it
+    assert_eq!(result.calls[0].arguments[0].string_values, vec!["SHA-256"]);
+    assert_eq!(result.calls[1].arguments[0].string_values, vec!["MD5"]);
+
+    let classification = classify_call(&result.calls[1], &botan_classifier());
+    assert_eq!(classification.finding_type, "hash");
+}
+
+#[test]
+fn test_cpp_basic_crypto_kdf_parameters() {
+    let result = scan_cpp_file("basic-crypto", "kdf.cpp");
+
+    assert_eq!(
+        functions(&result),
+        vec![
+            "PasswordHashFamily::create",
+            "PKCS5_PBKDF2_HMAC",
+            "EVP_sha256"
+        ]
+    );
+    assert_eq!(
+        result.calls[0].arguments[0].string_values,
+        vec!["PBKDF2(SHA-256)"]
+    );
+
+    // ::PKCS5_PBKDF2_HMAC is called through the global scope
+    let pbkdf2 = &result.calls[1];
+    assert_eq!(pbkdf2.package, None);
+    assert_eq!(pbkdf2.import_path.as_deref(), Some("openssl/evp.h"));
+    // kSalt is in an anonymous namespace, behind a reinterpret_cast
+    assert_eq!(pbkdf2.arguments[2].string_values, vec!["0123456789abcdef"]);
+    assert_eq!(pbkdf2.arguments[4].int_values, vec![600000]);
+    assert_eq!(pbkdf2.arguments[6].int_values, vec![32]);
+
+    let classification = classify_call(pbkdf2, &botan_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+}
+
+#[test]
+fn test_cpp_basic_crypto_member_function() {
+    let result = scan_cpp_file("basic-crypto", "cipher.cpp");
+
+    assert_eq!(
+        functions(&result),
+        vec!["EVP_EncryptInit_ex", "EVP_aes_256_gcm"]
+    );
+    assert!(
+        !result.calls[0].arguments[0].is_resolved,
+        "ctx_ is a member"
+    );
+}
+
+#[test]
+fn test_cpp_basic_crypto_no_false_positives() {
+    let result = scan_cpp_file("basic-crypto", "helpers.cpp");
+    assert_eq!(result.call_count(), 0);
+}
+
+// =============================================================================
+// Inline tests for C++-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_cpp_inline_using_namespace() {
+    let result = scan_cpp_inline(
+        r#"
+#include <botan/hash.h>
+
+using namespace Botan;
+
+void run() {
+    auto h = HashFunction::create(std::string("SHA-512"));
+}
+"#,
+    );
+
+    assert_eq!(functions(&result), vec!["HashFunction::create"]);
+    assert_eq!(result.calls[0].package.as_deref(), Some("HashFunction"));
+}
+
+#[test]
+fn test_cpp_inline_unincluded_header_is_ignored() {
+    let result = scan_cpp_inline(
+        r#"
+#include "hash.h"
+
+void run() {
+    Botan::HashFunction::create("SHA-256");
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
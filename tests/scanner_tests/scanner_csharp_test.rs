@@ -0,0 +1,181 @@
+//! C#-specific scanner e2e tests
+//!
+//! Tests System.Security.Cryptography detection through namespace and alias
+//! `using` directives, constructors and fully qualified calls, and
+//! parameter resolution through class constants.
+//! Fixtures: tests/fixtures/csharp/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_csharp(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_c_sharp::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn dotnet_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("csharp", Some("dotnet-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(dotnet_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_csharp_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("csharp", Some(project))
+        .join("src")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_csharp(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "csharp",
+    )
+}
+
+fn scan_csharp_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_csharp(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "Inline.cs", "csharp")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_csharp_basic_crypto_constructor() {
+    let result = scan_csharp_file("basic-crypto", "KeyDerivation.cs");
+
+    // kdf.GetBytes is not mapped
+    assert_eq!(functions(&result), vec!["Rfc2898DeriveBytes", "Pbkdf2"]);
+
+    let constructor = &result.calls[0];
+    assert_eq!(constructor.package, None);
+    assert_eq!(
+        constructor.import_path.as_deref(),
+        Some("System.Security.Cryptography")
+    );
+    // Params.Iterations is declared in Params.cs next to it
+    assert_eq!(constructor.arguments[2].int_values, vec![600000]);
+
+    let classification = classify_call(constructor, &dotnet_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+    assert_eq!(classification.operation, "keyderive");
+}
+
+#[test]
+fn test_csharp_basic_crypto_named_arguments() {
+    let result = scan_csharp_file("basic-crypto", "KeyDerivation.cs");
+
+    let pbkdf2 = &result.calls[1];
+    assert_eq!(
+        pbkdf2.import_path.as_deref(),
+        Some("System.Security.Cryptography.Rfc2898DeriveBytes")
+    );
+    assert_eq!(pbkdf2.keyword(2), Some("iterations"));
+    assert_eq!(pbkdf2.arguments[2].int_values, vec![100000]);
+    assert_eq!(pbkdf2.keyword(4), Some("outputLength"));
+    assert_eq!(pbkdf2.arguments[4].int_values, vec![32]);
+}
+
+#[test]
+fn test_csharp_basic_crypto_static_factories() {
+    let result = scan_csharp_file("basic-crypto", "Ciphers.cs");
+
+    // aes.KeySize is an assignment, not a call
+    assert_eq!(functions(&result), vec!["Create", "HashData", "Create"]);
+    let import_paths: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| c.import_path.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        import_paths,
+        vec![
+            "System.Security.Cryptography.Aes",
+            "System.Security.Cryptography.SHA256",
+            "System.Security.Cryptography.RSA"
+        ]
+    );
+
+    // System.Security.Cryptography.RSA.Create(Params.KeySize)
+    assert_eq!(result.calls[2].arguments[0].int_values, vec![2048]);
+}
+
+#[test]
+fn test_csharp_basic_crypto_no_false_positives() {
+    let result = scan_csharp_file("basic-crypto", "Helpers.cs");
+    assert_eq!(result.call_count(), 0);
+}
+
+// =============================================================================
+// Inline tests for C#-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_csharp_inline_generic_and_verbatim() {
+    let result = scan_csharp_inline(
+        r#"
+using System.Security.Cryptography;
+
+class Keys
+{
+    private static readonly string Label = @"C:\keys\""main""";
+
+    void Run()
+    {
+        var sha = SHA256.Create();
+        var rsa = RSA.Create<RSA>(Label);
+    }
+}
+"#,
+    );
+
+    assert_eq!(functions(&result), vec!["Create", "Create"]);
+    assert_eq!(
+        result.calls[1].arguments[0].string_values,
+        vec![r#"C:\keys\"main""#]
+    );
+}
+
+#[test]
+fn test_csharp_inline_unimported_namespace_is_ignored() {
+    let result = scan_csharp_inline(
+        r#"
+using Example.Security;
+
+class Local
+{
+    void Run()
+    {
+        Aes.Create();
+        new Rfc2898DeriveBytes("pw", 16);
+    }
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
@@ -3,6 +3,9 @@
 //! Tests crypto detection and parameter resolution for Go code.
 //! Fixtures: tests/fixtures/go/
 
+use std::sync::Arc;
+
+use argflow::engine::ProjectIndex;
 use argflow::scanner::Scanner;
 
 use crate::fixtures::{get_test_fixture_path, test_patterns};
@@ -172,6 +175,40 @@ fn test_go_cross_file_constants_kdf() {
     // Note: Cross-file constant resolution requires Identifier strategy
 }
 
+#[test]
+fn test_go_cross_file_constants_from_project_index() {
+    let root = get_test_fixture_path("go", None).join("cross-file-constants");
+    let index = Arc::new(ProjectIndex::new());
+    let files = [root.join("config/constants.go"), root.join("crypto/kdf.go")];
+    assert_eq!(index.index_files(&root, &files), 2);
+
+    let path = root.join("crypto/kdf.go");
+    let source = std::fs::read_to_string(&path).unwrap();
+    let tree = parse_go(&source);
+    let scanner = create_scanner().with_project_index(index);
+    let result = scanner.scan_tree(&tree, source.as_bytes(), &path.to_string_lossy(), "go");
+
+    let arguments: Vec<_> = result
+        .calls
+        .iter()
+        .filter(|c| c.function_name == "Key")
+        .map(|c| {
+            (
+                c.arguments[2].int_values.clone(),
+                c.arguments[3].int_values.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        arguments,
+        vec![
+            (vec![100000], vec![32]),
+            (vec![100000], vec![64]),
+            (vec![15000], vec![32]),
+        ]
+    );
+}
+
 // =============================================================================
 // Inline tests for Go-specific resolution behaviors
 // =============================================================================
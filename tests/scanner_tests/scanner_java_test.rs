@@ -0,0 +1,163 @@
+//! Java-specific scanner e2e tests
+//!
+//! Tests JCA detection and parameter resolution for Java code.
+//! Fixtures: tests/fixtures/java/
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_java(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_java::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn jca_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("java", Some("jca-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(jca_classifier().get_mappings().clone())
+}
+
+fn scan_java_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("java", Some(project))
+        .join("src/main/java/com/example/crypto")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_java(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "java",
+    )
+}
+
+fn scan_java_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_java(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "Inline.java", "java")
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_java_basic_crypto_cipher() {
+    let result = scan_java_file("basic-crypto", "CipherUtil.java");
+
+    // Cipher.getInstance(transformation); cipher.init and doFinal are not mapped
+    assert_eq!(
+        result.call_count(),
+        2,
+        "Should find 2 Cipher.getInstance calls"
+    );
+    for call in &result.calls {
+        assert_eq!(call.function_name, "getInstance");
+        assert_eq!(call.import_path.as_deref(), Some("javax.crypto.Cipher"));
+    }
+
+    // The transformation resolves through the class constant
+    let transformations: Vec<_> = result
+        .calls
+        .iter()
+        .flat_map(|c| c.arguments[0].string_values.clone())
+        .collect();
+    assert_eq!(
+        transformations,
+        vec!["AES/GCM/NoPadding", "AES/ECB/PKCS5Padding"]
+    );
+}
+
+#[test]
+fn test_java_basic_crypto_wildcard_import() {
+    let result = scan_java_file("basic-crypto", "HashUtil.java");
+
+    // import java.security.*;
+    assert_eq!(result.call_count(), 2);
+    let algorithms: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| {
+            assert_eq!(
+                c.import_path.as_deref(),
+                Some("java.security.MessageDigest")
+            );
+            c.arguments[0].string_values[0].as_str()
+        })
+        .collect();
+    assert_eq!(algorithms, vec!["SHA-256", "MD5"]);
+}
+
+#[test]
+fn test_java_basic_crypto_kdf_classification() {
+    let result = scan_java_file("basic-crypto", "KeyDerivation.java");
+
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(
+        call.arguments[0].string_values,
+        vec!["PBKDF2WithHmacSHA256"]
+    );
+
+    let classification = classify_call(call, &jca_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+    assert_eq!(classification.operation, "keyderive");
+}
+
+#[test]
+fn test_java_basic_crypto_no_false_positives() {
+    let result = scan_java_file("basic-crypto", "Helpers.java");
+    assert_eq!(
+        result.call_count(),
+        0,
+        "Should find NO crypto calls in Helpers.java"
+    );
+}
+
+// =============================================================================
+// Inline tests for Java-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_java_inline_fully_qualified_call() {
+    let result = scan_java_inline(
+        r#"
+class Sign {
+    void sign() throws Exception {
+        java.security.Signature.getInstance("SHA256withECDSA");
+    }
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(call.package.as_deref(), Some("java.security.Signature"));
+    assert_eq!(call.arguments[0].string_values, vec!["SHA256withECDSA"]);
+}
+
+#[test]
+fn test_java_inline_unimported_class_is_ignored() {
+    let result = scan_java_inline(
+        r#"
+import com.example.Cipher;
+
+class Local {
+    void run() {
+        Cipher.getInstance("AES");
+    }
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
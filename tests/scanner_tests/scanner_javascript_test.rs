@@ -0,0 +1,123 @@
+//! JavaScript/TypeScript scanner e2e tests for React components
+//!
+//! Tests crypto calls inside JSX (.jsx, parsed with the JavaScript grammar)
+//! and TSX (.tsx, parsed with the TSX grammar) components: event handlers,
+//! hooks and expressions embedded in JSX attributes.
+
+use std::path::Path;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::symbol_index::{file_grammar, tree_sitter_language_for_path};
+use argflow::engine::Language;
+use argflow::scanner::{ScanResult, Scanner};
+
+const RULES: &str = r#"{
+    "classifications": {
+        "js_hash": {"findingType": "hash", "operation": "hash", "primitive": "hash"},
+        "js_pbkdf2": {"findingType": "kdf", "algorithm": "PBKDF2", "operation": "keyderive", "primitive": "kdf"}
+    },
+    "mappings": {
+        "crypto": {"createHash": "js_hash", "pbkdf2Sync": "js_pbkdf2"}
+    }
+}"#;
+
+const JSX_COMPONENT: &str = r#"import React, { useMemo } from "react";
+import crypto from "crypto";
+
+export function Fingerprint({ value }) {
+  const digest = useMemo(() => crypto.createHash("sha256").update(value).digest("hex"), [value]);
+  return (
+    <div title={crypto.createHash("md5").update(value).digest("hex")}>
+      <button onClick={() => crypto.pbkdf2Sync(value, "salt", 100000, 32, "sha512")}>
+        {digest}
+      </button>
+    </div>
+  );
+}
+"#;
+
+const TSX_COMPONENT: &str = r#"import React from "react";
+import * as crypto from "crypto";
+
+const ITERATIONS: number = 600000;
+
+interface Props {
+  password: string;
+}
+
+export const Derive: React.FC<Props> = ({ password }: Props) => {
+  const key = crypto.pbkdf2Sync(password, "salt", ITERATIONS, 32, "sha256");
+  return <span data-key={key.toString("hex")}>{crypto.createHash("sha1").update(password).digest("hex")}</span>;
+};
+"#;
+
+fn scan(file: &str, language: Language, source: &str) -> ScanResult {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&file_grammar(language, Path::new(file)).unwrap())
+        .unwrap();
+    let tree = parser.parse(source, None).unwrap();
+    assert!(!tree.root_node().has_error(), "{file} did not parse");
+
+    let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+    Scanner::with_mappings(classifier.get_mappings().clone()).scan_tree(
+        &tree,
+        source.as_bytes(),
+        file,
+        language.tree_sitter_name(),
+    )
+}
+
+fn functions(result: &ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+#[test]
+fn test_jsx_component_calls() {
+    let result = scan("Fingerprint.jsx", Language::JavaScript, JSX_COMPONENT);
+
+    assert_eq!(
+        functions(&result),
+        vec!["createHash", "createHash", "pbkdf2Sync"]
+    );
+    assert_eq!(
+        result.calls.iter().map(|c| c.line).collect::<Vec<_>>(),
+        vec![5, 7, 8]
+    );
+    assert_eq!(result.calls[1].arguments[0].string_values, vec!["md5"]);
+}
+
+#[test]
+fn test_tsx_component_calls() {
+    let result = scan("Derive.tsx", Language::TypeScript, TSX_COMPONENT);
+
+    assert_eq!(functions(&result), vec!["pbkdf2Sync", "createHash"]);
+    let pbkdf2 = &result.calls[0];
+    assert_eq!(pbkdf2.arguments[2].int_values, vec![600000]);
+    assert_eq!(result.calls[1].arguments[0].string_values, vec!["sha1"]);
+
+    let classifier = RulesClassifier::from_json_str(RULES).unwrap();
+    let classification = classify_call(pbkdf2, &classifier);
+    assert_eq!(classification.algorithm.as_deref(), Some("PBKDF2"));
+}
+
+#[test]
+fn test_tsx_needs_the_tsx_grammar() {
+    let path = Path::new("Derive.tsx");
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+        .unwrap();
+    let tree = parser.parse(TSX_COMPONENT, None).unwrap();
+    assert!(tree.root_node().has_error());
+
+    parser
+        .set_language(&tree_sitter_language_for_path(path).unwrap())
+        .unwrap();
+    let tree = parser.parse(TSX_COMPONENT, None).unwrap();
+    assert!(!tree.root_node().has_error());
+}
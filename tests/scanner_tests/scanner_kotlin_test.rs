@@ -0,0 +1,214 @@
+//! Kotlin-specific scanner e2e tests
+//!
+//! Tests JCA detection and parameter resolution for Kotlin code, which is
+//! matched against the Java mappings.
+//! Fixtures: tests/fixtures/kotlin/
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_kotlin(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_kotlin_ng::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn jca_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("java", Some("jca-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(jca_classifier().get_mappings().clone())
+}
+
+fn scan_kotlin_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("kotlin", Some(project))
+        .join("src/main/kotlin/com/example/crypto")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_kotlin(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "kotlin",
+    )
+}
+
+fn scan_kotlin_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_kotlin(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "Inline.kt", "kotlin")
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_kotlin_basic_crypto_cipher() {
+    let result = scan_kotlin_file("basic-crypto", "CipherUtil.kt");
+
+    // Cipher.getInstance(transformation); cipher.init and doFinal are not mapped
+    assert_eq!(
+        result.call_count(),
+        2,
+        "Should find 2 Cipher.getInstance calls"
+    );
+    for call in &result.calls {
+        assert_eq!(call.function_name, "getInstance");
+        assert_eq!(call.import_path.as_deref(), Some("javax.crypto.Cipher"));
+        assert_eq!(call.language, "kotlin");
+    }
+
+    // The transformation resolves through the top-level `const val`
+    let transformations: Vec<_> = result
+        .calls
+        .iter()
+        .flat_map(|c| c.arguments[0].string_values.clone())
+        .collect();
+    assert_eq!(
+        transformations,
+        vec!["AES/GCM/NoPadding", "AES/ECB/PKCS5Padding"]
+    );
+}
+
+#[test]
+fn test_kotlin_basic_crypto_wildcard_import() {
+    let result = scan_kotlin_file("basic-crypto", "HashUtil.kt");
+
+    // import java.security.*
+    assert_eq!(result.call_count(), 2);
+    let algorithms: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| {
+            assert_eq!(
+                c.import_path.as_deref(),
+                Some("java.security.MessageDigest")
+            );
+            c.arguments[0].string_values[0].as_str()
+        })
+        .collect();
+    assert_eq!(algorithms, vec!["SHA-256", "MD5"]);
+}
+
+#[test]
+fn test_kotlin_basic_crypto_kdf_classification() {
+    let result = scan_kotlin_file("basic-crypto", "KeyDerivation.kt");
+
+    // The algorithm is a constant of the `Params` object
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(
+        call.arguments[0].string_values,
+        vec!["PBKDF2WithHmacSHA256"]
+    );
+
+    let classification = classify_call(call, &jca_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+    assert_eq!(classification.operation, "keyderive");
+}
+
+#[test]
+fn test_kotlin_basic_crypto_no_false_positives() {
+    let result = scan_kotlin_file("basic-crypto", "Helpers.kt");
+    assert_eq!(
+        result.call_count(),
+        0,
+        "Should find NO crypto calls in Helpers.kt"
+    );
+}
+
+// =============================================================================
+// Inline tests for Kotlin-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_kotlin_inline_aliased_import() {
+    let result = scan_kotlin_inline(
+        r#"
+import javax.crypto.Mac as HMac
+
+fun tag(): HMac = HMac.getInstance("HmacSHA256")
+"#,
+    );
+
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(call.import_path.as_deref(), Some("javax.crypto.Mac"));
+    assert_eq!(call.arguments[0].string_values, vec!["HmacSHA256"]);
+}
+
+#[test]
+fn test_kotlin_inline_local_and_companion_values() {
+    let result = scan_kotlin_inline(
+        r#"
+import javax.crypto.KeyGenerator
+import java.security.Signature
+
+class Signer {
+    companion object {
+        private const val SCHEME = "SHA256withECDSA"
+    }
+
+    fun sign() {
+        val algorithm = "AES"
+        KeyGenerator.getInstance(algorithm)
+        Signature.getInstance(Signer.SCHEME)
+    }
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 2);
+    assert_eq!(result.calls[0].arguments[0].string_values, vec!["AES"]);
+    assert_eq!(
+        result.calls[1].arguments[0].string_values,
+        vec!["SHA256withECDSA"]
+    );
+}
+
+#[test]
+fn test_kotlin_inline_string_template_is_partial() {
+    let result = scan_kotlin_inline(
+        r#"
+import javax.crypto.Cipher
+
+fun cipher(mode: String) {
+    Cipher.getInstance("AES/$mode/NoPadding")
+    Cipher.getInstance("AES/${mode.uppercase()}/NoPadding")
+    Cipher.getInstance("AES/GCM/NoPadding \$1")
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 3);
+    assert!(!result.calls[0].arguments[0].is_resolved);
+    assert!(!result.calls[1].arguments[0].is_resolved);
+    assert_eq!(
+        result.calls[2].arguments[0].string_values,
+        vec!["AES/GCM/NoPadding $1"]
+    );
+}
+
+#[test]
+fn test_kotlin_inline_unimported_class_is_ignored() {
+    let result = scan_kotlin_inline(
+        r#"
+import com.example.Cipher
+
+fun run() {
+    Cipher.getInstance("AES")
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
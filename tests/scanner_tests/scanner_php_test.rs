@@ -0,0 +1,209 @@
+//! PHP-specific scanner e2e tests
+//!
+//! Tests detection of built-in OpenSSL, hash, password and sodium functions
+//! called by name, classes imported with `use`, named arguments, and
+//! parameter resolution through `const`, `define()` and class constants.
+//! Fixtures: tests/fixtures/php/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_php(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_php::LANGUAGE_PHP.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn php_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("php", Some("php-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(php_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_php_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("php", Some(project))
+        .join("src")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_php(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "php",
+    )
+}
+
+fn scan_php_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_php(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "inline.php", "php")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_php_basic_crypto_builtin_functions() {
+    let result = scan_php_file("basic-crypto", "Ciphers.php");
+
+    // random_bytes is not mapped
+    assert_eq!(
+        functions(&result),
+        vec![
+            "openssl_encrypt",
+            "openssl_encrypt",
+            "sodium_crypto_secretbox",
+            "hash",
+            "createKey"
+        ]
+    );
+    let import_paths: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| c.import_path.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        import_paths,
+        vec!["php", "php", "php", "php", "phpseclib3\\Crypt\\RSA"]
+    );
+
+    let classification = classify_call(&result.calls[0], &php_classifier());
+    assert_eq!(classification.finding_type, "symmetric");
+}
+
+#[test]
+fn test_php_basic_crypto_cipher_strings() {
+    let result = scan_php_file("basic-crypto", "Ciphers.php");
+
+    // self::CIPHER is a constant of the enclosing class
+    assert_eq!(
+        result.calls[0].arguments[1].string_values,
+        vec!["aes-256-gcm"]
+    );
+    // LEGACY_CIPHER is a namespace constant
+    assert_eq!(
+        result.calls[1].arguments[1].string_values,
+        vec!["des-ede3-cbc"]
+    );
+    assert_eq!(result.calls[3].arguments[0].string_values, vec!["sha256"]);
+}
+
+#[test]
+fn test_php_basic_crypto_class_constants() {
+    let result = scan_php_file("basic-crypto", "Ciphers.php");
+
+    // Params::KEY_BITS is declared in Params.php next to it
+    assert_eq!(result.calls[4].arguments[0].int_values, vec![2048]);
+}
+
+#[test]
+fn test_php_basic_crypto_iteration_counts() {
+    let result = scan_php_file("basic-crypto", "KeyDerivation.php");
+
+    assert_eq!(
+        functions(&result),
+        vec!["hash_pbkdf2", "password_hash", "password_hash"]
+    );
+    let kdf = &result.calls[0];
+    assert_eq!(kdf.arguments[0].string_values, vec!["sha256"]);
+    assert_eq!(kdf.arguments[3].int_values, vec![600000]);
+    // KEY_LENGTH is defined with define()
+    assert_eq!(kdf.arguments[4].int_values, vec![32]);
+
+    let classification = classify_call(kdf, &php_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+}
+
+#[test]
+fn test_php_basic_crypto_named_arguments() {
+    let result = scan_php_file("basic-crypto", "KeyDerivation.php");
+
+    let argon = &result.calls[2];
+    assert_eq!(argon.keyword(0), Some("password"));
+    assert_eq!(argon.keyword(1), Some("algo"));
+    assert!(!argon.arguments[0].is_resolved);
+}
+
+#[test]
+fn test_php_basic_crypto_no_false_positives() {
+    let result = scan_php_file("basic-crypto", "Helpers.php");
+    assert_eq!(result.call_count(), 0);
+}
+
+// =============================================================================
+// Inline tests for PHP-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_php_inline_top_level_script() {
+    let result = scan_php_inline(
+        r#"<?php
+$cipher = "aes-128-cbc";
+$rounds = 10000;
+$key = hash_pbkdf2("sha1", $argv[1], "salt", $rounds * 2);
+echo openssl_encrypt($data, $cipher, $key);
+"#,
+    );
+
+    assert_eq!(functions(&result), vec!["hash_pbkdf2", "openssl_encrypt"]);
+    assert_eq!(result.calls[0].arguments[3].int_values, vec![20000]);
+    assert_eq!(
+        result.calls[1].arguments[1].string_values,
+        vec!["aes-128-cbc"]
+    );
+}
+
+#[test]
+fn test_php_inline_interpolated_and_parameter_values() {
+    let result = scan_php_inline(
+        r#"<?php
+function encrypt($data, $mode, $key) {
+    $cipher = "aes-256-$mode";
+    return openssl_encrypt($data, $cipher, $key);
+}
+"#,
+    );
+
+    let call = &result.calls[0];
+    assert!(!call.arguments[0].is_resolved);
+    assert!(!call.arguments[1].is_resolved);
+}
+
+#[test]
+fn test_php_inline_options_array() {
+    let result = scan_php_inline(
+        r#"<?php
+$hash = password_hash($password, PASSWORD_BCRYPT, ['cost' => 12]);
+$box = sodium_crypto_secretbox($message, $nonce, $key);
+"#,
+    );
+
+    assert_eq!(
+        functions(&result),
+        vec!["password_hash", "sodium_crypto_secretbox"]
+    );
+    let options = &result.calls[0].arguments[2];
+    assert!(options.is_resolved);
+}
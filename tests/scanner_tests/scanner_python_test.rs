@@ -3,6 +3,9 @@
 //! Tests crypto detection and parameter resolution for Python code.
 //! Fixtures: tests/fixtures/python/
 
+use std::sync::Arc;
+
+use argflow::engine::ProjectIndex;
 use argflow::scanner::Scanner;
 
 use crate::fixtures::{get_test_fixture_path, test_patterns};
@@ -195,3 +198,48 @@ def crypto_ops():
     assert!(names.contains(&"sha256"));
     assert!(names.contains(&"pbkdf2_hmac"));
 }
+
+#[test]
+fn test_python_constants_from_project_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("app")).unwrap();
+    std::fs::write(
+        root.join("app/settings.py"),
+        "ITERATIONS = 310_000\nKEY_LENGTH = 32\n",
+    )
+    .unwrap();
+    let source = r#"
+import hashlib
+from app import settings
+from app.settings import ITERATIONS
+
+def derive(password, salt):
+    a = hashlib.pbkdf2_hmac('sha256', password, salt, ITERATIONS, 32)
+    b = hashlib.pbkdf2_hmac('sha256', password, salt, settings.ITERATIONS, settings.KEY_LENGTH)
+"#;
+    let path = root.join("app/kdf.py");
+    std::fs::write(&path, source).unwrap();
+
+    let index = Arc::new(ProjectIndex::new());
+    index.index_files(root, &[root.join("app/settings.py"), path.clone()]);
+    let tree = parse_python(source);
+    let scanner = create_scanner().with_project_index(index);
+    let result = scanner.scan_tree(&tree, source.as_bytes(), &path.to_string_lossy(), "python");
+
+    let arguments: Vec<_> = result
+        .calls
+        .iter()
+        .filter(|c| c.function_name == "pbkdf2_hmac")
+        .map(|c| {
+            (
+                c.arguments[3].int_values.clone(),
+                c.arguments[4].int_values.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        arguments,
+        vec![(vec![310000], vec![32]), (vec![310000], vec![32])]
+    );
+}
@@ -0,0 +1,188 @@
+//! Ruby-specific scanner e2e tests
+//!
+//! Tests OpenSSL and Digest detection through constant receivers, symbol
+//! and string arguments, keyword arguments, and parameter resolution
+//! through module constants.
+//! Fixtures: tests/fixtures/ruby/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_ruby(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_ruby::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn ruby_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("ruby", Some("ruby-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(ruby_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_ruby_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("ruby", Some(project))
+        .join("lib")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_ruby(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "ruby",
+    )
+}
+
+fn scan_ruby_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_ruby(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "inline.rb", "ruby")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_ruby_basic_crypto_constant_receivers() {
+    let result = scan_ruby_file("basic-crypto", "ciphers.rb");
+
+    // cipher.encrypt and cipher.update are called on a local, not mapped
+    assert_eq!(
+        functions(&result),
+        vec!["new", "new", "hexdigest", "new", "generate_key"]
+    );
+    let import_paths: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| c.import_path.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        import_paths,
+        vec![
+            "OpenSSL::Cipher",
+            "OpenSSL::Cipher",
+            "Digest::SHA256",
+            "OpenSSL::PKey::RSA",
+            "OpenSSL::PKey"
+        ]
+    );
+
+    let classification = classify_call(&result.calls[0], &ruby_classifier());
+    assert_eq!(classification.finding_type, "symmetric");
+}
+
+#[test]
+fn test_ruby_basic_crypto_string_arguments() {
+    let result = scan_ruby_file("basic-crypto", "ciphers.rb");
+
+    // CIPHER is a constant of the enclosing class
+    assert_eq!(
+        result.calls[0].arguments[0].string_values,
+        vec!["aes-256-gcm"]
+    );
+    assert_eq!(
+        result.calls[1].arguments[0].string_values,
+        vec!["des-ede3-cbc"]
+    );
+}
+
+#[test]
+fn test_ruby_basic_crypto_module_constants() {
+    let result = scan_ruby_file("basic-crypto", "ciphers.rb");
+
+    // Params::KEY_SIZE is declared in params.rb next to it
+    assert_eq!(result.calls[3].arguments[0].int_values, vec![2048]);
+}
+
+#[test]
+fn test_ruby_basic_crypto_keyword_arguments() {
+    let result = scan_ruby_file("basic-crypto", "key_derivation.rb");
+
+    assert_eq!(functions(&result), vec!["pbkdf2_hmac"]);
+    let kdf = &result.calls[0];
+    assert_eq!(kdf.keyword(2), Some("iterations"));
+    assert_eq!(kdf.arguments[2].int_values, vec![600000]);
+    assert_eq!(kdf.keyword(3), Some("length"));
+    assert_eq!(kdf.arguments[3].int_values, vec![32]);
+    assert_eq!(kdf.keyword(4), Some("hash"));
+    assert_eq!(kdf.arguments[4].string_values, vec!["sha256"]);
+
+    let classification = classify_call(kdf, &ruby_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+}
+
+#[test]
+fn test_ruby_basic_crypto_no_false_positives() {
+    let result = scan_ruby_file("basic-crypto", "helpers.rb");
+    assert_eq!(result.call_count(), 0);
+}
+
+// =============================================================================
+// Inline tests for Ruby-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_ruby_inline_symbol_arguments() {
+    let result = scan_ruby_inline(
+        r#"
+require "openssl"
+
+def ec_key
+  curve = :prime256v1
+  OpenSSL::PKey.generate_key(:"EC", ec_paramgen_curve: curve)
+end
+"#,
+    );
+
+    assert_eq!(functions(&result), vec!["generate_key"]);
+    let call = &result.calls[0];
+    assert_eq!(call.arguments[0].string_values, vec!["EC"]);
+    assert_eq!(call.keyword(1), Some("ec_paramgen_curve"));
+    assert_eq!(call.arguments[1].string_values, vec!["prime256v1"]);
+}
+
+#[test]
+fn test_ruby_inline_top_level_script() {
+    let result = scan_ruby_inline(
+        r#"
+require "openssl"
+
+mode = "aes-128-cbc"
+cipher = ::OpenSSL::Cipher.new(mode)
+key = OpenSSL::PKey::RSA.generate(bits)
+"#,
+    );
+
+    assert_eq!(functions(&result), vec!["new", "generate"]);
+    assert_eq!(
+        result.calls[0].import_path.as_deref(),
+        Some("OpenSSL::Cipher")
+    );
+    assert_eq!(
+        result.calls[0].arguments[0].string_values,
+        vec!["aes-128-cbc"]
+    );
+    assert!(!result.calls[1].arguments[0].is_resolved);
+}
@@ -0,0 +1,260 @@
+//! Scala-specific scanner e2e tests
+//!
+//! Tests JCA detection and parameter resolution for Scala code, which is
+//! matched against the Java mappings.
+//! Fixtures: tests/fixtures/scala/
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_scala(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_scala::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn jca_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("java", Some("jca-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(jca_classifier().get_mappings().clone())
+}
+
+fn scan_scala_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("scala", Some(project))
+        .join("src/main/scala/com/example/crypto")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_scala(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "scala",
+    )
+}
+
+fn scan_scala_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_scala(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "Inline.scala", "scala")
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_scala_basic_crypto_cipher() {
+    let result = scan_scala_file("basic-crypto", "CipherUtil.scala");
+
+    // Cipher.getInstance(transformation) and `Cipher getInstance "..."`;
+    // cipher.init and doFinal are not mapped
+    assert_eq!(
+        result.call_count(),
+        2,
+        "Should find 2 Cipher.getInstance calls"
+    );
+    for call in &result.calls {
+        assert_eq!(call.function_name, "getInstance");
+        assert_eq!(call.import_path.as_deref(), Some("javax.crypto.Cipher"));
+        assert_eq!(call.language, "scala");
+    }
+
+    // The first transformation resolves through the top-level `val`
+    let transformations: Vec<_> = result
+        .calls
+        .iter()
+        .flat_map(|c| c.arguments[0].string_values.clone())
+        .collect();
+    assert_eq!(
+        transformations,
+        vec!["AES/GCM/NoPadding", "AES/ECB/PKCS5Padding"]
+    );
+}
+
+#[test]
+fn test_scala_basic_crypto_wildcard_import() {
+    let result = scan_scala_file("basic-crypto", "HashUtil.scala");
+
+    // import java.security._
+    assert_eq!(result.call_count(), 2);
+    let algorithms: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| {
+            assert_eq!(
+                c.import_path.as_deref(),
+                Some("java.security.MessageDigest")
+            );
+            c.arguments[0].string_values[0].as_str()
+        })
+        .collect();
+    assert_eq!(algorithms, vec!["SHA-256", "MD5"]);
+}
+
+#[test]
+fn test_scala_basic_crypto_selector_import() {
+    let result = scan_scala_file("basic-crypto", "MacUtil.scala");
+
+    // import javax.crypto.{KeyGenerator, Mac => HMac}, with the renamed Mac
+    // called infix
+    assert_eq!(result.call_count(), 2);
+    let calls: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| {
+            (
+                c.import_path.as_deref().unwrap(),
+                c.arguments[0].string_values[0].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("javax.crypto.Mac", "HmacSHA256"),
+            ("javax.crypto.KeyGenerator", "HmacSHA256"),
+        ]
+    );
+}
+
+#[test]
+fn test_scala_basic_crypto_kdf_classification() {
+    let result = scan_scala_file("basic-crypto", "KeyDerivation.scala");
+
+    // The algorithm is a `val` of the `Params` object
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(
+        call.arguments[0].string_values,
+        vec!["PBKDF2WithHmacSHA256"]
+    );
+
+    let classification = classify_call(call, &jca_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+    assert_eq!(classification.operation, "keyderive");
+}
+
+#[test]
+fn test_scala_basic_crypto_no_false_positives() {
+    let result = scan_scala_file("basic-crypto", "Helpers.scala");
+    assert_eq!(
+        result.call_count(),
+        0,
+        "Should find NO crypto calls in Helpers.scala"
+    );
+}
+
+// =============================================================================
+// Inline tests for Scala-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_scala_inline_infix_call_with_several_arguments() {
+    let result = scan_scala_inline(
+        r#"
+import java.security.Signature
+
+object Signer {
+  def signer = Signature getInstance ("SHA256withECDSA", "BC")
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 1);
+    let call = &result.calls[0];
+    assert_eq!(call.import_path.as_deref(), Some("java.security.Signature"));
+    assert_eq!(call.arguments.len(), 2);
+    assert_eq!(call.arguments[0].string_values, vec!["SHA256withECDSA"]);
+    assert_eq!(call.arguments[1].string_values, vec!["BC"]);
+}
+
+#[test]
+fn test_scala_inline_constructors() {
+    // Mappings are keyed in lowercase
+    let mut mappings = jca_classifier().get_mappings().clone();
+    mappings.insert(
+        "javax.crypto.spec".to_string(),
+        [("pbekeyspec".to_string(), "jca_kdf_spec".to_string())].into(),
+    );
+    let source = r#"
+import javax.crypto.spec.PBEKeySpec
+
+object Specs {
+  val Iterations = 600_000
+
+  def spec(password: Array[Char], salt: Array[Byte]) = {
+    new PBEKeySpec(password, salt, Iterations, 256)
+    PBEKeySpec(password, salt, 310_000, 256)
+  }
+}
+"#;
+    let tree = parse_scala(source);
+    let result = Scanner::with_mappings(mappings).scan_tree(
+        &tree,
+        source.as_bytes(),
+        "Inline.scala",
+        "scala",
+    );
+
+    // `new PBEKeySpec(...)` and its universal `apply`, `PBEKeySpec(...)`
+    assert_eq!(result.call_count(), 2);
+    for call in &result.calls {
+        assert_eq!(call.import_path.as_deref(), Some("javax.crypto.spec"));
+    }
+    assert_eq!(result.calls[0].arguments[2].int_values, vec![600_000]);
+    assert_eq!(result.calls[1].arguments[2].int_values, vec![310_000]);
+}
+
+#[test]
+fn test_scala_inline_string_interpolation_is_partial() {
+    let result = scan_scala_inline(
+        r#"
+import javax.crypto.Cipher
+
+object Ciphers {
+  def cipher(mode: String) = {
+    Cipher.getInstance(s"AES/$mode/NoPadding")
+    Cipher.getInstance(s"AES/${mode.toUpperCase}/NoPadding")
+    Cipher.getInstance(s"AES/GCM/NoPadding")
+    Cipher.getInstance("""AES/CBC/PKCS5Padding""")
+  }
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 4);
+    assert!(!result.calls[0].arguments[0].is_resolved);
+    assert!(!result.calls[1].arguments[0].is_resolved);
+    assert_eq!(
+        result.calls[2].arguments[0].string_values,
+        vec!["AES/GCM/NoPadding"]
+    );
+    assert_eq!(
+        result.calls[3].arguments[0].string_values,
+        vec!["AES/CBC/PKCS5Padding"]
+    );
+}
+
+#[test]
+fn test_scala_inline_unimported_class_is_ignored() {
+    let result = scan_scala_inline(
+        r#"
+import com.example.Cipher
+
+object Run {
+  def run() = Cipher.getInstance("AES")
+}
+"#,
+    );
+
+    assert_eq!(result.call_count(), 0);
+}
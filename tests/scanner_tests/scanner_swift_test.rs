@@ -0,0 +1,207 @@
+//! Swift-specific scanner e2e tests
+//!
+//! Tests detection of CryptoKit types and CommonCrypto functions brought in
+//! by `import`, labelled arguments, and parameter resolution through
+//! top-level and static `let` constants.
+//! Fixtures: tests/fixtures/swift/
+
+use std::sync::Arc;
+
+use argflow::classifier::{classify_call, RulesClassifier};
+use argflow::engine::SymbolIndex;
+use argflow::scanner::Scanner;
+
+use crate::fixtures::get_test_fixture_path;
+
+fn parse_swift(source: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_swift::LANGUAGE.into())
+        .unwrap();
+    parser.parse(source, None).unwrap()
+}
+
+fn swift_classifier() -> RulesClassifier {
+    RulesClassifier::from_file(&get_test_fixture_path("swift", Some("swift-rules.json"))).unwrap()
+}
+
+fn create_scanner() -> Scanner {
+    Scanner::with_mappings(swift_classifier().get_mappings().clone())
+        .with_symbol_index(Arc::new(SymbolIndex::new()))
+}
+
+fn scan_swift_file(project: &str, file_path: &str) -> argflow::scanner::ScanResult {
+    let full_path = get_test_fixture_path("swift", Some(project))
+        .join("Sources")
+        .join("Vault")
+        .join(file_path);
+    let source = std::fs::read_to_string(&full_path)
+        .unwrap_or_else(|_| panic!("Failed to read: {project}/{file_path}"));
+    let tree = parse_swift(&source);
+    let scanner = create_scanner();
+    scanner.scan_tree(
+        &tree,
+        source.as_bytes(),
+        &full_path.to_string_lossy(),
+        "swift",
+    )
+}
+
+fn scan_swift_inline(source: &str) -> argflow::scanner::ScanResult {
+    let tree = parse_swift(source);
+    let scanner = create_scanner();
+    scanner.scan_tree(&tree, source.as_bytes(), "inline.swift", "swift")
+}
+
+fn functions(result: &argflow::scanner::ScanResult) -> Vec<&str> {
+    result
+        .calls
+        .iter()
+        .map(|c| c.function_name.as_str())
+        .collect()
+}
+
+// =============================================================================
+// basic-crypto project tests
+// =============================================================================
+
+#[test]
+fn test_swift_basic_crypto_cryptokit_types() {
+    let result = scan_swift_file("basic-crypto", "Sealing.swift");
+
+    // SealedBox and SymmetricKeySize are not mapped
+    assert_eq!(
+        functions(&result),
+        vec![
+            "SymmetricKey",
+            "hash",
+            "seal",
+            "open",
+            "PrivateKey",
+            "hash",
+            "SymmetricKey"
+        ]
+    );
+    let import_paths: Vec<_> = result
+        .calls
+        .iter()
+        .map(|c| c.import_path.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        import_paths,
+        vec![
+            "CryptoKit",
+            "CryptoKit.SHA256",
+            "CryptoKit.AES.GCM",
+            "CryptoKit.AES.GCM",
+            "CryptoKit.P256.Signing",
+            "CryptoKit.Insecure.MD5",
+            "CryptoKit"
+        ]
+    );
+
+    let classification = classify_call(&result.calls[1], &swift_classifier());
+    assert_eq!(classification.finding_type, "hash");
+    let classification = classify_call(&result.calls[2], &swift_classifier());
+    assert_eq!(classification.operation, "encrypt");
+}
+
+#[test]
+fn test_swift_basic_crypto_labelled_arguments() {
+    let result = scan_swift_file("basic-crypto", "Sealing.swift");
+
+    let key = &result.calls[0];
+    assert_eq!(key.keyword(0), Some("size"));
+    // `.bits256` names a case of the expected type
+    assert_eq!(key.arguments[0].expression, ".bits256");
+
+    // `try AES.GCM.seal(plaintext, using: key, nonce: nil)`
+    let seal = &result.calls[2];
+    assert_eq!(seal.keyword(0), None);
+    assert_eq!(seal.keyword(1), Some("using"));
+    assert_eq!(seal.keyword(2), Some("nonce"));
+    assert_eq!(seal.arguments[2].string_values, vec!["nil"]);
+}
+
+#[test]
+fn test_swift_basic_crypto_common_crypto() {
+    let result = scan_swift_file("basic-crypto", "Legacy.swift");
+
+    assert_eq!(functions(&result), vec!["CCCrypt", "CCKeyDerivationPBKDF"]);
+    assert!(result
+        .calls
+        .iter()
+        .all(|c| c.import_path.as_deref() == Some("CommonCrypto")));
+
+    // pbkdfRounds is a top-level constant
+    let kdf = &result.calls[1];
+    assert_eq!(kdf.arguments[6].int_values, vec![100000]);
+
+    let classification = classify_call(kdf, &swift_classifier());
+    assert_eq!(classification.finding_type, "kdf");
+}
+
+// =============================================================================
+// Inline tests for Swift-specific resolution behaviors
+// =============================================================================
+
+#[test]
+fn test_swift_inline_static_members() {
+    let result = scan_swift_inline(
+        r#"import CommonCrypto
+
+enum KDF {
+    static let rounds: UInt32 = 210_000
+    static let length = (256 / 8)
+}
+
+func derive(password: String, salt: [UInt8]) -> [UInt8] {
+    var out = [UInt8](repeating: 0, count: KDF.length)
+    CCKeyDerivationPBKDF(CCPBKDFAlgorithm(kCCPBKDF2), password, password.utf8.count, salt, salt.count, CCPseudoRandomAlgorithm(kCCPRFHmacAlgSHA256), KDF.rounds, &out, KDF.length)
+    return out
+}
+"#,
+    );
+
+    let kdf = &result.calls[0];
+    assert_eq!(kdf.arguments[6].int_values, vec![210000]);
+    assert_eq!(kdf.arguments[8].int_values, vec![32]);
+    // `password` is a parameter of the enclosing function
+    assert!(!kdf.arguments[1].is_resolved);
+}
+
+#[test]
+fn test_swift_inline_string_literals() {
+    let result = scan_swift_inline(
+        r##"import CommonCrypto
+
+let digest = "sha\(bits)"
+let raw = #"aes\256"#
+CC_SHA256(digest, 1, nil)
+CC_MD5(raw, 1, nil)
+"##,
+    );
+
+    assert_eq!(functions(&result), vec!["CC_SHA256", "CC_MD5"]);
+    assert!(!result.calls[0].arguments[0].is_resolved);
+    assert_eq!(result.calls[1].arguments[0].string_values, vec!["aes\\256"]);
+}
+
+#[test]
+fn test_swift_inline_unimported_module() {
+    // Without `import CryptoKit`, SHA256 is some other type
+    let result = scan_swift_inline("let d = SHA256.hash(data: data)\n");
+    assert_eq!(result.call_count(), 0);
+}
+
+#[test]
+fn test_swift_inline_subscripts_are_not_calls() {
+    let result = scan_swift_inline(
+        r#"import CommonCrypto
+
+let CCCrypt = [1, 2]
+let first = CCCrypt[0]
+"#,
+    );
+    assert_eq!(result.call_count(), 0);
+}